@@ -0,0 +1,843 @@
+//! Multi-machine agent pool.
+//!
+//! With `pool.listen` set, the loop acts as a coordinator: other machines join
+//! with `mobius worker --join <addr>` and each iteration hands the ready tasks
+//! beyond the loop's own `max_parallel_agents` to workers with free slots.
+//! Messages are newline-delimited JSON over TCP.
+//!
+//! Workers run their share in their own worktree of the loop branch, so tasks
+//! stay worktree-isolated per machine. Commits travel through the `pool.remote`
+//! git remote: the loop pushes the branch before dispatching, workers rebase
+//! onto it and push their commits back, and the loop rebases onto those before
+//! the next iteration. Results come back to the loop and feed the same
+//! verification, retry and runtime state as local agents.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::process::Command;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time::{sleep, timeout, Instant};
+use tracing::{debug, warn};
+
+use crate::completion::result_file_for;
+use crate::context::{get_context_path, get_results_directory_path};
+use crate::error::MobiusError;
+use crate::executor::{ExecutionResult, ExecutionStatus};
+use crate::secrets::tokens_match;
+use crate::shutdown;
+use crate::types::{AgentRuntime, ExecutionConfig, SubTask};
+
+/// Bumped on incompatible protocol changes; mismatched workers are rejected
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// How often workers report in, including while agents run
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Workers silent for this long are dropped and their tasks failed
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Time a new connection has to register
+const REGISTER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long shutdown waits for workers to disconnect
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(3);
+
+/// Messages sent by workers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WorkerMessage {
+    Register {
+        name: String,
+        capacity: usize,
+        version: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token: Option<String>,
+    },
+    Heartbeat,
+    Results {
+        batch_id: u64,
+        results: Vec<TaskOutcome>,
+    },
+}
+
+/// Messages sent by the coordinator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CoordinatorMessage {
+    Registered { worker_id: u64 },
+    Rejected { reason: String },
+    Run(Box<Batch>),
+    Shutdown,
+}
+
+/// Tasks handed to one worker for one iteration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Batch {
+    pub batch_id: u64,
+    /// Parent issue identifier
+    pub issue_id: String,
+    /// Loop branch the worker checks out and pushes to
+    pub branch: String,
+    pub runtime: AgentRuntime,
+    pub model_override: Option<String>,
+    pub thinking_level_override: Option<String>,
+    pub execution: ExecutionConfig,
+    pub tasks: Vec<SubTask>,
+    /// `.mobius/issues/<id>/` contents by relative path, without execution state
+    pub issue_files: BTreeMap<String, String>,
+}
+
+/// Outcome of one remote task, as reported by the worker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOutcome {
+    pub task_id: String,
+    pub identifier: String,
+    pub success: bool,
+    pub status: ExecutionStatus,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u64>,
+    /// Raw `$MOBIUS_RESULT_FILE` contents written by the agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_file: Option<String>,
+}
+
+impl TaskOutcome {
+    pub fn from_result(result: &ExecutionResult, result_file: Option<String>) -> Self {
+        Self {
+            task_id: result.task_id.clone(),
+            identifier: result.identifier.clone(),
+            success: result.success,
+            status: result.status.clone(),
+            duration_ms: result.duration_ms,
+            error: result.error.clone(),
+            input_tokens: result.input_tokens,
+            output_tokens: result.output_tokens,
+            result_file,
+        }
+    }
+
+    fn into_result(self) -> ExecutionResult {
+        ExecutionResult {
+            task_id: self.task_id,
+            identifier: self.identifier,
+            success: self.success,
+            status: self.status,
+            token_usage: None,
+            duration_ms: self.duration_ms,
+            error: self.error,
+            pane_id: None,
+            raw_output: None,
+            input_tokens: self.input_tokens,
+            output_tokens: self.output_tokens,
+        }
+    }
+}
+
+/// Failed result for a task that never reported back
+pub fn failed_result(task: &SubTask, error: String) -> ExecutionResult {
+    ExecutionResult {
+        task_id: task.id.clone(),
+        identifier: task.identifier.clone(),
+        success: false,
+        status: ExecutionStatus::Error,
+        token_usage: None,
+        duration_ms: 0,
+        error: Some(error),
+        pane_id: None,
+        raw_output: None,
+        input_tokens: None,
+        output_tokens: None,
+    }
+}
+
+/// Write one message as a JSON line.
+pub async fn send_message<W, T>(writer: &mut W, message: &T) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let mut line = serde_json::to_string(message)?;
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Read one JSON-line message; `None` once the peer closed the connection.
+pub async fn recv_message<R, T>(reader: &mut R) -> Result<Option<T>>
+where
+    R: AsyncBufRead + Unpin,
+    T: DeserializeOwned,
+{
+    let mut line = String::new();
+    if reader.read_line(&mut line).await? == 0 {
+        return Ok(None);
+    }
+    let message = serde_json::from_str(line.trim_end()).context("Malformed pool message")?;
+    Ok(Some(message))
+}
+
+/// A connected worker with no batch in flight
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkerSlot {
+    pub id: u64,
+    pub name: String,
+    pub capacity: usize,
+}
+
+/// Split tasks across workers, filling the largest first.
+///
+/// Tasks beyond the workers' combined capacity are left out.
+pub fn distribute(tasks: Vec<SubTask>, workers: &[WorkerSlot]) -> Vec<(WorkerSlot, Vec<SubTask>)> {
+    let mut workers = workers.to_vec();
+    workers.sort_by(|a, b| b.capacity.cmp(&a.capacity).then(a.id.cmp(&b.id)));
+
+    let mut tasks = tasks.into_iter();
+    let mut assignments = Vec::new();
+    for worker in workers {
+        let batch: Vec<SubTask> = tasks.by_ref().take(worker.capacity).collect();
+        if batch.is_empty() {
+            break;
+        }
+        assignments.push((worker, batch));
+    }
+    assignments
+}
+
+/// Everything about a batch except its tasks
+pub struct BatchSpec<'a> {
+    pub issue_id: &'a str,
+    pub branch: &'a str,
+    pub runtime: AgentRuntime,
+    pub model_override: Option<&'a str>,
+    pub thinking_level_override: Option<&'a str>,
+    pub execution: &'a ExecutionConfig,
+}
+
+struct ConnectedWorker {
+    name: String,
+    capacity: usize,
+    outbox: mpsc::UnboundedSender<CoordinatorMessage>,
+    pending: Option<(u64, oneshot::Sender<Vec<TaskOutcome>>)>,
+}
+
+#[derive(Default)]
+struct PoolState {
+    workers: BTreeMap<u64, ConnectedWorker>,
+    next_worker_id: u64,
+    next_batch_id: u64,
+}
+
+fn lock(state: &Mutex<PoolState>) -> MutexGuard<'_, PoolState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Accepts workers and runs batches on them
+pub struct Coordinator {
+    state: Arc<Mutex<PoolState>>,
+    local_addr: SocketAddr,
+}
+
+impl Coordinator {
+    /// Listen on `addr` and accept workers in the background.
+    ///
+    /// Workers run agents on the loop branch, so without a token only loopback
+    /// addresses are accepted.
+    pub async fn start(addr: &str, token: Option<String>) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to listen for pool workers on {}", addr))?;
+        let local_addr = listener.local_addr()?;
+        if token.is_none() && !local_addr.ip().is_loopback() {
+            anyhow::bail!(
+                "Refusing to accept pool workers on {} without a token; set pool.token or MOBIUS_POOL_TOKEN",
+                local_addr
+            );
+        }
+        let state = Arc::new(Mutex::new(PoolState::default()));
+
+        let shared = Arc::clone(&state);
+        tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, peer)) => {
+                        tokio::spawn(handle_worker(
+                            stream,
+                            peer,
+                            Arc::clone(&shared),
+                            token.clone(),
+                        ));
+                    }
+                    Err(e) => warn!("Failed to accept pool worker: {}", e),
+                }
+            }
+        });
+
+        Ok(Self { state, local_addr })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Connected workers without a batch in flight.
+    pub fn idle_workers(&self) -> Vec<WorkerSlot> {
+        lock(&self.state)
+            .workers
+            .iter()
+            .filter(|(_, w)| w.pending.is_none())
+            .map(|(&id, w)| WorkerSlot {
+                id,
+                name: w.name.clone(),
+                capacity: w.capacity,
+            })
+            .collect()
+    }
+
+    /// Send a batch to a worker; the receiver closes if the worker drops.
+    fn dispatch(&self, worker_id: u64, mut batch: Batch) -> oneshot::Receiver<Vec<TaskOutcome>> {
+        let (reply, receiver) = oneshot::channel();
+        let mut state = lock(&self.state);
+        state.next_batch_id += 1;
+        batch.batch_id = state.next_batch_id;
+        if let Some(worker) = state.workers.get_mut(&worker_id) {
+            let batch_id = batch.batch_id;
+            if worker
+                .outbox
+                .send(CoordinatorMessage::Run(Box::new(batch)))
+                .is_ok()
+            {
+                worker.pending = Some((batch_id, reply));
+            }
+        }
+        receiver
+    }
+
+    /// Run each assignment on its worker and wait for every result.
+    ///
+    /// Tasks on workers that disconnect, and tasks still out when the loop is
+    /// interrupted, come back as failed results. Agent result files are
+    /// written to the local results directory, named after the dispatched
+    /// task, so verification sees them.
+    pub async fn run(
+        &self,
+        assignments: Vec<(WorkerSlot, Vec<SubTask>)>,
+        spec: &BatchSpec<'_>,
+    ) -> Vec<ExecutionResult> {
+        if assignments.is_empty() {
+            return Vec::new();
+        }
+        let issue_files = snapshot_issue_files(&get_context_path(spec.issue_id));
+
+        let pending: Vec<_> = assignments
+            .into_iter()
+            .map(|(worker, tasks)| {
+                let batch = Batch {
+                    batch_id: 0,
+                    issue_id: spec.issue_id.to_string(),
+                    branch: spec.branch.to_string(),
+                    runtime: spec.runtime,
+                    model_override: spec.model_override.map(str::to_string),
+                    thinking_level_override: spec.thinking_level_override.map(str::to_string),
+                    execution: spec.execution.clone(),
+                    tasks: tasks.clone(),
+                    issue_files: issue_files.clone(),
+                };
+                let receiver = self.dispatch(worker.id, batch);
+                collect(worker.name, tasks, receiver)
+            })
+            .collect();
+
+        let result_dir = get_results_directory_path(spec.issue_id);
+        let mut results = Vec::new();
+        for (result, result_file) in futures::future::join_all(pending)
+            .await
+            .into_iter()
+            .flatten()
+        {
+            if let Some(contents) = result_file {
+                if let Err(e) =
+                    fs::write(result_file_for(&result_dir, &result.identifier), contents)
+                {
+                    warn!(
+                        "Failed to save result file for {}: {}",
+                        result.identifier, e
+                    );
+                }
+            }
+            results.push(result);
+        }
+        results
+    }
+
+    /// Tell workers the loop is over and give them a moment to disconnect.
+    pub async fn shutdown(&self) {
+        for worker in lock(&self.state).workers.values() {
+            let _ = worker.outbox.send(CoordinatorMessage::Shutdown);
+        }
+        let deadline = Instant::now() + SHUTDOWN_GRACE;
+        while !lock(&self.state).workers.is_empty() && Instant::now() < deadline {
+            sleep(Duration::from_millis(50)).await;
+        }
+    }
+}
+
+/// Await one worker's outcomes and match them to the dispatched tasks.
+async fn collect(
+    worker: String,
+    tasks: Vec<SubTask>,
+    receiver: oneshot::Receiver<Vec<TaskOutcome>>,
+) -> Vec<(ExecutionResult, Option<String>)> {
+    let outcomes = tokio::select! {
+        reply = receiver => reply.map_err(|_| format!("Worker {} disconnected", worker)),
+        _ = shutdown::interrupted() => Err(format!("Interrupted while running on worker {}", worker)),
+    };
+
+    let mut by_id: HashMap<String, TaskOutcome> = match &outcomes {
+        Ok(outcomes) => outcomes
+            .iter()
+            .map(|o| (o.task_id.clone(), o.clone()))
+            .collect(),
+        Err(_) => HashMap::new(),
+    };
+    tasks
+        .iter()
+        .map(|task| match by_id.remove(&task.id) {
+            // The identifier names files on this machine, so it must be the
+            // dispatched task's own
+            Some(outcome) if outcome.identifier != task.identifier => (
+                failed_result(
+                    task,
+                    format!(
+                        "Worker {} returned a result for {}, which it was not given",
+                        worker, outcome.identifier
+                    ),
+                ),
+                None,
+            ),
+            Some(mut outcome) => {
+                let result_file = outcome.result_file.take();
+                (outcome.into_result(), result_file)
+            }
+            None => {
+                let error = match &outcomes {
+                    Ok(_) => format!("Worker {} returned no result", worker),
+                    Err(e) => e.clone(),
+                };
+                (failed_result(task, error), None)
+            }
+        })
+        .collect()
+}
+
+async fn handle_worker(
+    stream: TcpStream,
+    peer: SocketAddr,
+    state: Arc<Mutex<PoolState>>,
+    token: Option<String>,
+) {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+
+    let hello = timeout(REGISTER_TIMEOUT, recv_message(&mut reader)).await;
+    let (name, capacity) = match hello {
+        Ok(Ok(Some(WorkerMessage::Register {
+            name,
+            capacity,
+            version,
+            token: presented,
+        }))) => {
+            let rejection = if version != PROTOCOL_VERSION {
+                Some(format!(
+                    "protocol version {} does not match coordinator version {}",
+                    version, PROTOCOL_VERSION
+                ))
+            } else if !token_accepted(token.as_deref(), presented.as_deref()) {
+                Some("invalid pool token".to_string())
+            } else {
+                None
+            };
+            if let Some(reason) = rejection {
+                warn!("Rejected pool worker {} from {}: {}", name, peer, reason);
+                let _ = send_message(&mut write, &CoordinatorMessage::Rejected { reason }).await;
+                return;
+            }
+            (name, capacity.max(1))
+        }
+        _ => {
+            debug!("Pool connection from {} did not register", peer);
+            return;
+        }
+    };
+
+    let (outbox, mut messages) = mpsc::unbounded_channel();
+    let worker_id = {
+        let mut state = lock(&state);
+        state.next_worker_id += 1;
+        let id = state.next_worker_id;
+        state.workers.insert(
+            id,
+            ConnectedWorker {
+                name: name.clone(),
+                capacity,
+                outbox: outbox.clone(),
+                pending: None,
+            },
+        );
+        id
+    };
+    let _ = outbox.send(CoordinatorMessage::Registered { worker_id });
+    println!(
+        "Pool worker {} joined from {} ({} slot{})",
+        name,
+        peer,
+        capacity,
+        if capacity == 1 { "" } else { "s" }
+    );
+
+    let writer = tokio::spawn(async move {
+        while let Some(message) = messages.recv().await {
+            let last = matches!(message, CoordinatorMessage::Shutdown);
+            if send_message(&mut write, &message).await.is_err() || last {
+                break;
+            }
+        }
+    });
+
+    loop {
+        match timeout(HEARTBEAT_TIMEOUT, recv_message(&mut reader)).await {
+            Ok(Ok(Some(WorkerMessage::Results { batch_id, results }))) => {
+                let mut state = lock(&state);
+                if let Some(worker) = state.workers.get_mut(&worker_id) {
+                    if worker.pending.as_ref().map(|(id, _)| *id) == Some(batch_id) {
+                        if let Some((_, reply)) = worker.pending.take() {
+                            let _ = reply.send(results);
+                        }
+                    }
+                }
+            }
+            Ok(Ok(Some(_))) => {}
+            Ok(Ok(None)) => break,
+            Ok(Err(e)) => {
+                warn!("Dropping pool worker {}: {}", name, e);
+                break;
+            }
+            Err(_) => {
+                warn!("Pool worker {} stopped sending heartbeats", name);
+                break;
+            }
+        }
+    }
+
+    // Dropping the entry closes any pending reply, failing its tasks
+    lock(&state).workers.remove(&worker_id);
+    writer.abort();
+    println!("Pool worker {} left", name);
+}
+
+/// Whether a worker presenting `presented` may join a pool requiring `expected`.
+fn token_accepted(expected: Option<&str>, presented: Option<&str>) -> bool {
+    match (expected, presented) {
+        (None, _) => true,
+        (Some(expected), Some(presented)) => tokens_match(presented, expected),
+        (Some(_), None) => false,
+    }
+}
+
+/// Read an issue's context directory, skipping execution state.
+pub fn snapshot_issue_files(issue_dir: &Path) -> BTreeMap<String, String> {
+    let mut files = BTreeMap::new();
+    collect_files(issue_dir, issue_dir, &mut files);
+    files
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut BTreeMap<String, String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if dir == root && entry.file_name() == "execution" {
+                continue;
+            }
+            collect_files(root, &path, files);
+        } else if let (Ok(relative), Ok(contents)) =
+            (path.strip_prefix(root), fs::read_to_string(&path))
+        {
+            let relative = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(relative, contents);
+        }
+    }
+}
+
+/// Write a snapshot from [`snapshot_issue_files`] under `issue_dir`.
+pub fn write_issue_files(issue_dir: &Path, files: &BTreeMap<String, String>) -> Result<()> {
+    for (relative, contents) in files {
+        if relative
+            .split('/')
+            .any(|part| part.is_empty() || part == "." || part == "..")
+        {
+            anyhow::bail!(
+                "Refusing to write issue file outside the issue: {}",
+                relative
+            );
+        }
+        let path = issue_dir.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(())
+}
+
+async fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .with_context(|| format!("failed to run git {}", args[0]))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MobiusError::Git(format!("git {} failed: {}", args[0], stderr.trim())).into());
+    }
+    Ok(())
+}
+
+/// Push the worktree's HEAD to `branch` on `remote`.
+pub async fn publish_branch(worktree: &Path, remote: &str, branch: &str) -> Result<()> {
+    let refspec = format!("HEAD:refs/heads/{}", branch);
+    git(worktree, &["push", remote, &refspec]).await
+}
+
+/// Rebase the worktree onto `branch` from `remote`, aborting on conflict.
+pub async fn integrate_branch(worktree: &Path, remote: &str, branch: &str) -> Result<()> {
+    let result = git(
+        worktree,
+        &["pull", "--rebase", "--autostash", "--quiet", remote, branch],
+    )
+    .await;
+    if result.is_err() {
+        let _ = git(worktree, &["rebase", "--abort"]).await;
+    }
+    result
+}
+
+/// Fetch `branch` from `remote` into the repository at `dir`.
+pub async fn fetch_branch(dir: &Path, remote: &str, branch: &str) -> Result<()> {
+    git(dir, &["fetch", "--quiet", remote, branch]).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::TaskStatus;
+
+    fn task(id: &str) -> SubTask {
+        SubTask {
+            id: id.to_string(),
+            identifier: id.to_string(),
+            title: format!("Task {}", id),
+            status: TaskStatus::Ready,
+            blocked_by: vec![],
+            blocks: vec![],
+            git_branch_name: String::new(),
+            scoring: None,
+            priority: None,
+            labels: vec![],
+            timeout_minutes: None,
+            task_type: Default::default(),
+        }
+    }
+
+    fn slot(id: u64, capacity: usize) -> WorkerSlot {
+        WorkerSlot {
+            id,
+            name: format!("w{}", id),
+            capacity,
+        }
+    }
+
+    #[test]
+    fn test_distribute_fills_largest_worker_first() {
+        let tasks = vec![task("A"), task("B"), task("C"), task("D")];
+        let assignments = distribute(tasks, &[slot(1, 1), slot(2, 2), slot(3, 2)]);
+
+        let shape: Vec<(u64, Vec<&str>)> = assignments
+            .iter()
+            .map(|(w, ts)| (w.id, ts.iter().map(|t| t.id.as_str()).collect()))
+            .collect();
+        assert_eq!(shape, vec![(2, vec!["A", "B"]), (3, vec!["C", "D"])]);
+    }
+
+    #[test]
+    fn test_distribute_drops_tasks_beyond_capacity() {
+        let assignments = distribute(vec![task("A"), task("B"), task("C")], &[slot(1, 2)]);
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].1.len(), 2);
+        assert!(distribute(vec![task("A")], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_worker_message_wire_format() {
+        let json = serde_json::to_string(&WorkerMessage::Register {
+            name: "build-box".to_string(),
+            capacity: 4,
+            version: PROTOCOL_VERSION,
+            token: None,
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"register","name":"build-box","capacity":4,"version":1}"#
+        );
+
+        let outcome = TaskOutcome {
+            task_id: "id-1".to_string(),
+            identifier: "MOB-1".to_string(),
+            success: true,
+            status: ExecutionStatus::SubtaskComplete,
+            duration_ms: 1200,
+            error: None,
+            input_tokens: Some(10),
+            output_tokens: None,
+            result_file: None,
+        };
+        let message = WorkerMessage::Results {
+            batch_id: 7,
+            results: vec![outcome],
+        };
+        let json = serde_json::to_string(&message).unwrap();
+        assert!(json.contains(r#""status":"subtask_complete""#));
+        let parsed: WorkerMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, message);
+    }
+
+    #[tokio::test]
+    async fn test_missing_outcomes_become_failures() {
+        let (reply, receiver) = oneshot::channel();
+        let outcome = TaskOutcome::from_result(
+            &ExecutionResult {
+                success: true,
+                status: ExecutionStatus::SubtaskComplete,
+                ..failed_result(&task("A"), String::new())
+            },
+            Some("{}".to_string()),
+        );
+        reply.send(vec![outcome]).unwrap();
+
+        let results = collect("w1".to_string(), vec![task("A"), task("B")], receiver).await;
+        assert!(results[0].0.success);
+        assert_eq!(results[0].1.as_deref(), Some("{}"));
+        assert!(!results[1].0.success);
+        assert_eq!(
+            results[1].0.error.as_deref(),
+            Some("Worker w1 returned no result")
+        );
+
+        let (reply, receiver) = oneshot::channel::<Vec<TaskOutcome>>();
+        drop(reply);
+        let results = collect("w1".to_string(), vec![task("A")], receiver).await;
+        assert_eq!(
+            results[0].0.error.as_deref(),
+            Some("Worker w1 disconnected")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_outcome_for_other_identifier_is_rejected() {
+        let (reply, receiver) = oneshot::channel();
+        let mut outcome = TaskOutcome::from_result(
+            &failed_result(&task("A"), String::new()),
+            Some("{}".to_string()),
+        );
+        outcome.identifier = "../../x".to_string();
+        reply.send(vec![outcome]).unwrap();
+
+        let results = collect("w1".to_string(), vec![task("A")], receiver).await;
+        assert_eq!(results[0].0.identifier, "A");
+        assert!(!results[0].0.success);
+        assert!(results[0].1.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_coordinator_requires_token_off_loopback() {
+        assert!(Coordinator::start("0.0.0.0:0", None).await.is_err());
+        assert!(Coordinator::start("127.0.0.1:0", None).await.is_ok());
+    }
+
+    #[test]
+    fn test_issue_snapshot_roundtrip_skips_execution_state() {
+        let source = tempfile::tempdir().unwrap();
+        fs::create_dir_all(source.path().join("tasks")).unwrap();
+        fs::create_dir_all(source.path().join("execution/results")).unwrap();
+        fs::write(source.path().join("parent.json"), "{}").unwrap();
+        fs::write(source.path().join("tasks/MOB-1.json"), "[]").unwrap();
+        fs::write(source.path().join("execution/results/MOB-1.json"), "{}").unwrap();
+
+        let files = snapshot_issue_files(source.path());
+        assert_eq!(
+            files.keys().collect::<Vec<_>>(),
+            vec!["parent.json", "tasks/MOB-1.json"]
+        );
+
+        let target = tempfile::tempdir().unwrap();
+        write_issue_files(target.path(), &files).unwrap();
+        assert_eq!(
+            fs::read_to_string(target.path().join("tasks/MOB-1.json")).unwrap(),
+            "[]"
+        );
+
+        let escape = BTreeMap::from([("../x".to_string(), String::new())]);
+        assert!(write_issue_files(target.path(), &escape).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_coordinator_rejects_wrong_token() {
+        let coordinator = Coordinator::start("127.0.0.1:0", Some("secret".to_string()))
+            .await
+            .unwrap();
+        let stream = TcpStream::connect(coordinator.local_addr()).await.unwrap();
+        let (read, mut write) = stream.into_split();
+        let mut reader = BufReader::new(read);
+        send_message(
+            &mut write,
+            &WorkerMessage::Register {
+                name: "w".to_string(),
+                capacity: 1,
+                version: PROTOCOL_VERSION,
+                token: Some("wrong".to_string()),
+            },
+        )
+        .await
+        .unwrap();
+
+        let reply: Option<CoordinatorMessage> = recv_message(&mut reader).await.unwrap();
+        assert!(matches!(reply, Some(CoordinatorMessage::Rejected { .. })));
+        assert!(coordinator.idle_workers().is_empty());
+    }
+}
@@ -0,0 +1,339 @@
+//! Commit attribution for agent commits.
+//!
+//! Agents commit from their own shell, so authorship is controlled through the
+//! environment they inherit, whatever the runtime: a bot identity is exported
+//! as `GIT_AUTHOR_*`/`GIT_COMMITTER_*`, and `Co-authored-by:` trailers are added
+//! by a generated `commit-msg` hook. Git is pointed at the generated hooks with
+//! `GIT_CONFIG_COUNT`; every hook chains to the repository's own hook of the
+//! same name, so existing pre-commit and commit-msg checks still run.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use tracing::warn;
+
+use crate::local_state::get_project_mobius_path;
+use crate::types::{AgentRuntime, AttributionConfig, CommitAuthor};
+
+/// Separator between co-authors in `$MOBIUS_CO_AUTHORS`
+const CO_AUTHOR_SEPARATOR: char = '|';
+
+/// Hooks wrapped by the generated hooks directory
+const WRAPPED_HOOKS: &[&str] = &[
+    "applypatch-msg",
+    "pre-applypatch",
+    "post-applypatch",
+    "pre-commit",
+    "pre-merge-commit",
+    "prepare-commit-msg",
+    "commit-msg",
+    "post-commit",
+    "pre-rebase",
+    "post-checkout",
+    "post-merge",
+    "pre-push",
+    "post-rewrite",
+];
+
+const HOOK_SCRIPT: &str = r#"#!/bin/sh
+# Generated by mobius. Adds Co-authored-by trailers to agent commits, then
+# runs the repository's own hook.
+hook_name=$(basename "$0")
+if [ "$hook_name" = "commit-msg" ] && [ -n "$MOBIUS_CO_AUTHORS" ]; then
+    printf '%s\n' "$MOBIUS_CO_AUTHORS" | tr '|' '\n' | while IFS= read -r co_author; do
+        [ -n "$co_author" ] || continue
+        git interpret-trailers --in-place --if-exists addIfDifferent \
+            --trailer "Co-authored-by: $co_author" "$1" || exit 1
+    done || exit 1
+fi
+original="${MOBIUS_ORIGINAL_HOOKS:-$(git rev-parse --git-common-dir)/hooks}/$hook_name"
+if [ -x "$original" ]; then
+    exec "$original" "$@"
+fi
+exit 0
+"#;
+
+/// A git identity (`Name <email>`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitIdentity {
+    pub name: String,
+    pub email: String,
+}
+
+impl fmt::Display for GitIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} <{}>", self.name, self.email)
+    }
+}
+
+/// Authorship applied to one agent's commits
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitAttribution {
+    /// Author and committer override; `None` keeps the operator's git identity
+    pub author: Option<GitIdentity>,
+    pub co_authors: Vec<GitIdentity>,
+}
+
+impl CommitAttribution {
+    pub fn is_empty(&self) -> bool {
+        self.author.is_none() && self.co_authors.is_empty()
+    }
+}
+
+/// Identity credited for commits made by `runtime` running `model`.
+pub fn agent_identity(runtime: AgentRuntime, model: &str) -> GitIdentity {
+    let (name, email) = match runtime {
+        AgentRuntime::Claude => ("Claude", "noreply@anthropic.com"),
+        AgentRuntime::Opencode => ("OpenCode", "noreply@opencode.ai"),
+        AgentRuntime::Codex => ("Codex", "noreply@openai.com"),
+        AgentRuntime::Aider => ("Aider", "noreply@aider.chat"),
+    };
+    GitIdentity {
+        name: format!("{} ({})", name, model),
+        email: email.to_string(),
+    }
+}
+
+/// Decide authorship and trailers from config.
+///
+/// `user` is the operator's git identity, if configured. The operator is only
+/// credited as a co-author when the bot is the author.
+pub fn build_attribution(
+    config: &AttributionConfig,
+    user: Option<GitIdentity>,
+    agent: GitIdentity,
+) -> CommitAttribution {
+    let author = match config.author {
+        CommitAuthor::User => None,
+        CommitAuthor::Bot => Some(GitIdentity {
+            name: config.bot_name.clone(),
+            email: config.bot_email.clone(),
+        }),
+    };
+    let mut co_authors = Vec::new();
+    if author.is_some() && config.co_author_user {
+        co_authors.extend(user);
+    }
+    if config.co_author_agent {
+        co_authors.push(agent);
+    }
+    CommitAttribution { author, co_authors }
+}
+
+/// Resolve attribution for an agent working in `worktree_path`.
+pub fn resolve_attribution(
+    config: &AttributionConfig,
+    runtime: AgentRuntime,
+    model: &str,
+    worktree_path: &str,
+) -> CommitAttribution {
+    build_attribution(
+        config,
+        git_user_identity(worktree_path),
+        agent_identity(runtime, model),
+    )
+}
+
+/// The operator's `user.name` and `user.email`, if both are set.
+pub fn git_user_identity(cwd: &str) -> Option<GitIdentity> {
+    Some(GitIdentity {
+        name: git_config_value(cwd, "user.name")?,
+        email: git_config_value(cwd, "user.email")?,
+    })
+}
+
+fn git_config_value(cwd: &str, key: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "--get", key])
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
+
+/// Directory holding the generated hooks (`.mobius/state/hooks`)
+pub fn get_hooks_dir() -> PathBuf {
+    get_project_mobius_path().join("state").join("hooks")
+}
+
+/// Write the generated hooks into `dir`, rewriting any that are out of date.
+pub fn ensure_attribution_hooks(dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    for hook in WRAPPED_HOOKS {
+        let path = dir.join(hook);
+        if fs::read_to_string(&path).is_ok_and(|existing| existing == HOOK_SCRIPT) {
+            continue;
+        }
+        fs::write(&path, HOOK_SCRIPT)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+                .with_context(|| format!("Failed to make {} executable", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Prefix `command` so commits made by the agent carry `attribution`.
+///
+/// `hooks_dir` holds the generated hooks; without it no trailers are added.
+/// `original_hooks` is the repository's own `core.hooksPath`, if set.
+pub fn with_attribution_env(
+    command: &str,
+    attribution: &CommitAttribution,
+    hooks_dir: Option<&Path>,
+    original_hooks: Option<&str>,
+) -> String {
+    let mut vars: Vec<(&str, String)> = Vec::new();
+    if let Some(author) = &attribution.author {
+        vars.push(("GIT_AUTHOR_NAME", author.name.clone()));
+        vars.push(("GIT_AUTHOR_EMAIL", author.email.clone()));
+        vars.push(("GIT_COMMITTER_NAME", author.name.clone()));
+        vars.push(("GIT_COMMITTER_EMAIL", author.email.clone()));
+    }
+    if let (Some(dir), false) = (hooks_dir, attribution.co_authors.is_empty()) {
+        let co_authors: Vec<String> = attribution
+            .co_authors
+            .iter()
+            .map(|c| c.to_string().replace(CO_AUTHOR_SEPARATOR, ""))
+            .collect();
+        vars.push(("GIT_CONFIG_COUNT", "1".to_string()));
+        vars.push(("GIT_CONFIG_KEY_0", "core.hooksPath".to_string()));
+        vars.push(("GIT_CONFIG_VALUE_0", dir.display().to_string()));
+        vars.push((
+            "MOBIUS_CO_AUTHORS",
+            co_authors.join(&CO_AUTHOR_SEPARATOR.to_string()),
+        ));
+        if let Some(original) = original_hooks {
+            vars.push(("MOBIUS_ORIGINAL_HOOKS", original.to_string()));
+        }
+    }
+    if vars.is_empty() {
+        return command.to_string();
+    }
+
+    let assignments: Vec<String> = vars
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, shell_quote(value)))
+        .collect();
+    format!("export {} && {}", assignments.join(" "), command)
+}
+
+/// Apply configured attribution to an agent command running in `worktree_path`.
+///
+/// Failing to write the hooks drops the trailers but keeps the author.
+pub fn apply_attribution(
+    command: &str,
+    config: &AttributionConfig,
+    runtime: AgentRuntime,
+    model: &str,
+    worktree_path: &str,
+) -> String {
+    let attribution = resolve_attribution(config, runtime, model, worktree_path);
+    if attribution.is_empty() {
+        return command.to_string();
+    }
+    let hooks_dir = if attribution.co_authors.is_empty() {
+        None
+    } else {
+        let dir = get_hooks_dir();
+        match ensure_attribution_hooks(&dir) {
+            Ok(()) => Some(dir),
+            Err(e) => {
+                warn!("Co-authored-by trailers disabled: {}", e);
+                None
+            }
+        }
+    };
+    let original_hooks = git_config_value(worktree_path, "core.hooksPath");
+    with_attribution_env(
+        command,
+        &attribution,
+        hooks_dir.as_deref(),
+        original_hooks.as_deref(),
+    )
+}
+
+pub(crate) fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user() -> GitIdentity {
+        GitIdentity {
+            name: "Ada Lovelace".to_string(),
+            email: "ada@example.com".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_attribution() {
+        let agent = agent_identity(AgentRuntime::Claude, "opus");
+        assert_eq!(agent.to_string(), "Claude (opus) <noreply@anthropic.com>");
+
+        let default = build_attribution(&AttributionConfig::default(), Some(user()), agent.clone());
+        assert_eq!(default.author, None);
+        assert_eq!(default.co_authors, vec![agent.clone()]);
+
+        let bot = AttributionConfig {
+            author: CommitAuthor::Bot,
+            ..AttributionConfig::default()
+        };
+        let attribution = build_attribution(&bot, Some(user()), agent.clone());
+        assert_eq!(
+            attribution.author.map(|a| a.name),
+            Some("mobius[bot]".to_string())
+        );
+        assert_eq!(attribution.co_authors, vec![user(), agent.clone()]);
+
+        let none = AttributionConfig {
+            co_author_agent: false,
+            ..AttributionConfig::default()
+        };
+        assert!(build_attribution(&none, Some(user()), agent).is_empty());
+    }
+
+    #[test]
+    fn test_with_attribution_env() {
+        let attribution = CommitAttribution {
+            author: Some(GitIdentity {
+                name: "O'Brien".to_string(),
+                email: "bot@example.com".to_string(),
+            }),
+            co_authors: vec![user(), agent_identity(AgentRuntime::Opencode, "gpt")],
+        };
+        let cmd = with_attribution_env(
+            "cd /repo && claude",
+            &attribution,
+            Some(Path::new("/repo/.mobius/state/hooks")),
+            Some(".husky"),
+        );
+        assert!(cmd.starts_with("export GIT_AUTHOR_NAME='O'\\''Brien' "));
+        assert!(cmd.contains("GIT_COMMITTER_EMAIL='bot@example.com'"));
+        assert!(cmd.contains("GIT_CONFIG_VALUE_0='/repo/.mobius/state/hooks'"));
+        assert!(cmd.contains(
+            "MOBIUS_CO_AUTHORS='Ada Lovelace <ada@example.com>|OpenCode (gpt) <noreply@opencode.ai>'"
+        ));
+        assert!(cmd.contains("MOBIUS_ORIGINAL_HOOKS='.husky'"));
+        assert!(cmd.ends_with(" && cd /repo && claude"));
+
+        let no_hooks = with_attribution_env("claude", &attribution, None, None);
+        assert!(!no_hooks.contains("GIT_CONFIG_COUNT"));
+        assert_eq!(
+            with_attribution_env("claude", &CommitAttribution::default(), None, None),
+            "claude"
+        );
+    }
+}
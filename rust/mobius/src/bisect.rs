@@ -0,0 +1,424 @@
+//! Bisect the loop branch for the sub-task that broke integration.
+//!
+//! Every sub-task passes its own verification, yet the combined branch can
+//! still fail the Verification Gate. Commits on the loop branch are attributed
+//! to sub-tasks by the identifier in their message (as in churn) and grouped
+//! into runs of consecutive commits from the same task. The verify command is
+//! re-run at run boundaries in a scratch worktree until the first failing run
+//! is found; its task is the culprit.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::context::{atomic_write_json, get_bisect_report_path};
+
+/// Lines of verify output kept from the first failing step
+const FAILURE_OUTPUT_LINES: usize = 40;
+
+/// A first-parent commit on the loop branch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BranchCommit {
+    pub sha: String,
+    pub subject: String,
+    /// Sub-task named in the commit message, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+}
+
+/// How a bisect ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BisectVerdict {
+    /// A sub-task's commits turn the verify command from passing to failing
+    Culprit,
+    /// The verify command passes at the branch head; nothing to bisect
+    HeadPasses,
+    /// The verify command already fails at the base
+    BaseFails,
+}
+
+/// Outcome of searching run boundaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BisectOutcome {
+    /// Index of the first failing boundary
+    FirstFailing(usize),
+    HeadPasses,
+    BaseFails,
+}
+
+/// One verify run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectStep {
+    pub sha: String,
+    /// Last sub-task applied at this point (`None` at the base)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    pub passed: bool,
+}
+
+/// Result of `mobius bisect`, saved to `.mobius/issues/{id}/execution/bisect.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisectReport {
+    pub parent_id: String,
+    pub generated_at: String,
+    pub base: String,
+    pub command: String,
+    pub verdict: BisectVerdict,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub culprit_task: Option<String>,
+    /// Commits of the culprit run, oldest first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub culprit_commits: Vec<BranchCommit>,
+    pub steps: Vec<BisectStep>,
+    /// Tail of the verify output at the first failing point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_output: Option<String>,
+}
+
+/// First-parent commits in `base..head`, oldest first, attributed to the
+/// identifiers their messages mention.
+pub fn list_branch_commits(
+    repo_path: &Path,
+    base: &str,
+    head: &str,
+    identifiers: &[String],
+) -> Result<Vec<BranchCommit>> {
+    let log = git_output(
+        repo_path,
+        &[
+            "log",
+            "--reverse",
+            "--first-parent",
+            "--format=%H%x1f%s%x1f%b%x1e",
+            &format!("{}..{}", base, head),
+        ],
+    )?;
+    Ok(parse_branch_log(&log, identifiers))
+}
+
+fn parse_branch_log(log: &str, identifiers: &[String]) -> Vec<BranchCommit> {
+    let patterns: Vec<(&String, Regex)> = identifiers
+        .iter()
+        .filter_map(|id| {
+            Regex::new(&format!(r"(?i)\b{}\b", regex::escape(id)))
+                .ok()
+                .map(|re| (id, re))
+        })
+        .collect();
+
+    log.split('\x1e')
+        .filter(|r| !r.trim().is_empty())
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(3, '\x1f');
+            let sha = fields.next()?.trim().to_string();
+            let subject = fields.next().unwrap_or_default().to_string();
+            let message = format!("{}\n{}", subject, fields.next().unwrap_or_default());
+            let task = patterns
+                .iter()
+                .find(|(_, re)| re.is_match(&message))
+                .map(|(id, _)| (*id).clone());
+            Some(BranchCommit { sha, subject, task })
+        })
+        .collect()
+}
+
+/// Indices of the last commit in each run of consecutive same-task commits.
+///
+/// Commits naming no sub-task join the run before them (or the first run).
+pub fn task_boundaries(commits: &[BranchCommit]) -> Vec<usize> {
+    let mut boundaries = Vec::new();
+    let mut current: Option<&str> = None;
+    for (i, commit) in commits.iter().enumerate() {
+        if let Some(task) = commit.task.as_deref() {
+            if current.is_some_and(|c| c != task) {
+                boundaries.push(i - 1);
+            }
+            current = Some(task);
+        }
+    }
+    if !commits.is_empty() {
+        boundaries.push(commits.len() - 1);
+    }
+    boundaries
+}
+
+/// Binary search for the first of `count` points where `passes_at` fails.
+///
+/// `passes_at(None)` checks the base. The head (last point) is checked first,
+/// then the base, so an already-broken base or a passing head costs one or two
+/// runs.
+pub fn find_first_failing<F>(count: usize, mut passes_at: F) -> Result<BisectOutcome>
+where
+    F: FnMut(Option<usize>) -> Result<bool>,
+{
+    if count == 0 || passes_at(Some(count - 1))? {
+        return Ok(BisectOutcome::HeadPasses);
+    }
+    if !passes_at(None)? {
+        return Ok(BisectOutcome::BaseFails);
+    }
+
+    // Invariant: `good` passes (None = base), `bad` fails
+    let mut good: Option<usize> = None;
+    let mut bad = count - 1;
+    loop {
+        let low = good.map_or(0, |g| g + 1);
+        if low >= bad {
+            return Ok(BisectOutcome::FirstFailing(bad));
+        }
+        let mid = low + (bad - low) / 2;
+        if passes_at(Some(mid))? {
+            good = Some(mid);
+        } else {
+            bad = mid;
+        }
+    }
+}
+
+/// Bisect `base..head` in `repo_path` with `command`, calling `on_step` after
+/// each verify run, and save the report.
+pub fn run_bisect<F>(
+    parent_id: &str,
+    repo_path: &Path,
+    base: &str,
+    head: &str,
+    identifiers: &[String],
+    command: &str,
+    mut on_step: F,
+) -> Result<BisectReport>
+where
+    F: FnMut(&BisectStep),
+{
+    let base_sha = git_output(repo_path, &["merge-base", base, head])
+        .with_context(|| format!("No common ancestor of {} and {}", base, head))?
+        .trim()
+        .to_string();
+    let commits = list_branch_commits(repo_path, &base_sha, head, identifiers)?;
+    let boundaries = task_boundaries(&commits);
+
+    let scratch = ScratchWorktree::create(repo_path, &format!("bisect-{}", parent_id), &base_sha)?;
+    let mut steps = Vec::new();
+    let mut failure_outputs: Vec<(Option<usize>, String)> = Vec::new();
+    let outcome = find_first_failing(boundaries.len(), |point| {
+        let (sha, task) = match point {
+            Some(i) => {
+                let commit = &commits[boundaries[i]];
+                (commit.sha.clone(), run_task(&commits, boundaries[i]))
+            }
+            None => (base_sha.clone(), None),
+        };
+        let (passed, output) = scratch.verify_at(&sha, command)?;
+        if !passed {
+            failure_outputs.push((point, output));
+        }
+        let step = BisectStep { sha, task, passed };
+        on_step(&step);
+        steps.push(step);
+        Ok(passed)
+    });
+    scratch.remove();
+    let outcome = outcome?;
+
+    let (verdict, culprit_task, culprit_commits, failing_point) = match outcome {
+        BisectOutcome::FirstFailing(i) => {
+            let start = if i == 0 { 0 } else { boundaries[i - 1] + 1 };
+            let run = commits[start..=boundaries[i]].to_vec();
+            (
+                BisectVerdict::Culprit,
+                run_task(&commits, boundaries[i]),
+                run,
+                Some(Some(i)),
+            )
+        }
+        BisectOutcome::HeadPasses => (BisectVerdict::HeadPasses, None, Vec::new(), None),
+        BisectOutcome::BaseFails => (BisectVerdict::BaseFails, None, Vec::new(), Some(None)),
+    };
+    let failure_output = failing_point.and_then(|point| {
+        failure_outputs
+            .into_iter()
+            .find(|(p, _)| *p == point)
+            .map(|(_, output)| output)
+            .filter(|output| !output.trim().is_empty())
+    });
+
+    let report = BisectReport {
+        parent_id: parent_id.to_string(),
+        generated_at: Utc::now().to_rfc3339(),
+        base: base_sha,
+        command: command.to_string(),
+        verdict,
+        culprit_task,
+        culprit_commits,
+        steps,
+        failure_output,
+    };
+    write_bisect_report(&report)?;
+    Ok(report)
+}
+
+/// Sub-task of the run ending at `index`: the latest attributed commit at or
+/// before it.
+fn run_task(commits: &[BranchCommit], index: usize) -> Option<String> {
+    commits[..=index].iter().rev().find_map(|c| c.task.clone())
+}
+
+/// Read a previously written bisect report.
+pub fn read_bisect_report(parent_id: &str) -> Option<BisectReport> {
+    let content = fs::read_to_string(get_bisect_report_path(parent_id)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Persist a bisect report to `.mobius/issues/{id}/execution/bisect.json`.
+pub fn write_bisect_report(report: &BisectReport) -> Result<()> {
+    let path = get_bisect_report_path(&report.parent_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    atomic_write_json(&path, report)
+}
+
+/// Detached worktree the verify command runs in, so the loop worktree and
+/// its uncommitted state are never touched.
+pub(crate) struct ScratchWorktree {
+    repo_path: PathBuf,
+    pub(crate) path: PathBuf,
+}
+
+impl ScratchWorktree {
+    /// Detached worktree at `sha` under the temp dir, named after `purpose`
+    /// (e.g. `bisect-MOB-1`).
+    pub(crate) fn create(repo_path: &Path, purpose: &str, sha: &str) -> Result<Self> {
+        let path = std::env::temp_dir().join(format!("mobius-{}-{}", purpose, std::process::id()));
+        if path.exists() {
+            let _ = fs::remove_dir_all(&path);
+        }
+        let path_str = path.display().to_string();
+        git_output(
+            repo_path,
+            &["worktree", "add", "--detach", "--force", &path_str, sha],
+        )
+        .context("Failed to create scratch worktree")?;
+        Ok(Self {
+            repo_path: repo_path.to_path_buf(),
+            path,
+        })
+    }
+
+    /// Check out `sha` and run `command`; returns whether it passed and the
+    /// tail of its output.
+    fn verify_at(&self, sha: &str, command: &str) -> Result<(bool, String)> {
+        git_output(&self.path, &["checkout", "--detach", "--force", sha])?;
+        git_output(&self.path, &["clean", "-fdq"])?;
+        let output = Command::new("sh")
+            .args(["-c", command])
+            .current_dir(&self.path)
+            .output()
+            .with_context(|| format!("Failed to run '{}'", command))?;
+
+        let combined = format!(
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let lines: Vec<&str> = combined.lines().collect();
+        let tail = lines[lines.len().saturating_sub(FAILURE_OUTPUT_LINES)..].join("\n");
+        Ok((output.status.success(), tail))
+    }
+
+    pub(crate) fn remove(&self) {
+        let path = self.path.display().to_string();
+        if git_output(&self.repo_path, &["worktree", "remove", "--force", &path]).is_err() {
+            let _ = fs::remove_dir_all(&self.path);
+            let _ = git_output(&self.repo_path, &["worktree", "prune"]);
+        }
+    }
+}
+
+pub(crate) fn git_output(cwd: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(cwd)
+        .args(args)
+        .output()
+        .context("failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_branch_log_and_boundaries() {
+        let log = "a1\x1ffeat: add parser\x1fImplements: MOB-101\n\x1e\n\
+                   b2\x1ffix lint\x1f\x1e\n\
+                   c3\x1ffeat(api): mob-102 endpoint\x1f\x1e\n\
+                   d4\x1ftest: cover endpoint\x1fImplements: MOB-102\x1e\n\
+                   e5\x1ffeat: wire ui\x1fImplements: MOB-103\x1e";
+        let ids = vec![
+            "MOB-101".to_string(),
+            "MOB-102".to_string(),
+            "MOB-103".to_string(),
+        ];
+        let commits = parse_branch_log(log, &ids);
+        let tasks: Vec<Option<&str>> = commits.iter().map(|c| c.task.as_deref()).collect();
+        assert_eq!(
+            tasks,
+            vec![
+                Some("MOB-101"),
+                None,
+                Some("MOB-102"),
+                Some("MOB-102"),
+                Some("MOB-103")
+            ]
+        );
+        assert_eq!(commits[0].subject, "feat: add parser");
+        assert_eq!(task_boundaries(&commits), vec![1, 3, 4]);
+        assert_eq!(run_task(&commits, 1), Some("MOB-101".to_string()));
+        assert!(task_boundaries(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_find_first_failing() {
+        // Breaks at point 5 of 8
+        let mut runs = Vec::new();
+        let outcome = find_first_failing(8, |p| {
+            runs.push(p);
+            Ok(p.map_or(true, |i| i < 5))
+        })
+        .unwrap();
+        assert_eq!(outcome, BisectOutcome::FirstFailing(5));
+        assert!(runs.len() <= 5, "expected a logarithmic search: {:?}", runs);
+
+        let first = find_first_failing(3, |p| Ok(p.is_none())).unwrap();
+        assert_eq!(first, BisectOutcome::FirstFailing(0));
+
+        assert_eq!(
+            find_first_failing(3, |_| Ok(true)).unwrap(),
+            BisectOutcome::HeadPasses
+        );
+        assert_eq!(
+            find_first_failing(3, |_| Ok(false)).unwrap(),
+            BisectOutcome::BaseFails
+        );
+        assert_eq!(
+            find_first_failing(0, |_| Ok(false)).unwrap(),
+            BisectOutcome::HeadPasses
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Per-worktree dependency bootstrap.
+//!
+//! `execution.bootstrap` commands (e.g. `pnpm install`, `cargo fetch`) run once
+//! in a loop's worktree before any agent starts, so agents don't each spend
+//! time and tokens working out how to install dependencies. Output is appended
+//! to `.mobius/state/bootstrap.log` in the worktree, and a stamp in
+//! `.mobius/state/bootstrap.json` skips the commands on later runs until they
+//! or a lockfile change.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::context::atomic_write_json;
+
+/// Lockfiles and manifests whose content invalidates the bootstrap stamp
+const LOCK_FILES: &[&str] = &[
+    "Cargo.lock",
+    "Cargo.toml",
+    "package.json",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "bun.lockb",
+    "poetry.lock",
+    "uv.lock",
+    "requirements.txt",
+    "go.sum",
+    "Gemfile.lock",
+];
+
+/// Record of a successful bootstrap (`.mobius/state/bootstrap.json`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BootstrapStamp {
+    /// Hash of the commands and lockfiles the bootstrap ran against
+    pub fingerprint: String,
+    pub completed_at: String,
+}
+
+/// What `run_bootstrap` did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootstrapOutcome {
+    /// No commands configured
+    Skipped,
+    /// Stamp matched; commands were not rerun
+    Cached,
+    /// Commands ran and succeeded
+    Ran,
+}
+
+pub fn get_bootstrap_stamp_path(worktree: &Path) -> PathBuf {
+    worktree
+        .join(".mobius")
+        .join("state")
+        .join("bootstrap.json")
+}
+
+pub fn get_bootstrap_log_path(worktree: &Path) -> PathBuf {
+    worktree.join(".mobius").join("state").join("bootstrap.log")
+}
+
+/// Hash the commands and the worktree's lockfiles.
+pub fn bootstrap_fingerprint(worktree: &Path, commands: &[String]) -> String {
+    let mut hasher = DefaultHasher::new();
+    commands.hash(&mut hasher);
+    for name in LOCK_FILES {
+        if let Ok(bytes) = fs::read(worktree.join(name)) {
+            name.hash(&mut hasher);
+            bytes.hash(&mut hasher);
+        }
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+fn read_stamp(worktree: &Path) -> Option<BootstrapStamp> {
+    let content = fs::read_to_string(get_bootstrap_stamp_path(worktree)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Run `commands` in `worktree` with `sh -c`, in order, unless the stamp shows
+/// they already succeeded against the same lockfiles.
+///
+/// Stops at the first failing command; the stamp is only written once all of
+/// them succeed, so a failed bootstrap is retried on the next run.
+pub fn run_bootstrap(
+    worktree: &Path,
+    commands: &[String],
+    mut on_command: impl FnMut(&str),
+) -> Result<BootstrapOutcome> {
+    let commands: Vec<String> = commands
+        .iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect();
+    if commands.is_empty() {
+        return Ok(BootstrapOutcome::Skipped);
+    }
+
+    let fingerprint = bootstrap_fingerprint(worktree, &commands);
+    if read_stamp(worktree).is_some_and(|s| s.fingerprint == fingerprint) {
+        return Ok(BootstrapOutcome::Cached);
+    }
+
+    let log_path = get_bootstrap_log_path(worktree);
+    if let Some(parent) = log_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .with_context(|| format!("Failed to open {}", log_path.display()))?;
+
+    for command in &commands {
+        on_command(command);
+        writeln!(log, "[{}] $ {}", Utc::now().to_rfc3339(), command)?;
+        let status = Command::new("sh")
+            .args(["-c", command])
+            .current_dir(worktree)
+            .stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log.try_clone()?)
+            .status()
+            .with_context(|| format!("Failed to run bootstrap command `{}`", command))?;
+        if !status.success() {
+            bail!(
+                "Bootstrap command `{}` failed ({}); see {}",
+                command,
+                status,
+                log_path.display()
+            );
+        }
+    }
+
+    let stamp = BootstrapStamp {
+        fingerprint,
+        completed_at: Utc::now().to_rfc3339(),
+    };
+    atomic_write_json(&get_bootstrap_stamp_path(worktree), &stamp)
+        .context("Failed to write bootstrap stamp")?;
+    Ok(BootstrapOutcome::Ran)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bootstrap_runs_once_until_lockfile_changes() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        fs::write(root.join("Cargo.lock"), "v1").unwrap();
+        let commands = vec!["echo installed >> installs.txt".to_string()];
+        let installs = || fs::read_to_string(root.join("installs.txt")).unwrap_or_default();
+
+        assert_eq!(
+            run_bootstrap(root, &[], |_| {}).unwrap(),
+            BootstrapOutcome::Skipped
+        );
+
+        let mut ran = Vec::new();
+        assert_eq!(
+            run_bootstrap(root, &commands, |c| ran.push(c.to_string())).unwrap(),
+            BootstrapOutcome::Ran
+        );
+        assert_eq!(ran, commands);
+        assert_eq!(
+            run_bootstrap(root, &commands, |_| {}).unwrap(),
+            BootstrapOutcome::Cached
+        );
+        assert_eq!(installs().lines().count(), 1);
+
+        fs::write(root.join("Cargo.lock"), "v2").unwrap();
+        assert_eq!(
+            run_bootstrap(root, &commands, |_| {}).unwrap(),
+            BootstrapOutcome::Ran
+        );
+        assert_eq!(installs().lines().count(), 2);
+
+        let log = fs::read_to_string(get_bootstrap_log_path(root)).unwrap();
+        assert!(log.contains("$ echo installed"));
+    }
+
+    #[test]
+    fn test_failed_bootstrap_is_not_stamped() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let root = tmp.path();
+        let commands = vec!["echo oops >&2; exit 3".to_string()];
+
+        let err = run_bootstrap(root, &commands, |_| {}).unwrap_err();
+        assert!(err.to_string().contains("failed"));
+        assert!(read_stamp(root).is_none());
+        let log = fs::read_to_string(get_bootstrap_log_path(root)).unwrap();
+        assert!(log.contains("oops"));
+    }
+}
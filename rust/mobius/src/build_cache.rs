@@ -0,0 +1,403 @@
+//! Shared build caches for agent worktrees.
+//!
+//! With `execution.build_cache.enabled`, the build outputs and package caches
+//! of the project's build systems live in one directory shared by every
+//! worktree, so parallel loops and pool workers start from a warm build
+//! instead of each compiling and downloading everything again. Cargo's
+//! `target` becomes a symlink in the worktree; package-manager caches are
+//! pointed at through environment variables, which also warms sandboxed
+//! agents whose containers have no home directory. `node_modules` is only
+//! linked with `share_node_modules`, since worktrees on different lockfiles
+//! or installing at the same time would corrupt a shared copy.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use tracing::warn;
+
+use crate::attribution::shell_quote;
+use crate::types::config::BuildCacheConfig;
+use crate::types::enums::BuildSystem;
+
+/// Default cache directory, created in the worktree base
+const CACHE_DIR_NAME: &str = ".build-cache";
+
+/// Installed packages, shared only with `share_node_modules`
+const NODE_MODULES: &str = "node_modules";
+
+/// One cache a build system can share between worktrees
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SharedCache {
+    /// Worktree directory replaced by a link into the shared directory
+    Link(&'static str),
+    /// Variable pointing a tool at a directory in the shared directory
+    Env {
+        var: &'static str,
+        name: &'static str,
+    },
+}
+
+impl SharedCache {
+    /// Name used for the shared directory and in `exclude`.
+    fn name(self) -> &'static str {
+        match self {
+            SharedCache::Link(path) => path,
+            SharedCache::Env { name, .. } => name,
+        }
+    }
+}
+
+/// Caches worth sharing for `build_system`.
+fn caches_for(build_system: BuildSystem) -> &'static [SharedCache] {
+    match build_system {
+        BuildSystem::Cargo => &[SharedCache::Link("target")],
+        BuildSystem::Npm => &[
+            SharedCache::Link(NODE_MODULES),
+            SharedCache::Env {
+                var: "npm_config_cache",
+                name: "npm",
+            },
+        ],
+        BuildSystem::Yarn => &[
+            SharedCache::Link(NODE_MODULES),
+            SharedCache::Env {
+                var: "YARN_CACHE_FOLDER",
+                name: "yarn",
+            },
+        ],
+        BuildSystem::Pnpm => &[
+            SharedCache::Link(NODE_MODULES),
+            SharedCache::Env {
+                var: "npm_config_store_dir",
+                name: "pnpm-store",
+            },
+        ],
+        BuildSystem::Pip => &[SharedCache::Env {
+            var: "PIP_CACHE_DIR",
+            name: "pip",
+        }],
+        BuildSystem::Poetry => &[SharedCache::Env {
+            var: "POETRY_CACHE_DIR",
+            name: "poetry",
+        }],
+        BuildSystem::Gradle
+        | BuildSystem::Xcodebuild
+        | BuildSystem::Just
+        | BuildSystem::Make
+        | BuildSystem::Unknown => &[],
+    }
+}
+
+/// Names `execution.build_cache.exclude` accepts.
+pub fn cache_names() -> Vec<&'static str> {
+    let mut names: Vec<&str> = [
+        BuildSystem::Cargo,
+        BuildSystem::Npm,
+        BuildSystem::Yarn,
+        BuildSystem::Pnpm,
+        BuildSystem::Pip,
+        BuildSystem::Poetry,
+    ]
+    .into_iter()
+    .flat_map(|system| caches_for(system).iter().map(|cache| cache.name()))
+    .collect();
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Build systems with shareable caches, detected from `root`'s manifests.
+///
+/// Unlike project detection this reports every build system present, so a
+/// Cargo workspace with a pnpm front end shares both sets of caches.
+pub fn detect_cache_build_systems(root: &Path) -> Vec<BuildSystem> {
+    let mut systems = Vec::new();
+    if root.join("Cargo.toml").exists() {
+        systems.push(BuildSystem::Cargo);
+    }
+    if root.join("pnpm-lock.yaml").exists() {
+        systems.push(BuildSystem::Pnpm);
+    } else if root.join("yarn.lock").exists() {
+        systems.push(BuildSystem::Yarn);
+    } else if root.join("package.json").exists() {
+        systems.push(BuildSystem::Npm);
+    }
+    let pyproject = fs::read_to_string(root.join("pyproject.toml")).ok();
+    if pyproject
+        .as_deref()
+        .is_some_and(|content| content.contains("[tool.poetry]"))
+    {
+        systems.push(BuildSystem::Poetry);
+    } else if pyproject.is_some() || root.join("requirements.txt").exists() {
+        systems.push(BuildSystem::Pip);
+    }
+    systems
+}
+
+/// The shared caches one worktree uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachePlan {
+    /// Directory holding every shared cache
+    pub root: PathBuf,
+    /// Worktree-relative directories replaced by links into `root`
+    pub links: Vec<&'static str>,
+    /// Variables pointing tools at directories in `root`
+    pub env: Vec<(&'static str, PathBuf)>,
+}
+
+/// Work out which caches `worktree` shares, or `None` when sharing is off.
+pub fn plan_caches(config: &BuildCacheConfig, worktree: &Path) -> Option<CachePlan> {
+    if !config.enabled {
+        return None;
+    }
+    let root = match &config.dir {
+        Some(dir) => expand_home(dir),
+        None => worktree.parent()?.join(CACHE_DIR_NAME),
+    };
+    let systems = if config.build_systems.is_empty() {
+        detect_cache_build_systems(worktree)
+    } else {
+        config.build_systems.clone()
+    };
+
+    let mut plan = CachePlan {
+        root,
+        links: Vec::new(),
+        env: Vec::new(),
+    };
+    for &cache in systems.iter().flat_map(|&system| caches_for(system)) {
+        if config.exclude.iter().any(|name| name == cache.name())
+            || (cache == SharedCache::Link(NODE_MODULES) && !config.share_node_modules)
+        {
+            continue;
+        }
+        match cache {
+            SharedCache::Link(path) => {
+                if !plan.links.contains(&path) {
+                    plan.links.push(path);
+                }
+            }
+            SharedCache::Env { var, name } => {
+                if !plan.env.iter().any(|(existing, _)| *existing == var) {
+                    plan.env.push((var, plan.root.join(name)));
+                }
+            }
+        }
+    }
+    Some(plan)
+}
+
+/// Link the plan's directories from `worktree` into the shared directory.
+///
+/// A worktree that already has its own copy of a directory keeps it. The
+/// links are added to the repository's `info/exclude` so agents never commit
+/// them. Returns the directories that are linked.
+pub fn link_caches(plan: &CachePlan, worktree: &Path) -> Result<Vec<&'static str>> {
+    let mut linked = Vec::new();
+    for &link in &plan.links {
+        let shared = plan.root.join(link);
+        fs::create_dir_all(&shared)
+            .with_context(|| format!("Failed to create shared cache {}", shared.display()))?;
+        let path = worktree.join(link);
+        match fs::symlink_metadata(&path) {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                linked.push(link);
+                continue;
+            }
+            Ok(_) => {
+                warn!(
+                    "{} already exists in {}; it is not shared",
+                    link,
+                    worktree.display()
+                );
+                continue;
+            }
+            Err(_) => {}
+        }
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&shared, &path).with_context(|| {
+            format!("Failed to link {} to {}", path.display(), shared.display())
+        })?;
+        #[cfg(not(unix))]
+        {
+            warn!("Shared build caches need symlinks, which this platform lacks");
+            break;
+        }
+        linked.push(link);
+    }
+    if !linked.is_empty() {
+        exclude_from_git(worktree, &linked)?;
+    }
+    Ok(linked)
+}
+
+/// Prefix `command` with the plan's cache variables, creating their
+/// directories so tools don't fall back to their defaults.
+pub fn with_cache_env(command: &str, plan: &CachePlan) -> String {
+    if plan.env.is_empty() {
+        return command.to_string();
+    }
+    let assignments: Vec<String> = plan
+        .env
+        .iter()
+        .map(|(var, dir)| {
+            let _ = fs::create_dir_all(dir);
+            format!("{}={}", var, shell_quote(&dir.display().to_string()))
+        })
+        .collect();
+    format!("export {} && {}", assignments.join(" "), command)
+}
+
+/// Add `/<link>` patterns for `links` to the worktree's `info/exclude`.
+///
+/// `target/`-style ignore rules only match directories, not the symlinks
+/// that replace them.
+fn exclude_from_git(worktree: &Path, links: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .current_dir(worktree)
+        .args(["rev-parse", "--git-path", "info/exclude"])
+        .output()
+        .context("Failed to run git rev-parse --git-path")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git rev-parse --git-path failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let exclude = worktree.join(String::from_utf8_lossy(&output.stdout).trim());
+
+    let existing = fs::read_to_string(&exclude).unwrap_or_default();
+    let missing: Vec<String> = links
+        .iter()
+        .map(|link| format!("/{}", link))
+        .filter(|pattern| !existing.lines().any(|line| line.trim() == pattern))
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    if let Some(parent) = exclude.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&exclude)
+        .with_context(|| format!("Failed to open {}", exclude.display()))?;
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        writeln!(file)?;
+    }
+    writeln!(file, "# Shared build caches (mobius)")?;
+    for pattern in missing {
+        writeln!(file, "{}", pattern)?;
+    }
+    Ok(())
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> BuildCacheConfig {
+        BuildCacheConfig {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_plan_caches_for_detected_build_systems() {
+        let tmp = tempfile::tempdir().unwrap();
+        let worktree = tmp.path().join("MOB-1");
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(worktree.join("Cargo.toml"), "[package]\n").unwrap();
+        fs::write(worktree.join("package.json"), "{}").unwrap();
+        fs::write(worktree.join("pnpm-lock.yaml"), "").unwrap();
+
+        assert_eq!(plan_caches(&BuildCacheConfig::default(), &worktree), None);
+
+        let plan = plan_caches(&enabled(), &worktree).unwrap();
+        let root = tmp.path().join(".build-cache");
+        assert_eq!(plan.links, vec!["target"]);
+        assert_eq!(
+            plan.env,
+            vec![("npm_config_store_dir", root.join("pnpm-store"))]
+        );
+        assert_eq!(plan.root, root);
+
+        let shared = BuildCacheConfig {
+            share_node_modules: true,
+            ..enabled()
+        };
+        let plan = plan_caches(&shared, &worktree).unwrap();
+        assert_eq!(plan.links, vec!["target", "node_modules"]);
+
+        let config = BuildCacheConfig {
+            build_systems: vec![BuildSystem::Pip],
+            exclude: vec!["target".to_string()],
+            ..enabled()
+        };
+        let plan = plan_caches(&config, &worktree).unwrap();
+        assert!(plan.links.is_empty());
+        assert_eq!(plan.env, vec![("PIP_CACHE_DIR", root.join("pip"))]);
+    }
+
+    #[test]
+    fn test_link_caches_links_and_excludes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let worktree = tmp.path().join("MOB-1");
+        fs::create_dir_all(worktree.join("node_modules")).unwrap();
+        let status = Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(&worktree)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let plan = CachePlan {
+            root: tmp.path().join("cache"),
+            links: vec!["target", "node_modules"],
+            env: vec![],
+        };
+        // An existing directory is kept
+        assert_eq!(link_caches(&plan, &worktree).unwrap(), vec!["target"]);
+        assert_eq!(
+            fs::read_link(worktree.join("target")).unwrap(),
+            plan.root.join("target")
+        );
+        assert!(!fs::symlink_metadata(worktree.join("node_modules"))
+            .unwrap()
+            .file_type()
+            .is_symlink());
+
+        // Linking again is a no-op, and the pattern is written once
+        link_caches(&plan, &worktree).unwrap();
+        let exclude = fs::read_to_string(worktree.join(".git/info/exclude")).unwrap();
+        assert_eq!(exclude.matches("/target\n").count(), 1);
+    }
+
+    #[test]
+    fn test_with_cache_env() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().join("pip");
+        let plan = CachePlan {
+            root: tmp.path().to_path_buf(),
+            links: vec![],
+            env: vec![("PIP_CACHE_DIR", dir.clone())],
+        };
+        assert_eq!(
+            with_cache_env("claude", &plan),
+            format!("export PIP_CACHE_DIR='{}' && claude", dir.display())
+        );
+        assert!(dir.is_dir());
+    }
+}
@@ -0,0 +1,290 @@
+//! Loop checkpointing for resumable execution.
+//!
+//! After each dispatch and each settled iteration the loop persists its
+//! progress to `.mobius/issues/{id}/execution/checkpoint.json`, so
+//! `mobius resume` can pick up where a crashed or interrupted loop stopped
+//! instead of re-planning from scratch.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::context::{atomic_write_json, get_checkpoint_path};
+use crate::error::MobiusError;
+use crate::types::enums::TaskStatus;
+use crate::types::task_graph::{SubTask, TaskGraph};
+
+/// Persisted loop iteration state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    pub parent_id: String,
+    /// Last iteration that dispatched a batch
+    pub iteration: u32,
+    pub branch_name: String,
+    pub worktree_path: String,
+    /// Identifiers dispatched in the most recent batch
+    #[serde(default)]
+    pub batch: Vec<String>,
+    /// Whether the results of `batch` were recorded before the checkpoint was written
+    #[serde(default)]
+    pub batch_settled: bool,
+    /// Identifiers verified as done by this loop
+    #[serde(default)]
+    pub completed: Vec<String>,
+    /// Identifiers queued for retry in the next iteration
+    #[serde(default)]
+    pub retry_queue: Vec<String>,
+    /// Identifier → worktree path each task was dispatched to
+    #[serde(default)]
+    pub worktrees: BTreeMap<String, String>,
+    pub updated_at: String,
+}
+
+impl Checkpoint {
+    pub fn new(parent_id: &str, branch_name: &str, worktree_path: &str) -> Self {
+        Self {
+            parent_id: parent_id.to_string(),
+            iteration: 0,
+            branch_name: branch_name.to_string(),
+            worktree_path: worktree_path.to_string(),
+            batch: vec![],
+            batch_settled: true,
+            completed: vec![],
+            retry_queue: vec![],
+            worktrees: BTreeMap::new(),
+            updated_at: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Record a batch about to be executed in `iteration`.
+    pub fn record_dispatch(&mut self, iteration: u32, tasks: &[SubTask]) {
+        self.iteration = iteration;
+        self.batch = tasks.iter().map(|t| t.identifier.clone()).collect();
+        self.batch_settled = false;
+        for task in tasks {
+            self.worktrees
+                .insert(task.identifier.clone(), self.worktree_path.clone());
+        }
+        self.retry_queue.retain(|id| !self.batch.contains(id));
+    }
+
+    /// Record the outcome of the current batch.
+    pub fn record_results(&mut self, completed: &[String], retry_queue: &[SubTask]) {
+        for identifier in completed {
+            if !self.completed.contains(identifier) {
+                self.completed.push(identifier.clone());
+            }
+        }
+        self.retry_queue = retry_queue.iter().map(|t| t.identifier.clone()).collect();
+        self.batch_settled = true;
+    }
+
+    /// Identifiers to schedule first when resuming: queued retries plus any
+    /// task from an interrupted batch that never reported back.
+    pub fn pending_task_identifiers(&self) -> Vec<String> {
+        let mut pending = self.retry_queue.clone();
+        if !self.batch_settled {
+            for identifier in &self.batch {
+                if !self.completed.contains(identifier) && !pending.contains(identifier) {
+                    pending.push(identifier.clone());
+                }
+            }
+        }
+        pending
+    }
+
+    /// Resolve `pending_task_identifiers` against `graph`, skipping tasks
+    /// that no longer exist or are already done.
+    pub fn pending_tasks(&self, graph: &TaskGraph) -> Vec<SubTask> {
+        self.pending_task_identifiers()
+            .iter()
+            .filter_map(|identifier| {
+                graph
+                    .tasks
+                    .values()
+                    .find(|t| &t.identifier == identifier)
+                    .filter(|t| t.status != TaskStatus::Done)
+                    .cloned()
+            })
+            .collect()
+    }
+}
+
+/// Read the checkpoint for a parent issue, if one exists.
+pub fn read_checkpoint(parent_id: &str) -> Option<Checkpoint> {
+    read_checkpoint_from(&get_checkpoint_path(parent_id))
+}
+
+/// Like `read_checkpoint`, but a checkpoint file that cannot be parsed is an
+/// error rather than "no checkpoint", so `mobius resume` doesn't mask it.
+pub fn load_checkpoint(parent_id: &str) -> Result<Option<Checkpoint>> {
+    load_checkpoint_from(&get_checkpoint_path(parent_id))
+}
+
+/// Persist a checkpoint, stamping `updated_at`.
+pub fn write_checkpoint(checkpoint: &mut Checkpoint) -> Result<()> {
+    write_checkpoint_to(&get_checkpoint_path(&checkpoint.parent_id), checkpoint)
+}
+
+/// Delete the checkpoint for a parent issue. Returns true if a file was removed.
+pub fn delete_checkpoint(parent_id: &str) -> bool {
+    fs::remove_file(get_checkpoint_path(parent_id)).is_ok()
+}
+
+fn read_checkpoint_from(path: &Path) -> Option<Checkpoint> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn load_checkpoint_from(path: &Path) -> Result<Option<Checkpoint>> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(None);
+    };
+    serde_json::from_str(&content).map(Some).map_err(|e| {
+        MobiusError::StateCorruption {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        }
+        .into()
+    })
+}
+
+fn write_checkpoint_to(path: &Path, checkpoint: &mut Checkpoint) -> Result<()> {
+    checkpoint.updated_at = Utc::now().to_rfc3339();
+    atomic_write_json(path, checkpoint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::TaskType;
+    use crate::types::task_graph::build_task_graph;
+    use crate::types::task_graph::{LinearIssue, Relations};
+
+    fn make_task(identifier: &str) -> SubTask {
+        SubTask {
+            id: format!("id-{identifier}"),
+            identifier: identifier.to_string(),
+            title: format!("Task {identifier}"),
+            status: TaskStatus::Ready,
+            blocked_by: vec![],
+            blocks: vec![],
+            git_branch_name: String::new(),
+            scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
+        }
+    }
+
+    fn make_issue(identifier: &str, status: &str) -> LinearIssue {
+        LinearIssue {
+            id: format!("id-{identifier}"),
+            identifier: identifier.to_string(),
+            title: format!("Task {identifier}"),
+            status: status.to_string(),
+            git_branch_name: String::new(),
+            relations: Some(Relations {
+                blocked_by: vec![],
+                blocks: vec![],
+            }),
+            scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
+        }
+    }
+
+    #[test]
+    fn test_interrupted_batch_is_pending() {
+        let mut cp = Checkpoint::new("MOB-1", "feat/mob-1", "/tmp/wt");
+        cp.record_dispatch(2, &[make_task("MOB-2"), make_task("MOB-3")]);
+
+        assert!(!cp.batch_settled);
+        assert_eq!(cp.pending_task_identifiers(), vec!["MOB-2", "MOB-3"]);
+        assert_eq!(
+            cp.worktrees.get("MOB-2").map(String::as_str),
+            Some("/tmp/wt")
+        );
+    }
+
+    #[test]
+    fn test_settled_batch_only_keeps_retries() {
+        let mut cp = Checkpoint::new("MOB-1", "feat/mob-1", "/tmp/wt");
+        cp.record_dispatch(1, &[make_task("MOB-2"), make_task("MOB-3")]);
+        cp.record_results(&["MOB-2".to_string()], &[make_task("MOB-3")]);
+
+        assert!(cp.batch_settled);
+        assert_eq!(cp.completed, vec!["MOB-2"]);
+        assert_eq!(cp.pending_task_identifiers(), vec!["MOB-3"]);
+    }
+
+    #[test]
+    fn test_pending_tasks_skips_done_and_missing() {
+        let mut cp = Checkpoint::new("MOB-1", "feat/mob-1", "/tmp/wt");
+        cp.record_dispatch(
+            1,
+            &[make_task("MOB-2"), make_task("MOB-3"), make_task("MOB-9")],
+        );
+        let graph = build_task_graph(
+            "parent",
+            "MOB-1",
+            &[make_issue("MOB-2", "Done"), make_issue("MOB-3", "Todo")],
+        );
+
+        let pending: Vec<_> = cp
+            .pending_tasks(&graph)
+            .into_iter()
+            .map(|t| t.identifier)
+            .collect();
+        assert_eq!(pending, vec!["MOB-3"]);
+    }
+
+    #[test]
+    fn test_checkpoint_roundtrip() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("execution").join("checkpoint.json");
+
+        let mut cp = Checkpoint::new("MOB-1", "feat/mob-1", "/tmp/wt");
+        cp.record_dispatch(3, &[make_task("MOB-4")]);
+        write_checkpoint_to(&path, &mut cp).unwrap();
+
+        let loaded = read_checkpoint_from(&path).unwrap();
+        assert_eq!(loaded, cp);
+        assert_eq!(loaded.iteration, 3);
+
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(json.contains("\"batchSettled\": false"));
+    }
+
+    #[test]
+    fn test_read_checkpoint_missing_or_corrupt() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("checkpoint.json");
+        assert!(read_checkpoint_from(&path).is_none());
+
+        fs::write(&path, "{not json").unwrap();
+        assert!(read_checkpoint_from(&path).is_none());
+    }
+
+    #[test]
+    fn test_load_checkpoint_reports_corruption() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("checkpoint.json");
+        assert!(load_checkpoint_from(&path).unwrap().is_none());
+
+        fs::write(&path, "{not json").unwrap();
+        let err = load_checkpoint_from(&path).unwrap_err();
+        assert_eq!(
+            crate::error::classify(&err),
+            crate::error::ErrorKind::StateCorruption
+        );
+    }
+}
@@ -0,0 +1,372 @@
+//! File churn across a loop run.
+//!
+//! Aggregates the files each sub-task modified into a run-level report of
+//! which files were touched by more than one task. Hotspots explain merge
+//! conflicts after the fact and are a scheduling signal for keeping
+//! overlapping tasks out of the same batch.
+//!
+//! Per-task file lists come from commits on the loop branch that mention the
+//! task identifier, plus whatever agents reported through result files and
+//! the iteration log.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::completion::{read_agent_result, result_file_for};
+use crate::context::{atomic_write_json, get_churn_report_path, get_results_directory_path};
+use crate::local_state::read_iteration_log;
+
+/// Task identifier → files it modified
+pub type TaskFiles = BTreeMap<String, BTreeSet<String>>;
+
+/// Churn for a single file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileChurn {
+    pub path: String,
+    /// Tasks that modified the file, sorted
+    pub tasks: Vec<String>,
+}
+
+impl FileChurn {
+    /// True when more than one task modified the file.
+    pub fn is_hotspot(&self) -> bool {
+        self.tasks.len() > 1
+    }
+}
+
+/// Run-level churn report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChurnReport {
+    pub parent_id: String,
+    pub generated_at: String,
+    /// Files ordered by task count (descending), then path
+    pub files: Vec<FileChurn>,
+}
+
+impl ChurnReport {
+    /// Files touched by more than one task.
+    pub fn hotspots(&self) -> impl Iterator<Item = &FileChurn> {
+        self.files.iter().filter(|f| f.is_hotspot())
+    }
+
+    /// Tasks that share at least one file with `identifier`.
+    pub fn overlapping_tasks(&self, identifier: &str) -> BTreeSet<String> {
+        self.hotspots()
+            .filter(|f| f.tasks.iter().any(|t| t == identifier))
+            .flat_map(|f| f.tasks.iter().cloned())
+            .filter(|t| t != identifier)
+            .collect()
+    }
+}
+
+/// Build a churn report from per-task file lists.
+pub fn build_churn_report(parent_id: &str, task_files: &TaskFiles) -> ChurnReport {
+    let mut by_file: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for (task, files) in task_files {
+        for file in files {
+            by_file.entry(file).or_default().insert(task);
+        }
+    }
+
+    let mut files: Vec<FileChurn> = by_file
+        .into_iter()
+        .map(|(path, tasks)| FileChurn {
+            path: path.to_string(),
+            tasks: tasks.into_iter().map(str::to_string).collect(),
+        })
+        .collect();
+    files.sort_by(|a, b| b.tasks.len().cmp(&a.tasks.len()).then(a.path.cmp(&b.path)));
+
+    ChurnReport {
+        parent_id: parent_id.to_string(),
+        generated_at: Utc::now().to_rfc3339(),
+        files,
+    }
+}
+
+/// Merge `other` into `into`.
+pub fn merge_task_files(into: &mut TaskFiles, other: TaskFiles) {
+    for (task, files) in other {
+        into.entry(task).or_default().extend(files);
+    }
+}
+
+/// Attribute files changed in `base..head` to the task identifiers named in
+/// each commit message. Commits that mention no known identifier are ignored.
+pub fn collect_task_files_from_git(
+    repo_path: &Path,
+    base: &str,
+    head: &str,
+    identifiers: &[String],
+) -> Result<TaskFiles> {
+    let output = Command::new("git")
+        .current_dir(repo_path)
+        .args([
+            "log",
+            "--name-only",
+            "--format=%x1e%s%n%b%x1f",
+            &format!("{}..{}", base, head),
+        ])
+        .output()
+        .context("failed to run git log")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git log {}..{} failed: {}",
+            base,
+            head,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(parse_git_log(
+        &String::from_utf8_lossy(&output.stdout),
+        identifiers,
+    ))
+}
+
+/// Collect files agents reported via result files and the iteration log.
+pub fn collect_task_files_from_reports(parent_id: &str, identifiers: &[String]) -> TaskFiles {
+    let mut task_files = TaskFiles::new();
+
+    for entry in read_iteration_log(parent_id) {
+        if let Some(files) = entry.files_modified {
+            task_files
+                .entry(entry.subtask_id)
+                .or_default()
+                .extend(files);
+        }
+    }
+
+    let result_dir = get_results_directory_path(parent_id);
+    for identifier in identifiers {
+        if let Some(result) = read_agent_result(&result_file_for(&result_dir, identifier)) {
+            if !result.files.is_empty() {
+                task_files
+                    .entry(identifier.clone())
+                    .or_default()
+                    .extend(result.files);
+            }
+        }
+    }
+
+    task_files
+}
+
+/// Collect per-task files from `base..head` in `repo_path` and from agent
+/// reports, then build and persist the churn report.
+///
+/// Git failures (e.g. the branch no longer exists) are logged and the report
+/// falls back to agent-reported files.
+pub fn generate_churn_report(
+    parent_id: &str,
+    identifiers: &[String],
+    repo_path: &Path,
+    base: &str,
+    head: &str,
+) -> Result<ChurnReport> {
+    let mut task_files = collect_task_files_from_reports(parent_id, identifiers);
+    match collect_task_files_from_git(repo_path, base, head, identifiers) {
+        Ok(from_git) => merge_task_files(&mut task_files, from_git),
+        Err(e) => tracing::warn!("Churn: skipping git history for {}: {}", parent_id, e),
+    }
+
+    let report = build_churn_report(parent_id, &task_files);
+    write_churn_report(&report)?;
+    Ok(report)
+}
+
+/// Read a previously written churn report.
+pub fn read_churn_report(parent_id: &str) -> Option<ChurnReport> {
+    let content = fs::read_to_string(get_churn_report_path(parent_id)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Persist a churn report to `.mobius/issues/{id}/execution/churn.json`.
+pub fn write_churn_report(report: &ChurnReport) -> Result<()> {
+    atomic_write_json(&get_churn_report_path(&report.parent_id), report)
+}
+
+/// Render the report as a text heatmap, one row per file.
+///
+/// Shows at most `limit` files; single-task files are only listed when
+/// there is room after the hotspots.
+pub fn render_churn_heatmap(report: &ChurnReport, limit: usize) -> String {
+    if report.files.is_empty() {
+        return format!("No file changes recorded for {}.\n", report.parent_id);
+    }
+
+    let hotspot_count = report.hotspots().count();
+    let max_tasks = report.files.first().map(|f| f.tasks.len()).unwrap_or(1);
+    let shown: Vec<&FileChurn> = report.files.iter().take(limit).collect();
+    let path_width = shown.iter().map(|f| f.path.len()).max().unwrap_or(0);
+
+    let mut out = format!(
+        "File churn for {} ({} of {} files touched by multiple tasks)\n\n",
+        report.parent_id,
+        hotspot_count,
+        report.files.len()
+    );
+    for file in &shown {
+        let count = file.tasks.len();
+        out.push_str(&format!(
+            "  {:<bar$}  {} {:<5}  {:<path$}  {}\n",
+            "█".repeat(count),
+            count,
+            if count == 1 { "task" } else { "tasks" },
+            file.path,
+            file.tasks.join(", "),
+            bar = max_tasks,
+            path = path_width,
+        ));
+    }
+    if report.files.len() > shown.len() {
+        out.push_str(&format!(
+            "  … {} more file(s)\n",
+            report.files.len() - shown.len()
+        ));
+    }
+    out
+}
+
+fn parse_git_log(log: &str, identifiers: &[String]) -> TaskFiles {
+    let patterns: Vec<(&String, Regex)> = identifiers
+        .iter()
+        .filter_map(|id| {
+            Regex::new(&format!(r"(?i)\b{}\b", regex::escape(id)))
+                .ok()
+                .map(|re| (id, re))
+        })
+        .collect();
+
+    let mut task_files = TaskFiles::new();
+    for record in log.split('\x1e').filter(|r| !r.trim().is_empty()) {
+        let (message, files) = record.split_once('\x1f').unwrap_or((record, ""));
+        let files: Vec<String> = files
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        for (id, re) in &patterns {
+            if re.is_match(message) {
+                task_files
+                    .entry((*id).clone())
+                    .or_default()
+                    .extend(files.iter().cloned());
+            }
+        }
+    }
+    task_files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task_files(entries: &[(&str, &[&str])]) -> TaskFiles {
+        entries
+            .iter()
+            .map(|(task, files)| {
+                (
+                    task.to_string(),
+                    files.iter().map(|f| f.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_build_churn_report_orders_hotspots_first() {
+        let report = build_churn_report(
+            "MOB-100",
+            &task_files(&[
+                ("MOB-101", &["src/a.rs", "src/b.rs"]),
+                ("MOB-102", &["src/b.rs", "src/c.rs"]),
+                ("MOB-103", &["src/b.rs", "src/a.rs"]),
+            ]),
+        );
+
+        let paths: Vec<_> = report.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/b.rs", "src/a.rs", "src/c.rs"]);
+        assert_eq!(report.files[0].tasks, vec!["MOB-101", "MOB-102", "MOB-103"]);
+        assert_eq!(report.hotspots().count(), 2);
+        assert_eq!(
+            report.overlapping_tasks("MOB-102"),
+            ["MOB-101", "MOB-103"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect()
+        );
+    }
+
+    #[test]
+    fn test_parse_git_log_attributes_by_identifier() {
+        let log = "\x1efeat(MOB-101): add schema\n\x1f\nsrc/a.rs\nsrc/b.rs\n\
+                   \x1efix: follow-up for mob-102 and MOB-101\n\x1f\nsrc/b.rs\n\
+                   \x1echore: bump deps\n\x1f\nCargo.lock\n\
+                   \x1efeat(MOB-1010): unrelated\n\x1f\nsrc/z.rs\n";
+        let ids = vec!["MOB-101".to_string(), "MOB-102".to_string()];
+
+        let files = parse_git_log(log, &ids);
+        assert_eq!(
+            files["MOB-101"].iter().collect::<Vec<_>>(),
+            vec!["src/a.rs", "src/b.rs"]
+        );
+        assert_eq!(
+            files["MOB-102"].iter().collect::<Vec<_>>(),
+            vec!["src/b.rs"]
+        );
+        assert_eq!(files.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_task_files() {
+        let mut into = task_files(&[("MOB-101", &["src/a.rs"])]);
+        merge_task_files(
+            &mut into,
+            task_files(&[("MOB-101", &["src/b.rs"]), ("MOB-102", &["src/a.rs"])]),
+        );
+        assert_eq!(into["MOB-101"].len(), 2);
+        assert_eq!(into["MOB-102"].len(), 1);
+    }
+
+    #[test]
+    fn test_render_churn_heatmap() {
+        let report = build_churn_report(
+            "MOB-100",
+            &task_files(&[
+                ("MOB-101", &["src/lib.rs", "README.md"]),
+                ("MOB-102", &["src/lib.rs"]),
+            ]),
+        );
+
+        let text = render_churn_heatmap(&report, 10);
+        assert!(text.starts_with("File churn for MOB-100 (1 of 2 files touched by multiple tasks)"));
+        assert!(text.contains("██  2 tasks  src/lib.rs  MOB-101, MOB-102"));
+        assert!(text.contains("█   1 task   README.md   MOB-101"));
+
+        let truncated = render_churn_heatmap(&report, 1);
+        assert!(truncated.contains("… 1 more file(s)"));
+        assert!(!truncated.contains("README.md"));
+    }
+
+    #[test]
+    fn test_render_churn_heatmap_empty() {
+        let report = build_churn_report("MOB-100", &TaskFiles::new());
+        assert_eq!(
+            render_churn_heatmap(&report, 10),
+            "No file changes recorded for MOB-100.\n"
+        );
+    }
+}
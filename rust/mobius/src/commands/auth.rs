@@ -0,0 +1,175 @@
+//! Auth command - Keep tracker tokens in the OS keychain
+//!
+//! `login` stores a backend's token under the environment variable name it
+//! replaces, so the tracker clients find it whenever that variable is unset.
+
+use std::io::{BufRead, IsTerminal};
+
+use anyhow::Context;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::output::OutputFormat;
+use crate::secrets::{self, SecretSource};
+use crate::types::enums::Backend;
+
+/// Trackers whose credentials `mobius auth` manages
+const TRACKERS: [Backend; 4] = [
+    Backend::Linear,
+    Backend::Jira,
+    Backend::Github,
+    Backend::Gitlab,
+];
+
+/// Where one backend's token comes from
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthStatus {
+    pub backend: Backend,
+    /// Variable the token was found under
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub var: Option<String>,
+    /// `env` or `keychain`; unset when no token was found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<SecretSource>,
+}
+
+/// `mobius auth status` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthReport {
+    pub backends: Vec<AuthStatus>,
+}
+
+pub fn run_login(backend: &str, output: OutputFormat) -> anyhow::Result<()> {
+    let backend = parse_tracker(backend)?;
+    let token_var = secrets::token_vars(backend)[0];
+
+    if backend == Backend::Jira {
+        let email = prompt("Jira account email", false)?;
+        secrets::store_secret("JIRA_EMAIL", &email)?;
+    }
+    let token = prompt(&format!("{} API token", backend), true)?;
+    secrets::store_secret(token_var, &token)?;
+
+    output.note(format!("✓ Stored {} in the OS keychain", token_var).green());
+    if let Some(found) = secrets::find_secret(secrets::token_vars(backend)) {
+        if found.source == SecretSource::Env {
+            output.note(
+                format!(
+                    "⚠ {} is set in the environment and takes precedence over the keychain",
+                    found.var
+                )
+                .yellow(),
+            );
+        }
+    }
+    Ok(())
+}
+
+pub fn run_logout(backend: &str, output: OutputFormat) -> anyhow::Result<()> {
+    let backend = parse_tracker(backend)?;
+    let mut removed = Vec::new();
+    for &var in secrets::token_vars(backend) {
+        if secrets::delete_secret(var)? {
+            removed.push(var);
+        }
+    }
+    if backend == Backend::Jira && secrets::delete_secret("JIRA_EMAIL")? {
+        removed.push("JIRA_EMAIL");
+    }
+
+    if removed.is_empty() {
+        output.note(format!("No {} credentials in the OS keychain", backend).yellow());
+    } else {
+        output.note(format!("✓ Removed {} from the OS keychain", removed.join(", ")).green());
+    }
+    Ok(())
+}
+
+pub fn run_status(output: OutputFormat) -> anyhow::Result<()> {
+    let backends = TRACKERS
+        .iter()
+        .map(|&backend| {
+            let found = secrets::find_secret(secrets::token_vars(backend));
+            AuthStatus {
+                backend,
+                var: found.as_ref().map(|f| f.var.to_string()),
+                source: found.map(|f| f.source),
+            }
+        })
+        .collect();
+    let report = AuthReport { backends };
+    output.emit(&report, |report| {
+        for status in &report.backends {
+            let name = format!("{:<8}", status.backend.to_string());
+            match (&status.var, status.source) {
+                (Some(var), Some(SecretSource::Env)) => {
+                    println!(
+                        "{} {} {}",
+                        name,
+                        "✓".green(),
+                        format!("env {}", var).dimmed()
+                    )
+                }
+                (Some(var), Some(SecretSource::Keychain)) => println!(
+                    "{} {} {}",
+                    name,
+                    "✓".green(),
+                    format!("keychain {}", var).dimmed()
+                ),
+                _ => println!(
+                    "{} {} {}",
+                    name,
+                    "✗".red(),
+                    format!("not set (mobius auth login {})", status.backend).dimmed()
+                ),
+            }
+        }
+    })
+}
+
+/// A tracker backend name; `local` has no credentials.
+fn parse_tracker(name: &str) -> anyhow::Result<Backend> {
+    let backend: Backend = name.parse().map_err(|e: String| anyhow::anyhow!(e))?;
+    if backend == Backend::Local {
+        anyhow::bail!("The local backend needs no credentials");
+    }
+    Ok(backend)
+}
+
+/// Ask for a value, or read one line from stdin when it isn't a terminal so
+/// tokens can be piped in (`pbpaste | mobius auth login github`).
+fn prompt(label: &str, secret: bool) -> anyhow::Result<String> {
+    let value = if std::io::stdin().is_terminal() {
+        if secret {
+            dialoguer::Password::new().with_prompt(label).interact()?
+        } else {
+            dialoguer::Input::new().with_prompt(label).interact_text()?
+        }
+    } else {
+        let mut line = String::new();
+        std::io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .with_context(|| format!("Failed to read {} from stdin", label))?;
+        line
+    };
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        anyhow::bail!("{} cannot be empty", label);
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tracker_rejects_local() {
+        assert_eq!(parse_tracker("GitHub").unwrap(), Backend::Github);
+        assert!(parse_tracker("local").is_err());
+        assert!(parse_tracker("bitbucket").is_err());
+    }
+}
@@ -0,0 +1,369 @@
+//! Backend command - Discover workflow states and map them by ID
+
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::commands::output::OutputFormat;
+use crate::config::loader::{config_exists, read_config_file, read_config_with_env, write_config};
+use crate::config::paths::resolve_paths;
+use crate::jira::JiraClient;
+use crate::linear::LinearClient;
+use crate::types::config::{LoopConfig, StatusMapping};
+use crate::types::enums::{Backend, TaskStatus};
+
+/// A workflow state reported by the backend
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendState {
+    pub id: String,
+    pub name: String,
+    /// Backend category (Jira status category or Linear state type)
+    pub category: String,
+    /// Task status the state maps to after discovery
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maps_to: Option<TaskStatus>,
+}
+
+/// A transition available on one issue (Jira only)
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackendTransition {
+    pub id: String,
+    pub name: String,
+    pub to_id: String,
+    pub to_name: String,
+}
+
+/// `mobius backend states` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatesReport {
+    pub backend: Backend,
+    pub states: Vec<BackendState>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<BackendTransition>,
+    pub mapping: StatusMapping,
+    /// Config file the mapping was written to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub written_to: Option<String>,
+}
+
+/// English names tried first when several states share a category
+const PREFERRED_NAMES: [(TaskStatus, &[&str]); 3] = [
+    (TaskStatus::Pending, &["To Do", "Todo"]),
+    (TaskStatus::InProgress, &["In Progress"]),
+    (TaskStatus::Done, &["Done"]),
+];
+
+pub fn run_states(issue: Option<&str>, write: bool, output: OutputFormat) -> anyhow::Result<()> {
+    let paths = resolve_paths();
+    let config = read_config_with_env(&paths.config_path).unwrap_or_default();
+    let backend = config.backend;
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let (states, transitions) = match backend {
+        Backend::Jira => rt.block_on(fetch_jira_states(&config, issue))?,
+        Backend::Linear => {
+            if issue.is_some() {
+                output.note(
+                    "Linear has no per-issue transitions; any team state can be set directly."
+                        .dimmed(),
+                );
+            }
+            (rt.block_on(fetch_linear_states(&config))?, Vec::new())
+        }
+        Backend::Github | Backend::Gitlab | Backend::Local => anyhow::bail!(
+            "The {} backend has no configurable workflow states; statuses map to open/closed issues and labels",
+            backend
+        ),
+    };
+    if states.is_empty() {
+        anyhow::bail!("The {} backend returned no workflow states", backend);
+    }
+
+    let existing = config
+        .status_mapping_for(backend)
+        .cloned()
+        .unwrap_or_default();
+    let mapping = suggest_status_mapping(&states, &existing);
+    let states = states
+        .into_iter()
+        .map(|s| BackendState {
+            maps_to: mapping.task_status(&s.name),
+            ..s
+        })
+        .collect();
+
+    let written_to = if write {
+        if !config_exists(&paths.config_path) {
+            anyhow::bail!(
+                "No config at {}. Run 'mobius setup' first.",
+                paths.config_path
+            );
+        }
+        // Re-read without env or profile overrides so only the mapping changes on disk
+        let mut file_config = read_config_file(&paths.config_path)?;
+        file_config.status_mapping.insert(backend, mapping.clone());
+        write_config(&paths.config_path, &file_config)?;
+        Some(paths.config_path.clone())
+    } else {
+        None
+    };
+
+    let report = StatesReport {
+        backend,
+        states,
+        transitions,
+        mapping,
+        written_to,
+    };
+    output.emit(&report, print_states_report)
+}
+
+async fn fetch_jira_states(
+    config: &LoopConfig,
+    issue: Option<&str>,
+) -> anyhow::Result<(Vec<BackendState>, Vec<BackendTransition>)> {
+    let client = JiraClient::new()?;
+    let project_key = config.jira.as_ref().and_then(|j| j.project_key.as_deref());
+    let states = client
+        .list_jira_statuses(project_key)
+        .await?
+        .into_iter()
+        .map(|s| BackendState {
+            id: s.id,
+            name: s.name,
+            category: s.category,
+            maps_to: None,
+        })
+        .collect();
+    let transitions = match issue {
+        Some(key) => client
+            .list_jira_transitions(key)
+            .await?
+            .into_iter()
+            .map(|t| BackendTransition {
+                id: t.id,
+                name: t.name,
+                to_id: t.to_id,
+                to_name: t.to_name,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    Ok((states, transitions))
+}
+
+async fn fetch_linear_states(config: &LoopConfig) -> anyhow::Result<Vec<BackendState>> {
+    let client = LinearClient::new()?;
+    let team = config.linear.as_ref().and_then(|l| l.team.as_deref());
+    let states = client.list_linear_workflow_states(team).await?;
+
+    // State IDs belong to one team, so a mapping cannot span teams
+    let mut teams: Vec<&str> = states.iter().map(|s| s.team.as_str()).collect();
+    teams.sort_unstable();
+    teams.dedup();
+    if teams.len() > 1 {
+        anyhow::bail!(
+            "Workflow states span several teams ({}); set linear.team in the config",
+            teams.join(", ")
+        );
+    }
+
+    Ok(states
+        .into_iter()
+        .map(|s| BackendState {
+            id: s.id,
+            name: s.name,
+            category: s.state_type,
+            maps_to: None,
+        })
+        .collect())
+}
+
+/// Task status implied by a Jira status category or Linear state type.
+fn category_task_status(category: &str) -> Option<TaskStatus> {
+    match category {
+        "new" | "backlog" | "unstarted" => Some(TaskStatus::Pending),
+        "indeterminate" | "started" => Some(TaskStatus::InProgress),
+        "done" | "completed" => Some(TaskStatus::Done),
+        _ => None,
+    }
+}
+
+/// Extend `existing` with mappings derived from the discovered states.
+///
+/// Entries already configured are kept. Each state name maps back to the task
+/// status of its category, and pushes target a state ID per status: the state
+/// named in `to_backend` if any, then a conventional English name, then the
+/// first state in the category.
+fn suggest_status_mapping(states: &[BackendState], existing: &StatusMapping) -> StatusMapping {
+    let mut mapping = existing.clone();
+
+    for state in states {
+        if let Some(status) = category_task_status(&state.category) {
+            if mapping.task_status(&state.name).is_none() {
+                mapping.from_backend.insert(state.name.clone(), status);
+            }
+        }
+    }
+
+    for (status, preferred) in PREFERRED_NAMES {
+        if mapping.to_backend_id.contains_key(&status) {
+            continue;
+        }
+        let in_category: Vec<&BackendState> = states
+            .iter()
+            .filter(|s| category_task_status(&s.category) == Some(status))
+            .collect();
+        let named = |name: &str| {
+            in_category
+                .iter()
+                .find(|s| s.name.eq_ignore_ascii_case(name))
+                .copied()
+        };
+        let chosen = existing
+            .to_backend
+            .get(&status)
+            .and_then(|name| named(name))
+            .or_else(|| preferred.iter().find_map(|name| named(name)))
+            .or_else(|| in_category.first().copied());
+        if let Some(state) = chosen {
+            mapping.to_backend_id.insert(status, state.id.clone());
+        }
+    }
+
+    mapping
+}
+
+fn print_states_report(report: &StatesReport) {
+    println!(
+        "{}",
+        format!("Workflow states ({}):", report.backend).bold()
+    );
+    let ids_by_status: HashMap<&str, TaskStatus> = report
+        .mapping
+        .to_backend_id
+        .iter()
+        .map(|(status, id)| (id.as_str(), *status))
+        .collect();
+    for state in &report.states {
+        let mapped = state
+            .maps_to
+            .map(|s| format!("→ {}", s))
+            .unwrap_or_else(|| "(unmapped)".to_string());
+        let push = ids_by_status
+            .get(state.id.as_str())
+            .map(|s| format!("  pushed for {}", s).green().to_string())
+            .unwrap_or_default();
+        println!(
+            "  {:>8}  {:<28} {:<14} {}{}",
+            state.id.dimmed(),
+            state.name,
+            state.category.dimmed(),
+            mapped,
+            push
+        );
+    }
+
+    if !report.transitions.is_empty() {
+        println!();
+        println!("{}", "Available transitions:".bold());
+        for t in &report.transitions {
+            println!(
+                "  {:>8}  {} → {} {}",
+                t.id.dimmed(),
+                t.name,
+                t.to_name,
+                format!("(status {})", t.to_id).dimmed()
+            );
+        }
+    }
+
+    println!();
+    match &report.written_to {
+        Some(path) => println!(
+            "{}",
+            format!("✓ Wrote status_mapping.{} to {}", report.backend, path).green()
+        ),
+        None => {
+            let snippet = HashMap::from([(
+                "status_mapping",
+                HashMap::from([(report.backend, &report.mapping)]),
+            )]);
+            println!("{}", "Suggested mapping:".bold());
+            if let Ok(yaml) = serde_yaml::to_string(&snippet) {
+                for line in yaml.lines() {
+                    println!("  {}", line);
+                }
+            }
+            println!();
+            println!(
+                "{}",
+                "Run 'mobius backend states --write' to save it to the config.".dimmed()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(id: &str, name: &str, category: &str) -> BackendState {
+        BackendState {
+            id: id.to_string(),
+            name: name.to_string(),
+            category: category.to_string(),
+            maps_to: None,
+        }
+    }
+
+    #[test]
+    fn test_suggest_status_mapping_for_localized_states() {
+        let states = vec![
+            state("10000", "Zu erledigen", "new"),
+            state("3", "In Arbeit", "indeterminate"),
+            state("10002", "In Prüfung", "indeterminate"),
+            state("10001", "Fertig", "done"),
+        ];
+        let existing = StatusMapping {
+            to_backend: HashMap::from([(TaskStatus::InProgress, "in prüfung".to_string())]),
+            ..Default::default()
+        };
+
+        let mapping = suggest_status_mapping(&states, &existing);
+        assert_eq!(
+            mapping.task_status("in arbeit"),
+            Some(TaskStatus::InProgress)
+        );
+        assert_eq!(mapping.task_status("Fertig"), Some(TaskStatus::Done));
+        assert_eq!(mapping.backend_status_id("pending"), Some("10000"));
+        // The configured name wins over the first state in the category
+        assert_eq!(mapping.backend_status_id("in_progress"), Some("10002"));
+        assert_eq!(mapping.backend_status_id("done"), Some("10001"));
+        assert_eq!(mapping.to_backend, existing.to_backend);
+    }
+
+    #[test]
+    fn test_suggest_status_mapping_keeps_configured_entries() {
+        let states = vec![
+            state("a", "Backlog", "backlog"),
+            state("b", "Todo", "unstarted"),
+            state("c", "Canceled", "canceled"),
+        ];
+        let existing = StatusMapping {
+            from_backend: HashMap::from([("backlog".to_string(), TaskStatus::Blocked)]),
+            to_backend_id: HashMap::from([(TaskStatus::Done, "z".to_string())]),
+            ..Default::default()
+        };
+
+        let mapping = suggest_status_mapping(&states, &existing);
+        assert_eq!(mapping.task_status("Backlog"), Some(TaskStatus::Blocked));
+        assert_eq!(mapping.task_status("Canceled"), None);
+        assert_eq!(mapping.backend_status_id("pending"), Some("b"));
+        assert_eq!(mapping.backend_status_id("done"), Some("z"));
+        assert_eq!(mapping.backend_status_id("in progress"), None);
+    }
+}
@@ -0,0 +1,151 @@
+//! Bisect command - Find the sub-task whose commits broke integration
+
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::bisect::{run_bisect, BisectReport, BisectStep, BisectVerdict};
+use crate::checkpoint::read_checkpoint;
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::local_state::{read_parent_spec, read_subtasks};
+use crate::project_detector::detect_project_info;
+
+pub fn run(
+    task_id: &str,
+    command: Option<&str>,
+    base: Option<&str>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let paths = resolve_paths();
+    let config = read_config(&paths.config_path).unwrap_or_default();
+
+    let identifiers: Vec<String> = read_subtasks(task_id)
+        .into_iter()
+        .map(|t| t.identifier)
+        .filter(|id| !id.is_empty())
+        .collect();
+    if identifiers.is_empty() {
+        anyhow::bail!(
+            "No local sub-tasks found for {}. Run 'mobius pull {}' first.",
+            task_id,
+            task_id
+        );
+    }
+
+    // Same branch and worktree resolution as `mobius churn`
+    let checkpoint = read_checkpoint(task_id);
+    let head = checkpoint
+        .as_ref()
+        .map(|c| c.branch_name.clone())
+        .or_else(|| {
+            read_parent_spec(task_id)
+                .map(|s| s.git_branch_name)
+                .filter(|b| !b.is_empty())
+        })
+        .unwrap_or_else(|| format!("feat/{}", task_id.to_lowercase()));
+    let repo_path = checkpoint
+        .as_ref()
+        .map(|c| PathBuf::from(&c.worktree_path))
+        .filter(|p| p.is_dir())
+        .unwrap_or_else(|| PathBuf::from("."));
+    let base = base
+        .map(str::to_string)
+        .or_else(|| config.execution.base_branch.clone())
+        .unwrap_or_else(|| "main".to_string());
+
+    let command = match command {
+        Some(c) => c.to_string(),
+        None => detect_project_info(&repo_path.display().to_string())
+            .ok()
+            .and_then(|info| info.available_commands.test)
+            .ok_or_else(|| anyhow::anyhow!("No test command detected; pass one with --command"))?,
+    };
+
+    output.note(
+        format!(
+            "Bisecting {}..{} with '{}' (one verify run per step)...",
+            base, head, command
+        )
+        .dimmed(),
+    );
+    let report = run_bisect(
+        task_id,
+        &repo_path,
+        &base,
+        &head,
+        &identifiers,
+        &command,
+        |step| {
+            if !output.is_json() {
+                print_step(step);
+            }
+        },
+    )?;
+
+    output.emit(&report, print_report)
+}
+
+fn print_step(step: &BisectStep) {
+    let point = step.task.as_deref().unwrap_or("base");
+    let short = &step.sha[..step.sha.len().min(8)];
+    if step.passed {
+        println!("  {} {} {}", "pass".green(), short.dimmed(), point);
+    } else {
+        println!("  {} {} {}", "FAIL".red(), short.dimmed(), point);
+    }
+}
+
+fn print_report(report: &BisectReport) {
+    println!();
+    println!("{}", "Triage:".bold());
+    match report.verdict {
+        BisectVerdict::Culprit => {
+            let task = report
+                .culprit_task
+                .as_deref()
+                .unwrap_or("unattributed commits");
+            println!(
+                "  {} broke '{}' ({} commit(s)):",
+                task.red().bold(),
+                report.command,
+                report.culprit_commits.len()
+            );
+            for commit in &report.culprit_commits {
+                println!(
+                    "    {} {}",
+                    commit.sha[..commit.sha.len().min(8)].dimmed(),
+                    commit.subject
+                );
+            }
+        }
+        BisectVerdict::HeadPasses => {
+            println!(
+                "  {}",
+                format!(
+                    "'{}' passes at the branch head; nothing to bisect.",
+                    report.command
+                )
+                .green()
+            );
+        }
+        BisectVerdict::BaseFails => {
+            println!(
+                "  {}",
+                format!(
+                    "'{}' already fails at the base ({}); no sub-task introduced it.",
+                    report.command,
+                    &report.base[..report.base.len().min(8)]
+                )
+                .yellow()
+            );
+        }
+    }
+    if let Some(output) = &report.failure_output {
+        println!();
+        println!("{}", "Failing output (tail):".dimmed());
+        for line in output.lines() {
+            println!("    {}", line.dimmed());
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Export and import commands - Move an issue's local spec between repositories
+//!
+//! A bundle is one JSON file holding the parent spec and its sub-task specs;
+//! the blocking relations travel in each sub-task's `blockedBy`/`blocks`.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use anyhow::Context;
+use chrono::Utc;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::output::{to_json, OutputFormat};
+use crate::commands::task::{relation_problems, task_key};
+use crate::context::{get_context_path, resolve_task_id};
+use crate::local_state::{
+    get_next_local_id, is_valid_identifier, read_parent_spec, read_subtasks, remove_subtask_spec,
+    write_parent_spec, write_subtask_spec,
+};
+use crate::status_sync::is_local_id;
+use crate::types::context::{ParentIssueContext, SubTaskContext};
+
+/// Bundle format written by this version
+const BUNDLE_VERSION: u32 = 1;
+
+/// An issue's parent spec and sub-tasks, as written by `mobius export`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueBundle {
+    pub version: u32,
+    pub exported_at: String,
+    pub parent: ParentIssueContext,
+    pub sub_tasks: Vec<SubTaskContext>,
+}
+
+/// What `mobius export` or `mobius import` wrote
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleReport {
+    pub identifier: String,
+    pub sub_tasks: usize,
+    /// The bundle file on export, the issue's local directory on import
+    pub path: String,
+}
+
+pub fn run_export(
+    task_id: Option<&str>,
+    out: Option<&Path>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let Some(parent_id) = resolve_task_id(task_id) else {
+        anyhow::bail!(
+            "No task ID provided and no current task set. Usage: mobius export <task-id>"
+        );
+    };
+    let Some(parent) = read_parent_spec(&parent_id) else {
+        anyhow::bail!(
+            "No local spec for {}. Run 'mobius pull {}' first.",
+            parent_id,
+            parent_id
+        );
+    };
+    let mut sub_tasks = read_subtasks(&parent_id);
+    sub_tasks.sort_by(|a, b| task_key(a).cmp(task_key(b)));
+    let bundle = IssueBundle {
+        version: BUNDLE_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        parent,
+        sub_tasks,
+    };
+
+    let Some(out) = out else {
+        println!("{}", to_json(&bundle)?);
+        return Ok(());
+    };
+    fs::write(out, to_json(&bundle)? + "\n")
+        .with_context(|| format!("Failed to write {}", out.display()))?;
+    let report = BundleReport {
+        identifier: bundle.parent.identifier,
+        sub_tasks: bundle.sub_tasks.len(),
+        path: out.display().to_string(),
+    };
+    output.emit(&report, |report| {
+        println!(
+            "{}",
+            format!(
+                "✓ Exported {} with {} sub-task{} to {}",
+                report.identifier,
+                report.sub_tasks,
+                if report.sub_tasks == 1 { "" } else { "s" },
+                report.path
+            )
+            .green()
+        );
+    })
+}
+
+pub fn run_import(
+    file: &Path,
+    id: Option<&str>,
+    force: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let content =
+        fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let mut bundle = parse_bundle(&content)
+        .with_context(|| format!("{} is not a mobius issue bundle", file.display()))?;
+
+    // Local IDs are only unique within one repository, so take the next free one
+    let target = match id {
+        Some(id) => id.to_string(),
+        None if is_local_id(&bundle.parent.identifier) => get_next_local_id()?,
+        None => bundle.parent.identifier.clone(),
+    };
+    if !is_valid_identifier(&target) {
+        anyhow::bail!(
+            "Invalid issue ID '{}': only letters, digits, '_' and '-' are allowed",
+            target
+        );
+    }
+    if read_parent_spec(&target).is_some() && !force {
+        anyhow::bail!(
+            "{} already exists locally. Pass --force to replace it, or --id to import under another ID.",
+            target
+        );
+    }
+    retarget(&mut bundle, &target);
+
+    for problem in relation_problems(&bundle.sub_tasks, &HashSet::new()) {
+        output.note(format!("⚠ {}", problem).yellow());
+    }
+
+    for existing in read_subtasks(&target) {
+        remove_subtask_spec(&target, task_key(&existing))?;
+    }
+    write_parent_spec(&target, &bundle.parent)?;
+    for task in &bundle.sub_tasks {
+        write_subtask_spec(&target, task)?;
+    }
+
+    let report = BundleReport {
+        identifier: target.clone(),
+        sub_tasks: bundle.sub_tasks.len(),
+        path: get_context_path(&target).display().to_string(),
+    };
+    output.emit(&report, |report| {
+        println!(
+            "{}",
+            format!(
+                "✓ Imported {} with {} sub-task{}",
+                report.identifier,
+                report.sub_tasks,
+                if report.sub_tasks == 1 { "" } else { "s" }
+            )
+            .green()
+        );
+        println!("  {}", report.path.dimmed());
+    })
+}
+
+/// Parse a bundle, refusing ones written by a newer mobius and ones whose
+/// identifiers can't safely name files (bundles are shared, so untrusted).
+fn parse_bundle(content: &str) -> anyhow::Result<IssueBundle> {
+    let bundle: IssueBundle = serde_json::from_str(content)?;
+    if bundle.version > BUNDLE_VERSION {
+        anyhow::bail!(
+            "bundle version {} is newer than this mobius supports ({}); upgrade mobius",
+            bundle.version,
+            BUNDLE_VERSION
+        );
+    }
+    let invalid: Vec<&str> = std::iter::once(bundle.parent.identifier.as_str())
+        .chain(bundle.sub_tasks.iter().map(task_key))
+        .filter(|id| !is_valid_identifier(id))
+        .collect();
+    if !invalid.is_empty() {
+        anyhow::bail!(
+            "invalid identifiers {:?}: only letters, digits, '_' and '-' are allowed",
+            invalid
+        );
+    }
+    Ok(bundle)
+}
+
+/// Point the bundle's parent at `identifier`. A tracker parent keeps its
+/// tracker ID so the imported issue can still be traced to its source.
+fn retarget(bundle: &mut IssueBundle, identifier: &str) {
+    let parent = &mut bundle.parent;
+    if parent.identifier == identifier {
+        return;
+    }
+    if is_local_id(&parent.id) {
+        parent.id = identifier.to_string();
+    }
+    parent.identifier = identifier.to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundle_json(version: u32, parent_id: &str) -> String {
+        format!(
+            r#"{{
+                "version": {version},
+                "exportedAt": "2026-01-01T00:00:00Z",
+                "parent": {{"id": "{parent_id}", "identifier": "{parent_id}", "title": "Parent", "status": "Backlog"}},
+                "subTasks": [
+                    {{"id": "task-001", "title": "Add types", "status": "pending"}},
+                    {{"id": "task-002", "title": "Use types", "status": "pending", "blockedBy": ["task-001"]}}
+                ]
+            }}"#
+        )
+    }
+
+    #[test]
+    fn test_parse_bundle_keeps_relations_and_rejects_newer_versions() {
+        let bundle = parse_bundle(&bundle_json(1, "LOC-004")).unwrap();
+        assert_eq!(bundle.sub_tasks.len(), 2);
+        assert_eq!(bundle.sub_tasks[1].blocked_by[0].id, "task-001");
+        assert!(relation_problems(&bundle.sub_tasks, &HashSet::new()).is_empty());
+
+        let err = parse_bundle(&bundle_json(2, "LOC-004")).unwrap_err();
+        assert!(err.to_string().contains("bundle version 2"));
+    }
+
+    #[test]
+    fn test_parse_bundle_rejects_path_identifiers() {
+        let err = parse_bundle(&bundle_json(1, "../../x")).unwrap_err();
+        assert!(err.to_string().contains("../../x"), "{}", err);
+
+        let escaping_task = bundle_json(1, "LOC-004").replace("task-002", "../task-002");
+        assert!(parse_bundle(&escaping_task).is_err());
+    }
+
+    #[test]
+    fn test_retarget_keeps_tracker_ids() {
+        let mut local = parse_bundle(&bundle_json(1, "LOC-004")).unwrap();
+        retarget(&mut local, "LOC-011");
+        assert_eq!(local.parent.id, "LOC-011");
+        assert_eq!(local.parent.identifier, "LOC-011");
+
+        let mut tracked = parse_bundle(&bundle_json(1, "MOB-42")).unwrap();
+        tracked.parent.id = "uuid-42".to_string();
+        retarget(&mut tracked, "LOC-012");
+        assert_eq!(tracked.parent.id, "uuid-42");
+        assert_eq!(tracked.parent.identifier, "LOC-012");
+    }
+}
@@ -0,0 +1,188 @@
+//! Cancel command - Stop a running loop and its agents
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::checkpoint::load_checkpoint;
+use crate::commands::output::OutputFormat;
+use crate::context::{
+    cancel_runtime_tasks, end_session, is_process_running, read_runtime_state, read_session,
+    resolve_task_id, with_runtime_state_sync,
+};
+use crate::git_lock::{force_release_lock, is_locked};
+use crate::tmux::{get_session_name, interrupt_pane, kill_pane, list_session_panes};
+use crate::types::context::RuntimeActiveTask;
+use crate::types::enums::SessionStatus;
+
+/// How long the loop gets to stop its agents and checkpoint after SIGTERM
+const LOOP_EXIT_GRACE: Duration = Duration::from_secs(10);
+
+/// Pause between Ctrl-C and killing a pane, so agents can flush output
+const PANE_INTERRUPT_GRACE: Duration = Duration::from_millis(300);
+
+/// What `mobius cancel` stopped
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelReport {
+    pub parent_id: String,
+    /// PID of the loop process that was signalled
+    pub loop_pid: Option<u32>,
+    /// Whether the loop ignored SIGTERM and had to be killed
+    pub loop_killed: bool,
+    pub panes: Vec<String>,
+    pub cancelled_tasks: Vec<String>,
+    /// Worktrees whose git lock was released
+    pub released_locks: Vec<String>,
+}
+
+pub fn run(task_id: Option<&str>, output: OutputFormat) -> anyhow::Result<()> {
+    let Some(parent_id) = resolve_task_id(task_id) else {
+        anyhow::bail!(
+            "No task ID provided and no current task set. Usage: mobius cancel <task-id>"
+        );
+    };
+    let report = cancel_loop(&parent_id)?;
+    output.emit(&report, print_cancel_report)
+}
+
+/// Stop `parent_id`'s loop and its agents, and release their git locks.
+pub fn cancel_loop(parent_id: &str) -> anyhow::Result<CancelReport> {
+    let session = read_session(parent_id);
+    // Read before stopping the loop: it clears its active tasks on the way out
+    let runtime = read_runtime_state(parent_id);
+    if session.is_none() && runtime.is_none() {
+        anyhow::bail!("No loop state found for {}", parent_id);
+    }
+    let active_tasks: Vec<RuntimeActiveTask> = runtime
+        .as_ref()
+        .map(|r| r.active_tasks.clone())
+        .unwrap_or_default();
+
+    // Let the loop interrupt its own agents and write a checkpoint first
+    let loop_pid = runtime
+        .as_ref()
+        .and_then(|r| r.loop_pid)
+        .filter(|pid| *pid != 0 && *pid != std::process::id() && is_process_running(*pid));
+    let loop_killed = loop_pid.is_some_and(stop_process);
+
+    // Then stop anything still running: panes in the loop's tmux session and
+    // process-driver agents
+    let rt = tokio::runtime::Runtime::new()?;
+    let mut panes: BTreeSet<String> = active_tasks
+        .iter()
+        .map(|t| t.pane.clone())
+        .filter(|p| !p.is_empty())
+        .collect();
+    panes.extend(rt.block_on(list_session_panes(&get_session_name(parent_id))));
+    // Never kill the pane `mobius cancel` itself runs in
+    if let Ok(own_pane) = std::env::var("TMUX_PANE") {
+        panes.remove(&own_pane);
+    }
+    rt.block_on(async {
+        for pane in &panes {
+            interrupt_pane(pane).await;
+        }
+        tokio::time::sleep(PANE_INTERRUPT_GRACE).await;
+        for pane in &panes {
+            kill_pane(pane).await;
+        }
+    });
+    for task in &active_tasks {
+        if task.pid != 0 && is_process_running(task.pid) {
+            signal(task.pid, libc::SIGTERM);
+        }
+    }
+
+    if let Some(runtime) = runtime {
+        with_runtime_state_sync(parent_id, |state| {
+            cancel_runtime_tasks(&state.unwrap_or(runtime), &active_tasks)
+        })?;
+    }
+
+    // Locks held by interrupted agents would otherwise block the next run
+    let mut worktrees: BTreeSet<String> = active_tasks
+        .iter()
+        .filter_map(|t| t.worktree.clone())
+        .collect();
+    worktrees.extend(session.as_ref().and_then(|s| s.worktree_path.clone()));
+    if let Ok(Some(checkpoint)) = load_checkpoint(parent_id) {
+        worktrees.insert(checkpoint.worktree_path);
+    }
+    let mut released_locks = Vec::new();
+    for worktree in worktrees.into_iter().filter(|w| !w.is_empty()) {
+        let path = Path::new(&worktree);
+        if rt.block_on(is_locked(path)) && rt.block_on(force_release_lock(path)).is_ok() {
+            released_locks.push(worktree);
+        }
+    }
+
+    if session.is_some() {
+        end_session(parent_id, SessionStatus::Cancelled);
+    }
+
+    Ok(CancelReport {
+        parent_id: parent_id.to_string(),
+        loop_pid,
+        loop_killed,
+        panes: panes.into_iter().collect(),
+        cancelled_tasks: active_tasks.into_iter().map(|t| t.id).collect(),
+        released_locks,
+    })
+}
+
+/// SIGTERM `pid` and wait for it to exit, escalating to SIGKILL after
+/// [`LOOP_EXIT_GRACE`]. Returns whether it had to be killed.
+fn stop_process(pid: u32) -> bool {
+    signal(pid, libc::SIGTERM);
+    let deadline = Instant::now() + LOOP_EXIT_GRACE;
+    while Instant::now() < deadline {
+        if !is_process_running(pid) {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(200));
+    }
+    signal(pid, libc::SIGKILL);
+    true
+}
+
+fn signal(pid: u32, sig: libc::c_int) {
+    unsafe {
+        libc::kill(pid as libc::pid_t, sig);
+    }
+}
+
+fn print_cancel_report(report: &CancelReport) {
+    println!(
+        "{}",
+        format!("Cancelled {}", report.parent_id).yellow().bold()
+    );
+    match (report.loop_pid, report.loop_killed) {
+        (Some(pid), false) => println!("  Loop:    stopped (pid {})", pid),
+        (Some(pid), true) => println!(
+            "  Loop:    killed after {}s (pid {})",
+            LOOP_EXIT_GRACE.as_secs(),
+            pid
+        ),
+        (None, _) => println!("  Loop:    {}", "not running".dimmed()),
+    }
+    println!("  Panes:   {} interrupted", report.panes.len());
+    if report.cancelled_tasks.is_empty() {
+        println!("  Tasks:   {}", "none active".dimmed());
+    } else {
+        println!("  Tasks:   {}", report.cancelled_tasks.join(", "));
+    }
+    for worktree in &report.released_locks {
+        println!("  Unlocked {}", worktree.dimmed());
+    }
+    println!();
+    println!("{}", "Resume where this run stopped with:".dimmed());
+    println!(
+        "  {}",
+        format!("mobius resume {}", report.parent_id).dimmed()
+    );
+}
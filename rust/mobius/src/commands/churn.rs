@@ -0,0 +1,77 @@
+//! Churn command - Show which files were touched by multiple sub-tasks
+
+use colored::Colorize;
+use std::path::PathBuf;
+
+use crate::checkpoint::read_checkpoint;
+use crate::churn::{generate_churn_report, render_churn_heatmap};
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::local_state::{read_parent_spec, read_subtasks};
+
+/// Rows shown in the text heatmap
+const DEFAULT_LIMIT: usize = 20;
+
+pub fn run(task_id: &str, limit: Option<usize>, output: OutputFormat) -> anyhow::Result<()> {
+    let paths = resolve_paths();
+    let config = read_config(&paths.config_path).unwrap_or_default();
+
+    let identifiers: Vec<String> = read_subtasks(task_id)
+        .into_iter()
+        .map(|t| t.identifier)
+        .filter(|id| !id.is_empty())
+        .collect();
+    if identifiers.is_empty() {
+        anyhow::bail!(
+            "No local sub-tasks found for {}. Run 'mobius pull {}' first.",
+            task_id,
+            task_id
+        );
+    }
+
+    let (head, repo_path) = loop_branch(task_id);
+    let base = config
+        .execution
+        .base_branch
+        .clone()
+        .unwrap_or_else(|| "main".to_string());
+
+    let report = generate_churn_report(task_id, &identifiers, &repo_path, &base, &head)?;
+
+    output.emit(&report, |report| {
+        let heatmap = render_churn_heatmap(report, limit.unwrap_or(DEFAULT_LIMIT));
+        for line in heatmap.lines() {
+            if line.contains(" tasks ") {
+                println!("{}", line.yellow());
+            } else if line.contains(" task ") {
+                println!("{}", line.dimmed());
+            } else {
+                println!("{}", line);
+            }
+        }
+    })
+}
+
+/// The loop branch for `task_id` and a checkout to read its history from.
+///
+/// Prefers the loop's own branch and worktree; falls back to the parent
+/// spec's branch in the current repository.
+pub fn loop_branch(task_id: &str) -> (String, PathBuf) {
+    let checkpoint = read_checkpoint(task_id);
+    let head = checkpoint
+        .as_ref()
+        .map(|c| c.branch_name.clone())
+        .or_else(|| {
+            read_parent_spec(task_id)
+                .map(|s| s.git_branch_name)
+                .filter(|b| !b.is_empty())
+        })
+        .unwrap_or_else(|| format!("feat/{}", task_id.to_lowercase()));
+    let repo_path = checkpoint
+        .as_ref()
+        .map(|c| PathBuf::from(&c.worktree_path))
+        .filter(|p| p.is_dir())
+        .unwrap_or_else(|| PathBuf::from("."));
+    (head, repo_path)
+}
@@ -7,7 +7,8 @@ use crate::config::loader::read_config;
 use crate::config::paths::resolve_paths;
 use crate::context::cleanup_context;
 use crate::local_state::{get_project_mobius_path, read_parent_spec};
-use crate::types::enums::Backend;
+use crate::types::config::StatusMapping;
+use crate::types::enums::{Backend, TaskStatus};
 use crate::worktree::{
     is_issue_merged_into_base, remove_worktree, MergeDetectionResult, WorktreeConfig,
 };
@@ -39,15 +40,24 @@ fn classify_branch_delete_failure(stderr: &str) -> BranchDeleteFailure {
     }
 }
 
-fn is_completed_status(status: &str, backend: &Backend) -> bool {
+pub(crate) fn is_completed_status(
+    status: &str,
+    backend: &Backend,
+    mapping: Option<&StatusMapping>,
+) -> bool {
+    if let Some(mapped) = mapping.and_then(|m| m.task_status(status)) {
+        return mapped == TaskStatus::Done;
+    }
+
     match backend {
         Backend::Linear => matches!(status, "Done" | "Canceled" | "Cancelled"),
         Backend::Jira => matches!(status, "Done" | "Closed"),
+        Backend::Github | Backend::Gitlab => matches!(status, "Done" | "closed"),
         Backend::Local => status == "done",
     }
 }
 
-fn is_local_id(id: &str) -> bool {
+pub(crate) fn is_local_id(id: &str) -> bool {
     let re = regex::Regex::new(r"^(LOC-\d+|task-\d+)$").unwrap();
     re.is_match(id)
 }
@@ -100,7 +110,7 @@ pub fn run(dry_run: bool, backend_override: Option<&str>) -> anyhow::Result<()>
         };
 
         if is_local_id(issue_id) {
-            if is_completed_status(&spec.status, &Backend::Local) {
+            if is_completed_status(&spec.status, &Backend::Local, None) {
                 candidates.push(CleanupCandidate {
                     identifier: spec.identifier,
                     title: spec.title,
@@ -127,7 +137,7 @@ pub fn run(dry_run: bool, backend_override: Option<&str>) -> anyhow::Result<()>
             }
         } else {
             // No git branch name — fall back to local status check
-            if is_completed_status(&spec.status, &backend) {
+            if is_completed_status(&spec.status, &backend, config.status_mapping_for(backend)) {
                 candidates.push(CleanupCandidate {
                     identifier: spec.identifier,
                     title: spec.title,
@@ -200,6 +210,7 @@ pub fn run(dry_run: bool, backend_override: Option<&str>) -> anyhow::Result<()>
         worktree_path: config.execution.worktree_path.clone(),
         base_branch: config.execution.base_branch.clone(),
         runtime: config.runtime,
+        checkout: config.execution.checkout.clone(),
     };
 
     for candidate in &candidates {
@@ -0,0 +1,245 @@
+//! Completions command - Generate shell completion scripts
+//!
+//! Scripts come from `clap_complete`, then gain dynamic completion of local
+//! issue IDs: wherever a command's first positional argument is `task_id`, the
+//! shell calls the hidden `mobius __task-ids` command, which lists the issue
+//! directories under `.mobius/issues/`.
+
+use clap_complete::{generate, Shell};
+use regex::Regex;
+
+use crate::local_state::list_local_issue_ids;
+
+/// Hidden subcommand that prints one local issue ID per line
+pub const TASK_IDS_COMMAND: &str = "__task-ids";
+
+pub fn run(shell: Shell, mut cmd: clap::Command) -> anyhow::Result<()> {
+    let name = cmd.get_name().to_string();
+    let mut buf = Vec::new();
+    generate(shell, &mut cmd, &name, &mut buf);
+    let script = String::from_utf8(buf)?;
+
+    let paths = task_id_paths(&cmd, &[]);
+    print!("{}", add_task_id_completion(shell, &name, &script, &paths));
+    Ok(())
+}
+
+/// Print local issue IDs for shell completion.
+pub fn run_task_ids() -> anyhow::Result<()> {
+    for id in list_local_issue_ids() {
+        println!("{}", id);
+    }
+    Ok(())
+}
+
+/// Subcommand paths whose first positional argument is `task_id`.
+///
+/// The root command is the empty path. Hidden commands and `help` are skipped.
+pub fn task_id_paths(cmd: &clap::Command, prefix: &[String]) -> Vec<Vec<String>> {
+    let mut paths = Vec::new();
+    if cmd
+        .get_positionals()
+        .next()
+        .is_some_and(|arg| arg.get_id() == "task_id")
+    {
+        paths.push(prefix.to_vec());
+    }
+    for sub in cmd.get_subcommands() {
+        if sub.is_hide_set() || sub.get_name() == "help" {
+            continue;
+        }
+        let mut path = prefix.to_vec();
+        path.push(sub.get_name().to_string());
+        paths.extend(task_id_paths(sub, &path));
+    }
+    paths
+}
+
+/// Extend a generated script so task ID arguments complete local issue IDs.
+///
+/// Shells other than bash, zsh, fish and PowerShell are returned unchanged.
+pub fn add_task_id_completion(
+    shell: Shell,
+    bin: &str,
+    script: &str,
+    paths: &[Vec<String>],
+) -> String {
+    match shell {
+        Shell::Bash => bash_task_ids(bin, script, paths),
+        Shell::Zsh => zsh_task_ids(bin, script),
+        Shell::Fish => fish_task_ids(bin, script, paths),
+        Shell::PowerShell => powershell_task_ids(bin, script, paths),
+        _ => script.to_string(),
+    }
+}
+
+/// Wrap the generated `_bin` function; IDs replace option suggestions at a
+/// task ID position and are added alongside subcommands at the root.
+fn bash_task_ids(bin: &str, script: &str, paths: &[Vec<String>]) -> String {
+    let patterns: Vec<String> = paths
+        .iter()
+        .map(|p| format!("\"{}\"", p.join(" ")))
+        .collect();
+    if patterns.is_empty() {
+        return script.to_string();
+    }
+    format!(
+        r#"{script}
+_{bin}_task_ids() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}"
+    local path="${{COMP_WORDS[*]:1:COMP_CWORD-1}}"
+    _{bin} "$@"
+    [[ ${{cur}} == -* ]] && return 0
+    case "${{path}}" in
+        {patterns})
+            local ids
+            ids=$({bin} {command} 2>/dev/null)
+            if [[ -n "${{path}}" ]]; then
+                COMPREPLY=()
+            fi
+            COMPREPLY+=( $(compgen -W "${{ids}}" -- "${{cur}}") )
+            ;;
+    esac
+}}
+
+if [[ "${{BASH_VERSINFO[0]}}" -eq 4 && "${{BASH_VERSINFO[1]}}" -ge 4 || "${{BASH_VERSINFO[0]}}" -gt 4 ]]; then
+    complete -F _{bin}_task_ids -o nosort -o bashdefault -o default {bin}
+else
+    complete -F _{bin}_task_ids -o bashdefault -o default {bin}
+fi
+"#,
+        patterns = patterns.join("|"),
+        command = TASK_IDS_COMMAND,
+    )
+}
+
+/// Point every `task_id` argument spec at a `_bin_task_ids` helper.
+fn zsh_task_ids(bin: &str, script: &str) -> String {
+    let spec = Regex::new(r"(?m)^('::?task_id(?: -- .*)?):_default' \\$").unwrap();
+    let script = spec.replace_all(script, format!("$1:_{bin}_task_ids' \\"));
+
+    let helper = format!(
+        r#"(( $+functions[_{bin}_task_ids] )) ||
+_{bin}_task_ids() {{
+    local -a task_ids
+    task_ids=(${{(f)"$({bin} {command} 2>/dev/null)"}})
+    _describe -t task-ids 'task id' task_ids
+}}
+"#,
+        command = TASK_IDS_COMMAND,
+    );
+    let entry = format!("\nif [ \"$funcstack[1]\" = \"_{bin}\" ]; then");
+    match script.find(&entry) {
+        Some(i) => format!("{}\n{}{}", &script[..i], helper, &script[i..]),
+        None => format!("{}\n{}", script, helper),
+    }
+}
+
+fn fish_task_ids(bin: &str, script: &str, paths: &[Vec<String>]) -> String {
+    let ids = format!("({} {} 2>/dev/null)", bin, TASK_IDS_COMMAND);
+    let mut out = script.to_string();
+    out.push('\n');
+
+    let top_level: Vec<&str> = paths
+        .iter()
+        .filter(|p| p.len() == 1)
+        .map(|p| p[0].as_str())
+        .collect();
+    if paths.iter().any(|p| p.is_empty()) {
+        out.push_str(&format!(
+            "complete -c {bin} -n \"__fish_{bin}_needs_command\" -f -a \"{ids}\" -d 'Task ID'\n"
+        ));
+    }
+    if !top_level.is_empty() {
+        out.push_str(&format!(
+            "complete -c {bin} -n \"__fish_{bin}_using_subcommand {}\" -f -a \"{ids}\" -d 'Task ID'\n",
+            top_level.join(" ")
+        ));
+    }
+    for path in paths.iter().filter(|p| p.len() == 2) {
+        out.push_str(&format!(
+            "complete -c {bin} -n \"__fish_{bin}_using_subcommand {}; and __fish_seen_subcommand_from {}\" -f -a \"{ids}\" -d 'Task ID'\n",
+            path[0], path[1]
+        ));
+    }
+    out
+}
+
+/// Add local IDs to each matching `switch ($command)` arm.
+fn powershell_task_ids(bin: &str, script: &str, paths: &[Vec<String>]) -> String {
+    let mut out = script.to_string();
+    for path in paths {
+        let mut key = vec![bin.to_string()];
+        key.extend(path.iter().cloned());
+        let arm = format!("        '{}' {{\n", key.join(";"));
+        let ids = format!(
+            "            {} {} 2>$null | ForEach-Object {{ [CompletionResult]::new($_, $_, [CompletionResultType]::ParameterValue, 'Task ID') }}\n",
+            bin, TASK_IDS_COMMAND
+        );
+        if let Some(i) = out.find(&arm) {
+            out.insert_str(i + arm.len(), &ids);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{Arg, Command};
+
+    fn sample_command() -> Command {
+        Command::new("mobius")
+            .arg(Arg::new("task_id").help("Task ID to execute"))
+            .subcommand(
+                Command::new("loop").arg(Arg::new("task_id").required(true).help("Task ID")),
+            )
+            .subcommand(Command::new("config"))
+            .subcommand(
+                Command::new("deps")
+                    .subcommand(Command::new("list").arg(Arg::new("task_id").required(true))),
+            )
+            .subcommand(Command::new(TASK_IDS_COMMAND).hide(true))
+    }
+
+    fn generated(shell: Shell) -> String {
+        let mut cmd = sample_command();
+        let mut buf = Vec::new();
+        generate(shell, &mut cmd, "mobius", &mut buf);
+        let script = String::from_utf8(buf).unwrap();
+        add_task_id_completion(shell, "mobius", &script, &task_id_paths(&cmd, &[]))
+    }
+
+    #[test]
+    fn test_task_id_paths() {
+        let paths = task_id_paths(&sample_command(), &[]);
+        assert_eq!(
+            paths,
+            vec![
+                vec![],
+                vec!["loop".to_string()],
+                vec!["deps".to_string(), "list".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scripts_call_task_ids_command() {
+        let bash = generated(Shell::Bash);
+        assert!(bash.contains(r#"""|"loop"|"deps list")"#));
+        assert!(bash.contains("complete -F _mobius_task_ids"));
+
+        let zsh = generated(Shell::Zsh);
+        assert!(zsh.contains("'::task_id -- Task ID to execute:_mobius_task_ids' \\"));
+        assert!(zsh.contains("':task_id -- Task ID:_mobius_task_ids' \\"));
+        assert!(!zsh.contains("task_id -- Task ID:_default"));
+        assert!(zsh.contains("_mobius_task_ids() {"));
+
+        let fish = generated(Shell::Fish);
+        assert!(fish.contains("__fish_mobius_using_subcommand loop\" -f -a \"(mobius __task-ids"));
+        assert!(fish.contains("__fish_seen_subcommand_from list\" -f -a"));
+
+        let powershell = generated(Shell::PowerShell);
+        assert_eq!(powershell.matches("mobius __task-ids 2>$null").count(), 3);
+    }
+}
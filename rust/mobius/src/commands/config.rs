@@ -1,19 +1,69 @@
 //! Config command - Show or edit current configuration
 
 use colored::Colorize;
+use serde::Serialize;
 use std::path::Path;
 use std::process::Command;
 
-use crate::config::loader::read_config_with_env;
+use crate::commands::output::OutputFormat;
+use crate::config::layers::{ConfigOrigin, ConfigSource};
+use crate::config::loader::{
+    active_profile, read_config_origins, read_config_with_env, ENV_OVERRIDES, PROFILE_ENV,
+};
 use crate::config::paths::resolve_paths;
 use crate::runtime_adapter;
+use crate::types::config::LoopConfig;
+
+/// `mobius config --output json` payload
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigReport {
+    config_path: String,
+    config_found: bool,
+    skills_path: String,
+    skills_found: bool,
+    runtime_model: Option<String>,
+    /// Profile applied on top of the config file
+    profile: Option<String>,
+    config: Option<LoopConfig>,
+    error: Option<String>,
+}
 
-pub fn run(edit: bool) -> anyhow::Result<()> {
+pub fn run(edit: bool, show_origin: bool, output: OutputFormat) -> anyhow::Result<()> {
     let paths = resolve_paths();
 
     if edit {
         return edit_config(&paths.config_path);
     }
+    if show_origin {
+        let origins = read_config_origins(&paths.config_path)?;
+        return output.emit(&origins, print_origins);
+    }
+
+    if output.is_json() {
+        let config_found = Path::new(&paths.config_path).exists();
+        let (config, error) = if config_found {
+            match read_config_with_env(&paths.config_path) {
+                Ok(config) => (Some(config), None),
+                Err(e) => (None, Some(e.to_string())),
+            }
+        } else {
+            (None, None)
+        };
+        let report = ConfigReport {
+            runtime_model: config.as_ref().map(|c| {
+                runtime_adapter::effective_model_for_runtime(c.runtime, &c.execution, None)
+            }),
+            profile: active_profile(),
+            config_found,
+            skills_found: Path::new(&paths.skills_path).exists(),
+            config_path: paths.config_path,
+            skills_path: paths.skills_path,
+            config,
+            error,
+        };
+        return output.emit(&report, |_| {});
+    }
 
     println!("{}", "\nMobius Configuration\n".bold());
 
@@ -53,6 +103,9 @@ pub fn run(edit: bool) -> anyhow::Result<()> {
             );
 
             println!("{}", "\nCurrent settings:".dimmed());
+            if let Some(profile) = active_profile() {
+                println!("  profile:         {}", profile.cyan());
+            }
             println!(
                 "  runtime:         {}",
                 format!("{}", config.runtime).cyan()
@@ -84,18 +137,11 @@ pub fn run(edit: bool) -> anyhow::Result<()> {
             );
 
             println!("{}", "\nEnvironment overrides:".dimmed());
-            let env_vars = [
-                "MOBIUS_RUNTIME",
-                "MOBIUS_BACKEND",
-                "MOBIUS_DELAY_SECONDS",
-                "MOBIUS_MAX_ITERATIONS",
-                "MOBIUS_MODEL",
-                "MOBIUS_SANDBOX_ENABLED",
-                "MOBIUS_CONTAINER",
-            ];
+            let env_vars =
+                std::iter::once(PROFILE_ENV).chain(ENV_OVERRIDES.iter().map(|(var, _)| *var));
 
             let mut has_overrides = false;
-            for var in &env_vars {
+            for var in env_vars {
                 if let Ok(val) = std::env::var(var) {
                     println!("  {}={}", var, val.yellow());
                     has_overrides = true;
@@ -117,6 +163,25 @@ pub fn run(edit: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// One line per effective setting, with the layer that set it.
+fn print_origins(origins: &Vec<ConfigOrigin>) {
+    let width = origins.iter().map(|o| o.key.len()).max().unwrap_or(0);
+    for origin in origins {
+        let source = format!("({})", origin.source);
+        println!(
+            "{:width$} = {}  {}",
+            origin.key,
+            origin.value.cyan(),
+            if origin.source == ConfigSource::Default {
+                source.dimmed()
+            } else {
+                source.yellow()
+            },
+            width = width
+        );
+    }
+}
+
 fn edit_config(config_path: &str) -> anyhow::Result<()> {
     if !Path::new(config_path).exists() {
         eprintln!("{}", format!("Config not found at {}", config_path).red());
@@ -0,0 +1,105 @@
+//! Debug command - Read back the structured debug log written under `--debug`
+
+use colored::Colorize;
+
+use crate::commands::output::OutputFormat;
+use crate::debug_logger::{debug_dir, format_debug_event, read_debug_events};
+use crate::types::debug::DebugEvent;
+
+/// Print an issue's debug events, oldest first, optionally filtered.
+///
+/// `event_type` matches the snake_case type (`lock_contention`) or the
+/// console label (`lock:contention`); `task` matches the sub-task identifier.
+pub fn run_dump(
+    task_id: &str,
+    event_type: Option<&str>,
+    task: Option<&str>,
+    tail: Option<usize>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let dir = debug_dir(task_id);
+    let events = filter_events(read_debug_events(&dir), event_type, task, tail);
+    output.emit(&events, |events| {
+        if events.is_empty() {
+            println!(
+                "{}",
+                format!(
+                    "No debug events in {}. Run 'mobius loop {} --debug' to record them.",
+                    dir.display(),
+                    task_id
+                )
+                .dimmed()
+            );
+        }
+        for event in events {
+            println!("{}", format_debug_event(event));
+        }
+    })
+}
+
+fn filter_events(
+    events: Vec<DebugEvent>,
+    event_type: Option<&str>,
+    task: Option<&str>,
+    tail: Option<usize>,
+) -> Vec<DebugEvent> {
+    let wanted_type = event_type.map(|t| t.replace(':', "_").to_lowercase());
+    let mut events: Vec<DebugEvent> = events
+        .into_iter()
+        .filter(|e| {
+            wanted_type.as_deref().map_or(true, |wanted| {
+                serde_json::to_value(e.event_type)
+                    .ok()
+                    .and_then(|v| v.as_str().map(|s| s == wanted))
+                    .unwrap_or(false)
+            })
+        })
+        .filter(|e| {
+            task.map_or(true, |task| {
+                e.task_id
+                    .as_deref()
+                    .is_some_and(|id| id.eq_ignore_ascii_case(task))
+            })
+        })
+        .collect();
+    if let Some(tail) = tail {
+        events.drain(..events.len().saturating_sub(tail));
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::{DebugEventSource, DebugEventType};
+
+    fn event(event_type: DebugEventType, task: Option<&str>) -> DebugEvent {
+        DebugEvent {
+            timestamp: "2024-01-15T14:30:00.000Z".to_string(),
+            event_type,
+            source: DebugEventSource::Loop,
+            task_id: task.map(str::to_string),
+            data: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_filter_events_by_type_task_and_tail() {
+        let events = vec![
+            event(DebugEventType::StateDrift, Some("MOB-1")),
+            event(DebugEventType::LockContention, None),
+            event(DebugEventType::StateDrift, Some("MOB-2")),
+            event(DebugEventType::StateDrift, Some("MOB-1")),
+        ];
+
+        let drift = filter_events(events.clone(), Some("state:drift"), None, None);
+        assert_eq!(drift.len(), 3);
+
+        let mob1 = filter_events(events.clone(), Some("state_drift"), Some("mob-1"), None);
+        assert_eq!(mob1.len(), 2);
+
+        let last = filter_events(events, None, None, Some(2));
+        assert_eq!(last.len(), 2);
+        assert_eq!(last[1].task_id.as_deref(), Some("MOB-1"));
+    }
+}
@@ -0,0 +1,550 @@
+//! Deps command - List and resolve external URL blockers and human tasks for an issue,
+//! and add or remove blocking relations between its sub-tasks
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::output::OutputFormat;
+use crate::commands::task::{external_refs, refers_to, relation_problems, sync_blocks, task_key};
+use crate::config::loader::read_config_with_env;
+use crate::config::paths::resolve_paths;
+use crate::context::{queue_pending_update, with_pending_updates_sync, PendingUpdateInput};
+use crate::external_deps::{apply_resolved_blockers, find_merged_pulls, pending_urls};
+use crate::local_state::{
+    read_local_subtasks_as_linear_issues, read_resolved_blockers, read_subtasks,
+    update_subtask_status, write_resolved_blockers, write_subtask_spec, BlockerResolution,
+    ResolvedBlocker,
+};
+use crate::types::context::{IssueRef, PendingUpdateData, SubTaskContext};
+use crate::types::enums::{Backend, TaskStatus, TaskType};
+use crate::types::task_graph::{
+    build_task_graph, get_awaiting_human_tasks, get_blocked_tasks, get_external_blockers,
+    get_task_by_identifier, is_external_blocker, TaskGraph,
+};
+
+/// An unresolved external blocker on one sub-task
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingBlocker {
+    pub task: String,
+    pub url: String,
+}
+
+/// An unblocked human sub-task nobody has marked done yet
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AwaitingTask {
+    pub task: String,
+    pub title: String,
+}
+
+/// `mobius deps list` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepsReport {
+    pub pending: Vec<PendingBlocker>,
+    pub resolved: Vec<ResolvedBlocker>,
+    /// Human sub-tasks the loop is waiting on
+    pub human: Vec<AwaitingTask>,
+}
+
+/// `mobius deps resolve` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolveReport {
+    /// URLs newly marked as resolved
+    pub resolved: Vec<String>,
+    /// Sub-tasks that are no longer blocked by any external URL
+    pub unblocked: Vec<String>,
+    /// External blockers still pending
+    pub pending: Vec<PendingBlocker>,
+}
+
+/// `mobius deps done` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DoneReport {
+    /// Human sub-tasks newly marked done
+    pub done: Vec<String>,
+    /// Sub-tasks that were blocked and can now start
+    pub unblocked: Vec<String>,
+}
+
+pub fn run_list(task_id: &str, output: OutputFormat) -> anyhow::Result<()> {
+    let graph = apply_resolved_blockers(task_id, &load_graph(task_id));
+    let report = DepsReport {
+        pending: pending_blockers(&graph),
+        resolved: read_resolved_blockers(task_id),
+        human: get_awaiting_human_tasks(&graph)
+            .into_iter()
+            .map(|t| AwaitingTask {
+                task: t.identifier.clone(),
+                title: t.title.clone(),
+            })
+            .collect(),
+    };
+    output.emit(&report, print_deps_report)
+}
+
+pub fn run_resolve(
+    task_id: &str,
+    urls: &[String],
+    all: bool,
+    check: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    if let Some(url) = urls.iter().find(|u| !is_external_blocker(u)) {
+        anyhow::bail!("Not an external blocker URL: {}", url);
+    }
+    let before = apply_resolved_blockers(task_id, &load_graph(task_id));
+    let pending = pending_urls(&before);
+    if urls.is_empty() && !all && !check {
+        anyhow::bail!(
+            "Specify URLs to resolve, --all, or --check ({} pending; see 'mobius deps list {}')",
+            pending.len(),
+            task_id
+        );
+    }
+
+    let mut resolved = Vec::new();
+    if check {
+        let rt = tokio::runtime::Runtime::new()?;
+        let merged = rt.block_on(find_merged_pulls(&pending));
+        write_resolved_blockers(task_id, &merged, BlockerResolution::Merged)?;
+        resolved.extend(merged);
+    }
+    let manual: Vec<String> = if all {
+        pending
+            .iter()
+            .filter(|u| !resolved.contains(u))
+            .cloned()
+            .collect()
+    } else {
+        urls.to_vec()
+    };
+    for url in manual.iter().filter(|u| !pending.contains(u)) {
+        output.note(format!("No sub-task of {} is waiting on {}", task_id, url).yellow());
+    }
+    write_resolved_blockers(task_id, &manual, BlockerResolution::Manual)?;
+    resolved.extend(manual);
+
+    let after = apply_resolved_blockers(task_id, &before);
+    let still_blocked: Vec<&str> = get_external_blockers(&after)
+        .into_iter()
+        .map(|(task, _)| task.id.as_str())
+        .collect();
+    let mut unblocked: Vec<String> = get_external_blockers(&before)
+        .into_iter()
+        .map(|(task, _)| task)
+        .filter(|task| !still_blocked.contains(&task.id.as_str()))
+        .map(|task| task.identifier.clone())
+        .collect();
+    unblocked.dedup();
+
+    let report = ResolveReport {
+        resolved,
+        unblocked,
+        pending: pending_blockers(&after),
+    };
+    output.emit(&report, print_resolve_report)
+}
+
+/// Mark human sub-tasks done so a loop waiting on them resumes.
+///
+/// The status change is queued for `mobius push` as well.
+pub fn run_done(task_id: &str, tasks: &[String], output: OutputFormat) -> anyhow::Result<()> {
+    let before = apply_resolved_blockers(task_id, &load_graph(task_id));
+    let mut done = Vec::new();
+    for identifier in tasks {
+        let Some(task) = get_task_by_identifier(&before, identifier) else {
+            anyhow::bail!("No sub-task {} in {}", identifier, task_id);
+        };
+        if task.task_type != TaskType::Human {
+            anyhow::bail!(
+                "{} is an agent task; only human tasks are marked done by hand",
+                identifier
+            );
+        }
+        if task.status == TaskStatus::Done {
+            output.note(format!("{} is already done", identifier).yellow());
+            continue;
+        }
+        update_subtask_status(task_id, identifier, "done");
+        queue_pending_update(
+            task_id,
+            &PendingUpdateInput::StatusChange {
+                issue_id: task.id.clone(),
+                identifier: identifier.clone(),
+                old_status: task.status.to_string(),
+                new_status: "Done".to_string(),
+            },
+        )?;
+        done.push(identifier.clone());
+    }
+
+    let after = apply_resolved_blockers(task_id, &load_graph(task_id));
+    let still_blocked: Vec<&str> = get_blocked_tasks(&after)
+        .into_iter()
+        .map(|t| t.id.as_str())
+        .collect();
+    let unblocked = get_blocked_tasks(&before)
+        .into_iter()
+        .filter(|t| !still_blocked.contains(&t.id.as_str()))
+        .map(|t| t.identifier.clone())
+        .collect();
+
+    let report = DoneReport { done, unblocked };
+    output.emit(&report, print_done_report)
+}
+
+/// `mobius deps add` / `mobius deps remove` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelationReport {
+    pub task: String,
+    pub blocker: String,
+    pub added: bool,
+    pub changed: bool,
+    /// Whether the relation change was queued for `mobius push`
+    pub queued: bool,
+}
+
+/// Make `task` blocked by `blocker`, a sibling sub-task or an external URL.
+pub fn run_add(
+    task_id: &str,
+    task: &str,
+    blocker: &str,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    change_relation(task_id, task, blocker, true, output)
+}
+
+/// Drop `blocker` from the blockers of `task`.
+pub fn run_remove(
+    task_id: &str,
+    task: &str,
+    blocker: &str,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    change_relation(task_id, task, blocker, false, output)
+}
+
+/// Edit one `blockedBy` entry, re-validate the graph, and save the specs.
+///
+/// Nothing is written when the change leaves an unknown blocker or a cycle.
+/// Relations between backend issues are queued for Linear and Jira.
+fn change_relation(
+    task_id: &str,
+    task: &str,
+    blocker: &str,
+    add: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let mut tasks = read_subtasks(task_id);
+    if tasks.is_empty() {
+        anyhow::bail!(
+            "No local sub-tasks for {}; run 'mobius pull {}' first",
+            task_id,
+            task_id
+        );
+    }
+    let index = find_subtask(&tasks, task)
+        .ok_or_else(|| anyhow::anyhow!("No sub-task {} in {}", task, task_id))?;
+    let sibling = if is_external_blocker(blocker) {
+        None
+    } else {
+        let i = find_subtask(&tasks, blocker)
+            .ok_or_else(|| anyhow::anyhow!("No sub-task {} in {}", blocker, task_id))?;
+        if i == index {
+            anyhow::bail!("{} cannot block itself", task);
+        }
+        Some(tasks[i].clone())
+    };
+    let blocker_ref = match &sibling {
+        Some(t) => IssueRef {
+            id: t.id.clone(),
+            identifier: task_key(t).to_string(),
+        },
+        None => IssueRef {
+            id: blocker.to_string(),
+            identifier: blocker.to_string(),
+        },
+    };
+    let allowed = external_refs(&tasks);
+    let key = task_key(&tasks[index]).to_string();
+
+    let existing = tasks[index].blocked_by.iter().position(|r| match &sibling {
+        Some(t) => refers_to(r, t),
+        None => r.id == blocker_ref.id,
+    });
+    let changed = match (add, existing) {
+        (true, None) => {
+            tasks[index].blocked_by.push(blocker_ref.clone());
+            true
+        }
+        (false, Some(pos)) => {
+            tasks[index].blocked_by.remove(pos);
+            true
+        }
+        _ => false,
+    };
+
+    let mut queued = false;
+    if changed {
+        let problems = relation_problems(&tasks, &allowed);
+        if !problems.is_empty() {
+            anyhow::bail!("Relation not saved:\n  {}", problems.join("\n  "));
+        }
+        for i in sync_blocks(&mut tasks) {
+            if i != index {
+                write_subtask_spec(task_id, &tasks[i])?;
+            }
+        }
+        write_subtask_spec(task_id, &tasks[index])?;
+
+        let paths = resolve_paths();
+        let config = read_config_with_env(&paths.config_path).unwrap_or_default();
+        let tracked = matches!(config.backend, Backend::Linear | Backend::Jira);
+        if tracked && !is_external_blocker(&blocker_ref.id) {
+            let (issue_id, identifier) = (tasks[index].id.clone(), key.clone());
+            let (blocker_id, blocker_identifier) =
+                (blocker_ref.id.clone(), blocker_ref.identifier.clone());
+            let update = if add {
+                PendingUpdateInput::AddRelation {
+                    issue_id,
+                    identifier,
+                    blocker_id,
+                    blocker_identifier,
+                }
+            } else {
+                PendingUpdateInput::RemoveRelation {
+                    issue_id,
+                    identifier,
+                    blocker_id,
+                    blocker_identifier,
+                }
+            };
+            queued = queue_relation_update(task_id, &update)?;
+        }
+    }
+
+    let report = RelationReport {
+        task: key,
+        blocker: blocker_ref.identifier,
+        added: add,
+        changed,
+        queued,
+    };
+    output.emit(&report, |report| {
+        if !report.changed {
+            let state = if report.added { "already" } else { "not" };
+            println!(
+                "{}",
+                format!("{} is {} blocked by {}", report.task, state, report.blocker).yellow()
+            );
+            return;
+        }
+        let verb = if report.added { "now" } else { "no longer" };
+        println!(
+            "{} {} is {} blocked by {}",
+            "✓".green(),
+            report.task,
+            verb,
+            report.blocker
+        );
+        if report.queued {
+            println!(
+                "{}",
+                format!(
+                    "Relation change queued: run 'mobius push {}' to sync it",
+                    task_id
+                )
+                .dimmed()
+            );
+        }
+    })
+}
+
+/// Queue a relation change, or drop the unsynced opposite change it undoes.
+///
+/// Returns whether an update is left waiting for `mobius push`.
+fn queue_relation_update(task_id: &str, update: &PendingUpdateInput) -> anyhow::Result<bool> {
+    let mut cancelled = false;
+    with_pending_updates_sync(task_id, |mut queue| {
+        if let Some(pos) = queue
+            .updates
+            .iter()
+            .position(|existing| existing.synced_at.is_none() && undoes(&existing.data, update))
+        {
+            queue.updates.remove(pos);
+            cancelled = true;
+        }
+        queue
+    })?;
+    if cancelled {
+        return Ok(false);
+    }
+    queue_pending_update(task_id, update)?;
+    Ok(true)
+}
+
+/// Whether `update` reverses the queued relation change `existing`.
+fn undoes(existing: &PendingUpdateData, update: &PendingUpdateInput) -> bool {
+    match (existing, update) {
+        (
+            PendingUpdateData::AddRelation {
+                issue_id: e_id,
+                blocker_id: e_blocker,
+                ..
+            },
+            PendingUpdateInput::RemoveRelation {
+                issue_id,
+                blocker_id,
+                ..
+            },
+        )
+        | (
+            PendingUpdateData::RemoveRelation {
+                issue_id: e_id,
+                blocker_id: e_blocker,
+                ..
+            },
+            PendingUpdateInput::AddRelation {
+                issue_id,
+                blocker_id,
+                ..
+            },
+        ) => e_id == issue_id && e_blocker == blocker_id,
+        _ => false,
+    }
+}
+
+/// Index of the sub-task whose identifier or ID is `name`.
+fn find_subtask(tasks: &[SubTaskContext], name: &str) -> Option<usize> {
+    tasks
+        .iter()
+        .position(|t| task_key(t).eq_ignore_ascii_case(name) || t.id == name)
+}
+
+fn load_graph(task_id: &str) -> TaskGraph {
+    let issues = read_local_subtasks_as_linear_issues(task_id);
+    build_task_graph(task_id, task_id, &issues)
+}
+
+fn pending_blockers(graph: &TaskGraph) -> Vec<PendingBlocker> {
+    get_external_blockers(graph)
+        .into_iter()
+        .map(|(task, url)| PendingBlocker {
+            task: task.identifier.clone(),
+            url: url.to_string(),
+        })
+        .collect()
+}
+
+fn print_pending(pending: &[PendingBlocker]) {
+    for blocker in pending {
+        println!("  {} {}", blocker.task.bold(), blocker.url.yellow());
+    }
+}
+
+fn print_deps_report(report: &DepsReport) {
+    if report.pending.is_empty() {
+        println!("{}", "No unresolved external blockers.".green());
+    } else {
+        println!("{}", "Waiting on:".bold());
+        print_pending(&report.pending);
+    }
+    if !report.human.is_empty() {
+        println!();
+        println!("{}", "Waiting on you:".bold());
+        for task in &report.human {
+            println!("  {} {}", task.task.bold(), task.title.yellow());
+        }
+    }
+    if !report.resolved.is_empty() {
+        println!();
+        println!("{}", "Resolved:".bold());
+        for blocker in &report.resolved {
+            let source = match blocker.source {
+                BlockerResolution::Manual => "manual",
+                BlockerResolution::Merged => "merged",
+            };
+            println!(
+                "  {} {}",
+                blocker.url.dimmed(),
+                format!("({}, {})", source, blocker.resolved_at).dimmed()
+            );
+        }
+    }
+}
+
+fn print_resolve_report(report: &ResolveReport) {
+    if report.resolved.is_empty() {
+        println!("{}", "Nothing resolved.".yellow());
+    }
+    for url in &report.resolved {
+        println!("{} Resolved {}", "✓".green(), url);
+    }
+    if !report.unblocked.is_empty() {
+        println!(
+            "{}",
+            format!("Unblocked: {}", report.unblocked.join(", ")).green()
+        );
+    }
+    if !report.pending.is_empty() {
+        println!();
+        println!("{}", "Still waiting on:".bold());
+        print_pending(&report.pending);
+    }
+}
+
+fn print_done_report(report: &DoneReport) {
+    for task in &report.done {
+        println!("{} Marked {} done", "✓".green(), task);
+    }
+    if !report.unblocked.is_empty() {
+        println!(
+            "{}",
+            format!("Unblocked: {}", report.unblocked.join(", ")).green()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relation(add: bool, blocker_id: &str) -> PendingUpdateInput {
+        let (issue_id, identifier) = ("a".to_string(), "MOB-2".to_string());
+        let (blocker_id, blocker_identifier) = (blocker_id.to_string(), "MOB-1".to_string());
+        if add {
+            PendingUpdateInput::AddRelation {
+                issue_id,
+                identifier,
+                blocker_id,
+                blocker_identifier,
+            }
+        } else {
+            PendingUpdateInput::RemoveRelation {
+                issue_id,
+                identifier,
+                blocker_id,
+                blocker_identifier,
+            }
+        }
+    }
+
+    #[test]
+    fn test_undoes_only_the_opposite_change_to_the_same_relation() {
+        let queued = PendingUpdateData::AddRelation {
+            issue_id: "a".into(),
+            identifier: "MOB-2".into(),
+            blocker_id: "b".into(),
+            blocker_identifier: "MOB-1".into(),
+        };
+
+        assert!(undoes(&queued, &relation(false, "b")));
+        assert!(!undoes(&queued, &relation(true, "b")));
+        assert!(!undoes(&queued, &relation(false, "c")));
+    }
+}
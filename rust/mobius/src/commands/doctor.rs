@@ -1,128 +1,32 @@
 //! Doctor command - Check system requirements and configuration
+//!
+//! `--fix` also repairs local state: missing shortcut scripts and
+//! `.mobius/.gitignore`, stale locks, dead agents in runtime state, a corrupted
+//! ID counter and a broken current-session pointer.
 
 use colored::Colorize;
+use serde::Serialize;
+use std::fs;
 use std::path::Path;
-use std::process::Command;
 
+use crate::commands::output::OutputFormat;
 use crate::config::loader::{read_config, read_config_with_env};
-use crate::config::paths::resolve_paths;
-use crate::types::enums::{AgentRuntime, Backend};
-
-struct CheckResult {
-    name: String,
-    status: CheckStatus,
-    message: String,
-    required: bool,
-    details: Option<String>,
-}
-
-enum CheckStatus {
-    Pass,
-    Fail,
-    Warn,
-}
-
-fn format_result(result: &CheckResult) -> String {
-    let icon = match result.status {
-        CheckStatus::Pass => "✓".green().to_string(),
-        CheckStatus::Fail => "✗".red().to_string(),
-        CheckStatus::Warn => "!".yellow().to_string(),
-    };
-
-    let required_str = if result.required { "" } else { " (optional)" };
-    let required_suffix = required_str.dimmed().to_string();
-
-    let message = match result.status {
-        CheckStatus::Fail => result.message.red().to_string(),
-        _ => result.message.clone(),
-    };
-
-    let mut line = format!("  {} {}: {}{}", icon, result.name, message, required_suffix);
-
-    if let Some(ref details) = result.details {
-        if !matches!(result.status, CheckStatus::Pass) {
-            line += &format!("\n      {}", details.dimmed());
-        }
-    }
-
-    line
-}
-
-fn check_command_exists(name: &str) -> bool {
-    which::which(name).is_ok()
-}
-
-fn check_command_version(name: &str) -> Option<String> {
-    let output = Command::new(name).arg("--version").output().ok()?;
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    let version = if stdout.trim().is_empty() {
-        stderr.trim().to_string()
-    } else {
-        stdout.trim().to_string()
-    };
-    if version.is_empty() {
-        None
-    } else {
-        Some(version.lines().next().unwrap_or("").to_string())
-    }
-}
-
-struct RuntimeCliSpec {
-    command: &'static str,
-    display_name: &'static str,
-    install_hint: &'static str,
-}
-
-fn runtime_cli_spec(runtime: AgentRuntime) -> RuntimeCliSpec {
-    match runtime {
-        AgentRuntime::Claude => RuntimeCliSpec {
-            command: "claude",
-            display_name: "Claude CLI",
-            install_hint: "Install: npm install -g @anthropic-ai/claude-code",
-        },
-        AgentRuntime::Opencode => RuntimeCliSpec {
-            command: "opencode",
-            display_name: "OpenCode CLI",
-            install_hint: "Install opencode and ensure it is available in PATH",
-        },
-    }
-}
-
-fn check_runtime_cli_with<F, G>(
-    runtime: AgentRuntime,
-    command_exists: F,
-    command_version: G,
-) -> CheckResult
-where
-    F: Fn(&str) -> bool,
-    G: Fn(&str) -> Option<String>,
-{
-    let spec = runtime_cli_spec(runtime);
-
-    if command_exists(spec.command) {
-        let version = command_version(spec.command).unwrap_or_else(|| "unknown version".into());
-        CheckResult {
-            name: spec.display_name.into(),
-            status: CheckStatus::Pass,
-            message: format!("Installed ({})", version),
-            required: true,
-            details: None,
-        }
-    } else {
-        CheckResult {
-            name: spec.display_name.into(),
-            status: CheckStatus::Fail,
-            message: "Not found".into(),
-            required: true,
-            details: Some(spec.install_hint.into()),
-        }
-    }
-}
-
-fn check_runtime_cli(runtime: AgentRuntime) -> CheckResult {
-    check_runtime_cli_with(runtime, check_command_exists, check_command_version)
-}
+use crate::config::paths::{get_shortcuts_install_path, resolve_paths};
+use crate::config::setup::{copy_shortcuts, shortcuts_source_line};
+use crate::context::{
+    diagnose_current_session_pointer, find_dead_runtime_tasks, find_stale_lock_files,
+    prune_dead_runtime_tasks, repair_current_session_pointer,
+};
+use crate::local_state::{
+    diagnose_local_counter, ensure_project_mobius_dir, get_project_mobius_path,
+    list_local_issue_ids, missing_gitignore_entries, rebuild_local_counter,
+};
+use crate::preflight::{
+    check_api_token, check_cclean, check_command_exists, check_gh, check_git, check_runtime_cli,
+    check_tmux, format_result, CheckResult, CheckStatus,
+};
+use crate::sandbox::Sandbox;
+use crate::types::enums::{AgentRuntime, Backend, StateStoreKind};
 
 fn check_config(config_path: &str) -> CheckResult {
     if Path::new(config_path).exists() {
@@ -173,128 +77,48 @@ fn check_path(skills_path: &str) -> CheckResult {
     }
 }
 
-fn check_git() -> CheckResult {
-    if check_command_exists("git") {
-        let version = check_command_version("git").unwrap_or_else(|| "unknown".into());
-        CheckResult {
-            name: "Git".into(),
-            status: CheckStatus::Pass,
-            message: format!("Installed ({})", version),
-            required: true,
-            details: None,
-        }
-    } else {
-        CheckResult {
-            name: "Git".into(),
-            status: CheckStatus::Fail,
-            message: "Not found".into(),
-            required: true,
-            details: Some("Install git for your platform".into()),
-        }
-    }
-}
-
-fn check_api_keys(backend: &Backend) -> CheckResult {
-    match backend {
-        Backend::Linear => {
-            let has_key = std::env::var("LINEAR_API_KEY").is_ok()
-                || std::env::var("LINEAR_API_TOKEN").is_ok();
-            if has_key {
-                CheckResult {
-                    name: "API keys".into(),
-                    status: CheckStatus::Pass,
-                    message: "LINEAR_API_KEY or LINEAR_API_TOKEN set".into(),
-                    required: true,
-                    details: None,
-                }
-            } else {
-                CheckResult {
-                    name: "API keys".into(),
-                    status: CheckStatus::Fail,
-                    message: "LINEAR_API_KEY not set".into(),
-                    required: true,
-                    details: Some("Set LINEAR_API_KEY environment variable".into()),
-                }
-            }
-        }
-        Backend::Jira => {
-            let has_host = std::env::var("JIRA_HOST").is_ok();
-            let has_email = std::env::var("JIRA_EMAIL").is_ok();
-            let has_token = std::env::var("JIRA_API_TOKEN").is_ok();
-
-            if has_host && has_email && has_token {
-                CheckResult {
-                    name: "API keys".into(),
-                    status: CheckStatus::Pass,
-                    message: "JIRA_HOST, JIRA_EMAIL, JIRA_API_TOKEN set".into(),
-                    required: true,
-                    details: None,
-                }
-            } else {
-                let mut missing = Vec::new();
-                if !has_host {
-                    missing.push("JIRA_HOST");
-                }
-                if !has_email {
-                    missing.push("JIRA_EMAIL");
-                }
-                if !has_token {
-                    missing.push("JIRA_API_TOKEN");
-                }
-                CheckResult {
-                    name: "API keys".into(),
-                    status: CheckStatus::Fail,
-                    message: format!("Missing: {}", missing.join(", ")),
-                    required: true,
-                    details: Some("Set Jira environment variables".into()),
-                }
-            }
-        }
-        Backend::Local => CheckResult {
-            name: "API keys".into(),
+/// Check the container engine when agents run in the sandbox.
+fn check_container_engine(sandbox_engine: Option<&str>) -> CheckResult {
+    let Some(engine) = sandbox_engine else {
+        return CheckResult {
+            name: "Container sandbox".into(),
             status: CheckStatus::Pass,
-            message: "Not required for local backend".into(),
-            required: true,
+            message: "Disabled (agents run on the host)".into(),
+            required: false,
             details: None,
-        },
-    }
-}
+        };
+    };
 
-fn check_tmux() -> CheckResult {
-    if check_command_exists("tmux") {
-        let version = check_command_version("tmux").unwrap_or_else(|| "unknown".into());
+    if check_command_exists(engine) {
         CheckResult {
-            name: "tmux".into(),
+            name: "Container sandbox".into(),
             status: CheckStatus::Pass,
-            message: format!("Installed ({})", version),
+            message: format!("{} installed", engine),
             required: false,
             details: None,
         }
     } else {
         CheckResult {
-            name: "tmux".into(),
+            name: "Container sandbox".into(),
             status: CheckStatus::Warn,
-            message: "Not found".into(),
+            message: format!("{} not found (execution.container.image is set)", engine),
             required: false,
-            details: Some("Install: brew install tmux (macOS) or apt install tmux (Linux)".into()),
+            details: Some(format!(
+                "Install {} or run with --no-sandbox",
+                if engine == "podman" {
+                    "Podman"
+                } else {
+                    "Docker Desktop or Docker Engine"
+                }
+            )),
         }
     }
 }
 
-fn check_docker(sandbox_enabled: bool) -> CheckResult {
-    if !sandbox_enabled {
-        return CheckResult {
-            name: "Docker".into(),
-            status: CheckStatus::Pass,
-            message: "Sandbox disabled".into(),
-            required: false,
-            details: None,
-        };
-    }
-
-    if check_command_exists("docker") {
+fn check_jq() -> CheckResult {
+    if check_command_exists("jq") {
         CheckResult {
-            name: "Docker".into(),
+            name: "jq".into(),
             status: CheckStatus::Pass,
             message: "Installed".into(),
             required: false,
@@ -302,148 +126,352 @@ fn check_docker(sandbox_enabled: bool) -> CheckResult {
         }
     } else {
         CheckResult {
-            name: "Docker".into(),
+            name: "jq".into(),
             status: CheckStatus::Warn,
-            message: "Not found (sandbox mode requires Docker)".into(),
+            message: "Not found".into(),
             required: false,
-            details: Some("Install Docker Desktop or Docker Engine".into()),
+            details: Some("Install: brew install jq (macOS) or apt install jq (Linux)".into()),
         }
     }
 }
 
-fn check_cclean() -> CheckResult {
-    if check_command_exists("cclean") {
-        CheckResult {
-            name: "cclean".into(),
-            status: CheckStatus::Pass,
-            message: "Installed".into(),
-            required: false,
-            details: None,
+/// Remediation `--fix` runs for a local-state problem; returns what it did
+type Fix = Box<dyn FnOnce() -> anyhow::Result<String>>;
+
+/// A local-state check, with its fix when it found a problem
+struct StateCheck {
+    result: CheckResult,
+    fix: Option<Fix>,
+}
+
+impl StateCheck {
+    fn ok(name: &str, message: impl Into<String>) -> Self {
+        Self {
+            result: CheckResult {
+                name: name.into(),
+                status: CheckStatus::Pass,
+                message: message.into(),
+                required: false,
+                details: None,
+            },
+            fix: None,
         }
-    } else {
-        CheckResult {
-            name: "cclean".into(),
-            status: CheckStatus::Warn,
-            message: "Not found".into(),
-            required: false,
-            details: Some("Install: npm install -g cclean".into()),
+    }
+
+    fn broken(name: &str, message: impl Into<String>, details: Option<String>, fix: Fix) -> Self {
+        Self {
+            result: CheckResult {
+                name: name.into(),
+                status: CheckStatus::Warn,
+                message: message.into(),
+                required: false,
+                details,
+            },
+            fix: Some(fix),
         }
     }
 }
 
-fn check_jq() -> CheckResult {
-    if check_command_exists("jq") {
-        CheckResult {
-            name: "jq".into(),
-            status: CheckStatus::Pass,
-            message: "Installed".into(),
-            required: false,
-            details: None,
+/// Shortcuts sourced from a shell rc file must exist, or every shell errors.
+fn check_shortcuts() -> StateCheck {
+    let script = get_shortcuts_install_path();
+    if script.exists() {
+        return StateCheck::ok("Shortcuts", format!("Installed at {}", script.display()));
+    }
+    let home = dirs::home_dir().unwrap_or_default();
+    let source_line = shortcuts_source_line();
+    let Some(rc) = [".zshrc", ".bashrc"]
+        .iter()
+        .map(|name| home.join(name))
+        .find(|rc| fs::read_to_string(rc).is_ok_and(|c| c.contains(&source_line)))
+    else {
+        return StateCheck::ok("Shortcuts", "Not installed");
+    };
+    StateCheck::broken(
+        "Shortcuts",
+        format!("{} sources a missing script", rc.display()),
+        Some(script.display().to_string()),
+        Box::new(move || {
+            let bundled = [
+                crate::commands::setup::get_bundled_shortcuts_path(),
+                crate::commands::shortcuts::get_bundled_shortcuts_path(),
+            ]
+            .into_iter()
+            .find(|path| path.exists())
+            .ok_or_else(|| anyhow::anyhow!("Bundled shortcuts script not found"))?;
+            copy_shortcuts(&bundled)?;
+            Ok(format!("installed {}", script.display()))
+        }),
+    )
+}
+
+/// Without `.mobius/.gitignore`, runtime state and per-checkout config show
+/// up as untracked changes.
+fn check_mobius_gitignore() -> StateCheck {
+    let mobius_path = get_project_mobius_path();
+    if !mobius_path.is_dir() {
+        return StateCheck::ok(".mobius/.gitignore", "No .mobius directory yet");
+    }
+    let missing = missing_gitignore_entries(&mobius_path);
+    if missing.is_empty() {
+        return StateCheck::ok(".mobius/.gitignore", "Present");
+    }
+    let exists = mobius_path.join(".gitignore").exists();
+    StateCheck::broken(
+        ".mobius/.gitignore",
+        if exists {
+            format!("Missing {}", missing.join(", "))
+        } else {
+            "Missing".to_string()
+        },
+        Some("Runtime state or per-checkout config under .mobius/ is not ignored".into()),
+        Box::new(move || {
+            ensure_project_mobius_dir()?;
+            Ok(if exists {
+                "updated .mobius/.gitignore".into()
+            } else {
+                "created .mobius/.gitignore".into()
+            })
+        }),
+    )
+}
+
+fn check_stale_locks() -> StateCheck {
+    let stale = find_stale_lock_files();
+    if stale.is_empty() {
+        return StateCheck::ok("Locks", "No stale locks");
+    }
+    let details = stale
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    StateCheck::broken(
+        "Locks",
+        format!("{} lock(s) held by exited processes", stale.len()),
+        Some(details),
+        Box::new(move || {
+            for path in &stale {
+                fs::remove_file(path)?;
+            }
+            Ok(format!("removed {} stale lock(s)", stale.len()))
+        }),
+    )
+}
+
+fn check_runtime_entries() -> StateCheck {
+    let dead: Vec<(String, Vec<String>)> = list_local_issue_ids()
+        .into_iter()
+        .map(|id| {
+            let tasks = find_dead_runtime_tasks(&id);
+            (id, tasks)
+        })
+        .filter(|(_, tasks)| !tasks.is_empty())
+        .collect();
+    if dead.is_empty() {
+        return StateCheck::ok("Runtime state", "No dead agents");
+    }
+    let count: usize = dead.iter().map(|(_, tasks)| tasks.len()).sum();
+    let details = dead
+        .iter()
+        .map(|(id, tasks)| format!("{}: {}", id, tasks.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ");
+    StateCheck::broken(
+        "Runtime state",
+        format!("{} active task(s) with no running agent", count),
+        Some(details),
+        Box::new(move || {
+            let mut removed = 0;
+            for (id, _) in &dead {
+                removed += prune_dead_runtime_tasks(id)?.len();
+            }
+            Ok(format!("removed {} dead task(s)", removed))
+        }),
+    )
+}
+
+fn check_local_counter(state_store: StateStoreKind) -> StateCheck {
+    if state_store == StateStoreKind::Sqlite {
+        return StateCheck::ok("Local ID counter", "Kept in state.db");
+    }
+    match diagnose_local_counter() {
+        None => StateCheck::ok("Local ID counter", "OK"),
+        Some(problem) => StateCheck::broken(
+            "Local ID counter",
+            problem,
+            None,
+            Box::new(|| {
+                let next = rebuild_local_counter()?;
+                Ok(format!("rebuilt counter.json (next {})", next))
+            }),
+        ),
+    }
+}
+
+fn check_session_pointer() -> StateCheck {
+    match diagnose_current_session_pointer() {
+        None => StateCheck::ok("Current session", "OK"),
+        Some(problem) => StateCheck::broken(
+            "Current session",
+            problem,
+            None,
+            Box::new(|| {
+                Ok(match repair_current_session_pointer()? {
+                    Some(parent_id) => format!("pointed current-session at {}", parent_id),
+                    None => "removed current-session".to_string(),
+                })
+            }),
+        ),
+    }
+}
+
+/// Apply `check`'s fix when `fix` is set, or say how to.
+fn settle_state_check(check: StateCheck, fix: bool) -> (CheckResult, bool) {
+    let mut result = check.result;
+    let Some(remedy) = check.fix else {
+        return (result, false);
+    };
+    if !fix {
+        result.details = Some(match result.details {
+            Some(details) => format!("{} (run 'mobius doctor --fix')", details),
+            None => "Run 'mobius doctor --fix' to repair".into(),
+        });
+        return (result, false);
+    }
+    match remedy() {
+        Ok(done) => {
+            result.status = CheckStatus::Pass;
+            result.message = format!("Fixed: {}", done);
+            result.details = None;
+            (result, true)
         }
-    } else {
-        CheckResult {
-            name: "jq".into(),
-            status: CheckStatus::Warn,
-            message: "Not found".into(),
-            required: false,
-            details: Some("Install: brew install jq (macOS) or apt install jq (Linux)".into()),
+        Err(e) => {
+            result.details = Some(format!("Fix failed: {:#}", e));
+            (result, false)
         }
     }
 }
 
-pub fn run() -> anyhow::Result<()> {
-    println!("{}", "\nLoop Doctor\n".bold());
-    println!("Checking system requirements...\n");
+#[derive(Serialize)]
+struct DoctorReport {
+    checks: Vec<CheckResult>,
+    failed: usize,
+    warnings: usize,
+    fixed: usize,
+}
+
+pub fn run(output: OutputFormat, fix: bool) -> anyhow::Result<()> {
+    output.note("\nLoop Doctor\n".bold());
+    output.note("Checking system requirements...\n");
 
     let paths = resolve_paths();
 
     // Try to read config for runtime, sandbox, and backend settings
     let mut runtime = AgentRuntime::Claude;
-    let mut sandbox_enabled = false;
+    let mut sandbox_engine = None;
     let mut backend = Backend::Linear;
+    let mut state_store = StateStoreKind::default();
 
     if let Ok(config) = read_config_with_env(&paths.config_path) {
         runtime = config.runtime;
-        sandbox_enabled = config.execution.sandbox;
+        sandbox_engine = Sandbox::for_config(&config.execution).map(|s| s.engine());
         backend = config.backend;
+        state_store = config.state_store;
     }
 
-    // Run required checks
+    // Run required checks, then optional ones
     let mut results = Vec::new();
 
-    println!("{}", "Required:".bold());
-
-    let runtime_result = check_runtime_cli(runtime);
-    println!("{}", format_result(&runtime_result));
-    results.push(runtime_result);
-
-    let config_result = check_config(&paths.config_path);
-    println!("{}", format_result(&config_result));
-    results.push(config_result);
-
-    let path_result = check_path(&paths.skills_path);
-    println!("{}", format_result(&path_result));
-    results.push(path_result);
-
-    let git_result = check_git();
-    println!("{}", format_result(&git_result));
-    results.push(git_result);
-
-    let api_result = check_api_keys(&backend);
-    println!("{}", format_result(&api_result));
-    results.push(api_result);
-
-    // Optional checks
-    println!("{}", "\nOptional:".bold());
-
-    let docker_result = check_docker(sandbox_enabled);
-    println!("{}", format_result(&docker_result));
-    results.push(docker_result);
-
-    let cclean_result = check_cclean();
-    println!("{}", format_result(&cclean_result));
-    results.push(cclean_result);
+    output.note("Required:".bold());
+    let required_checks: [&dyn Fn() -> CheckResult; 5] = [
+        &|| check_runtime_cli(runtime),
+        &|| check_config(&paths.config_path),
+        &|| check_path(&paths.skills_path),
+        &check_git,
+        &|| check_api_token(backend),
+    ];
+    for check in required_checks {
+        let result = check();
+        output.note(format_result(&result));
+        results.push(result);
+    }
 
-    let tmux_result = check_tmux();
-    println!("{}", format_result(&tmux_result));
-    results.push(tmux_result);
+    output.note("\nOptional:".bold());
+    let optional_checks: [&dyn Fn() -> CheckResult; 5] = [
+        &|| check_container_engine(sandbox_engine),
+        &|| check_gh(backend),
+        &check_cclean,
+        &check_tmux,
+        &check_jq,
+    ];
+    for check in optional_checks {
+        let result = check();
+        output.note(format_result(&result));
+        results.push(result);
+    }
 
-    let jq_result = check_jq();
-    println!("{}", format_result(&jq_result));
-    results.push(jq_result);
+    output.note("\nLocal state:".bold());
+    let state_checks: [&dyn Fn() -> StateCheck; 6] = [
+        &check_shortcuts,
+        &check_mobius_gitignore,
+        &check_stale_locks,
+        &check_runtime_entries,
+        &|| check_local_counter(state_store),
+        &check_session_pointer,
+    ];
+    let mut fixed = 0;
+    for check in state_checks {
+        let (result, was_fixed) = settle_state_check(check(), fix);
+        fixed += usize::from(was_fixed);
+        output.note(format_result(&result));
+        results.push(result);
+    }
 
     // Summary
-    println!();
-    let failed: Vec<_> = results
+    let failed = results
         .iter()
         .filter(|r| matches!(r.status, CheckStatus::Fail) && r.required)
-        .collect();
-    let warnings: Vec<_> = results
+        .count();
+    let warnings = results
         .iter()
         .filter(|r| {
             matches!(r.status, CheckStatus::Warn)
                 || (matches!(r.status, CheckStatus::Fail) && !r.required)
         })
-        .collect();
+        .count();
+
+    if output.is_json() {
+        output.emit(
+            &DoctorReport {
+                checks: results,
+                failed,
+                warnings,
+                fixed,
+            },
+            |_| {},
+        )?;
+        if failed > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
 
-    if !failed.is_empty() {
-        eprintln!(
-            "{}",
-            format!("✗ {} required check(s) failed", failed.len()).red()
-        );
+    println!();
+    if fixed > 0 {
+        println!("{}", format!("✓ Fixed {} problem(s)", fixed).green());
+    }
+    if failed > 0 {
+        eprintln!("{}", format!("✗ {} required check(s) failed", failed).red());
         eprintln!(
             "{}",
             "  Run 'mobius setup' to fix configuration issues\n".dimmed()
         );
         std::process::exit(1);
-    } else if !warnings.is_empty() {
+    } else if warnings > 0 {
         println!(
             "{}",
-            format!(
-                "! All required checks passed, {} warning(s)",
-                warnings.len()
-            )
-            .yellow()
+            format!("! All required checks passed, {} warning(s)", warnings).yellow()
         );
         println!(
             "{}",
@@ -458,46 +486,3 @@ pub fn run() -> anyhow::Result<()> {
 
     Ok(())
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn runtime_cli_spec_uses_expected_command_and_label() {
-        let claude = runtime_cli_spec(AgentRuntime::Claude);
-        assert_eq!(claude.command, "claude");
-        assert_eq!(claude.display_name, "Claude CLI");
-
-        let opencode = runtime_cli_spec(AgentRuntime::Opencode);
-        assert_eq!(opencode.command, "opencode");
-        assert_eq!(opencode.display_name, "OpenCode CLI");
-    }
-
-    #[test]
-    fn opencode_runtime_does_not_require_claude_cli() {
-        let result = check_runtime_cli_with(
-            AgentRuntime::Opencode,
-            |command| command == "opencode",
-            |_| Some("opencode 1.0.0".to_string()),
-        );
-
-        assert!(matches!(result.status, CheckStatus::Pass));
-        assert_eq!(result.name, "OpenCode CLI");
-        assert!(result.message.contains("opencode 1.0.0"));
-    }
-
-    #[test]
-    fn opencode_runtime_failure_uses_opencode_install_hint() {
-        let result = check_runtime_cli_with(AgentRuntime::Opencode, |_| false, |_| None);
-
-        assert!(matches!(result.status, CheckStatus::Fail));
-        assert_eq!(result.name, "OpenCode CLI");
-        assert_eq!(result.message, "Not found");
-        assert!(result
-            .details
-            .as_deref()
-            .unwrap_or_default()
-            .contains("opencode"));
-    }
-}
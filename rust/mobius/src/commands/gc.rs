@@ -0,0 +1,416 @@
+//! GC command - Remove worktrees and branches left behind by finished issues
+//!
+//! Looks at worktrees under the configured worktree directory and at the
+//! branches mobius records for each issue in `.mobius/issues/` (the loop
+//! branch, the spec's branch and stacked sub-task branches). They are stale
+//! once their issue is done or cancelled in its local spec, or once the
+//! issue is gone from `.mobius/issues/`. Issues with a running loop and
+//! worktrees with uncommitted changes are left alone, and branches git won't
+//! delete as unmerged are kept, unless `--force` is given.
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::bisect::git_output;
+use crate::checkpoint::read_checkpoint;
+use crate::commands::clean::{is_completed_status, is_local_id};
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::context::{is_process_running, read_runtime_state};
+use crate::local_state::{get_project_mobius_path, read_parent_spec};
+use crate::stack::read_stack;
+use crate::types::enums::Backend;
+use crate::worktree::{
+    get_git_repo_root, get_worktree_base, list_worktrees, prune_worktrees, WorktreeConfig,
+};
+
+/// Why an issue's worktree and branches are stale
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum StaleReason {
+    /// The issue is no longer in `.mobius/issues/`
+    Missing,
+    /// The issue's spec has a done or cancelled status
+    Closed { status: String },
+}
+
+impl std::fmt::Display for StaleReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StaleReason::Missing => write!(f, "issue no longer exists"),
+            StaleReason::Closed { status } => write!(f, "status: {}", status),
+        }
+    }
+}
+
+/// One issue's stale worktree and branches
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GcEntry {
+    pub issue_id: String,
+    pub reason: StaleReason,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree: Option<String>,
+    pub branches: Vec<String>,
+    /// Set when the entry was left in place, e.g. a running loop
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skipped: Option<String>,
+    /// Branches `git branch -d` refused to delete, e.g. not fully merged
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub kept_branches: Vec<String>,
+    /// Worktree and branch removals that failed
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// Everything `mobius gc` reports
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub entries: Vec<GcEntry>,
+    pub worktrees_removed: usize,
+    pub branches_deleted: usize,
+}
+
+/// An issue's worktree and local branches, before deciding whether they're stale
+#[derive(Debug, Default)]
+struct IssueRefs {
+    worktree: Option<PathBuf>,
+    branches: BTreeSet<String>,
+}
+
+pub fn run(dry_run: bool, force: bool, output: OutputFormat) -> anyhow::Result<()> {
+    let config = read_config(&resolve_paths().config_path).unwrap_or_default();
+    let backend = config.backend;
+    let base_branch = config
+        .execution
+        .base_branch
+        .clone()
+        .unwrap_or_else(|| "main".to_string());
+    let worktree_config = WorktreeConfig {
+        worktree_path: config.execution.worktree_path.clone(),
+        base_branch: config.execution.base_branch.clone(),
+        runtime: config.runtime,
+        checkout: config.execution.checkout.clone(),
+    };
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let repo_root = rt.block_on(get_git_repo_root())?;
+    let worktree_base = rt.block_on(get_worktree_base(&worktree_config))?;
+    let worktrees = rt.block_on(list_worktrees())?;
+
+    let issue_ids = list_issue_ids();
+    let local_branches = list_local_branches(&repo_root)?;
+    // Never delete what the main checkout is on, or the branch loops start from
+    let mut protected: HashSet<String> = worktrees
+        .iter()
+        .filter(|w| Path::new(&w.path) == repo_root)
+        .map(|w| w.branch.clone())
+        .collect();
+    protected.insert(base_branch);
+
+    let mut refs: BTreeMap<String, IssueRefs> = BTreeMap::new();
+    if let Ok(base) = worktree_base.canonicalize() {
+        for worktree in &worktrees {
+            let path = PathBuf::from(&worktree.path);
+            if path.parent() != Some(base.as_path()) {
+                continue;
+            }
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let issue_id = issue_ids
+                .iter()
+                .find(|id| id.eq_ignore_ascii_case(name))
+                .cloned()
+                .unwrap_or_else(|| name.to_string());
+            let entry = refs.entry(issue_id).or_default();
+            entry.worktree = Some(path);
+            if local_branches.contains(&worktree.branch) {
+                entry.branches.insert(worktree.branch.clone());
+            }
+        }
+    }
+    for issue_id in &issue_ids {
+        let branches: BTreeSet<String> = recorded_branches(issue_id)
+            .into_iter()
+            .filter(|b| local_branches.contains(b))
+            .collect();
+        if !branches.is_empty() {
+            refs.entry(issue_id.clone())
+                .or_default()
+                .branches
+                .extend(branches);
+        }
+    }
+
+    let mut entries = Vec::new();
+    for (issue_id, issue_refs) in refs {
+        let status = read_parent_spec(&issue_id).map(|spec| {
+            let backend = if is_local_id(&issue_id) {
+                Backend::Local
+            } else {
+                backend
+            };
+            let done =
+                is_completed_status(&spec.status, &backend, config.status_mapping_for(backend));
+            (spec.status, done)
+        });
+        let Some(reason) = stale_reason(status) else {
+            continue;
+        };
+        let branches: Vec<String> = issue_refs
+            .branches
+            .into_iter()
+            .filter(|b| !protected.contains(b))
+            .collect();
+        if issue_refs.worktree.is_none() && branches.is_empty() {
+            continue;
+        }
+        let skipped = if loop_is_running(&issue_id) {
+            Some("loop is running".to_string())
+        } else if !force && issue_refs.worktree.as_deref().is_some_and(is_dirty) {
+            Some("worktree has uncommitted changes (use --force)".to_string())
+        } else {
+            None
+        };
+        entries.push(GcEntry {
+            issue_id,
+            reason,
+            worktree: issue_refs.worktree.map(|p| p.display().to_string()),
+            branches,
+            skipped,
+            kept_branches: Vec::new(),
+            errors: Vec::new(),
+        });
+    }
+
+    let mut report = GcReport {
+        dry_run,
+        entries,
+        worktrees_removed: 0,
+        branches_deleted: 0,
+    };
+    if !dry_run {
+        remove_stale(&repo_root, &mut report, force);
+        if let Err(e) = rt.block_on(prune_worktrees()) {
+            output.note(format!("Warning: {}", e).yellow());
+        }
+    }
+    output.emit(&report, print_gc_report)
+}
+
+/// Why an issue's worktree and branches can go, from its spec's status and
+/// whether that status counts as done; `None` keeps them.
+fn stale_reason(status: Option<(String, bool)>) -> Option<StaleReason> {
+    match status {
+        None => Some(StaleReason::Missing),
+        Some((status, true)) => Some(StaleReason::Closed { status }),
+        Some((_, false)) => None,
+    }
+}
+
+/// Issue directories in `.mobius/issues/`.
+fn list_issue_ids() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(get_project_mobius_path().join("issues")) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|e| e.file_name().to_str().map(str::to_string))
+        .collect()
+}
+
+/// Branches mobius may have created for `issue_id`: the loop branch, the
+/// spec's branch and any stacked sub-task branches.
+fn recorded_branches(issue_id: &str) -> Vec<String> {
+    let mut branches = vec![format!("feat/{}", issue_id.to_lowercase())];
+    if let Some(spec) = read_parent_spec(issue_id) {
+        branches.push(spec.git_branch_name);
+    }
+    if let Some(checkpoint) = read_checkpoint(issue_id) {
+        branches.push(checkpoint.branch_name);
+    }
+    if let Some(stack) = read_stack(issue_id) {
+        branches.extend(stack.entries.into_iter().map(|e| e.branch));
+    }
+    branches.retain(|b| !b.is_empty());
+    branches
+}
+
+fn list_local_branches(repo_root: &Path) -> anyhow::Result<HashSet<String>> {
+    let out = git_output(
+        repo_root,
+        &["for-each-ref", "--format=%(refname:short)", "refs/heads"],
+    )?;
+    Ok(out.lines().map(str::to_string).collect())
+}
+
+fn loop_is_running(issue_id: &str) -> bool {
+    read_runtime_state(issue_id)
+        .and_then(|state| state.loop_pid)
+        .is_some_and(|pid| pid != 0 && is_process_running(pid))
+}
+
+fn is_dirty(worktree: &Path) -> bool {
+    git_output(worktree, &["status", "--porcelain"]).is_ok_and(|out| !out.trim().is_empty())
+}
+
+/// Remove each entry's worktree, then its branches, recording failures.
+///
+/// Without `force`, branches go through `git branch -d`, and one git refuses
+/// to delete (e.g. unmerged work of an issue that vanished) is kept.
+fn remove_stale(repo_root: &Path, report: &mut GcReport, force: bool) {
+    for entry in report.entries.iter_mut().filter(|e| e.skipped.is_none()) {
+        if let Some(worktree) = &entry.worktree {
+            let mut args = vec!["worktree", "remove"];
+            if force {
+                args.push("--force");
+            }
+            args.push(worktree);
+            match git_output(repo_root, &args) {
+                Ok(_) => report.worktrees_removed += 1,
+                Err(e) => entry.errors.push(format!("{:#}", e)),
+            }
+        }
+        for branch in &entry.branches {
+            let delete = if force { "-D" } else { "-d" };
+            match git_output(repo_root, &["branch", delete, branch]) {
+                Ok(_) => report.branches_deleted += 1,
+                Err(_) if !force => entry.kept_branches.push(branch.clone()),
+                Err(e) => entry.errors.push(format!("{:#}", e)),
+            }
+        }
+    }
+}
+
+fn print_gc_report(report: &GcReport) {
+    if report.entries.is_empty() {
+        println!("{}", "No stale worktrees or branches found.".green());
+        return;
+    }
+    for entry in &report.entries {
+        println!(
+            "{}  {}",
+            entry.issue_id.cyan(),
+            format!("({})", entry.reason).dimmed()
+        );
+        if let Some(worktree) = &entry.worktree {
+            println!("  worktree {}", worktree);
+        }
+        for branch in &entry.branches {
+            println!("  branch   {}", branch);
+        }
+        if let Some(skipped) = &entry.skipped {
+            println!("  {}", format!("Skipped: {}", skipped).yellow());
+        }
+        for branch in &entry.kept_branches {
+            println!(
+                "  {}",
+                format!("Kept {}: git branch -d refused (use --force)", branch).yellow()
+            );
+        }
+        for error in &entry.errors {
+            println!("  {}", format!("Warning: {}", error).yellow());
+        }
+    }
+    println!();
+    if report.dry_run {
+        println!("{}", "Dry run — nothing was removed.".yellow());
+    } else {
+        println!(
+            "{}",
+            format!(
+                "Removed {} worktree{} and {} branch{}.",
+                report.worktrees_removed,
+                if report.worktrees_removed == 1 {
+                    ""
+                } else {
+                    "s"
+                },
+                report.branches_deleted,
+                if report.branches_deleted == 1 {
+                    ""
+                } else {
+                    "es"
+                }
+            )
+            .green()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_reason() {
+        assert_eq!(stale_reason(None), Some(StaleReason::Missing));
+        assert_eq!(
+            stale_reason(Some(("Canceled".to_string(), true))),
+            Some(StaleReason::Closed {
+                status: "Canceled".to_string()
+            })
+        );
+        assert_eq!(stale_reason(Some(("In Progress".to_string(), false))), None);
+    }
+
+    #[test]
+    fn test_remove_stale_keeps_unmerged_branches() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .current_dir(repo)
+                .args(args)
+                .env("GIT_AUTHOR_NAME", "Dev")
+                .env("GIT_AUTHOR_EMAIL", "dev@example.com")
+                .env("GIT_COMMITTER_NAME", "Dev")
+                .env("GIT_COMMITTER_EMAIL", "dev@example.com")
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "base"]);
+        git(&["branch", "feat/merged"]);
+        git(&["checkout", "-q", "-b", "feat/unmerged"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "unpushed work"]);
+        git(&["checkout", "-q", "main"]);
+
+        let report = || GcReport {
+            dry_run: false,
+            entries: vec![GcEntry {
+                issue_id: "MOB-1".to_string(),
+                reason: StaleReason::Missing,
+                worktree: None,
+                branches: vec!["feat/merged".to_string(), "feat/unmerged".to_string()],
+                skipped: None,
+                kept_branches: Vec::new(),
+                errors: Vec::new(),
+            }],
+            worktrees_removed: 0,
+            branches_deleted: 0,
+        };
+
+        let mut safe = report();
+        remove_stale(repo, &mut safe, false);
+        assert_eq!(safe.branches_deleted, 1);
+        assert_eq!(safe.entries[0].kept_branches, vec!["feat/unmerged"]);
+        assert!(safe.entries[0].errors.is_empty());
+
+        let mut forced = report();
+        forced.entries[0].branches = vec!["feat/unmerged".to_string()];
+        remove_stale(repo, &mut forced, true);
+        assert_eq!(forced.branches_deleted, 1);
+        assert!(forced.entries[0].kept_branches.is_empty());
+    }
+}
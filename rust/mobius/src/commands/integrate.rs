@@ -0,0 +1,205 @@
+//! Integrate command - Merge completed sub-task branches into an integration
+//! branch in dependency order, verifying after each merge
+
+use std::time::Duration;
+
+use colored::Colorize;
+
+use crate::commands::churn::loop_branch;
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::integration::{
+    build_missing_branches, default_integration_branch, get_integration_log_dir, merge_order,
+    run_integration, write_integration_report, IntegrationOutcome, IntegrationPlan,
+    IntegrationReport, MergeStatus, MergeStep,
+};
+use crate::local_state::read_local_subtasks_as_linear_issues;
+use crate::types::task_graph::build_task_graph;
+use crate::verify::read_verify_commands;
+
+pub fn run(
+    task_id: &str,
+    branch: Option<&str>,
+    base: Option<&str>,
+    command: Option<&str>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let config = read_config(&resolve_paths().config_path).unwrap_or_default();
+    let verification = config.execution.verification.clone().unwrap_or_default();
+
+    let issues = read_local_subtasks_as_linear_issues(task_id);
+    if issues.is_empty() {
+        anyhow::bail!(
+            "No local sub-tasks found for {}. Run 'mobius pull {}' first.",
+            task_id,
+            task_id
+        );
+    }
+    let graph = build_task_graph(task_id, task_id, &issues);
+    let (loop_branch, repo_path) = loop_branch(task_id);
+    let candidates = merge_order(&graph, &loop_branch);
+    if candidates.is_empty() {
+        anyhow::bail!("No completed sub-tasks of {} to integrate", task_id);
+    }
+
+    let base = base
+        .map(str::to_string)
+        .or_else(|| config.execution.base_branch.clone())
+        .unwrap_or_else(|| "main".to_string());
+    let built = build_missing_branches(
+        &repo_path,
+        task_id,
+        &graph,
+        &base,
+        &loop_branch,
+        &candidates,
+    )?;
+    if !built.is_empty() {
+        output.note(
+            format!(
+                "Built {} sub-task branch(es) from {}: {}",
+                built.len(),
+                loop_branch,
+                built.join(", ")
+            )
+            .dimmed(),
+        );
+    }
+
+    let plan = IntegrationPlan {
+        parent_id: task_id.to_string(),
+        base,
+        branch: branch.map_or_else(|| default_integration_branch(task_id), str::to_string),
+        candidates,
+        verify_commands: read_verify_commands(task_id),
+        command: command.map(str::to_string),
+        timeout: Duration::from_secs(u64::from(verification.verify_timeout_minutes) * 60),
+        log_dir: get_integration_log_dir(task_id),
+    };
+
+    output.note(
+        format!(
+            "Merging {} sub-task branch(es) into {} from {}...",
+            plan.candidates.len(),
+            plan.branch,
+            plan.base
+        )
+        .dimmed(),
+    );
+    let report = run_integration(&plan, &repo_path, |step| {
+        if !output.is_json() {
+            print_step(step);
+        }
+    })?;
+    write_integration_report(&report)?;
+
+    output.emit(&report, print_report)?;
+    match report.outcome {
+        IntegrationOutcome::Integrated if !has_merged(&report) => anyhow::bail!(
+            "None of the completed sub-tasks of {} has a branch to merge",
+            task_id
+        ),
+        IntegrationOutcome::Integrated => Ok(()),
+        IntegrationOutcome::Conflict => anyhow::bail!(
+            "Stopped at a merge conflict; {} is at the last verified merge",
+            report.branch
+        ),
+        IntegrationOutcome::VerifyFailed => anyhow::bail!(
+            "Stopped at a failing verify command; {} is at the last verified merge",
+            report.branch
+        ),
+    }
+}
+
+/// Whether the integration branch holds any sub-task's work.
+fn has_merged(report: &IntegrationReport) -> bool {
+    report
+        .steps
+        .iter()
+        .any(|s| matches!(s.status, MergeStatus::Merged | MergeStatus::UpToDate))
+}
+
+fn print_step(step: &MergeStep) {
+    let label = format!("{} {}", step.identifier, step.branch.dimmed());
+    match step.status {
+        MergeStatus::Merged => println!("  {} {}", "merged".green(), label),
+        MergeStatus::UpToDate => println!("  {} {}", "up to date".green(), label),
+        MergeStatus::NoBranch => println!("  {} {}", "no branch".yellow(), label),
+        MergeStatus::Conflict => println!("  {} {}", "CONFLICT".red(), label),
+        MergeStatus::VerifyFailed => println!("  {} {}", "VERIFY FAILED".red(), label),
+    }
+}
+
+fn print_report(report: &IntegrationReport) {
+    let merged = report
+        .steps
+        .iter()
+        .filter(|s| s.status == MergeStatus::Merged)
+        .count();
+    println!();
+    match report.outcome {
+        IntegrationOutcome::Integrated if !has_merged(report) => {
+            println!("{} Nothing merged into {}", "✗".red(), report.branch.bold())
+        }
+        IntegrationOutcome::Integrated => println!(
+            "{} Merged {} branch(es) into {}",
+            "✓".green(),
+            merged,
+            report.branch.bold()
+        ),
+        IntegrationOutcome::Conflict | IntegrationOutcome::VerifyFailed => println!(
+            "{} {} stops at the last verified merge ({}, {} merged)",
+            "✗".red(),
+            report.branch.bold(),
+            &report.head[..report.head.len().min(8)],
+            merged
+        ),
+    }
+
+    let skipped: Vec<&str> = report
+        .steps
+        .iter()
+        .filter(|s| s.status == MergeStatus::NoBranch)
+        .map(|s| s.identifier.as_str())
+        .collect();
+    if !skipped.is_empty() {
+        println!(
+            "  {}",
+            format!("Skipped (no branch): {}", skipped.join(", ")).yellow()
+        );
+    }
+
+    if let Some(step) = report.steps.last() {
+        match step.status {
+            MergeStatus::Conflict => {
+                println!(
+                    "  {} conflicts with the integration branch in:",
+                    step.identifier.red().bold()
+                );
+                for file in &step.conflicts {
+                    println!("    {}", file);
+                }
+            }
+            MergeStatus::VerifyFailed => {
+                if let Some(check) = step.checks.iter().find(|c| !c.run.passed) {
+                    let owner = check.subtask_id.as_deref().unwrap_or("the verify command");
+                    println!(
+                        "  Merging {} broke {}: {}",
+                        step.identifier.red().bold(),
+                        owner,
+                        check.run.failure_message()
+                    );
+                    println!("  {}", format!("Full output: {}", check.log).dimmed());
+                }
+            }
+            _ => {}
+        }
+    }
+    if !report.not_merged.is_empty() {
+        println!(
+            "  {}",
+            format!("Not attempted: {}", report.not_merged.join(", ")).dimmed()
+        );
+    }
+}
@@ -1,14 +1,24 @@
 //! List command - Display local issues with interactive selector
 
 use colored::Colorize;
+use serde::Serialize;
 use std::fs;
 
+use crate::commands::output::OutputFormat;
 use crate::config::loader::read_config;
 use crate::config::paths::resolve_paths;
 use crate::local_state::{get_project_mobius_path, read_parent_spec};
 use crate::types::enums::Backend;
 
-pub fn run(backend_override: Option<&str>) -> anyhow::Result<()> {
+/// Local issue entry for `--output json`
+#[derive(Serialize)]
+struct ListedIssue {
+    identifier: String,
+    title: String,
+    status: String,
+}
+
+pub fn run(backend_override: Option<&str>, output: OutputFormat) -> anyhow::Result<()> {
     let paths = resolve_paths();
     let config = read_config(&paths.config_path).unwrap_or_default();
     let _backend: Backend = if let Some(b) = backend_override {
@@ -21,6 +31,7 @@ pub fn run(backend_override: Option<&str>) -> anyhow::Result<()> {
 
     let entries = match fs::read_dir(&issues_path) {
         Ok(entries) => entries,
+        Err(_) if output.is_json() => return output.emit(&Vec::<ListedIssue>::new(), |_| {}),
         Err(_) => {
             eprintln!("{}", "No local issues found.".yellow());
             eprintln!(
@@ -40,6 +51,21 @@ pub fn run(backend_override: Option<&str>) -> anyhow::Result<()> {
         }
     }
 
+    dirs.sort();
+
+    if output.is_json() {
+        let issues: Vec<ListedIssue> = dirs
+            .iter()
+            .filter_map(|issue_id| read_parent_spec(issue_id))
+            .map(|spec| ListedIssue {
+                identifier: spec.identifier,
+                title: spec.title,
+                status: spec.status,
+            })
+            .collect();
+        return output.emit(&issues, |_| {});
+    }
+
     if dirs.is_empty() {
         eprintln!("{}", "No local issues found.".yellow());
         eprintln!(
@@ -49,8 +75,6 @@ pub fn run(backend_override: Option<&str>) -> anyhow::Result<()> {
         return Ok(());
     }
 
-    dirs.sort();
-
     let mut choices: Vec<(String, String)> = Vec::new();
 
     for issue_id in &dirs {
@@ -0,0 +1,159 @@
+//! Listen command - Receive Linear webhooks and apply them to local issue state
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::Context;
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::Router;
+use colored::Colorize;
+use tokio::net::TcpListener;
+
+use crate::config::loader::read_config_with_env;
+use crate::config::paths::resolve_paths;
+use crate::linear_webhook::{
+    apply_webhook, is_fresh, verify_signature, WebhookOutcome, WebhookPayload, SIGNATURE_HEADER,
+};
+use crate::shutdown;
+use crate::types::enums::Backend;
+
+/// Path to register as the webhook URL in Linear
+const WEBHOOK_PATH: &str = "/webhooks/linear";
+
+struct Listener {
+    secret: Option<String>,
+}
+
+pub fn run(bind: &str, port: u16, secret: Option<&str>) -> anyhow::Result<()> {
+    let paths = resolve_paths();
+    let config = read_config_with_env(&paths.config_path).unwrap_or_default();
+    if config.backend != Backend::Linear {
+        anyhow::bail!(
+            "mobius listen receives Linear webhooks, but the configured backend is {}",
+            config.backend
+        );
+    }
+    let _ = shutdown::install_handler(|| {});
+
+    let state = Arc::new(Listener {
+        secret: secret.map(str::to_string),
+    });
+    let app = Router::new()
+        .route(WEBHOOK_PATH, post(receive))
+        .route("/health", get(|| async { "ok" }))
+        .with_state(state);
+
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(async {
+        let listener = TcpListener::bind((bind, port))
+            .await
+            .with_context(|| format!("Failed to listen on {}:{}", bind, port))?;
+        let addr: SocketAddr = listener.local_addr()?;
+        // Deliveries rewrite specs that agents are prompted with
+        if secret.is_none() && !addr.ip().is_loopback() {
+            anyhow::bail!(
+                "Refusing to accept unsigned webhooks on {}; set --secret or LINEAR_WEBHOOK_SECRET",
+                addr
+            );
+        }
+        if secret.is_none() {
+            eprintln!(
+                "{}",
+                "⚠ No webhook secret set (--secret or LINEAR_WEBHOOK_SECRET); deliveries are not authenticated"
+                    .yellow()
+            );
+        }
+        println!(
+            "{}",
+            format!("Listening for Linear webhooks on http://{}{}", addr, WEBHOOK_PATH).green()
+        );
+        println!(
+            "{}",
+            "Register that URL (publicly reachable) under Linear → Settings → API → Webhooks, with Issue and Comment events."
+                .dimmed()
+        );
+        axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown::interrupted())
+            .await
+            .context("Webhook server failed")
+    })
+}
+
+async fn receive(
+    State(state): State<Arc<Listener>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    if let Some(secret) = &state.secret {
+        let signature = headers
+            .get(SIGNATURE_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        if !verify_signature(secret, &body, signature) {
+            eprintln!("{}", "✗ Rejected delivery with a bad signature".red());
+            return StatusCode::UNAUTHORIZED;
+        }
+    }
+
+    let payload: WebhookPayload = match serde_json::from_slice(&body) {
+        Ok(payload) => payload,
+        Err(e) => {
+            eprintln!("{}", format!("✗ Unreadable delivery: {}", e).red());
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+    if state.secret.is_some() {
+        let sent = payload.webhook_timestamp.unwrap_or_default();
+        if !is_fresh(sent, chrono::Utc::now().timestamp_millis()) {
+            eprintln!("{}", "✗ Rejected a stale or replayed delivery".red());
+            return StatusCode::UNAUTHORIZED;
+        }
+    }
+
+    match apply_webhook(&payload) {
+        Ok(outcome) => {
+            print_outcome(&outcome);
+            StatusCode::OK
+        }
+        Err(e) => {
+            eprintln!(
+                "{}",
+                format!(
+                    "✗ Failed to apply {} {}: {:#}",
+                    payload.kind, payload.action, e
+                )
+                .red()
+            );
+            // Linear retries non-2xx deliveries
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+fn print_outcome(outcome: &WebhookOutcome) {
+    let time = chrono::Local::now().format("%H:%M:%S");
+    let line = match outcome {
+        WebhookOutcome::TaskUpdated {
+            parent,
+            task,
+            status: Some(status),
+        } => format!("{} → {} ({})", task, status.cyan(), parent),
+        WebhookOutcome::TaskUpdated { parent, task, .. } => {
+            format!("{} updated ({})", task, parent)
+        }
+        WebhookOutcome::TaskCreated { parent, task } => {
+            format!("+ {} new sub-task of {}", task, parent)
+                .green()
+                .to_string()
+        }
+        WebhookOutcome::ParentUpdated { parent } => format!("{} updated", parent),
+        WebhookOutcome::CommentAdded { parent, task } => {
+            format!("{} new comment ({})", task, parent)
+        }
+        WebhookOutcome::Ignored(reason) => format!("ignored: {}", reason).dimmed().to_string(),
+    };
+    println!("{} {}", time.to_string().dimmed(), line);
+}
@@ -0,0 +1,166 @@
+//! Lock command - Inspect, take or break a worktree's git lock
+//!
+//! Agents sharing a worktree take turns on its git lock through a FIFO queue.
+//! `run` holds the lock for one git command; `status` shows the holder and who
+//! is waiting; `release` removes a lock whose holder is stuck.
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::output::OutputFormat;
+use crate::git_lock::{
+    force_release_lock, get_lock_info, get_lock_queue, is_locked, with_lock, LockInfo, LockWaiter,
+};
+
+/// How long `lock run` waits for its turn; a queue of agents committing one
+/// after another can take a while to drain
+const RUN_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// `mobius lock status` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockStatus {
+    pub worktree: String,
+    /// Held by a live process and not stale
+    pub locked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder: Option<LockInfo>,
+    /// Waiting processes, first in line first
+    pub queue: Vec<LockWaiter>,
+}
+
+/// `mobius lock release` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockRelease {
+    pub worktree: String,
+    /// Holder of the lock that was removed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub released: Option<LockInfo>,
+}
+
+pub fn run_status(path: Option<&str>, output: OutputFormat) -> anyhow::Result<()> {
+    let worktree = worktree_path(path)?;
+    let rt = tokio::runtime::Runtime::new()?;
+    let status = LockStatus {
+        worktree: worktree.clone(),
+        locked: rt.block_on(is_locked(Path::new(&worktree))),
+        holder: rt.block_on(get_lock_info(Path::new(&worktree))),
+        queue: rt.block_on(get_lock_queue(Path::new(&worktree))),
+    };
+    output.emit(&status, print_lock_status)
+}
+
+pub fn run_release(path: Option<&str>, output: OutputFormat) -> anyhow::Result<()> {
+    let worktree = worktree_path(path)?;
+    let rt = tokio::runtime::Runtime::new()?;
+    let released = rt.block_on(get_lock_info(Path::new(&worktree)));
+    rt.block_on(force_release_lock(Path::new(&worktree)))?;
+    let report = LockRelease { worktree, released };
+    output.emit(&report, |report| match &report.released {
+        Some(holder) => println!(
+            "{} Released git lock held by {}",
+            "✓".green(),
+            describe(holder.pid, holder.task.as_deref())
+        ),
+        None => println!("{}", format!("No git lock in {}", report.worktree).yellow()),
+    })
+}
+
+/// Run `command` in the worktree while holding its git lock for `task`.
+pub fn run_locked(
+    path: Option<&str>,
+    task: Option<&str>,
+    command: &[String],
+) -> anyhow::Result<()> {
+    let Some((program, args)) = command.split_first() else {
+        anyhow::bail!("No command given. Usage: mobius lock run -- git commit -m \"...\"");
+    };
+    let worktree = worktree_path(path)?;
+    let rt = tokio::runtime::Runtime::new()?;
+    let status = rt.block_on(with_lock(
+        Path::new(&worktree),
+        task,
+        Some(RUN_TIMEOUT),
+        || async {
+            Command::new(program)
+                .args(args)
+                .current_dir(&worktree)
+                .status()
+                .map_err(|e| anyhow::anyhow!("Failed to run {}: {}", program, e))
+        },
+    ))?;
+    if !status.success() {
+        anyhow::bail!("{} exited with {}", command.join(" "), status);
+    }
+    Ok(())
+}
+
+/// The worktree to inspect, defaulting to the current directory.
+fn worktree_path(path: Option<&str>) -> anyhow::Result<String> {
+    let path = match path {
+        Some(path) => Path::new(path).to_path_buf(),
+        None => std::env::current_dir()?,
+    };
+    if !path.is_dir() {
+        anyhow::bail!("{} is not a directory", path.display());
+    }
+    Ok(path.display().to_string())
+}
+
+fn describe(pid: u32, task: Option<&str>) -> String {
+    match task {
+        Some(task) => format!("{} (PID {})", task, pid),
+        None => format!("PID {}", pid),
+    }
+}
+
+fn print_lock_status(status: &LockStatus) {
+    println!("{}", status.worktree.bold());
+    match (&status.holder, status.locked) {
+        (Some(holder), true) => println!(
+            "  {} held by {} on {} since {}",
+            "●".yellow(),
+            describe(holder.pid, holder.task.as_deref()),
+            holder.hostname,
+            holder.acquired_at
+        ),
+        (Some(holder), false) => println!(
+            "  {} stale lock left by {} since {} (cleared on the next acquire, or run mobius lock release)",
+            "●".red(),
+            describe(holder.pid, holder.task.as_deref()),
+            holder.acquired_at
+        ),
+        (None, true) => println!("  {} held, holder unknown", "●".yellow()),
+        (None, false) => println!("  {} free", "○".green()),
+    }
+    if status.queue.is_empty() {
+        return;
+    }
+    println!("  {} waiting:", status.queue.len());
+    for (position, waiter) in status.queue.iter().enumerate() {
+        println!(
+            "  {:>3}. {} {}",
+            position + 1,
+            describe(waiter.pid, waiter.task.as_deref()),
+            format!("since {}", waiter.enqueued_at).dimmed()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worktree_path_requires_directory() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path().display().to_string();
+        assert_eq!(worktree_path(Some(&dir)).unwrap(), dir);
+        assert!(worktree_path(Some(&format!("{}/missing", dir))).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Logs command - Show the stream-json output captured for each agent
+
+use colored::Colorize;
+use serde::Serialize;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use crate::commands::output::OutputFormat;
+use crate::stream_json::{agent_output_dir, parse_log_events, LogEvent, LogEventKind};
+
+/// Poll interval for `--follow`
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A sub-task's captured agent output
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentLog {
+    pub identifier: String,
+    pub path: String,
+    pub size_bytes: u64,
+    pub modified: Option<String>,
+}
+
+pub fn run(
+    task_id: &str,
+    subtask: Option<&str>,
+    follow: bool,
+    raw: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let dir = agent_output_dir(task_id);
+    let Some(subtask) = subtask else {
+        let logs = list_agent_logs(&dir);
+        return output.emit(&logs, |logs| print_log_list(task_id, &dir, logs));
+    };
+
+    let path =
+        find_agent_log(&dir, subtask).unwrap_or_else(|| dir.join(format!("{}.jsonl", subtask)));
+    // JSON output is the stream itself
+    let raw = raw || output.is_json();
+
+    if follow {
+        return follow_log(&path, raw, output);
+    }
+
+    let content = fs::read_to_string(&path).map_err(|_| {
+        anyhow::anyhow!(
+            "No agent log for {} at {}. Run 'mobius logs {}' to list available logs.",
+            subtask,
+            path.display(),
+            task_id
+        )
+    })?;
+    for line in content.lines() {
+        print_line(line, raw);
+    }
+    Ok(())
+}
+
+/// Agent logs in `dir`, ordered by sub-task identifier.
+pub fn list_agent_logs(dir: &Path) -> Vec<AgentLog> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut logs: Vec<AgentLog> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+            Some(AgentLog {
+                identifier: path.file_stem()?.to_string_lossy().to_string(),
+                path: path.display().to_string(),
+                size_bytes: metadata.len(),
+                modified: metadata.modified().ok().map(|t| {
+                    chrono::DateTime::<chrono::Local>::from(t)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                }),
+            })
+        })
+        .collect();
+    logs.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    logs
+}
+
+/// Match a sub-task identifier to its log, ignoring case.
+pub fn find_agent_log(dir: &Path, subtask: &str) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.extension().and_then(|e| e.to_str()) == Some("jsonl")
+                && path
+                    .file_stem()
+                    .is_some_and(|stem| stem.to_string_lossy().eq_ignore_ascii_case(subtask))
+        })
+}
+
+fn print_log_list(task_id: &str, dir: &Path, logs: &[AgentLog]) {
+    if logs.is_empty() {
+        println!(
+            "{}",
+            format!("No agent logs for {} in {}", task_id, dir.display()).yellow()
+        );
+        return;
+    }
+    println!("{}", format!("Agent logs for {}:", task_id).bold());
+    for log in logs {
+        println!(
+            "  {:<16} {:>10}  {}",
+            log.identifier.cyan(),
+            format_size(log.size_bytes),
+            log.modified.as_deref().unwrap_or("").dimmed()
+        );
+    }
+    println!();
+    println!(
+        "{}",
+        format!(
+            "View one with 'mobius logs {} <subtask> [--follow]'",
+            task_id
+        )
+        .dimmed()
+    );
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{} B", bytes)
+    }
+}
+
+/// Print one stream-json line; returns whether it was the final `result` event.
+fn print_line(line: &str, raw: bool) -> bool {
+    let events = parse_log_events(line);
+    if raw {
+        if !line.trim().is_empty() {
+            println!("{}", line);
+        }
+    } else {
+        for event in &events {
+            print_event(event);
+        }
+    }
+    events
+        .iter()
+        .any(|e| matches!(e.kind, LogEventKind::Result | LogEventKind::ResultError))
+}
+
+fn print_event(event: &LogEvent) {
+    match event.kind {
+        LogEventKind::System => println!("{}", format!("● {}", event.text).dimmed()),
+        LogEventKind::Text => println!("{}", event.text),
+        LogEventKind::ToolUse => println!("{} {}", "→".cyan(), event.text.cyan()),
+        LogEventKind::ToolResult => println!("  {}", event.text.dimmed()),
+        LogEventKind::ToolError => println!("  {}", event.text.red()),
+        LogEventKind::Result => println!("{}", format!("✓ {}", event.text).green().bold()),
+        LogEventKind::ResultError => println!("{}", format!("✗ {}", event.text).red().bold()),
+    }
+}
+
+/// Tail the log until the agent's final `result` event, waiting for the file if
+/// the agent has not started yet.
+fn follow_log(path: &Path, raw: bool, output: OutputFormat) -> anyhow::Result<()> {
+    if !path.exists() {
+        output.note(format!("Waiting for {}...", path.display()).dimmed());
+    }
+    let mut tail = LogTail::new(path);
+    loop {
+        let mut finished = false;
+        for line in tail.read_lines()? {
+            finished |= print_line(&line, raw);
+        }
+        if finished {
+            return Ok(());
+        }
+        thread::sleep(FOLLOW_POLL_INTERVAL);
+    }
+}
+
+/// Reads the lines appended to a log since the last call.
+pub struct LogTail {
+    path: PathBuf,
+    offset: u64,
+    /// Trailing partial line, completed by a later read
+    pending: String,
+}
+
+impl LogTail {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            offset: 0,
+            pending: String::new(),
+        }
+    }
+
+    /// Complete lines written since the last call; none while the file is
+    /// missing.
+    pub fn read_lines(&mut self) -> std::io::Result<Vec<String>> {
+        let Ok(mut file) = fs::File::open(&self.path) else {
+            return Ok(Vec::new());
+        };
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if len < self.offset {
+            // Truncated by a retry; start over
+            self.offset = 0;
+            self.pending.clear();
+        }
+        if len <= self.offset {
+            return Ok(Vec::new());
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut chunk = Vec::new();
+        file.read_to_end(&mut chunk)?;
+        self.offset += chunk.len() as u64;
+        self.pending.push_str(&String::from_utf8_lossy(&chunk));
+
+        let complete = match self.pending.rfind('\n') {
+            Some(idx) => self.pending.drain(..=idx).collect::<String>(),
+            None => String::new(),
+        };
+        Ok(complete.lines().map(str::to_string).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_and_find_agent_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("MOB-2.jsonl"), "{}\n").unwrap();
+        fs::write(dir.path().join("MOB-1.jsonl"), "").unwrap();
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let logs = list_agent_logs(dir.path());
+        let ids: Vec<&str> = logs.iter().map(|l| l.identifier.as_str()).collect();
+        assert_eq!(ids, vec!["MOB-1", "MOB-2"]);
+        assert_eq!(logs[1].size_bytes, 3);
+
+        assert_eq!(
+            find_agent_log(dir.path(), "mob-2"),
+            Some(dir.path().join("MOB-2.jsonl"))
+        );
+        assert_eq!(find_agent_log(dir.path(), "notes"), None);
+        assert!(list_agent_logs(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_log_tail_returns_complete_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("MOB-1.jsonl");
+        let mut tail = LogTail::new(&path);
+        assert!(tail.read_lines().unwrap().is_empty());
+
+        fs::write(&path, "{\"a\":1}\n{\"b\"").unwrap();
+        assert_eq!(tail.read_lines().unwrap(), vec!["{\"a\":1}"]);
+        fs::write(&path, "{\"a\":1}\n{\"b\":2}\n").unwrap();
+        assert_eq!(tail.read_lines().unwrap(), vec!["{\"b\":2}"]);
+        // A retry truncates the log
+        fs::write(&path, "{}\n").unwrap();
+        assert_eq!(tail.read_lines().unwrap(), vec!["{}"]);
+    }
+}
@@ -1,47 +1,91 @@
 //! Loop command - Main parallel orchestrator for task execution
 //!
 //! Orchestrates parallel execution of sub-tasks with worktree isolation
-//! and tmux-based display.
+//! and tmux-based display (or plain child processes with `driver: process`).
 
 use colored::Colorize;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use anyhow::Context as AnyhowContext;
-
+use tracing::{field, info_span};
+
+use crate::agent_pool::{distribute, integrate_branch, publish_branch, BatchSpec, Coordinator};
+use crate::bootstrap::{run_bootstrap, BootstrapOutcome};
+use crate::build_cache::{link_caches, plan_caches};
+use crate::checkpoint::{delete_checkpoint, load_checkpoint, write_checkpoint, Checkpoint};
+use crate::churn::generate_churn_report;
+use crate::completion::{read_agent_result, result_file_for};
 use crate::config::loader::read_config_with_env;
 use crate::config::paths::resolve_paths;
 use crate::context::{
-    add_runtime_active_task, clear_all_runtime_active_tasks, complete_runtime_task,
-    create_session as create_mobius_session, delete_runtime_state, end_session, fail_runtime_task,
-    generate_context, initialize_runtime_state, remove_runtime_active_task,
-    update_runtime_task_pane, write_full_context_file, write_runtime_state,
+    add_runtime_active_task, add_runtime_task_cost, apply_task_action, changed_specs,
+    clear_all_runtime_active_tasks, complete_runtime_task, create_session as create_mobius_session,
+    delete_runtime_state, end_session, fail_runtime_task, generate_context,
+    get_pending_updates_count, get_results_directory_path, initialize_runtime_state, is_paused,
+    read_last_pull, recalculate_total_tokens, remove_runtime_active_task, set_offline_flag,
+    set_paused, spec_fingerprint, take_task_actions, update_runtime_task_pane,
+    update_runtime_task_tokens, update_session, write_full_context_file, write_runtime_state,
 };
+use crate::cost::{budget_exceeded, estimate_cost_usd, format_cost};
+use crate::debug_logger::{debug_log, initialize_debug_logger, is_debug_enabled};
+use crate::error::MobiusError;
 use crate::executor::{
-    calculate_parallelism, execute_parallel, select_model_for_task, ExecutionContext,
+    calculate_parallelism, execute_parallel, resolve_task_runtime, ExecutionContext,
 };
+use crate::external_deps::{apply_resolved_blockers, refresh_external_blockers};
+use crate::failure::{classify_failure, is_provider_incident, FailureSignals};
+use crate::git_lock::with_lock;
+use crate::github::GithubClient;
+use crate::gitlab::GitlabClient;
 use crate::jira::JiraClient;
 use crate::local_state::{
     read_local_subtasks_as_linear_issues, read_parent_spec, read_subtasks, update_subtask_status,
     write_iteration_log, IterationLogEntry, IterationStatus,
 };
+use crate::notifications::{Notification, Notifier};
+use crate::offline::is_offline;
+use crate::output_archive::{archive_output, TaskOutput};
+use crate::preflight::{format_result, run_preflight, CheckStatus, PreflightOptions};
+use crate::process_executor::ProcessExecutor;
+use crate::report::generate_html_report;
 use crate::runtime_adapter;
+use crate::sandbox::Sandbox;
+use crate::shutdown;
+use crate::status_sync::sync_human_task_statuses;
+use crate::stream_json::parse_result_event;
+use crate::subtask_sync::{refresh_subtasks, supports_subtask_refresh};
+use crate::telemetry;
 use crate::tmux::{
     create_session, create_status_pane, destroy_session, get_session_name, update_status_pane,
     LoopStatus, TmuxSession,
 };
 use crate::tracker::{
-    assign_task, create_tracker, get_retry_tasks, has_permanent_failures, process_results,
+    assign_task, create_tracker_from_config, forget_task, get_retry_tasks, has_permanent_failures,
+    process_results, retry_count, retry_wait, ExecutionTracker,
 };
 use crate::tree_renderer::render_full_tree_output;
-use crate::types::context::RuntimeActiveTask;
-use crate::types::enums::{AgentRuntime, Backend, Model, SessionStatus, TaskStatus};
+use crate::types::config::{LoopConfig, StatusMapping};
+use crate::types::context::{RuntimeActiveTask, RuntimeState, TaskAttemptMetrics};
+use crate::types::enums::{
+    AgentRuntime, Backend, DebugEventSource, DebugEventType, DebugVerbosity, ExecutionDriver,
+    Model, SessionStatus, TaskAction, TaskStatus,
+};
 use crate::types::task_graph::ParentIssue;
 use crate::types::task_graph::{
-    build_task_graph, get_blocked_tasks, get_graph_stats, get_ready_tasks, get_verification_task,
-    update_task_status, SubTask,
+    break_cycles, build_task_graph, build_task_graph_with_mapping, format_cycle,
+    get_awaiting_human_tasks, get_blocked_tasks, get_external_blockers, get_graph_stats,
+    get_ready_tasks, get_task_by_id, get_task_by_identifier, get_verification_task,
+    map_backend_status, plan_batches, sort_for_dispatch, update_task_status, validate_graph,
+    SubTask, TaskGraph,
+};
+use crate::verify::gate_results;
+use crate::worktree::{
+    create_worktree, remove_worktree, symlink_runtime_config_dir, WorktreeConfig,
 };
-use crate::worktree::{create_worktree, remove_worktree, WorktreeConfig};
 
 use super::push::push_pending_updates_for_task;
 use super::submit;
@@ -55,6 +99,18 @@ pub struct LoopOptions<'a> {
     pub fresh: bool,
     pub no_submit: bool,
     pub no_tui: bool,
+    /// Continue from the last checkpoint instead of planning from scratch
+    pub resume: bool,
+    /// Break dependency cycles instead of refusing to start
+    pub break_cycles: bool,
+    /// Print the batches that would be dispatched without spawning agents
+    pub plan: bool,
+    /// Run agents on the host even when the container sandbox is configured
+    pub no_sandbox: bool,
+    /// Record debug events at this verbosity (`--debug`)
+    pub debug: Option<DebugVerbosity>,
+    /// Start without checking tools, the working tree, base branch and tokens
+    pub skip_preflight: bool,
 }
 
 pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
@@ -66,12 +122,45 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
     let fresh = opts.fresh;
     let no_submit = opts.no_submit;
 
+    let resume_checkpoint = if opts.resume {
+        let checkpoint = load_checkpoint(task_id)?.with_context(|| {
+            format!(
+                "No checkpoint found for {}. Start a new loop with 'mobius loop {}'.",
+                task_id, task_id
+            )
+        })?;
+        Some(checkpoint)
+    } else {
+        None
+    };
+
+    // Refuse to start on a cyclic graph before creating worktrees or panes
+    let local_issues = read_local_subtasks_as_linear_issues(task_id);
+    check_graph_cycles(
+        &build_task_graph(task_id, task_id, &local_issues),
+        opts.break_cycles,
+    )?;
+
+    if opts.plan {
+        return print_plan(task_id, opts);
+    }
+
+    // Fail fast on missing tools, a dirty tree or bad tokens before spawning agents
+    if !opts.skip_preflight {
+        check_preflight(task_id, opts)?;
+    }
+
     if !opts.no_tui {
         return run_with_tui(task_id, opts);
     }
 
+    if let Some(verbosity) = opts.debug {
+        initialize_debug_logger(task_id, verbosity);
+    }
+
     let paths = resolve_paths();
     let config = read_config_with_env(&paths.config_path).unwrap_or_default();
+    let _telemetry = telemetry::init(&config.observability);
     let backend: Backend = if let Some(b) = backend_override {
         b.parse().unwrap_or(config.backend)
     } else {
@@ -79,7 +168,7 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
     };
 
     // Validate task ID format
-    if !validate_task_id(task_id, &backend) {
+    if !backend.is_valid_task_id(task_id) {
         eprintln!(
             "{}",
             format!("Error: Invalid task ID format for {}: {}", backend, task_id).red()
@@ -91,8 +180,18 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
         std::process::exit(1);
     }
 
+    // Runtimes that complete on exit can only be watched as child processes
+    let mut driver = config.execution.driver;
+    if driver == ExecutionDriver::Tmux && routes_to_exit_only_runtime(task_id, &config) {
+        println!(
+            "{}",
+            "Aider sub-tasks complete on exit; running agents as child processes.".dimmed()
+        );
+        driver = ExecutionDriver::Process;
+    }
+
     // Check for tmux availability
-    if which::which("tmux").is_err() {
+    if driver == ExecutionDriver::Tmux && which::which("tmux").is_err() {
         eprintln!(
             "{}",
             "Error: tmux is required for parallel execution mode".red()
@@ -105,11 +204,19 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
             "{}",
             "Alternatively, use '--sequential' flag for sequential execution.".dimmed()
         );
+        eprintln!(
+            "{}",
+            "Or set 'execution.driver: process' in your config to run agents without tmux."
+                .dimmed()
+        );
         std::process::exit(1);
     }
 
     // Apply option overrides to config
     let mut execution_config = config.execution.clone();
+    if opts.no_sandbox {
+        execution_config.sandbox = false;
+    }
     if let Some(p) = parallel_override {
         execution_config.max_parallel_agents = Some(p);
     }
@@ -130,21 +237,16 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
         }
     }
 
-    let execution_model_override = if config.runtime == AgentRuntime::Opencode {
+    let mut execution_model_override = if config.runtime != AgentRuntime::Claude {
         model_override
     } else {
         None
     };
-    let execution_thinking_override = if config.runtime == AgentRuntime::Opencode {
+    let mut execution_thinking_override = if config.runtime != AgentRuntime::Claude {
         thinking_level_override
     } else {
         None
     };
-    let runtime_model_label = runtime_adapter::effective_model_for_runtime(
-        config.runtime,
-        &execution_config,
-        execution_model_override,
-    );
 
     let max_iterations = max_iterations_override.unwrap_or(config.execution.max_iterations);
 
@@ -154,7 +256,7 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
 
     // Clear stale state if --fresh flag
     if fresh {
-        let deleted = delete_runtime_state(task_id);
+        let deleted = delete_runtime_state(task_id) | delete_checkpoint(task_id);
         if deleted {
             println!(
                 "{}",
@@ -163,10 +265,21 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
         }
     }
 
-    println!(
-        "{}",
-        format!("Starting parallel loop for {}...", task_id).blue()
-    );
+    if let Some(ref checkpoint) = resume_checkpoint {
+        println!(
+            "{}",
+            format!(
+                "Resuming loop for {} from iteration {}...",
+                task_id, checkpoint.iteration
+            )
+            .blue()
+        );
+    } else {
+        println!(
+            "{}",
+            format!("Starting parallel loop for {}...", task_id).blue()
+        );
+    }
 
     // Fetch parent issue
     let rt = tokio::runtime::Runtime::new()?;
@@ -184,7 +297,9 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
     };
 
     // Derive branch name with fallback when Linear/backend doesn't provide one
-    let branch_name = if parent_issue.git_branch_name.is_empty() {
+    let branch_name = if let Some(ref checkpoint) = resume_checkpoint {
+        checkpoint.branch_name.clone()
+    } else if parent_issue.git_branch_name.is_empty() {
         format!("feat/{}", task_id.to_lowercase())
     } else {
         parent_issue.git_branch_name.clone()
@@ -192,12 +307,15 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
 
     println!("{}", format!("Issue: {}", parent_issue.title).dimmed());
     println!("{}", format!("Branch: {}", branch_name).dimmed());
+    let loop_span = info_span!("loop", issue = task_id, branch = %branch_name);
+    let _loop_span = loop_span.enter();
 
     // Create or resume worktree
     let worktree_config = WorktreeConfig {
         worktree_path: execution_config.worktree_path.clone(),
         base_branch: execution_config.base_branch.clone(),
         runtime: config.runtime,
+        checkout: execution_config.checkout.clone(),
     };
     let worktree_info = rt.block_on(create_worktree(task_id, &branch_name, &worktree_config))?;
 
@@ -217,24 +335,96 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
         );
     }
 
-    // Create tmux session
+    // Link shared build caches before bootstrap fills them
+    if let Some(plan) = plan_caches(&execution_config.build_cache, &worktree_info.path) {
+        let linked = link_caches(&plan, &worktree_info.path)?;
+        if !linked.is_empty() {
+            println!(
+                "{}",
+                format!("Sharing {} from {}", linked.join(", "), plan.root.display()).dimmed()
+            );
+        }
+    }
+
+    // Install dependencies once per worktree so agents start ready to build
+    match run_bootstrap(
+        &worktree_info.path,
+        &execution_config.bootstrap,
+        |command| {
+            println!(
+                "{}",
+                format!("Bootstrapping worktree: {}", command).dimmed()
+            )
+        },
+    )? {
+        BootstrapOutcome::Cached => println!(
+            "{}",
+            "Worktree already bootstrapped; lockfiles unchanged.".dimmed()
+        ),
+        BootstrapOutcome::Ran => println!("{}", "Worktree bootstrapped.".green()),
+        BootstrapOutcome::Skipped => {}
+    }
+
+    // Create tmux session (process driver runs agents as child processes instead)
     let session_name = get_session_name(task_id);
-    let session: TmuxSession = rt.block_on(create_session(&session_name))?;
-    let _status_pane = rt.block_on(create_status_pane(&session))?;
-    println!(
-        "{}",
-        format!("Created tmux session: {}", session_name).green()
-    );
+    let session: Option<TmuxSession> = if driver == ExecutionDriver::Tmux {
+        let session = rt.block_on(create_session(&session_name))?;
+        let _status_pane = rt.block_on(create_status_pane(&session))?;
+        println!(
+            "{}",
+            format!("Created tmux session: {}", session_name).green()
+        );
+        Some(session)
+    } else {
+        println!("{}", "Running agents as child processes.".dimmed());
+        None
+    };
 
     // Build initial task graph from local state
     let issues = read_local_subtasks_as_linear_issues(task_id);
     if issues.is_empty() {
         eprintln!("{}", format!("No sub-tasks found for {}", task_id).yellow());
-        rt.block_on(destroy_session(&session))?;
+        if let Some(ref session) = session {
+            rt.block_on(destroy_session(session))?;
+        }
         std::process::exit(1);
     }
 
-    let mut graph = build_task_graph(&parent_issue.id, &parent_issue.identifier, &issues);
+    let status_mapping = config.status_mapping_for(backend);
+    let mut graph = build_task_graph_with_mapping(
+        &parent_issue.id,
+        &parent_issue.identifier,
+        &issues,
+        status_mapping,
+    );
+    if opts.break_cycles {
+        graph = break_cycles(&graph).0;
+    }
+    graph = rt.block_on(refresh_external_blockers(task_id, &graph));
+
+    // Sub-tasks routed to another runtime need its config directory as well
+    if let Ok(cwd) = std::env::current_dir() {
+        let mut routed: Vec<AgentRuntime> = Vec::new();
+        for task in graph.tasks.values() {
+            let runtime = runtime_adapter::route_runtime(
+                task.scoring.as_ref(),
+                &execution_config,
+                config.runtime,
+            )
+            .runtime;
+            if runtime != config.runtime && !routed.contains(&runtime) {
+                routed.push(runtime);
+            }
+        }
+        if let Some(ref fallback) = execution_config.fallback {
+            if fallback.runtime != config.runtime && !routed.contains(&fallback.runtime) {
+                routed.push(fallback.runtime);
+            }
+        }
+        for runtime in routed {
+            symlink_runtime_config_dir(&cwd, &worktree_info.path, runtime);
+        }
+    }
 
     // Generate local context for skills to read
     println!("{}", "Generating local context for skills...".dimmed());
@@ -262,6 +452,9 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
         }
     }
 
+    // Specs edited mid-run trigger a context refresh before the next batch
+    let mut specs = spec_fingerprint(task_id);
+
     let mut worktree_context_file = mirror_issue_context_to_worktree(task_id, &worktree_info.path)
         .with_context(|| {
             format!(
@@ -273,25 +466,110 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
         "{}",
         format!("Worktree context file: {}", worktree_context_file).dimmed()
     );
+    if let Some(sandbox) = Sandbox::for_config(&execution_config) {
+        println!(
+            "{}",
+            format!("Agents sandboxed in {}", sandbox.describe()).dimmed()
+        );
+    }
 
     // Display ASCII tree
     println!();
     println!("{}", render_full_tree_output(&graph));
     println!();
 
+    // Agents report completion through per-task result files in this directory
+    let result_dir = get_results_directory_path(task_id);
+    fs::create_dir_all(&result_dir)
+        .with_context(|| format!("Failed to create result directory {}", result_dir.display()))?;
+
+    // Raw stream-json output per agent, used to extract token usage for cost tracking
+    let output_dir = crate::stream_json::agent_output_dir(task_id);
+    let output_dir = fs::create_dir_all(&output_dir)
+        .is_ok()
+        .then_some(output_dir);
+    let max_cost_usd = config.loop_settings.max_cost_usd;
+    let notifier = Notifier::new(&config.notifications);
+
+    // Machines joined with `mobius worker` take ready tasks beyond local capacity
+    let pool = match config.pool.listen.as_deref() {
+        Some(listen) => {
+            let token = config
+                .pool
+                .token
+                .clone()
+                .or_else(|| std::env::var("MOBIUS_POOL_TOKEN").ok());
+            let pool = rt.block_on(Coordinator::start(listen, token))?;
+            println!(
+                "{}",
+                format!(
+                    "Agent pool listening on {}. Add machines with: mobius worker --join <host>:{}",
+                    pool.local_addr(),
+                    pool.local_addr().port()
+                )
+                .dimmed()
+            );
+            Some(pool)
+        }
+        None => None,
+    };
+
     // Track loop state
     let start_time = std::time::Instant::now();
+
+    // Interrupt agents gracefully once the loop-level wall clock runs out
+    let loop_timed_out = Arc::new(AtomicBool::new(false));
+    if let Some(minutes) = execution_config.loop_timeout_minutes.filter(|m| *m > 0) {
+        let timed_out = Arc::clone(&loop_timed_out);
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_secs(u64::from(minutes) * 60));
+            if shutdown::request_shutdown() {
+                timed_out.store(true, Ordering::SeqCst);
+                eprintln!(
+                    "\nLoop time limit of {} minutes reached; interrupting agents and saving state...",
+                    minutes
+                );
+            }
+        });
+    }
     let mut iteration = 0u32;
     let mut all_complete = false;
     let mut any_failed = false;
+    let mut interrupted = false;
+
+    // Provider incidents move the rest of the run to `execution.fallback`, once
+    let mut active_runtime = config.runtime;
+    let mut pending_fallback = execution_config.fallback.clone();
+    let mut fallback_active = false;
 
     // Initialize execution tracker
-    let mut tracker = create_tracker(
-        execution_config.max_retries,
-        execution_config.verification_timeout.map(|v| v as u64),
-    );
+    let mut tracker = create_tracker_from_config(&execution_config);
 
     let mut retry_queue: Vec<SubTask> = Vec::new();
+    // Human tasks already announced, so each is surfaced once
+    let mut announced_human: HashSet<String> = HashSet::new();
+
+    // Restore iteration state from the checkpoint, or start a fresh one
+    let mut checkpoint = match resume_checkpoint {
+        Some(mut checkpoint) => {
+            iteration = checkpoint.iteration;
+            retry_queue = checkpoint.pending_tasks(&graph);
+            if !retry_queue.is_empty() {
+                let ids: Vec<_> = retry_queue.iter().map(|t| t.identifier.as_str()).collect();
+                println!(
+                    "{}",
+                    format!("Rescheduling from checkpoint: {}", ids.join(", ")).dimmed()
+                );
+            }
+            checkpoint.worktree_path = worktree_info.path.display().to_string();
+            checkpoint
+        }
+        None => Checkpoint::new(
+            task_id,
+            &branch_name,
+            &worktree_info.path.display().to_string(),
+        ),
+    };
 
     // Create session in context system
     let _ = create_mobius_session(task_id, backend, None);
@@ -307,19 +585,84 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
     // Pre-populate completed tasks
     for task in graph.tasks.values() {
         if task.status == TaskStatus::Done {
-            runtime_state = complete_runtime_task(&runtime_state, &task.identifier);
+            runtime_state = complete_runtime_task(&runtime_state, &task.identifier, None);
         }
     }
     write_runtime_state(&runtime_state)?;
+    // A pause left over from an earlier run shouldn't stall this one
+    let _ = set_paused(task_id, false);
+    let mut offline = false;
+    note_offline_state(task_id, backend, &mut offline);
+    // Tracker edits since the last pull are picked up between batches
+    let mut last_refresh = read_last_pull(task_id);
 
     // Main execution loop
     while iteration < max_iterations {
+        if shutdown::is_interrupted() {
+            interrupted = true;
+            break;
+        }
+        if is_paused(task_id) {
+            wait_while_paused(task_id);
+            if shutdown::is_interrupted() {
+                interrupted = true;
+                break;
+            }
+        }
+        apply_dashboard_actions(
+            task_id,
+            &graph,
+            &mut tracker,
+            &mut retry_queue,
+            &mut runtime_state,
+        )?;
         iteration += 1;
+        let iteration_span = info_span!("iteration", iteration, tasks = field::Empty);
+        let _iteration_span = iteration_span.enter();
+
+        if supports_subtask_refresh(backend) && !offline {
+            refresh_from_tracker(task_id, backend, status_mapping, &mut last_refresh, &rt);
+        }
 
         // Re-sync task graph from local state
         let local_issues = read_local_subtasks_as_linear_issues(task_id);
         if !local_issues.is_empty() {
-            graph = build_task_graph(&parent_issue.id, &parent_issue.identifier, &local_issues);
+            let synced = build_task_graph_with_mapping(
+                &parent_issue.id,
+                &parent_issue.identifier,
+                &local_issues,
+                status_mapping,
+            );
+            log_state_drift(&graph, &synced);
+            graph = synced;
+            if opts.break_cycles {
+                graph = break_cycles(&graph).0;
+            }
+            graph = rt.block_on(refresh_external_blockers(task_id, &graph));
+        }
+
+        let current_specs = spec_fingerprint(task_id);
+        let changed = changed_specs(&specs, &current_specs);
+        if !changed.is_empty() {
+            println!(
+                "{}",
+                format!(
+                    "Specs changed ({}); refreshing context...",
+                    changed.join(", ")
+                )
+                .dimmed()
+            );
+            match generate_context(task_id, None, false).and_then(|ctx| {
+                ctx.map(|c| write_full_context_file(task_id, &c))
+                    .transpose()
+            }) {
+                Ok(_) => specs = current_specs,
+                // Left stale so the refresh is retried next iteration
+                Err(e) => eprintln!(
+                    "{}",
+                    format!("Warning: Failed to refresh context: {}", e).yellow()
+                ),
+            }
         }
 
         // Check if verification task is complete
@@ -350,6 +693,28 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
             }
         }
         retry_queue.clear();
+        sort_for_dispatch(&graph, &mut ready_tasks, config.loop_settings.strategy);
+
+        // Hold back retries still in their backoff window
+        let (mut backing_off, ready): (Vec<SubTask>, Vec<SubTask>) = ready_tasks
+            .into_iter()
+            .partition(|t| retry_wait(&tracker, &t.id).is_some());
+        ready_tasks = ready;
+        if ready_tasks.is_empty() {
+            if let Some(wait) = backing_off
+                .iter()
+                .filter_map(|t| retry_wait(&tracker, &t.id))
+                .min()
+            {
+                println!(
+                    "{}",
+                    format!("Backing off {}s before retrying...", wait.as_secs().max(1)).dimmed()
+                );
+                std::thread::sleep(wait);
+                ready_tasks.append(&mut backing_off);
+            }
+        }
+        retry_queue.append(&mut backing_off);
 
         let stats = get_graph_stats(&graph);
 
@@ -360,6 +725,42 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
             break;
         }
 
+        // Human tasks are never dispatched; say once what each one needs
+        let mut human_done = false;
+        for task in graph.tasks.values() {
+            if task.status == TaskStatus::Done && announced_human.remove(&task.id) {
+                runtime_state = complete_runtime_task(&runtime_state, &task.identifier, None);
+                println!("{}", format!("  ✓ {} (human)", task.identifier).green());
+                human_done = true;
+            }
+        }
+        if human_done {
+            write_runtime_state(&runtime_state)?;
+        }
+        let awaiting = get_awaiting_human_tasks(&graph);
+        for task in &awaiting {
+            if announced_human.insert(task.id.clone()) {
+                println!(
+                    "{}",
+                    format!("⏸ {} needs you: {}", task.identifier, task.title).yellow()
+                );
+                notifier.send_blocking(
+                    &rt,
+                    &Notification::human_action_needed(task_id, &task.identifier, &task.title),
+                );
+            }
+        }
+
+        if ready_tasks.is_empty() && !awaiting.is_empty() {
+            if !wait_for_human_tasks(task_id, &graph, backend, status_mapping, &rt) {
+                interrupted = true;
+                break;
+            }
+            // Waiting is not an iteration
+            iteration -= 1;
+            continue;
+        }
+
         if ready_tasks.is_empty() {
             let blocked = get_blocked_tasks(&graph);
             if !blocked.is_empty() {
@@ -369,19 +770,84 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
                 );
                 let ids: Vec<_> = blocked.iter().map(|t| t.identifier.as_str()).collect();
                 println!("{}", format!("Blocked: {}", ids.join(", ")).dimmed());
+
+                let external = get_external_blockers(&graph);
+                if !external.is_empty() {
+                    println!("{}", "Waiting on external blockers:".yellow());
+                    for (task, url) in &external {
+                        println!("  {} {}", task.identifier, url);
+                    }
+                    println!(
+                        "{}",
+                        format!(
+                            "Run 'mobius deps resolve {} <url>' once they land, then resume.",
+                            task_id
+                        )
+                        .dimmed()
+                    );
+                }
             }
             break;
         }
 
-        // Calculate parallelism
+        // Calculate parallelism; idle pool workers add their slots on top
         let parallel_count = calculate_parallelism(ready_tasks.len(), &execution_config);
-        let tasks_to_execute: Vec<SubTask> = ready_tasks.into_iter().take(parallel_count).collect();
+        let idle_workers = pool
+            .as_ref()
+            .map(|pool| pool.idle_workers())
+            .unwrap_or_default();
+        let remote_count = idle_workers
+            .iter()
+            .map(|w| w.capacity)
+            .sum::<usize>()
+            .min(ready_tasks.len() - parallel_count);
+        let mut tasks_to_execute: Vec<SubTask> = ready_tasks
+            .into_iter()
+            .take(parallel_count + remote_count)
+            .collect();
+        if fallback_active {
+            // Scored runtimes would route straight back to the failing provider
+            for task in &mut tasks_to_execute {
+                if let Some(scoring) = task.scoring.as_mut() {
+                    scoring.recommended_runtime = None;
+                }
+            }
+        }
+
+        // Workers check out the branch from the pool remote, so publish it first
+        let mut assignments =
+            distribute(tasks_to_execute[parallel_count..].to_vec(), &idle_workers);
+        if !assignments.is_empty() {
+            if let Err(e) = rt.block_on(with_lock(&worktree_info.path, Some(task_id), None, || {
+                publish_branch(&worktree_info.path, &config.pool.remote, &branch_name)
+            })) {
+                println!(
+                    "{}",
+                    format!(
+                        "⚠ Could not push {} for pool workers; running locally only: {:#}",
+                        branch_name, e
+                    )
+                    .yellow()
+                );
+                assignments.clear();
+                tasks_to_execute.truncate(parallel_count);
+            }
+        }
+        let task_workers: HashMap<String, String> = assignments
+            .iter()
+            .flat_map(|(worker, tasks)| {
+                tasks
+                    .iter()
+                    .map(move |t| (t.identifier.clone(), worker.name.clone()))
+            })
+            .collect();
 
         println!(
             "{}",
             format!(
                 "\nIteration {}: Executing {} task(s) in parallel...",
-                iteration, parallel_count
+                iteration,
+                tasks_to_execute.len()
             )
             .blue()
         );
@@ -390,12 +856,25 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
             .map(|t| t.identifier.as_str())
             .collect();
         println!("{}", format!("  Tasks: {}", task_ids.join(", ")).dimmed());
+        iteration_span.record("tasks", task_ids.join(", ").as_str());
+        for (worker, tasks) in &assignments {
+            let ids: Vec<_> = tasks.iter().map(|t| t.identifier.as_str()).collect();
+            println!(
+                "{}",
+                format!("  On {}: {}", worker.name, ids.join(", ")).dimmed()
+            );
+        }
 
         // Assign tasks to tracker
         for task in &tasks_to_execute {
             assign_task(&mut tracker, task);
         }
 
+        checkpoint.record_dispatch(iteration, &tasks_to_execute);
+        if let Err(e) = write_checkpoint(&mut checkpoint) {
+            tracing::warn!("Failed to write checkpoint for {}: {}", task_id, e);
+        }
+
         // Update runtime state with active tasks
         for task in &tasks_to_execute {
             runtime_state = add_runtime_active_task(
@@ -405,22 +884,33 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
                     pid: 0,
                     pane: String::new(),
                     started_at: chrono::Utc::now().to_rfc3339(),
-                    worktree: Some(worktree_info.path.display().to_string()),
-                    model: Some(if config.runtime == AgentRuntime::Claude {
-                        select_model_for_task(
+                    worktree: (!task_workers.contains_key(&task.identifier))
+                        .then(|| worktree_info.path.display().to_string()),
+                    model: Some(
+                        resolve_task_runtime(
                             task,
-                            execution_config.model.parse::<Model>().unwrap_or_default(),
+                            &execution_config,
+                            active_runtime,
+                            execution_model_override,
+                            execution_thinking_override,
                         )
-                        .to_string()
-                    } else {
-                        runtime_model_label.clone()
-                    }),
+                        .model,
+                    ),
                     input_tokens: None,
                     output_tokens: None,
+                    retry_count: Some(retry_count(&tracker, &task.id)).filter(|&n| n > 0),
+                    worker: task_workers.get(&task.identifier).cloned(),
                 },
             );
         }
         write_runtime_state(&runtime_state)?;
+        for task in &tasks_to_execute {
+            let attempt = retry_count(&tracker, &task.id) + 1;
+            notifier.send_blocking(
+                &rt,
+                &Notification::task_started(task_id, &task.identifier, attempt),
+            );
+        }
 
         // Update status pane
         let loop_status = LoopStatus {
@@ -439,7 +929,9 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
                 .collect(),
             elapsed_ms: start_time.elapsed().as_millis() as u64,
         };
-        let _ = rt.block_on(update_status_pane(&loop_status, &session_name));
+        if session.is_some() {
+            let _ = rt.block_on(update_status_pane(&loop_status, &session_name));
+        }
 
         // Execute tasks in parallel
         worktree_context_file = mirror_issue_context_to_worktree(task_id, &worktree_info.path)
@@ -451,20 +943,63 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
             })?;
         let worktree_path = worktree_info.path.display().to_string();
         let execution_context = ExecutionContext {
-            runtime: config.runtime,
+            runtime: active_runtime,
             worktree_path: &worktree_path,
             config: &execution_config,
             context_file_path: Some(worktree_context_file.as_str()),
             model_override: execution_model_override,
             thinking_level_override: execution_thinking_override,
-            output_dir: None,
+            output_dir: output_dir.as_deref(),
+            result_dir: Some(&result_dir),
         };
-        let results = rt.block_on(execute_parallel(
-            &tasks_to_execute,
-            &session,
-            execution_context,
-            None,
-        ));
+        let local_tasks = &tasks_to_execute[..parallel_count];
+        let batch_spec = BatchSpec {
+            issue_id: task_id,
+            branch: &branch_name,
+            runtime: active_runtime,
+            model_override: execution_model_override,
+            thinking_level_override: execution_thinking_override,
+            execution: &execution_config,
+        };
+        let (mut results, remote_results) = rt.block_on(async {
+            let local = async {
+                match session {
+                    Some(ref session) => {
+                        execute_parallel(local_tasks, session, execution_context, None).await
+                    }
+                    None => {
+                        ProcessExecutor::new(execution_context, None)
+                            .execute_parallel(local_tasks)
+                            .await
+                    }
+                }
+            };
+            let remote = async {
+                match pool {
+                    Some(ref pool) => pool.run(assignments, &batch_spec).await,
+                    None => Vec::new(),
+                }
+            };
+            tokio::join!(local, remote)
+        });
+
+        // Pick up the commits workers pushed before the next batch builds on them
+        if remote_results.iter().any(|r| r.success) {
+            if let Err(e) = rt.block_on(with_lock(&worktree_info.path, Some(task_id), None, || {
+                integrate_branch(&worktree_info.path, &config.pool.remote, &branch_name)
+            })) {
+                println!(
+                    "{}",
+                    format!(
+                        "⚠ Could not rebase onto pool worker commits; resolve in {}: {:#}",
+                        worktree_info.path.display(),
+                        e
+                    )
+                    .yellow()
+                );
+            }
+        }
+        results.extend(remote_results);
 
         // Update runtime state with pane IDs
         for result in &results {
@@ -473,9 +1008,52 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
             }
         }
 
+        // Record token usage and cost for each attempt; the iteration log
+        // keeps the model and cost after the active task entry is gone
+        let attempt_models: HashMap<String, String> = runtime_state
+            .active_tasks
+            .iter()
+            .filter_map(|t| Some((t.id.clone(), t.model.clone()?)))
+            .collect();
+        let mut attempt_costs: HashMap<String, f64> = HashMap::new();
+        for result in &results {
+            if result.input_tokens.is_none() && result.output_tokens.is_none() {
+                continue;
+            }
+            runtime_state = update_runtime_task_tokens(
+                &runtime_state,
+                &result.identifier,
+                result.input_tokens,
+                result.output_tokens,
+            );
+            let cost = runtime_state
+                .active_tasks
+                .iter()
+                .find(|t| t.id == result.identifier)
+                .and_then(|t| t.model.as_deref())
+                .and_then(|model| {
+                    estimate_cost_usd(
+                        model,
+                        result.input_tokens.unwrap_or(0),
+                        result.output_tokens.unwrap_or(0),
+                    )
+                });
+            if let Some(cost) = cost {
+                runtime_state = add_runtime_task_cost(&runtime_state, &result.identifier, cost);
+                attempt_costs.insert(result.identifier.clone(), cost);
+            }
+        }
+        runtime_state = recalculate_total_tokens(&runtime_state);
+
+        // Interrupted agents were stopped mid-task; the checkpoint reschedules them on resume
+        if shutdown::is_interrupted() {
+            interrupted = true;
+            break;
+        }
+
         // Auto-push queued updates to backend
         let (push_success, push_failed, push_errors) =
-            push_pending_updates_for_task(task_id, &backend);
+            push_pending_updates_for_task(task_id, &backend, status_mapping);
         if push_success > 0 || push_failed > 0 {
             println!(
                 "{}",
@@ -489,6 +1067,29 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
                 println!("{}", format!("  ⚠ {}", error).yellow());
             }
         }
+        note_offline_state(task_id, backend, &mut offline);
+
+        // Run verify commands ourselves before trusting an agent's success
+        let verification = execution_config.verification.clone().unwrap_or_default();
+        let verify_runs = if verification.run_verify_commands {
+            let timeout =
+                std::time::Duration::from_secs(u64::from(verification.verify_timeout_minutes) * 60);
+            gate_results(
+                task_id,
+                &mut results,
+                &worktree_info.path,
+                timeout,
+                |id, run| {
+                    if run.passed {
+                        println!("{}", format!("  ✓ {}: verify command passed", id).dimmed());
+                    } else {
+                        println!("{}", format!("  ✗ {}: {}", id, run.failure_message()).red());
+                    }
+                },
+            )
+        } else {
+            HashMap::new()
+        };
 
         // Verify results
         println!("{}", "Verifying results...".dimmed());
@@ -523,16 +1124,30 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
         for result in &verified_results {
             if result.success && result.backend_verified {
                 graph = update_task_status(&graph, &result.task_id, TaskStatus::Done);
-                runtime_state = complete_runtime_task(&runtime_state, &result.identifier);
+                let attempt = results.iter().find(|r| r.identifier == result.identifier);
+                let metrics = TaskAttemptMetrics {
+                    duration_ms: result.duration_ms,
+                    input_tokens: attempt.and_then(|r| r.input_tokens),
+                    output_tokens: attempt.and_then(|r| r.output_tokens),
+                    model: attempt_models.get(&result.identifier).cloned(),
+                };
+                runtime_state =
+                    complete_runtime_task(&runtime_state, &result.identifier, Some(&metrics));
                 update_subtask_status(task_id, &result.identifier, "done");
                 println!("{}", format!("  ✓ {}", result.identifier).green());
+                notifier.send_blocking(
+                    &rt,
+                    &Notification::task_completed(task_id, &result.identifier, result.duration_ms),
+                );
             } else if result.should_retry {
                 runtime_state = remove_runtime_active_task(&runtime_state, &result.identifier);
                 println!(
                     "{}",
                     format!(
-                        "  ↻ {}: Retrying ({})",
+                        "  ↻ {}: Retrying {}/{} ({})",
                         result.identifier,
+                        result.attempts,
+                        tracker.max_retries,
                         result.error.as_deref().unwrap_or("verification pending")
                     )
                     .yellow()
@@ -548,6 +1163,15 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
                     )
                     .red()
                 );
+                notifier.send_blocking(
+                    &rt,
+                    &Notification::task_failed(
+                        task_id,
+                        &result.identifier,
+                        result.attempts,
+                        result.error.as_deref(),
+                    ),
+                );
             }
         }
         write_runtime_state(&runtime_state)?;
@@ -559,18 +1183,15 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
             }
         }
 
-        // Check for permanent failures
-        if has_permanent_failures(&verified_results) {
-            any_failed = true;
-            println!(
-                "{}",
-                "\nStopping due to permanent task failure (max retries exceeded).".red()
-            );
-            break;
+        let verified_ids: Vec<String> = verified.iter().map(|r| r.identifier.clone()).collect();
+        checkpoint.record_results(&verified_ids, &retry_queue);
+        if let Err(e) = write_checkpoint(&mut checkpoint) {
+            tracing::warn!("Failed to write checkpoint for {}: {}", task_id, e);
         }
 
-        // Write iteration log entries
+        // Write iteration log entries before a permanent failure stops the loop
         let iteration_timestamp = chrono::Utc::now().to_rfc3339();
+        let mut provider_incident: Option<(String, String)> = None;
         for result in &verified_results {
             let status = if result.success && result.backend_verified {
                 IterationStatus::Success
@@ -579,6 +1200,30 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
             } else {
                 IterationStatus::Failed
             };
+            let reported = read_agent_result(&result_file_for(&result_dir, &result.identifier));
+            let failure_class = (status != IterationStatus::Success).then(|| {
+                let result_event = output_dir.as_ref().and_then(|dir| {
+                    parse_result_event(&dir.join(format!("{}.jsonl", result.identifier)))
+                });
+                let signals = FailureSignals {
+                    status: &result.status,
+                    reported_status: reported.as_ref().map(|r| r.status),
+                    error: result.error.as_deref(),
+                    output: result.raw_output.as_deref(),
+                    result_event: result_event.as_ref(),
+                };
+                if provider_incident.is_none() && is_provider_incident(&signals) {
+                    provider_incident = Some((
+                        result.identifier.clone(),
+                        result
+                            .error
+                            .clone()
+                            .unwrap_or_else(|| "provider overloaded".to_string()),
+                    ));
+                }
+                classify_failure(&signals)
+            });
+            let attempt_tokens = results.iter().find(|r| r.identifier == result.identifier);
             let entry = IterationLogEntry {
                 subtask_id: result.identifier.clone(),
                 attempt: iteration,
@@ -586,10 +1231,108 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
                 completed_at: Some(chrono::Utc::now().to_rfc3339()),
                 status,
                 error: result.error.clone(),
-                files_modified: None,
-                commit_hash: None,
+                files_modified: reported
+                    .as_ref()
+                    .map(|r| r.files.clone())
+                    .filter(|f| !f.is_empty()),
+                commit_hash: reported.and_then(|r| r.commit),
+                retry_count: Some(result.attempts.saturating_sub(1)),
+                failure_class,
+                model: attempt_models.get(&result.identifier).cloned(),
+                duration_ms: Some(result.duration_ms),
+                cost_usd: attempt_costs.get(&result.identifier).copied(),
+                input_tokens: attempt_tokens.and_then(|r| r.input_tokens),
+                output_tokens: attempt_tokens.and_then(|r| r.output_tokens),
+                verify: verify_runs.get(&result.identifier).cloned(),
             };
             let _ = write_iteration_log(task_id, entry);
+
+            let log_path = output_dir
+                .as_ref()
+                .map(|dir| dir.join(format!("{}.jsonl", result.identifier)));
+            let status_label = format!("{:?}", result.status);
+            let archived = TaskOutput {
+                identifier: &result.identifier,
+                status: &status_label,
+                pane: result.raw_output.as_deref(),
+                log: log_path.as_deref(),
+            };
+            if let Err(e) = archive_output(task_id, &archived, &execution_config.output_archive) {
+                tracing::warn!("Failed to archive output for {}: {}", result.identifier, e);
+            }
+        }
+
+        // Switch remaining tasks to the fallback runtime/model on a provider incident
+        if let Some((failed_task, reason)) = provider_incident {
+            if let Some(fallback) = pending_fallback.take() {
+                let from = runtime_adapter::runtime_model_label(
+                    active_runtime,
+                    &execution_config,
+                    execution_model_override,
+                );
+                execution_config =
+                    runtime_adapter::fallback_execution_config(&execution_config, &fallback);
+                active_runtime = fallback.runtime;
+                execution_model_override = None;
+                execution_thinking_override = None;
+                fallback_active = true;
+                let to =
+                    runtime_adapter::runtime_model_label(active_runtime, &execution_config, None);
+                println!(
+                    "{}",
+                    format!(
+                        "Provider incident on {} ({}); switching remaining tasks from {} to {}.",
+                        failed_task, reason, from, to
+                    )
+                    .yellow()
+                );
+                notifier.send_blocking(
+                    &rt,
+                    &Notification::model_fallback(task_id, &failed_task, &from, &to, &reason),
+                );
+            }
+        }
+
+        // Check for permanent failures
+        if has_permanent_failures(&verified_results) {
+            any_failed = true;
+            println!(
+                "{}",
+                "\nStopping due to permanent task failure (not retryable or max retries exceeded)."
+                    .red()
+            );
+            let gate_failed = get_verification_task(&graph).is_some_and(|gate| {
+                permanent_failures
+                    .iter()
+                    .any(|r| r.identifier == gate.identifier)
+            });
+            if gate_failed {
+                println!(
+                    "{}",
+                    format!(
+                        "Triage: find the sub-task that broke integration with 'mobius bisect {}'",
+                        task_id
+                    )
+                    .yellow()
+                );
+            }
+            break;
+        }
+
+        // Enforce the cost budget
+        let spent_usd = runtime_state.total_cost_usd.unwrap_or(0.0);
+        if budget_exceeded(spent_usd, max_cost_usd) {
+            any_failed = true;
+            println!(
+                "{}",
+                format!(
+                    "\nStopping: cost budget exceeded ({} spent, limit {}).",
+                    format_cost(spent_usd),
+                    format_cost(max_cost_usd.unwrap_or_default())
+                )
+                .red()
+            );
+            break;
         }
 
         // Re-render ASCII tree
@@ -597,26 +1340,99 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
         println!("{}", render_full_tree_output(&graph));
     }
 
+    if let Some(ref pool) = pool {
+        rt.block_on(pool.shutdown());
+    }
+
     // Final status
     let final_stats = get_graph_stats(&graph);
     println!();
-    println!("{}", "Loop completed:".bold());
+    if interrupted && loop_timed_out.load(Ordering::SeqCst) {
+        println!("{}", "Loop stopped at its time limit:".yellow().bold());
+    } else if interrupted {
+        println!("{}", "Loop interrupted:".yellow().bold());
+    } else {
+        println!("{}", "Loop completed:".bold());
+    }
     println!("  Iterations: {}", iteration);
     println!(
         "  Tasks: {}/{} completed",
         final_stats.done, final_stats.total
     );
     println!("  Time: {}", format_elapsed(start_time.elapsed()));
+    if let Some(spent_usd) = runtime_state.total_cost_usd {
+        println!("  Cost: {}", format_cost(spent_usd));
+    }
 
-    // Clear active tasks
+    // Summarize files touched by more than one task
+    let identifiers: Vec<String> = graph.tasks.values().map(|t| t.identifier.clone()).collect();
+    let base_branch = execution_config
+        .base_branch
+        .clone()
+        .unwrap_or_else(|| "main".to_string());
+    match generate_churn_report(
+        task_id,
+        &identifiers,
+        &worktree_info.path,
+        &base_branch,
+        &branch_name,
+    ) {
+        Ok(report) => {
+            let hotspots = report.hotspots().count();
+            if hotspots > 0 {
+                println!(
+                    "  Churn: {} file(s) touched by multiple tasks (mobius churn {})",
+                    hotspots, task_id
+                );
+            }
+        }
+        Err(e) => tracing::warn!("Failed to write churn report for {}: {}", task_id, e),
+    }
+    match generate_html_report(task_id, &worktree_info.path, &base_branch, &branch_name) {
+        Ok(path) => println!("  Report: {}", path.display()),
+        Err(e) => tracing::warn!("Failed to write HTML report for {}: {}", task_id, e),
+    }
+
+    // Flush runtime state and clear active tasks
+    if interrupted {
+        if let Err(e) = write_runtime_state(&runtime_state) {
+            tracing::warn!("Failed to flush runtime state for {}: {}", task_id, e);
+        }
+        if let Err(e) = write_checkpoint(&mut checkpoint) {
+            tracing::warn!("Failed to write checkpoint for {}: {}", task_id, e);
+        }
+    }
     clear_all_runtime_active_tasks(task_id);
+    set_offline_flag(task_id, false);
 
     // End session
     if all_complete {
+        delete_checkpoint(task_id);
         end_session(task_id, SessionStatus::Completed);
+    } else if interrupted {
+        end_session(task_id, SessionStatus::Interrupted);
     } else if any_failed {
         end_session(task_id, SessionStatus::Failed);
     }
+    let loop_status = if all_complete {
+        "completed"
+    } else if interrupted {
+        "interrupted"
+    } else if any_failed {
+        "failed"
+    } else {
+        "stopped"
+    };
+    notifier.send_blocking(
+        &rt,
+        &Notification::loop_finished(
+            task_id,
+            loop_status,
+            final_stats.done,
+            final_stats.total,
+            start_time.elapsed().as_millis() as u64,
+        ),
+    );
 
     // Auto-submit PR on success
     if all_complete && !no_submit {
@@ -626,11 +1442,13 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
 
         match submit::run(
             Some(task_id),
-            backend_override,
-            model_override,
-            thinking_level_override,
-            false,
-            true,
+            &submit::SubmitOptions {
+                backend_override,
+                model_override,
+                thinking_level_override,
+                skip_status_update: true,
+                ..Default::default()
+            },
         ) {
             Ok(()) => println!("{}", "Pull request created successfully.".green()),
             Err(e) => {
@@ -647,23 +1465,401 @@ pub fn run(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
     // Cleanup
     if all_complete && execution_config.cleanup_on_success != Some(false) {
         println!("{}", "\nCleaning up worktree...".dimmed());
-        let _ = rt.block_on(remove_worktree(task_id, &worktree_config));
-        println!("{}", "Worktree removed.".green());
+        match rt.block_on(remove_worktree(task_id, &worktree_config)) {
+            Ok(()) => println!("{}", "Worktree removed.".green()),
+            Err(e) => println!(
+                "{}",
+                format!("⚠ Worktree kept at {}: {}", worktree_info.path.display(), e).yellow()
+            ),
+        }
 
-        let _ = rt.block_on(destroy_session(&session));
-        println!("{}", "tmux session destroyed.".green());
+        if let Some(ref session) = session {
+            let _ = rt.block_on(destroy_session(session));
+            println!("{}", "tmux session destroyed.".green());
+        }
     } else if any_failed {
         println!("{}", "\nWorktree preserved for debugging at:".yellow());
         println!("  {}", worktree_info.path.display().to_string().dimmed());
-        println!("{}", "tmux session preserved. Attach with:".yellow());
-        println!("  {}", format!("tmux attach -t {}", session_name).dimmed());
+        if session.is_some() {
+            println!("{}", "tmux session preserved. Attach with:".yellow());
+            println!("  {}", format!("tmux attach -t {}", session_name).dimmed());
+        }
     } else {
         println!("{}", "\nWorktree preserved at:".yellow());
         println!("  {}", worktree_info.path.display().to_string().dimmed());
-        println!("{}", "tmux session:".yellow());
-        println!("  {}", format!("tmux attach -t {}", session_name).dimmed());
+        if session.is_some() {
+            println!("{}", "tmux session:".yellow());
+            println!("  {}", format!("tmux attach -t {}", session_name).dimmed());
+        }
+    }
+
+    if interrupted {
+        println!();
+        println!("{}", "Resume where this run stopped with:".yellow());
+        println!("  {}", format!("mobius resume {}", task_id).dimmed());
+    }
+
+    if any_failed {
+        return Err(MobiusError::Agent(format!(
+            "{} of {} sub-tasks completed; see 'mobius logs {}'",
+            final_stats.done, final_stats.total, task_id
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Apply retry/skip requests queued from the dashboard since the last batch.
+///
+/// Skipped tasks reach the graph through their spec status on the next
+/// re-sync; retried ones rejoin the retry queue with a fresh retry budget.
+fn apply_dashboard_actions(
+    task_id: &str,
+    graph: &TaskGraph,
+    tracker: &mut ExecutionTracker,
+    retry_queue: &mut Vec<SubTask>,
+    runtime_state: &mut RuntimeState,
+) -> anyhow::Result<()> {
+    let requests = take_task_actions(task_id).unwrap_or_else(|e| {
+        tracing::warn!("Failed to read dashboard actions for {}: {}", task_id, e);
+        Vec::new()
+    });
+    if requests.is_empty() {
+        return Ok(());
+    }
+    for request in &requests {
+        *runtime_state = apply_task_action(runtime_state, &request.task, request.action);
+        let Some(task) = get_task_by_identifier(graph, &request.task) else {
+            continue;
+        };
+        match request.action {
+            TaskAction::Retry => {
+                forget_task(tracker, &task.id);
+                if !retry_queue.iter().any(|t| t.id == task.id) {
+                    retry_queue.push(task.clone());
+                }
+                println!(
+                    "{}",
+                    format!("↻ {}: retry requested from the dashboard", task.identifier).yellow()
+                );
+            }
+            TaskAction::Skip => {
+                retry_queue.retain(|t| t.id != task.id);
+                println!(
+                    "{}",
+                    format!("⤼ {}: skipped from the dashboard", task.identifier).yellow()
+                );
+            }
+        }
+    }
+    write_runtime_state(runtime_state)
+}
+
+/// Block between batches while `mobius pause` is in effect.
+///
+/// Agents already running have finished by the time this is called; it
+/// returns once the loop is unpaused or interrupted.
+fn wait_while_paused(task_id: &str) {
+    update_session(task_id, Some(SessionStatus::Paused), None);
+    println!(
+        "{}",
+        format!("Paused. Run 'mobius unpause {}' to continue.", task_id).yellow()
+    );
+    while is_paused(task_id) && !shutdown::is_interrupted() {
+        std::thread::sleep(std::time::Duration::from_secs(1));
+    }
+    update_session(task_id, Some(SessionStatus::Active), None);
+    if !shutdown::is_interrupted() {
+        println!("{}", "Resumed.".green());
+    }
+}
+
+/// How often a loop waiting on human tasks asks the backend about them
+const HUMAN_TASK_POLL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Block while only human tasks can make progress.
+///
+/// Returns once one of them is marked done, in its local task file (e.g. by
+/// `mobius deps done`) or on the backend, or `false` if interrupted first.
+fn wait_for_human_tasks(
+    task_id: &str,
+    graph: &TaskGraph,
+    backend: Backend,
+    status_mapping: Option<&StatusMapping>,
+    rt: &tokio::runtime::Runtime,
+) -> bool {
+    let awaiting: Vec<&str> = get_awaiting_human_tasks(graph)
+        .iter()
+        .map(|t| t.identifier.as_str())
+        .collect();
+    update_session(task_id, Some(SessionStatus::Paused), None);
+    println!(
+        "{}",
+        format!(
+            "\nWaiting on {}. Mark done in {} or run 'mobius deps done {} <id>'.",
+            awaiting.join(", "),
+            backend,
+            task_id
+        )
+        .yellow()
+    );
+
+    let mut last_poll: Option<std::time::Instant> = None;
+    loop {
+        if shutdown::is_interrupted() {
+            return false;
+        }
+        let done_locally = read_subtasks(task_id).iter().any(|t| {
+            awaiting.contains(&t.identifier.as_str())
+                && map_backend_status(&t.status, status_mapping) == TaskStatus::Done
+        });
+        if done_locally {
+            break;
+        }
+        if backend != Backend::Local && last_poll.map_or(true, |at| at.elapsed() >= HUMAN_TASK_POLL)
+        {
+            let closed = rt.block_on(sync_human_task_statuses(
+                task_id,
+                graph,
+                backend,
+                status_mapping,
+            ));
+            if !closed.is_empty() {
+                break;
+            }
+            last_poll = Some(std::time::Instant::now());
+        }
+        std::thread::sleep(std::time::Duration::from_secs(1));
+    }
+    update_session(task_id, Some(SessionStatus::Active), None);
+    println!("{}", "Human task done; resuming.".green());
+    true
+}
+
+/// Run the pre-flight checks, printing any that did not pass, and refuse to
+/// start when a required one failed.
+fn check_preflight(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
+    let config = read_config_with_env(&resolve_paths().config_path).unwrap_or_default();
+    let backend = opts
+        .backend_override
+        .and_then(|b| b.parse().ok())
+        .unwrap_or(config.backend);
+    let driver = if routes_to_exit_only_runtime(task_id, &config) {
+        ExecutionDriver::Process
+    } else {
+        config.execution.driver
+    };
+
+    println!("{}", "Running pre-flight checks...".dimmed());
+    let results = run_preflight(
+        &config,
+        &PreflightOptions {
+            backend,
+            driver,
+            check_base_branch: !opts.resume,
+        },
+    );
+    for result in results
+        .iter()
+        .filter(|r| !matches!(r.status, CheckStatus::Pass))
+    {
+        eprintln!("{}", format_result(result));
+    }
+
+    let blocking = results.iter().filter(|r| r.is_blocking()).count();
+    if blocking > 0 {
+        anyhow::bail!(
+            "{} pre-flight check(s) failed. Fix them, or rerun with --skip-preflight.",
+            blocking
+        );
+    }
+    Ok(())
+}
+
+/// Stop before any agent starts when sub-task dependencies form a cycle.
+///
+/// With `--break-cycles` the cycles are reported and the loop proceeds on a
+/// repaired graph instead; see `break_cycles` for which edge is dropped.
+fn check_graph_cycles(graph: &TaskGraph, break_cycles_enabled: bool) -> anyhow::Result<()> {
+    let validation = validate_graph(graph);
+    if validation.is_valid() {
+        return Ok(());
+    }
+
+    let paths: Vec<String> = validation
+        .cycles
+        .iter()
+        .map(|cycle| format_cycle(graph, cycle))
+        .collect();
+    if !break_cycles_enabled {
+        anyhow::bail!(
+            "Dependency cycle detected: {}. Fix the blockedBy relations or rerun with --break-cycles.",
+            paths.join("; ")
+        );
+    }
+
+    for path in &paths {
+        eprintln!("{}", format!("Warning: dependency cycle {}", path).yellow());
+    }
+    let identifier = |id: &str| {
+        get_task_by_id(graph, id)
+            .map(|t| t.identifier.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    for edge in break_cycles(graph).1 {
+        eprintln!(
+            "{}",
+            format!(
+                "  Ignoring: {} blocked by {}",
+                identifier(&edge.task_id),
+                identifier(&edge.blocker_id)
+            )
+            .dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Log sub-tasks the loop had as done that local state no longer shows as done.
+fn log_state_drift(loop_graph: &TaskGraph, synced: &TaskGraph) {
+    if !is_debug_enabled() {
+        return;
+    }
+    for (id, task) in &loop_graph.tasks {
+        let Some(local) = synced.tasks.get(id) else {
+            continue;
+        };
+        if task.status == TaskStatus::Done && local.status != TaskStatus::Done {
+            debug_log(
+                DebugEventType::StateDrift,
+                DebugEventSource::Loop,
+                Some(&task.identifier),
+                HashMap::from([
+                    ("expected".to_string(), serde_json::json!(task.status)),
+                    ("local".to_string(), serde_json::json!(local.status)),
+                ]),
+            );
+        }
+    }
+}
+
+/// Print the batches the scheduler would dispatch, assuming every task
+/// succeeds, with each task's routed runtime and model. Nothing is spawned.
+fn print_plan(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
+    let issues = read_local_subtasks_as_linear_issues(task_id);
+    if issues.is_empty() {
+        anyhow::bail!(
+            "No local sub-tasks found for {}. Run 'mobius pull {}' first.",
+            task_id,
+            task_id
+        );
+    }
+    let parent_id = read_parent_spec(task_id)
+        .map(|p| p.identifier)
+        .unwrap_or_else(|| task_id.to_string());
+    let mut graph =
+        apply_resolved_blockers(task_id, &build_task_graph(task_id, &parent_id, &issues));
+    if opts.break_cycles {
+        graph = break_cycles(&graph).0;
     }
 
+    let config = read_config_with_env(&resolve_paths().config_path).unwrap_or_default();
+    let mut execution_config = config.execution.clone();
+    if let Some(p) = opts.parallel_override {
+        execution_config.max_parallel_agents = Some(p);
+    }
+    let model_override = opts.model_override.map(str::trim).filter(|m| !m.is_empty());
+    if let Some(m) = model_override {
+        execution_config.model = if config.runtime == AgentRuntime::Claude {
+            m.parse::<Model>().map_err(anyhow::Error::msg)?.to_string()
+        } else {
+            m.to_string()
+        };
+    }
+    let (model_override, thinking_level_override) = if config.runtime != AgentRuntime::Claude {
+        (model_override, opts.thinking_level_override)
+    } else {
+        (None, None)
+    };
+
+    let max_parallel = calculate_parallelism(graph.tasks.len(), &execution_config);
+    let max_batches = opts
+        .max_iterations_override
+        .unwrap_or(config.execution.max_iterations) as usize;
+    let batches = plan_batches(
+        &graph,
+        max_parallel,
+        max_batches,
+        config.loop_settings.strategy,
+    );
+    let stats = get_graph_stats(&graph);
+    let scheduled: usize = batches.iter().map(Vec::len).sum();
+
+    println!(
+        "{}",
+        format!(
+            "Plan for {}: {} task(s) in {} batch(es), up to {} in parallel",
+            task_id,
+            scheduled,
+            batches.len(),
+            max_parallel
+        )
+        .bold()
+    );
+    if stats.done > 0 {
+        println!(
+            "{}",
+            format!("  {} task(s) already done", stats.done).dimmed()
+        );
+    }
+    for (i, batch) in batches.iter().enumerate() {
+        println!();
+        println!("{}", format!("Batch {}", i + 1).cyan());
+        for task in batch {
+            let task_runtime = resolve_task_runtime(
+                task,
+                &execution_config,
+                config.runtime,
+                model_override,
+                thinking_level_override,
+            );
+            println!(
+                "  {}  {}  {}",
+                task.identifier,
+                task.title,
+                format!("{}/{}", task_runtime.runtime, task_runtime.model).dimmed()
+            );
+        }
+    }
+
+    let mut unscheduled: Vec<&str> = graph
+        .tasks
+        .values()
+        .filter(|t| t.status != TaskStatus::Done)
+        .filter(|t| !batches.iter().flatten().any(|s| s.id == t.id))
+        .map(|t| t.identifier.as_str())
+        .collect();
+    if !unscheduled.is_empty() {
+        unscheduled.sort_unstable();
+        println!();
+        println!(
+            "{}",
+            format!("Not scheduled: {}", unscheduled.join(", ")).yellow()
+        );
+        if max_batches > 0 && batches.len() == max_batches {
+            println!(
+                "{}",
+                format!("  Iteration limit of {} reached", max_batches).dimmed()
+            );
+        } else {
+            println!(
+                "{}",
+                "  Blocked by external dependencies; see 'mobius deps'".dimmed()
+            );
+        }
+    }
+    println!();
+    println!("{}", "No agents were spawned.".dimmed());
     Ok(())
 }
 
@@ -690,12 +1886,13 @@ fn run_with_tui(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
         .as_ref()
         .map(|p| p.identifier.clone())
         .unwrap_or_else(|| task_id.to_string());
-    let graph = build_task_graph(task_id, &parent_id, &issues);
+    let graph = apply_resolved_blockers(task_id, &build_task_graph(task_id, &parent_id, &issues));
     let runtime_state_path = crate::context::get_runtime_path(task_id);
 
     // 3. Build subprocess args (pass through all overrides, always add --no-tui)
+    let subcommand = if opts.resume { "resume" } else { "loop" };
     let mut args = vec![
-        "loop".to_string(),
+        subcommand.to_string(),
         task_id.to_string(),
         "--no-tui".to_string(),
     ];
@@ -720,6 +1917,17 @@ fn run_with_tui(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
     if no_submit {
         args.push("--no-submit".into());
     }
+    if opts.break_cycles {
+        args.push("--break-cycles".into());
+    }
+    // Already checked before the dashboard started
+    args.push("--skip-preflight".into());
+    if let Some(verbosity) = opts.debug {
+        args.push(format!(
+            "--debug={}",
+            format!("{verbosity:?}").to_lowercase()
+        ));
+    }
 
     // 4. Spawn subprocess with stderr redirected to a log file for diagnostics
     let log_dir = runtime_state_path
@@ -752,6 +1960,7 @@ fn run_with_tui(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
         graph,
         runtime_state_path,
         max_parallel_agents,
+        Default::default(),
     );
 
     // Reap the child if it already exited (avoids lingering zombies), but do not
@@ -763,8 +1972,67 @@ fn run_with_tui(task_id: &str, opts: &LoopOptions<'_>) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Copy sub-tasks changed on the tracker since `since` into the local specs,
+/// so the spec check that follows sees them. `since` only moves forward when
+/// the refresh succeeds; `mobius pull`'s own baseline is left alone.
+fn refresh_from_tracker(
+    task_id: &str,
+    backend: Backend,
+    status_mapping: Option<&StatusMapping>,
+    since: &mut Option<chrono::DateTime<chrono::Utc>>,
+    rt: &tokio::runtime::Runtime,
+) {
+    let started = chrono::Utc::now();
+    match rt.block_on(refresh_subtasks(task_id, backend, *since, status_mapping)) {
+        Ok(refresh) => {
+            *since = Some(started);
+            if refresh.updated > 0 {
+                println!(
+                    "{}",
+                    format!("{} sub-task(s) changed on {}", refresh.updated, backend).dimmed()
+                );
+            }
+        }
+        Err(e) => eprintln!(
+            "{}",
+            format!("Warning: sub-task refresh failed: {}", e).yellow()
+        ),
+    }
+}
+
+/// Keep the dashboard's offline flag in step with this process and announce
+/// switching offline or back online.
+fn note_offline_state(task_id: &str, backend: Backend, offline: &mut bool) {
+    let now_offline = backend != Backend::Local && is_offline();
+    if now_offline == *offline {
+        return;
+    }
+    *offline = now_offline;
+    set_offline_flag(task_id, now_offline);
+    if now_offline {
+        println!(
+            "{}",
+            format!(
+                "Offline: running from local state, {} update(s) queued for 'mobius push'",
+                get_pending_updates_count(task_id)
+            )
+            .yellow()
+        );
+    } else {
+        println!("{}", "Back online; pushing queued updates.".green());
+    }
+}
+
 async fn fetch_parent_issue(task_id: &str, backend: &Backend) -> Result<ParentIssue, String> {
     match backend {
+        _ if is_offline() => read_parent_spec(task_id)
+            .map(|s| ParentIssue {
+                id: s.id,
+                identifier: s.identifier,
+                title: s.title,
+                git_branch_name: s.git_branch_name,
+            })
+            .ok_or_else(|| format!("Offline and no local state found for {}", task_id)),
         Backend::Local => {
             let spec = read_parent_spec(task_id);
             spec.map(|s| ParentIssue {
@@ -821,9 +2089,75 @@ async fn fetch_parent_issue(task_id: &str, backend: &Backend) -> Result<ParentIs
                 None => Err(api_err),
             }
         }
+        Backend::Github => {
+            let api_err = match GithubClient::new() {
+                Ok(client) => match client.fetch_github_issue(task_id).await {
+                    Ok(issue) => return Ok(issue),
+                    Err(e) => e.to_string(),
+                },
+                Err(e) => e.to_string(),
+            };
+            // API failed, try local state fallback
+            tracing::warn!(
+                "GitHub API fetch failed, falling back to local state: {}",
+                api_err
+            );
+            match read_parent_spec(task_id) {
+                Some(s) => Ok(ParentIssue {
+                    id: s.id,
+                    identifier: s.identifier,
+                    title: s.title,
+                    git_branch_name: s.git_branch_name,
+                }),
+                None => Err(api_err),
+            }
+        }
+        Backend::Gitlab => {
+            let api_err = match GitlabClient::new() {
+                Ok(client) => match client.fetch_gitlab_issue(task_id).await {
+                    Ok(issue) => return Ok(issue),
+                    Err(e) => e.to_string(),
+                },
+                Err(e) => e.to_string(),
+            };
+            // API failed, try local state fallback
+            tracing::warn!(
+                "GitLab API fetch failed, falling back to local state: {}",
+                api_err
+            );
+            match read_parent_spec(task_id) {
+                Some(s) => Ok(ParentIssue {
+                    id: s.id,
+                    identifier: s.identifier,
+                    title: s.title,
+                    git_branch_name: s.git_branch_name,
+                }),
+                None => Err(api_err),
+            }
+        }
     }
 }
 
+/// Whether the configured runtime, a runtime rule, or a sub-task's scored
+/// runtime selects a runtime that reports completion only through its exit status.
+fn routes_to_exit_only_runtime(task_id: &str, config: &LoopConfig) -> bool {
+    runtime_adapter::completes_on_exit(config.runtime)
+        || config
+            .execution
+            .runtime_rules
+            .iter()
+            .any(|rule| runtime_adapter::completes_on_exit(rule.runtime))
+        || config
+            .execution
+            .fallback
+            .as_ref()
+            .is_some_and(|fallback| runtime_adapter::completes_on_exit(fallback.runtime))
+        || read_local_subtasks_as_linear_issues(task_id)
+            .iter()
+            .filter_map(|issue| issue.scoring.as_ref()?.recommended_runtime)
+            .any(runtime_adapter::completes_on_exit)
+}
+
 fn mirror_issue_context_to_worktree(task_id: &str, worktree_path: &Path) -> anyhow::Result<String> {
     let source_issue_path = crate::context::get_context_path(task_id);
     if !source_issue_path.exists() {
@@ -890,19 +2224,10 @@ fn format_elapsed(duration: std::time::Duration) -> String {
     }
 }
 
+/// First Ctrl-C (or SIGTERM) stops the loop gracefully; a second exits at once.
 fn ctrlc_handler(task_id: &str) {
     let task_id = task_id.to_string();
-    let _ = ctrlc::set_handler(move || {
+    let _ = shutdown::install_handler(move || {
         clear_all_runtime_active_tasks(&task_id);
-        std::process::exit(130);
     });
 }
-
-fn validate_task_id(task_id: &str, backend: &Backend) -> bool {
-    let pattern = match backend {
-        Backend::Linear => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Jira => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Local => regex::Regex::new(r"^(LOC-\d+|task-\d+)$").unwrap(),
-    };
-    pattern.is_match(task_id)
-}
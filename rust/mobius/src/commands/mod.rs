@@ -1,13 +1,43 @@
+pub mod auth;
+pub mod backend;
+pub mod bisect;
+pub mod bundle;
+pub mod cancel;
+pub mod churn;
 pub mod clean;
+pub mod completions;
 pub mod config;
+pub mod debug;
+pub mod deps;
 pub mod doctor;
+pub mod gc;
+pub mod integrate;
 pub mod list;
+pub mod listen;
+pub mod lock;
+pub mod logs;
 pub mod loop_cmd;
+pub mod new;
+pub mod output;
+pub mod pause;
+pub mod pr_status;
+pub mod primer;
 pub mod pull;
 pub mod push;
+pub mod refine;
+pub mod report;
 pub mod run;
+pub mod search;
+pub mod serve;
 pub mod set_id;
 pub mod setup;
 pub mod shortcuts;
+pub mod stats;
+pub mod status;
 pub mod submit;
+pub mod task;
 pub mod tree;
+pub mod verify;
+pub mod verify_scope;
+pub mod watch;
+pub mod worker;
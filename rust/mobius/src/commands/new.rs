@@ -0,0 +1,294 @@
+//! New command - Create a parent issue and scaffold its local state
+
+use std::io::{IsTerminal, Read};
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Context;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config_with_env;
+use crate::config::paths::resolve_paths;
+use crate::context::get_context_path;
+use crate::jira::{CreateJiraIssueOptions, JiraClient};
+use crate::linear::{CreateLinearIssueInput, LinearClient};
+use crate::local_state::{get_next_local_id, write_parent_spec};
+use crate::runtime_adapter::{build_interactive_command, runtime_cli_command};
+use crate::types::config::LoopConfig;
+use crate::types::context::ParentIssueContext;
+use crate::types::enums::Backend;
+
+/// Issue type used for parent issues created on Jira
+const JIRA_PARENT_TYPE: &str = "Task";
+
+/// Status recorded for a freshly created parent
+const NEW_ISSUE_STATUS: &str = "Backlog";
+
+/// Issue reported by `mobius new --output json`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreatedParent {
+    identifier: String,
+    backend: Backend,
+    url: String,
+    path: String,
+}
+
+pub struct NewOptions<'a> {
+    pub title: &'a str,
+    pub description: Option<&'a str>,
+    /// File to read the description from; `-` reads stdin
+    pub description_file: Option<&'a Path>,
+    pub backend_override: Option<&'a str>,
+    pub labels: &'a [String],
+    /// Open an interactive `/refine` session once the issue exists
+    pub refine: bool,
+}
+
+pub fn run(opts: &NewOptions<'_>, output: OutputFormat) -> anyhow::Result<()> {
+    let title = opts.title.trim();
+    if title.is_empty() {
+        anyhow::bail!("An issue title is required");
+    }
+    let paths = resolve_paths();
+    let config = read_config_with_env(&paths.config_path).unwrap_or_default();
+    let backend: Backend = match opts.backend_override {
+        Some(b) => b.parse().unwrap_or(config.backend),
+        None => config.backend,
+    };
+    let description = read_description(opts.description, opts.description_file)?;
+    let labels = if opts.labels.is_empty() {
+        default_labels(&config, backend)
+    } else {
+        opts.labels.to_vec()
+    };
+
+    let spec = match backend {
+        Backend::Local => {
+            let id = get_next_local_id()?;
+            ParentIssueContext {
+                id: id.clone(),
+                identifier: id,
+                title: title.to_string(),
+                description,
+                git_branch_name: String::new(),
+                status: NEW_ISSUE_STATUS.to_string(),
+                labels,
+                url: String::new(),
+            }
+        }
+        Backend::Linear | Backend::Jira => {
+            let rt = tokio::runtime::Runtime::new()?;
+            rt.block_on(create_remote(backend, &config, title, description, labels))?
+        }
+        Backend::Github | Backend::Gitlab => anyhow::bail!(
+            "mobius new creates issues locally, on Linear or on Jira; create the {} issue there and run 'mobius pull <id>'",
+            backend
+        ),
+    };
+    write_parent_spec(&spec.identifier, &spec)?;
+
+    let created = CreatedParent {
+        identifier: spec.identifier.clone(),
+        backend,
+        url: spec.url.clone(),
+        path: get_context_path(&spec.identifier).display().to_string(),
+    };
+    output.emit(&created, |created| {
+        println!(
+            "{}",
+            format!("✓ Created {} {}", created.identifier, title).green()
+        );
+        if !created.url.is_empty() {
+            println!("  {}", created.url.dimmed());
+        }
+        println!("  {}", created.path.dimmed());
+        if !opts.refine {
+            println!(
+                "{}",
+                format!(
+                    "Break it into sub-tasks with '{} \"/refine {}\"', then run 'mobius loop {}'",
+                    runtime_cli_command(config.runtime),
+                    created.identifier,
+                    created.identifier
+                )
+                .dimmed()
+            );
+        }
+    })?;
+
+    if opts.refine {
+        launch_refine_session(&config, &spec.identifier)?;
+    }
+    Ok(())
+}
+
+/// The description from `--description`, `--description-file`, or piped stdin.
+fn read_description(text: Option<&str>, file: Option<&Path>) -> anyhow::Result<String> {
+    if let Some(text) = text {
+        return Ok(text.trim().to_string());
+    }
+    let description = match file {
+        Some(path) if path == Path::new("-") => read_stdin()?,
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?,
+        None if !std::io::stdin().is_terminal() => read_stdin()?,
+        None => String::new(),
+    };
+    Ok(description.trim().to_string())
+}
+
+fn read_stdin() -> anyhow::Result<String> {
+    let mut buf = String::new();
+    std::io::stdin()
+        .read_to_string(&mut buf)
+        .context("Failed to read the description from stdin")?;
+    Ok(buf)
+}
+
+fn default_labels(config: &LoopConfig, backend: Backend) -> Vec<String> {
+    let labels = match backend {
+        Backend::Linear => config
+            .linear
+            .as_ref()
+            .and_then(|l| l.default_labels.clone()),
+        Backend::Jira => config.jira.as_ref().and_then(|j| j.default_labels.clone()),
+        Backend::Github => config
+            .github
+            .as_ref()
+            .and_then(|g| g.default_labels.clone()),
+        Backend::Gitlab => config
+            .gitlab
+            .as_ref()
+            .and_then(|g| g.default_labels.clone()),
+        Backend::Local => None,
+    };
+    labels.unwrap_or_default()
+}
+
+/// Create the parent issue on Linear or Jira and describe it as a local spec.
+async fn create_remote(
+    backend: Backend,
+    config: &LoopConfig,
+    title: &str,
+    description: String,
+    labels: Vec<String>,
+) -> anyhow::Result<ParentIssueContext> {
+    let spec =
+        |id: String, identifier: String, git_branch_name: String, url: String| ParentIssueContext {
+            id,
+            identifier,
+            title: title.to_string(),
+            description: description.clone(),
+            git_branch_name,
+            status: NEW_ISSUE_STATUS.to_string(),
+            labels: Vec::new(),
+            url,
+        };
+    if backend == Backend::Linear {
+        let team = config
+            .linear
+            .as_ref()
+            .and_then(|l| l.team.as_deref())
+            .context("Set linear.team in mobius.config.yaml to create Linear issues")?;
+        let client = LinearClient::new()?;
+        let team_id = client
+            .find_linear_team_id(team)
+            .await
+            .with_context(|| format!("Failed to look up Linear team {}", team))?;
+        let input = CreateLinearIssueInput {
+            team_id,
+            title: title.to_string(),
+            description: Some(description.clone()).filter(|d| !d.is_empty()),
+            parent_id: None,
+            blocked_by: Vec::new(),
+            // Linear takes label IDs, not the names mobius is configured with
+            labels: Vec::new(),
+            priority: None,
+        };
+        let issue = client
+            .create_linear_issue(&input)
+            .await
+            .context("Failed to create the Linear issue")?;
+        return Ok(spec(
+            issue.id,
+            issue.identifier,
+            issue.branch_name,
+            issue.url,
+        ));
+    }
+
+    let jira = config.jira.as_ref();
+    let project_key = jira
+        .and_then(|j| j.project_key.clone())
+        .context("Set jira.project_key in mobius.config.yaml to create Jira issues")?;
+    let options = CreateJiraIssueOptions {
+        project_key,
+        issue_type_name: JIRA_PARENT_TYPE.to_string(),
+        summary: title.to_string(),
+        description: Some(description.clone()).filter(|d| !d.is_empty()),
+        parent_key: None,
+        labels: Some(labels.clone()),
+        assignee_id: None,
+    };
+    let issue = JiraClient::new()?
+        .create_jira_issue(&options)
+        .await
+        .context("Failed to create the Jira issue")?;
+    let url = jira
+        .and_then(|j| j.base_url.as_deref())
+        .map(|base| format!("{}/browse/{}", base.trim_end_matches('/'), issue.key))
+        .unwrap_or_default();
+    Ok(ParentIssueContext {
+        labels,
+        ..spec(issue.id, issue.key, String::new(), url)
+    })
+}
+
+/// Hand the terminal to the configured runtime running the `/refine` skill.
+fn launch_refine_session(config: &LoopConfig, identifier: &str) -> anyhow::Result<()> {
+    let prompt = format!("/refine {}", identifier);
+    let Some(args) = build_interactive_command(config.runtime, &prompt) else {
+        anyhow::bail!(
+            "{} has no interactive /refine session; break {} into sub-tasks with another runtime",
+            runtime_cli_command(config.runtime),
+            identifier
+        );
+    };
+    println!(
+        "{}",
+        format!("\nStarting sub-task breakdown: {}", args.join(" ")).cyan()
+    );
+    let status = Command::new(&args[0])
+        .args(&args[1..])
+        .status()
+        .with_context(|| format!("Failed to start {}", args[0]))?;
+    if !status.success() {
+        anyhow::bail!("{} exited with {}", args[0], status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_description_prefers_inline_text_then_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("desc.md");
+        std::fs::write(&file, "  From the file\n").unwrap();
+
+        assert_eq!(
+            read_description(Some(" Inline "), Some(&file)).unwrap(),
+            "Inline"
+        );
+        assert_eq!(
+            read_description(None, Some(&file)).unwrap(),
+            "From the file"
+        );
+        assert!(read_description(None, Some(&dir.path().join("missing.md"))).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+//! Shared output mode for commands (`--output text|json`)
+//!
+//! Commands build a serializable result and hand it to `OutputFormat::emit`,
+//! which prints it as JSON or runs the command's text renderer. Progress
+//! lines go through `OutputFormat::note` so stdout stays parseable in JSON
+//! mode; errors and warnings keep going to stderr either way (a failed command
+//! prints a JSON error object there in JSON mode, see `crate::error`).
+
+use std::fmt::Display;
+
+use serde::Serialize;
+
+/// Output format selected with the global `--output` flag
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable, colored output
+    #[default]
+    Text,
+    /// Machine-readable JSON on stdout
+    Json,
+}
+
+impl OutputFormat {
+    pub fn is_json(self) -> bool {
+        self == OutputFormat::Json
+    }
+
+    /// Print `value` as JSON, or render it with `text`.
+    pub fn emit<T: Serialize>(self, value: &T, text: impl FnOnce(&T)) -> anyhow::Result<()> {
+        match self {
+            OutputFormat::Json => println!("{}", to_json(value)?),
+            OutputFormat::Text => text(value),
+        }
+        Ok(())
+    }
+
+    /// Print a human-only progress line; suppressed in JSON mode.
+    pub fn note(self, line: impl Display) {
+        if !self.is_json() {
+            println!("{}", line);
+        }
+    }
+}
+
+/// Serialize a command result as pretty-printed JSON.
+pub fn to_json<T: Serialize>(value: &T) -> anyhow::Result<String> {
+    Ok(serde_json::to_string_pretty(value)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::ValueEnum;
+
+    #[test]
+    fn test_output_format_parses_flag_values() {
+        assert_eq!(
+            OutputFormat::from_str("json", true).unwrap(),
+            OutputFormat::Json
+        );
+        assert_eq!(
+            OutputFormat::from_str("text", true).unwrap(),
+            OutputFormat::Text
+        );
+        assert!(OutputFormat::from_str("yaml", true).is_err());
+        assert_eq!(OutputFormat::default(), OutputFormat::Text);
+    }
+
+    #[test]
+    fn test_emit_runs_text_renderer_only_in_text_mode() {
+        let mut rendered = false;
+        OutputFormat::Text
+            .emit(&vec![1, 2], |v| rendered = v.len() == 2)
+            .unwrap();
+        assert!(rendered);
+
+        let mut rendered = false;
+        OutputFormat::Json
+            .emit(&vec![1, 2], |_| rendered = true)
+            .unwrap();
+        assert!(!rendered);
+    }
+
+    #[test]
+    fn test_to_json_is_pretty_printed() {
+        let json = to_json(&serde_json::json!({ "id": "MOB-1" })).unwrap();
+        assert_eq!(json, "{\n  \"id\": \"MOB-1\"\n}");
+    }
+}
@@ -0,0 +1,68 @@
+//! Pause command - Hold a running loop between batches, or let it continue
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::output::OutputFormat;
+use crate::context::{
+    is_process_running, read_runtime_state, read_session, resolve_task_id, set_paused,
+};
+
+/// Result of `mobius pause` / `mobius unpause`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseReport {
+    pub parent_id: String,
+    pub paused: bool,
+    /// Whether a loop process is running to act on the flag
+    pub loop_running: bool,
+}
+
+pub fn run(task_id: Option<&str>, paused: bool, output: OutputFormat) -> anyhow::Result<()> {
+    let command = if paused { "pause" } else { "unpause" };
+    let Some(parent_id) = resolve_task_id(task_id) else {
+        anyhow::bail!(
+            "No task ID provided and no current task set. Usage: mobius {} <task-id>",
+            command
+        );
+    };
+
+    let report = pause_loop(&parent_id, paused)?;
+    output.emit(&report, print_pause_report)
+}
+
+/// Set or clear the pause flag of `parent_id`'s loop.
+pub fn pause_loop(parent_id: &str, paused: bool) -> anyhow::Result<PauseReport> {
+    let runtime = read_runtime_state(parent_id);
+    if read_session(parent_id).is_none() && runtime.is_none() {
+        anyhow::bail!("No loop state found for {}", parent_id);
+    }
+    set_paused(parent_id, paused)?;
+
+    let loop_running = runtime
+        .and_then(|r| r.loop_pid)
+        .is_some_and(|pid| pid != 0 && is_process_running(pid));
+    Ok(PauseReport {
+        parent_id: parent_id.to_string(),
+        paused,
+        loop_running,
+    })
+}
+
+fn print_pause_report(report: &PauseReport) {
+    if report.paused {
+        println!("{}", format!("Paused {}", report.parent_id).yellow().bold());
+        println!(
+            "  {}",
+            "Running agents will finish; no new agents start until unpaused.".dimmed()
+        );
+    } else {
+        println!(
+            "{}",
+            format!("Unpaused {}", report.parent_id).green().bold()
+        );
+    }
+    if !report.loop_running {
+        println!("  {}", "No loop is running for this task.".dimmed());
+    }
+}
@@ -0,0 +1,305 @@
+//! PR status command - CI checks and review state of a parent's pull request,
+//! moving the parent issue to Done once it merges
+//!
+//! GitHub is queried through the `gh` CLI and GitLab through its API. The PR
+//! is the one opened from the loop branch.
+
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::churn::loop_branch;
+use crate::commands::output::OutputFormat;
+use crate::commands::submit::{gh, set_parent_status};
+use crate::config::loader::read_config_with_env;
+use crate::config::paths::resolve_paths;
+use crate::context::resolve_task_id;
+use crate::gitlab::{GitlabClient, GitlabMergeRequestStatus};
+use crate::types::enums::Backend;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PrState {
+    Open,
+    Merged,
+    Closed,
+}
+
+/// CI checks on the PR's head commit, by outcome
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CheckCounts {
+    pub passed: usize,
+    pub failed: usize,
+    pub pending: usize,
+}
+
+/// Everything `mobius pr-status` reports for one parent issue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrStatusReport {
+    pub parent_id: String,
+    pub branch: String,
+    pub url: String,
+    pub state: PrState,
+    pub draft: bool,
+    pub checks: CheckCounts,
+    /// e.g. `approved`, `changes_requested`, `review_required`
+    pub review: Option<String>,
+    pub auto_merge: bool,
+    /// Whether this run moved the parent issue to Done
+    pub issue_updated: bool,
+}
+
+pub fn run(
+    task_id: Option<&str>,
+    backend_override: Option<&str>,
+    watch: bool,
+    interval_secs: u64,
+    skip_status_update: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let Some(parent_id) = resolve_task_id(task_id) else {
+        anyhow::bail!(
+            "No task ID provided and no current task set. Usage: mobius pr-status <task-id>"
+        );
+    };
+    let config = read_config_with_env(&resolve_paths().config_path).unwrap_or_default();
+    let backend = backend_override
+        .and_then(|b| b.parse().ok())
+        .unwrap_or(config.backend);
+    let (branch, _) = loop_branch(&parent_id);
+
+    let mut last: Option<PrStatusReport> = None;
+    let mut report = loop {
+        let report = fetch_pr_status(&parent_id, &branch, backend)?;
+        if !watch || report.state != PrState::Open {
+            break report;
+        }
+        if last.as_ref() != Some(&report) {
+            output.note(status_line(&report));
+        }
+        last = Some(report);
+        thread::sleep(Duration::from_secs(interval_secs.max(1)));
+    };
+
+    if report.state == PrState::Merged && !skip_status_update {
+        match set_parent_status(
+            &parent_id,
+            &backend,
+            "Done",
+            config.status_mapping_for(backend),
+        ) {
+            Ok(()) => report.issue_updated = true,
+            Err(e) => eprintln!(
+                "{}",
+                format!("⚠ Could not update {} status to \"Done\": {}", parent_id, e).yellow()
+            ),
+        }
+    }
+    output.emit(&report, print_pr_status)
+}
+
+fn fetch_pr_status(
+    parent_id: &str,
+    branch: &str,
+    backend: Backend,
+) -> anyhow::Result<PrStatusReport> {
+    let mut report = if backend == Backend::Gitlab {
+        let rt = tokio::runtime::Runtime::new()?;
+        let mr = rt
+            .block_on(async {
+                GitlabClient::new()?
+                    .fetch_gitlab_merge_request_for_branch(branch)
+                    .await
+            })?
+            .ok_or_else(|| anyhow::anyhow!("No merge request found for branch {}", branch))?;
+        report_from_gitlab(&mr)
+    } else {
+        let json = gh(
+            Path::new("."),
+            &[
+                "pr",
+                "view",
+                branch,
+                "--json",
+                "url,state,isDraft,reviewDecision,statusCheckRollup,autoMergeRequest",
+            ],
+        )
+        .map_err(|e| anyhow::anyhow!("No pull request found for branch {}: {}", branch, e))?;
+        parse_github_pr_view(&json)?
+    };
+    report.parent_id = parent_id.to_string();
+    report.branch = branch.to_string();
+    Ok(report)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GhPullView {
+    url: String,
+    state: String,
+    #[serde(default)]
+    is_draft: bool,
+    #[serde(default)]
+    review_decision: Option<String>,
+    #[serde(default)]
+    status_check_rollup: Vec<GhCheck>,
+    #[serde(default)]
+    auto_merge_request: Option<serde_json::Value>,
+}
+
+/// A check run (`status`/`conclusion`) or commit status context (`state`)
+#[derive(Debug, Deserialize)]
+struct GhCheck {
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    conclusion: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+}
+
+/// Build a report from `gh pr view --json` output.
+fn parse_github_pr_view(json: &str) -> anyhow::Result<PrStatusReport> {
+    let view: GhPullView = serde_json::from_str(json)?;
+    let mut checks = CheckCounts::default();
+    for check in &view.status_check_rollup {
+        let outcome = match (check.state.as_deref(), check.status.as_deref()) {
+            (Some(state), _) => state,
+            (None, Some("COMPLETED")) => check.conclusion.as_deref().unwrap_or("PENDING"),
+            _ => "PENDING",
+        };
+        match outcome {
+            "SUCCESS" | "NEUTRAL" | "SKIPPED" => checks.passed += 1,
+            "PENDING" | "EXPECTED" => checks.pending += 1,
+            _ => checks.failed += 1,
+        }
+    }
+    Ok(PrStatusReport {
+        parent_id: String::new(),
+        branch: String::new(),
+        url: view.url,
+        state: match view.state.as_str() {
+            "MERGED" => PrState::Merged,
+            "CLOSED" => PrState::Closed,
+            _ => PrState::Open,
+        },
+        draft: view.is_draft,
+        checks,
+        review: view
+            .review_decision
+            .filter(|r| !r.is_empty())
+            .map(|r| r.to_lowercase()),
+        auto_merge: view.auto_merge_request.is_some_and(|a| !a.is_null()),
+        issue_updated: false,
+    })
+}
+
+fn report_from_gitlab(mr: &GitlabMergeRequestStatus) -> PrStatusReport {
+    let mut checks = CheckCounts::default();
+    match mr.head_pipeline.as_ref().map(|p| p.status.as_str()) {
+        None => {}
+        Some("success" | "skipped") => checks.passed = 1,
+        Some("failed" | "canceled") => checks.failed = 1,
+        Some(_) => checks.pending = 1,
+    }
+    PrStatusReport {
+        parent_id: String::new(),
+        branch: String::new(),
+        url: mr.web_url.clone(),
+        state: match mr.state.as_str() {
+            "merged" => PrState::Merged,
+            "closed" | "locked" => PrState::Closed,
+            _ => PrState::Open,
+        },
+        draft: mr.draft,
+        checks,
+        review: (mr.detailed_merge_status.as_deref() == Some("not_approved"))
+            .then(|| "review_required".to_string()),
+        auto_merge: mr.merge_when_pipeline_succeeds,
+        issue_updated: false,
+    }
+}
+
+/// One-line summary, printed on each change while watching.
+fn status_line(report: &PrStatusReport) -> String {
+    let state = match report.state {
+        PrState::Open if report.draft => "draft".yellow(),
+        PrState::Open => "open".cyan(),
+        PrState::Merged => "merged".green(),
+        PrState::Closed => "closed".red(),
+    };
+    let checks = &report.checks;
+    let mut line = format!(
+        "{} {}  checks: {} passed, {} failed, {} pending",
+        report.parent_id.bold(),
+        state,
+        checks.passed,
+        checks.failed,
+        checks.pending
+    );
+    if let Some(review) = &report.review {
+        line.push_str(&format!("  review: {}", review.replace('_', " ")));
+    }
+    if report.auto_merge {
+        line.push_str("  auto-merge on");
+    }
+    line
+}
+
+fn print_pr_status(report: &PrStatusReport) {
+    println!("{}", status_line(report));
+    println!("{}", report.url.dimmed());
+    if report.issue_updated {
+        println!(
+            "{}",
+            format!("✓ Updated {} status to \"Done\"", report.parent_id).green()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_github_pr_view_counts_checks_and_review() {
+        let json = r#"{
+            "url": "https://github.com/o/r/pull/7",
+            "state": "OPEN",
+            "isDraft": false,
+            "reviewDecision": "CHANGES_REQUESTED",
+            "autoMergeRequest": {"mergeMethod": "SQUASH"},
+            "statusCheckRollup": [
+                {"__typename": "CheckRun", "status": "COMPLETED", "conclusion": "SUCCESS"},
+                {"__typename": "CheckRun", "status": "COMPLETED", "conclusion": "SKIPPED"},
+                {"__typename": "CheckRun", "status": "COMPLETED", "conclusion": "FAILURE"},
+                {"__typename": "CheckRun", "status": "IN_PROGRESS", "conclusion": ""},
+                {"__typename": "StatusContext", "state": "PENDING"}
+            ]
+        }"#;
+        let report = parse_github_pr_view(json).unwrap();
+        assert_eq!(report.state, PrState::Open);
+        assert_eq!(
+            report.checks,
+            CheckCounts {
+                passed: 2,
+                failed: 1,
+                pending: 2
+            }
+        );
+        assert_eq!(report.review.as_deref(), Some("changes_requested"));
+        assert!(report.auto_merge);
+
+        let merged = parse_github_pr_view(
+            r#"{"url": "u", "state": "MERGED", "reviewDecision": "", "autoMergeRequest": null}"#,
+        )
+        .unwrap();
+        assert_eq!(merged.state, PrState::Merged);
+        assert_eq!(merged.review, None);
+        assert!(!merged.auto_merge);
+    }
+}
@@ -0,0 +1,29 @@
+//! Primer command - Show or regenerate the cached repository primer
+
+use colored::Colorize;
+
+use crate::commands::output::OutputFormat;
+use crate::local_state::get_project_mobius_path;
+use crate::primer::{get_primer_path, load_or_generate_primer, RepoPrimer};
+
+pub fn run(refresh: bool, output: OutputFormat) -> anyhow::Result<()> {
+    let mobius_path = get_project_mobius_path();
+    let root = mobius_path
+        .parent()
+        .ok_or_else(|| anyhow::anyhow!("Could not resolve repository root"))?;
+    let primer = load_or_generate_primer(root, refresh)?;
+
+    output.note(
+        format!(
+            "Primer: {} (generated {})",
+            get_primer_path(root).display(),
+            primer.generated_at
+        )
+        .dimmed(),
+    );
+    output.emit(&primer, print_primer)
+}
+
+fn print_primer(primer: &RepoPrimer) {
+    println!("\n{}", primer.content);
+}
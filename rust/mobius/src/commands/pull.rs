@@ -1,15 +1,27 @@
-//! Pull command - Fetch fresh context from Linear/Jira
+//! Pull command - Fetch fresh context from Linear/Jira/GitHub/GitLab
 
+use std::collections::HashSet;
+
+use chrono::Utc;
 use colored::Colorize;
 
 use crate::config::loader::read_config;
 use crate::config::paths::resolve_paths;
 use crate::context::{
-    generate_context, get_full_context_path, resolve_task_id, write_full_context_file,
+    generate_context, get_full_context_path, read_last_pull, resolve_task_id,
+    write_full_context_file, write_last_pull,
 };
+use crate::field_mapping::{supports_field_mapping, sync_fields_from_backend};
+use crate::offline::is_offline;
+use crate::search_index::refresh_index;
+use crate::subtask_sync::{refresh_subtasks_from_backend, supports_subtask_refresh};
 use crate::types::enums::Backend;
 
-pub fn run(task_id: Option<&str>, backend_override: Option<&str>) -> anyhow::Result<()> {
+pub fn run(
+    task_id: Option<&str>,
+    backend_override: Option<&str>,
+    full: bool,
+) -> anyhow::Result<()> {
     // Resolve task ID
     let resolved_id = resolve_task_id(task_id);
 
@@ -38,7 +50,7 @@ pub fn run(task_id: Option<&str>, backend_override: Option<&str>) -> anyhow::Res
     };
 
     // Validate task ID format
-    if !validate_task_id(&resolved_id, &backend) {
+    if !backend.is_valid_task_id(&resolved_id) {
         eprintln!(
             "{}",
             format!(
@@ -54,19 +66,126 @@ pub fn run(task_id: Option<&str>, backend_override: Option<&str>) -> anyhow::Res
         std::process::exit(1);
     }
 
-    println!(
-        "Fetching context for {} from {}...",
-        resolved_id.cyan(),
-        backend
-    );
+    // Offline, the context is rebuilt from the cached specs alone
+    let offline = backend != Backend::Local && is_offline();
+    if offline {
+        println!(
+            "{}",
+            format!(
+                "Offline: rebuilding context for {} from cached specs",
+                resolved_id
+            )
+            .yellow()
+        );
+    } else {
+        println!(
+            "Fetching context for {} from {}...",
+            resolved_id.cyan(),
+            backend
+        );
+    }
+
+    let pull_started = Utc::now();
+    // Nothing was synced offline, so the last-pull baseline stays put
+    let mut synced = !offline;
+    // Sub-tasks the delta refresh downloaded; `None` when every one was
+    let mut changed: Option<HashSet<String>> = None;
+
+    // Refresh tracker-owned sub-task fields, downloading only the sub-tasks
+    // changed since the last completed pull
+    if supports_subtask_refresh(backend) && !offline {
+        let since = if full {
+            None
+        } else {
+            read_last_pull(&resolved_id)
+        };
+        match refresh_subtasks_from_backend(
+            &resolved_id,
+            backend,
+            since,
+            config.status_mapping_for(backend),
+        ) {
+            Ok(mut refresh) => {
+                if since.is_some() {
+                    changed = Some(std::mem::take(&mut refresh.identifiers));
+                }
+                let scope = if since.is_some() {
+                    "changed"
+                } else {
+                    "fetched"
+                };
+                println!(
+                    "{} {} sub-task(s) {}, {} updated locally",
+                    "✓".green(),
+                    refresh.fetched,
+                    scope,
+                    refresh.updated
+                );
+                if refresh.untracked > 0 {
+                    println!(
+                        "{}",
+                        format!(
+                            "{} sub-task(s) on {} have no local spec and were skipped",
+                            refresh.untracked, backend
+                        )
+                        .dimmed()
+                    );
+                }
+            }
+            Err(e) => {
+                synced = false;
+                eprintln!(
+                    "{}",
+                    format!("Warning: sub-task refresh failed: {}", e).yellow()
+                );
+            }
+        }
+    }
+
+    // Copy mapped tracker fields into local sub-task specs before the
+    // context snapshot is rebuilt from them
+    if let Some(mapping) = config
+        .field_mapping_for(backend)
+        .filter(|_| supports_field_mapping(backend) && !offline)
+    {
+        match sync_fields_from_backend(&resolved_id, backend, mapping, changed.as_ref()) {
+            Ok(0) => {}
+            Ok(n) => println!(
+                "{} Updated scoring fields for {} sub-task(s)",
+                "✓".green(),
+                n
+            ),
+            Err(e) => {
+                synced = false;
+                eprintln!(
+                    "{}",
+                    format!("Warning: field mapping sync failed: {}", e).yellow()
+                );
+            }
+        }
+    }
 
     // Generate context
     match generate_context(&resolved_id, None, false) {
         Ok(Some(context)) => {
             // Write full context file
             write_full_context_file(&resolved_id, &context)?;
+            // Best effort: `mobius search` refreshes the index itself too
+            let _ = refresh_index();
+            // A failed sync must not move the baseline later pulls diff against
+            if synced {
+                if let Err(e) = write_last_pull(&resolved_id, pull_started) {
+                    tracing::warn!("Failed to record pull time: {}", e);
+                }
+            }
 
-            println!("{} Context fetched for {}", "✓".green(), resolved_id.cyan());
+            let verb = if offline { "rebuilt" } else { "fetched" };
+            println!(
+                "{} Context {} for {}",
+                "✓".green(),
+                verb,
+                resolved_id.cyan()
+            );
 
             // Display summary
             println!();
@@ -113,6 +232,12 @@ pub fn run(task_id: Option<&str>, backend_override: Option<&str>) -> anyhow::Res
         }
         Ok(None) => {
             eprintln!("{}", format!("No context found for {}", resolved_id).red());
+            if offline {
+                eprintln!(
+                    "{}",
+                    "Nothing is cached for it yet; pull it once while online.".dimmed()
+                );
+            }
             std::process::exit(1);
         }
         Err(e) => {
@@ -127,12 +252,3 @@ pub fn run(task_id: Option<&str>, backend_override: Option<&str>) -> anyhow::Res
 
     Ok(())
 }
-
-fn validate_task_id(task_id: &str, backend: &Backend) -> bool {
-    let pattern = match backend {
-        Backend::Linear => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Jira => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Local => regex::Regex::new(r"^(LOC-\d+|task-\d+)$").unwrap(),
-    };
-    pattern.is_match(task_id)
-}
@@ -1,20 +1,54 @@
-//! Push command - Push pending local changes to Linear/Jira
+//! Push command - Push pending local changes to Linear/Jira/GitHub/GitLab
 
+use anyhow::Context as AnyhowContext;
 use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::fs;
 
+use crate::commands::output::OutputFormat;
 use crate::config::loader::read_config;
 use crate::config::paths::resolve_paths;
 use crate::context::{
     get_context_path, get_pending_updates_path, get_sync_log_path, read_pending_updates,
-    resolve_task_id, write_pending_updates,
+    resolve_task_id, with_pending_updates_sync,
 };
+use crate::field_mapping::{
+    pending_field_updates, supports_field_mapping, update_backend_fields, FieldValues,
+};
+use crate::github::GithubClient;
+use crate::gitlab::GitlabClient;
 use crate::jira::JiraClient;
+use crate::local_state::update_subtask_status;
 use crate::local_state::{
-    get_project_mobius_path, read_iteration_log, write_summary, CompletionSummary, IterationStatus,
+    get_project_mobius_path, read_iteration_log, read_subtasks, remove_subtask_spec,
+    write_subtask_spec, write_summary, CompletionSummary, IterationStatus,
+};
+use crate::offline::is_offline;
+use crate::subtask_batch::{order_drafts, CreatedSubtask, DraftSubtask, SubtaskBatch};
+use crate::types::config::{LoopConfig, StatusMapping};
+use crate::types::context::{
+    IssueRef, PendingUpdate, PendingUpdateData, SubTaskContext, SyncLog, SyncLogEntry,
+    UpdateConflict,
 };
-use crate::types::context::{PendingUpdate, SyncLog, SyncLogEntry};
 use crate::types::enums::{Backend, PendingUpdateType};
+use crate::types::task_graph::map_backend_status;
+
+/// Pending update reported by `push --dry-run --output json`
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PlannedUpdate<'a> {
+    parent_id: &'a str,
+    #[serde(flatten)]
+    update: &'a PendingUpdate,
+}
+
+/// Scoring fields reported by `push --scoring --dry-run --output json`
+#[derive(Serialize)]
+struct PlannedFieldUpdate<'a> {
+    identifier: &'a str,
+    fields: &'a FieldValues,
+}
 
 struct PushResult {
     update_id: String,
@@ -22,15 +56,43 @@ struct PushResult {
     issue_identifier: String,
     success: bool,
     error: Option<String>,
+    /// Remote value that held the update back
+    conflict: Option<String>,
+}
+
+/// How a queued update relates to the backend's current value
+#[derive(Debug, PartialEq, Eq)]
+enum RemoteCheck {
+    /// The backend still matches the local snapshot
+    Clear,
+    /// The backend already holds the queued value
+    AlreadyApplied,
+    /// The backend changed since the update was queued
+    Conflict(String),
+}
+
+pub struct PushOptions<'a> {
+    pub backend_override: Option<&'a str>,
+    pub dry_run: bool,
+    pub all: bool,
+    pub summary: bool,
+    pub scoring: bool,
+    /// Review and create queued sub-tasks as one batch
+    pub review: bool,
+    /// Walk through conflicted updates interactively
+    pub resolve: bool,
 }
 
 pub fn run(
     parent_id: Option<&str>,
-    backend_override: Option<&str>,
-    dry_run: bool,
-    all: bool,
-    summary: bool,
+    opts: &PushOptions<'_>,
+    output: OutputFormat,
 ) -> anyhow::Result<()> {
+    let backend_override = opts.backend_override;
+    let dry_run = opts.dry_run;
+    let all = opts.all;
+    let summary = opts.summary;
+    let scoring = opts.scoring;
     let paths = resolve_paths();
     let config = read_config(&paths.config_path).unwrap_or_default();
     let backend: Backend = if let Some(b) = backend_override {
@@ -50,6 +112,25 @@ pub fn run(
         return push_loop_summary(&resolved_id.unwrap(), &backend);
     }
 
+    // Handle --scoring flag
+    if scoring {
+        let resolved_id = resolve_task_id(parent_id);
+        if resolved_id.is_none() {
+            eprintln!("{}", "Error: No task ID provided for scoring".red());
+            eprintln!("{}", "Usage: mobius push <task-id> --scoring".dimmed());
+            std::process::exit(1);
+        }
+        return push_scoring_fields(&resolved_id.unwrap(), backend, &config, dry_run, output);
+    }
+
+    if opts.review {
+        return push_review(parent_id, all, backend, &config, dry_run, output);
+    }
+
+    if opts.resolve {
+        return push_resolve(parent_id, all, backend, &config, output);
+    }
+
     // Resolve which issues to push
     let resolved_id = if all {
         None
@@ -81,24 +162,59 @@ pub fn run(
     let mut total_pending = 0;
     let mut all_updates: Vec<(String, PendingUpdate)> = Vec::new();
 
+    let mut drafts = 0;
+    let mut held = 0;
+
     for issue_id in &issues_to_push {
         let queue = read_pending_updates(issue_id);
         for update in &queue.updates {
             let synced = update.synced_at.is_some();
             let has_error = update.error.is_some();
-            if !synced && !has_error {
+            if DraftSubtask::from_update(update).is_some() {
+                drafts += 1;
+            } else if update.conflict.is_some() && !synced {
+                held += 1;
+            } else if !synced && !has_error {
                 total_pending += 1;
                 all_updates.push((issue_id.clone(), update.clone()));
             }
         }
     }
+    if drafts > 0 && !output.is_json() {
+        println!(
+            "{}",
+            format!(
+                "{} queued sub-task(s) wait for review: run 'mobius push --review' to create them",
+                drafts
+            )
+            .yellow()
+        );
+    }
+    if held > 0 && !output.is_json() {
+        println!(
+            "{}",
+            format!(
+                "{} conflicted update(s) are held back: run 'mobius push --resolve' to settle them",
+                held
+            )
+            .yellow()
+        );
+    }
+
+    // Dry run mode
+    if dry_run && output.is_json() {
+        let planned: Vec<PlannedUpdate> = all_updates
+            .iter()
+            .map(|(parent_id, update)| PlannedUpdate { parent_id, update })
+            .collect();
+        return output.emit(&planned, |_| {});
+    }
 
     if total_pending == 0 {
         println!("{}", "No pending updates to push".green());
         return Ok(());
     }
 
-    // Dry run mode
     if dry_run {
         println!("{}", "\nDry run - pending changes to push:\n".bold());
         display_pending_changes(&all_updates, &backend);
@@ -115,23 +231,65 @@ pub fn run(
         return Ok(());
     }
 
+    if backend != Backend::Local && is_offline() {
+        println!(
+            "{}",
+            format!(
+                "Offline: {} update(s) stay queued. Run 'mobius push' without --offline once back online.",
+                total_pending
+            )
+            .yellow()
+        );
+        return Ok(());
+    }
+
     // Execute push
     println!("Pushing {} update(s) to {}...", total_pending, backend);
 
     let rt = tokio::runtime::Runtime::new()?;
+    let status_mapping = config.status_mapping_for(backend);
     let mut success_count = 0;
     let mut failure_count = 0;
+    let mut conflict_count = 0;
     let mut results: Vec<PushResult> = Vec::new();
 
     for (issue_parent_id, update) in &all_updates {
-        let update_value = serde_json::to_value(update).unwrap_or_default();
-        let result = rt.block_on(push_update(&update_value, &backend));
+        let result = match rt.block_on(check_remote(update, &backend, status_mapping)) {
+            Ok(RemoteCheck::Clear) => {
+                let update_value = serde_json::to_value(update).unwrap_or_default();
+                rt.block_on(push_update(&update_value, &backend, status_mapping))
+            }
+            Ok(RemoteCheck::AlreadyApplied) => Ok(()),
+            Ok(RemoteCheck::Conflict(remote)) => {
+                conflict_count += 1;
+                mark_update_conflicted(issue_parent_id, &update.id, &remote);
+                let result = PushResult {
+                    update_id: update.id.clone(),
+                    update_type: get_update_type_str(update),
+                    issue_identifier: get_pending_update_identifier(update),
+                    success: false,
+                    error: Some(format!(
+                        "Remote changed to '{}' since it was queued",
+                        conflict_preview(&remote)
+                    )),
+                    conflict: Some(remote),
+                };
+                log_push_result(issue_parent_id, &result);
+                results.push(result);
+                continue;
+            }
+            Err(e) => Err(anyhow::anyhow!(
+                "Failed to check the remote before pushing: {}",
+                e
+            )),
+        };
         results.push(PushResult {
             update_id: update.id.clone(),
             update_type: get_update_type_str(update),
             issue_identifier: get_pending_update_identifier(update),
             success: result.is_ok(),
             error: result.err().map(|e| e.to_string()),
+            conflict: None,
         });
 
         if results.last().unwrap().success {
@@ -154,12 +312,21 @@ pub fn run(
         log_push_result(issue_parent_id, results.last().unwrap());
     }
 
-    if failure_count == 0 {
+    if failure_count == 0 && conflict_count == 0 {
         println!(
             "{}",
             format!("Successfully pushed {} update(s)", success_count).green()
         );
-    } else if success_count == 0 {
+    } else if failure_count == 0 {
+        println!(
+            "{}",
+            format!(
+                "Pushed {} update(s), {} held back by conflicts",
+                success_count, conflict_count
+            )
+            .yellow()
+        );
+    } else if success_count == 0 && conflict_count == 0 {
         eprintln!(
             "{}",
             format!("Failed to push all {} update(s)", failure_count).red()
@@ -168,8 +335,8 @@ pub fn run(
         println!(
             "{}",
             format!(
-                "Pushed {} update(s), {} failed",
-                success_count, failure_count
+                "Pushed {} update(s), {} failed, {} held back by conflicts",
+                success_count, failure_count, conflict_count
             )
             .yellow()
         );
@@ -179,6 +346,13 @@ pub fn run(
     println!();
     display_push_summary(&results);
 
+    if conflict_count > 0 {
+        println!(
+            "{}",
+            "\nConflicted updates were not pushed. Run 'mobius push --resolve' to keep local, keep remote, or merge"
+                .dimmed()
+        );
+    }
     if failure_count > 0 {
         println!(
             "{}",
@@ -195,6 +369,7 @@ pub fn run(
 pub fn push_pending_updates_for_task(
     parent_id: &str,
     backend: &Backend,
+    status_mapping: Option<&StatusMapping>,
 ) -> (usize, usize, Vec<String>) {
     let queue = read_pending_updates(parent_id);
     let mut pending: Vec<PendingUpdate> = Vec::new();
@@ -202,12 +377,19 @@ pub fn push_pending_updates_for_task(
     for update in &queue.updates {
         let synced = update.synced_at.is_some();
         let has_error = update.error.is_some();
-        if !synced && !has_error {
+        // Queued sub-tasks are only created through `push --review`, and
+        // conflicted updates only through `push --resolve`
+        if !synced
+            && !has_error
+            && update.conflict.is_none()
+            && DraftSubtask::from_update(update).is_none()
+        {
             pending.push(update.clone());
         }
     }
 
-    if pending.is_empty() {
+    // Offline, everything stays queued for a later push
+    if pending.is_empty() || (*backend != Backend::Local && is_offline()) {
         return (0, 0, Vec::new());
     }
 
@@ -227,10 +409,33 @@ pub fn push_pending_updates_for_task(
     let mut errors: Vec<String> = Vec::new();
 
     for update in &pending {
-        let update_value = serde_json::to_value(update).unwrap_or_default();
-        let result = rt.block_on(push_update(&update_value, backend));
+        let result = match rt.block_on(check_remote(update, backend, status_mapping)) {
+            Ok(RemoteCheck::Clear) => {
+                let update_value = serde_json::to_value(update).unwrap_or_default();
+                rt.block_on(push_update(&update_value, backend, status_mapping))
+            }
+            Ok(RemoteCheck::AlreadyApplied) => Ok(()),
+            Ok(RemoteCheck::Conflict(remote)) => {
+                failed += 1;
+                mark_update_conflicted(parent_id, &update.id, &remote);
+                errors.push(format!(
+                    "{}: remote changed to '{}'; run 'mobius push --resolve'",
+                    get_pending_update_identifier(update),
+                    conflict_preview(&remote)
+                ));
+                continue;
+            }
+            Err(e) => Err(anyhow::anyhow!(
+                "Failed to check the remote before pushing: {}",
+                e
+            )),
+        };
         let update_id = update.id.clone();
 
+        // The tracker just became unreachable: keep this and the rest queued
+        if result.is_err() && is_offline() {
+            break;
+        }
         if result.is_ok() {
             success += 1;
             mark_update_synced(parent_id, &update_id);
@@ -246,6 +451,496 @@ pub fn push_pending_updates_for_task(
     (success, failed, errors)
 }
 
+/// Show every queued sub-task, then create them per parent as one batch.
+///
+/// A batch that fails partway is rolled back, leaving its drafts queued.
+fn push_review(
+    parent_id: Option<&str>,
+    all: bool,
+    backend: Backend,
+    config: &LoopConfig,
+    dry_run: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    if backend == Backend::Local {
+        anyhow::bail!(
+            "The local backend has no sub-tasks to create; --review needs a remote backend"
+        );
+    }
+    let resolved_id = if all {
+        None
+    } else {
+        let Some(id) = resolve_task_id(parent_id) else {
+            anyhow::bail!(
+                "No task ID provided and no current task set. Usage: mobius push <task-id> --review"
+            );
+        };
+        Some(id)
+    };
+
+    let mut batches: Vec<(String, Vec<DraftSubtask>)> = Vec::new();
+    for issue_id in get_issues_to_push(resolved_id.as_deref(), all) {
+        let drafts: Vec<DraftSubtask> = read_pending_updates(&issue_id)
+            .updates
+            .iter()
+            .filter_map(DraftSubtask::from_update)
+            .collect();
+        if !drafts.is_empty() {
+            let drafts = order_drafts(drafts).with_context(|| format!("In {}", issue_id))?;
+            batches.push((issue_id, drafts));
+        }
+    }
+
+    if dry_run && output.is_json() {
+        let drafts: Vec<&DraftSubtask> = batches.iter().flat_map(|(_, d)| d).collect();
+        return output.emit(&drafts, |_| {});
+    }
+    let total: usize = batches.iter().map(|(_, d)| d.len()).sum();
+    if total == 0 {
+        println!("{}", "No queued sub-tasks to review".green());
+        return Ok(());
+    }
+
+    display_draft_batches(&batches);
+    if dry_run {
+        println!("{}", "Run without --dry-run to create them".dimmed());
+        return Ok(());
+    }
+    let confirmed = dialoguer::Confirm::new()
+        .with_prompt(format!(
+            "Create {} sub-task{} on {}?",
+            total,
+            if total == 1 { "" } else { "s" },
+            backend
+        ))
+        .default(false)
+        .interact()?;
+    if !confirmed {
+        println!("{}", "Aborted. Sub-tasks remain queued.".dimmed());
+        return Ok(());
+    }
+
+    let rt = tokio::runtime::Runtime::new()?;
+    for (issue_id, drafts) in &batches {
+        let batch = rt.block_on(SubtaskBatch::new(backend, config, issue_id))?;
+        let failure = match rt.block_on(batch.create(drafts)) {
+            Ok(created) => {
+                for issue in &created {
+                    mark_update_synced(issue_id, &issue.update_id);
+                    log_push_result(
+                        issue_id,
+                        &PushResult {
+                            update_id: issue.update_id.clone(),
+                            update_type: "create_subtask".to_string(),
+                            issue_identifier: issue.identifier.clone(),
+                            success: true,
+                            error: None,
+                            conflict: None,
+                        },
+                    );
+                    println!("  {} {} {}", "✓".green(), issue.identifier, issue.title);
+                }
+                rename_created_specs(issue_id, &created)?;
+                continue;
+            }
+            Err(failure) => failure,
+        };
+
+        eprintln!(
+            "{}",
+            format!(
+                "✗ Failed to create \"{}\": {:#}",
+                failure.title, failure.error
+            )
+            .red()
+        );
+        let mut leftover = Vec::new();
+        if !failure.created.is_empty() {
+            println!(
+                "Rolling back {} sub-task(s) created for {}...",
+                failure.created.len(),
+                issue_id
+            );
+        }
+        for (identifier, outcome) in rt.block_on(batch.rollback(&failure.created)) {
+            match outcome {
+                Ok(how) => println!("  {}", format!("{} {}", how, identifier).dimmed()),
+                Err(e) => {
+                    eprintln!(
+                        "  {}",
+                        format!("could not remove {}: {:#}", identifier, e).red()
+                    );
+                    leftover.push(identifier);
+                }
+            }
+        }
+        if !leftover.is_empty() {
+            anyhow::bail!(
+                "Rollback for {} left {} behind; remove them by hand before retrying",
+                issue_id,
+                leftover.join(", ")
+            );
+        }
+        anyhow::bail!(
+            "No sub-tasks were created for {}; they remain queued for 'mobius push --review'",
+            issue_id
+        );
+    }
+
+    println!(
+        "{}",
+        format!("Created {} sub-task(s) on {}", total, backend).green()
+    );
+    Ok(())
+}
+
+/// Move local specs written for drafts (`task-NNN`) to the identifiers the
+/// backend assigned, so the next pull updates them instead of adding copies.
+fn rename_created_specs(parent_id: &str, created: &[CreatedSubtask]) -> anyhow::Result<()> {
+    let (renamed, stale) = renamed_specs(read_subtasks(parent_id), created);
+    for spec in &renamed {
+        write_subtask_spec(parent_id, spec)?;
+    }
+    for identifier in &stale {
+        remove_subtask_spec(parent_id, identifier)?;
+    }
+    Ok(())
+}
+
+/// Specs to rewrite after `created` issues replaced drafts of the same
+/// title, and the old spec names to remove. Blocker references to a renamed
+/// spec are rewritten too.
+fn renamed_specs(
+    specs: Vec<SubTaskContext>,
+    created: &[CreatedSubtask],
+) -> (Vec<SubTaskContext>, Vec<String>) {
+    let spec_key = |spec: &SubTaskContext| {
+        if spec.identifier.is_empty() {
+            spec.id.clone()
+        } else {
+            spec.identifier.clone()
+        }
+    };
+    let renames: HashMap<String, IssueRef> = specs
+        .iter()
+        .filter_map(|spec| {
+            let issue = created.iter().find(|c| c.title == spec.title.trim())?;
+            let key = spec_key(spec);
+            (key != issue.identifier).then(|| {
+                (
+                    key,
+                    IssueRef {
+                        id: issue.id.clone(),
+                        identifier: issue.identifier.clone(),
+                    },
+                )
+            })
+        })
+        .collect();
+    if renames.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let rename_ref = |r: &mut IssueRef| -> bool {
+        let target = renames
+            .get(&r.identifier)
+            .or_else(|| renames.get(&r.id))
+            .cloned();
+        target.map(|t| *r = t).is_some()
+    };
+    let mut rewritten = Vec::new();
+    for mut spec in specs {
+        let mut changed = false;
+        for r in spec.blocked_by.iter_mut().chain(spec.blocks.iter_mut()) {
+            changed |= rename_ref(r);
+        }
+        if let Some(target) = renames.get(&spec_key(&spec)) {
+            spec.id = target.id.clone();
+            spec.identifier = target.identifier.clone();
+            changed = true;
+        }
+        if changed {
+            rewritten.push(spec);
+        }
+    }
+    (rewritten, renames.into_keys().collect())
+}
+
+/// Walk through conflicted updates and settle each one: keep local, keep
+/// remote, or merge by choosing the value to push.
+fn push_resolve(
+    parent_id: Option<&str>,
+    all: bool,
+    backend: Backend,
+    config: &LoopConfig,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let resolved_id = if all {
+        None
+    } else {
+        let Some(id) = resolve_task_id(parent_id) else {
+            anyhow::bail!(
+                "No task ID provided and no current task set. Usage: mobius push <task-id> --resolve"
+            );
+        };
+        Some(id)
+    };
+
+    let mut conflicted: Vec<(String, PendingUpdate)> = Vec::new();
+    for issue_id in get_issues_to_push(resolved_id.as_deref(), all) {
+        for update in read_pending_updates(&issue_id).updates {
+            if update.conflict.is_some() && update.synced_at.is_none() {
+                conflicted.push((issue_id.clone(), update));
+            }
+        }
+    }
+
+    if output.is_json() {
+        let planned: Vec<PlannedUpdate> = conflicted
+            .iter()
+            .map(|(parent_id, update)| PlannedUpdate { parent_id, update })
+            .collect();
+        return output.emit(&planned, |_| {});
+    }
+    if conflicted.is_empty() {
+        println!("{}", "No conflicted updates to resolve".green());
+        return Ok(());
+    }
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let status_mapping = config.status_mapping_for(backend);
+    for (issue_id, update) in &conflicted {
+        let Some(conflict) = &update.conflict else {
+            continue;
+        };
+        let remote = &conflict.remote_value;
+        match &update.data {
+            PendingUpdateData::StatusChange {
+                identifier,
+                old_status,
+                new_status,
+                ..
+            } => {
+                println!();
+                println!(
+                    "{} {} {}",
+                    format_update_type("status_change").cyan(),
+                    identifier.bold(),
+                    format!("({})", issue_id).dimmed()
+                );
+                println!("  queued:  {} → {}", old_status, new_status);
+                println!("  remote:  {}", remote.yellow());
+
+                let choices = [
+                    format!("Keep local (set {} to {})", identifier, new_status),
+                    format!("Keep remote (drop the update, record {} locally)", remote),
+                    "Merge (choose the status to push)".to_string(),
+                    "Skip for now".to_string(),
+                ];
+                let choice = dialoguer::Select::new()
+                    .with_prompt("Resolve conflict")
+                    .items(&choices)
+                    .default(0)
+                    .interact()?;
+
+                match choice {
+                    0 => {
+                        push_resolved(&rt, issue_id, update, &backend, status_mapping);
+                    }
+                    1 => keep_remote(issue_id, update, identifier, remote),
+                    2 => {
+                        let chosen: String = dialoguer::Input::new()
+                            .with_prompt("Status to push")
+                            .with_initial_text(new_status.clone())
+                            .interact_text()?;
+                        if classify_status(old_status, &chosen, remote, status_mapping)
+                            == RemoteCheck::AlreadyApplied
+                        {
+                            keep_remote(issue_id, update, identifier, remote);
+                            continue;
+                        }
+                        let mut merged = update.clone();
+                        if let PendingUpdateData::StatusChange { new_status, .. } = &mut merged.data
+                        {
+                            new_status.clone_from(&chosen);
+                        }
+                        if push_resolved(&rt, issue_id, &merged, &backend, status_mapping) {
+                            update_subtask_status(issue_id, identifier, &chosen);
+                        }
+                    }
+                    _ => println!("  {}", "skipped; still held back".dimmed()),
+                }
+            }
+            PendingUpdateData::UpdateDescription {
+                identifier,
+                description,
+                ..
+            } => {
+                println!();
+                println!(
+                    "{} {} {}",
+                    format_update_type("update_description").cyan(),
+                    identifier.bold(),
+                    format!("({})", issue_id).dimmed()
+                );
+                println!("  queued:  {}", conflict_preview(description));
+                println!("  remote:  {}", conflict_preview(remote).yellow());
+
+                let choices = [
+                    format!("Keep local (push the queued description to {})", identifier),
+                    "Keep remote (drop the update, record the remote description locally)"
+                        .to_string(),
+                    "Merge (edit the description to push)".to_string(),
+                    "Skip for now".to_string(),
+                ];
+                let choice = dialoguer::Select::new()
+                    .with_prompt("Resolve conflict")
+                    .items(&choices)
+                    .default(0)
+                    .interact()?;
+
+                match choice {
+                    0 => {
+                        push_resolved(&rt, issue_id, update, &backend, status_mapping);
+                    }
+                    1 => {
+                        update_subtask_description(issue_id, identifier, remote)?;
+                        mark_update_synced(issue_id, &update.id);
+                        println!("  {} kept remote", "✓".green());
+                    }
+                    2 => {
+                        let Some(chosen) = dialoguer::Editor::new()
+                            .extension(".md")
+                            .edit(&merge_draft(description, remote))?
+                        else {
+                            println!("  {}", "edit aborted; still held back".dimmed());
+                            continue;
+                        };
+                        let chosen = merged_text(&chosen);
+                        if same_text(&chosen, remote) {
+                            update_subtask_description(issue_id, identifier, remote)?;
+                            mark_update_synced(issue_id, &update.id);
+                            println!("  {} kept remote", "✓".green());
+                            continue;
+                        }
+                        let mut merged = update.clone();
+                        if let PendingUpdateData::UpdateDescription { description, .. } =
+                            &mut merged.data
+                        {
+                            description.clone_from(&chosen);
+                        }
+                        if push_resolved(&rt, issue_id, &merged, &backend, status_mapping) {
+                            update_subtask_description(issue_id, identifier, &chosen)?;
+                        }
+                    }
+                    _ => println!("  {}", "skipped; still held back".dimmed()),
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Push a conflicted update as-is, without re-checking the remote.
+fn push_resolved(
+    rt: &tokio::runtime::Runtime,
+    parent_id: &str,
+    update: &PendingUpdate,
+    backend: &Backend,
+    status_mapping: Option<&StatusMapping>,
+) -> bool {
+    let update_value = serde_json::to_value(update).unwrap_or_default();
+    let result = rt.block_on(push_update(&update_value, backend, status_mapping));
+    let push_result = PushResult {
+        update_id: update.id.clone(),
+        update_type: get_update_type_str(update),
+        issue_identifier: get_pending_update_identifier(update),
+        success: result.is_ok(),
+        error: result.err().map(|e| e.to_string()),
+        conflict: None,
+    };
+    log_push_result(parent_id, &push_result);
+    match &push_result.error {
+        None => {
+            mark_update_synced(parent_id, &update.id);
+            println!("  {} pushed", "✓".green());
+        }
+        Some(error) => {
+            // Stays conflicted so the next --resolve offers it again
+            eprintln!("  {} {}", "✗".red(), error);
+        }
+    }
+    push_result.success
+}
+
+/// Drop a conflicted update and adopt the remote value locally.
+fn keep_remote(parent_id: &str, update: &PendingUpdate, identifier: &str, remote: &str) {
+    update_subtask_status(parent_id, identifier, remote);
+    mark_update_synced(parent_id, &update.id);
+    println!("  {} kept remote", "✓".green());
+}
+
+/// Record a description in a sub-task's local spec.
+fn update_subtask_description(
+    parent_id: &str,
+    identifier: &str,
+    description: &str,
+) -> anyhow::Result<()> {
+    let Some(mut spec) = read_subtasks(parent_id)
+        .into_iter()
+        .find(|t| t.identifier == identifier)
+    else {
+        return Ok(());
+    };
+    spec.description = description.to_string();
+    write_subtask_spec(parent_id, &spec)
+        .with_context(|| format!("Failed to save the description of {}", identifier))
+}
+
+/// Line separating the text to push from the remote text shown for reference
+const MERGE_MARKER: &str = "======= remote description (everything below is ignored) =======";
+
+/// Editor buffer for merging two descriptions: the queued text first, the
+/// remote text below a marker line to copy from.
+fn merge_draft(local: &str, remote: &str) -> String {
+    format!(
+        "{}\n\n{}\n{}\n",
+        local.trim_end(),
+        MERGE_MARKER,
+        remote.trim_end()
+    )
+}
+
+/// The text to push from an edited merge buffer.
+fn merged_text(edited: &str) -> String {
+    edited
+        .split(MERGE_MARKER)
+        .next()
+        .unwrap_or_default()
+        .trim_end()
+        .to_string()
+}
+
+fn display_draft_batches(batches: &[(String, Vec<DraftSubtask>)]) {
+    for (parent_id, drafts) in batches {
+        println!(
+            "{}",
+            format!("{}: {} sub-task(s) to create", parent_id, drafts.len()).bold()
+        );
+        for (i, draft) in drafts.iter().enumerate() {
+            println!("  {}. {}", i + 1, draft.title.cyan());
+            if !draft.blocked_by.is_empty() {
+                println!("     Blocked by: {}", draft.blocked_by.join(", "));
+            }
+            for line in draft.description.lines() {
+                println!("     {}", line.dimmed());
+            }
+        }
+        println!();
+    }
+}
+
 fn push_loop_summary(parent_id: &str, backend: &Backend) -> anyhow::Result<()> {
     let iterations = read_iteration_log(parent_id);
 
@@ -301,6 +996,72 @@ fn push_loop_summary(parent_id: &str, backend: &Backend) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn push_scoring_fields(
+    parent_id: &str,
+    backend: Backend,
+    config: &LoopConfig,
+    dry_run: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    if !supports_field_mapping(backend) {
+        anyhow::bail!("Field mapping is not supported for the {} backend", backend);
+    }
+    let Some(mapping) = config.field_mapping_for(backend) else {
+        anyhow::bail!(
+            "No field_mapping configured for {}. Add a field_mapping.{} section to mobius.config.yaml",
+            backend,
+            backend
+        );
+    };
+
+    let updates = pending_field_updates(parent_id, mapping);
+    if dry_run && output.is_json() {
+        let planned: Vec<PlannedFieldUpdate> = updates
+            .iter()
+            .map(|(identifier, fields)| PlannedFieldUpdate { identifier, fields })
+            .collect();
+        return output.emit(&planned, |_| {});
+    }
+    if updates.is_empty() {
+        println!("{}", "No local scoring to push".green());
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("{}", "\nDry run - scoring fields to push:\n".bold());
+        for (identifier, fields) in &updates {
+            let mut names: Vec<_> = fields.iter().collect();
+            names.sort_by(|a, b| a.0.cmp(b.0));
+            let rendered: Vec<String> = names.iter().map(|(k, v)| format!("{k}={v}")).collect();
+            println!("  {} {}", identifier.cyan(), rendered.join(" ").dimmed());
+        }
+        println!("{}", "Run without --dry-run to apply changes".dimmed());
+        return Ok(());
+    }
+
+    println!(
+        "Pushing scoring for {} sub-task(s) to {}...",
+        updates.len(),
+        backend
+    );
+    let rt = tokio::runtime::Runtime::new()?;
+    let mut failures = 0;
+    for (identifier, fields) in &updates {
+        match rt.block_on(update_backend_fields(backend, identifier, fields)) {
+            Ok(()) => println!("  {} {}", "✓".green(), identifier),
+            Err(e) => {
+                failures += 1;
+                eprintln!("  {} {}: {}", "✗".red(), identifier, e);
+            }
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{} of {} scoring update(s) failed", failures, updates.len());
+    }
+    Ok(())
+}
+
 fn get_issues_to_push(parent_id: Option<&str>, all: bool) -> Vec<String> {
     let issues_path = get_project_mobius_path().join("issues");
 
@@ -364,11 +1125,17 @@ fn format_update_type(update_type: &str) -> String {
         "update_description" => "[DESCRIPTION]".to_string(),
         "add_label" => "[+LABEL]".to_string(),
         "remove_label" => "[-LABEL]".to_string(),
+        "add_relation" => "[+BLOCKER]".to_string(),
+        "remove_relation" => "[-BLOCKER]".to_string(),
         other => format!("[{}]", other.to_uppercase()),
     }
 }
 
-async fn push_update(update: &serde_json::Value, backend: &Backend) -> anyhow::Result<()> {
+async fn push_update(
+    update: &serde_json::Value,
+    backend: &Backend,
+    status_mapping: Option<&StatusMapping>,
+) -> anyhow::Result<()> {
     let update_type = update
         .get("type")
         .and_then(|v| v.as_str())
@@ -376,11 +1143,7 @@ async fn push_update(update: &serde_json::Value, backend: &Backend) -> anyhow::R
     let identifier = get_issue_identifier(update);
 
     // Skip API calls for local-only task IDs
-    let backend_pattern = match backend {
-        Backend::Linear | Backend::Jira => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Local => return Ok(()),
-    };
-    if !backend_pattern.is_match(&identifier) {
+    if !is_backend_identifier(&identifier, backend) {
         return Ok(());
     }
 
@@ -390,25 +1153,50 @@ async fn push_update(update: &serde_json::Value, backend: &Backend) -> anyhow::R
                 .get("issueId")
                 .and_then(|v| v.as_str())
                 .unwrap_or(&identifier);
-            let new_status = update
+            let requested_status = update
                 .get("newStatus")
                 .and_then(|v| v.as_str())
                 .unwrap_or("Done");
+            let new_status = status_mapping
+                .and_then(|m| m.backend_status(requested_status))
+                .unwrap_or(requested_status);
+            // IDs survive localized workflow names; only Jira and Linear have them
+            let status_id = status_mapping.and_then(|m| m.backend_status_id(requested_status));
 
             match backend {
                 Backend::Jira => {
                     let client = JiraClient::new()?;
-                    client
-                        .update_jira_issue_status(issue_id, new_status)
-                        .await
-                        .map_err(|e| anyhow::anyhow!("Failed to update Jira status: {}", e))?;
+                    let result = match status_id {
+                        Some(id) => client.update_jira_issue_status_by_id(issue_id, id).await,
+                        None => client.update_jira_issue_status(issue_id, new_status).await,
+                    };
+                    result.map_err(|e| anyhow::anyhow!("Failed to update Jira status: {}", e))?;
                 }
                 Backend::Linear => {
                     let client = crate::linear::LinearClient::new()?;
+                    let result = match status_id {
+                        Some(id) => client.update_linear_issue_state_id(issue_id, id).await,
+                        None => {
+                            client
+                                .update_linear_issue_status(issue_id, new_status)
+                                .await
+                        }
+                    };
+                    result.map_err(|e| anyhow::anyhow!("Failed to update Linear status: {}", e))?;
+                }
+                Backend::Github => {
+                    let client = GithubClient::new()?;
+                    client
+                        .update_github_issue_status(issue_id, new_status)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Failed to update GitHub status: {}", e))?;
+                }
+                Backend::Gitlab => {
+                    let client = GitlabClient::new()?;
                     client
-                        .update_linear_issue_status(issue_id, new_status)
+                        .update_gitlab_issue_status(issue_id, new_status)
                         .await
-                        .map_err(|e| anyhow::anyhow!("Failed to update Linear status: {}", e))?;
+                        .map_err(|e| anyhow::anyhow!("Failed to update GitLab status: {}", e))?;
                 }
                 Backend::Local => {}
             }
@@ -432,9 +1220,113 @@ async fn push_update(update: &serde_json::Value, backend: &Backend) -> anyhow::R
                         .await
                         .map_err(|e| anyhow::anyhow!("Failed to add Linear comment: {}", e))?;
                 }
+                Backend::Github => {
+                    let client = GithubClient::new()?;
+                    client
+                        .add_github_comment(issue_id, body)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Failed to add GitHub comment: {}", e))?;
+                }
+                Backend::Gitlab => {
+                    let client = GitlabClient::new()?;
+                    client
+                        .add_gitlab_comment(issue_id, body)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Failed to add GitLab comment: {}", e))?;
+                }
                 Backend::Local => {}
             }
         }
+        "update_description" => {
+            let issue_id = update
+                .get("issueId")
+                .and_then(|v| v.as_str())
+                .unwrap_or(&identifier);
+            let description = update
+                .get("description")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+
+            match backend {
+                Backend::Jira => {
+                    JiraClient::new()?
+                        .update_jira_issue_description(issue_id, description)
+                        .await
+                        .map_err(|e| anyhow::anyhow!("Failed to update Jira description: {}", e))?;
+                }
+                Backend::Linear => {
+                    crate::linear::LinearClient::new()?
+                        .update_linear_issue_description(issue_id, description)
+                        .await
+                        .map_err(|e| {
+                            anyhow::anyhow!("Failed to update Linear description: {}", e)
+                        })?;
+                }
+                Backend::Github => {
+                    GithubClient::new()?
+                        .update_github_issue_body(issue_id, description)
+                        .await
+                        .map_err(|e| {
+                            anyhow::anyhow!("Failed to update GitHub description: {}", e)
+                        })?;
+                }
+                Backend::Gitlab => {
+                    GitlabClient::new()?
+                        .update_gitlab_issue_description(issue_id, description)
+                        .await
+                        .map_err(|e| {
+                            anyhow::anyhow!("Failed to update GitLab description: {}", e)
+                        })?;
+                }
+                Backend::Local => {}
+            }
+        }
+        "add_relation" | "remove_relation" => {
+            let field = |key: &str| update.get(key).and_then(|v| v.as_str()).unwrap_or("");
+            let issue_id = Some(field("issueId"))
+                .filter(|id| !id.is_empty())
+                .unwrap_or(&identifier);
+            let blocker_identifier = field("blockerIdentifier");
+            let blocker_id = Some(field("blockerId"))
+                .filter(|id| !id.is_empty())
+                .unwrap_or(blocker_identifier);
+            if !is_backend_identifier(blocker_identifier, backend) {
+                return Ok(());
+            }
+            let adding = update_type == "add_relation";
+
+            match backend {
+                Backend::Jira => {
+                    let client = JiraClient::new()?;
+                    let result = if adding {
+                        client
+                            .create_jira_issue_link(blocker_identifier, &identifier)
+                            .await
+                    } else {
+                        client
+                            .delete_jira_issue_link(blocker_identifier, &identifier)
+                            .await
+                    };
+                    result.map_err(|e| anyhow::anyhow!("Failed to update Jira links: {}", e))?;
+                }
+                Backend::Linear => {
+                    let client = crate::linear::LinearClient::new()?;
+                    let result = if adding {
+                        client
+                            .add_linear_blocking_relation(blocker_id, issue_id)
+                            .await
+                    } else {
+                        client
+                            .remove_linear_blocking_relation(blocker_id, issue_id)
+                            .await
+                    };
+                    result
+                        .map_err(|e| anyhow::anyhow!("Failed to update Linear relations: {}", e))?;
+                }
+                // Neither tracker models blocking relations
+                Backend::Github | Backend::Gitlab | Backend::Local => {}
+            }
+        }
         _ => {
             // Other types not yet implemented
         }
@@ -443,29 +1335,221 @@ async fn push_update(update: &serde_json::Value, backend: &Backend) -> anyhow::R
     Ok(())
 }
 
-fn mark_update_synced(parent_id: &str, update_id: &str) {
-    let mut queue = read_pending_updates(parent_id);
-    let now = chrono::Utc::now().to_rfc3339();
+/// Whether an identifier names an issue on the backend rather than a local-only task
+fn is_backend_identifier(identifier: &str, backend: &Backend) -> bool {
+    let backend_pattern = match backend {
+        Backend::Linear | Backend::Jira => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
+        Backend::Github | Backend::Gitlab => regex::Regex::new(r"^\d+$").unwrap(),
+        Backend::Local => return false,
+    };
+    backend_pattern.is_match(identifier)
+}
 
-    for update in &mut queue.updates {
-        if update.id == update_id {
-            update.synced_at = Some(now.clone());
+/// Re-fetch the issue an update targets and compare it with the local snapshot.
+///
+/// Status changes compare against `oldStatus` and description updates against
+/// `oldDescription`; a comment is already applied when the same text was
+/// posted after it was queued. Other updates are additive and always clear.
+async fn check_remote(
+    update: &PendingUpdate,
+    backend: &Backend,
+    status_mapping: Option<&StatusMapping>,
+) -> anyhow::Result<RemoteCheck> {
+    match &update.data {
+        PendingUpdateData::StatusChange {
+            issue_id,
+            identifier,
+            old_status,
+            new_status,
+        } if is_backend_identifier(identifier, backend) => {
+            let remote = fetch_remote_status(issue_id, backend).await?;
+            Ok(classify_status(
+                old_status,
+                new_status,
+                &remote,
+                status_mapping,
+            ))
+        }
+        PendingUpdateData::UpdateDescription {
+            issue_id,
+            identifier,
+            description,
+            old_description: Some(old_description),
+        } if is_backend_identifier(identifier, backend) => {
+            let remote = fetch_remote_description(issue_id, backend).await?;
+            Ok(classify_text(old_description, description, &remote))
         }
+        PendingUpdateData::AddComment {
+            issue_id,
+            identifier,
+            body,
+        } if is_backend_identifier(identifier, backend) => {
+            let posted = fetch_remote_comments_since(issue_id, &update.created_at, backend).await?;
+            if posted.iter().any(|remote| same_text(remote, body)) {
+                Ok(RemoteCheck::AlreadyApplied)
+            } else {
+                Ok(RemoteCheck::Clear)
+            }
+        }
+        _ => Ok(RemoteCheck::Clear),
     }
+}
 
-    let _ = write_pending_updates(parent_id, &queue);
+async fn fetch_remote_status(issue_id: &str, backend: &Backend) -> anyhow::Result<String> {
+    Ok(match backend {
+        Backend::Jira => JiraClient::new()?
+            .fetch_jira_issue_status(issue_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch Jira status: {}", e))?,
+        Backend::Linear => crate::linear::LinearClient::new()?
+            .fetch_linear_issue_status(issue_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch Linear status: {}", e))?,
+        Backend::Github => GithubClient::new()?
+            .fetch_github_issue_status(issue_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch GitHub status: {}", e))?,
+        Backend::Gitlab => GitlabClient::new()?
+            .fetch_gitlab_issue_status(issue_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch GitLab status: {}", e))?,
+        Backend::Local => anyhow::bail!("The local backend has no remote status"),
+    })
 }
 
-fn mark_update_failed(parent_id: &str, update_id: &str, error: &str) {
-    let mut queue = read_pending_updates(parent_id);
+async fn fetch_remote_description(issue_id: &str, backend: &Backend) -> anyhow::Result<String> {
+    Ok(match backend {
+        Backend::Jira => JiraClient::new()?
+            .fetch_jira_issue_description(issue_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch Jira description: {}", e))?,
+        Backend::Linear => crate::linear::LinearClient::new()?
+            .fetch_linear_issue_description(issue_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch Linear description: {}", e))?,
+        Backend::Github => GithubClient::new()?
+            .fetch_github_issue_description(issue_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch GitHub description: {}", e))?,
+        Backend::Gitlab => GitlabClient::new()?
+            .fetch_gitlab_issue_description(issue_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch GitLab description: {}", e))?,
+        Backend::Local => anyhow::bail!("The local backend has no remote description"),
+    })
+}
 
-    for update in &mut queue.updates {
-        if update.id == update_id {
-            update.error = Some(error.to_string());
-        }
+async fn fetch_remote_comments_since(
+    issue_id: &str,
+    since: &str,
+    backend: &Backend,
+) -> anyhow::Result<Vec<String>> {
+    Ok(match backend {
+        Backend::Jira => JiraClient::new()?
+            .fetch_jira_comments_since(issue_id, since)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch Jira comments: {}", e))?,
+        Backend::Linear => crate::linear::LinearClient::new()?
+            .fetch_linear_comments_since(issue_id, since)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch Linear comments: {}", e))?,
+        Backend::Github => GithubClient::new()?
+            .fetch_github_comments_since(issue_id, since)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch GitHub comments: {}", e))?,
+        Backend::Gitlab => GitlabClient::new()?
+            .fetch_gitlab_comments_since(issue_id, since)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to fetch GitLab comments: {}", e))?,
+        Backend::Local => Vec::new(),
+    })
+}
+
+/// Compare free text ignoring whitespace, which trackers re-flow on save
+/// (Jira's rich text in particular).
+fn same_text(a: &str, b: &str) -> bool {
+    a.split_whitespace().eq(b.split_whitespace())
+}
+
+/// Classify a remote description against the snapshot and the queued text.
+fn classify_text(old: &str, new: &str, remote: &str) -> RemoteCheck {
+    if same_text(remote, new) {
+        RemoteCheck::AlreadyApplied
+    } else if same_text(remote, old) {
+        RemoteCheck::Clear
+    } else {
+        RemoteCheck::Conflict(remote.to_string())
+    }
+}
+
+/// First line of a remote value, shortened for one-line messages.
+fn conflict_preview(remote: &str) -> String {
+    const MAX_CHARS: usize = 60;
+    let line = remote.lines().next().unwrap_or_default().trim();
+    if line.chars().count() > MAX_CHARS || remote.trim().lines().count() > 1 {
+        let short: String = line.chars().take(MAX_CHARS).collect();
+        format!("{}…", short.trim_end())
+    } else {
+        line.to_string()
     }
+}
+
+/// Compare statuses by what they mean (`in_progress` and "In Progress" agree),
+/// so a renamed workflow state is not mistaken for a remote edit.
+fn classify_status(
+    old_status: &str,
+    new_status: &str,
+    remote: &str,
+    status_mapping: Option<&StatusMapping>,
+) -> RemoteCheck {
+    let meaning = |status: &str| map_backend_status(&status.replace('_', " "), status_mapping);
+    let remote_meaning = meaning(remote);
+    if remote_meaning == meaning(new_status) {
+        RemoteCheck::AlreadyApplied
+    } else if remote_meaning == meaning(old_status) {
+        RemoteCheck::Clear
+    } else {
+        RemoteCheck::Conflict(remote.to_string())
+    }
+}
+
+fn mark_update_synced(parent_id: &str, update_id: &str) {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let _ = with_pending_updates_sync(parent_id, |mut queue| {
+        for update in &mut queue.updates {
+            if update.id == update_id {
+                update.synced_at = Some(now.clone());
+            }
+        }
+        queue
+    });
+}
+
+fn mark_update_failed(parent_id: &str, update_id: &str, error: &str) {
+    let _ = with_pending_updates_sync(parent_id, |mut queue| {
+        for update in &mut queue.updates {
+            if update.id == update_id {
+                update.error = Some(error.to_string());
+            }
+        }
+        queue
+    });
+}
 
-    let _ = write_pending_updates(parent_id, &queue);
+fn mark_update_conflicted(parent_id: &str, update_id: &str, remote_value: &str) {
+    let conflict = UpdateConflict {
+        remote_value: remote_value.to_string(),
+        detected_at: chrono::Utc::now().to_rfc3339(),
+    };
+    let _ = with_pending_updates_sync(parent_id, |mut queue| {
+        for update in &mut queue.updates {
+            if update.id == update_id {
+                update.conflict = Some(conflict.clone());
+            }
+        }
+        queue
+    });
 }
 
 fn log_push_result(parent_id: &str, result: &PushResult) {
@@ -530,6 +1614,8 @@ fn get_update_type_str(update: &PendingUpdate) -> String {
         PendingUpdateData::UpdateDescription { .. } => "update_description".to_string(),
         PendingUpdateData::AddLabel { .. } => "add_label".to_string(),
         PendingUpdateData::RemoveLabel { .. } => "remove_label".to_string(),
+        PendingUpdateData::AddRelation { .. } => "add_relation".to_string(),
+        PendingUpdateData::RemoveRelation { .. } => "remove_relation".to_string(),
     }
 }
 
@@ -542,6 +1628,8 @@ fn get_pending_update_identifier(update: &PendingUpdate) -> String {
         PendingUpdateData::UpdateDescription { identifier, .. } => identifier.clone(),
         PendingUpdateData::AddLabel { identifier, .. } => identifier.clone(),
         PendingUpdateData::RemoveLabel { identifier, .. } => identifier.clone(),
+        PendingUpdateData::AddRelation { identifier, .. } => identifier.clone(),
+        PendingUpdateData::RemoveRelation { identifier, .. } => identifier.clone(),
     }
 }
 
@@ -553,13 +1641,19 @@ fn parse_update_type(type_str: &str) -> PendingUpdateType {
         "update_description" => PendingUpdateType::UpdateDescription,
         "add_label" => PendingUpdateType::AddLabel,
         "remove_label" => PendingUpdateType::RemoveLabel,
+        "add_relation" => PendingUpdateType::AddRelation,
+        "remove_relation" => PendingUpdateType::RemoveRelation,
         _ => PendingUpdateType::StatusChange,
     }
 }
 
 fn display_push_summary(results: &[PushResult]) {
     let successful: Vec<_> = results.iter().filter(|r| r.success).collect();
-    let failed: Vec<_> = results.iter().filter(|r| !r.success).collect();
+    let failed: Vec<_> = results
+        .iter()
+        .filter(|r| !r.success && r.conflict.is_none())
+        .collect();
+    let conflicted: Vec<_> = results.iter().filter(|r| r.conflict.is_some()).collect();
 
     if !successful.is_empty() {
         println!("{}", "Pushed:".green());
@@ -593,4 +1687,134 @@ fn display_push_summary(results: &[PushResult]) {
             }
         }
     }
+
+    if !conflicted.is_empty() {
+        println!("{}", "\nConflicted:".yellow());
+        for result in &conflicted {
+            println!(
+                "  {}",
+                format!(
+                    "! {} {} (remote: {})",
+                    format_update_type(&result.update_type),
+                    result.issue_identifier,
+                    conflict_preview(result.conflict.as_deref().unwrap_or_default())
+                )
+                .dimmed()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renamed_specs_follow_created_issues() {
+        let spec = |id: &str, title: &str, blocked_by: &[&str]| -> SubTaskContext {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "identifier": id,
+                "title": title,
+                "status": "pending",
+                "blockedBy": blocked_by,
+            }))
+            .unwrap()
+        };
+        let created = |identifier: &str, title: &str| CreatedSubtask {
+            update_id: format!("u-{}", identifier),
+            title: title.to_string(),
+            identifier: identifier.to_string(),
+            id: format!("uuid-{}", identifier),
+        };
+        let specs = vec![
+            spec("task-001", "Add types", &[]),
+            spec("task-002", "Use types ", &["task-001"]),
+            spec("MOB-9", "Already synced", &[]),
+        ];
+        let (renamed, stale) = renamed_specs(
+            specs,
+            &[
+                created("MOB-10", "Add types"),
+                created("MOB-11", "Use types"),
+            ],
+        );
+
+        assert_eq!(renamed.len(), 2);
+        assert_eq!(renamed[0].identifier, "MOB-10");
+        assert_eq!(renamed[0].id, "uuid-MOB-10");
+        assert_eq!(renamed[1].identifier, "MOB-11");
+        assert_eq!(renamed[1].blocked_by[0].identifier, "MOB-10");
+        let mut stale = stale;
+        stale.sort();
+        assert_eq!(stale, vec!["task-001", "task-002"]);
+
+        // Nothing was created for these titles
+        let (renamed, stale) = renamed_specs(
+            vec![spec("task-001", "Other", &[])],
+            &[created("MOB-10", "Add types")],
+        );
+        assert!(renamed.is_empty() && stale.is_empty());
+    }
+
+    #[test]
+    fn test_classify_status_against_remote() {
+        // Remote untouched since the update was queued
+        assert_eq!(
+            classify_status("ready", "Done", "Todo", None),
+            RemoteCheck::Clear
+        );
+        // Someone already finished it on the backend
+        assert_eq!(
+            classify_status("in_progress", "Done", "Completed", None),
+            RemoteCheck::AlreadyApplied
+        );
+        // Remote moved somewhere the snapshot didn't expect
+        assert_eq!(
+            classify_status("ready", "Done", "In Progress", None),
+            RemoteCheck::Conflict("In Progress".to_string())
+        );
+    }
+
+    #[test]
+    fn test_classify_text_against_remote() {
+        // Jira re-flows whitespace; that is not an edit
+        assert_eq!(
+            classify_text(
+                "Add the\nparser",
+                "Add the parser and tests",
+                "Add the parser"
+            ),
+            RemoteCheck::Clear
+        );
+        assert_eq!(
+            classify_text(
+                "Old",
+                "Add the parser  and tests",
+                "Add the parser and tests\n"
+            ),
+            RemoteCheck::AlreadyApplied
+        );
+        assert_eq!(
+            classify_text("Old", "New", "Edited on the tracker"),
+            RemoteCheck::Conflict("Edited on the tracker".to_string())
+        );
+    }
+
+    #[test]
+    fn test_conflict_preview_keeps_the_first_line() {
+        assert_eq!(conflict_preview("In Progress"), "In Progress");
+        assert_eq!(conflict_preview("First line\nsecond"), "First line…");
+        assert_eq!(
+            conflict_preview(&"x".repeat(80)),
+            format!("{}…", "x".repeat(60))
+        );
+    }
+
+    #[test]
+    fn test_merged_text_drops_the_remote_block() {
+        let draft = merge_draft("Local text\n", "Remote text");
+        assert_eq!(merged_text(&draft), "Local text");
+        assert_eq!(merged_text("Edited only"), "Edited only");
+    }
 }
@@ -0,0 +1,435 @@
+//! Refine command - Ask the agent runtime to break a parent issue into sub-tasks
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::Context;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config_with_env;
+use crate::config::paths::resolve_paths;
+use crate::context::{
+    queue_pending_update, read_pending_updates, resolve_task_id, with_pending_updates_sync,
+    PendingUpdateInput,
+};
+use crate::local_state::{
+    read_parent_spec, read_subtasks, remove_subtask_spec, write_subtask_spec,
+};
+use crate::runtime_adapter::{
+    build_submit_command, effective_model_for_runtime, translate_codex_output,
+};
+use crate::subtask_batch::{order_drafts, DraftSubtask};
+use crate::types::config::LoopConfig;
+use crate::types::context::{IssueRef, ParentIssueContext, SubTaskContext};
+use crate::types::enums::{AgentRuntime, Backend, Model};
+use crate::types::task_graph::TaskScoring;
+
+/// A sub-task as proposed by the agent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposedSubtask {
+    /// Short key other proposals use in `blockedBy` (e.g. `task-001`)
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub blocked_by: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify_command: Option<String>,
+    /// 1-10
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complexity: Option<u8>,
+    /// 1-10
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recommended_model: Option<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub rationale: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Proposal {
+    sub_tasks: Vec<ProposedSubtask>,
+}
+
+/// `mobius refine --output json` payload
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RefineReport {
+    parent_id: String,
+    sub_tasks: Vec<ProposedSubtask>,
+    /// Whether local sub-task specs were written (local backend only)
+    written: bool,
+    /// `create_subtask` updates queued for `mobius push --review`
+    queued: usize,
+}
+
+pub struct RefineOptions<'a> {
+    pub backend_override: Option<&'a str>,
+    /// Show the proposal without writing anything
+    pub dry_run: bool,
+    /// Replace existing sub-tasks
+    pub force: bool,
+}
+
+pub fn run(
+    task_id: Option<&str>,
+    opts: &RefineOptions<'_>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let Some(parent_id) = resolve_task_id(task_id) else {
+        anyhow::bail!(
+            "No task ID provided and no current task set. Usage: mobius refine <task-id>"
+        );
+    };
+    let paths = resolve_paths();
+    let config = read_config_with_env(&paths.config_path).unwrap_or_default();
+    let backend: Backend = match opts.backend_override {
+        Some(b) => b.parse().unwrap_or(config.backend),
+        None => config.backend,
+    };
+    let Some(parent) = read_parent_spec(&parent_id) else {
+        anyhow::bail!(
+            "No local spec for {}; run 'mobius pull {}' (or create it with 'mobius new')",
+            parent_id,
+            parent_id
+        );
+    };
+    let existing = read_subtasks(&parent_id);
+    let drafts = read_pending_updates(&parent_id)
+        .updates
+        .iter()
+        .filter_map(DraftSubtask::from_update)
+        .count();
+    if existing.len() + drafts > 0 && !opts.force && !opts.dry_run {
+        anyhow::bail!(
+            "{} already has {} sub-task(s); pass --force to refine it again",
+            parent_id,
+            existing.len() + drafts
+        );
+    }
+
+    output.note(
+        format!(
+            "Asking {} to break {} into sub-tasks...",
+            config.runtime, parent_id
+        )
+        .cyan(),
+    );
+    let response = ask_runtime(&config, &build_prompt(&parent))?;
+    let sub_tasks = parse_proposal(&response)?;
+
+    let mut report = RefineReport {
+        parent_id: parent_id.clone(),
+        sub_tasks,
+        written: false,
+        queued: 0,
+    };
+    if !opts.dry_run {
+        if backend == Backend::Local {
+            // --force replaces the previous breakdown rather than merging into it
+            for task in &existing {
+                let identifier = if task.identifier.is_empty() {
+                    &task.id
+                } else {
+                    &task.identifier
+                };
+                remove_subtask_spec(&parent_id, identifier)?;
+            }
+            for task in &report.sub_tasks {
+                write_subtask_spec(&parent_id, &to_subtask_context(task, &report.sub_tasks))?;
+            }
+            report.written = true;
+        } else {
+            // Remote sub-tasks get their specs from the tracker once `push
+            // --review` creates them; local `task-NNN` specs would be pulled
+            // again as duplicates.
+            drop_queued_creations(&parent_id)?;
+            report.queued = queue_creations(&parent_id, &report.sub_tasks)?;
+        }
+    }
+
+    output.emit(&report, |report| {
+        println!();
+        for task in &report.sub_tasks {
+            let blockers = if task.blocked_by.is_empty() {
+                String::new()
+            } else {
+                format!(" (blocked by {})", task.blocked_by.join(", "))
+            };
+            let score = task
+                .complexity
+                .map(|c| format!(" [complexity {}]", c))
+                .unwrap_or_default();
+            println!(
+                "  {} {}{}{}",
+                task.id.cyan(),
+                task.title,
+                blockers.dimmed(),
+                score.dimmed()
+            );
+        }
+        println!();
+        if report.written {
+            println!(
+                "{}",
+                format!(
+                    "✓ Wrote {} sub-task(s) for {}",
+                    report.sub_tasks.len(),
+                    report.parent_id
+                )
+                .green()
+            );
+        } else if report.queued > 0 {
+            println!(
+                "{}",
+                format!(
+                    "✓ Queued {} sub-task(s) for {}: run 'mobius push {} --review' to create them",
+                    report.queued, backend, report.parent_id
+                )
+                .green()
+            );
+        } else {
+            println!("{}", "Dry run - nothing written".dimmed());
+        }
+    })
+}
+
+fn build_prompt(parent: &ParentIssueContext) -> String {
+    format!(
+        r#"Break the issue below into sub-tasks that agents can implement independently, each focused on one or two files.
+Explore the repository as needed, but do not change any files.
+
+Issue {identifier}: {title}
+
+{description}
+
+Reply with only a JSON object in a ```json fenced block, shaped like:
+{{"subTasks": [{{
+  "id": "task-001",
+  "title": "Short imperative title",
+  "description": "Markdown: summary, target files, action, acceptance criteria",
+  "blockedBy": ["ids of sub-tasks that must finish first"],
+  "verifyCommand": "shell command that checks this sub-task",
+  "complexity": 1-10,
+  "risk": 1-10,
+  "recommendedModel": "haiku | sonnet | opus",
+  "rationale": "why this complexity and model"
+}}]}}
+Order sub-tasks so blockers come first and number ids task-001, task-002, ..."#,
+        identifier = parent.identifier,
+        title = parent.title,
+        description = if parent.description.is_empty() {
+            "(no description)"
+        } else {
+            &parent.description
+        },
+    )
+}
+
+/// Run the configured runtime headless with `prompt` on stdin and return its output.
+fn ask_runtime(config: &LoopConfig, prompt: &str) -> anyhow::Result<String> {
+    let model = effective_model_for_runtime(config.runtime, &config.execution, None);
+    let cmd = build_submit_command(config.runtime, &model, false, None);
+    let mut child = Command::new("sh")
+        .args(["-c", &cmd])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("Failed to start {}", config.runtime))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(prompt.as_bytes())?;
+    }
+    let out = child.wait_with_output()?;
+    if !out.status.success() {
+        anyhow::bail!("{} exited with {}", config.runtime, out.status);
+    }
+    let text = String::from_utf8_lossy(&out.stdout).into_owned();
+    Ok(if config.runtime == AgentRuntime::Codex {
+        translate_codex_output(&text)
+    } else {
+        text
+    })
+}
+
+/// Pull the sub-task list out of the agent's reply and check it hangs together.
+///
+/// The last ```json block wins; a reply that is bare JSON is accepted too.
+fn parse_proposal(response: &str) -> anyhow::Result<Vec<ProposedSubtask>> {
+    let json = response
+        .rsplit("```json")
+        .next()
+        .filter(|_| response.contains("```json"))
+        .and_then(|rest| rest.split("```").next())
+        .unwrap_or(response)
+        .trim();
+    let proposal: Proposal = serde_json::from_str(json)
+        .context("The agent did not reply with a sub-task list in the expected JSON shape")?;
+    let mut sub_tasks = proposal.sub_tasks;
+    if sub_tasks.is_empty() {
+        anyhow::bail!("The agent proposed no sub-tasks");
+    }
+
+    let mut seen = HashSet::new();
+    for task in &mut sub_tasks {
+        task.id = task.id.trim().to_string();
+        if task.id.is_empty() || task.title.trim().is_empty() {
+            anyhow::bail!("Every proposed sub-task needs an id and a title");
+        }
+        if !seen.insert(task.id.clone()) {
+            anyhow::bail!("Sub-task id {} is proposed twice", task.id);
+        }
+        task.complexity = task.complexity.map(|c| c.clamp(1, 10));
+        task.risk = task.risk.map(|r| r.clamp(1, 10));
+    }
+    for task in &sub_tasks {
+        if let Some(unknown) = task.blocked_by.iter().find(|b| !seen.contains(*b)) {
+            anyhow::bail!("{} is blocked by unknown sub-task {}", task.id, unknown);
+        }
+    }
+    // Reject cycles the same way queued drafts are checked before creation
+    order_drafts(
+        sub_tasks
+            .iter()
+            .map(|t| DraftSubtask {
+                update_id: String::new(),
+                parent_id: String::new(),
+                title: t.id.clone(),
+                description: String::new(),
+                blocked_by: t.blocked_by.clone(),
+            })
+            .collect(),
+    )?;
+    Ok(sub_tasks)
+}
+
+fn to_subtask_context(task: &ProposedSubtask, all: &[ProposedSubtask]) -> SubTaskContext {
+    let issue_ref = |id: &str| IssueRef {
+        id: id.to_string(),
+        identifier: id.to_string(),
+    };
+    let mut description = task.description.trim().to_string();
+    if let Some(command) = task.verify_command.as_deref().map(str::trim) {
+        if !command.is_empty() && !description.contains("### Verify Command") {
+            description.push_str(&format!(
+                "\n\n### Verify Command\n```bash\n{}\n```",
+                command
+            ));
+        }
+    }
+    let scoring = task.complexity.map(|complexity| TaskScoring {
+        complexity,
+        risk: task.risk.unwrap_or(complexity),
+        recommended_model: task
+            .recommended_model
+            .as_deref()
+            .and_then(|m| m.trim().parse::<Model>().ok())
+            .unwrap_or_default(),
+        rationale: task.rationale.clone(),
+        recommended_runtime: None,
+    });
+    SubTaskContext {
+        id: task.id.clone(),
+        identifier: task.id.clone(),
+        title: task.title.trim().to_string(),
+        description,
+        status: "pending".to_string(),
+        git_branch_name: String::new(),
+        blocked_by: task.blocked_by.iter().map(|b| issue_ref(b)).collect(),
+        blocks: all
+            .iter()
+            .filter(|other| other.blocked_by.contains(&task.id))
+            .map(|other| issue_ref(&other.id))
+            .collect(),
+        scoring,
+        priority: None,
+        labels: Vec::new(),
+        task_type: Default::default(),
+        comments: Vec::new(),
+    }
+}
+
+/// Drop `create_subtask` drafts still queued for `parent_id`, so a forced
+/// refine replaces the previous proposal instead of adding to it.
+fn drop_queued_creations(parent_id: &str) -> anyhow::Result<()> {
+    with_pending_updates_sync(parent_id, |mut queue| {
+        queue
+            .updates
+            .retain(|update| DraftSubtask::from_update(update).is_none());
+        queue
+    })?;
+    Ok(())
+}
+
+/// Queue a `create_subtask` update per proposal; blockers are named by title,
+/// as `mobius push --review` expects.
+fn queue_creations(parent_id: &str, sub_tasks: &[ProposedSubtask]) -> anyhow::Result<usize> {
+    let titles: HashMap<&str, &str> = sub_tasks
+        .iter()
+        .map(|t| (t.id.as_str(), t.title.trim()))
+        .collect();
+    for task in sub_tasks {
+        let blocked_by: Vec<String> = task
+            .blocked_by
+            .iter()
+            .filter_map(|id| titles.get(id.as_str()).map(|t| t.to_string()))
+            .collect();
+        queue_pending_update(
+            parent_id,
+            &PendingUpdateInput::CreateSubtask {
+                parent_id: parent_id.to_string(),
+                title: task.title.trim().to_string(),
+                description: to_subtask_context(task, sub_tasks).description,
+                blocked_by: Some(blocked_by).filter(|b| !b.is_empty()),
+            },
+        )?;
+    }
+    Ok(sub_tasks.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proposal_from_fenced_reply() {
+        let reply = r#"Here is the breakdown:
+
+```json
+{"subTasks": [
+  {"id": "task-001", "title": "Add types", "complexity": 3, "verifyCommand": "cargo check"},
+  {"id": "task-002", "title": "Use types", "blockedBy": ["task-001"], "complexity": 14}
+]}
+```
+"#;
+        let tasks = parse_proposal(reply).unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[1].complexity, Some(10));
+
+        let spec = to_subtask_context(&tasks[0], &tasks);
+        assert!(spec
+            .description
+            .contains("### Verify Command\n```bash\ncargo check\n```"));
+        assert_eq!(spec.blocks[0].id, "task-002");
+        assert_eq!(spec.scoring.unwrap().complexity, 3);
+    }
+
+    #[test]
+    fn test_parse_proposal_rejects_unknown_blockers_and_cycles() {
+        let unknown = r#"{"subTasks": [{"id": "a", "title": "A", "blockedBy": ["zzz"]}]}"#;
+        assert!(parse_proposal(unknown).is_err());
+
+        let cycle = r#"{"subTasks": [
+            {"id": "a", "title": "A", "blockedBy": ["b"]},
+            {"id": "b", "title": "B", "blockedBy": ["a"]}
+        ]}"#;
+        assert!(parse_proposal(cycle).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+//! Report command - Regenerate the HTML run report and optionally open it
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Context;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::churn::loop_branch;
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::local_state::read_subtasks;
+use crate::report::generate_html_report;
+
+/// `mobius report --output json` payload
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReportPath {
+    parent_id: String,
+    path: String,
+}
+
+pub fn run(task_id: &str, open: bool, output: OutputFormat) -> anyhow::Result<()> {
+    if read_subtasks(task_id).is_empty() {
+        anyhow::bail!(
+            "No local sub-tasks found for {}. Run 'mobius pull {}' first.",
+            task_id,
+            task_id
+        );
+    }
+    let config = read_config(&resolve_paths().config_path).unwrap_or_default();
+    let (head, repo_path) = loop_branch(task_id);
+    let base = config
+        .execution
+        .base_branch
+        .clone()
+        .unwrap_or_else(|| "main".to_string());
+
+    let path = generate_html_report(task_id, &repo_path, &base, &head)?;
+    let report = ReportPath {
+        parent_id: task_id.to_string(),
+        path: path.display().to_string(),
+    };
+    output.emit(&report, |report| {
+        println!("{} Wrote {}", "✓".green(), report.path);
+    })?;
+
+    if open {
+        open_in_browser(&path)?;
+    }
+    Ok(())
+}
+
+fn open_in_browser(path: &Path) -> anyhow::Result<()> {
+    let opener = if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    let status = Command::new(opener)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to run {}; open {} by hand", opener, path.display()))?;
+    if !status.success() {
+        anyhow::bail!("{} exited with {}", opener, status);
+    }
+    Ok(())
+}
@@ -40,7 +40,7 @@ pub fn run(
     };
 
     // Validate task ID format
-    if !validate_task_id(task_id, &backend) {
+    if !backend.is_valid_task_id(task_id) {
         eprintln!(
             "{}",
             format!("Error: Invalid task ID format for {}: {}", backend, task_id).red()
@@ -93,12 +93,3 @@ pub fn run(
         }
     }
 }
-
-fn validate_task_id(task_id: &str, backend: &Backend) -> bool {
-    let pattern = match backend {
-        Backend::Linear => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Jira => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Local => regex::Regex::new(r"^(LOC-\d+|task-\d+)$").unwrap(),
-    };
-    pattern.is_match(task_id)
-}
@@ -0,0 +1,50 @@
+//! Search command - Full-text search over local issues and sub-tasks
+
+use colored::Colorize;
+
+use crate::commands::output::OutputFormat;
+use crate::search_index::{refresh_index, DocKind, SearchHit};
+
+pub fn run(query: &str, limit: usize, output: OutputFormat) -> anyhow::Result<()> {
+    if query.trim().is_empty() {
+        anyhow::bail!("Empty search query. Usage: mobius search \"<words>\"");
+    }
+    let index = refresh_index()?;
+    let hits = index.search(query, limit);
+    output.emit(&hits, |hits| print_hits(hits, index.is_empty()))
+}
+
+fn print_hits(hits: &[SearchHit], index_empty: bool) {
+    if index_empty {
+        eprintln!("{}", "No local issues found.".yellow());
+        eprintln!(
+            "{}",
+            "Run `mobius refine <issue-id>` to create local issue state.".dimmed()
+        );
+        return;
+    }
+    if hits.is_empty() {
+        eprintln!("{}", "No matching issues.".yellow());
+        return;
+    }
+
+    let width = hits
+        .iter()
+        .map(|h| h.identifier.chars().count())
+        .max()
+        .unwrap_or(0);
+    for hit in hits {
+        let parent = match hit.kind {
+            DocKind::Parent => String::new(),
+            DocKind::SubTask => format!("  ({})", hit.parent_id).dimmed().to_string(),
+        };
+        let identifier = format!("{:width$}", hit.identifier, width = width);
+        println!(
+            "{}  {}  [{}]{}",
+            identifier.bold(),
+            hit.title,
+            hit.status.dimmed(),
+            parent
+        );
+    }
+}
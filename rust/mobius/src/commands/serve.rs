@@ -0,0 +1,705 @@
+//! Serve command - Expose task graphs, loop state and agent logs over HTTP
+//!
+//! Every route under `/api` answers with JSON, so a dashboard or a team
+//! channel bot can follow a run without a terminal; `/` serves a web
+//! dashboard built on them. Loop state and agent logs are also streamed as
+//! server-sent events. With `--token`, API requests must send
+//! `Authorization: Bearer <token>`, or a `mobius_token` cookie where headers
+//! can't be set (`EventSource`). With `--observe` the loop control routes
+//! are not registered, so the server only ever reads state.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fs;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{Html, IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use chrono::Utc;
+use colored::Colorize;
+use futures::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+
+use crate::commands::cancel::cancel_loop;
+use crate::commands::logs::{find_agent_log, list_agent_logs, LogTail};
+use crate::commands::pause::pause_loop;
+use crate::commands::status::{build_status_report, StatusReport};
+use crate::context::{
+    get_completed_task_id, get_runtime_path, is_process_running, read_pending_updates,
+    read_runtime_state, read_session,
+};
+use crate::external_deps::apply_resolved_blockers;
+use crate::local_state::{
+    get_project_mobius_path, read_iteration_log, read_local_subtasks_as_linear_issues,
+    read_parent_spec,
+};
+use crate::secrets::tokens_match;
+use crate::shutdown;
+use crate::stream_json::{agent_output_dir, parse_log_events, LogEvent, LogEventKind};
+use crate::types::context::ParentIssueContext;
+use crate::types::enums::TaskStatus;
+use crate::types::task_graph::{build_task_graph, critical_path, get_graph_stats};
+use crate::types::task_graph::{GraphStats, SubTask};
+
+/// Log file a loop started through the API writes its output to
+const SERVED_LOOP_LOG: &str = "loop-serve.log";
+
+/// The web dashboard, a single page using the API below
+const DASHBOARD_HTML: &str = include_str!("../../assets/dashboard.html");
+
+/// How often the state stream rebuilds the status; elapsed times tick with it
+const STATE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Cookie the dashboard keeps the API token in
+const TOKEN_COOKIE: &str = "mobius_token";
+
+/// How often a log stream checks for new output
+const LOG_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+struct Server {
+    token: Option<String>,
+    /// Bound to a loopback address, so `Host` must name one (DNS rebinding)
+    loopback: bool,
+}
+
+/// A local parent issue in `GET /api/issues`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueSummary {
+    pub identifier: String,
+    pub title: String,
+    pub status: String,
+    pub loop_running: bool,
+}
+
+/// `GET /api/issues/{id}/graph` payload; task statuses include the running
+/// loop's progress
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphReport {
+    pub parent: Option<ParentIssueContext>,
+    /// Sub-tasks ordered by identifier
+    pub tasks: Vec<SubTask>,
+    pub stats: GraphStats,
+    /// Longest chain of unfinished tasks, first blocker first
+    pub critical_path: Vec<String>,
+}
+
+/// `POST /api/issues/{id}/start` payload
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartReport {
+    pub parent_id: String,
+    pub pid: u32,
+    /// Where the loop's output goes
+    pub log: String,
+}
+
+/// A `state` event on `GET /api/issues/{id}/events`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateEvent {
+    pub status: StatusReport,
+    pub graph: GraphReport,
+}
+
+#[derive(Debug, Deserialize)]
+struct LogQuery {
+    /// Only the last `tail` lines
+    tail: Option<usize>,
+}
+
+/// An API failure, answered as `{"error": "..."}`
+struct ApiError(StatusCode, String);
+
+impl ApiError {
+    fn not_found(what: impl Into<String>) -> Self {
+        ApiError(StatusCode::NOT_FOUND, what.into())
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        ApiError(StatusCode::INTERNAL_SERVER_ERROR, format!("{:#}", e))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+pub fn run(bind: &str, port: u16, token: Option<&str>, observe: bool) -> anyhow::Result<()> {
+    let _ = shutdown::install_handler(|| {});
+
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(async {
+        let listener = TcpListener::bind((bind, port))
+            .await
+            .with_context(|| format!("Failed to listen on {}:{}", bind, port))?;
+        let addr: SocketAddr = listener.local_addr()?;
+        let loopback = addr.ip().is_loopback();
+        if token.is_none() && !loopback {
+            let exposed = if observe {
+                "read task specs and agent logs"
+            } else {
+                "start and cancel loops"
+            };
+            anyhow::bail!(
+                "Refusing to serve on {} without an API token; anyone who can reach it could {}. Set --token or MOBIUS_SERVE_TOKEN",
+                addr,
+                exposed
+            );
+        }
+        let state = Arc::new(Server {
+            token: token.map(str::to_string),
+            loopback,
+        });
+        println!(
+            "{}",
+            format!("Serving the mobius API on http://{}/api", addr).green()
+        );
+        if observe {
+            println!("{}", "Read-only: loop control routes are disabled".dimmed());
+        }
+        axum::serve(listener, router(state, observe))
+            .with_graceful_shutdown(shutdown::interrupted())
+            .await
+            .context("API server failed")
+    })
+}
+
+fn router(state: Arc<Server>, observe: bool) -> Router {
+    let mut api = Router::new()
+        .route("/issues", get(list_issues))
+        .route("/issues/{id}/graph", get(issue_graph))
+        .route("/issues/{id}/status", get(issue_status))
+        .route("/issues/{id}/runtime", get(issue_runtime))
+        .route("/issues/{id}/iterations", get(issue_iterations))
+        .route("/issues/{id}/logs", get(issue_logs))
+        .route("/issues/{id}/events", get(issue_events))
+        .route("/issues/{id}/logs/{subtask}", get(agent_log))
+        .route("/issues/{id}/logs/{subtask}/events", get(agent_log_events));
+    if !observe {
+        api = api
+            .route("/issues/{id}/start", post(start_loop))
+            .route("/issues/{id}/cancel", post(cancel))
+            .route("/issues/{id}/pause", post(pause))
+            .route("/issues/{id}/unpause", post(unpause));
+    }
+    let api = api.route_layer(middleware::from_fn_with_state(state.clone(), authorize));
+    Router::new()
+        .nest("/api", api)
+        .route("/", get(|| async { Html(DASHBOARD_HTML) }))
+        .route("/health", get(|| async { "ok" }))
+        .with_state(state)
+}
+
+async fn authorize(
+    State(state): State<Arc<Server>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if let Err(reason) = check_origin(request.headers(), state.loopback) {
+        return Err(ApiError(StatusCode::FORBIDDEN, reason));
+    }
+    if let Some(token) = &state.token {
+        let from_header = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .map(str::to_string);
+        let presented = from_header.or_else(|| token_cookie(request.headers()));
+        if !presented.is_some_and(|presented| tokens_match(&presented, token)) {
+            return Err(ApiError(
+                StatusCode::UNAUTHORIZED,
+                "missing or invalid API token".to_string(),
+            ));
+        }
+    }
+    Ok(next.run(request).await)
+}
+
+/// Token the dashboard stores in the `mobius_token` cookie, percent-decoded.
+fn token_cookie(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .find_map(|pair| pair.trim().strip_prefix(TOKEN_COOKIE)?.strip_prefix('='))
+        .map(percent_decode)
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .filter(|_| bytes[i] == b'%')
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Reject requests a browser sent on behalf of another site.
+///
+/// A cross-origin `Origin` means a page elsewhere issued the request (CSRF).
+/// On a loopback bind, `Host` must also be a loopback name, or a DNS name
+/// rebound to 127.0.0.1 could reach the API as its own origin.
+fn check_origin(headers: &HeaderMap, loopback: bool) -> Result<(), String> {
+    let host = headers
+        .get(header::HOST)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if loopback && !is_loopback_host(host) {
+        return Err(format!("Host '{}' is not this server", host));
+    }
+    if let Some(origin) = headers.get(header::ORIGIN) {
+        let origin = origin.to_str().unwrap_or_default();
+        let same_origin = origin
+            .strip_prefix("http://")
+            .or_else(|| origin.strip_prefix("https://"))
+            .is_some_and(|origin_host| !host.is_empty() && origin_host == host);
+        if !same_origin {
+            return Err(format!("Cross-origin request from '{}'", origin));
+        }
+    }
+    Ok(())
+}
+
+/// Whether a `Host` header value names the local machine.
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+async fn list_issues() -> ApiResult<Vec<IssueSummary>> {
+    let Ok(entries) = fs::read_dir(get_project_mobius_path().join("issues")) else {
+        return Ok(Json(Vec::new()));
+    };
+    let mut issues: Vec<IssueSummary> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|entry| read_parent_spec(&entry.file_name().to_string_lossy()))
+        .map(|spec| IssueSummary {
+            loop_running: loop_pid(&spec.identifier).is_some(),
+            identifier: spec.identifier,
+            title: spec.title,
+            status: spec.status,
+        })
+        .collect();
+    issues.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    Ok(Json(issues))
+}
+
+async fn issue_graph(Path(id): Path<String>) -> ApiResult<GraphReport> {
+    let id = known_issue(&id)?;
+    Ok(Json(graph_report(&id)))
+}
+
+async fn issue_status(Path(id): Path<String>) -> ApiResult<StatusReport> {
+    let id = known_issue(&id)?;
+    Ok(Json(status_report(&id)))
+}
+
+/// Stream a `state` event whenever the loop's status or graph changes.
+async fn issue_events(
+    Path(id): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let id = known_issue(&id)?;
+    let events = stream::unfold((id, String::new()), |(id, last)| async move {
+        loop {
+            let state = StateEvent {
+                status: status_report(&id),
+                graph: graph_report(&id),
+            };
+            let data = serde_json::to_string(&state).unwrap_or_default();
+            if data != last {
+                let event = Event::default().event("state").data(data.clone());
+                return Some((Ok(event), (id, data)));
+            }
+            tokio::time::sleep(STATE_POLL_INTERVAL).await;
+        }
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+async fn issue_runtime(Path(id): Path<String>) -> ApiResult<crate::types::context::RuntimeState> {
+    let id = known_issue(&id)?;
+    read_runtime_state(&id)
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("No loop has run for {}", id)))
+}
+
+async fn issue_iterations(
+    Path(id): Path<String>,
+) -> ApiResult<Vec<crate::local_state::IterationLogEntry>> {
+    let id = known_issue(&id)?;
+    Ok(Json(read_iteration_log(&id)))
+}
+
+async fn issue_logs(Path(id): Path<String>) -> ApiResult<Vec<crate::commands::logs::AgentLog>> {
+    let id = known_issue(&id)?;
+    Ok(Json(list_agent_logs(&agent_output_dir(&id))))
+}
+
+/// One agent's stream-json output as newline-delimited JSON.
+async fn agent_log(
+    Path((id, subtask)): Path<(String, String)>,
+    Query(query): Query<LogQuery>,
+) -> Result<Response, ApiError> {
+    let id = known_issue(&id)?;
+    let subtask = log_subtask(&subtask)?;
+    let content = find_agent_log(&agent_output_dir(&id), &subtask)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .ok_or_else(|| ApiError::not_found(format!("No agent log for {}", subtask)))?;
+    let body = match query.tail {
+        Some(tail) => tail_lines(&content, tail),
+        None => content,
+    };
+    Ok(([(header::CONTENT_TYPE, "application/x-ndjson")], body).into_response())
+}
+
+/// Stream an agent's log as `log` events, each a batch of rendered lines,
+/// then an `end` event after its final result.
+async fn agent_log_events(
+    Path((id, subtask)): Path<(String, String)>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let id = known_issue(&id)?;
+    let subtask = log_subtask(&subtask)?;
+    let dir = agent_output_dir(&id);
+    // The agent may not have started yet
+    let path =
+        find_agent_log(&dir, &subtask).unwrap_or_else(|| dir.join(format!("{}.jsonl", subtask)));
+
+    let events = stream::unfold(Some((LogTail::new(&path), false)), |tail| async move {
+        let (mut tail, finished) = tail?;
+        if finished {
+            return Some((Ok(Event::default().event("end").data("")), None));
+        }
+        loop {
+            let lines = tail.read_lines().unwrap_or_default();
+            let rendered: Vec<LogEvent> = lines.iter().flat_map(|l| parse_log_events(l)).collect();
+            if !rendered.is_empty() {
+                let finished = rendered
+                    .iter()
+                    .any(|e| matches!(e.kind, LogEventKind::Result | LogEventKind::ResultError));
+                let data = serde_json::to_string(&rendered).unwrap_or_default();
+                let event = Event::default().event("log").data(data);
+                return Some((Ok(event), Some((tail, finished))));
+            }
+            tokio::time::sleep(LOG_POLL_INTERVAL).await;
+        }
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Start `mobius loop <id>` in the background, like running it without the TUI.
+async fn start_loop(Path(id): Path<String>) -> Result<(StatusCode, Json<StartReport>), ApiError> {
+    let id = known_issue(&id)?;
+    if let Some(pid) = loop_pid(&id) {
+        return Err(ApiError(
+            StatusCode::CONFLICT,
+            format!("A loop is already running for {} (pid {})", id, pid),
+        ));
+    }
+    let report = spawn_loop(&id)?;
+    println!(
+        "{} {}",
+        chrono::Local::now().format("%H:%M:%S").to_string().dimmed(),
+        format!("Started loop for {} (pid {})", id, report.pid).green()
+    );
+    Ok((StatusCode::ACCEPTED, Json(report)))
+}
+
+async fn cancel(Path(id): Path<String>) -> ApiResult<crate::commands::cancel::CancelReport> {
+    let id = known_issue(&id)?;
+    // Cancelling drives tmux through its own async runtime, which cannot
+    // start on one of the server's threads
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(cancel_loop(&id));
+    });
+    let report = rx.await.context("cancel thread stopped")??;
+    Ok(Json(report))
+}
+
+async fn pause(Path(id): Path<String>) -> ApiResult<crate::commands::pause::PauseReport> {
+    let id = known_issue(&id)?;
+    Ok(Json(pause_loop(&id, true)?))
+}
+
+async fn unpause(Path(id): Path<String>) -> ApiResult<crate::commands::pause::PauseReport> {
+    let id = known_issue(&id)?;
+    Ok(Json(pause_loop(&id, false)?))
+}
+
+fn status_report(id: &str) -> StatusReport {
+    build_status_report(
+        id,
+        read_session(id).as_ref(),
+        read_runtime_state(id).as_ref(),
+        &read_pending_updates(id),
+        Utc::now(),
+        is_process_running,
+    )
+}
+
+fn graph_report(id: &str) -> GraphReport {
+    let parent = read_parent_spec(id);
+    let parent_identifier = parent
+        .as_ref()
+        .map(|p| p.identifier.clone())
+        .unwrap_or_else(|| id.to_string());
+    let issues = read_local_subtasks_as_linear_issues(id);
+    let mut graph = apply_resolved_blockers(id, &build_task_graph(id, &parent_identifier, &issues));
+
+    // Show what the running loop knows before it lands in the specs
+    if let Some(runtime) = read_runtime_state(id) {
+        let mut overrides: HashMap<String, TaskStatus> = HashMap::new();
+        for entry in &runtime.completed_tasks {
+            overrides.insert(get_completed_task_id(entry), TaskStatus::Done);
+        }
+        for task in &runtime.active_tasks {
+            overrides
+                .entry(task.id.clone())
+                .or_insert(TaskStatus::InProgress);
+        }
+        for entry in &runtime.failed_tasks {
+            overrides
+                .entry(get_completed_task_id(entry))
+                .or_insert(TaskStatus::Failed);
+        }
+        for task in graph.tasks.values_mut() {
+            if let Some(status) = overrides.get(&task.id) {
+                task.status = *status;
+            }
+        }
+    }
+
+    let path = critical_path(&graph, false);
+    let mut tasks: Vec<SubTask> = graph.tasks.values().cloned().collect();
+    tasks.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    GraphReport {
+        parent,
+        stats: get_graph_stats(&graph),
+        critical_path: path
+            .task_ids
+            .iter()
+            .map(|task_id| graph.tasks[task_id].identifier.clone())
+            .collect(),
+        tasks,
+    }
+}
+
+/// `id` when it names a parent issue with local state.
+fn known_issue(id: &str) -> Result<String, ApiError> {
+    if is_plain_id(id) && (read_parent_spec(id).is_some() || read_runtime_state(id).is_some()) {
+        Ok(id.to_string())
+    } else {
+        Err(ApiError::not_found(format!("No local state for {}", id)))
+    }
+}
+
+/// `subtask` when it can name an agent log file.
+fn log_subtask(subtask: &str) -> Result<String, ApiError> {
+    if is_plain_id(subtask) {
+        Ok(subtask.to_string())
+    } else {
+        Err(ApiError::not_found(format!("No agent log for {}", subtask)))
+    }
+}
+
+/// Whether `id` is safe to use as a file name: letters, digits, `-` and `_`.
+fn is_plain_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// PID of the loop running for `id`, if any.
+fn loop_pid(id: &str) -> Option<u32> {
+    read_runtime_state(id)
+        .and_then(|r| r.loop_pid)
+        .filter(|pid| *pid != 0 && is_process_running(*pid))
+}
+
+fn spawn_loop(id: &str) -> anyhow::Result<StartReport> {
+    use std::os::unix::process::CommandExt;
+
+    let log_dir = get_runtime_path(id)
+        .parent()
+        .map(std::path::Path::to_path_buf)
+        .unwrap_or_default();
+    fs::create_dir_all(&log_dir)?;
+    let log_path = log_dir.join(SERVED_LOOP_LOG);
+    let log_file = fs::File::create(&log_path)
+        .with_context(|| format!("Failed to create {}", log_path.display()))?;
+
+    let mut child = std::process::Command::new(std::env::current_exe()?)
+        .args(["loop", id, "--no-tui"])
+        .stdin(std::process::Stdio::null())
+        .stdout(log_file.try_clone()?)
+        .stderr(log_file)
+        // Its own process group, so stopping the server leaves the loop running
+        .process_group(0)
+        .spawn()
+        .context("Failed to start mobius loop")?;
+    let pid = child.id();
+    // Reap the loop when it exits
+    std::thread::spawn(move || child.wait());
+
+    Ok(StartReport {
+        parent_id: id.to_string(),
+        pid,
+        log: log_path.display().to_string(),
+    })
+}
+
+/// The last `n` lines of `content`.
+fn tail_lines(content: &str, n: usize) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut tail = lines[lines.len().saturating_sub(n)..].join("\n");
+    if !tail.is_empty() {
+        tail.push('\n');
+    }
+    tail
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail_lines() {
+        let content = "{\"a\":1}\n{\"b\":2}\n{\"c\":3}\n";
+        assert_eq!(tail_lines(content, 2), "{\"b\":2}\n{\"c\":3}\n");
+        assert_eq!(tail_lines(content, 10), content);
+        assert_eq!(tail_lines(content, 0), "");
+    }
+
+    fn headers(pairs: &[(header::HeaderName, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(name.clone(), value.parse().unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn test_check_origin() {
+        let local = headers(&[(header::HOST, "localhost:8080")]);
+        assert!(check_origin(&local, true).is_ok());
+        let same = headers(&[
+            (header::HOST, "127.0.0.1:8080"),
+            (header::ORIGIN, "http://127.0.0.1:8080"),
+        ]);
+        assert!(check_origin(&same, true).is_ok());
+        assert!(check_origin(&headers(&[(header::HOST, "[::1]:8080")]), true).is_ok());
+
+        // DNS rebinding: a foreign name resolving to loopback
+        let rebound = headers(&[(header::HOST, "evil.example:8080")]);
+        assert!(check_origin(&rebound, true).is_err());
+        assert!(check_origin(&rebound, false).is_ok());
+
+        // CSRF: another site's page posting to the API
+        let cross = headers(&[
+            (header::HOST, "localhost:8080"),
+            (header::ORIGIN, "https://evil.example"),
+        ]);
+        assert!(check_origin(&cross, true).is_err());
+        let opaque = headers(&[(header::HOST, "build-1:8080"), (header::ORIGIN, "null")]);
+        assert!(check_origin(&opaque, false).is_err());
+    }
+
+    #[test]
+    fn test_token_cookie() {
+        let jar = headers(&[(header::COOKIE, "theme=dark; mobius_token=s%3Bcr%20et%zz")]);
+        assert_eq!(token_cookie(&jar).as_deref(), Some("s;cr et%zz"));
+        assert!(token_cookie(&headers(&[(header::COOKIE, "mobius_tokens=x")])).is_none());
+        assert!(token_cookie(&HeaderMap::new()).is_none());
+    }
+
+    /// Serve `router(.., observe)` on a loopback port and return its base URL.
+    async fn spawn_server(observe: bool) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Server {
+            token: None,
+            loopback: true,
+        });
+        tokio::spawn(async move { axum::serve(listener, router(state, observe)).await });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_observe_omits_control_routes() {
+        let client = reqwest::Client::new();
+        for (observe, control_status) in [
+            (false, StatusCode::METHOD_NOT_ALLOWED),
+            (true, StatusCode::NOT_FOUND),
+        ] {
+            let url = spawn_server(observe).await;
+            // A GET on a POST-only route is refused only if the route exists
+            for action in ["start", "cancel", "pause", "unpause"] {
+                let resp = client
+                    .get(format!("{url}/api/issues/MOB-1/{action}"))
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(resp.status().as_u16(), control_status.as_u16(), "{action}");
+            }
+            let resp = client
+                .get(format!("{url}/api/issues"))
+                .send()
+                .await
+                .unwrap();
+            assert!(resp.status().is_success());
+        }
+    }
+
+    #[test]
+    fn test_known_issue_rejects_paths() {
+        for id in ["", "../etc", "MOB-1/tasks", "MOB 1"] {
+            let err = known_issue(id).err().unwrap();
+            assert_eq!(err.0, StatusCode::NOT_FOUND, "{id}");
+            let err = log_subtask(id).err().unwrap();
+            assert_eq!(err.0, StatusCode::NOT_FOUND, "{id}");
+        }
+        assert_eq!(log_subtask("MOB-2").ok().as_deref(), Some("MOB-2"));
+    }
+}
@@ -60,7 +60,7 @@ pub fn run(task_id: Option<&str>, backend: Option<&str>, clear: bool) -> anyhow:
     let resolved_backend = resolve_backend(backend, &config.backend);
 
     // Validate task ID format
-    if !validate_task_id(task_id, &resolved_backend) {
+    if !resolved_backend.is_valid_task_id(task_id) {
         eprintln!(
             "{}",
             format!(
@@ -107,12 +107,3 @@ fn resolve_backend(override_backend: Option<&str>, config_backend: &Backend) ->
         *config_backend
     }
 }
-
-fn validate_task_id(task_id: &str, backend: &Backend) -> bool {
-    let pattern = match backend {
-        Backend::Linear => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Jira => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Local => regex::Regex::new(r"^(LOC-\d+|task-\d+)$").unwrap(),
-    };
-    pattern.is_match(task_id)
-}
@@ -12,7 +12,7 @@ use crate::config::setup::{
     add_shortcuts_source_line, copy_commands, copy_shortcuts, copy_skills, ensure_runtime_settings,
 };
 use crate::types::config::{ExecutionConfig, LoopConfig, PathConfigType};
-use crate::types::enums::{AgentRuntime, Backend, Model};
+use crate::types::enums::{AgentRuntime, Backend, ExecutionDriver, Model};
 
 pub fn run(update_skills: bool, update_shortcuts: bool, _install: bool) -> anyhow::Result<()> {
     // --update-skills: Skip config wizard, just update skills/commands
@@ -143,13 +143,16 @@ pub fn run(update_skills: bool, update_shortcuts: bool, _install: bool) -> anyho
             .items(&[
                 "Claude - Use Claude Code runtime",
                 "OpenCode - Use OpenCode runtime",
+                "Codex - Use OpenAI Codex CLI runtime",
+                "Aider - Use Aider runtime (process driver only)",
             ])
             .default(0)
             .interact()?;
-        if runtime_idx == 0 {
-            AgentRuntime::Claude
-        } else {
-            AgentRuntime::Opencode
+        match runtime_idx {
+            0 => AgentRuntime::Claude,
+            1 => AgentRuntime::Opencode,
+            2 => AgentRuntime::Codex,
+            _ => AgentRuntime::Aider,
         }
     };
 
@@ -187,6 +190,8 @@ pub fn run(update_skills: bool, update_shortcuts: bool, _install: bool) -> anyho
         .items(&[
             "Linear - Recommended, native MCP integration",
             "Jira - Atlassian Jira integration",
+            "GitHub - GitHub Issues with sub-issues",
+            "GitLab - GitLab issues with merge request submission",
             "Local - No external issue tracker, issues stored in .mobius/",
         ])
         .default(0)
@@ -195,6 +200,8 @@ pub fn run(update_skills: bool, update_shortcuts: bool, _install: bool) -> anyho
     let backend = match backend_idx {
         0 => Backend::Linear,
         1 => Backend::Jira,
+        2 => Backend::Github,
+        3 => Backend::Gitlab,
         _ => Backend::Local,
     };
 
@@ -256,6 +263,11 @@ pub fn run(update_skills: bool, update_shortcuts: bool, _install: bool) -> anyho
             max_iterations,
             model: model.to_string(),
             sandbox,
+            driver: if which::which("tmux").is_ok() {
+                ExecutionDriver::Tmux
+            } else {
+                ExecutionDriver::Process
+            },
             ..ExecutionConfig::default()
         },
         ..LoopConfig::default()
@@ -409,7 +421,7 @@ fn get_bundled_commands_dir() -> std::path::PathBuf {
     std::path::PathBuf::from("commands")
 }
 
-fn get_bundled_shortcuts_path() -> std::path::PathBuf {
+pub(crate) fn get_bundled_shortcuts_path() -> std::path::PathBuf {
     if let Ok(exe) = std::env::current_exe() {
         let dir = exe.parent().unwrap_or(Path::new("."));
         let shortcuts = dir.join("shortcuts.sh");
@@ -96,7 +96,7 @@ pub fn run() -> anyhow::Result<()> {
 }
 
 /// Get bundled shortcuts path (relative to executable)
-fn get_bundled_shortcuts_path() -> std::path::PathBuf {
+pub(crate) fn get_bundled_shortcuts_path() -> std::path::PathBuf {
     let paths = resolve_paths();
     Path::new(&paths.skills_path)
         .parent()
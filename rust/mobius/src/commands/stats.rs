@@ -0,0 +1,791 @@
+//! Stats command - Summarize attempt outcomes, token usage, cost, and trends across loop runs
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use chrono::Datelike;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::cost::format_cost;
+use crate::local_state::{
+    list_local_issue_ids, read_iteration_log, read_parent_spec, IterationLogEntry, IterationStatus,
+};
+use crate::stream_json::{agent_output_dir, parse_final_tokens};
+use crate::tui::header::format_duration;
+use crate::tui::theme::format_token_pair;
+use crate::types::enums::FailureClass;
+use crate::worktree::is_identifier_in_base_log;
+
+/// Failures recorded on one day
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DailyFailures {
+    /// `YYYY-MM-DD` from the attempt's start time
+    pub date: String,
+    pub total: usize,
+    pub by_class: BTreeMap<FailureClass, usize>,
+}
+
+/// Failure breakdown for `mobius stats --failures`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureStats {
+    pub by_class: BTreeMap<FailureClass, usize>,
+    /// Failures logged before classification was recorded
+    pub unclassified: usize,
+    /// Oldest day first
+    pub by_day: Vec<DailyFailures>,
+}
+
+/// Attempt outcomes for one model
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelStats {
+    pub attempts: usize,
+    pub succeeded: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+/// Attempts and usage for one sub-task
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStats {
+    pub issue_id: String,
+    pub subtask_id: String,
+    /// Outcome of the latest attempt
+    pub status: IterationStatus,
+    pub attempts: usize,
+    pub retries: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Summed over attempts
+    pub duration_ms: u64,
+    pub cost_usd: f64,
+    /// Model of the latest attempt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// Attempts started in one ISO week
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyStats {
+    /// `YYYY-Www`, e.g. `2026-W41`
+    pub week: String,
+    pub attempts: usize,
+    pub succeeded: usize,
+    pub cost_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub median_duration_ms: Option<u64>,
+}
+
+/// Everything `mobius stats` reports
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatsReport {
+    pub issues: usize,
+    pub attempts: usize,
+    pub succeeded: usize,
+    pub partial: usize,
+    pub failed: usize,
+    /// Distinct sub-tasks attempted
+    pub tasks: usize,
+    pub avg_attempts_per_task: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub median_duration_ms: Option<u64>,
+    /// Estimated spend over attempts that recorded one
+    pub cost_usd: f64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Attempts that recorded which model ran them
+    pub by_model: BTreeMap<String, ModelStats>,
+    /// Parent issues whose identifier appears on the base branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merged_issues: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_per_merged_usd: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failures: Option<FailureStats>,
+    /// Oldest week first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trends: Option<Vec<WeeklyStats>>,
+    /// Per sub-task rows, for a single issue or `--csv`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub by_task: Option<Vec<TaskStats>>,
+}
+
+impl StatsReport {
+    /// Record how many of the issues were merged, spreading all spend over them.
+    pub fn with_merged(mut self, merged: usize) -> Self {
+        self.merged_issues = Some(merged);
+        self.cost_per_merged_usd =
+            (merged > 0 && self.cost_usd > 0.0).then(|| self.cost_usd / merged as f64);
+        self
+    }
+
+    pub fn with_tasks(mut self, tasks: Vec<TaskStats>) -> Self {
+        self.by_task = Some(tasks);
+        self
+    }
+}
+
+pub fn run(
+    task_id: Option<&str>,
+    failures: bool,
+    trends: bool,
+    csv: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let issue_ids = match task_id {
+        Some(id) => vec![id.to_string()],
+        None => list_local_issue_ids(),
+    };
+    let (issue_ids, logs): (Vec<String>, Vec<Vec<IterationLogEntry>>) = issue_ids
+        .into_iter()
+        .map(|id| {
+            let mut log = read_iteration_log(&id);
+            backfill_tokens(&id, &mut log);
+            (id, log)
+        })
+        .filter(|(_, log)| !log.is_empty())
+        .unzip();
+
+    let config = read_config(&resolve_paths().config_path).unwrap_or_default();
+    let base_branch = config.execution.base_branch.as_deref().unwrap_or("main");
+    let rt = tokio::runtime::Runtime::new()?;
+    let merged = issue_ids
+        .iter()
+        .filter(|id| {
+            let identifier = read_parent_spec(id)
+                .map(|spec| spec.identifier)
+                .unwrap_or_else(|| id.to_string());
+            rt.block_on(is_identifier_in_base_log(&identifier, base_branch))
+                .unwrap_or(false)
+        })
+        .count();
+
+    let tasks: Vec<TaskStats> = issue_ids
+        .iter()
+        .zip(&logs)
+        .flat_map(|(id, log)| build_task_stats(id, log))
+        .collect();
+    if csv {
+        print!("{}", tasks_to_csv(&tasks));
+        return Ok(());
+    }
+
+    let mut report = build_stats_report(&logs, failures, trends).with_merged(merged);
+    if task_id.is_some() {
+        report = report.with_tasks(tasks);
+    }
+    output.emit(&report, print_stats_report)
+}
+
+/// Fill in tokens for attempts logged before the loop recorded them.
+///
+/// Only a sub-task's latest attempt can be recovered: its stream-json output
+/// is overwritten on every dispatch.
+fn backfill_tokens(issue_id: &str, log: &mut [IterationLogEntry]) {
+    let output_dir = agent_output_dir(issue_id);
+    let mut seen = HashSet::new();
+    for entry in log.iter_mut().rev() {
+        if !seen.insert(entry.subtask_id.clone())
+            || entry.input_tokens.is_some()
+            || entry.output_tokens.is_some()
+        {
+            continue;
+        }
+        if let Some(usage) =
+            parse_final_tokens(&output_dir.join(format!("{}.jsonl", entry.subtask_id)))
+        {
+            entry.input_tokens = Some(usage.input_tokens);
+            entry.output_tokens = Some(usage.output_tokens);
+        }
+    }
+}
+
+/// One row per sub-task in the issue's log, in order of first attempt.
+pub fn build_task_stats(issue_id: &str, log: &[IterationLogEntry]) -> Vec<TaskStats> {
+    let mut rows: Vec<TaskStats> = Vec::new();
+    for entry in log {
+        let index = match rows.iter().position(|r| r.subtask_id == entry.subtask_id) {
+            Some(index) => index,
+            None => {
+                rows.push(TaskStats {
+                    issue_id: issue_id.to_string(),
+                    subtask_id: entry.subtask_id.clone(),
+                    status: entry.status.clone(),
+                    attempts: 0,
+                    retries: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    duration_ms: 0,
+                    cost_usd: 0.0,
+                    model: None,
+                });
+                rows.len() - 1
+            }
+        };
+        let row = &mut rows[index];
+        row.status = entry.status.clone();
+        row.attempts += 1;
+        row.retries = row.attempts - 1;
+        row.input_tokens += entry.input_tokens.unwrap_or(0);
+        row.output_tokens += entry.output_tokens.unwrap_or(0);
+        row.duration_ms += entry.duration_ms.unwrap_or(0);
+        row.cost_usd += entry.cost_usd.unwrap_or(0.0);
+        if entry.model.is_some() {
+            row.model = entry.model.clone();
+        }
+    }
+    rows
+}
+
+/// Per sub-task rows as CSV with a header line.
+pub fn tasks_to_csv(tasks: &[TaskStats]) -> String {
+    let mut csv = String::from(
+        "issue,subtask,status,attempts,retries,input_tokens,output_tokens,duration_ms,cost_usd,model\n",
+    );
+    for task in tasks {
+        let status = match task.status {
+            IterationStatus::Success => "success",
+            IterationStatus::Partial => "partial",
+            IterationStatus::Failed => "failed",
+        };
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{:.4},{}\n",
+            csv_field(&task.issue_id),
+            csv_field(&task.subtask_id),
+            status,
+            task.attempts,
+            task.retries,
+            task.input_tokens,
+            task.output_tokens,
+            task.duration_ms,
+            task.cost_usd,
+            csv_field(task.model.as_deref().unwrap_or_default()),
+        ));
+    }
+    csv
+}
+
+/// Quote a CSV field when it contains a separator, quote, or newline.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Median of `values`, averaging the middle pair for even counts.
+fn median(mut values: Vec<u64>) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let mid = values.len() / 2;
+    Some(if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2
+    } else {
+        values[mid]
+    })
+}
+
+/// ISO week of an RFC 3339 timestamp, as `YYYY-Www`.
+fn iso_week(timestamp: &str) -> Option<String> {
+    let week = chrono::DateTime::parse_from_rfc3339(timestamp)
+        .ok()?
+        .iso_week();
+    Some(format!("{}-W{:02}", week.year(), week.week()))
+}
+
+/// Aggregate iteration logs, one per issue.
+pub fn build_stats_report(
+    logs: &[Vec<IterationLogEntry>],
+    failures: bool,
+    trends: bool,
+) -> StatsReport {
+    let entries: Vec<&IterationLogEntry> = logs.iter().flatten().collect();
+    let count = |status: IterationStatus| entries.iter().filter(|e| e.status == status).count();
+    let succeeded = |e: &&&IterationLogEntry| e.status == IterationStatus::Success;
+
+    let tasks: usize = logs
+        .iter()
+        .map(|log| {
+            log.iter()
+                .map(|e| e.subtask_id.as_str())
+                .collect::<BTreeSet<_>>()
+                .len()
+        })
+        .sum();
+
+    let mut by_model: BTreeMap<String, ModelStats> = BTreeMap::new();
+    for entry in &entries {
+        if let Some(model) = &entry.model {
+            let stats = by_model.entry(model.clone()).or_default();
+            stats.attempts += 1;
+            stats.succeeded += usize::from(entry.status == IterationStatus::Success);
+            stats.input_tokens += entry.input_tokens.unwrap_or(0);
+            stats.output_tokens += entry.output_tokens.unwrap_or(0);
+        }
+    }
+
+    let trends = trends.then(|| {
+        let mut by_week: BTreeMap<String, Vec<&IterationLogEntry>> = BTreeMap::new();
+        for entry in &entries {
+            if let Some(week) = iso_week(&entry.started_at) {
+                by_week.entry(week).or_default().push(entry);
+            }
+        }
+        by_week
+            .into_iter()
+            .map(|(week, week_entries)| WeeklyStats {
+                week,
+                attempts: week_entries.len(),
+                succeeded: week_entries.iter().filter(succeeded).count(),
+                cost_usd: week_entries.iter().filter_map(|e| e.cost_usd).sum(),
+                median_duration_ms: median(
+                    week_entries.iter().filter_map(|e| e.duration_ms).collect(),
+                ),
+            })
+            .collect()
+    });
+
+    let failures = failures.then(|| {
+        let mut by_class = BTreeMap::new();
+        let mut by_day: BTreeMap<String, DailyFailures> = BTreeMap::new();
+        let mut unclassified = 0;
+
+        for entry in entries
+            .iter()
+            .filter(|e| e.status != IterationStatus::Success)
+        {
+            let Some(class) = entry.failure_class else {
+                unclassified += 1;
+                continue;
+            };
+            *by_class.entry(class).or_insert(0) += 1;
+
+            let date: String = entry.started_at.chars().take(10).collect();
+            let day = by_day.entry(date.clone()).or_insert_with(|| DailyFailures {
+                date,
+                total: 0,
+                by_class: BTreeMap::new(),
+            });
+            day.total += 1;
+            *day.by_class.entry(class).or_insert(0) += 1;
+        }
+
+        FailureStats {
+            by_class,
+            unclassified,
+            by_day: by_day.into_values().collect(),
+        }
+    });
+
+    StatsReport {
+        issues: logs.len(),
+        attempts: entries.len(),
+        succeeded: count(IterationStatus::Success),
+        partial: count(IterationStatus::Partial),
+        failed: count(IterationStatus::Failed),
+        tasks,
+        avg_attempts_per_task: if tasks == 0 {
+            0.0
+        } else {
+            entries.len() as f64 / tasks as f64
+        },
+        median_duration_ms: median(entries.iter().filter_map(|e| e.duration_ms).collect()),
+        cost_usd: entries.iter().filter_map(|e| e.cost_usd).sum(),
+        input_tokens: entries.iter().filter_map(|e| e.input_tokens).sum(),
+        output_tokens: entries.iter().filter_map(|e| e.output_tokens).sum(),
+        by_model,
+        merged_issues: None,
+        cost_per_merged_usd: None,
+        failures,
+        trends,
+        by_task: None,
+    }
+}
+
+fn print_stats_report(report: &StatsReport) {
+    println!("{}", "Attempts:".bold());
+    println!(
+        "  {} across {} issue{}",
+        report.attempts,
+        report.issues,
+        if report.issues == 1 { "" } else { "s" }
+    );
+    println!("  Succeeded: {}", report.succeeded.to_string().green());
+    println!("  Retried: {}", report.partial.to_string().yellow());
+    println!("  Failed: {}", report.failed.to_string().red());
+
+    if report.tasks > 0 {
+        println!();
+        println!("{}", "Sub-tasks:".bold());
+        println!(
+            "  {} attempted, {:.1} attempts each",
+            report.tasks, report.avg_attempts_per_task
+        );
+        if let Some(ms) = report.median_duration_ms {
+            println!("  Median attempt: {}", format_duration(ms));
+        }
+    }
+
+    if report.input_tokens > 0 || report.output_tokens > 0 {
+        println!();
+        println!("{}", "Tokens:".bold());
+        println!(
+            "  {}",
+            format_token_pair(report.input_tokens, report.output_tokens)
+        );
+    }
+
+    if report.cost_usd > 0.0 {
+        println!();
+        println!("{}", "Cost:".bold());
+        println!("  Total: {}", format_cost(report.cost_usd));
+        if let (Some(per_merged), Some(merged)) = (report.cost_per_merged_usd, report.merged_issues)
+        {
+            println!(
+                "  Per merged PR: {} ({} merged)",
+                format_cost(per_merged),
+                merged
+            );
+        }
+    }
+
+    if !report.by_model.is_empty() {
+        println!();
+        println!("{}", "By model:".bold());
+        for (model, stats) in &report.by_model {
+            println!(
+                "  {:<16} {:>4} attempts  {:>3}% succeeded  {}",
+                model,
+                stats.attempts,
+                stats.succeeded * 100 / stats.attempts,
+                format_token_pair(stats.input_tokens, stats.output_tokens).dimmed()
+            );
+        }
+    }
+
+    if let Some(tasks) = report.by_task.as_deref().filter(|t| !t.is_empty()) {
+        println!();
+        println!("{}", "By sub-task:".bold());
+        for task in tasks {
+            let status = match task.status {
+                IterationStatus::Success => "success".green(),
+                IterationStatus::Partial => "partial".yellow(),
+                IterationStatus::Failed => "failed".red(),
+            };
+            println!(
+                "  {:<12} {:<8} {:>2} attempt{}  {:<22} {:>8}  {:>7}  {}",
+                task.subtask_id,
+                status,
+                task.attempts,
+                if task.attempts == 1 { " " } else { "s" },
+                format_token_pair(task.input_tokens, task.output_tokens),
+                format_duration(task.duration_ms),
+                format_cost(task.cost_usd),
+                task.model.as_deref().unwrap_or_default().dimmed()
+            );
+        }
+    }
+
+    if let Some(weeks) = &report.trends {
+        println!();
+        println!("{}", "By week:".bold());
+        if weeks.is_empty() {
+            println!("  {}", "No dated attempts recorded.".dimmed());
+        }
+        for week in weeks {
+            println!(
+                "  {}  {:>4} attempts  {:>3}% succeeded  {:>8}  {}",
+                week.week,
+                week.attempts,
+                week.succeeded * 100 / week.attempts,
+                format_cost(week.cost_usd),
+                week.median_duration_ms
+                    .map(|ms| format!("median {}", format_duration(ms)))
+                    .unwrap_or_default()
+                    .dimmed()
+            );
+        }
+    }
+
+    let Some(failures) = &report.failures else {
+        return;
+    };
+    let total: usize = failures.by_class.values().sum();
+
+    println!();
+    println!("{}", "Failure classes:".bold());
+    if total == 0 {
+        println!("  {}", "No classified failures recorded.".dimmed());
+    }
+    let mut classes: Vec<(&FailureClass, &usize)> = failures.by_class.iter().collect();
+    classes.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    for (class, count) in classes {
+        println!(
+            "  {:<16} {:>4}  {:>3}%",
+            class.to_string(),
+            count,
+            count * 100 / total
+        );
+    }
+    if failures.unclassified > 0 {
+        println!(
+            "  {}",
+            format!("{} older failures have no class", failures.unclassified).dimmed()
+        );
+    }
+
+    if !failures.by_day.is_empty() {
+        println!();
+        println!("{}", "By day:".bold());
+        for day in &failures.by_day {
+            let breakdown: Vec<String> = day
+                .by_class
+                .iter()
+                .map(|(class, count)| format!("{} {}", class, count))
+                .collect();
+            println!(
+                "  {}  {:>4}  {}",
+                day.date,
+                day.total,
+                breakdown.join(", ").dimmed()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(
+        started_at: &str,
+        status: IterationStatus,
+        failure_class: Option<FailureClass>,
+    ) -> IterationLogEntry {
+        IterationLogEntry {
+            subtask_id: "MOB-101".to_string(),
+            attempt: 1,
+            started_at: started_at.to_string(),
+            completed_at: None,
+            status,
+            error: None,
+            files_modified: None,
+            commit_hash: None,
+            retry_count: None,
+            failure_class,
+            model: None,
+            duration_ms: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+            verify: None,
+        }
+    }
+
+    #[test]
+    fn test_build_stats_report_counts_outcomes() {
+        let logs = vec![
+            vec![
+                entry("2026-10-01T10:00:00Z", IterationStatus::Success, None),
+                entry("2026-10-01T11:00:00Z", IterationStatus::Partial, None),
+            ],
+            vec![entry("2026-10-02T09:00:00Z", IterationStatus::Failed, None)],
+        ];
+
+        let report = build_stats_report(&logs, false, false);
+        assert_eq!(report.issues, 2);
+        assert_eq!(report.attempts, 3);
+        assert_eq!((report.succeeded, report.partial, report.failed), (1, 1, 1));
+        assert!(report.failures.is_none());
+    }
+
+    #[test]
+    fn test_build_stats_report_groups_failures_by_class_and_day() {
+        let logs = vec![vec![
+            entry(
+                "2026-10-01T10:00:00Z",
+                IterationStatus::Partial,
+                Some(FailureClass::VerifyFailure),
+            ),
+            entry(
+                "2026-10-01T12:00:00Z",
+                IterationStatus::Failed,
+                Some(FailureClass::Timeout),
+            ),
+            entry(
+                "2026-10-02T08:00:00Z",
+                IterationStatus::Partial,
+                Some(FailureClass::VerifyFailure),
+            ),
+            entry("2026-09-30T08:00:00Z", IterationStatus::Failed, None),
+            entry("2026-10-02T09:00:00Z", IterationStatus::Success, None),
+        ]];
+
+        let failures = build_stats_report(&logs, true, false).failures.unwrap();
+        assert_eq!(failures.by_class[&FailureClass::VerifyFailure], 2);
+        assert_eq!(failures.by_class[&FailureClass::Timeout], 1);
+        assert_eq!(failures.unclassified, 1);
+        assert_eq!(failures.by_day.len(), 2);
+        assert_eq!(failures.by_day[0].date, "2026-10-01");
+        assert_eq!(failures.by_day[0].total, 2);
+        assert_eq!(failures.by_day[1].by_class[&FailureClass::VerifyFailure], 1);
+
+        let json = serde_json::to_value(&failures).unwrap();
+        assert_eq!(json["byClass"]["verify_failure"], 2);
+    }
+
+    #[test]
+    fn test_build_stats_report_models_costs_and_weekly_trends() {
+        let attempt =
+            |subtask: &str, started_at: &str, status, model: &str, ms, cost| IterationLogEntry {
+                subtask_id: subtask.to_string(),
+                model: Some(model.to_string()),
+                duration_ms: Some(ms),
+                cost_usd: Some(cost),
+                ..entry(started_at, status, None)
+            };
+        let logs = vec![
+            vec![
+                // Thursday of 2026-W40
+                attempt(
+                    "MOB-101",
+                    "2026-10-01T10:00:00Z",
+                    IterationStatus::Partial,
+                    "opus",
+                    60_000,
+                    1.0,
+                ),
+                attempt(
+                    "MOB-101",
+                    "2026-10-01T11:00:00Z",
+                    IterationStatus::Success,
+                    "opus",
+                    120_000,
+                    1.0,
+                ),
+                attempt(
+                    "MOB-102",
+                    "2026-10-01T11:00:00Z",
+                    IterationStatus::Success,
+                    "sonnet",
+                    30_000,
+                    0.5,
+                ),
+            ],
+            // Monday of 2026-W41
+            vec![attempt(
+                "MOB-201",
+                "2026-10-05T09:00:00Z",
+                IterationStatus::Failed,
+                "sonnet",
+                90_000,
+                0.5,
+            )],
+        ];
+
+        let report = build_stats_report(&logs, false, true).with_merged(2);
+        assert_eq!(report.tasks, 3);
+        assert!((report.avg_attempts_per_task - 4.0 / 3.0).abs() < 1e-9);
+        assert_eq!(report.median_duration_ms, Some(75_000));
+        assert!((report.cost_usd - 3.0).abs() < 1e-9);
+        assert_eq!(report.cost_per_merged_usd, Some(1.5));
+        assert_eq!(
+            report.by_model["opus"],
+            ModelStats {
+                attempts: 2,
+                succeeded: 1,
+                input_tokens: 0,
+                output_tokens: 0,
+            }
+        );
+        assert_eq!(report.by_model["sonnet"].succeeded, 1);
+
+        let weeks = report.trends.unwrap();
+        assert_eq!(weeks.len(), 2);
+        assert_eq!(weeks[0].week, "2026-W40");
+        assert_eq!((weeks[0].attempts, weeks[0].succeeded), (3, 2));
+        assert_eq!(weeks[0].median_duration_ms, Some(60_000));
+        assert_eq!(weeks[1].week, "2026-W41");
+        assert_eq!(weeks[1].succeeded, 0);
+
+        assert_eq!(
+            build_stats_report(&logs, false, false)
+                .with_merged(0)
+                .cost_per_merged_usd,
+            None
+        );
+    }
+
+    #[test]
+    fn test_task_stats_sum_attempts_and_export_csv() {
+        let attempt = |subtask: &str, status, model: Option<&str>, tokens: Option<(u64, u64)>| {
+            IterationLogEntry {
+                subtask_id: subtask.to_string(),
+                model: model.map(str::to_string),
+                duration_ms: Some(60_000),
+                cost_usd: Some(0.25),
+                input_tokens: tokens.map(|t| t.0),
+                output_tokens: tokens.map(|t| t.1),
+                verify: None,
+                ..entry("2026-10-01T10:00:00Z", status, None)
+            }
+        };
+        let log = vec![
+            attempt(
+                "MOB-101",
+                IterationStatus::Partial,
+                Some("opus"),
+                Some((1_000, 200)),
+            ),
+            attempt("MOB-102", IterationStatus::Success, Some("a,b"), None),
+            attempt(
+                "MOB-101",
+                IterationStatus::Success,
+                None,
+                Some((2_000, 300)),
+            ),
+        ];
+
+        let tasks = build_task_stats("MOB-100", &log);
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].subtask_id, "MOB-101");
+        assert_eq!(tasks[0].status, IterationStatus::Success);
+        assert_eq!((tasks[0].attempts, tasks[0].retries), (2, 1));
+        assert_eq!(
+            (tasks[0].input_tokens, tasks[0].output_tokens),
+            (3_000, 500)
+        );
+        assert_eq!(tasks[0].duration_ms, 120_000);
+        assert_eq!(tasks[0].model.as_deref(), Some("opus"));
+
+        let report = build_stats_report(&[log], false, false);
+        assert_eq!((report.input_tokens, report.output_tokens), (3_000, 500));
+        assert_eq!(report.by_model["opus"].input_tokens, 1_000);
+
+        let csv = tasks_to_csv(&tasks);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "issue,subtask,status,attempts,retries,input_tokens,output_tokens,duration_ms,cost_usd,model"
+        );
+        assert_eq!(
+            lines[1],
+            "MOB-100,MOB-101,success,2,1,3000,500,120000,0.5000,opus"
+        );
+        assert_eq!(
+            lines[2],
+            "MOB-100,MOB-102,success,1,0,0,0,60000,0.2500,\"a,b\""
+        );
+    }
+}
@@ -0,0 +1,487 @@
+//! Status command - One-screen summary of a loop's runtime state without the TUI
+
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::output::OutputFormat;
+use crate::context::{
+    is_process_running, read_pending_updates, read_runtime_state, read_session, resolve_task_id,
+};
+use crate::cost::format_cost;
+use crate::tui::header::format_duration;
+use crate::types::context::{PendingUpdateData, PendingUpdatesQueue, RuntimeState, SessionInfo};
+use crate::types::enums::SessionStatus;
+
+/// Agent entry in the status summary
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentStatus {
+    pub id: String,
+    pub pid: u32,
+    pub pane: String,
+    pub worktree: Option<String>,
+    pub model: Option<String>,
+    /// Pool worker running the agent; `None` on this machine
+    pub worker: Option<String>,
+    pub running: bool,
+    pub elapsed_ms: Option<u64>,
+}
+
+/// Unsynced entry from the pending-updates queue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingSync {
+    pub update_type: String,
+    pub identifier: String,
+    pub error: Option<String>,
+    /// Remote value holding the update back until `mobius push --resolve`
+    pub conflict: Option<String>,
+}
+
+/// Everything `mobius status` reports for one parent issue
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusReport {
+    pub parent_id: String,
+    pub parent_title: Option<String>,
+    pub session_status: Option<SessionStatus>,
+    pub elapsed_ms: Option<u64>,
+    pub total_tasks: Option<u32>,
+    pub completed: usize,
+    pub failed: usize,
+    pub agents: Vec<AgentStatus>,
+    pub pending_sync: Vec<PendingSync>,
+    pub worktrees: Vec<String>,
+    pub total_cost_usd: Option<f64>,
+}
+
+pub fn run(task_id: Option<&str>, output: OutputFormat) -> anyhow::Result<()> {
+    let Some(parent_id) = resolve_task_id(task_id) else {
+        anyhow::bail!(
+            "No task ID provided and no current task set. Usage: mobius status <task-id>"
+        );
+    };
+
+    let session = read_session(&parent_id);
+    let runtime = read_runtime_state(&parent_id);
+    if session.is_none() && runtime.is_none() && !output.is_json() {
+        println!(
+            "{}",
+            format!("No loop state found for {}", parent_id).yellow()
+        );
+        println!(
+            "{}",
+            format!("Run 'mobius loop {}' to start one.", parent_id).dimmed()
+        );
+        return Ok(());
+    }
+
+    let report = build_status_report(
+        &parent_id,
+        session.as_ref(),
+        runtime.as_ref(),
+        &read_pending_updates(&parent_id),
+        Utc::now(),
+        is_process_running,
+    );
+    output.emit(&report, print_status_report)
+}
+
+/// Assemble a status report from the on-disk loop state.
+///
+/// `is_running` checks agent PIDs; elapsed time runs to `now` while the
+/// session is active and to the last runtime update otherwise.
+pub fn build_status_report(
+    parent_id: &str,
+    session: Option<&SessionInfo>,
+    runtime: Option<&RuntimeState>,
+    pending: &PendingUpdatesQueue,
+    now: DateTime<Utc>,
+    is_running: impl Fn(u32) -> bool,
+) -> StatusReport {
+    let session_status = session.map(|s| s.status);
+    let active = session_status.map_or(true, |s| s == SessionStatus::Active);
+
+    let started_at = runtime
+        .map(|r| r.started_at.as_str())
+        .or(session.map(|s| s.started_at.as_str()))
+        .and_then(parse_time);
+    let ended_at = if active {
+        Some(now)
+    } else {
+        runtime.and_then(|r| parse_time(&r.updated_at))
+    };
+    let elapsed_ms = started_at
+        .zip(ended_at)
+        .map(|(start, end)| (end - start).num_milliseconds().max(0) as u64);
+
+    let agents: Vec<AgentStatus> = runtime
+        .map(|r| r.active_tasks.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|task| AgentStatus {
+            id: task.id.clone(),
+            pid: task.pid,
+            pane: task.pane.clone(),
+            worktree: task.worktree.clone(),
+            model: task.model.clone(),
+            worker: task.worker.clone(),
+            // Remote agents have no local PID; their worker reports liveness
+            running: task.worker.is_some() || (task.pid > 0 && is_running(task.pid)),
+            elapsed_ms: parse_time(&task.started_at)
+                .map(|start| (now - start).num_milliseconds().max(0) as u64),
+        })
+        .collect();
+
+    let pending_sync = pending
+        .updates
+        .iter()
+        .filter(|u| u.synced_at.is_none())
+        .map(|u| {
+            let (update_type, identifier) = describe_update(&u.data);
+            PendingSync {
+                update_type: update_type.to_string(),
+                identifier,
+                error: u.error.clone(),
+                conflict: u.conflict.as_ref().map(|c| c.remote_value.clone()),
+            }
+        })
+        .collect();
+
+    let mut worktrees: Vec<String> = session
+        .and_then(|s| s.worktree_path.clone())
+        .into_iter()
+        .chain(agents.iter().filter_map(|a| a.worktree.clone()))
+        .collect();
+    worktrees.sort();
+    worktrees.dedup();
+
+    StatusReport {
+        parent_id: parent_id.to_string(),
+        parent_title: runtime
+            .map(|r| r.parent_title.clone())
+            .filter(|t| !t.is_empty()),
+        session_status,
+        elapsed_ms,
+        total_tasks: runtime.and_then(|r| r.total_tasks),
+        completed: runtime.map_or(0, |r| r.completed_tasks.len()),
+        failed: runtime.map_or(0, |r| r.failed_tasks.len()),
+        agents,
+        pending_sync,
+        worktrees,
+        total_cost_usd: runtime.and_then(|r| r.total_cost_usd),
+    }
+}
+
+fn print_status_report(report: &StatusReport) {
+    match &report.parent_title {
+        Some(title) => println!("{} - {}", report.parent_id.cyan().bold(), title),
+        None => println!("{}", report.parent_id.cyan().bold()),
+    }
+
+    let session = match report.session_status {
+        Some(SessionStatus::Active) => "active".green().to_string(),
+        Some(SessionStatus::Completed) => "completed".green().to_string(),
+        Some(SessionStatus::Failed) => "failed".red().to_string(),
+        Some(SessionStatus::Paused) => "paused".yellow().to_string(),
+        Some(SessionStatus::Interrupted) => "interrupted".yellow().to_string(),
+        Some(SessionStatus::Cancelled) => "cancelled".yellow().to_string(),
+        None => "no session".dimmed().to_string(),
+    };
+    let elapsed = report
+        .elapsed_ms
+        .map(format_duration)
+        .unwrap_or_else(|| "-".to_string());
+    println!("  Session:   {}  ({} elapsed)", session, elapsed);
+
+    let total = report
+        .total_tasks
+        .map(|t| t.to_string())
+        .unwrap_or_else(|| "?".to_string());
+    println!(
+        "  Progress:  {}/{} done, {} failed, {} active",
+        report.completed.to_string().green(),
+        total,
+        if report.failed > 0 {
+            report.failed.to_string().red()
+        } else {
+            report.failed.to_string().normal()
+        },
+        report.agents.len()
+    );
+    if let Some(cost) = report.total_cost_usd {
+        println!("  Cost:      {}", format_cost(cost));
+    }
+
+    println!();
+    println!("{}", "Agents:".bold());
+    if report.agents.is_empty() {
+        println!("  {}", "none".dimmed());
+    }
+    for agent in &report.agents {
+        let state = if let Some(worker) = &agent.worker {
+            format!("on {}", worker).green()
+        } else if agent.running {
+            "running".green()
+        } else {
+            "stale".yellow()
+        };
+        println!(
+            "  {:<12} pid {:<8} pane {:<6} {:<8} {}{}",
+            agent.id,
+            agent.pid,
+            if agent.pane.is_empty() {
+                "-"
+            } else {
+                &agent.pane
+            },
+            state,
+            agent.elapsed_ms.map(format_duration).unwrap_or_default(),
+            agent
+                .model
+                .as_deref()
+                .map(|m| format!("  [{}]", m))
+                .unwrap_or_default()
+        );
+    }
+
+    println!();
+    println!("{}", "Pending sync:".bold());
+    if report.pending_sync.is_empty() {
+        println!("  {}", "none".dimmed());
+    }
+    for update in &report.pending_sync {
+        match (&update.error, &update.conflict) {
+            (Some(error), _) => println!(
+                "  {:<20} {}  {}",
+                update.update_type,
+                update.identifier,
+                format!("failed: {}", error).red()
+            ),
+            (None, Some(remote)) => println!(
+                "  {:<20} {}  {}",
+                update.update_type,
+                update.identifier,
+                format!("conflict: remote is {} (mobius push --resolve)", remote).yellow()
+            ),
+            (None, None) => println!("  {:<20} {}", update.update_type, update.identifier),
+        }
+    }
+
+    if !report.worktrees.is_empty() {
+        println!();
+        println!("{}", "Worktrees:".bold());
+        for path in &report.worktrees {
+            println!("  {}", path.dimmed());
+        }
+    }
+}
+
+fn describe_update(data: &PendingUpdateData) -> (&'static str, String) {
+    match data {
+        PendingUpdateData::StatusChange {
+            identifier,
+            new_status,
+            ..
+        } => ("status_change", format!("{} → {}", identifier, new_status)),
+        PendingUpdateData::AddComment { identifier, .. } => ("add_comment", identifier.clone()),
+        PendingUpdateData::CreateSubtask { title, .. } => ("create_subtask", title.clone()),
+        PendingUpdateData::UpdateDescription { identifier, .. } => {
+            ("update_description", identifier.clone())
+        }
+        PendingUpdateData::AddLabel { identifier, .. } => ("add_label", identifier.clone()),
+        PendingUpdateData::RemoveLabel { identifier, .. } => ("remove_label", identifier.clone()),
+        PendingUpdateData::AddRelation {
+            identifier,
+            blocker_identifier,
+            ..
+        } => (
+            "add_relation",
+            format!("{} blocked by {}", identifier, blocker_identifier),
+        ),
+        PendingUpdateData::RemoveRelation {
+            identifier,
+            blocker_identifier,
+            ..
+        } => (
+            "remove_relation",
+            format!("{} no longer blocked by {}", identifier, blocker_identifier),
+        ),
+    }
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::context::{PendingUpdate, RuntimeActiveTask};
+    use crate::types::enums::Backend;
+
+    fn now() -> DateTime<Utc> {
+        parse_time("2026-01-01T00:10:00Z").unwrap()
+    }
+
+    fn runtime() -> RuntimeState {
+        RuntimeState {
+            parent_id: "MOB-100".to_string(),
+            parent_title: "Task list feature".to_string(),
+            active_tasks: vec![
+                RuntimeActiveTask {
+                    id: "MOB-102".to_string(),
+                    pid: 4242,
+                    pane: "%3".to_string(),
+                    started_at: "2026-01-01T00:08:00Z".to_string(),
+                    worktree: Some("/wt/mob-100".to_string()),
+                    model: Some("sonnet".to_string()),
+                    input_tokens: None,
+                    output_tokens: None,
+                    retry_count: None,
+                    worker: None,
+                },
+                RuntimeActiveTask {
+                    id: "MOB-103".to_string(),
+                    pid: 4343,
+                    pane: "%4".to_string(),
+                    started_at: "2026-01-01T00:09:00Z".to_string(),
+                    worktree: Some("/wt/mob-100".to_string()),
+                    model: None,
+                    input_tokens: None,
+                    output_tokens: None,
+                    retry_count: None,
+                    worker: None,
+                },
+                RuntimeActiveTask {
+                    id: "MOB-104".to_string(),
+                    pid: 0,
+                    pane: String::new(),
+                    started_at: "2026-01-01T00:09:00Z".to_string(),
+                    worktree: None,
+                    model: None,
+                    input_tokens: None,
+                    output_tokens: None,
+                    retry_count: None,
+                    worker: Some("build-2".to_string()),
+                },
+            ],
+            completed_tasks: vec![serde_json::json!("MOB-101")],
+            failed_tasks: vec![],
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:09:00Z".to_string(),
+            loop_pid: None,
+            total_tasks: Some(4),
+            backend_statuses: None,
+            total_input_tokens: None,
+            total_output_tokens: None,
+            task_costs_usd: Default::default(),
+            total_cost_usd: Some(0.5),
+            cancelled_tasks: vec![],
+        }
+    }
+
+    fn pending_queue() -> PendingUpdatesQueue {
+        let update = |id: &str, synced: bool, error: Option<&str>| PendingUpdate {
+            id: id.to_string(),
+            created_at: String::new(),
+            synced_at: synced.then(|| "2026-01-01T00:05:00Z".to_string()),
+            error: error.map(str::to_string),
+            conflict: None,
+            data: PendingUpdateData::StatusChange {
+                issue_id: "id".to_string(),
+                identifier: "MOB-101".to_string(),
+                old_status: "Todo".to_string(),
+                new_status: "Done".to_string(),
+            },
+        };
+        PendingUpdatesQueue {
+            updates: vec![
+                update("u1", true, None),
+                update("u2", false, None),
+                update("u3", false, Some("HTTP 500")),
+            ],
+            last_sync_attempt: None,
+            last_sync_success: None,
+        }
+    }
+
+    #[test]
+    fn test_build_status_report_active_session() {
+        let session = SessionInfo {
+            parent_id: "MOB-100".to_string(),
+            backend: Backend::Linear,
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            worktree_path: Some("/wt/mob-100".to_string()),
+            status: SessionStatus::Active,
+        };
+
+        let report = build_status_report(
+            "MOB-100",
+            Some(&session),
+            Some(&runtime()),
+            &pending_queue(),
+            now(),
+            |pid| pid == 4242,
+        );
+
+        assert_eq!(report.elapsed_ms, Some(10 * 60 * 1000));
+        assert_eq!(report.completed, 1);
+        assert_eq!(report.total_tasks, Some(4));
+        assert_eq!(report.agents.len(), 3);
+        assert!(report.agents[0].running);
+        assert!(!report.agents[1].running);
+        assert!(report.agents[2].running);
+        assert_eq!(report.agents[2].worker.as_deref(), Some("build-2"));
+        assert_eq!(report.agents[0].elapsed_ms, Some(2 * 60 * 1000));
+        assert_eq!(report.pending_sync.len(), 2);
+        assert_eq!(report.pending_sync[0].identifier, "MOB-101 → Done");
+        assert_eq!(report.pending_sync[1].error.as_deref(), Some("HTTP 500"));
+        assert_eq!(report.worktrees, vec!["/wt/mob-100"]);
+    }
+
+    #[test]
+    fn test_build_status_report_finished_without_session() {
+        let mut state = runtime();
+        state.active_tasks.clear();
+        let session = SessionInfo {
+            parent_id: "MOB-100".to_string(),
+            backend: Backend::Linear,
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            worktree_path: None,
+            status: SessionStatus::Completed,
+        };
+
+        let report = build_status_report(
+            "MOB-100",
+            Some(&session),
+            Some(&state),
+            &pending_queue(),
+            now(),
+            |_| true,
+        );
+        // Elapsed stops at the last runtime update once the session ended
+        assert_eq!(report.elapsed_ms, Some(9 * 60 * 1000));
+        assert!(report.agents.is_empty());
+        assert!(report.worktrees.is_empty());
+
+        let report = build_status_report(
+            "MOB-100",
+            None,
+            None,
+            &PendingUpdatesQueue {
+                updates: vec![],
+                last_sync_attempt: None,
+                last_sync_success: None,
+            },
+            now(),
+            |_| true,
+        );
+        assert_eq!(report.elapsed_ms, None);
+        assert_eq!(report.completed, 0);
+        assert!(report.parent_title.is_none());
+    }
+}
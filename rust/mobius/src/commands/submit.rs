@@ -1,24 +1,122 @@
 //! Submit command - Create a pull request via configured runtime CLI
+//!
+//! The GitLab backend creates a merge request through the GitLab API instead.
 
+use anyhow::Context;
 use colored::Colorize;
+use std::path::Path;
 use std::process::Command;
 
+use crate::bisect::list_branch_commits;
+use crate::commands::churn::loop_branch;
+
 use crate::config::loader::read_config_with_env;
 use crate::config::paths::resolve_paths;
 use crate::runtime_adapter;
 // Session reading not needed here currently
+use crate::github::GithubClient;
+use crate::gitlab::{CreateMergeRequestOptions, GitlabAutoMerge, GitlabClient};
 use crate::jira::JiraClient;
-use crate::local_state::{read_parent_spec, write_parent_spec};
-use crate::types::enums::{AgentRuntime, Backend, Model};
+use crate::local_state::{
+    read_iteration_log, read_local_subtasks_as_linear_issues, read_parent_spec, write_parent_spec,
+};
+use crate::notifications::{Notification, Notifier};
+use crate::pr_template::{pr_template_vars, render_pr_body};
+use crate::stack::{
+    build_stack_branches, plan_stack, push_lease, read_stack, render_stack_graph, write_stack,
+    StackEntry, StackState,
+};
+use crate::types::config::StatusMapping;
+use crate::types::enums::{AgentRuntime, Backend, MergeMethod, Model};
+use crate::types::task_graph::build_task_graph;
+use crate::types::{NotificationsConfig, SubmitConfig};
 
-pub fn run(
-    task_id: Option<&str>,
-    backend_override: Option<&str>,
-    model_override: Option<&str>,
-    thinking_level_override: Option<&str>,
-    draft: bool,
-    skip_status_update: bool,
-) -> anyhow::Result<()> {
+#[derive(Default)]
+pub struct SubmitOptions<'a> {
+    pub backend_override: Option<&'a str>,
+    pub model_override: Option<&'a str>,
+    pub thinking_level_override: Option<&'a str>,
+    pub draft: bool,
+    pub skip_status_update: bool,
+    /// One PR per completed sub-task instead of one for the loop branch
+    pub stacked: bool,
+    /// `--reviewer`; replaces `submit.reviewers` when given
+    pub reviewers: Vec<String>,
+    /// `--label`; added to `submit.labels`
+    pub labels: Vec<String>,
+    /// `--milestone`; overrides `submit.milestone`
+    pub milestone: Option<String>,
+    /// Merge the PR with `submit.merge_method` once checks pass
+    pub auto_merge: bool,
+}
+
+/// Reviewers, labels and milestone to set on the PR
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrMetadata {
+    pub reviewers: Vec<String>,
+    pub labels: Vec<String>,
+    pub milestone: Option<String>,
+}
+
+impl PrMetadata {
+    fn is_empty(&self) -> bool {
+        self.reviewers.is_empty() && self.labels.is_empty() && self.milestone.is_none()
+    }
+
+    /// Flags for `gh pr create`, or for `gh pr edit` when `edit` is set
+    fn gh_args(&self, edit: bool) -> Vec<String> {
+        let (reviewer, label) = if edit {
+            ("--add-reviewer", "--add-label")
+        } else {
+            ("--reviewer", "--label")
+        };
+        let mut args = Vec::new();
+        if !self.reviewers.is_empty() {
+            args.extend([reviewer.to_string(), self.reviewers.join(",")]);
+        }
+        if !self.labels.is_empty() {
+            args.extend([label.to_string(), self.labels.join(",")]);
+        }
+        if let Some(milestone) = &self.milestone {
+            args.extend(["--milestone".to_string(), milestone.clone()]);
+        }
+        args
+    }
+}
+
+/// Combine the command-line values with the `submit:` config defaults and,
+/// with `submit.inherit_labels`, the parent issue's tracker labels.
+fn resolve_pr_metadata(
+    opts: &SubmitOptions<'_>,
+    config: &SubmitConfig,
+    parent_labels: &[String],
+) -> PrMetadata {
+    let reviewers = if opts.reviewers.is_empty() {
+        config.reviewers.clone()
+    } else {
+        opts.reviewers.clone()
+    };
+    let inherited = parent_labels.iter().filter(|_| config.inherit_labels);
+    let mut labels: Vec<String> = Vec::new();
+    for label in config.labels.iter().chain(&opts.labels).chain(inherited) {
+        let label = label.trim();
+        if !label.is_empty() && !labels.iter().any(|l| l.eq_ignore_ascii_case(label)) {
+            labels.push(label.to_string());
+        }
+    }
+    PrMetadata {
+        reviewers,
+        labels,
+        milestone: opts.milestone.clone().or_else(|| config.milestone.clone()),
+    }
+}
+
+pub fn run(task_id: Option<&str>, opts: &SubmitOptions<'_>) -> anyhow::Result<()> {
+    let backend_override = opts.backend_override;
+    let model_override = opts.model_override;
+    let thinking_level_override = opts.thinking_level_override;
+    let draft = opts.draft;
+    let skip_status_update = opts.skip_status_update;
     let paths = resolve_paths();
     let config = read_config_with_env(&paths.config_path).unwrap_or_default();
     let backend: Backend = if let Some(b) = backend_override {
@@ -26,6 +124,11 @@ pub fn run(
     } else {
         config.backend
     };
+    if config.runtime == AgentRuntime::Aider {
+        anyhow::bail!(
+            "The aider runtime cannot run the /pr skill; set runtime to claude, opencode or codex to submit"
+        );
+    }
     let mut execution_config = config.execution.clone();
     if config.runtime == AgentRuntime::Claude {
         if let Some(override_model) = model_override {
@@ -39,7 +142,7 @@ pub fn run(
                 });
         }
     }
-    let model_override = if config.runtime == AgentRuntime::Opencode {
+    let model_override = if config.runtime != AgentRuntime::Claude {
         model_override
     } else {
         None
@@ -52,7 +155,7 @@ pub fn run(
 
     // Validate task ID format if provided
     if let Some(tid) = task_id {
-        if !validate_task_id(tid, &backend) {
+        if !backend.is_valid_task_id(tid) {
             eprintln!(
                 "{}",
                 format!("Error: Invalid task ID format for {}: {}", backend, tid).red()
@@ -61,6 +164,55 @@ pub fn run(
         }
     }
 
+    let mut parent_labels = task_id
+        .and_then(read_parent_spec)
+        .map(|p| p.labels)
+        .unwrap_or_default();
+    // GitLab creates missing labels; `gh pr create --label` aborts on them
+    if config.submit.inherit_labels && backend != Backend::Gitlab && !parent_labels.is_empty() {
+        parent_labels = github_known_labels(parent_labels);
+    }
+    let metadata = resolve_pr_metadata(opts, &config.submit, &parent_labels);
+
+    // Stacked: one PR per sub-task through the gh CLI, no runtime PR skill
+    if let Some(tid) = task_id.filter(|_| opts.stacked) {
+        if backend == Backend::Gitlab {
+            anyhow::bail!("--stacked opens pull requests with the gh CLI; GitLab is not supported");
+        }
+        let base = config.execution.base_branch.as_deref().unwrap_or("main");
+        submit_stacked(tid, base, draft, &metadata)?;
+        if !skip_status_update {
+            update_parent_status(tid, &backend, "In Review");
+        }
+        return Ok(());
+    }
+
+    let templated_body = templated_pr_body(
+        config.submit.template.as_deref(),
+        Path::new(&paths.config_path),
+        task_id,
+        backend,
+    )?;
+
+    // GitLab: open a merge request through the API instead of the runtime PR skill
+    if backend == Backend::Gitlab {
+        let url = submit_gitlab_merge_request(
+            task_id,
+            draft,
+            config.execution.base_branch.as_deref(),
+            templated_body,
+            &metadata,
+            opts.auto_merge.then_some(config.submit.merge_method),
+        )?;
+        notify_pr_created(&config.notifications, task_id, Some(&url));
+        if let Some(tid) = task_id {
+            if !skip_status_update {
+                update_parent_status(tid, &backend, "In Review");
+            }
+        }
+        return Ok(());
+    }
+
     let task_label = task_id.map(|t| format!(" for {}", t)).unwrap_or_default();
     println!(
         "{}",
@@ -79,11 +231,23 @@ pub fn run(
         format!("/pr {}", skill_args.join(" "))
     };
 
-    let context_note = if let Some(tid) = task_id {
+    let context_note = if let Some(body) = &templated_body {
         format!(
-            "\n\nNote: This PR is for issue {}. Ensure this issue is linked in the PR.",
-            tid
+            "\n\nUse this exact PR description, verbatim:\n\n<pr-body>\n{}\n</pr-body>",
+            body
         )
+    } else if let Some(tid) = task_id {
+        if backend == Backend::Github {
+            format!(
+                "\n\nNote: This PR is for GitHub issue #{}. Include \"Closes #{}\" in the PR body.",
+                tid, tid
+            )
+        } else {
+            format!(
+                "\n\nNote: This PR is for issue {}. Ensure this issue is linked in the PR.",
+                tid
+            )
+        }
     } else {
         String::new()
     };
@@ -95,7 +259,7 @@ pub fn run(
 
     // Claude output can be piped through cclean; OpenCode does not require it.
     let use_cclean = config.runtime == AgentRuntime::Claude && which::which("cclean").is_ok();
-    let execution_thinking_override = if config.runtime == AgentRuntime::Opencode {
+    let execution_thinking_override = if config.runtime != AgentRuntime::Claude {
         thinking_level_override
     } else {
         None
@@ -125,6 +289,31 @@ pub fn run(
     match status {
         Ok(s) if s.success() => {
             println!("{}", "\n✓ Submit complete".green());
+            if !config.notifications.is_empty() || !metadata.is_empty() || opts.auto_merge {
+                let url = git_output(&["rev-parse", "--abbrev-ref", "HEAD"])
+                    .ok()
+                    .and_then(|branch| find_pull_request_url(&branch));
+                // The PR skill opens the PR, so reviewers and labels are added afterwards
+                if let Some(url) = url.as_deref().filter(|_| !metadata.is_empty()) {
+                    let mut args = vec!["pr".to_string(), "edit".to_string(), url.to_string()];
+                    args.extend(metadata.gh_args(true));
+                    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+                    match gh(Path::new("."), &args) {
+                        Ok(_) => println!("{}", "✓ Set PR reviewers, labels and milestone".green()),
+                        Err(e) => eprintln!(
+                            "{}",
+                            format!("⚠ Could not set PR reviewers, labels or milestone: {}", e)
+                                .yellow()
+                        ),
+                    }
+                }
+                if opts.auto_merge {
+                    enable_github_auto_merge(url.as_deref(), config.submit.merge_method);
+                }
+                if !config.notifications.is_empty() {
+                    notify_pr_created(&config.notifications, task_id, url.as_deref());
+                }
+            }
         }
         Ok(_) | Err(_) => {
             eprintln!("{}", format!("Error running {} CLI", config.runtime).red());
@@ -135,93 +324,518 @@ pub fn run(
     // Update parent issue status to "In Review"
     if let Some(tid) = task_id {
         if !skip_status_update {
-            update_parent_status_to_review(tid, &backend);
+            update_parent_status(tid, &backend, "In Review");
         }
     }
 
     Ok(())
 }
 
-fn update_parent_status_to_review(task_id: &str, backend: &Backend) {
-    let review_status = "In Review";
-
-    match backend {
-        Backend::Linear => {
-            let rt = tokio::runtime::Runtime::new().ok();
-            if let Some(rt) = rt {
-                rt.block_on(async {
-                    if let Ok(client) = crate::linear::LinearClient::new() {
-                        match client
-                            .update_linear_issue_status(task_id, review_status)
-                            .await
-                        {
-                            Ok(()) => println!(
-                                "{}",
-                                format!("✓ Updated {} status to \"{}\"", task_id, review_status)
-                                    .green()
-                            ),
-                            Err(_) => eprintln!(
-                                "{}",
-                                format!(
-                                    "⚠ Could not update {} status to \"{}\"",
-                                    task_id, review_status
-                                )
-                                .yellow()
-                            ),
-                        }
-                    }
-                });
-            }
+fn submit_gitlab_merge_request(
+    task_id: Option<&str>,
+    draft: bool,
+    base_branch: Option<&str>,
+    templated_body: Option<String>,
+    metadata: &PrMetadata,
+    auto_merge: Option<MergeMethod>,
+) -> anyhow::Result<String> {
+    let source_branch = git_output(&["rev-parse", "--abbrev-ref", "HEAD"])?;
+    let target_branch = base_branch.unwrap_or("main").to_string();
+    if source_branch == target_branch || source_branch == "HEAD" {
+        anyhow::bail!(
+            "Cannot open a merge request from '{}'; check out a feature branch first",
+            source_branch
+        );
+    }
+
+    let task_label = task_id.map(|t| format!(" for #{}", t)).unwrap_or_default();
+    println!(
+        "{}",
+        format!("\nCreating merge request{}...\n", task_label).cyan()
+    );
+
+    // Make sure the branch exists on the remote before opening the MR
+    let push_status = Command::new("git")
+        .args(["push", "-u", "origin", &source_branch])
+        .status()?;
+    if !push_status.success() {
+        anyhow::bail!("Failed to push {} to origin", source_branch);
+    }
+
+    let title = task_id
+        .and_then(read_parent_spec)
+        .map(|spec| spec.title)
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| git_output(&["log", "-1", "--format=%s"]).unwrap_or_default());
+    let description = templated_body.or_else(|| task_id.map(|t| format!("Closes #{}", t)));
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let mr = rt.block_on(async {
+        let client = GitlabClient::new()?;
+        let reviewer_ids = client.resolve_gitlab_user_ids(&metadata.reviewers).await?;
+        let milestone_id = match &metadata.milestone {
+            Some(title) => Some(client.resolve_gitlab_milestone_id(title).await?),
+            None => None,
+        };
+        let options = CreateMergeRequestOptions {
+            source_branch,
+            target_branch,
+            title,
+            description,
+            draft,
+            remove_source_branch: true,
+            labels: metadata.labels.clone(),
+            reviewer_ids,
+            milestone_id,
+        };
+        client.create_gitlab_merge_request(&options).await
+    })?;
+
+    println!(
+        "{}",
+        format!("✓ Created merge request !{}: {}", mr.iid, mr.web_url).green()
+    );
+
+    if let Some(method) = auto_merge {
+        let result = rt.block_on(async {
+            GitlabClient::new()?
+                .enable_gitlab_auto_merge(mr.iid, method == MergeMethod::Squash)
+                .await
+        });
+        match result {
+            Ok(GitlabAutoMerge::Scheduled) => println!(
+                "{}",
+                "✓ Merge request will merge when its pipeline succeeds".green()
+            ),
+            Ok(GitlabAutoMerge::Merged) => println!(
+                "{}",
+                "✓ Merge request merged (the project runs no pipeline)".green()
+            ),
+            Err(e) => eprintln!(
+                "{}",
+                format!("⚠ Could not enable auto-merge: {}", e).yellow()
+            ),
         }
-        Backend::Jira => {
-            let rt = tokio::runtime::Runtime::new().ok();
-            if let Some(rt) = rt {
-                rt.block_on(async {
-                    if let Ok(client) = JiraClient::new() {
-                        match client
-                            .update_jira_issue_status(task_id, review_status)
-                            .await
-                        {
-                            Ok(()) => println!(
-                                "{}",
-                                format!("✓ Updated {} status to \"{}\"", task_id, review_status)
-                                    .green()
-                            ),
-                            Err(_) => eprintln!(
-                                "{}",
-                                format!(
-                                    "⚠ Could not update {} status to \"{}\"",
-                                    task_id, review_status
-                                )
-                                .yellow()
-                            ),
-                        }
-                    }
-                });
-            }
+    }
+    Ok(mr.web_url)
+}
+
+/// Turn on GitHub auto-merge for the PR at `url`, warning when that fails
+/// (e.g. the repository does not allow auto-merge).
+fn enable_github_auto_merge(url: Option<&str>, method: MergeMethod) {
+    let Some(url) = url else {
+        eprintln!(
+            "{}",
+            "⚠ Could not find the new PR to enable auto-merge".yellow()
+        );
+        return;
+    };
+    let method_flag = format!("--{}", method);
+    match gh(
+        Path::new("."),
+        &["pr", "merge", url, "--auto", &method_flag],
+    ) {
+        Ok(_) => println!(
+            "{}",
+            format!(
+                "✓ Auto-merge ({}) enabled; the PR merges once checks pass",
+                method
+            )
+            .green()
+        ),
+        Err(e) => eprintln!(
+            "{}",
+            format!("⚠ Could not enable auto-merge: {}", e).yellow()
+        ),
+    }
+}
+
+/// The PR description rendered from `submit.template` for `task_id`, when
+/// both are set. Relative template paths resolve against the config file.
+fn templated_pr_body(
+    template: Option<&str>,
+    config_path: &Path,
+    task_id: Option<&str>,
+    backend: Backend,
+) -> anyhow::Result<Option<String>> {
+    let (Some(template), Some(tid)) = (template, task_id) else {
+        return Ok(None);
+    };
+    let path = config_path.parent().map_or_else(
+        || Path::new(template).to_path_buf(),
+        |dir| dir.join(template),
+    );
+    let graph = build_task_graph(tid, tid, &read_local_subtasks_as_linear_issues(tid));
+    let parent_title = read_parent_spec(tid)
+        .map(|p| p.title)
+        .unwrap_or_else(|| tid.to_string());
+    let vars = pr_template_vars(
+        tid,
+        &parent_title,
+        backend,
+        &graph,
+        &read_iteration_log(tid),
+    );
+    render_pr_body(&path, &vars).map(Some)
+}
+
+/// Rebuild the sub-task stack for `task_id` and open or update one PR per
+/// branch, then write the stack graph into every PR description.
+fn submit_stacked(
+    task_id: &str,
+    base: &str,
+    draft: bool,
+    metadata: &PrMetadata,
+) -> anyhow::Result<()> {
+    let (loop_branch, repo_path) = loop_branch(task_id);
+    let issues = read_local_subtasks_as_linear_issues(task_id);
+    let graph = build_task_graph(task_id, task_id, &issues);
+    let identifiers: Vec<String> = graph.tasks.values().map(|t| t.identifier.clone()).collect();
+    let commits = list_branch_commits(&repo_path, base, &loop_branch, &identifiers)?;
+    let entries = plan_stack(&graph, &commits, base, &loop_branch)?;
+    if entries.is_empty() {
+        anyhow::bail!(
+            "No commits from completed sub-tasks on {} since {}",
+            loop_branch,
+            base
+        );
+    }
+
+    println!(
+        "{}",
+        format!(
+            "\nBuilding {} stacked branches from {}...\n",
+            entries.len(),
+            loop_branch
+        )
+        .cyan()
+    );
+    build_stack_branches(&repo_path, task_id, &entries)?;
+
+    let parent_title = read_parent_spec(task_id)
+        .map(|p| p.title)
+        .unwrap_or_else(|| task_id.to_string());
+    let previous = read_stack(task_id);
+    let mut state = StackState::new(task_id, base, entries);
+    for old in previous.iter().flat_map(|p| &p.entries) {
+        if !state.entries.iter().any(|e| e.branch == old.branch) {
+            println!(
+                "{}",
+                format!(
+                    "⚠ {} is no longer in the stack; {} was left as is",
+                    old.identifier,
+                    old.pr_url.as_deref().unwrap_or(&old.branch)
+                )
+                .yellow()
+            );
         }
-        Backend::Local => {
-            if let Some(mut spec) = read_parent_spec(task_id) {
-                spec.status = review_status.to_string();
-                let _ = write_parent_spec(task_id, &spec);
-                println!(
-                    "{}",
-                    format!(
-                        "✓ Updated local parent.json status to \"{}\"",
-                        review_status
-                    )
-                    .green()
-                );
+    }
+    for i in 0..state.entries.len() {
+        let entry = &state.entries[i];
+        // Lease on what the last submit pushed, so commits someone else
+        // pushed to the branch since then are not overwritten
+        let lease = push_lease(&entry.branch, previous.as_ref());
+        let push = Command::new("git")
+            .current_dir(&repo_path)
+            .args(["push", &lease, "-u", "origin", &entry.branch])
+            .status()?;
+        if !push.success() {
+            anyhow::bail!(
+                "Failed to push {} to origin; if it has commits this stack didn't push, fetch and review them first",
+                entry.branch
+            );
+        }
+        let pushed = Command::new("git")
+            .current_dir(&repo_path)
+            .args(["rev-parse", &entry.branch])
+            .output()?;
+        state.entries[i].pushed = Some(String::from_utf8_lossy(&pushed.stdout).trim().to_string())
+            .filter(|sha| pushed.status.success() && !sha.is_empty());
+        let entry = &state.entries[i];
+
+        let url = match find_pull_request_url(&entry.branch) {
+            Some(url) => {
+                gh(&repo_path, &["pr", "edit", &url, "--base", &entry.base])?;
+                url
+            }
+            None => {
+                let title = format!("{}: {}", entry.identifier, entry.title);
+                let body = stacked_pr_body(&state, entry, task_id, &parent_title);
+                let metadata_args = metadata.gh_args(false);
+                let mut args = vec![
+                    "pr",
+                    "create",
+                    "--head",
+                    &entry.branch,
+                    "--base",
+                    &entry.base,
+                    "--title",
+                    &title,
+                    "--body",
+                    &body,
+                ];
+                args.extend(metadata_args.iter().map(String::as_str));
+                if draft {
+                    args.push("--draft");
+                }
+                let output = gh(&repo_path, &args)?;
+                output.lines().last().unwrap_or_default().trim().to_string()
             }
+        };
+        println!(
+            "{}",
+            format!("✓ {} → {}: {}", entry.branch, entry.base, url).green()
+        );
+        state.entries[i].pr_url = Some(url);
+        write_stack(&state)?;
+    }
+
+    // Every PR exists now, so each description can link the whole stack
+    for entry in &state.entries {
+        if let Some(url) = &entry.pr_url {
+            let body = stacked_pr_body(&state, entry, task_id, &parent_title);
+            gh(&repo_path, &["pr", "edit", url, "--body", &body])?;
         }
     }
+    println!(
+        "{}",
+        format!(
+            "
+✓ Submitted a stack of {} PRs; merge them from the bottom up",
+            state.entries.len()
+        )
+        .green()
+    );
+    Ok(())
 }
 
-fn validate_task_id(task_id: &str, backend: &Backend) -> bool {
-    let pattern = match backend {
-        Backend::Linear => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Jira => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Local => regex::Regex::new(r"^(LOC-\d+|task-\d+)$").unwrap(),
+fn stacked_pr_body(
+    state: &StackState,
+    entry: &StackEntry,
+    parent_id: &str,
+    parent_title: &str,
+) -> String {
+    format!(
+        "Sub-task {} of {}: {}.\n\n### Stack\n\n{}\n\n_Stacked by `mobius submit --stacked`; merge from the bottom up._",
+        entry.identifier,
+        parent_id,
+        parent_title,
+        render_stack_graph(state, Some(&entry.identifier))
+    )
+}
+
+/// Keep the tracker labels the GitHub repository defines, warning about the
+/// rest; all of them are dropped when the repository's labels can't be listed.
+fn github_known_labels(labels: Vec<String>) -> Vec<String> {
+    let repo_labels = match gh(
+        Path::new("."),
+        &[
+            "label", "list", "--limit", "1000", "--json", "name", "--jq", ".[].name",
+        ],
+    ) {
+        Ok(out) => out.lines().map(str::to_string).collect::<Vec<_>>(),
+        Err(e) => {
+            eprintln!(
+                "{}",
+                format!("Not inheriting the parent's labels: {:#}", e).yellow()
+            );
+            return Vec::new();
+        }
     };
-    pattern.is_match(task_id)
+    let (known, unknown) = partition_known_labels(labels, &repo_labels);
+    if !unknown.is_empty() {
+        eprintln!(
+            "{}",
+            format!(
+                "Not inheriting labels the repository doesn't have: {}",
+                unknown.join(", ")
+            )
+            .yellow()
+        );
+    }
+    known
+}
+
+/// Split `labels` into those `repo_labels` has (GitHub matches them
+/// case-insensitively) and the rest.
+fn partition_known_labels(
+    labels: Vec<String>,
+    repo_labels: &[String],
+) -> (Vec<String>, Vec<String>) {
+    labels.into_iter().partition(|label| {
+        repo_labels
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(label.trim()))
+    })
+}
+
+/// Run the `gh` CLI in `dir`, returning its stdout.
+pub(crate) fn gh(dir: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("gh")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .context("Failed to run gh; install the GitHub CLI")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "gh {} failed: {}",
+            args.iter().take(2).copied().collect::<Vec<_>>().join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// URL of the open pull request for `branch`, looked up with the `gh` CLI.
+fn find_pull_request_url(branch: &str) -> Option<String> {
+    let output = Command::new("gh")
+        .args(["pr", "view", branch, "--json", "url", "--jq", ".url"])
+        .output()
+        .ok()?;
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !url.is_empty()).then_some(url)
+}
+
+fn notify_pr_created(config: &NotificationsConfig, task_id: Option<&str>, url: Option<&str>) {
+    let notifier = Notifier::new(config);
+    if !notifier.is_enabled() {
+        return;
+    }
+    match tokio::runtime::Runtime::new() {
+        Ok(rt) => notifier.send_blocking(&rt, &Notification::pr_created(task_id, url)),
+        Err(e) => tracing::warn!("Failed to send pr_created notification: {}", e),
+    }
+}
+
+fn git_output(args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Move the parent issue to `status` on its backend, warning on failure.
+pub(crate) fn update_parent_status(task_id: &str, backend: &Backend, status: &str) {
+    // Review states spell no task status, so no status mapping applies
+    match set_parent_status(task_id, backend, status, None) {
+        Ok(()) => println!(
+            "{}",
+            format!("✓ Updated {} status to \"{}\"", task_id, status).green()
+        ),
+        Err(e) => eprintln!(
+            "{}",
+            format!(
+                "⚠ Could not update {} status to \"{}\": {}",
+                task_id, status, e
+            )
+            .yellow()
+        ),
+    }
+}
+
+/// Move the parent issue to `status` on its backend (or in the local
+/// `parent.json` for the local backend).
+///
+/// A status that spells a task status ("Done") goes through `status_mapping`
+/// the way pushed status changes do.
+pub(crate) fn set_parent_status(
+    task_id: &str,
+    backend: &Backend,
+    status: &str,
+    status_mapping: Option<&StatusMapping>,
+) -> anyhow::Result<()> {
+    if *backend == Backend::Local {
+        let mut spec = read_parent_spec(task_id)
+            .with_context(|| format!("No local parent.json for {}", task_id))?;
+        spec.status = status.to_string();
+        return write_parent_spec(task_id, &spec);
+    }
+    let status_id = status_mapping.and_then(|m| m.backend_status_id(status));
+    let status = status_mapping
+        .and_then(|m| m.backend_status(status))
+        .unwrap_or(status);
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(async {
+        match backend {
+            Backend::Linear => {
+                let client = crate::linear::LinearClient::new()?;
+                match status_id {
+                    Some(id) => client.update_linear_issue_state_id(task_id, id).await?,
+                    None => client.update_linear_issue_status(task_id, status).await?,
+                }
+            }
+            Backend::Jira => {
+                let client = JiraClient::new()?;
+                match status_id {
+                    Some(id) => client.update_jira_issue_status_by_id(task_id, id).await?,
+                    None => client.update_jira_issue_status(task_id, status).await?,
+                }
+            }
+            Backend::Github => {
+                GithubClient::new()?
+                    .update_github_issue_status(task_id, status)
+                    .await?
+            }
+            Backend::Gitlab => {
+                GitlabClient::new()?
+                    .update_gitlab_issue_status(task_id, status)
+                    .await?
+            }
+            Backend::Local => anyhow::bail!("The local backend has no remote status to set"),
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_pr_metadata_merges_flags_config_and_parent_labels() {
+        let config = SubmitConfig {
+            reviewers: vec!["alice".to_string()],
+            labels: vec!["mobius".to_string()],
+            milestone: Some("v2".to_string()),
+            inherit_labels: true,
+            ..Default::default()
+        };
+        let opts = SubmitOptions {
+            labels: vec!["backend".to_string(), "Mobius".to_string()],
+            ..Default::default()
+        };
+        let parent_labels = vec!["Feature".to_string()];
+
+        let metadata = resolve_pr_metadata(&opts, &config, &parent_labels);
+        assert_eq!(metadata.reviewers, vec!["alice"]);
+        assert_eq!(metadata.labels, vec!["mobius", "backend", "Feature"]);
+        assert_eq!(metadata.milestone.as_deref(), Some("v2"));
+
+        let opts = SubmitOptions {
+            reviewers: vec!["bob".to_string()],
+            milestone: Some("v3".to_string()),
+            ..Default::default()
+        };
+        let metadata = resolve_pr_metadata(&opts, &SubmitConfig::default(), &parent_labels);
+        assert_eq!(
+            metadata.gh_args(true),
+            vec!["--add-reviewer", "bob", "--milestone", "v3"]
+        );
+    }
+
+    #[test]
+    fn test_partition_known_labels() {
+        let repo_labels = vec!["bug".to_string(), "Feature".to_string()];
+        let (known, unknown) = partition_known_labels(
+            vec!["feature".to_string(), "Team: Core".to_string()],
+            &repo_labels,
+        );
+        assert_eq!(known, vec!["feature"]);
+        assert_eq!(unknown, vec!["Team: Core"]);
+    }
 }
@@ -0,0 +1,390 @@
+//! Task command - Edit a sub-task spec in $EDITOR with validation
+
+use std::collections::HashSet;
+use std::fs;
+use std::process::Command;
+
+use anyhow::Context;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config_with_env;
+use crate::config::paths::resolve_paths;
+use crate::context::{
+    extract_verify_commands, queue_pending_update, resolve_task_id, PendingUpdateInput,
+};
+use crate::local_state::{
+    list_local_issue_ids, read_subtasks, subtasks_as_linear_issues, write_subtask_spec,
+};
+use crate::types::context::{IssueRef, SubTaskContext};
+use crate::types::enums::Backend;
+use crate::types::task_graph::{
+    build_task_graph, format_cycle, is_external_blocker, validate_graph,
+};
+
+/// `mobius task edit --output json` payload
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EditReport {
+    parent_id: String,
+    task: String,
+    changed: bool,
+    /// Whether an `update_description` update was queued for the backend
+    queued_description: bool,
+}
+
+/// Open a sub-task spec in `$VISUAL`/`$EDITOR`, validate it, and save it.
+///
+/// The sub-task is looked up under `parent`, else the current task, else
+/// every local issue. A changed description is queued for `mobius push`.
+pub fn run_edit(subtask: &str, parent: Option<&str>, output: OutputFormat) -> anyhow::Result<()> {
+    let (parent_id, mut siblings) = find_parent_of(subtask, parent)?;
+    let index = siblings
+        .iter()
+        .position(|t| task_key(t).eq_ignore_ascii_case(subtask))
+        .context("sub-task vanished while reading")?;
+    let original = siblings[index].clone();
+    let key = task_key(&original).to_string();
+
+    let path = std::env::temp_dir().join(format!("mobius-{}-{}.json", key, uuid::Uuid::new_v4()));
+    fs::write(&path, serde_json::to_string_pretty(&original)? + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    let edited = loop {
+        open_editor(&path)?;
+        let content = fs::read_to_string(&path)?;
+        let problems = match serde_json::from_str::<SubTaskContext>(&content) {
+            Ok(edited) => {
+                let mut candidate = siblings.clone();
+                candidate[index] = edited.clone();
+                let mut problems = edit_problems(&original, &edited);
+                problems.extend(relation_problems(&candidate, &external_refs(&siblings)));
+                if problems.is_empty() {
+                    break edited;
+                }
+                problems
+            }
+            Err(e) => vec![format!("Not a valid sub-task: {}", e)],
+        };
+        for problem in &problems {
+            eprintln!("  {} {}", "✗".red(), problem);
+        }
+        let again = dialoguer::Confirm::new()
+            .with_prompt("Edit again?")
+            .default(true)
+            .interact()?;
+        if !again {
+            let _ = fs::remove_file(&path);
+            anyhow::bail!("No changes saved to {}", key);
+        }
+    };
+    let _ = fs::remove_file(&path);
+
+    let changed = serde_json::to_value(&edited)? != serde_json::to_value(&original)?;
+    let mut queued_description = false;
+    if changed {
+        siblings[index] = edited.clone();
+        for i in sync_blocks(&mut siblings) {
+            if i != index {
+                write_subtask_spec(&parent_id, &siblings[i])?;
+            }
+        }
+        write_subtask_spec(&parent_id, &siblings[index])?;
+
+        let paths = resolve_paths();
+        let config = read_config_with_env(&paths.config_path).unwrap_or_default();
+        if edited.description != original.description && config.backend != Backend::Local {
+            queue_pending_update(
+                &parent_id,
+                &PendingUpdateInput::UpdateDescription {
+                    issue_id: edited.id.clone(),
+                    identifier: key.clone(),
+                    description: edited.description.clone(),
+                    old_description: Some(original.description.clone()),
+                },
+            )?;
+            queued_description = true;
+        }
+    }
+
+    let report = EditReport {
+        parent_id,
+        task: key,
+        changed,
+        queued_description,
+    };
+    output.emit(&report, |report| {
+        if !report.changed {
+            println!("{}", format!("No changes to {}", report.task).dimmed());
+            return;
+        }
+        println!(
+            "{}",
+            format!("✓ Saved {} ({})", report.task, report.parent_id).green()
+        );
+        if report.queued_description {
+            println!(
+                "{}",
+                format!(
+                    "Description change queued: run 'mobius push {}' to sync it",
+                    report.parent_id
+                )
+                .dimmed()
+            );
+        }
+    })
+}
+
+/// Identifier a sub-task is known by (falls back to its ID).
+pub fn task_key(task: &SubTaskContext) -> &str {
+    if task.identifier.is_empty() {
+        &task.id
+    } else {
+        &task.identifier
+    }
+}
+
+/// The parent issue holding `subtask`, with all of that parent's sub-tasks.
+fn find_parent_of(
+    subtask: &str,
+    parent: Option<&str>,
+) -> anyhow::Result<(String, Vec<SubTaskContext>)> {
+    let holds = |parent_id: &str| {
+        let tasks = read_subtasks(parent_id);
+        tasks
+            .iter()
+            .any(|t| task_key(t).eq_ignore_ascii_case(subtask))
+            .then_some(tasks)
+    };
+    if let Some(parent_id) = resolve_task_id(parent) {
+        if let Some(tasks) = holds(&parent_id) {
+            return Ok((parent_id, tasks));
+        }
+        if parent.is_some() {
+            anyhow::bail!("No sub-task {} in {}", subtask, parent_id);
+        }
+    }
+
+    let mut found: Vec<(String, Vec<SubTaskContext>)> = list_local_issue_ids()
+        .into_iter()
+        .filter_map(|id| holds(&id).map(|tasks| (id, tasks)))
+        .collect();
+    match found.len() {
+        0 => anyhow::bail!(
+            "No local sub-task {}; run 'mobius pull <parent>' first",
+            subtask
+        ),
+        1 => Ok(found.remove(0)),
+        _ => anyhow::bail!(
+            "{} exists under {}; pass --parent to pick one",
+            subtask,
+            found
+                .iter()
+                .map(|(id, _)| id.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn open_editor(path: &std::path::Path) -> anyhow::Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // $EDITOR may carry flags (`code --wait`)
+    let status = Command::new("sh")
+        .args(["-c", &format!("{} \"$1\"", editor), "sh"])
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to open editor: {}", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor {} exited with {}", editor, status);
+    }
+    Ok(())
+}
+
+/// Checks that only concern the edited sub-task.
+fn edit_problems(original: &SubTaskContext, edited: &SubTaskContext) -> Vec<String> {
+    let mut problems = Vec::new();
+    if edited.id != original.id || task_key(edited) != task_key(original) {
+        problems.push(format!(
+            "The id and identifier cannot change (expected {})",
+            task_key(original)
+        ));
+    }
+    if edited.title.trim().is_empty() {
+        problems.push("The title is empty".to_string());
+    }
+    if let Some(problem) = verify_command_problem(edited) {
+        problems.push(problem);
+    }
+    problems
+}
+
+/// Whether the spec's `### Verify Command` block parses as shell.
+fn verify_command_problem(task: &SubTaskContext) -> Option<String> {
+    let command = extract_verify_commands(std::slice::from_ref(task))
+        .pop()?
+        .command;
+    let out = Command::new("sh")
+        .args(["-n", "-c", &command])
+        .output()
+        .ok()?;
+    if out.status.success() {
+        return None;
+    }
+    Some(format!(
+        "Verify command does not parse: {}",
+        String::from_utf8_lossy(&out.stderr).trim()
+    ))
+}
+
+/// Blocker references that name no sibling, as spelled in the specs.
+///
+/// Passing the references from before an edit as `allowed` keeps
+/// cross-issue blockers the tracker already had from being flagged.
+pub fn external_refs(tasks: &[SubTaskContext]) -> HashSet<String> {
+    tasks
+        .iter()
+        .flat_map(|t| &t.blocked_by)
+        .filter(|r| !tasks.iter().any(|t| refers_to(r, t)))
+        .map(|r| r.id.clone())
+        .collect()
+}
+
+/// Problems with the relations between one parent's sub-tasks: blockers that
+/// name no sibling (URLs and `allowed` references aside) and dependency cycles.
+pub fn relation_problems(tasks: &[SubTaskContext], allowed: &HashSet<String>) -> Vec<String> {
+    let mut problems = Vec::new();
+    for task in tasks {
+        for blocker in &task.blocked_by {
+            let known = tasks.iter().any(|t| refers_to(blocker, t))
+                || is_external_blocker(&blocker.id)
+                || allowed.contains(&blocker.id);
+            if !known {
+                problems.push(format!(
+                    "{} is blocked by {}, which is not a sub-task of this issue",
+                    task_key(task),
+                    blocker.id
+                ));
+            }
+        }
+    }
+
+    let graph = build_task_graph("", "", &subtasks_as_linear_issues(tasks.to_vec()));
+    for cycle in validate_graph(&graph).cycles {
+        problems.push(format!(
+            "Dependency cycle: {}",
+            format_cycle(&graph, &cycle)
+        ));
+    }
+    problems
+}
+
+/// Whether `r` names `task` by ID or identifier.
+pub fn refers_to(r: &IssueRef, task: &SubTaskContext) -> bool {
+    [r.id.as_str(), r.identifier.as_str()]
+        .iter()
+        .any(|name| !name.is_empty() && (*name == task.id || *name == task_key(task)))
+}
+
+/// Recompute each sub-task's `blocks` from its siblings' `blockedBy`,
+/// keeping references to issues outside the parent. Returns the indices of
+/// the sub-tasks whose `blocks` changed.
+pub fn sync_blocks(tasks: &mut [SubTaskContext]) -> Vec<usize> {
+    let mut changed = Vec::new();
+    for i in 0..tasks.len() {
+        let task = &tasks[i];
+        let mut blocks: Vec<IssueRef> = task
+            .blocks
+            .iter()
+            .filter(|r| !tasks.iter().any(|t| refers_to(r, t)))
+            .cloned()
+            .collect();
+        blocks.extend(
+            tasks
+                .iter()
+                .filter(|other| other.blocked_by.iter().any(|r| refers_to(r, task)))
+                .map(|other| IssueRef {
+                    id: other.id.clone(),
+                    identifier: task_key(other).to_string(),
+                }),
+        );
+        let same = blocks.len() == task.blocks.len()
+            && blocks
+                .iter()
+                .all(|b| task.blocks.iter().any(|r| r.id == b.id));
+        if !same {
+            tasks[i].blocks = blocks;
+            changed.push(i);
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, blocked_by: &[&str]) -> SubTaskContext {
+        SubTaskContext {
+            id: id.to_string(),
+            identifier: id.to_string(),
+            title: format!("Task {}", id),
+            description: String::new(),
+            status: "pending".to_string(),
+            git_branch_name: String::new(),
+            blocked_by: blocked_by
+                .iter()
+                .map(|b| IssueRef {
+                    id: b.to_string(),
+                    identifier: b.to_string(),
+                })
+                .collect(),
+            blocks: Vec::new(),
+            scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: Default::default(),
+            comments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_relation_problems_flags_unknown_blockers_and_cycles() {
+        let ok = vec![task("task-001", &[]), task("task-002", &["task-001"])];
+        assert!(relation_problems(&ok, &HashSet::new()).is_empty());
+
+        let unknown = vec![task("task-001", &["task-404"])];
+        assert_eq!(relation_problems(&unknown, &HashSet::new()).len(), 1);
+        let allowed = HashSet::from(["task-404".to_string()]);
+        assert!(relation_problems(&unknown, &allowed).is_empty());
+
+        let cycle = vec![
+            task("task-001", &["task-002"]),
+            task("task-002", &["task-001"]),
+        ];
+        let problems = relation_problems(&cycle, &HashSet::new());
+        assert!(problems.iter().any(|p| p.starts_with("Dependency cycle")));
+    }
+
+    #[test]
+    fn test_sync_blocks_mirrors_blocked_by() {
+        let mut tasks = vec![task("task-001", &[]), task("task-002", &["task-001"])];
+        assert_eq!(sync_blocks(&mut tasks), vec![0]);
+        assert_eq!(tasks[0].blocks[0].id, "task-002");
+
+        tasks[1].blocked_by.clear();
+        assert_eq!(sync_blocks(&mut tasks), vec![0]);
+        assert!(tasks[0].blocks.is_empty());
+    }
+
+    #[test]
+    fn test_verify_command_problem_detects_bad_shell() {
+        let mut spec = task("task-001", &[]);
+        spec.description = "### Verify Command\n```bash\ncargo test -p core\n```".to_string();
+        assert!(verify_command_problem(&spec).is_none());
+        spec.description = "### Verify Command\n```bash\nif then (\n```".to_string();
+        assert!(verify_command_problem(&spec).is_some());
+    }
+}
@@ -1,18 +1,56 @@
 //! Tree command - Display sub-task dependency tree without execution
 
 use colored::Colorize;
+use serde::Serialize;
 
+use crate::commands::output::OutputFormat;
 use crate::config::loader::read_config;
 use crate::config::paths::resolve_paths;
+use crate::external_deps::apply_resolved_blockers;
+use crate::github::GithubClient;
+use crate::gitlab::GitlabClient;
 use crate::jira::JiraClient;
-use crate::local_state::{read_local_subtasks_as_linear_issues, read_parent_spec};
-use crate::mermaid_renderer::render_mermaid_with_title;
-use crate::tree_renderer::render_full_tree_output;
+use crate::local_state::{
+    read_iteration_log, read_local_subtasks_as_linear_issues, read_parent_spec,
+};
+use crate::mermaid_renderer::{render_mermaid_gantt_markdown, render_mermaid_with_critical_path};
+use crate::tree_renderer::{render_critical_path, render_full_tree_output};
 use crate::types::enums::Backend;
-use crate::types::task_graph::ParentIssue;
-use crate::types::task_graph::{build_task_graph, get_graph_stats};
+use crate::types::task_graph::{
+    build_task_graph_with_mapping, critical_path, format_cycle, get_graph_stats, validate_graph,
+};
+use crate::types::task_graph::{GraphStats, ParentIssue, SubTask};
 
-pub fn run(task_id: &str, backend_override: Option<&str>, mermaid: bool) -> anyhow::Result<()> {
+/// `mobius tree --output json` payload
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TreeReport {
+    parent: ParentIssue,
+    /// Sub-tasks ordered by identifier
+    tasks: Vec<SubTask>,
+    stats: GraphStats,
+    /// Dependency cycles as identifiers in blocking order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    cycles: Vec<Vec<String>>,
+    /// Longest chain of unfinished tasks, first blocker first
+    critical_path: Vec<String>,
+    /// Task count, or summed complexity with `--weighted`
+    critical_path_length: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mermaid: Option<String>,
+    /// Gantt chart of the attempts so far, with `--timeline`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeline: Option<String>,
+}
+
+pub fn run(
+    task_id: &str,
+    backend_override: Option<&str>,
+    mermaid: bool,
+    timeline: bool,
+    weighted: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
     let paths = resolve_paths();
     let config = read_config(&paths.config_path).unwrap_or_default();
     let backend: Backend = if let Some(b) = backend_override {
@@ -22,7 +60,7 @@ pub fn run(task_id: &str, backend_override: Option<&str>, mermaid: bool) -> anyh
     };
 
     // Validate task ID format
-    if !validate_task_id(task_id, &backend) {
+    if !backend.is_valid_task_id(task_id) {
         eprintln!(
             "{}",
             format!("Error: Invalid task ID format for {}: {}", backend, task_id).red()
@@ -88,15 +126,62 @@ pub fn run(task_id: &str, backend_override: Option<&str>, mermaid: bool) -> anyh
                 }
             })
         }
+        Backend::Github => {
+            let rt = tokio::runtime::Runtime::new()?;
+            rt.block_on(async {
+                let api_err = match GithubClient::new() {
+                    Ok(client) => match client.fetch_github_issue(task_id).await {
+                        Ok(issue) => return Ok(issue),
+                        Err(e) => e.to_string(),
+                    },
+                    Err(e) => e.to_string(),
+                };
+                match read_parent_spec(task_id) {
+                    Some(s) => Ok(ParentIssue {
+                        id: s.id,
+                        identifier: s.identifier,
+                        title: s.title,
+                        git_branch_name: s.git_branch_name,
+                    }),
+                    None => Err(api_err),
+                }
+            })
+        }
+        Backend::Gitlab => {
+            let rt = tokio::runtime::Runtime::new()?;
+            rt.block_on(async {
+                let api_err = match GitlabClient::new() {
+                    Ok(client) => match client.fetch_gitlab_issue(task_id).await {
+                        Ok(issue) => return Ok(issue),
+                        Err(e) => e.to_string(),
+                    },
+                    Err(e) => e.to_string(),
+                };
+                match read_parent_spec(task_id) {
+                    Some(s) => Ok(ParentIssue {
+                        id: s.id,
+                        identifier: s.identifier,
+                        title: s.title,
+                        git_branch_name: s.git_branch_name,
+                    }),
+                    None => Err(api_err),
+                }
+            })
+        }
     };
 
     let parent_issue = match parent_issue {
         Ok(issue) => {
-            println!("{} {}: {}", "✓".green(), issue.identifier, issue.title);
-            println!(
+            output.note(format!(
+                "{} {}: {}",
+                "✓".green(),
+                issue.identifier,
+                issue.title
+            ));
+            output.note(format!(
                 "  {}",
                 format!("Branch: {}", issue.git_branch_name).dimmed()
-            );
+            ));
             issue
         }
         Err(cause) => {
@@ -111,20 +196,60 @@ pub fn run(task_id: &str, backend_override: Option<&str>, mermaid: bool) -> anyh
 
     // Read sub-tasks from local state
     let sub_tasks = read_local_subtasks_as_linear_issues(task_id);
-    if sub_tasks.is_empty() {
+    if sub_tasks.is_empty() && !output.is_json() {
         println!("{}", format!("No sub-tasks found for {}", task_id).yellow());
         return Ok(());
     }
 
-    println!(
+    output.note(format!(
         "{} Found {} sub-task{}",
         "✓".green(),
         sub_tasks.len(),
         if sub_tasks.len() == 1 { "" } else { "s" }
-    );
+    ));
 
     // Build the graph
-    let graph = build_task_graph(&parent_issue.id, &parent_issue.identifier, &sub_tasks);
+    let graph = build_task_graph_with_mapping(
+        &parent_issue.id,
+        &parent_issue.identifier,
+        &sub_tasks,
+        config.status_mapping_for(backend),
+    );
+    let graph = apply_resolved_blockers(task_id, &graph);
+
+    let validation = validate_graph(&graph);
+    let path = critical_path(&graph, weighted);
+    let timeline =
+        timeline.then(|| render_mermaid_gantt_markdown(&graph, &read_iteration_log(task_id)));
+
+    if output.is_json() {
+        let mut tasks: Vec<SubTask> = graph.tasks.values().cloned().collect();
+        tasks.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+        let report = TreeReport {
+            parent: parent_issue,
+            tasks,
+            stats: get_graph_stats(&graph),
+            cycles: validation
+                .cycles
+                .iter()
+                .map(|cycle| {
+                    cycle
+                        .iter()
+                        .map(|id| graph.tasks[id].identifier.clone())
+                        .collect()
+                })
+                .collect(),
+            critical_path: path
+                .task_ids
+                .iter()
+                .map(|id| graph.tasks[id].identifier.clone())
+                .collect(),
+            critical_path_length: path.length,
+            mermaid: mermaid.then(|| render_mermaid_with_critical_path(&graph, &path)),
+            timeline,
+        };
+        return output.emit(&report, |_| {});
+    }
 
     // Display ASCII tree
     println!();
@@ -134,7 +259,12 @@ pub fn run(task_id: &str, backend_override: Option<&str>, mermaid: bool) -> anyh
     if mermaid {
         println!();
         println!("{}", "Mermaid Diagram:".bold());
-        println!("{}", render_mermaid_with_title(&graph));
+        println!("{}", render_mermaid_with_critical_path(&graph, &path));
+    }
+    if let Some(timeline) = &timeline {
+        println!();
+        println!("{}", "Execution Timeline:".bold());
+        println!("{}", timeline);
     }
 
     // Display summary stats
@@ -146,15 +276,29 @@ pub fn run(task_id: &str, backend_override: Option<&str>, mermaid: bool) -> anyh
     println!("  Ready: {}", stats.ready.to_string().blue());
     println!("  Blocked: {}", stats.blocked.to_string().yellow());
     println!("  In Progress: {}", stats.in_progress.to_string().cyan());
+    println!();
+    println!("{}", render_critical_path(&graph, &path));
 
-    Ok(())
-}
+    if !validation.is_valid() {
+        println!();
+        println!(
+            "{}",
+            "Dependency cycles (these tasks can never start):"
+                .red()
+                .bold()
+        );
+        for cycle in &validation.cycles {
+            println!("  {}", format_cycle(&graph, cycle).red());
+        }
+        println!(
+            "{}",
+            format!(
+                "Fix the blockedBy relations, or run 'mobius loop {} --break-cycles'.",
+                task_id
+            )
+            .dimmed()
+        );
+    }
 
-fn validate_task_id(task_id: &str, backend: &Backend) -> bool {
-    let pattern = match backend {
-        Backend::Linear => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Jira => regex::Regex::new(r"^[A-Z]+-\d+$").unwrap(),
-        Backend::Local => regex::Regex::new(r"^(LOC-\d+|task-\d+)$").unwrap(),
-    };
-    pattern.is_match(task_id)
+    Ok(())
 }
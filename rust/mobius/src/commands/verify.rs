@@ -0,0 +1,140 @@
+//! Verify command - Run sub-task verify commands in the loop's worktree and
+//! record each outcome in the verify run log
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::Utc;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::churn::loop_branch;
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::context::resolve_task_id;
+use crate::tui::header::format_duration;
+use crate::verify::{
+    append_manual_run, get_verify_log_path, read_verify_commands, run_verify_command,
+    ManualVerifyRun, VerifyRun,
+};
+
+/// One sub-task's verify command outcome
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyResult {
+    pub subtask_id: String,
+    pub title: String,
+    #[serde(flatten)]
+    pub run: VerifyRun,
+    pub log: String,
+}
+
+/// Everything `mobius verify` reports
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyReport {
+    pub parent_id: String,
+    pub dir: String,
+    pub results: Vec<VerifyResult>,
+    pub passed: usize,
+    pub failed: usize,
+}
+
+pub fn run(
+    task_id: Option<&str>,
+    subtask: Option<&str>,
+    path: Option<&str>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let Some(parent_id) = resolve_task_id(task_id) else {
+        anyhow::bail!(
+            "No task ID provided and no current task set. Usage: mobius verify <task-id> [subtask]"
+        );
+    };
+    let config = read_config(&resolve_paths().config_path).unwrap_or_default();
+    let verification = config.execution.verification.unwrap_or_default();
+    let timeout = Duration::from_secs(u64::from(verification.verify_timeout_minutes) * 60);
+
+    let mut commands = read_verify_commands(&parent_id);
+    if let Some(subtask) = subtask {
+        commands.retain(|c| c.subtask_id.eq_ignore_ascii_case(subtask));
+        if commands.is_empty() {
+            anyhow::bail!("{} has no ### Verify Command in its spec", subtask);
+        }
+    } else if commands.is_empty() {
+        anyhow::bail!("No sub-task of {} has a ### Verify Command", parent_id);
+    }
+    let dir = path.map_or_else(|| loop_branch(&parent_id).1, PathBuf::from);
+
+    let mut results = Vec::new();
+    for verify in commands {
+        output.note(format!("Running {} verify: {}", verify.subtask_id, verify.command).dimmed());
+        let started_at = Utc::now().to_rfc3339();
+        let log_path = get_verify_log_path(&parent_id, &verify.subtask_id);
+        let run = run_verify_command(&dir, &verify.command, timeout, &log_path)?;
+
+        // Not an attempt: keep it out of the iteration log the loop and stats read
+        append_manual_run(
+            &parent_id,
+            &ManualVerifyRun {
+                subtask_id: verify.subtask_id.clone(),
+                started_at,
+                dir: dir.display().to_string(),
+                run: run.clone(),
+            },
+        )?;
+
+        results.push(VerifyResult {
+            subtask_id: verify.subtask_id,
+            title: verify.title,
+            run,
+            log: log_path.display().to_string(),
+        });
+    }
+
+    let passed = results.iter().filter(|r| r.run.passed).count();
+    let report = VerifyReport {
+        parent_id,
+        dir: dir.display().to_string(),
+        failed: results.len() - passed,
+        passed,
+        results,
+    };
+    output.emit(&report, print_verify_report)?;
+    if report.failed > 0 {
+        anyhow::bail!(
+            "{} of {} verify commands failed",
+            report.failed,
+            report.results.len()
+        );
+    }
+    Ok(())
+}
+
+fn print_verify_report(report: &VerifyReport) {
+    for result in &report.results {
+        let took = format_duration(result.run.duration_ms);
+        if result.run.passed {
+            println!(
+                "{} {} {}",
+                "✓".green(),
+                result.subtask_id.bold(),
+                format!("({})", took).dimmed()
+            );
+        } else {
+            println!(
+                "{} {} {}",
+                "✗".red(),
+                result.subtask_id.bold(),
+                format!("({})", took).dimmed()
+            );
+            println!("  {}", result.run.failure_message().red());
+            println!("  {}", format!("Full output: {}", result.log).dimmed());
+        }
+    }
+    println!(
+        "\n{} passed, {} failed in {}",
+        report.passed, report.failed, report.dir
+    );
+}
@@ -0,0 +1,41 @@
+//! Verify-scope command - Print the test command scoped to changed workspace packages
+
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::commands::output::OutputFormat;
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::project_detector::detect_project_info;
+use crate::workspace::{changed_files, resolve_verify_scope, VerifyScope};
+
+pub fn run(base: Option<&str>, path: &str, output: OutputFormat) -> anyhow::Result<()> {
+    let paths = resolve_paths();
+    let config = read_config(&paths.config_path).unwrap_or_default();
+    let base = base
+        .map(str::to_string)
+        .or_else(|| config.execution.base_branch.clone())
+        .unwrap_or_else(|| "main".to_string());
+    let verification = config.execution.verification.clone().unwrap_or_default();
+
+    let root = Path::new(path);
+    let files = changed_files(root, &base)?;
+    let full_command = detect_project_info(path)
+        .ok()
+        .and_then(|info| info.available_commands.test);
+    let scope = resolve_verify_scope(root, files, full_command, &verification);
+
+    output.emit(&scope, print_scope)
+}
+
+/// Print only the command on stdout so it can be used as `$(mobius verify-scope)`.
+fn print_scope(scope: &VerifyScope) {
+    if let Some(reason) = &scope.fallback_reason {
+        eprintln!("{}", format!("Full test suite: {}", reason).dimmed());
+    }
+    match &scope.command {
+        Some(command) => println!("{}", command),
+        None => eprintln!("{}", "No test command detected".yellow()),
+    }
+}
@@ -0,0 +1,337 @@
+//! Watch command - Print one line per runtime state change of a running loop
+//!
+//! A read-only, non-interactive alternative to the TUI: pipe it into a log
+//! or leave it running in a second terminal. With `--output json` every
+//! event is a single-line JSON object.
+
+use std::collections::HashSet;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::commands::output::OutputFormat;
+use crate::context::{read_runtime_state, read_session, watch_runtime_state};
+use crate::tui::header::format_duration;
+use crate::types::context::{RuntimeCompletedTask, RuntimeState};
+use crate::types::enums::SessionStatus;
+
+/// How often the session is checked for the loop having finished
+const SESSION_POLL: Duration = Duration::from_secs(1);
+
+/// A change between two runtime states
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum WatchEvent {
+    Started {
+        task: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        retry: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        worker: Option<String>,
+    },
+    Completed {
+        task: String,
+        #[serde(rename = "durationMs")]
+        duration_ms: u64,
+    },
+    Failed {
+        task: String,
+    },
+    Cancelled {
+        task: String,
+    },
+    LoopFinished {
+        status: SessionStatus,
+        completed: usize,
+        failed: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        total: Option<u32>,
+    },
+}
+
+/// A timestamped event as printed
+#[derive(Debug, Serialize)]
+struct WatchLine<'a> {
+    at: String,
+    #[serde(flatten)]
+    event: &'a WatchEvent,
+}
+
+pub fn run(task_id: &str, output: OutputFormat) -> anyhow::Result<()> {
+    let mut seen_active = is_running(read_session(task_id).map(|s| s.status));
+    if !seen_active {
+        output.note(
+            format!(
+                "No loop is running for {}; waiting for one to start (Ctrl-C to stop)",
+                task_id
+            )
+            .dimmed(),
+        );
+    }
+
+    let (tx, rx) = mpsc::channel::<Option<RuntimeState>>();
+    let _handle = watch_runtime_state(task_id, move |state| {
+        let _ = tx.send(state);
+    })?;
+    // The watcher reports the current state first; only later changes are events
+    let mut last = rx.recv().ok().flatten();
+
+    loop {
+        match rx.recv_timeout(SESSION_POLL) {
+            Ok(state) => {
+                for event in diff_states(last.as_ref(), state.as_ref()) {
+                    print_event(&event, output)?;
+                }
+                last = state;
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        let status = read_session(task_id).map(|s| s.status);
+        if is_running(status) {
+            seen_active = true;
+        } else if seen_active {
+            // Flush changes the debounce has not delivered yet
+            let state = read_runtime_state(task_id).or_else(|| last.clone());
+            for event in diff_states(last.as_ref(), state.as_ref()) {
+                print_event(&event, output)?;
+            }
+            let finished = WatchEvent::LoopFinished {
+                status: status.unwrap_or(SessionStatus::Completed),
+                completed: state.as_ref().map_or(0, |s| s.completed_tasks.len()),
+                failed: state.as_ref().map_or(0, |s| s.failed_tasks.len()),
+                total: state.as_ref().and_then(|s| s.total_tasks),
+            };
+            print_event(&finished, output)?;
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn is_running(status: Option<SessionStatus>) -> bool {
+    matches!(status, Some(SessionStatus::Active | SessionStatus::Paused))
+}
+
+/// Events for the tasks that started, completed, failed, or were cancelled
+/// between `old` and `new`.
+pub fn diff_states(old: Option<&RuntimeState>, new: Option<&RuntimeState>) -> Vec<WatchEvent> {
+    let Some(new) = new else {
+        return Vec::new();
+    };
+    let ids = |entries: &[serde_json::Value]| -> HashSet<String> {
+        entries.iter().filter_map(entry_id).collect()
+    };
+    let old_active: HashSet<&str> = old
+        .map(|s| s.active_tasks.iter().map(|t| t.id.as_str()).collect())
+        .unwrap_or_default();
+    let old_completed = old.map(|s| ids(&s.completed_tasks)).unwrap_or_default();
+    let old_failed = old.map(|s| ids(&s.failed_tasks)).unwrap_or_default();
+    let old_cancelled = old.map(|s| ids(&s.cancelled_tasks)).unwrap_or_default();
+
+    let mut events: Vec<WatchEvent> = new
+        .active_tasks
+        .iter()
+        .filter(|t| !old_active.contains(t.id.as_str()))
+        .map(|t| WatchEvent::Started {
+            task: t.id.clone(),
+            model: t.model.clone(),
+            retry: t.retry_count.unwrap_or(0),
+            worker: t.worker.clone(),
+        })
+        .collect();
+    for entry in &new.completed_tasks {
+        let Some(task) = entry_id(entry).filter(|id| !old_completed.contains(id)) else {
+            continue;
+        };
+        let duration_ms = serde_json::from_value::<RuntimeCompletedTask>(entry.clone())
+            .map(|c| c.duration)
+            .unwrap_or(0);
+        events.push(WatchEvent::Completed { task, duration_ms });
+    }
+    events.extend(
+        new.failed_tasks
+            .iter()
+            .filter_map(entry_id)
+            .filter(|id| !old_failed.contains(id))
+            .map(|task| WatchEvent::Failed { task }),
+    );
+    events.extend(
+        new.cancelled_tasks
+            .iter()
+            .filter_map(entry_id)
+            .filter(|id| !old_cancelled.contains(id))
+            .map(|task| WatchEvent::Cancelled { task }),
+    );
+    events
+}
+
+/// Task ID of a completed/failed entry (object with `id`, or a legacy plain string).
+fn entry_id(entry: &serde_json::Value) -> Option<String> {
+    if let Some(id) = entry.as_str() {
+        return Some(id.to_string());
+    }
+    entry.get("id")?.as_str().map(String::from)
+}
+
+fn print_event(event: &WatchEvent, output: OutputFormat) -> anyhow::Result<()> {
+    let now = chrono::Local::now();
+    if output.is_json() {
+        let line = WatchLine {
+            at: now.to_rfc3339(),
+            event,
+        };
+        println!("{}", serde_json::to_string(&line)?);
+        return Ok(());
+    }
+    println!(
+        "{} {}",
+        now.format("%H:%M:%S").to_string().dimmed(),
+        describe(event)
+    );
+    Ok(())
+}
+
+fn describe(event: &WatchEvent) -> String {
+    match event {
+        WatchEvent::Started {
+            task,
+            model,
+            retry,
+            worker,
+        } => {
+            let mut details = Vec::new();
+            if let Some(model) = model {
+                details.push(model.clone());
+            }
+            if *retry > 0 {
+                details.push(format!("retry {}", retry));
+            }
+            if let Some(worker) = worker {
+                details.push(format!("on {}", worker));
+            }
+            let details = if details.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", details.join(", "))
+            };
+            format!("{} {} started{}", "▶".cyan(), task, details)
+        }
+        WatchEvent::Completed { task, duration_ms } if *duration_ms > 0 => format!(
+            "{} {} completed in {}",
+            "✓".green(),
+            task,
+            format_duration(*duration_ms)
+        ),
+        WatchEvent::Completed { task, .. } => format!("{} {} completed", "✓".green(), task),
+        WatchEvent::Failed { task } => format!("{} {} failed", "✗".red(), task),
+        WatchEvent::Cancelled { task } => format!("{} {} cancelled", "■".yellow(), task),
+        WatchEvent::LoopFinished {
+            status,
+            completed,
+            failed,
+            total,
+        } => {
+            let status = serde_json::to_value(status)
+                .ok()
+                .and_then(|v| v.as_str().map(String::from))
+                .unwrap_or_default();
+            let total = total.map(|t| format!("/{}", t)).unwrap_or_default();
+            format!(
+                "{} Loop {}: {}{} completed, {} failed",
+                "■".bold(),
+                status,
+                completed,
+                total,
+                failed
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::context::RuntimeActiveTask;
+
+    fn state(active: &[&str], completed: serde_json::Value, failed: &[&str]) -> RuntimeState {
+        RuntimeState {
+            parent_id: "MOB-1".into(),
+            parent_title: "Parent".into(),
+            active_tasks: active
+                .iter()
+                .map(|id| RuntimeActiveTask {
+                    id: id.to_string(),
+                    pid: 1,
+                    pane: "%1".into(),
+                    started_at: "2026-10-01T10:00:00Z".into(),
+                    worktree: None,
+                    model: Some("sonnet".into()),
+                    input_tokens: None,
+                    output_tokens: None,
+                    retry_count: None,
+                    worker: None,
+                })
+                .collect(),
+            completed_tasks: completed.as_array().cloned().unwrap_or_default(),
+            failed_tasks: failed.iter().map(|id| serde_json::json!(id)).collect(),
+            started_at: "2026-10-01T10:00:00Z".into(),
+            updated_at: "2026-10-01T10:00:00Z".into(),
+            loop_pid: Some(1),
+            total_tasks: Some(3),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_diff_states_reports_only_new_transitions() {
+        let before = state(&["MOB-2", "MOB-3"], serde_json::json!([]), &[]);
+        let after = state(
+            &["MOB-4"],
+            serde_json::json!([{"id": "MOB-2", "completedAt": "t", "duration": 61000}]),
+            &["MOB-3"],
+        );
+
+        let events = diff_states(Some(&before), Some(&after));
+        assert_eq!(
+            events,
+            vec![
+                WatchEvent::Started {
+                    task: "MOB-4".into(),
+                    model: Some("sonnet".into()),
+                    retry: 0,
+                    worker: None,
+                },
+                WatchEvent::Completed {
+                    task: "MOB-2".into(),
+                    duration_ms: 61000,
+                },
+                WatchEvent::Failed {
+                    task: "MOB-3".into()
+                },
+            ]
+        );
+        assert!(diff_states(Some(&after), Some(&after)).is_empty());
+        assert!(diff_states(Some(&after), None).is_empty());
+    }
+
+    #[test]
+    fn test_watch_line_serializes_as_flat_json() {
+        let event = WatchEvent::Completed {
+            task: "MOB-2".into(),
+            duration_ms: 5,
+        };
+        let line = WatchLine {
+            at: "t".into(),
+            event: &event,
+        };
+        assert_eq!(
+            serde_json::to_string(&line).unwrap(),
+            r#"{"at":"t","event":"completed","task":"MOB-2","durationMs":5}"#
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Worker command - Join a loop's agent pool and run the tasks it hands out
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Context;
+use colored::Colorize;
+use tokio::io::BufReader;
+use tokio::net::TcpStream;
+use tokio::sync::mpsc;
+use tokio::time::{interval, sleep};
+
+use crate::agent_pool::{
+    failed_result, fetch_branch, integrate_branch, publish_branch, recv_message, send_message,
+    write_issue_files, Batch, CoordinatorMessage, TaskOutcome, WorkerMessage, HEARTBEAT_INTERVAL,
+    PROTOCOL_VERSION,
+};
+use crate::bootstrap::run_bootstrap;
+use crate::build_cache::{link_caches, plan_caches};
+use crate::completion::result_file_for;
+use crate::config::loader::read_config_with_env;
+use crate::config::paths::resolve_paths;
+use crate::context::get_results_directory_path;
+use crate::executor::{ExecutionContext, ExecutionResult};
+use crate::process_executor::ProcessExecutor;
+use crate::shutdown;
+use crate::types::LoopConfig;
+use crate::worktree::{create_worktree, WorktreeConfig};
+
+/// Wait between connection attempts
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Attempts to land a batch's commits when other machines push concurrently
+const PUSH_ATTEMPTS: u32 = 3;
+
+/// How a connection to the coordinator ended
+enum Session {
+    Ended,
+    Rejected(String),
+    Interrupted,
+}
+
+pub fn run(
+    join: &str,
+    name: Option<&str>,
+    capacity: Option<usize>,
+    token: Option<&str>,
+) -> anyhow::Result<()> {
+    let paths = resolve_paths();
+    let config = read_config_with_env(&paths.config_path).unwrap_or_default();
+    let capacity = capacity
+        .unwrap_or(config.execution.max_parallel_agents.unwrap_or(3) as usize)
+        .max(1);
+    let name = name
+        .map(str::to_string)
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .unwrap_or_else(|| "worker".to_string());
+    let _ = shutdown::install_handler(|| {});
+
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(async {
+        loop {
+            match serve(join, &name, capacity, token, &config).await {
+                Ok(Session::Ended) => {
+                    println!("{}", "Loop finished; waiting for the next one...".dimmed())
+                }
+                Ok(Session::Rejected(reason)) => {
+                    anyhow::bail!("Coordinator at {} rejected this worker: {}", join, reason)
+                }
+                Ok(Session::Interrupted) => return Ok(()),
+                Err(e) => println!("{}", format!("Not connected to {}: {:#}", join, e).yellow()),
+            }
+            tokio::select! {
+                _ = sleep(RECONNECT_DELAY) => {}
+                _ = shutdown::interrupted() => return Ok(()),
+            }
+        }
+    })
+}
+
+async fn serve(
+    join: &str,
+    name: &str,
+    capacity: usize,
+    token: Option<&str>,
+    config: &LoopConfig,
+) -> anyhow::Result<Session> {
+    let stream = TcpStream::connect(join)
+        .await
+        .with_context(|| format!("Failed to connect to {}", join))?;
+    let (read, mut write) = stream.into_split();
+
+    // Read on a separate task: a partially read line must survive heartbeat ticks
+    let (inbox_tx, mut inbox) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(read);
+        while let Ok(Some(message)) = recv_message::<_, CoordinatorMessage>(&mut reader).await {
+            if inbox_tx.send(message).is_err() {
+                break;
+            }
+        }
+    });
+
+    send_message(
+        &mut write,
+        &WorkerMessage::Register {
+            name: name.to_string(),
+            capacity,
+            version: PROTOCOL_VERSION,
+            token: token.map(str::to_string),
+        },
+    )
+    .await?;
+    match inbox.recv().await {
+        Some(CoordinatorMessage::Registered { .. }) => println!(
+            "{}",
+            format!("Joined {} as {} ({} slots)", join, name, capacity).green()
+        ),
+        Some(CoordinatorMessage::Rejected { reason }) => return Ok(Session::Rejected(reason)),
+        _ => anyhow::bail!("Coordinator closed the connection"),
+    }
+
+    let mut heartbeat = interval(HEARTBEAT_INTERVAL);
+    loop {
+        tokio::select! {
+            _ = heartbeat.tick() => send_message(&mut write, &WorkerMessage::Heartbeat).await?,
+            _ = shutdown::interrupted() => return Ok(Session::Interrupted),
+            message = inbox.recv() => match message {
+                Some(CoordinatorMessage::Run(batch)) => {
+                    let run = run_batch(&batch, config);
+                    tokio::pin!(run);
+                    let results = loop {
+                        tokio::select! {
+                            results = &mut run => break results,
+                            _ = heartbeat.tick() => {
+                                send_message(&mut write, &WorkerMessage::Heartbeat).await?
+                            }
+                        }
+                    };
+                    send_message(
+                        &mut write,
+                        &WorkerMessage::Results { batch_id: batch.batch_id, results },
+                    )
+                    .await?;
+                    if shutdown::is_interrupted() {
+                        return Ok(Session::Interrupted);
+                    }
+                }
+                Some(CoordinatorMessage::Shutdown) | None => return Ok(Session::Ended),
+                Some(_) => {}
+            },
+        }
+    }
+}
+
+/// Run a batch in this machine's worktree and land its commits.
+async fn run_batch(batch: &Batch, config: &LoopConfig) -> Vec<TaskOutcome> {
+    let identifiers: Vec<_> = batch.tasks.iter().map(|t| t.identifier.as_str()).collect();
+    println!(
+        "{}",
+        format!("\n{}: running {}", batch.issue_id, identifiers.join(", ")).blue()
+    );
+
+    let remote = config.pool.remote.as_str();
+    let results = match prepare_worktree(batch, config).await {
+        Ok((worktree, context_file)) => {
+            let mut results = execute(batch, &worktree, &context_file).await;
+            if results.iter().any(|r| r.success) {
+                if let Err(e) = land_commits(&worktree, remote, &batch.branch).await {
+                    for result in results.iter_mut().filter(|r| r.success) {
+                        result.success = false;
+                        result.error = Some(format!(
+                            "Failed to push commits to {}/{}: {:#}",
+                            remote, batch.branch, e
+                        ));
+                    }
+                }
+            }
+            results
+        }
+        Err(e) => {
+            let error = format!("Worker setup failed: {:#}", e);
+            batch
+                .tasks
+                .iter()
+                .map(|task| failed_result(task, error.clone()))
+                .collect()
+        }
+    };
+
+    let result_dir = get_results_directory_path(&batch.issue_id);
+    results
+        .iter()
+        .map(|result| {
+            if result.success {
+                println!("{}", format!("  ✓ {}", result.identifier).green());
+            } else {
+                println!(
+                    "{}",
+                    format!(
+                        "  ✗ {}: {}",
+                        result.identifier,
+                        result.error.as_deref().unwrap_or("failed")
+                    )
+                    .red()
+                );
+            }
+            let result_file = fs::read_to_string(result_file_for(&result_dir, &result.identifier));
+            TaskOutcome::from_result(result, result_file.ok())
+        })
+        .collect()
+}
+
+/// Check out the loop branch and write the coordinator's issue context into it.
+///
+/// Returns the worktree path and its `context.json`.
+async fn prepare_worktree(batch: &Batch, config: &LoopConfig) -> anyhow::Result<(PathBuf, String)> {
+    let remote = config.pool.remote.as_str();
+    let repo = std::env::current_dir().context("failed to get current directory")?;
+    fetch_branch(&repo, remote, &batch.branch).await?;
+
+    let worktree_config = WorktreeConfig {
+        worktree_path: config.execution.worktree_path.clone(),
+        base_branch: config.execution.base_branch.clone(),
+        runtime: batch.runtime,
+        checkout: config.execution.checkout.clone(),
+    };
+    let worktree = create_worktree(&batch.issue_id, &batch.branch, &worktree_config)
+        .await?
+        .path;
+    integrate_branch(&worktree, remote, &batch.branch).await?;
+
+    // Bootstrap can take minutes; run it off this task so heartbeats keep going
+    let setup_dir = worktree.clone();
+    let execution = batch.execution.clone();
+    tokio::task::spawn_blocking(move || -> anyhow::Result<()> {
+        if let Some(plan) = plan_caches(&execution.build_cache, &setup_dir) {
+            link_caches(&plan, &setup_dir)?;
+        }
+        run_bootstrap(&setup_dir, &execution.bootstrap, |command| {
+            println!(
+                "{}",
+                format!("Bootstrapping worktree: {}", command).dimmed()
+            )
+        })?;
+        Ok(())
+    })
+    .await
+    .context("Worktree setup task failed")??;
+
+    let mobius_dir = worktree.join(".mobius");
+    let issue_dir = mobius_dir.join("issues").join(&batch.issue_id);
+    write_issue_files(&issue_dir, &batch.issue_files)?;
+    let gitignore = mobius_dir.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(&gitignore, "state/\n")?;
+    }
+
+    let result_dir = get_results_directory_path(&batch.issue_id);
+    fs::create_dir_all(&result_dir)
+        .with_context(|| format!("Failed to create result directory {}", result_dir.display()))?;
+
+    let context_file = issue_dir.join("context.json");
+    Ok((worktree, context_file.to_string_lossy().to_string()))
+}
+
+async fn execute(batch: &Batch, worktree: &Path, context_file: &str) -> Vec<ExecutionResult> {
+    // The coordinator already sized the batch to this worker's capacity
+    let mut execution = batch.execution.clone();
+    execution.max_parallel_agents = Some(batch.tasks.len() as u32);
+
+    let result_dir = get_results_directory_path(&batch.issue_id);
+    let output_dir = crate::stream_json::agent_output_dir(&batch.issue_id);
+    let output_dir = fs::create_dir_all(&output_dir)
+        .is_ok()
+        .then_some(output_dir);
+    let worktree_path = worktree.display().to_string();
+    let context = ExecutionContext {
+        runtime: batch.runtime,
+        worktree_path: &worktree_path,
+        config: &execution,
+        context_file_path: Some(context_file),
+        model_override: batch.model_override.as_deref(),
+        thinking_level_override: batch.thinking_level_override.as_deref(),
+        output_dir: output_dir.as_deref(),
+        result_dir: Some(&result_dir),
+    };
+    ProcessExecutor::new(context, None)
+        .execute_parallel(&batch.tasks)
+        .await
+}
+
+/// Rebase onto whatever other machines pushed, then push, retrying on races.
+async fn land_commits(worktree: &Path, remote: &str, branch: &str) -> anyhow::Result<()> {
+    let mut attempt = 1;
+    loop {
+        integrate_branch(worktree, remote, branch).await?;
+        match publish_branch(worktree, remote, branch).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt >= PUSH_ATTEMPTS => return Err(e),
+            Err(_) => attempt += 1,
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! Structured completion protocol between agents and the executor.
+//!
+//! Before spawning an agent the executor exports `MOBIUS_RESULT_FILE`; the
+//! skill writes `{status, error, files, commit}` JSON there when it finishes.
+//! Executors check this file first and only fall back to scraping STATUS
+//! markers from agent output when it is absent (see `CompletionProtocol`).
+//! A [`CompletionWatcher`] wakes the executor as soon as either the result
+//! file or the agent's stream-json output changes, instead of on a timer.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+use crate::executor::{ExecutionResult, ExecutionStatus};
+use crate::types::enums::SkillOutputStatus;
+use crate::types::task_graph::SubTask;
+
+/// Environment variable naming the result file an agent should write
+pub const RESULT_FILE_ENV: &str = "MOBIUS_RESULT_FILE";
+
+/// Completion payload written by an agent to `$MOBIUS_RESULT_FILE`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentResult {
+    pub status: SkillOutputStatus,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub files: Vec<String>,
+    #[serde(default)]
+    pub commit: Option<String>,
+}
+
+/// Result file path for a task within `result_dir`.
+pub fn result_file_for(result_dir: &Path, task_identifier: &str) -> PathBuf {
+    result_dir.join(format!("{}.json", task_identifier))
+}
+
+/// Read and parse a result file. Returns `None` while the agent has not
+/// written it yet or if it is not valid protocol JSON.
+pub fn read_agent_result(path: &Path) -> Option<AgentResult> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Remove a stale result file left by a previous attempt.
+pub fn clear_agent_result(path: &Path) {
+    let _ = fs::remove_file(path);
+}
+
+/// Prefix `command` so the agent inherits `MOBIUS_RESULT_FILE`.
+pub fn with_result_file_env(command: &str, result_file: &Path) -> String {
+    format!(
+        "export {}=\"{}\" && {}",
+        RESULT_FILE_ENV,
+        result_file.display(),
+        command
+    )
+}
+
+/// Convert an agent-reported result into an `ExecutionResult`.
+pub fn agent_result_to_execution(
+    result: &AgentResult,
+    task: &SubTask,
+    start_time: Instant,
+    pane_id: Option<&str>,
+) -> ExecutionResult {
+    let (success, status, error) = match result.status {
+        SkillOutputStatus::SubtaskComplete
+        | SkillOutputStatus::AllComplete
+        | SkillOutputStatus::Pass => (true, ExecutionStatus::SubtaskComplete, None),
+        SkillOutputStatus::VerificationFailed
+        | SkillOutputStatus::NeedsWork
+        | SkillOutputStatus::Fail => (
+            false,
+            ExecutionStatus::VerificationFailed,
+            Some(
+                result
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "Verification failed".to_string()),
+            ),
+        ),
+        SkillOutputStatus::AllBlocked | SkillOutputStatus::NoSubtasks => (
+            false,
+            ExecutionStatus::Error,
+            Some(
+                result
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "No actionable sub-tasks available".to_string()),
+            ),
+        ),
+        SkillOutputStatus::SubtaskPartial => (
+            false,
+            ExecutionStatus::Error,
+            Some(
+                result
+                    .error
+                    .clone()
+                    .unwrap_or_else(|| "Agent reported partial progress".to_string()),
+            ),
+        ),
+    };
+
+    ExecutionResult {
+        task_id: task.id.clone(),
+        identifier: task.identifier.clone(),
+        success,
+        status,
+        token_usage: None,
+        duration_ms: start_time.elapsed().as_millis() as u64,
+        error,
+        pane_id: pane_id.map(str::to_string),
+        raw_output: None,
+        input_tokens: None,
+        output_tokens: None,
+    }
+}
+
+/// Filesystem watcher over the files an agent reports completion through.
+///
+/// Watches each file's parent directory (the files may not exist yet) and
+/// coalesces bursts of writes into a single wake-up.
+pub struct CompletionWatcher {
+    wake: Arc<Notify>,
+    _watcher: RecommendedWatcher,
+}
+
+impl CompletionWatcher {
+    /// Start watching `files`. Returns `None` when nothing is given or the
+    /// platform watcher cannot be created, in which case callers poll.
+    pub fn new(files: &[&Path]) -> Option<Self> {
+        if files.is_empty() {
+            return None;
+        }
+        let wake = Arc::new(Notify::new());
+        // Match on file names: some platforms report canonicalized paths
+        let targets: Vec<std::ffi::OsString> = files
+            .iter()
+            .filter_map(|f| f.file_name())
+            .map(|name| name.to_os_string())
+            .collect();
+        let signal = Arc::clone(&wake);
+        let mut watcher = RecommendedWatcher::new(
+            move |res: notify::Result<notify::Event>| {
+                let Ok(event) = res else { return };
+                if (event.kind.is_create() || event.kind.is_modify())
+                    && event
+                        .paths
+                        .iter()
+                        .filter_map(|p| p.file_name())
+                        .any(|name| targets.iter().any(|t| t == name))
+                {
+                    signal.notify_one();
+                }
+            },
+            Config::default(),
+        )
+        .ok()?;
+
+        let mut watched = Vec::new();
+        for dir in files.iter().filter_map(|f| f.parent()) {
+            if watched.contains(&dir) {
+                continue;
+            }
+            fs::create_dir_all(dir).ok()?;
+            watcher.watch(dir, RecursiveMode::NonRecursive).ok()?;
+            watched.push(dir);
+        }
+
+        Some(Self {
+            wake,
+            _watcher: watcher,
+        })
+    }
+
+    /// Wait until a watched file is created or written.
+    pub async fn changed(&self) {
+        self.wake.notified().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::{TaskStatus, TaskType};
+
+    fn make_task() -> SubTask {
+        SubTask {
+            id: "id-1".to_string(),
+            identifier: "MOB-2".to_string(),
+            title: "Task".to_string(),
+            status: TaskStatus::Ready,
+            blocked_by: vec![],
+            blocks: vec![],
+            git_branch_name: String::new(),
+            scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
+        }
+    }
+
+    #[test]
+    fn test_read_agent_result_full_payload() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = result_file_for(tmp.path(), "MOB-2");
+        fs::write(
+            &path,
+            r#"{"status":"SUBTASK_COMPLETE","files":["src/a.rs"],"commit":"abc1234"}"#,
+        )
+        .unwrap();
+
+        let result = read_agent_result(&path).unwrap();
+        assert_eq!(result.status, SkillOutputStatus::SubtaskComplete);
+        assert_eq!(result.files, vec!["src/a.rs"]);
+        assert_eq!(result.commit.as_deref(), Some("abc1234"));
+        assert!(result.error.is_none());
+    }
+
+    #[test]
+    fn test_read_agent_result_missing_or_invalid() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = result_file_for(tmp.path(), "MOB-2");
+        assert!(read_agent_result(&path).is_none());
+
+        fs::write(&path, r#"{"status":"DONE"}"#).unwrap();
+        assert!(read_agent_result(&path).is_none());
+
+        clear_agent_result(&path);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_with_result_file_env() {
+        let cmd = with_result_file_env("cd /repo && claude", Path::new("/tmp/r/MOB-2.json"));
+        assert_eq!(
+            cmd,
+            "export MOBIUS_RESULT_FILE=\"/tmp/r/MOB-2.json\" && cd /repo && claude"
+        );
+    }
+
+    #[test]
+    fn test_agent_result_to_execution_mapping() {
+        let task = make_task();
+        let start = Instant::now();
+
+        let complete = AgentResult {
+            status: SkillOutputStatus::SubtaskComplete,
+            error: None,
+            files: vec![],
+            commit: None,
+        };
+        let r = agent_result_to_execution(&complete, &task, start, Some("%3"));
+        assert!(r.success);
+        assert_eq!(r.status, ExecutionStatus::SubtaskComplete);
+        assert_eq!(r.pane_id.as_deref(), Some("%3"));
+
+        let failed = AgentResult {
+            status: SkillOutputStatus::VerificationFailed,
+            error: Some("tests failed".to_string()),
+            ..complete.clone()
+        };
+        let r = agent_result_to_execution(&failed, &task, start, None);
+        assert!(!r.success);
+        assert_eq!(r.status, ExecutionStatus::VerificationFailed);
+        assert_eq!(r.error.as_deref(), Some("tests failed"));
+
+        let blocked = AgentResult {
+            status: SkillOutputStatus::AllBlocked,
+            ..complete
+        };
+        let r = agent_result_to_execution(&blocked, &task, start, None);
+        assert_eq!(r.status, ExecutionStatus::Error);
+        assert_eq!(
+            r.error.as_deref(),
+            Some("No actionable sub-tasks available")
+        );
+    }
+}
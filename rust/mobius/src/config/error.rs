@@ -13,6 +13,11 @@ pub enum ConfigError {
     ValidationError(Vec<String>),
     /// Invalid path encountered
     InvalidPath(String),
+    /// `--profile`/`MOBIUS_PROFILE` names no entry under `profiles`
+    UnknownProfile {
+        name: String,
+        available: Vec<String>,
+    },
 }
 
 impl fmt::Display for ConfigError {
@@ -29,6 +34,14 @@ impl fmt::Display for ConfigError {
                 Ok(())
             }
             ConfigError::InvalidPath(msg) => write!(f, "Invalid path: {msg}"),
+            ConfigError::UnknownProfile { name, available } if available.is_empty() => {
+                write!(f, "Unknown profile '{name}': no profiles are configured")
+            }
+            ConfigError::UnknownProfile { name, available } => write!(
+                f,
+                "Unknown profile '{name}' (available: {})",
+                available.join(", ")
+            ),
         }
     }
 }
@@ -0,0 +1,324 @@
+//! Config layering.
+//!
+//! A project's effective config is built from, lowest precedence first: the
+//! built-in defaults, the global `~/.config/mobius/config.yaml`, the
+//! project's `mobius.config.yaml`, its gitignored `.mobius/config.local.yaml`,
+//! the active profile and finally `MOBIUS_*` environment variables. Files are
+//! merged key by key; lists and scalars from a later layer replace earlier
+//! ones wholesale.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+use super::error::ConfigError;
+use super::paths::get_global_config_dir;
+use crate::types::config::{ConfigProfile, LoopConfig};
+
+/// File name of a project config
+pub const PROJECT_CONFIG_FILE: &str = "mobius.config.yaml";
+
+/// Per-checkout overrides kept in the project's `.mobius/` directory
+pub const LOCAL_CONFIG_FILE: &str = "config.local.yaml";
+
+/// Profile keys that live under `execution` in the config file
+const PROFILE_EXECUTION_KEYS: &[&str] = &[
+    "model",
+    "max_parallel_agents",
+    "sandbox",
+    "container_name",
+    "container",
+];
+
+/// Where an effective config value came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "kind", content = "name")]
+pub enum ConfigSource {
+    Default,
+    Global(String),
+    Project(String),
+    Local(String),
+    Profile(String),
+    Env(String),
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::Global(path) => write!(f, "global {path}"),
+            ConfigSource::Project(path) => write!(f, "project {path}"),
+            ConfigSource::Local(path) => write!(f, "local {path}"),
+            ConfigSource::Profile(name) => write!(f, "profile {name}"),
+            ConfigSource::Env(var) => write!(f, "env {var}"),
+        }
+    }
+}
+
+/// One config key's effective value and the layer that set it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigOrigin {
+    /// Dotted path, e.g. `execution.model`
+    pub key: String,
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+/// A config layer's own settings
+pub type ConfigLayer = (ConfigSource, Value);
+
+/// Files merged for `config_path`, lowest precedence first.
+///
+/// A project config sits on top of the global config and under its local
+/// overrides; any other path (the global config itself, or an explicit file)
+/// is read on its own. Missing files are left out.
+pub fn config_layer_paths(config_path: &Path) -> Vec<(ConfigSource, PathBuf)> {
+    let label = |path: &Path| path.display().to_string();
+    let is_project = config_path
+        .file_name()
+        .is_some_and(|name| name == PROJECT_CONFIG_FILE);
+    let mut paths = Vec::new();
+    if is_project {
+        let global = get_global_config_dir().join("config.yaml");
+        paths.push((ConfigSource::Global(label(&global)), global));
+        paths.push((
+            ConfigSource::Project(label(config_path)),
+            config_path.to_path_buf(),
+        ));
+        if let Some(dir) = config_path.parent() {
+            let local = dir.join(".mobius").join(LOCAL_CONFIG_FILE);
+            paths.push((ConfigSource::Local(label(&local)), local));
+        }
+    } else if config_path.starts_with(get_global_config_dir()) {
+        paths.push((
+            ConfigSource::Global(label(config_path)),
+            config_path.to_path_buf(),
+        ));
+    } else {
+        paths.push((
+            ConfigSource::Project(label(config_path)),
+            config_path.to_path_buf(),
+        ));
+    }
+    paths.retain(|(_, path)| path.exists());
+    paths
+}
+
+/// Parse every file layer for `config_path`, lowest precedence first.
+pub fn read_layers(config_path: &Path) -> Result<Vec<ConfigLayer>, ConfigError> {
+    config_layer_paths(config_path)
+        .into_iter()
+        .map(|(source, path)| {
+            let content = fs::read_to_string(&path)?;
+            let value: Value = serde_yaml::from_str(&content)
+                .map_err(|e| ConfigError::ParseError(format!("{}: {e}", path.display())))?;
+            Ok((source, value))
+        })
+        .collect()
+}
+
+/// Merge layers into one value, later layers winning key by key.
+pub fn merge_layers(layers: &[ConfigLayer]) -> Value {
+    let mut merged = Value::Null;
+    for (_, value) in layers {
+        merge_value(&mut merged, value.clone());
+    }
+    merged
+}
+
+fn merge_value(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        // An empty file or a bare key adds nothing
+        (_, Value::Null) => {}
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Build a config from merged layers; nothing at all means the defaults.
+pub fn config_from_value(value: Value) -> Result<LoopConfig, ConfigError> {
+    if value.is_null() {
+        return Ok(LoopConfig::default());
+    }
+    Ok(serde_yaml::from_value(value)?)
+}
+
+/// The settings a profile overrides, shaped like the config file.
+pub fn profile_layer(profile: &ConfigProfile) -> Value {
+    let Ok(Value::Mapping(fields)) = serde_yaml::to_value(profile) else {
+        return Value::Null;
+    };
+    let mut top = Mapping::new();
+    let mut execution = Mapping::new();
+    for (key, value) in fields {
+        if key
+            .as_str()
+            .is_some_and(|k| PROFILE_EXECUTION_KEYS.contains(&k))
+        {
+            execution.insert(key, value);
+        } else {
+            top.insert(key, value);
+        }
+    }
+    if !execution.is_empty() {
+        top.insert(Value::from("execution"), Value::Mapping(execution));
+    }
+    Value::Mapping(top)
+}
+
+/// A value setting only the dotted `key`, e.g. for an environment override.
+pub fn key_layer(key: &str, value: Value) -> Value {
+    key.rsplit('.').fold(value, |inner, segment| {
+        let mut mapping = Mapping::new();
+        mapping.insert(Value::from(segment), inner);
+        Value::Mapping(mapping)
+    })
+}
+
+/// Every effective setting of `config`, with the last of `layers` that set it.
+pub fn config_origins(config: &LoopConfig, layers: &[ConfigLayer]) -> Vec<ConfigOrigin> {
+    let mut set_by: Vec<(String, &ConfigSource)> = Vec::new();
+    for (source, value) in layers {
+        let mut keys = Vec::new();
+        flatten("", value, &mut keys);
+        set_by.extend(keys.into_iter().map(|(key, _)| (key, source)));
+    }
+
+    let effective = serde_yaml::to_value(config).unwrap_or(Value::Null);
+    let mut leaves = Vec::new();
+    flatten("", &effective, &mut leaves);
+    leaves
+        .into_iter()
+        .map(|(key, value)| {
+            let source = set_by
+                .iter()
+                .rev()
+                .find(|(set, _)| covers(set, &key))
+                .map_or(ConfigSource::Default, |(_, source)| (*source).clone());
+            ConfigOrigin {
+                value: format_value(value),
+                key,
+                source,
+            }
+        })
+        .collect()
+}
+
+/// Whether setting `set` determines `key`: the key itself or one of its parents.
+fn covers(set: &str, key: &str) -> bool {
+    key == set
+        || key
+            .strip_prefix(set)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+fn flatten<'a>(prefix: &str, value: &'a Value, out: &mut Vec<(String, &'a Value)>) {
+    match value {
+        Value::Mapping(mapping) if !mapping.is_empty() => {
+            for (key, value) in mapping {
+                let key = match key {
+                    Value::String(s) => s.clone(),
+                    other => format_value(other),
+                };
+                let path = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&path, value, out);
+            }
+        }
+        // Unset options, and keys a file leaves empty
+        Value::Null => {}
+        _ => out.push((prefix.to_string(), value)),
+    }
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "~".to_string(),
+        other => serde_json::to_string(other).unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(text: &str) -> Value {
+        serde_yaml::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_merge_layers_and_origins() {
+        let global = ConfigSource::Global("g".to_string());
+        let project = ConfigSource::Project("p".to_string());
+        let local = ConfigSource::Local("l".to_string());
+        let env = ConfigSource::Env("MOBIUS_MODEL".to_string());
+        let layers = vec![
+            (
+                global.clone(),
+                yaml("backend: jira\nexecution:\n  model: opus\n  max_iterations: 20\n"),
+            ),
+            (
+                project.clone(),
+                yaml("backend: linear\nexecution:\n  disallowed_tools: [Bash]\n"),
+            ),
+            (local.clone(), yaml("execution:\n  max_iterations: 5\n")),
+            (
+                env.clone(),
+                key_layer("execution.model", Value::from("sonnet")),
+            ),
+        ];
+
+        let mut config = config_from_value(merge_layers(&layers[..3])).unwrap();
+        assert_eq!(config.backend, crate::types::enums::Backend::Linear);
+        assert_eq!(config.execution.model, "opus");
+        assert_eq!(config.execution.max_iterations, 5);
+        config.execution.model = "sonnet".to_string();
+
+        let origins = config_origins(&config, &layers);
+        let source_of = |key: &str| {
+            origins
+                .iter()
+                .find(|o| o.key == key)
+                .map(|o| (o.value.as_str(), o.source.clone()))
+                .unwrap()
+        };
+        assert_eq!(source_of("backend"), ("linear", project.clone()));
+        assert_eq!(source_of("execution.model"), ("sonnet", env));
+        assert_eq!(source_of("execution.max_iterations"), ("5", local));
+        assert_eq!(
+            source_of("execution.disallowed_tools"),
+            ("[\"Bash\"]", project)
+        );
+        assert_eq!(
+            source_of("execution.delay_seconds"),
+            ("3", ConfigSource::Default)
+        );
+    }
+
+    #[test]
+    fn test_profile_layer_nests_execution_keys() {
+        let profile: ConfigProfile =
+            serde_yaml::from_str("backend: github\nmodel: sonnet\nsandbox: false\n").unwrap();
+        assert_eq!(
+            profile_layer(&profile),
+            yaml("backend: github\nexecution:\n  model: sonnet\n  sandbox: false\n")
+        );
+    }
+}
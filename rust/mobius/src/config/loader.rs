@@ -2,14 +2,52 @@ use std::env;
 use std::fs;
 use std::path::Path;
 
-use crate::types::config::{JiraConfig, LoopConfig};
+use serde_yaml::Value;
+
+use crate::types::config::{GithubConfig, GitlabConfig, JiraConfig, LoopConfig};
 use crate::types::enums::{AgentRuntime, Backend, Model};
 
 use super::error::ConfigError;
-
-/// Read and parse config file, merging with defaults.
-/// If the file doesn't exist, returns defaults.
+use super::layers::{
+    config_from_value, config_origins, key_layer, merge_layers, profile_layer, read_layers,
+    ConfigOrigin, ConfigSource,
+};
+
+/// Environment variable naming the active config profile; `--profile` sets it
+pub const PROFILE_ENV: &str = "MOBIUS_PROFILE";
+
+/// Environment variables `read_config_with_env` honors, with the key each overrides
+pub const ENV_OVERRIDES: &[(&str, &str)] = &[
+    ("MOBIUS_RUNTIME", "runtime"),
+    ("MOBIUS_BACKEND", "backend"),
+    ("MOBIUS_DELAY_SECONDS", "execution.delay_seconds"),
+    ("MOBIUS_MAX_ITERATIONS", "execution.max_iterations"),
+    ("MOBIUS_MODEL", "execution.model"),
+    ("MOBIUS_SANDBOX_ENABLED", "execution.sandbox"),
+    ("MOBIUS_CONTAINER", "execution.container_name"),
+    ("MOBIUS_MAX_COST_USD", "loop.max_cost_usd"),
+];
+
+/// Read and parse config file, merging with defaults and applying the
+/// active profile. If the file doesn't exist, returns defaults.
+///
+/// For a project `mobius.config.yaml`, the global config and the project's
+/// `.mobius/config.local.yaml` are merged in as well; see `config::layers`.
 pub fn read_config(config_path: &str) -> Result<LoopConfig, ConfigError> {
+    let mut config = read_layered_config(config_path)?;
+    apply_profile(&mut config, active_profile().as_deref())?;
+    Ok(config)
+}
+
+/// Read the config files layered for `config_path`, without applying a profile.
+pub fn read_layered_config(config_path: &str) -> Result<LoopConfig, ConfigError> {
+    let layers = read_layers(Path::new(config_path))?;
+    config_from_value(merge_layers(&layers))
+}
+
+/// Read just the file at `config_path` as written, without other layers or
+/// a profile; for rewriting that one file.
+pub fn read_config_file(config_path: &str) -> Result<LoopConfig, ConfigError> {
     let path = Path::new(config_path);
 
     if !path.exists() {
@@ -22,16 +60,65 @@ pub fn read_config(config_path: &str) -> Result<LoopConfig, ConfigError> {
     Ok(parsed)
 }
 
+/// Name of the profile selected with `--profile` or `MOBIUS_PROFILE`, if any.
+pub fn active_profile() -> Option<String> {
+    env::var(PROFILE_ENV)
+        .ok()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+}
+
+/// Apply `profiles.<name>` on top of the rest of the config.
+pub fn apply_profile(config: &mut LoopConfig, name: Option<&str>) -> Result<(), ConfigError> {
+    let Some(name) = name else {
+        return Ok(());
+    };
+    let Some(profile) = config.profiles.get(name).cloned() else {
+        let mut available: Vec<String> = config.profiles.keys().cloned().collect();
+        available.sort();
+        return Err(ConfigError::UnknownProfile {
+            name: name.to_string(),
+            available,
+        });
+    };
+    profile.apply(config);
+    Ok(())
+}
+
+/// Effective config for `config_path`, each value with the layer it came from.
+pub fn read_config_origins(config_path: &str) -> Result<Vec<ConfigOrigin>, ConfigError> {
+    let mut layers = read_layers(Path::new(config_path))?;
+    let config = read_config_with_env(config_path)?;
+    if let Some(name) = active_profile() {
+        let files = config_from_value(merge_layers(&layers))?;
+        if let Some(profile) = files.profiles.get(&name) {
+            layers.push((ConfigSource::Profile(name), profile_layer(profile)));
+        }
+    }
+    for (var, key) in ENV_OVERRIDES {
+        if let Ok(value) = env::var(var) {
+            if !value.trim().is_empty() {
+                layers.push((
+                    ConfigSource::Env(var.to_string()),
+                    key_layer(key, Value::from(value)),
+                ));
+            }
+        }
+    }
+    Ok(config_origins(&config, &layers))
+}
+
 /// Read config with environment variable overrides applied.
 ///
-/// Supported environment variables:
-/// - MOBIUS_RUNTIME: Override runtime (claude, opencode)
+/// Environment variables win over the active profile. Supported:
+/// - MOBIUS_RUNTIME: Override runtime (claude, opencode, codex, aider)
 /// - MOBIUS_BACKEND: Override backend (linear, jira, local)
 /// - MOBIUS_DELAY_SECONDS: Override delay between iterations
 /// - MOBIUS_MAX_ITERATIONS: Override max iterations
 /// - MOBIUS_MODEL: Override model profile or runtime model ID
 /// - MOBIUS_SANDBOX_ENABLED: Override sandbox setting (true/false)
 /// - MOBIUS_CONTAINER: Override container name
+/// - MOBIUS_MAX_COST_USD: Override loop cost budget in dollars
 pub fn read_config_with_env(config_path: &str) -> Result<LoopConfig, ConfigError> {
     let mut config = read_config(config_path)?;
 
@@ -83,6 +170,12 @@ pub fn read_config_with_env(config_path: &str) -> Result<LoopConfig, ConfigError
         }
     }
 
+    if let Ok(max_cost) = env::var("MOBIUS_MAX_COST_USD") {
+        if let Ok(m) = max_cost.parse::<f64>() {
+            config.loop_settings.max_cost_usd = Some(m);
+        }
+    }
+
     Ok(config)
 }
 
@@ -158,11 +251,91 @@ pub fn validate_config(config: &LoopConfig) -> ValidationResult {
         }
     }
 
+    // Validate checkout options
+    let checkout = &config.execution.checkout;
+    if checkout.partial_clone && !checkout.mirror {
+        errors.push(
+            "execution.checkout.partial_clone requires execution.checkout.mirror".to_string(),
+        );
+    }
+    for (i, cache) in checkout.reflink_caches.iter().enumerate() {
+        let path = std::path::Path::new(cache);
+        if cache.trim().is_empty()
+            || path.is_absolute()
+            || path
+                .components()
+                .any(|c| c == std::path::Component::ParentDir)
+        {
+            errors.push(format!(
+                "execution.checkout.reflink_caches[{}] must be a path inside the repository",
+                i
+            ));
+        }
+    }
+
+    // Validate shared build cache names
+    let cache_names = crate::build_cache::cache_names();
+    for name in &config.execution.build_cache.exclude {
+        if !cache_names.contains(&name.as_str()) {
+            errors.push(format!(
+                "execution.build_cache.exclude: unknown cache '{}' (expected one of: {})",
+                name,
+                cache_names.join(", ")
+            ));
+        }
+    }
+
+    // Validate skill routing rules
+    for (i, rule) in config.execution.skill_rules.iter().enumerate() {
+        if rule.skill.trim().is_empty() {
+            errors.push(format!(
+                "execution.skill_rules[{}].skill cannot be empty",
+                i
+            ));
+        }
+        if let Some(ref pattern) = rule.title {
+            if let Err(e) = regex::Regex::new(pattern) {
+                errors.push(format!(
+                    "execution.skill_rules[{}].title is not a valid regex: {}",
+                    i, e
+                ));
+            }
+        }
+    }
+
+    // Validate dashboard color overrides
+    if let Some(ref tui) = config.execution.tui {
+        if let Err(e) = crate::tui::theme::Theme::from_config(tui) {
+            errors.push(format!("execution.tui.colors: {}", e));
+        }
+    }
+
+    // Validate cost budget
+    if let Some(max_cost) = config.loop_settings.max_cost_usd {
+        if max_cost <= 0.0 {
+            errors.push("loop.max_cost_usd must be greater than 0".to_string());
+        }
+    }
+
     // Validate Jira config when backend is jira
     if config.backend == Backend::Jira {
         validate_jira_config(config, &mut errors);
     }
 
+    // Validate GitHub config when backend is github (repository may also come from env)
+    if config.backend == Backend::Github {
+        if let Some(ref github) = config.github {
+            validate_github_fields(github, &mut errors);
+        }
+    }
+
+    // Validate GitLab config when backend is gitlab (project may also come from env)
+    if config.backend == Backend::Gitlab {
+        if let Some(ref gitlab) = config.gitlab {
+            validate_gitlab_fields(gitlab, &mut errors);
+        }
+    }
+
     // Validate verification config if present
     if let Some(ref verification) = config.execution.verification {
         validate_verification_config(verification, &mut errors);
@@ -209,6 +382,38 @@ fn validate_jira_fields(jira: &JiraConfig, errors: &mut Vec<String>) {
     }
 }
 
+fn validate_github_fields(github: &GithubConfig, errors: &mut Vec<String>) {
+    if github.owner.as_deref().is_some_and(|o| o.trim().is_empty()) {
+        errors.push("github.owner cannot be empty".to_string());
+    }
+
+    match &github.repo {
+        Some(repo) if repo.trim().is_empty() => {
+            errors.push("github.repo cannot be empty".to_string());
+        }
+        Some(repo) if repo.contains('/') => {
+            errors.push("github.repo must be a repository name without the owner".to_string());
+        }
+        _ => {}
+    }
+}
+
+fn validate_gitlab_fields(gitlab: &GitlabConfig, errors: &mut Vec<String>) {
+    if let Some(ref url) = gitlab.base_url {
+        if url.starts_with("http://") {
+            errors.push("gitlab.base_url must be an HTTPS URL".to_string());
+        }
+    }
+
+    if gitlab
+        .project
+        .as_deref()
+        .is_some_and(|p| p.trim().is_empty())
+    {
+        errors.push("gitlab.project cannot be empty".to_string());
+    }
+}
+
 fn validate_verification_config(
     verification: &crate::types::config::VerificationConfig,
     errors: &mut Vec<String>,
@@ -391,6 +596,49 @@ execution:
         env::remove_var("MOBIUS_SANDBOX_ENABLED");
     }
 
+    #[test]
+    fn test_read_config_applies_active_profile() {
+        let _lock = ENV_MUTEX.lock().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let config_path = tmp.path().join("config.yaml");
+        let yaml = r#"
+backend: linear
+execution:
+  model: opus
+  max_parallel_agents: 2
+profiles:
+  oss:
+    backend: github
+    model: sonnet
+    max_parallel_agents: 6
+    sandbox: false
+"#;
+        std::fs::write(&config_path, yaml).unwrap();
+        let path = config_path.to_str().unwrap();
+
+        env::set_var(PROFILE_ENV, "oss");
+        let config = read_config(path).unwrap();
+        assert_eq!(config.backend, Backend::Github);
+        assert_eq!(config.execution.model, "sonnet");
+        assert_eq!(config.execution.max_parallel_agents, Some(6));
+        assert!(!config.execution.sandbox);
+        assert_eq!(config.runtime, AgentRuntime::Claude);
+
+        // Environment overrides still win over the profile
+        env::set_var("MOBIUS_MODEL", "haiku");
+        assert_eq!(read_config_with_env(path).unwrap().execution.model, "haiku");
+        env::remove_var("MOBIUS_MODEL");
+
+        let file = read_config_file(path).unwrap();
+        assert_eq!(file.backend, Backend::Linear);
+        assert_eq!(file.execution.max_parallel_agents, Some(2));
+
+        env::set_var(PROFILE_ENV, "work");
+        let err = read_config(path).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown profile 'work' (available: oss)");
+        env::remove_var(PROFILE_ENV);
+    }
+
     #[test]
     fn test_write_config_creates_file() {
         let tmp = tempfile::tempdir().unwrap();
@@ -478,14 +726,75 @@ execution:
     }
 
     #[test]
-    fn test_validate_config_jira_requires_base_url() {
+    fn test_validate_config_checkout_options() {
         let mut config = LoopConfig::default();
-        config.backend = Backend::Jira;
-        config.jira = Some(JiraConfig {
-            base_url: None,
-            project_key: Some("PROJ".to_string()),
-            ..Default::default()
-        });
+        config.execution.checkout.partial_clone = true;
+        config.execution.checkout.reflink_caches =
+            vec!["target".to_string(), "../shared/node_modules".to_string()];
+        let result = validate_config(&config);
+        assert!(!result.valid);
+        assert!(result.errors.iter().any(|e| e.contains("requires")));
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.contains("reflink_caches[1]")));
+
+        config.execution.checkout.mirror = true;
+        config.execution.checkout.reflink_caches.pop();
+        assert!(validate_config(&config).valid);
+    }
+
+    #[test]
+    fn test_validate_config_build_cache_exclude() {
+        let mut config = LoopConfig::default();
+        config.execution.build_cache.exclude = vec!["node_modules".to_string()];
+        assert!(validate_config(&config).valid);
+
+        config.execution.build_cache.exclude = vec!["node-modules".to_string()];
+        let result = validate_config(&config);
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.contains("unknown cache 'node-modules'")));
+    }
+
+    #[test]
+    fn test_validate_config_invalid_skill_rule_regex() {
+        let mut config = LoopConfig::default();
+        config.execution.skill_rules = vec![crate::types::SkillRule {
+            label: None,
+            title: Some("(docs".to_string()),
+            identifier_prefix: None,
+            skill: "/docs".to_string(),
+        }];
+        let result = validate_config(&config);
+        assert!(!result.valid);
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.contains("skill_rules[0].title")));
+    }
+
+    #[test]
+    fn test_validate_config_non_positive_cost_budget() {
+        let mut config = LoopConfig::default();
+        config.loop_settings.max_cost_usd = Some(0.0);
+        let result = validate_config(&config);
+        assert!(!result.valid);
+        assert!(result.errors.iter().any(|e| e.contains("max_cost_usd")));
+    }
+
+    #[test]
+    fn test_validate_config_jira_requires_base_url() {
+        let config = LoopConfig {
+            backend: Backend::Jira,
+            jira: Some(JiraConfig {
+                base_url: None,
+                project_key: Some("PROJ".to_string()),
+                ..Default::default()
+            }),
+            ..LoopConfig::default()
+        };
         let result = validate_config(&config);
         assert!(!result.valid);
         assert!(result.errors.iter().any(|e| e.contains("base_url")));
@@ -493,13 +802,15 @@ execution:
 
     #[test]
     fn test_validate_config_jira_requires_https() {
-        let mut config = LoopConfig::default();
-        config.backend = Backend::Jira;
-        config.jira = Some(JiraConfig {
-            base_url: Some("http://example.com".to_string()),
-            project_key: Some("PROJ".to_string()),
-            ..Default::default()
-        });
+        let config = LoopConfig {
+            backend: Backend::Jira,
+            jira: Some(JiraConfig {
+                base_url: Some("http://example.com".to_string()),
+                project_key: Some("PROJ".to_string()),
+                ..Default::default()
+            }),
+            ..LoopConfig::default()
+        };
         let result = validate_config(&config);
         assert!(!result.valid);
         assert!(result.errors.iter().any(|e| e.contains("HTTPS")));
@@ -507,18 +818,52 @@ execution:
 
     #[test]
     fn test_validate_config_jira_project_key_uppercase() {
-        let mut config = LoopConfig::default();
-        config.backend = Backend::Jira;
-        config.jira = Some(JiraConfig {
-            base_url: Some("https://example.atlassian.net".to_string()),
-            project_key: Some("proj".to_string()),
-            ..Default::default()
-        });
+        let config = LoopConfig {
+            backend: Backend::Jira,
+            jira: Some(JiraConfig {
+                base_url: Some("https://example.atlassian.net".to_string()),
+                project_key: Some("proj".to_string()),
+                ..Default::default()
+            }),
+            ..LoopConfig::default()
+        };
         let result = validate_config(&config);
         assert!(!result.valid);
         assert!(result.errors.iter().any(|e| e.contains("uppercase")));
     }
 
+    #[test]
+    fn test_validate_config_github_repo_without_owner() {
+        let config = LoopConfig {
+            backend: Backend::Github,
+            github: Some(GithubConfig {
+                owner: Some("octo-org".to_string()),
+                repo: Some("octo-org/widgets".to_string()),
+                ..Default::default()
+            }),
+            ..LoopConfig::default()
+        };
+        let result = validate_config(&config);
+        assert!(!result.valid);
+        assert!(result.errors.iter().any(|e| e.contains("github.repo")));
+    }
+
+    #[test]
+    fn test_validate_config_gitlab_requires_https() {
+        let config = LoopConfig {
+            backend: Backend::Gitlab,
+            gitlab: Some(GitlabConfig {
+                base_url: Some("http://gitlab.example.com".to_string()),
+                project: Some("group/project".to_string()),
+                ..Default::default()
+            }),
+            ..LoopConfig::default()
+        };
+        let result = validate_config(&config);
+        assert!(!result.valid);
+        assert!(result.errors.iter().any(|e| e.contains("gitlab.base_url")));
+    }
+
     #[test]
     fn test_validate_config_verification_threshold() {
         let mut config = LoopConfig::default();
@@ -1,4 +1,5 @@
 pub mod error;
+pub mod layers;
 pub mod loader;
 pub mod paths;
 pub mod setup;
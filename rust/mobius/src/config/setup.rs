@@ -66,10 +66,14 @@ pub fn copy_shortcuts(source_path: &Path) -> Result<(), ConfigError> {
     Ok(())
 }
 
+/// The line shell rc files use to load the shortcuts script
+pub fn shortcuts_source_line() -> String {
+    format!("source \"{}\"", get_shortcuts_install_path().display())
+}
+
 /// Append source line for shortcuts to a shell rc file (idempotent)
 pub fn add_shortcuts_source_line(rc_file_path: &Path) -> Result<(), ConfigError> {
-    let install_path = get_shortcuts_install_path();
-    let source_line = format!("source \"{}\"", install_path.display());
+    let source_line = shortcuts_source_line();
 
     let content = if rc_file_path.exists() {
         fs::read_to_string(rc_file_path)?
@@ -5,8 +5,11 @@
 //!
 //! Ported from context-generator.ts (1,676 lines).
 
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io::{Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use std::thread;
@@ -14,22 +17,25 @@ use std::time::{Duration, Instant};
 
 use anyhow::{bail, Context as AnyhowContext, Result};
 use chrono::Utc;
+use fs2::FileExt;
 use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::debug_logger::debug_log;
 use crate::local_state::{
     self, get_project_mobius_path, read_parent_spec, read_subtasks, write_parent_spec,
     write_subtask_spec,
 };
 use crate::types::config::SubTaskVerifyCommand;
 use crate::types::context::{
-    BackendStatusEntry, ContextMetadata, IssueContext, PendingUpdate, PendingUpdateData,
-    PendingUpdatesQueue, RuntimeActiveTask, RuntimeCompletedTask, RuntimeState, SessionInfo,
-    SubTaskContext,
+    BackendStatusEntry, ContextMetadata, IssueContext, ParentIssueContext, PendingUpdate,
+    PendingUpdateData, PendingUpdatesQueue, RuntimeActiveTask, RuntimeCompletedTask,
+    RuntimeJournalEntry, RuntimeState, SessionInfo, SubTaskContext, TaskActionRequest,
+    TaskAttemptMetrics,
 };
-use crate::types::enums::{Backend, SessionStatus};
+use crate::types::enums::{Backend, DebugEventSource, DebugEventType, SessionStatus, TaskAction};
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -47,6 +53,21 @@ const DEBOUNCE_MS: u64 = 150;
 /// Maximum age for context freshness check (default 5 minutes).
 const DEFAULT_MAX_AGE_MS: u64 = 5 * 60 * 1000;
 
+/// Pause flag file name inside a parent's execution directory
+pub const PAUSE_FLAG_FILE: &str = "paused";
+
+/// Flag the loop keeps in its execution directory while it works offline
+pub const OFFLINE_FLAG_FILE: &str = "offline";
+
+/// Runtime state revision journal inside a parent's execution directory
+pub const RUNTIME_JOURNAL_FILE: &str = "runtime.journal.jsonl";
+
+/// Dashboard action queue inside a parent's execution directory
+pub const TASK_ACTIONS_FILE: &str = "actions.json";
+
+/// Size at which the runtime journal drops its older half
+const RUNTIME_JOURNAL_MAX_BYTES: u64 = 8 * 1024 * 1024;
+
 // ---------------------------------------------------------------------------
 // Path resolution
 // ---------------------------------------------------------------------------
@@ -81,6 +102,11 @@ pub fn get_pending_updates_path(parent_id: &str) -> PathBuf {
     get_context_path(parent_id).join("pending-updates.json")
 }
 
+/// Get the path to report.html for a parent issue.
+pub fn get_html_report_path(parent_id: &str) -> PathBuf {
+    get_context_path(parent_id).join("report.html")
+}
+
 /// Get the path to sync-log.json for a parent issue.
 pub fn get_sync_log_path(parent_id: &str) -> PathBuf {
     get_context_path(parent_id).join("sync-log.json")
@@ -91,6 +117,11 @@ pub fn get_full_context_path(parent_id: &str) -> PathBuf {
     get_context_path(parent_id).join("context.json")
 }
 
+/// Get the path to last-pull.json, the start time of the last completed pull.
+pub fn get_last_pull_path(parent_id: &str) -> PathBuf {
+    get_context_path(parent_id).join("last-pull.json")
+}
+
 /// Get the path to the execution directory for a parent issue.
 pub fn get_execution_path(parent_id: &str) -> PathBuf {
     get_context_path(parent_id).join("execution")
@@ -106,6 +137,56 @@ pub fn get_runtime_path(parent_id: &str) -> PathBuf {
     get_execution_path(parent_id).join("runtime.json")
 }
 
+/// Get the path to the journal of runtime.json revisions.
+pub fn get_runtime_journal_path(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join(RUNTIME_JOURNAL_FILE)
+}
+
+/// Get the directory agents write their completion result files to.
+pub fn get_results_directory_path(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join("results")
+}
+
+/// Get the path to checkpoint.json.
+pub fn get_checkpoint_path(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join("checkpoint.json")
+}
+
+/// Get the path to churn.json.
+pub fn get_churn_report_path(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join("churn.json")
+}
+
+/// Get the path to bisect.json.
+pub fn get_bisect_report_path(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join("bisect.json")
+}
+
+/// Get the path to integration.json.
+pub fn get_integration_report_path(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join("integration.json")
+}
+
+/// Get the path to the pause flag the loop checks between batches.
+pub fn get_pause_flag_path(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join(PAUSE_FLAG_FILE)
+}
+
+/// Get the path to the flag the loop sets while it works offline.
+pub fn get_offline_flag_path(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join(OFFLINE_FLAG_FILE)
+}
+
+/// Get the path to the queue of dashboard actions the loop applies between batches.
+pub fn get_task_actions_path(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join(TASK_ACTIONS_FILE)
+}
+
+/// Get the directory archived agent output is written to.
+pub fn get_outputs_directory_path(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join("outputs")
+}
+
 /// Get the path to the current-session pointer file.
 pub fn get_current_session_pointer_path() -> PathBuf {
     get_mobius_base_path().join("current-session")
@@ -165,6 +246,24 @@ pub fn extract_verify_commands(sub_tasks: &[SubTaskContext]) -> Vec<SubTaskVerif
         .collect()
 }
 
+/// Extract the agent timeout from a sub-task description.
+///
+/// Reads the first line of a `### Timeout` section: a number of minutes,
+/// optionally suffixed with `m`/`min`/`minutes` or `h`/`hours`.
+pub fn extract_timeout_minutes(description: &str) -> Option<u32> {
+    let pattern = Regex::new(
+        r"(?i)###\s+Timeout\s*\n\s*(\d+)\s*(h|hrs?|hours?|m|mins?|minutes?)?\s*(?:\n|$)",
+    )
+    .unwrap();
+    let caps = pattern.captures(description)?;
+    let value: u32 = caps.get(1)?.as_str().parse().ok()?;
+    let hours = caps
+        .get(2)
+        .is_some_and(|unit| unit.as_str().to_lowercase().starts_with('h'));
+    let minutes = if hours { value.checked_mul(60)? } else { value };
+    (minutes > 0).then_some(minutes)
+}
+
 // ---------------------------------------------------------------------------
 // Context I/O
 // ---------------------------------------------------------------------------
@@ -186,6 +285,28 @@ pub fn read_context(parent_identifier: &str) -> Option<IssueContext> {
     serde_json::from_str(&content).ok()
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LastPull {
+    pulled_at: chrono::DateTime<Utc>,
+}
+
+/// When the last completed `mobius pull` of `parent_identifier` started.
+pub fn read_last_pull(parent_identifier: &str) -> Option<chrono::DateTime<Utc>> {
+    let content = fs::read_to_string(get_last_pull_path(parent_identifier)).ok()?;
+    serde_json::from_str::<LastPull>(&content)
+        .ok()
+        .map(|l| l.pulled_at)
+}
+
+/// Record that a pull of `parent_identifier` started at `pulled_at` completed.
+pub fn write_last_pull(parent_identifier: &str, pulled_at: chrono::DateTime<Utc>) -> Result<()> {
+    atomic_write_json(
+        &get_last_pull_path(parent_identifier),
+        &LastPull { pulled_at },
+    )
+}
+
 /// Check if a context.json file exists for the given parent.
 pub fn context_exists(parent_identifier: &str) -> bool {
     get_full_context_path(parent_identifier).exists()
@@ -209,10 +330,63 @@ pub fn is_context_fresh(parent_identifier: &str, max_age_ms: Option<u64>) -> boo
     }
 }
 
+/// Content hashes of the parent and sub-task specs, keyed by identifier.
+///
+/// Statuses are left out: agents update them every batch, while the context
+/// only needs regenerating when titles, descriptions, dependencies or scoring
+/// change.
+pub fn spec_fingerprint(parent_identifier: &str) -> HashMap<String, u64> {
+    fn hash_json<T: Serialize>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        serde_json::to_string(value)
+            .unwrap_or_default()
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let mut fingerprint: HashMap<String, u64> = read_subtasks(parent_identifier)
+        .into_iter()
+        .map(|task| {
+            let key = if task.identifier.is_empty() {
+                task.id.clone()
+            } else {
+                task.identifier.clone()
+            };
+            let task = SubTaskContext {
+                status: String::new(),
+                ..task
+            };
+            (key, hash_json(&task))
+        })
+        .collect();
+    if let Some(parent) = read_parent_spec(parent_identifier) {
+        let parent = ParentIssueContext {
+            status: String::new(),
+            ..parent
+        };
+        fingerprint.insert(parent_identifier.to_string(), hash_json(&parent));
+    }
+    fingerprint
+}
+
+/// Identifiers whose spec was added, removed or edited between two
+/// [`spec_fingerprint`]s, sorted.
+pub fn changed_specs(before: &HashMap<String, u64>, after: &HashMap<String, u64>) -> Vec<String> {
+    let mut changed: Vec<String> = after
+        .iter()
+        .filter(|(id, hash)| before.get(*id) != Some(*hash))
+        .map(|(id, _)| id.clone())
+        .chain(before.keys().filter(|id| !after.contains_key(*id)).cloned())
+        .collect();
+    changed.sort();
+    changed
+}
+
 /// Clean up all context files for a parent issue.
 pub fn cleanup_context(parent_identifier: &str) {
     let ctx_path = get_context_path(parent_identifier);
     let _ = fs::remove_dir_all(&ctx_path);
+    let _ = local_state::forget_local_issue(parent_identifier);
 }
 
 /// Update a single task's context file.
@@ -269,9 +443,10 @@ pub fn generate_context(
 
     // Fetch parent context
     // For linear/jira backends, we'd call the respective API clients.
-    // For local or as fallback, read from local state.
+    // For local, offline, or as fallback, read from local state.
     let parent_context = match backend {
         Backend::Local => read_parent_spec(parent_identifier),
+        _ if crate::offline::is_offline() => read_parent_spec(parent_identifier),
         Backend::Linear => {
             let rt = tokio::runtime::Runtime::new().ok();
             let fetched = rt.and_then(|rt| {
@@ -312,6 +487,54 @@ pub fn generate_context(
             });
             fetched.or_else(|| read_parent_spec(parent_identifier))
         }
+        Backend::Github => {
+            let rt = tokio::runtime::Runtime::new().ok();
+            let fetched = rt.and_then(|rt| {
+                rt.block_on(async {
+                    let client = crate::github::GithubClient::new().ok()?;
+                    let issue = client.fetch_github_issue(parent_identifier).await.ok()?;
+                    let status = client
+                        .fetch_github_issue_status(parent_identifier)
+                        .await
+                        .unwrap_or_default();
+                    Some(crate::types::context::ParentIssueContext {
+                        id: issue.id,
+                        identifier: issue.identifier,
+                        title: issue.title,
+                        status,
+                        git_branch_name: issue.git_branch_name,
+                        description: String::new(),
+                        labels: vec![],
+                        url: String::new(),
+                    })
+                })
+            });
+            fetched.or_else(|| read_parent_spec(parent_identifier))
+        }
+        Backend::Gitlab => {
+            let rt = tokio::runtime::Runtime::new().ok();
+            let fetched = rt.and_then(|rt| {
+                rt.block_on(async {
+                    let client = crate::gitlab::GitlabClient::new().ok()?;
+                    let issue = client.fetch_gitlab_issue(parent_identifier).await.ok()?;
+                    let status = client
+                        .fetch_gitlab_issue_status(parent_identifier)
+                        .await
+                        .unwrap_or_default();
+                    Some(crate::types::context::ParentIssueContext {
+                        id: issue.id,
+                        identifier: issue.identifier,
+                        title: issue.title,
+                        status,
+                        git_branch_name: issue.git_branch_name,
+                        description: String::new(),
+                        labels: vec![],
+                        url: String::new(),
+                    })
+                })
+            });
+            fetched.or_else(|| read_parent_spec(parent_identifier))
+        }
     };
 
     // Read sub-tasks from local state
@@ -339,6 +562,7 @@ pub fn generate_context(
 
     // Build full context
     let context = IssueContext {
+        primer: load_repo_primer(project_path),
         parent: parent_context.clone(),
         sub_tasks: sub_tasks.clone(),
         metadata,
@@ -392,6 +616,29 @@ pub fn generate_context(
     Ok(Some(context))
 }
 
+/// Load the cached repository primer when `execution.repo_primer` is enabled.
+///
+/// Primer failures are logged and never block context generation.
+fn load_repo_primer(project_path: Option<&str>) -> Option<String> {
+    let paths = crate::config::paths::resolve_paths();
+    let config = crate::config::loader::read_config(&paths.config_path).unwrap_or_default();
+    if !config.execution.repo_primer {
+        return None;
+    }
+
+    let root = match project_path {
+        Some(path) => PathBuf::from(path),
+        None => get_project_mobius_path().parent()?.to_path_buf(),
+    };
+    match crate::primer::load_or_generate_primer(&root, false) {
+        Ok(primer) => Some(primer.content),
+        Err(e) => {
+            tracing::warn!("Failed to load repository primer: {}", e);
+            None
+        }
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Pending updates management
 // ---------------------------------------------------------------------------
@@ -432,6 +679,13 @@ pub enum PendingUpdateInput {
         issue_id: String,
         identifier: String,
         description: String,
+        /// Description as last pulled, to tell a remote edit from our own
+        #[serde(
+            rename = "oldDescription",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        old_description: Option<String>,
     },
     #[serde(rename = "add_label")]
     AddLabel {
@@ -447,6 +701,26 @@ pub enum PendingUpdateInput {
         identifier: String,
         label: String,
     },
+    #[serde(rename = "add_relation")]
+    AddRelation {
+        #[serde(rename = "issueId")]
+        issue_id: String,
+        identifier: String,
+        #[serde(rename = "blockerId")]
+        blocker_id: String,
+        #[serde(rename = "blockerIdentifier")]
+        blocker_identifier: String,
+    },
+    #[serde(rename = "remove_relation")]
+    RemoveRelation {
+        #[serde(rename = "issueId")]
+        issue_id: String,
+        identifier: String,
+        #[serde(rename = "blockerId")]
+        blocker_id: String,
+        #[serde(rename = "blockerIdentifier")]
+        blocker_identifier: String,
+    },
 }
 
 /// Check if an existing pending update is a duplicate of the incoming one.
@@ -534,6 +808,31 @@ fn is_duplicate_update(existing: &PendingUpdate, incoming: &PendingUpdateInput)
             },
         ) => e_id == i_id && e_label == i_label,
 
+        (
+            PendingUpdateData::AddRelation {
+                issue_id: e_id,
+                blocker_id: e_blocker,
+                ..
+            },
+            PendingUpdateInput::AddRelation {
+                issue_id: i_id,
+                blocker_id: i_blocker,
+                ..
+            },
+        )
+        | (
+            PendingUpdateData::RemoveRelation {
+                issue_id: e_id,
+                blocker_id: e_blocker,
+                ..
+            },
+            PendingUpdateInput::RemoveRelation {
+                issue_id: i_id,
+                blocker_id: i_blocker,
+                ..
+            },
+        ) => e_id == i_id && e_blocker == i_blocker,
+
         _ => false,
     }
 }
@@ -576,10 +875,12 @@ fn input_to_data(input: &PendingUpdateInput) -> PendingUpdateData {
             issue_id,
             identifier,
             description,
+            old_description,
         } => PendingUpdateData::UpdateDescription {
             issue_id: issue_id.clone(),
             identifier: identifier.clone(),
             description: description.clone(),
+            old_description: old_description.clone(),
         },
         PendingUpdateInput::AddLabel {
             issue_id,
@@ -599,6 +900,28 @@ fn input_to_data(input: &PendingUpdateInput) -> PendingUpdateData {
             identifier: identifier.clone(),
             label: label.clone(),
         },
+        PendingUpdateInput::AddRelation {
+            issue_id,
+            identifier,
+            blocker_id,
+            blocker_identifier,
+        } => PendingUpdateData::AddRelation {
+            issue_id: issue_id.clone(),
+            identifier: identifier.clone(),
+            blocker_id: blocker_id.clone(),
+            blocker_identifier: blocker_identifier.clone(),
+        },
+        PendingUpdateInput::RemoveRelation {
+            issue_id,
+            identifier,
+            blocker_id,
+            blocker_identifier,
+        } => PendingUpdateData::RemoveRelation {
+            issue_id: issue_id.clone(),
+            identifier: identifier.clone(),
+            blocker_id: blocker_id.clone(),
+            blocker_identifier: blocker_identifier.clone(),
+        },
     }
 }
 
@@ -630,30 +953,48 @@ pub fn write_pending_updates(parent_identifier: &str, queue: &PendingUpdatesQueu
 /// Only unsynced updates (no `synced_at` and no `error`) block duplicates.
 /// Once an update is synced or errored, new equivalent updates are allowed.
 pub fn queue_pending_update(parent_identifier: &str, update: &PendingUpdateInput) -> Result<()> {
-    ensure_context_directories(parent_identifier)?;
-    let mut queue = read_pending_updates(parent_identifier);
-
-    // Check for duplicates among unsynced, non-errored updates
-    let is_dup = queue.updates.iter().any(|existing| {
-        existing.synced_at.is_none()
-            && existing.error.is_none()
-            && is_duplicate_update(existing, update)
-    });
+    with_pending_updates_sync(parent_identifier, |mut queue| {
+        // Check for duplicates among unsynced, non-errored updates
+        let is_dup = queue.updates.iter().any(|existing| {
+            existing.synced_at.is_none()
+                && existing.error.is_none()
+                && is_duplicate_update(existing, update)
+        });
 
-    if is_dup {
-        return Ok(());
-    }
+        if !is_dup {
+            queue.updates.push(PendingUpdate {
+                id: Uuid::new_v4().to_string(),
+                created_at: Utc::now().to_rfc3339(),
+                synced_at: None,
+                error: None,
+                conflict: None,
+                data: input_to_data(update),
+            });
+        }
+        queue
+    })
+    .map(|_| ())
+}
 
-    let new_update = PendingUpdate {
-        id: Uuid::new_v4().to_string(),
-        created_at: Utc::now().to_rfc3339(),
-        synced_at: None,
-        error: None,
-        data: input_to_data(update),
-    };
+/// Atomically read-modify-write the pending updates queue with file locking.
+///
+/// Same locking as `with_runtime_state_sync`; agents queuing updates while
+/// `mobius push` marks others synced would otherwise drop entries.
+pub fn with_pending_updates_sync<F>(
+    parent_identifier: &str,
+    mutate: F,
+) -> Result<PendingUpdatesQueue>
+where
+    F: FnOnce(PendingUpdatesQueue) -> PendingUpdatesQueue,
+{
+    ensure_context_directories(parent_identifier)?;
+    let lock_path = get_pending_updates_path(parent_identifier).with_extension("json.lock");
 
-    queue.updates.push(new_update);
-    write_pending_updates(parent_identifier, &queue)
+    with_file_lock(&lock_path, "pending updates", || {
+        let queue = mutate(read_pending_updates(parent_identifier));
+        write_pending_updates(parent_identifier, &queue)?;
+        Ok(queue)
+    })
 }
 
 /// Get the count of unsynced pending updates.
@@ -735,6 +1076,46 @@ pub fn delete_session(parent_id: &str) {
     clear_current_session_pointer(parent_id);
 }
 
+/// Pause or unpause a loop by creating or removing its pause flag.
+///
+/// The flag holds the time it was set. Unpausing a loop that isn't paused is
+/// a no-op.
+pub fn set_paused(parent_id: &str, paused: bool) -> Result<()> {
+    let path = get_pause_flag_path(parent_id);
+    if paused {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, Utc::now().to_rfc3339())
+            .with_context(|| format!("Failed to write pause flag: {}", path.display()))
+    } else {
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove pause flag: {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Record whether a loop is working offline, for the dashboard's banner.
+pub fn set_offline_flag(parent_id: &str, offline: bool) {
+    let path = get_offline_flag_path(parent_id);
+    if offline {
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(&path, Utc::now().to_rfc3339());
+    } else {
+        let _ = fs::remove_file(&path);
+    }
+}
+
+/// Whether `mobius pause` has been run for a loop (and not undone).
+pub fn is_paused(parent_id: &str) -> bool {
+    get_pause_flag_path(parent_id).exists()
+}
+
 /// Set the current-session pointer to a parent ID.
 pub fn set_current_session_pointer(parent_id: &str) -> Result<()> {
     local_state::ensure_project_mobius_dir()?;
@@ -786,6 +1167,49 @@ fn get_current_session_parent_id_raw() -> Option<String> {
     }
 }
 
+/// Describe what is wrong with the current-session pointer, if anything.
+///
+/// The pointer is broken when it is empty or names a parent without a session.
+pub fn diagnose_current_session_pointer() -> Option<String> {
+    let content = fs::read_to_string(get_current_session_pointer_path()).ok()?;
+    let parent_id = content.trim();
+    if parent_id.is_empty() {
+        Some("current-session is empty".to_string())
+    } else if read_session(parent_id).is_none() {
+        Some(format!(
+            "current-session points to {}, which has no session",
+            parent_id
+        ))
+    } else {
+        None
+    }
+}
+
+/// Point the current-session pointer at the most recently started active
+/// session, or remove it when there is none. Returns the new target.
+pub fn repair_current_session_pointer() -> Result<Option<String>> {
+    let latest = local_state::list_local_issue_ids()
+        .iter()
+        .filter_map(|id| read_session(id))
+        .filter(|session| session.status == SessionStatus::Active)
+        .max_by(|a, b| a.started_at.cmp(&b.started_at));
+    match latest {
+        Some(session) => {
+            set_current_session_pointer(&session.parent_id)?;
+            Ok(Some(session.parent_id))
+        }
+        None => {
+            let path = get_current_session_pointer_path();
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+                }
+                _ => Ok(None),
+            }
+        }
+    }
+}
+
 /// Resolve task ID from provided ID or current session.
 pub fn resolve_task_id(provided_id: Option<&str>) -> Option<String> {
     if let Some(id) = provided_id {
@@ -819,46 +1243,176 @@ pub fn read_runtime_state(parent_id: &str) -> Option<RuntimeState> {
     serde_json::from_str(&content).ok()
 }
 
-/// Write runtime state to disk.
+/// Write runtime state to disk, journaling it when its content changed.
 pub fn write_runtime_state(state: &RuntimeState) -> Result<()> {
     let path = get_runtime_path(&state.parent_id);
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
-    atomic_write_json(&path, state)
+    let previous = read_runtime_state(&state.parent_id);
+    atomic_write_json(&path, state)?;
+    if previous.map_or(true, |prev| runtime_content_changed(&prev, state)) {
+        // The journal is a diagnostic aid; never fail a state write over it
+        let _ = append_runtime_journal(&get_runtime_journal_path(&state.parent_id), state);
+    }
+    Ok(())
+}
+
+/// Whether two states differ in anything besides `updatedAt`.
+fn runtime_content_changed(before: &RuntimeState, after: &RuntimeState) -> bool {
+    let strip = |state: &RuntimeState| {
+        let mut value = serde_json::to_value(state).unwrap_or_default();
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("updatedAt");
+        }
+        value
+    };
+    strip(before) != strip(after)
+}
+
+/// Append `state` to the journal, dropping the older half once it is full.
+fn append_runtime_journal(path: &Path, state: &RuntimeState) -> Result<()> {
+    let entry = RuntimeJournalEntry {
+        recorded_at: Utc::now().to_rfc3339(),
+        state: state.clone(),
+    };
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+
+    if file.metadata()?.len() > RUNTIME_JOURNAL_MAX_BYTES {
+        let content = fs::read_to_string(path)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let kept = lines[lines.len() / 2..].join("\n");
+        fs::write(path, format!("{}\n", kept))?;
+    }
+    Ok(())
+}
+
+/// Read every revision recorded in a runtime journal, oldest first.
+///
+/// Lines that fail to parse (e.g. a torn final write) are skipped.
+pub fn read_runtime_journal(path: &Path) -> Vec<RuntimeJournalEntry> {
+    fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 /// Atomically read-modify-write runtime state with file locking.
 ///
-/// Acquires a `.lock` file (5s timeout, 10ms retry interval), reads the current
-/// state, applies the mutation, writes the result, and releases the lock.
+/// Holds an advisory lock on a `.lock` file (5s timeout, 10ms retry interval)
+/// while it reads the current state, applies the mutation, and writes the
+/// result.
 pub fn with_runtime_state_sync<F>(parent_id: &str, mutate: F) -> Result<RuntimeState>
 where
     F: FnOnce(Option<RuntimeState>) -> RuntimeState,
 {
     ensure_context_directories(parent_id)?;
-    let lock_path = get_runtime_path(parent_id).with_extension("json.lock");
-    let start = Instant::now();
 
-    loop {
-        if try_acquire_lock(&lock_path) {
-            let current_state = read_runtime_state(parent_id);
-            let new_state = mutate(current_state);
-            let write_result = write_runtime_state(&new_state);
-            release_lock(&lock_path);
-            write_result?;
-            return Ok(new_state);
+    with_file_lock(&runtime_lock_path(parent_id), "runtime state", || {
+        let current_state = read_runtime_state(parent_id);
+        let new_state = mutate(current_state);
+        write_runtime_state(&new_state)?;
+        Ok(new_state)
+    })
+}
+
+fn runtime_lock_path(parent_id: &str) -> PathBuf {
+    get_runtime_path(parent_id).with_extension("json.lock")
+}
+
+/// Queue a dashboard `action` on `task` for the loop and apply it to the
+/// runtime state right away, so dashboards reflect it before the loop's next
+/// batch picks it up.
+///
+/// Both files are written under the runtime state lock. A newer request for
+/// the same task replaces an older one.
+pub fn queue_task_action(parent_id: &str, task: &str, action: TaskAction) -> Result<()> {
+    ensure_context_directories(parent_id)?;
+
+    with_file_lock(&runtime_lock_path(parent_id), "runtime state", || {
+        let path = get_task_actions_path(parent_id);
+        let mut queue = read_task_actions(&path);
+        queue.retain(|r| r.task != task);
+        queue.push(TaskActionRequest {
+            task: task.to_string(),
+            action,
+            requested_at: Utc::now().to_rfc3339(),
+        });
+        atomic_write_json(&path, &queue)?;
+
+        if let Some(state) = read_runtime_state(parent_id) {
+            write_runtime_state(&apply_task_action(&state, task, action))?;
         }
+        Ok(())
+    })
+}
 
-        if start.elapsed() > Duration::from_millis(LOCK_TIMEOUT_MS) {
-            bail!(
-                "Timeout acquiring runtime state lock after {}ms",
-                LOCK_TIMEOUT_MS
-            );
+/// Remove and return the queued dashboard actions, oldest first.
+pub fn take_task_actions(parent_id: &str) -> Result<Vec<TaskActionRequest>> {
+    let path = get_task_actions_path(parent_id);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    with_file_lock(&runtime_lock_path(parent_id), "runtime state", || {
+        let queue = read_task_actions(&path);
+        match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+            }
+            _ => Ok(queue),
         }
+    })
+}
 
-        thread::sleep(Duration::from_millis(LOCK_RETRY_INTERVAL_MS));
+fn read_task_actions(path: &Path) -> Vec<TaskActionRequest> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Apply a dashboard action to runtime state.
+///
+/// Either action drops the task from the failed list. A retried task also
+/// leaves the completed list (undoing an earlier skip); a skipped one is
+/// recorded as completed with no duration or usage.
+pub fn apply_task_action(state: &RuntimeState, task: &str, action: TaskAction) -> RuntimeState {
+    let mut new_state = state.clone();
+    new_state
+        .failed_tasks
+        .retain(|entry| get_completed_task_id(entry) != task);
+    let completed = new_state
+        .completed_tasks
+        .iter()
+        .any(|entry| get_completed_task_id(entry) == task);
+    if action == TaskAction::Retry {
+        new_state
+            .completed_tasks
+            .retain(|entry| get_completed_task_id(entry) != task);
+    } else if !completed {
+        let skipped = RuntimeCompletedTask {
+            id: task.to_string(),
+            completed_at: Utc::now().to_rfc3339(),
+            duration: 0,
+            input_tokens: None,
+            output_tokens: None,
+            model: None,
+        };
+        new_state
+            .completed_tasks
+            .push(serde_json::to_value(skipped).unwrap_or_default());
     }
+    new_state.updated_at = Utc::now().to_rfc3339();
+    new_state
 }
 
 /// Initialize runtime state for a new execution session.
@@ -868,6 +1422,8 @@ pub fn initialize_runtime_state(
     loop_pid: Option<u32>,
     total_tasks: Option<u32>,
 ) -> Result<RuntimeState> {
+    // Each run starts its own journal
+    let _ = fs::remove_file(get_runtime_journal_path(parent_id));
     with_runtime_state_sync(parent_id, |_| RuntimeState {
         parent_id: parent_id.to_string(),
         parent_title: parent_title.to_string(),
@@ -881,6 +1437,9 @@ pub fn initialize_runtime_state(
         backend_statuses: None,
         total_input_tokens: None,
         total_output_tokens: None,
+        task_costs_usd: HashMap::new(),
+        total_cost_usd: None,
+        cancelled_tasks: vec![],
     })
 }
 
@@ -894,18 +1453,39 @@ pub fn add_runtime_active_task(state: &RuntimeState, task: RuntimeActiveTask) ->
     new_state
 }
 
-/// Mark a task as completed in runtime state, preserving token data from the active task.
-pub fn complete_runtime_task(state: &RuntimeState, task_id: &str) -> RuntimeState {
+/// Mark a task as completed in runtime state.
+///
+/// `metrics` from the finished attempt take precedence; without them the
+/// duration is measured from the active task's start and its recorded
+/// tokens and model are kept.
+pub fn complete_runtime_task(
+    state: &RuntimeState,
+    task_id: &str,
+    metrics: Option<&TaskAttemptMetrics>,
+) -> RuntimeState {
     let mut new_state = state.clone();
     // Find and remove from active tasks
     if let Some(pos) = new_state.active_tasks.iter().position(|t| t.id == task_id) {
         let active = new_state.active_tasks.remove(pos);
+        let now = Utc::now();
+        let elapsed_ms = || {
+            chrono::DateTime::parse_from_rfc3339(&active.started_at)
+                .map(|started| {
+                    (now - started.with_timezone(&Utc))
+                        .num_milliseconds()
+                        .max(0) as u64
+                })
+                .unwrap_or(0)
+        };
         let completed = RuntimeCompletedTask {
+            completed_at: now.to_rfc3339(),
+            duration: metrics.map_or_else(elapsed_ms, |m| m.duration_ms),
+            input_tokens: metrics.and_then(|m| m.input_tokens).or(active.input_tokens),
+            output_tokens: metrics
+                .and_then(|m| m.output_tokens)
+                .or(active.output_tokens),
+            model: metrics.and_then(|m| m.model.clone()).or(active.model),
             id: active.id,
-            completed_at: Utc::now().to_rfc3339(),
-            duration: 0, // Approximate; can be calculated from started_at
-            input_tokens: active.input_tokens,
-            output_tokens: active.output_tokens,
         };
         new_state
             .completed_tasks
@@ -928,6 +1508,24 @@ pub fn fail_runtime_task(state: &RuntimeState, task_id: &str) -> RuntimeState {
     new_state
 }
 
+/// Record `tasks` as cancelled and drop them from the active list.
+///
+/// Takes the tasks explicitly because a stopping loop clears its active list
+/// before `mobius cancel` gets to update the state.
+pub fn cancel_runtime_tasks(state: &RuntimeState, tasks: &[RuntimeActiveTask]) -> RuntimeState {
+    let mut new_state = state.clone();
+    new_state
+        .active_tasks
+        .retain(|active| !tasks.iter().any(|t| t.id == active.id));
+    new_state.cancelled_tasks.extend(
+        tasks
+            .iter()
+            .map(|t| serde_json::to_value(t).unwrap_or_default()),
+    );
+    new_state.updated_at = Utc::now().to_rfc3339();
+    new_state
+}
+
 /// Remove an active task from runtime state without marking it completed or failed.
 pub fn remove_runtime_active_task(state: &RuntimeState, task_id: &str) -> RuntimeState {
     let mut new_state = state.clone();
@@ -970,6 +1568,19 @@ pub fn update_runtime_task_tokens(
     new_state
 }
 
+/// Add the cost of one agent attempt to a task's running total and the
+/// parent-level total.
+pub fn add_runtime_task_cost(state: &RuntimeState, task_id: &str, cost_usd: f64) -> RuntimeState {
+    let mut new_state = state.clone();
+    *new_state
+        .task_costs_usd
+        .entry(task_id.to_string())
+        .or_insert(0.0) += cost_usd;
+    new_state.total_cost_usd = Some(new_state.task_costs_usd.values().sum());
+    new_state.updated_at = Utc::now().to_rfc3339();
+    new_state
+}
+
 /// Recalculate total token usage from all active and completed tasks.
 pub fn recalculate_total_tokens(state: &RuntimeState) -> RuntimeState {
     let mut new_state = state.clone();
@@ -1050,6 +1661,9 @@ pub fn clear_all_runtime_active_tasks(parent_id: &str) -> Option<RuntimeState> {
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         });
         s.active_tasks.clear();
         s.updated_at = Utc::now().to_rfc3339();
@@ -1080,6 +1694,9 @@ pub fn update_backend_status(parent_id: &str, task_identifier: &str, status: &st
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         });
         let statuses = s.backend_statuses.get_or_insert_with(HashMap::new);
         statuses.insert(
@@ -1104,6 +1721,7 @@ pub fn normalize_completed_task(entry: &serde_json::Value) -> RuntimeCompletedTa
             duration: 0,
             input_tokens: None,
             output_tokens: None,
+            model: None,
         }
     } else {
         serde_json::from_value(entry.clone()).unwrap_or(RuntimeCompletedTask {
@@ -1112,6 +1730,7 @@ pub fn normalize_completed_task(entry: &serde_json::Value) -> RuntimeCompletedTa
             duration: 0,
             input_tokens: None,
             output_tokens: None,
+            model: None,
         })
     }
 }
@@ -1152,6 +1771,47 @@ pub fn filter_running_tasks(active_tasks: &[RuntimeActiveTask]) -> Vec<RuntimeAc
         .collect()
 }
 
+/// Active tasks left in runtime state by a loop that is no longer running.
+///
+/// Tasks on pool workers count as dead with their loop; local tasks count as
+/// dead once their process has exited. A running loop's state is left alone.
+fn dead_runtime_tasks(state: &RuntimeState) -> Vec<String> {
+    if state
+        .loop_pid
+        .is_some_and(|pid| pid != 0 && is_process_running(pid))
+    {
+        return Vec::new();
+    }
+    state
+        .active_tasks
+        .iter()
+        .filter(|task| task.worker.is_some() || task.pid == 0 || !is_process_running(task.pid))
+        .map(|task| task.id.clone())
+        .collect()
+}
+
+/// Active tasks of `parent_id` whose agents are gone; see `prune_dead_runtime_tasks`.
+pub fn find_dead_runtime_tasks(parent_id: &str) -> Vec<String> {
+    read_runtime_state(parent_id)
+        .map(|state| dead_runtime_tasks(&state))
+        .unwrap_or_default()
+}
+
+/// Remove dead active tasks from `parent_id`'s runtime state, returning their IDs.
+pub fn prune_dead_runtime_tasks(parent_id: &str) -> Result<Vec<String>> {
+    with_file_lock(&runtime_lock_path(parent_id), "runtime state", || {
+        let Some(mut state) = read_runtime_state(parent_id) else {
+            return Ok(Vec::new());
+        };
+        let dead = dead_runtime_tasks(&state);
+        if !dead.is_empty() {
+            state.active_tasks.retain(|task| !dead.contains(&task.id));
+            write_runtime_state(&state)?;
+        }
+        Ok(dead)
+    })
+}
+
 /// Progress summary for display.
 #[derive(Debug, Clone)]
 pub struct ProgressSummary {
@@ -1425,60 +2085,196 @@ fn backend_statuses_equal(
 // Lock helpers
 // ---------------------------------------------------------------------------
 
-/// Try to acquire a file lock.
+/// Run `f` while holding the advisory lock at `lock_path`.
 ///
-/// Uses atomic file creation. Returns true if lock was acquired.
-fn try_acquire_lock(lock_path: &Path) -> bool {
-    if lock_path.exists() {
-        if is_lock_stale(lock_path) {
-            let _ = fs::remove_file(lock_path);
-        } else {
-            return false;
+/// Retries every `LOCK_RETRY_INTERVAL_MS` and gives up after `LOCK_TIMEOUT_MS`.
+/// `label` names the protected resource in the timeout error.
+pub(crate) fn with_file_lock<T, F>(lock_path: &Path, label: &str, f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T>,
+{
+    let start = Instant::now();
+    let mut contended_by = None;
+
+    loop {
+        if let Some(_lock) = try_acquire_lock(lock_path) {
+            if let Some(holder) = contended_by {
+                log_lock_contention(lock_path, label, holder, start, "acquired");
+            }
+            return f();
+        }
+        if contended_by.is_none() {
+            contended_by = Some(read_lock_holder(lock_path));
+        }
+
+        if start.elapsed() > Duration::from_millis(LOCK_TIMEOUT_MS) {
+            log_lock_contention(lock_path, label, contended_by.flatten(), start, "timeout");
+            let holder = read_lock_holder(lock_path)
+                .map(|pid| format!(" (held by PID {})", pid))
+                .unwrap_or_default();
+            bail!(
+                "Timeout acquiring {} lock after {}ms{}",
+                label,
+                LOCK_TIMEOUT_MS,
+                holder
+            );
         }
+
+        thread::sleep(Duration::from_millis(LOCK_RETRY_INTERVAL_MS));
+    }
+}
+
+/// Record that acquiring `lock_path` had to wait (`--debug` only).
+fn log_lock_contention(
+    lock_path: &Path,
+    label: &str,
+    holder: Option<u32>,
+    start: Instant,
+    outcome: &str,
+) {
+    let mut data = HashMap::from([
+        ("lock".to_string(), serde_json::json!(label)),
+        (
+            "path".to_string(),
+            serde_json::json!(lock_path.display().to_string()),
+        ),
+        (
+            "waitedMs".to_string(),
+            serde_json::json!(start.elapsed().as_millis() as u64),
+        ),
+        ("outcome".to_string(), serde_json::json!(outcome)),
+    ]);
+    if let Some(pid) = holder {
+        data.insert("holderPid".to_string(), serde_json::json!(pid));
     }
+    debug_log(
+        DebugEventType::LockContention,
+        DebugEventSource::Lock,
+        None,
+        data,
+    );
+}
+
+/// A held lock, released when dropped.
+#[derive(Debug)]
+enum FileLock {
+    /// OS advisory lock (`flock`/`LockFileEx`) on the lock file. The OS drops
+    /// it when the holder exits, so a crashed holder never leaves it stale.
+    Os(fs::File),
+    /// Exclusively created PID file, for filesystems without advisory locks
+    PidFile(PathBuf),
+}
 
-    // Try creating the lock file atomically
-    // Use OpenOptions with create_new for atomic creation
-    match fs::OpenOptions::new()
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        match self {
+            FileLock::Os(file) => {
+                let _ = FileExt::unlock(file);
+            }
+            FileLock::PidFile(path) => {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+}
+
+/// Try to acquire the lock at `lock_path` without waiting.
+///
+/// The lock file is kept between holders: removing it would let a waiter
+/// that already opened the old file lock a different inode than newcomers.
+fn try_acquire_lock(lock_path: &Path) -> Option<FileLock> {
+    let file = fs::OpenOptions::new()
+        .read(true)
         .write(true)
-        .create_new(true)
+        .create(true)
+        .truncate(false)
         .open(lock_path)
-    {
-        Ok(file) => {
-            use std::io::Write;
-            let mut file = file;
-            let _ = write!(
-                file,
-                "{}",
-                std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis()
-            );
-            true
+        .ok()?;
+    match file.try_lock_exclusive() {
+        Ok(()) => {}
+        Err(e) if e.kind() == fs2::lock_contended_error().kind() => return None,
+        // Advisory locks unsupported (e.g. some network filesystems)
+        Err(_) => return try_acquire_pid_lock(&pid_lock_path(lock_path)),
+    }
+    write_lock_holder(&file);
+    Some(FileLock::Os(file))
+}
+
+/// Record this process as the lock holder, for diagnostics.
+fn write_lock_holder(mut file: &fs::File) {
+    let _ = file.set_len(0);
+    let _ = file.seek(SeekFrom::Start(0));
+    let _ = write!(file, "{}", std::process::id());
+}
+
+/// Fallback PID lock files under `.mobius/` whose holder has exited.
+pub fn find_stale_lock_files() -> Vec<PathBuf> {
+    let mut stale = Vec::new();
+    let mut dirs = vec![get_mobius_base_path()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                dirs.push(path);
+            } else if path.to_string_lossy().ends_with(".lock.pid") && is_pid_lock_stale(&path) {
+                stale.push(path);
+            }
         }
-        Err(_) => false,
     }
+    stale.sort();
+    stale
 }
 
-/// Check if a lock file is stale (older than LOCK_TIMEOUT_MS).
-fn is_lock_stale(lock_path: &Path) -> bool {
-    match fs::read_to_string(lock_path) {
-        Ok(content) => {
-            let timestamp: u128 = content.trim().parse().unwrap_or(0);
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis();
-            now - timestamp > LOCK_TIMEOUT_MS as u128
+/// PID recorded in a lock file, if any.
+fn read_lock_holder(lock_path: &Path) -> Option<u32> {
+    [lock_path.to_path_buf(), pid_lock_path(lock_path)]
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .find_map(|content| content.trim().parse().ok())
+}
+
+fn pid_lock_path(lock_path: &Path) -> PathBuf {
+    lock_path.with_extension("lock.pid")
+}
+
+/// Fallback lock: atomically create `pid_path` holding this process's PID.
+fn try_acquire_pid_lock(pid_path: &Path) -> Option<FileLock> {
+    if pid_path.exists() {
+        if is_pid_lock_stale(pid_path) {
+            let _ = fs::remove_file(pid_path);
+        } else {
+            return None;
         }
-        Err(_) => true, // Can't read => treat as stale
     }
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(pid_path)
+        .ok()?;
+    write_lock_holder(&file);
+    Some(FileLock::PidFile(pid_path.to_path_buf()))
 }
 
-/// Release a file lock.
-fn release_lock(lock_path: &Path) {
-    let _ = fs::remove_file(lock_path);
+/// A PID lock is stale once its holder process has exited.
+///
+/// A lock whose PID has not been written yet counts as held until it is
+/// older than `LOCK_TIMEOUT_MS`, so a holder racing between create and write
+/// is not mistaken for a dead one.
+fn is_pid_lock_stale(pid_path: &Path) -> bool {
+    match fs::read_to_string(pid_path) {
+        Ok(content) => match content.trim().parse::<u32>() {
+            Ok(pid) => !is_process_running(pid),
+            Err(_) => fs::metadata(pid_path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > Duration::from_millis(LOCK_TIMEOUT_MS)),
+        },
+        Err(_) => true, // Can't read => treat as stale
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -1486,7 +2282,7 @@ fn release_lock(lock_path: &Path) {
 // ---------------------------------------------------------------------------
 
 /// Write data to a file atomically using temp file + rename pattern.
-fn atomic_write_json<T: Serialize>(path: &Path, data: &T) -> Result<()> {
+pub(crate) fn atomic_write_json<T: Serialize>(path: &Path, data: &T) -> Result<()> {
     let tmp_path = path.with_extension("json.tmp");
     let json = serde_json::to_string_pretty(data)?;
 
@@ -1516,12 +2312,30 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    use crate::types::enums::TaskType;
+
     fn setup_test_dir() -> TempDir {
         TempDir::new().expect("Failed to create temp dir")
     }
 
     // -- Verify command extraction tests --
 
+    #[test]
+    fn test_extract_timeout_minutes() {
+        assert_eq!(
+            extract_timeout_minutes("## Summary\nx\n\n### Timeout\n45\n"),
+            Some(45)
+        );
+        assert_eq!(
+            extract_timeout_minutes("### Timeout\n  90 minutes\n### Next"),
+            Some(90)
+        );
+        assert_eq!(extract_timeout_minutes("### timeout\n2h"), Some(120));
+        assert_eq!(extract_timeout_minutes("### Timeout\n0"), None);
+        assert_eq!(extract_timeout_minutes("### Timeout\nsoon"), None);
+        assert_eq!(extract_timeout_minutes("No timeout section"), None);
+    }
+
     #[test]
     fn test_extract_verify_commands_basic() {
         let tasks = vec![SubTaskContext {
@@ -1542,6 +2356,10 @@ cd /tmp && echo "hello"
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         }];
 
         let commands = extract_verify_commands(&tasks);
@@ -1562,6 +2380,10 @@ cd /tmp && echo "hello"
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         }];
 
         let commands = extract_verify_commands(&tasks);
@@ -1580,6 +2402,10 @@ cd /tmp && echo "hello"
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         }];
 
         let commands = extract_verify_commands(&tasks);
@@ -1608,6 +2434,10 @@ cargo test -- --nocapture
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         }];
 
         let commands = extract_verify_commands(&tasks);
@@ -1633,6 +2463,10 @@ echo "works"
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         }];
 
         let commands = extract_verify_commands(&tasks);
@@ -1657,6 +2491,10 @@ echo "test"
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         }];
 
         let commands = extract_verify_commands(&tasks);
@@ -1680,6 +2518,10 @@ echo "test"
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         }];
 
         let commands = extract_verify_commands(&tasks);
@@ -1695,6 +2537,7 @@ echo "test"
             created_at: "2026-01-01T00:00:00Z".to_string(),
             synced_at: None,
             error: None,
+            conflict: None,
             data: PendingUpdateData::StatusChange {
                 issue_id: "abc".to_string(),
                 identifier: "MOB-101".to_string(),
@@ -1728,6 +2571,7 @@ echo "test"
             created_at: "t".to_string(),
             synced_at: None,
             error: None,
+            conflict: None,
             data: PendingUpdateData::AddComment {
                 issue_id: "abc".to_string(),
                 identifier: "MOB-101".to_string(),
@@ -1758,6 +2602,7 @@ echo "test"
             created_at: "t".to_string(),
             synced_at: None,
             error: None,
+            conflict: None,
             data: PendingUpdateData::StatusChange {
                 issue_id: "abc".to_string(),
                 identifier: "MOB-101".to_string(),
@@ -1777,58 +2622,107 @@ echo "test"
 
     // -- Lock tests --
 
+    /// PID of a process that has already exited
+    fn dead_pid() -> u32 {
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+        pid
+    }
+
     #[test]
     fn test_lock_acquire_and_release() {
         let tmp = setup_test_dir();
         let lock_path = tmp.path().join("test.lock");
 
-        assert!(try_acquire_lock(&lock_path));
-        assert!(lock_path.exists());
+        let lock = try_acquire_lock(&lock_path);
+        assert!(lock.is_some());
+        assert_eq!(read_lock_holder(&lock_path), Some(std::process::id()));
 
         // Second acquire should fail
-        assert!(!try_acquire_lock(&lock_path));
+        assert!(try_acquire_lock(&lock_path).is_none());
 
-        release_lock(&lock_path);
-        assert!(!lock_path.exists());
+        // The lock file stays in place for the next holder
+        drop(lock);
+        assert!(lock_path.exists());
 
         // Can acquire again after release
-        assert!(try_acquire_lock(&lock_path));
-        release_lock(&lock_path);
+        assert!(try_acquire_lock(&lock_path).is_some());
     }
 
     #[test]
     fn test_stale_lock_detection() {
         let tmp = setup_test_dir();
-        let lock_path = tmp.path().join("stale.lock");
+        let pid_path = tmp.path().join("stale.lock.pid");
 
-        // Write a lock with timestamp 0 (always stale)
-        fs::write(&lock_path, "0").unwrap();
-        assert!(is_lock_stale(&lock_path));
+        // A holder that has exited leaves a stale lock
+        fs::write(&pid_path, dead_pid().to_string()).unwrap();
+        assert!(is_pid_lock_stale(&pid_path));
 
-        // Write a lock with current timestamp (not stale)
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis();
-        fs::write(&lock_path, now.to_string()).unwrap();
-        assert!(!is_lock_stale(&lock_path));
+        // A live holder keeps it, however old the file is
+        fs::write(&pid_path, std::process::id().to_string()).unwrap();
+        assert!(!is_pid_lock_stale(&pid_path));
+
+        // A freshly created lock whose PID is not written yet is held
+        fs::write(&pid_path, "").unwrap();
+        assert!(!is_pid_lock_stale(&pid_path));
     }
 
     #[test]
     fn test_stale_lock_broken_on_acquire() {
         let tmp = setup_test_dir();
-        let lock_path = tmp.path().join("stale.lock");
+        let pid_path = tmp.path().join("stale.lock.pid");
 
-        // Create a stale lock
-        fs::write(&lock_path, "0").unwrap();
+        // Create a lock held by a dead process
+        fs::write(&pid_path, dead_pid().to_string()).unwrap();
 
         // Acquire should succeed (stale lock is broken)
-        assert!(try_acquire_lock(&lock_path));
-        release_lock(&lock_path);
+        let lock = try_acquire_pid_lock(&pid_path);
+        assert!(lock.is_some());
+        assert_eq!(
+            fs::read_to_string(&pid_path).unwrap(),
+            std::process::id().to_string()
+        );
+        drop(lock);
+        assert!(!pid_path.exists());
     }
 
     // -- Runtime state tests --
 
+    #[test]
+    fn test_dead_runtime_tasks_only_after_loop_exits() {
+        let task = |id: &str, pid: u32, worker: Option<&str>| RuntimeActiveTask {
+            id: id.to_string(),
+            pid,
+            pane: String::new(),
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            worktree: None,
+            model: None,
+            input_tokens: None,
+            output_tokens: None,
+            retry_count: None,
+            worker: worker.map(str::to_string),
+        };
+        let mut state = RuntimeState {
+            parent_id: "MOB-100".to_string(),
+            parent_title: "Test".to_string(),
+            active_tasks: vec![
+                task("MOB-101", dead_pid(), None),
+                task("MOB-102", std::process::id(), None),
+                task("MOB-103", 0, Some("build-host")),
+            ],
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            loop_pid: Some(dead_pid()),
+            total_tasks: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(dead_runtime_tasks(&state), vec!["MOB-101", "MOB-103"]);
+
+        state.loop_pid = Some(std::process::id());
+        assert!(dead_runtime_tasks(&state).is_empty());
+    }
+
     #[test]
     fn test_runtime_state_mutations() {
         let state = RuntimeState {
@@ -1844,6 +2738,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         };
 
         // Add active task
@@ -1856,6 +2753,8 @@ echo "test"
             model: None,
             input_tokens: None,
             output_tokens: None,
+            retry_count: None,
+            worker: None,
         };
         let state = add_runtime_active_task(&state, task);
         assert_eq!(state.active_tasks.len(), 1);
@@ -1865,9 +2764,11 @@ echo "test"
         assert_eq!(state.active_tasks[0].pane, "%2");
 
         // Complete task
-        let state = complete_runtime_task(&state, "task-001");
+        let state = complete_runtime_task(&state, "task-001", None);
         assert!(state.active_tasks.is_empty());
         assert_eq!(state.completed_tasks.len(), 1);
+        // Without metrics the duration is measured from the start time
+        assert!(normalize_completed_task(&state.completed_tasks[0]).duration > 0);
 
         // Add and fail another task
         let task2 = RuntimeActiveTask {
@@ -1879,11 +2780,32 @@ echo "test"
             model: None,
             input_tokens: None,
             output_tokens: None,
+            retry_count: None,
+            worker: None,
         };
         let state = add_runtime_active_task(&state, task2);
         let state = fail_runtime_task(&state, "task-002");
         assert!(state.active_tasks.is_empty());
         assert_eq!(state.failed_tasks.len(), 1);
+
+        // Cancel a task the loop already dropped from its active list
+        let task3 = RuntimeActiveTask {
+            id: "task-003".to_string(),
+            pid: 0,
+            pane: String::new(),
+            started_at: "2026-01-01T00:02:00Z".to_string(),
+            worktree: None,
+            model: None,
+            input_tokens: None,
+            output_tokens: None,
+            retry_count: None,
+            worker: None,
+        };
+        let running = add_runtime_active_task(&state, task3.clone());
+        let state = cancel_runtime_tasks(&running, std::slice::from_ref(&task3));
+        assert!(state.active_tasks.is_empty());
+        assert_eq!(state.cancelled_tasks.len(), 1);
+        assert_eq!(get_completed_task_id(&state.cancelled_tasks[0]), "task-003");
     }
 
     #[test]
@@ -1900,6 +2822,8 @@ echo "test"
                 model: None,
                 input_tokens: None,
                 output_tokens: None,
+                retry_count: None,
+                worker: None,
             }],
             completed_tasks: vec![],
             failed_tasks: vec![],
@@ -1910,6 +2834,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         };
 
         // Re-adding same task ID should replace, not duplicate
@@ -1922,6 +2849,8 @@ echo "test"
             model: None,
             input_tokens: None,
             output_tokens: None,
+            retry_count: None,
+            worker: None,
         };
         let state = add_runtime_active_task(&state, task);
         assert_eq!(state.active_tasks.len(), 1);
@@ -1930,6 +2859,57 @@ echo "test"
 
     // -- Completed task normalization tests --
 
+    #[test]
+    fn test_complete_runtime_task_records_attempt_metrics() {
+        let state = RuntimeState {
+            parent_id: "MOB-100".to_string(),
+            parent_title: "Test".to_string(),
+            active_tasks: vec![],
+            completed_tasks: vec![],
+            failed_tasks: vec![],
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:00:00Z".to_string(),
+            loop_pid: None,
+            total_tasks: None,
+            backend_statuses: None,
+            total_input_tokens: None,
+            total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
+        };
+        let state = add_runtime_active_task(
+            &state,
+            RuntimeActiveTask {
+                id: "MOB-101".to_string(),
+                pid: 0,
+                pane: String::new(),
+                started_at: Utc::now().to_rfc3339(),
+                worktree: None,
+                model: Some("sonnet".to_string()),
+                input_tokens: Some(10),
+                output_tokens: Some(5),
+                retry_count: None,
+                worker: None,
+            },
+        );
+        let metrics = TaskAttemptMetrics {
+            duration_ms: 90_000,
+            input_tokens: Some(1_200),
+            output_tokens: None,
+            model: None,
+        };
+
+        let state = complete_runtime_task(&state, "MOB-101", Some(&metrics));
+        let completed = normalize_completed_task(&state.completed_tasks[0]);
+        assert_eq!(completed.duration, 90_000);
+        assert_eq!(completed.input_tokens, Some(1_200));
+        // Values the attempt didn't report fall back to the active entry
+        assert_eq!(completed.output_tokens, Some(5));
+        assert_eq!(completed.model.as_deref(), Some("sonnet"));
+        assert_eq!(state.completed_tasks[0]["model"], "sonnet");
+    }
+
     #[test]
     fn test_normalize_completed_task_string() {
         let entry = serde_json::Value::String("MOB-101".to_string());
@@ -1977,6 +2957,8 @@ echo "test"
                 model: None,
                 input_tokens: None,
                 output_tokens: None,
+                retry_count: None,
+                worker: None,
             }],
             completed_tasks: vec![],
             failed_tasks: vec![],
@@ -1987,6 +2969,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         });
 
         let new_same = old.clone();
@@ -2003,6 +2988,8 @@ echo "test"
                     model: None,
                     input_tokens: None,
                     output_tokens: None,
+                    retry_count: None,
+                    worker: None,
                 },
                 RuntimeActiveTask {
                     id: "task-002".to_string(),
@@ -2013,6 +3000,8 @@ echo "test"
                     model: None,
                     input_tokens: None,
                     output_tokens: None,
+                    retry_count: None,
+                    worker: None,
                 },
             ],
             ..old.as_ref().unwrap().clone()
@@ -2035,6 +3024,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         };
 
         // Same except updated_at -> no change
@@ -2172,6 +3164,8 @@ echo "test"
                 model: None,
                 input_tokens: None,
                 output_tokens: None,
+                retry_count: None,
+                worker: None,
             }],
             completed_tasks: vec![serde_json::json!("t2"), serde_json::json!("t3")],
             failed_tasks: vec![serde_json::json!("t4")],
@@ -2182,6 +3176,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         };
 
         let summary = get_progress_summary(Some(&state));
@@ -2227,6 +3224,8 @@ echo "test"
                     model: None,
                     input_tokens: None,
                     output_tokens: None,
+                    retry_count: None,
+                    worker: None,
                 },
                 RuntimeActiveTask {
                     id: "task-002".to_string(),
@@ -2237,6 +3236,8 @@ echo "test"
                     model: None,
                     input_tokens: None,
                     output_tokens: None,
+                    retry_count: None,
+                    worker: None,
                 },
             ],
             completed_tasks: vec![],
@@ -2248,6 +3249,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         };
 
         let state = remove_runtime_active_task(&state, "task-001");
@@ -2259,6 +3263,37 @@ echo "test"
         assert_eq!(state.active_tasks.len(), 1);
     }
 
+    #[test]
+    fn test_add_runtime_task_cost_accumulates_across_attempts() {
+        let mut state = RuntimeState {
+            parent_id: "p".to_string(),
+            parent_title: "t".to_string(),
+            active_tasks: vec![],
+            completed_tasks: vec![],
+            failed_tasks: vec![],
+            started_at: "t".to_string(),
+            updated_at: "t".to_string(),
+            loop_pid: None,
+            total_tasks: None,
+            backend_statuses: None,
+            total_input_tokens: None,
+            total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
+        };
+
+        state = add_runtime_task_cost(&state, "task-001", 0.5);
+        state = add_runtime_task_cost(&state, "task-001", 0.25);
+        state = add_runtime_task_cost(&state, "task-002", 1.0);
+
+        assert_eq!(state.task_costs_usd["task-001"], 0.75);
+        assert_eq!(state.total_cost_usd, Some(1.75));
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert!(json.contains("\"totalCostUsd\":1.75"));
+    }
+
     // -- Session lifecycle tests --
 
     /// Helper to clean up test context directories
@@ -2412,6 +3447,22 @@ echo "test"
         cleanup_test_parent(parent_id);
     }
 
+    #[test]
+    fn test_set_paused_roundtrip() {
+        let parent_id = "TEST-CTX-PAUSE-001";
+        cleanup_test_parent(parent_id);
+
+        assert!(!is_paused(parent_id));
+        set_paused(parent_id, true).expect("pause should succeed");
+        assert!(is_paused(parent_id));
+        set_paused(parent_id, false).expect("unpause should succeed");
+        assert!(!is_paused(parent_id));
+        // Unpausing twice is harmless
+        set_paused(parent_id, false).expect("repeat unpause should succeed");
+
+        cleanup_test_parent(parent_id);
+    }
+
     // -- Concurrent lock tests --
 
     #[test]
@@ -2420,61 +3471,71 @@ echo "test"
         let lock_path = tmp.path().join("contention.lock");
 
         // Acquire the lock (simulating another process holding it)
-        assert!(try_acquire_lock(&lock_path));
+        let lock = try_acquire_lock(&lock_path);
+        assert!(lock.is_some());
 
-        // Try to acquire again - should fail (lock is held and not stale)
-        assert!(!try_acquire_lock(&lock_path), "second acquire should fail");
+        // Try to acquire again - should fail (lock is held)
+        assert!(
+            try_acquire_lock(&lock_path).is_none(),
+            "second acquire should fail"
+        );
 
         // Release the lock
-        release_lock(&lock_path);
+        drop(lock);
 
         // Now acquisition should succeed
-        assert!(try_acquire_lock(&lock_path), "should succeed after release");
-        release_lock(&lock_path);
+        assert!(
+            try_acquire_lock(&lock_path).is_some(),
+            "should succeed after release"
+        );
     }
 
     #[test]
-    fn test_stale_lock_cleanup_on_acquire() {
-        let tmp = setup_test_dir();
-        let lock_path = tmp.path().join("stale-cleanup.lock");
-
-        // Write a stale lock with timestamp 0
-        fs::write(&lock_path, "0").unwrap();
-        assert!(lock_path.exists());
+    fn test_lock_held_across_read_modify_write() {
+        use std::sync::{Arc, Barrier};
 
-        // Acquiring should succeed by cleaning up the stale lock
-        assert!(
-            try_acquire_lock(&lock_path),
-            "should acquire over stale lock"
-        );
-
-        // The lock file should now contain a recent timestamp
-        let content = fs::read_to_string(&lock_path).unwrap();
-        let ts: u128 = content.trim().parse().unwrap();
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis();
-        assert!(now - ts < 5000, "lock timestamp should be recent");
+        let tmp = setup_test_dir();
+        let lock_path = Arc::new(tmp.path().join("counter.lock"));
+        let counter_path = Arc::new(tmp.path().join("counter"));
+        fs::write(counter_path.as_ref(), "0").unwrap();
+
+        let threads = 8;
+        let barrier = Arc::new(Barrier::new(threads));
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                let (lock_path, counter_path, barrier) =
+                    (lock_path.clone(), counter_path.clone(), barrier.clone());
+                thread::spawn(move || {
+                    barrier.wait();
+                    with_file_lock(&lock_path, "counter", || {
+                        let n: u32 = fs::read_to_string(counter_path.as_ref())?.parse()?;
+                        thread::sleep(Duration::from_millis(5));
+                        fs::write(counter_path.as_ref(), (n + 1).to_string())?;
+                        Ok(())
+                    })
+                    .unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
 
-        release_lock(&lock_path);
+        assert_eq!(fs::read_to_string(counter_path.as_ref()).unwrap(), "8");
     }
 
     #[test]
-    fn test_lock_release_idempotent() {
+    fn test_lock_released_when_closure_fails() {
         let tmp = setup_test_dir();
-        let lock_path = tmp.path().join("idempotent.lock");
-
-        // Acquire lock
-        assert!(try_acquire_lock(&lock_path));
+        let lock_path = tmp.path().join("failing.lock");
 
-        // Release once
-        release_lock(&lock_path);
-        assert!(!lock_path.exists());
+        let result: Result<()> = with_file_lock(&lock_path, "failing", || bail!("boom"));
+        assert!(result.is_err());
 
-        // Release again - should not panic or error
-        release_lock(&lock_path);
-        assert!(!lock_path.exists(), "double release should be safe");
+        assert!(
+            try_acquire_lock(&lock_path).is_some(),
+            "a failed holder must not leave the lock held"
+        );
     }
 
     // -- Runtime state edge cases --
@@ -2545,6 +3606,122 @@ echo "test"
         cleanup_test_parent(parent_id);
     }
 
+    #[test]
+    fn test_last_pull_round_trip() {
+        let parent_id = "TEST-CTX-LASTPULL-001";
+        cleanup_test_parent(parent_id);
+        assert!(read_last_pull(parent_id).is_none());
+
+        let at = chrono::DateTime::parse_from_rfc3339("2026-10-16T09:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        write_last_pull(parent_id, at).unwrap();
+        assert_eq!(read_last_pull(parent_id), Some(at));
+
+        cleanup_test_parent(parent_id);
+    }
+
+    #[test]
+    fn test_runtime_journal_records_content_changes_only() {
+        let parent_id = "TEST-CTX-JOURNAL-001";
+        cleanup_test_parent(parent_id);
+        let journal = get_runtime_journal_path(parent_id);
+
+        initialize_runtime_state(parent_id, "Journal", None, Some(2)).unwrap();
+        // Touching only updatedAt is not a revision
+        with_runtime_state_sync(parent_id, |s| {
+            let mut s = s.unwrap();
+            s.updated_at = Utc::now().to_rfc3339();
+            s
+        })
+        .unwrap();
+        with_runtime_state_sync(parent_id, |s| {
+            add_runtime_task_cost(&s.unwrap(), "MOB-1", 0.5)
+        })
+        .unwrap();
+
+        let entries = read_runtime_journal(&journal);
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].state.task_costs_usd.is_empty());
+        assert_eq!(entries[1].state.task_costs_usd.get("MOB-1"), Some(&0.5));
+
+        // A new run starts a fresh journal
+        initialize_runtime_state(parent_id, "Journal", None, Some(2)).unwrap();
+        assert_eq!(read_runtime_journal(&journal).len(), 1);
+
+        cleanup_test_parent(parent_id);
+    }
+
+    #[test]
+    fn test_concurrent_queue_pending_update_keeps_all_updates() {
+        use std::sync::{Arc, Barrier};
+
+        let parent_id = "TEST-CTX-PUCONC-001";
+        cleanup_test_parent(parent_id);
+
+        let threads = 8;
+        let per_thread = 5;
+        let barrier = Arc::new(Barrier::new(threads));
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    for n in 0..per_thread {
+                        let update = PendingUpdateInput::AddComment {
+                            issue_id: format!("id-{}", t),
+                            identifier: format!("MOB-{}", t),
+                            body: format!("comment {}", n),
+                        };
+                        queue_pending_update(parent_id, &update).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(
+            read_pending_updates(parent_id).updates.len(),
+            threads * per_thread
+        );
+
+        // Marking updates while others are queued must not drop either
+        let queued = read_pending_updates(parent_id).updates[0].id.clone();
+        let marker = thread::spawn(move || {
+            with_pending_updates_sync(parent_id, |mut queue| {
+                for update in &mut queue.updates {
+                    if update.id == queued {
+                        update.synced_at = Some("t".to_string());
+                    }
+                }
+                queue
+            })
+            .unwrap();
+        });
+        let update = PendingUpdateInput::AddComment {
+            issue_id: "id-x".to_string(),
+            identifier: "MOB-X".to_string(),
+            body: "late".to_string(),
+        };
+        queue_pending_update(parent_id, &update).unwrap();
+        marker.join().unwrap();
+
+        let queue = read_pending_updates(parent_id);
+        assert_eq!(queue.updates.len(), threads * per_thread + 1);
+        assert_eq!(
+            queue
+                .updates
+                .iter()
+                .filter(|u| u.synced_at.is_some())
+                .count(),
+            1
+        );
+
+        cleanup_test_parent(parent_id);
+    }
+
     #[test]
     fn test_with_runtime_state_sync_creates_parent_dir() {
         let parent_id = "TEST-CTX-WRSS-001";
@@ -2571,6 +3748,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         });
 
         assert!(result.is_ok(), "with_runtime_state_sync should succeed");
@@ -2587,6 +3767,52 @@ echo "test"
         cleanup_test_parent(parent_id);
     }
 
+    #[test]
+    fn test_task_actions_apply_to_runtime_and_queue_for_loop() {
+        let parent_id = "TEST-CTX-ACT-001";
+        cleanup_test_parent(parent_id);
+        initialize_runtime_state(parent_id, "Test", None, Some(3)).unwrap();
+        with_runtime_state_sync(parent_id, |state| {
+            let mut state = state.unwrap();
+            state.failed_tasks = vec![
+                serde_json::json!({"id": "task-001"}),
+                serde_json::json!({"id": "task-002"}),
+            ];
+            state
+        })
+        .unwrap();
+
+        queue_task_action(parent_id, "task-001", TaskAction::Skip).unwrap();
+        queue_task_action(parent_id, "task-002", TaskAction::Skip).unwrap();
+        // A newer request replaces the older one for the same task
+        queue_task_action(parent_id, "task-002", TaskAction::Retry).unwrap();
+
+        let state = read_runtime_state(parent_id).unwrap();
+        assert!(state.failed_tasks.is_empty());
+        let completed: Vec<String> = state
+            .completed_tasks
+            .iter()
+            .map(get_completed_task_id)
+            .collect();
+        assert_eq!(completed, vec!["task-001"]);
+
+        let actions = take_task_actions(parent_id).unwrap();
+        let actions: Vec<(&str, TaskAction)> = actions
+            .iter()
+            .map(|r| (r.task.as_str(), r.action))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("task-001", TaskAction::Skip),
+                ("task-002", TaskAction::Retry)
+            ]
+        );
+        assert!(take_task_actions(parent_id).unwrap().is_empty());
+
+        cleanup_test_parent(parent_id);
+    }
+
     // -- ProgressSummary is_complete tests --
 
     #[test]
@@ -2605,6 +3831,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         };
 
         let summary = get_progress_summary(Some(&state));
@@ -2644,6 +3873,8 @@ echo "test"
                 model: None,
                 input_tokens: None,
                 output_tokens: None,
+                retry_count: None,
+                worker: None,
             }],
             completed_tasks: vec![serde_json::json!("t1")],
             failed_tasks: vec![],
@@ -2654,6 +3885,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         };
 
         let summary = get_progress_summary(Some(&state));
@@ -2694,6 +3928,8 @@ echo "test"
                 model: None,
                 input_tokens: None,
                 output_tokens: None,
+                retry_count: None,
+                worker: None,
             }],
             completed_tasks: vec![],
             failed_tasks: vec![],
@@ -2704,6 +3940,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         });
         assert!(
             has_new_active_tasks(&None, &new),
@@ -2744,6 +3983,8 @@ echo "test"
                     model: None,
                     input_tokens: None,
                     output_tokens: None,
+                    retry_count: None,
+                    worker: None,
                 },
                 RuntimeActiveTask {
                     id: "task-002".to_string(),
@@ -2754,6 +3995,8 @@ echo "test"
                     model: None,
                     input_tokens: None,
                     output_tokens: None,
+                    retry_count: None,
+                    worker: None,
                 },
             ],
             ..new.as_ref().unwrap().clone()
@@ -2784,6 +4027,8 @@ echo "test"
                 model: None,
                 input_tokens: None,
                 output_tokens: None,
+                retry_count: None,
+                worker: None,
             }],
             completed_tasks: vec![serde_json::json!("done-1")],
             failed_tasks: vec![serde_json::json!("fail-1")],
@@ -2794,6 +4039,9 @@ echo "test"
             backend_statuses: None,
             total_input_tokens: None,
             total_output_tokens: None,
+            task_costs_usd: HashMap::new(),
+            total_cost_usd: None,
+            cancelled_tasks: vec![],
         };
 
         // Only updated_at changed → no content change
@@ -2828,6 +4076,8 @@ echo "test"
                     model: None,
                     input_tokens: None,
                     output_tokens: None,
+                    retry_count: None,
+                    worker: None,
                 },
                 RuntimeActiveTask {
                     id: "task-002".to_string(),
@@ -2838,6 +4088,8 @@ echo "test"
                     model: None,
                     input_tokens: None,
                     output_tokens: None,
+                    retry_count: None,
+                    worker: None,
                 },
             ],
             ..base.clone()
@@ -2876,4 +4128,24 @@ echo "test"
             "added backend_statuses should count as content change"
         );
     }
+
+    #[test]
+    fn test_changed_specs() {
+        let before = HashMap::from([
+            ("MOB-1".to_string(), 1u64),
+            ("MOB-2".to_string(), 2),
+            ("MOB-3".to_string(), 3),
+        ]);
+        let after = HashMap::from([
+            ("MOB-1".to_string(), 1u64),
+            ("MOB-3".to_string(), 30),
+            ("MOB-4".to_string(), 4),
+        ]);
+
+        assert_eq!(
+            changed_specs(&before, &after),
+            vec!["MOB-2", "MOB-3", "MOB-4"]
+        );
+        assert!(changed_specs(&after, &after).is_empty());
+    }
 }
@@ -0,0 +1,135 @@
+//! Token usage to dollar cost conversion and budget enforcement.
+//!
+//! Prices are USD per million tokens, matched against the model name the
+//! loop records for each agent (e.g. `opus`, `claude-sonnet-4-5`,
+//! `openai/gpt-5.3-codex`). Models without a known price are not costed.
+
+/// Per-model token pricing (USD per million tokens)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input_per_mtok: f64,
+    pub output_per_mtok: f64,
+}
+
+/// Pricing table keyed by a case-insensitive substring of the model name.
+/// More specific keys must come before broader ones.
+const PRICING_TABLE: &[(&str, ModelPricing)] = &[
+    (
+        "opus",
+        ModelPricing {
+            input_per_mtok: 15.0,
+            output_per_mtok: 75.0,
+        },
+    ),
+    (
+        "sonnet",
+        ModelPricing {
+            input_per_mtok: 3.0,
+            output_per_mtok: 15.0,
+        },
+    ),
+    (
+        "haiku",
+        ModelPricing {
+            input_per_mtok: 1.0,
+            output_per_mtok: 5.0,
+        },
+    ),
+    (
+        "gpt-5",
+        ModelPricing {
+            input_per_mtok: 1.25,
+            output_per_mtok: 10.0,
+        },
+    ),
+];
+
+/// Look up pricing for a model name.
+pub fn pricing_for_model(model: &str) -> Option<ModelPricing> {
+    let lower = model.to_lowercase();
+    PRICING_TABLE
+        .iter()
+        .find(|(key, _)| lower.contains(key))
+        .map(|(_, pricing)| *pricing)
+}
+
+/// Estimate the dollar cost of a token count for `model`.
+pub fn estimate_cost_usd(model: &str, input_tokens: u64, output_tokens: u64) -> Option<f64> {
+    let pricing = pricing_for_model(model)?;
+    Some(
+        (input_tokens as f64 * pricing.input_per_mtok
+            + output_tokens as f64 * pricing.output_per_mtok)
+            / 1_000_000.0,
+    )
+}
+
+/// Format a dollar amount for display (`$0.42`, `$12.30`).
+pub fn format_cost(usd: f64) -> String {
+    format!("${:.2}", usd)
+}
+
+/// Share of the budget at which the dashboard starts warning
+pub const BUDGET_WARNING_RATIO: f64 = 0.8;
+
+/// True when `spent` has reached the configured budget.
+pub fn budget_exceeded(spent_usd: f64, max_cost_usd: Option<f64>) -> bool {
+    max_cost_usd.is_some_and(|max| spent_usd >= max)
+}
+
+/// True when `spent` is within `BUDGET_WARNING_RATIO` of the configured budget.
+pub fn budget_approaching(spent_usd: f64, max_cost_usd: Option<f64>) -> bool {
+    max_cost_usd.is_some_and(|max| spent_usd >= max * BUDGET_WARNING_RATIO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pricing_for_model() {
+        assert_eq!(pricing_for_model("opus").unwrap().input_per_mtok, 15.0);
+        assert_eq!(
+            pricing_for_model("claude-sonnet-4-5-20250929")
+                .unwrap()
+                .output_per_mtok,
+            15.0
+        );
+        assert_eq!(
+            pricing_for_model("openai/gpt-5.3-codex")
+                .unwrap()
+                .input_per_mtok,
+            1.25
+        );
+        assert!(pricing_for_model("llama-3").is_none());
+    }
+
+    #[test]
+    fn test_estimate_cost_usd() {
+        let cost = estimate_cost_usd("sonnet", 1_000_000, 200_000).unwrap();
+        assert!((cost - 6.0).abs() < 1e-9);
+        assert_eq!(estimate_cost_usd("sonnet", 0, 0), Some(0.0));
+        assert!(estimate_cost_usd("unknown", 1000, 1000).is_none());
+    }
+
+    #[test]
+    fn test_format_cost() {
+        assert_eq!(format_cost(0.0), "$0.00");
+        assert_eq!(format_cost(0.4249), "$0.42");
+        assert_eq!(format_cost(12.3), "$12.30");
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        assert!(!budget_exceeded(100.0, None));
+        assert!(!budget_exceeded(4.99, Some(5.0)));
+        assert!(budget_exceeded(5.0, Some(5.0)));
+        assert!(budget_exceeded(7.5, Some(5.0)));
+    }
+
+    #[test]
+    fn test_budget_approaching() {
+        assert!(!budget_approaching(100.0, None));
+        assert!(!budget_approaching(3.99, Some(5.0)));
+        assert!(budget_approaching(4.0, Some(5.0)));
+    }
+}
@@ -2,12 +2,14 @@
 //!
 //! Provides a thread-safe singleton logger that:
 //! - Maintains a ring buffer of recent events (for TUI display)
-//! - Writes to log files at `.mobius/issues/{parentId}/execution/debug-{sessionId}.log`
+//! - Appends JSONL events to `.mobius/issues/{parentId}/execution/debug/` through a
+//!   size-rotated [`DebugSink`] (read back with `mobius debug dump`)
 //! - Outputs to stderr in non-TUI mode with color-coded event types
 
 use std::collections::{HashMap, VecDeque};
 use std::fs::{self, OpenOptions};
-use std::io::Write;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Mutex, OnceLock};
 
 use chrono::Utc;
@@ -20,6 +22,15 @@ use crate::types::enums::{DebugEventSource, DebugEventType, DebugVerbosity};
 /// Maximum events to keep in ring buffer.
 const RING_BUFFER_SIZE: usize = 100;
 
+/// Size at which the active debug log is rotated.
+const MAX_LOG_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Log files kept per issue, including the active one.
+const MAX_LOG_FILES: usize = 5;
+
+/// Name of the active debug log; rotated files are `debug.1.jsonl` (newest) onward.
+const ACTIVE_LOG: &str = "debug.jsonl";
+
 /// Global singleton debug logger.
 static DEBUG_LOGGER: OnceLock<Mutex<DebugLogger>> = OnceLock::new();
 
@@ -36,6 +47,9 @@ fn event_label(event_type: DebugEventType) -> &'static str {
         DebugEventType::LockAcquire => "lock:acquire",
         DebugEventType::LockRelease => "lock:release",
         DebugEventType::TuiStateReceive => "tui:state:receive",
+        DebugEventType::StateDrift => "state:drift",
+        DebugEventType::LockContention => "lock:contention",
+        DebugEventType::PaneCapture => "pane:capture",
     }
 }
 
@@ -52,14 +66,131 @@ fn color_label(event_type: DebugEventType, label: &str) -> String {
         DebugEventType::LockAcquire => label.dimmed().to_string(),
         DebugEventType::LockRelease => label.dimmed().to_string(),
         DebugEventType::TuiStateReceive => label.bright_blue().to_string(),
+        DebugEventType::StateDrift => label.red().to_string(),
+        DebugEventType::LockContention => label.bright_yellow().to_string(),
+        DebugEventType::PaneCapture => label.dimmed().to_string(),
     }
 }
 
+/// Directory holding an issue's debug logs.
+pub fn debug_dir(parent_id: &str) -> PathBuf {
+    get_execution_path(parent_id).join("debug")
+}
+
+/// Append-only JSONL event log with size-based rotation.
+///
+/// When the active file would grow past `max_bytes` it is renamed to
+/// `debug.1.jsonl`, older files shift up by one, and anything beyond
+/// `max_files` is deleted.
+pub struct DebugSink {
+    dir: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+}
+
+impl DebugSink {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self::with_limits(dir, MAX_LOG_BYTES, MAX_LOG_FILES)
+    }
+
+    pub fn with_limits(dir: impl Into<PathBuf>, max_bytes: u64, max_files: usize) -> Self {
+        Self {
+            dir: dir.into(),
+            max_bytes,
+            max_files: max_files.max(1),
+        }
+    }
+
+    /// Append `event` as one JSON line, rotating first if it would not fit.
+    pub fn write(&self, event: &DebugEvent) -> io::Result<()> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+
+        fs::create_dir_all(&self.dir)?;
+        let active = self.dir.join(ACTIVE_LOG);
+        let size = fs::metadata(&active).map(|m| m.len()).unwrap_or(0);
+        if size > 0 && size + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&active)?
+            .write_all(line.as_bytes())
+    }
+
+    fn rotate(&self) -> io::Result<()> {
+        let _ = fs::remove_file(rotated_log(&self.dir, self.max_files - 1));
+        for n in (1..self.max_files - 1).rev() {
+            let from = rotated_log(&self.dir, n);
+            if from.exists() {
+                fs::rename(&from, rotated_log(&self.dir, n + 1))?;
+            }
+        }
+        let active = self.dir.join(ACTIVE_LOG);
+        if self.max_files > 1 {
+            fs::rename(&active, rotated_log(&self.dir, 1))
+        } else {
+            fs::remove_file(&active)
+        }
+    }
+}
+
+fn rotated_log(dir: &Path, n: usize) -> PathBuf {
+    dir.join(format!("debug.{n}.jsonl"))
+}
+
+/// Debug log files in `dir`, oldest first.
+pub fn debug_log_files(dir: &Path) -> Vec<PathBuf> {
+    let mut rotated: Vec<(usize, PathBuf)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let n = name
+                .strip_prefix("debug.")?
+                .strip_suffix(".jsonl")?
+                .parse()
+                .ok()?;
+            Some((n, entry.path()))
+        })
+        .collect();
+    rotated.sort_by_key(|(n, _)| std::cmp::Reverse(*n));
+
+    let mut files: Vec<PathBuf> = rotated.into_iter().map(|(_, path)| path).collect();
+    let active = dir.join(ACTIVE_LOG);
+    if active.exists() {
+        files.push(active);
+    }
+    files
+}
+
+/// Every event in `dir`'s debug logs, oldest first; unparseable lines are skipped.
+pub fn read_debug_events(dir: &Path) -> Vec<DebugEvent> {
+    debug_log_files(dir)
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Render an event the way `--debug` prints it to stderr.
+pub fn format_debug_event(event: &DebugEvent) -> String {
+    DebugLogger::format_for_console(event)
+}
+
 /// Internal debug logger state.
 struct DebugLogger {
     config: DebugConfig,
     ring_buffer: VecDeque<DebugEvent>,
-    log_file_path: Option<String>,
+    sink: Option<DebugSink>,
 }
 
 impl DebugLogger {
@@ -67,7 +198,7 @@ impl DebugLogger {
         Self {
             config: DebugConfig::default(),
             ring_buffer: VecDeque::with_capacity(RING_BUFFER_SIZE),
-            log_file_path: None,
+            sink: None,
         }
     }
 
@@ -81,17 +212,8 @@ impl DebugLogger {
             session_id: Some(session_id.clone()),
         };
 
-        // Set up log file
-        let execution_dir = get_execution_path(parent_id);
-        if !execution_dir.exists() {
-            let _ = fs::create_dir_all(&execution_dir);
-        }
-        self.log_file_path = Some(
-            execution_dir
-                .join(format!("debug-{session_id}.log"))
-                .to_string_lossy()
-                .to_string(),
-        );
+        let log_dir = debug_dir(parent_id);
+        self.sink = Some(DebugSink::new(&log_dir));
 
         // Log initialization
         let mut data = HashMap::new();
@@ -103,12 +225,14 @@ impl DebugLogger {
             "verbosity".to_string(),
             serde_json::Value::String(format!("{verbosity:?}").to_lowercase()),
         );
-        if let Some(ref path) = self.log_file_path {
-            data.insert(
-                "logFile".to_string(),
-                serde_json::Value::String(path.clone()),
-            );
-        }
+        data.insert(
+            "sessionId".to_string(),
+            serde_json::Value::String(session_id),
+        );
+        data.insert(
+            "logDir".to_string(),
+            serde_json::Value::String(log_dir.to_string_lossy().to_string()),
+        );
 
         self.log_event(
             DebugEventType::TaskStateChange,
@@ -165,33 +289,6 @@ impl DebugLogger {
         self.ring_buffer.iter().skip(start).cloned().collect()
     }
 
-    fn format_for_file(event: &DebugEvent) -> String {
-        let time = event
-            .timestamp
-            .split('T')
-            .nth(1)
-            .unwrap_or(&event.timestamp)
-            .trim_end_matches('Z');
-        let task_part = event
-            .task_id
-            .as_ref()
-            .map(|id| format!(" [{id}]"))
-            .unwrap_or_default();
-        let data_str = if event.data.is_empty() {
-            String::new()
-        } else {
-            format!(
-                " {}",
-                serde_json::to_string(&event.data).unwrap_or_default()
-            )
-        };
-
-        format!(
-            "[DEBUG {time}] {}{task_part}{data_str}\n",
-            event_label(event.event_type)
-        )
-    }
-
     fn format_for_console(event: &DebugEvent) -> String {
         let time = event
             .timestamp
@@ -233,15 +330,9 @@ impl DebugLogger {
     }
 
     fn write_to_file(&self, event: &DebugEvent) {
-        let Some(ref path) = self.log_file_path else {
-            return;
-        };
-
-        let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) else {
-            return;
-        };
-
-        let _ = file.write_all(Self::format_for_file(event).as_bytes());
+        if let Some(ref sink) = self.sink {
+            let _ = sink.write(event);
+        }
     }
 
     fn write_to_stderr(&self, event: &DebugEvent) {
@@ -257,7 +348,7 @@ impl DebugLogger {
 /// Initialize the debug logger for a session.
 ///
 /// Creates a new debug session with the given parent ID and verbosity level.
-/// Sets up log file at `.mobius/issues/{parent_id}/execution/debug-{session_id}.log`.
+/// Events are appended to `.mobius/issues/{parent_id}/execution/debug/debug.jsonl`.
 pub fn initialize_debug_logger(parent_id: &str, verbosity: DebugVerbosity) {
     let logger = DEBUG_LOGGER.get_or_init(|| Mutex::new(DebugLogger::new()));
     if let Ok(mut guard) = logger.lock() {
@@ -304,3 +395,60 @@ pub fn get_recent_debug_events(count: usize) -> Vec<DebugEvent> {
     }
     Vec::new()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(n: usize) -> DebugEvent {
+        DebugEvent {
+            timestamp: format!("2024-01-15T14:30:{:02}.000Z", n % 60),
+            event_type: DebugEventType::LockContention,
+            source: DebugEventSource::Lock,
+            task_id: Some(format!("MOB-{n}")),
+            data: HashMap::from([("n".to_string(), serde_json::json!(n))]),
+        }
+    }
+
+    #[test]
+    fn test_debug_sink_rotates_and_reads_back_in_order() {
+        let tmp = tempfile::tempdir().unwrap();
+        let line_len = serde_json::to_string(&event(0)).unwrap().len() as u64 + 1;
+        // Two events per file, three files kept
+        let sink = DebugSink::with_limits(tmp.path(), line_len * 2, 3);
+        for n in 0..8 {
+            sink.write(&event(n)).unwrap();
+        }
+
+        let files = debug_log_files(tmp.path());
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, ["debug.2.jsonl", "debug.1.jsonl", "debug.jsonl"]);
+
+        let tasks: Vec<_> = read_debug_events(tmp.path())
+            .into_iter()
+            .filter_map(|e| e.task_id)
+            .collect();
+        assert_eq!(
+            tasks,
+            ["MOB-2", "MOB-3", "MOB-4", "MOB-5", "MOB-6", "MOB-7"]
+        );
+    }
+
+    #[test]
+    fn test_read_debug_events_skips_torn_lines() {
+        let tmp = tempfile::tempdir().unwrap();
+        DebugSink::new(tmp.path()).write(&event(1)).unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(tmp.path().join(ACTIVE_LOG))
+            .unwrap();
+        file.write_all(b"{\"timestamp\":\"2024").unwrap();
+
+        let events = read_debug_events(tmp.path());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, DebugEventType::LockContention);
+    }
+}
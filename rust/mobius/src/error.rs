@@ -0,0 +1,237 @@
+//! Typed errors shared across commands.
+//!
+//! Modules still return `anyhow::Result`, but failures callers may want to
+//! handle programmatically are raised as a `MobiusError` (or one of the backend
+//! client errors) so `main` can map them to a stable kind, exit code, and JSON
+//! error object.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::commands::output::{to_json, OutputFormat};
+use crate::config::error::ConfigError;
+use crate::github::GithubError;
+use crate::gitlab::GitlabError;
+use crate::jira::JiraError;
+use crate::linear::LinearError;
+
+/// Failures with a stable kind and exit code
+#[derive(Debug, thiserror::Error)]
+pub enum MobiusError {
+    /// Invalid or missing configuration
+    #[error("{0}")]
+    Config(String),
+    /// Missing or rejected tracker credentials
+    #[error("{0}")]
+    BackendAuth(String),
+    /// Tracker API request failed
+    #[error("{0}")]
+    BackendApi(String),
+    /// A git command failed
+    #[error("{0}")]
+    Git(String),
+    /// A tmux command failed
+    #[error("{0}")]
+    Tmux(String),
+    /// A `.mobius` state file exists but cannot be read
+    #[error("Corrupt state file {path}: {message}")]
+    StateCorruption { path: String, message: String },
+    /// Agents failed to complete their sub-tasks
+    #[error("{0}")]
+    Agent(String),
+}
+
+impl MobiusError {
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            MobiusError::Config(_) => ErrorKind::Config,
+            MobiusError::BackendAuth(_) => ErrorKind::BackendAuth,
+            MobiusError::BackendApi(_) => ErrorKind::BackendApi,
+            MobiusError::Git(_) => ErrorKind::Git,
+            MobiusError::Tmux(_) => ErrorKind::Tmux,
+            MobiusError::StateCorruption { .. } => ErrorKind::StateCorruption,
+            MobiusError::Agent(_) => ErrorKind::Agent,
+        }
+    }
+}
+
+/// Error category reported in JSON output and mapped to an exit code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    Config,
+    BackendAuth,
+    BackendApi,
+    Git,
+    Tmux,
+    StateCorruption,
+    Agent,
+    /// Anything without a more specific kind
+    Other,
+}
+
+impl ErrorKind {
+    /// Process exit code; 2 is left to clap's usage errors.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Other => 1,
+            ErrorKind::Config => 3,
+            ErrorKind::BackendAuth => 4,
+            ErrorKind::BackendApi => 5,
+            ErrorKind::Git => 6,
+            ErrorKind::Tmux => 7,
+            ErrorKind::StateCorruption => 8,
+            ErrorKind::Agent => 9,
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::Config => write!(f, "config"),
+            ErrorKind::BackendAuth => write!(f, "backend_auth"),
+            ErrorKind::BackendApi => write!(f, "backend_api"),
+            ErrorKind::Git => write!(f, "git"),
+            ErrorKind::Tmux => write!(f, "tmux"),
+            ErrorKind::StateCorruption => write!(f, "state_corruption"),
+            ErrorKind::Agent => write!(f, "agent"),
+            ErrorKind::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// Kind of the first recognized error in `err`'s cause chain.
+pub fn classify(err: &anyhow::Error) -> ErrorKind {
+    err.chain().find_map(kind_of).unwrap_or(ErrorKind::Other)
+}
+
+fn kind_of(cause: &(dyn std::error::Error + 'static)) -> Option<ErrorKind> {
+    if let Some(e) = cause.downcast_ref::<MobiusError>() {
+        return Some(e.kind());
+    }
+    if cause.is::<ConfigError>() {
+        return Some(ErrorKind::Config);
+    }
+    if let Some(e) = cause.downcast_ref::<LinearError>() {
+        return match e {
+            LinearError::MissingApiKey
+            | LinearError::AuthFailed
+            | LinearError::PermissionDenied => Some(ErrorKind::BackendAuth),
+            LinearError::Other(_) => None,
+            _ => Some(ErrorKind::BackendApi),
+        };
+    }
+    if let Some(e) = cause.downcast_ref::<JiraError>() {
+        return match e {
+            JiraError::MissingHost
+            | JiraError::MissingEmail
+            | JiraError::MissingApiToken
+            | JiraError::AuthFailed
+            | JiraError::PermissionDenied => Some(ErrorKind::BackendAuth),
+            JiraError::Other(_) => None,
+            _ => Some(ErrorKind::BackendApi),
+        };
+    }
+    if let Some(e) = cause.downcast_ref::<GithubError>() {
+        return match e {
+            GithubError::MissingToken | GithubError::AuthFailed | GithubError::PermissionDenied => {
+                Some(ErrorKind::BackendAuth)
+            }
+            GithubError::MissingRepository | GithubError::InvalidRepository(_) => {
+                Some(ErrorKind::Config)
+            }
+            GithubError::Other(_) => None,
+            _ => Some(ErrorKind::BackendApi),
+        };
+    }
+    if let Some(e) = cause.downcast_ref::<GitlabError>() {
+        return match e {
+            GitlabError::MissingToken | GitlabError::AuthFailed | GitlabError::PermissionDenied => {
+                Some(ErrorKind::BackendAuth)
+            }
+            GitlabError::MissingProject => Some(ErrorKind::Config),
+            GitlabError::Other(_) => None,
+            _ => Some(ErrorKind::BackendApi),
+        };
+    }
+    None
+}
+
+/// JSON printed in `--output json` mode: `{"error": {...}}`
+#[derive(Debug, Serialize)]
+struct ErrorEnvelope {
+    error: ErrorReport,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorReport {
+    kind: ErrorKind,
+    message: String,
+    /// Full cause chain, outermost first
+    causes: Vec<String>,
+    exit_code: i32,
+}
+
+/// Print a failed command's error to stderr and exit with its kind's code.
+pub fn exit_with(command: &str, err: anyhow::Error, output: OutputFormat) -> ! {
+    let kind = classify(&err);
+    if output.is_json() {
+        let report = ErrorEnvelope {
+            error: ErrorReport {
+                kind,
+                message: err.to_string(),
+                causes: err.chain().map(ToString::to_string).collect(),
+                exit_code: kind.exit_code(),
+            },
+        };
+        match to_json(&report) {
+            Ok(json) => eprintln!("{}", json),
+            Err(_) => eprintln!("{} error: {}", command, err),
+        }
+    } else {
+        eprintln!("{} error: {}", command, err);
+    }
+    std::process::exit(kind.exit_code());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_classify_walks_the_cause_chain() {
+        let err = anyhow::Error::new(MobiusError::Tmux("tmux new-session failed".to_string()))
+            .context("Failed to start loop");
+        assert_eq!(classify(&err), ErrorKind::Tmux);
+        assert_eq!(classify(&err).exit_code(), 7);
+
+        let err: anyhow::Result<()> =
+            Err(JiraError::AuthFailed).context("Failed to fetch MOB-1 from Jira");
+        assert_eq!(classify(&err.unwrap_err()), ErrorKind::BackendAuth);
+
+        let err = anyhow::Error::new(GithubError::HttpError {
+            status: 502,
+            message: "Bad Gateway".to_string(),
+        });
+        assert_eq!(classify(&err), ErrorKind::BackendApi);
+
+        let err = anyhow::Error::new(ConfigError::ParseError("bad yaml".to_string()));
+        assert_eq!(classify(&err), ErrorKind::Config);
+
+        assert_eq!(classify(&anyhow::anyhow!("boom")), ErrorKind::Other);
+        assert_eq!(ErrorKind::Other.exit_code(), 1);
+    }
+
+    #[test]
+    fn test_error_kind_names() {
+        assert_eq!(
+            serde_json::to_value(ErrorKind::StateCorruption).unwrap(),
+            "state_corruption"
+        );
+        assert_eq!(ErrorKind::BackendAuth.to_string(), "backend_auth");
+    }
+}
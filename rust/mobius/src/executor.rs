@@ -1,19 +1,33 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 use anyhow::Result;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use tokio::time::{sleep, Duration};
+use tracing::Instrument;
 
+use crate::attribution::{apply_attribution, get_hooks_dir};
+use crate::build_cache::{plan_caches, with_cache_env};
+use crate::completion::{
+    agent_result_to_execution, clear_agent_result, read_agent_result, result_file_for,
+    with_result_file_env, CompletionWatcher,
+};
+use crate::debug_logger::debug_log;
+use crate::resource_limits::with_resource_limits;
 use crate::runtime_adapter;
-use crate::stream_json;
+use crate::sandbox::{Container, Sandbox};
+use crate::shutdown;
+use crate::stream_json::{self, StreamTail};
+use crate::telemetry;
 use crate::tmux::{
     capture_pane_content, create_agent_pane, interrupt_pane, kill_pane, layout_panes, run_in_pane,
     set_pane_title, TmuxPane, TmuxSession,
 };
-use crate::types::enums::Model;
+use crate::types::enums::{CompletionProtocol, DebugEventSource, DebugEventType, Model};
 use crate::types::AgentRuntime;
-use crate::types::{ExecutionConfig, SubTask};
+use crate::types::{ExecutionConfig, SkillRule, SubTask};
 
 /// Verification skill identifier
 const VERIFICATION_SKILL: &str = "/verify";
@@ -24,11 +38,37 @@ const EXECUTE_SKILL: &str = "/execute";
 /// Polling interval for checking agent completion (2 seconds)
 const POLL_INTERVAL_MS: u64 = 2000;
 
+/// Safety-net re-check while a file watcher is delivering completion events
+const WATCH_FALLBACK_INTERVAL_MS: u64 = 10_000;
+
+/// Error recorded for agents stopped by SIGINT/SIGTERM
+pub(crate) const INTERRUPTED_ERROR: &str = "Interrupted by signal";
+
+/// Pane output kept per debug snapshot
+const PANE_SNAPSHOT_BYTES: usize = 8 * 1024;
+
+/// Error recorded when an agent's session ends without any completion status
+const NO_STATUS_ERROR: &str = "Agent exited without reporting a completion status";
+
 /// Default timeout per agent (30 minutes)
-const DEFAULT_TIMEOUT_MS: u64 = 30 * 60 * 1000;
+pub(crate) const DEFAULT_TIMEOUT_MS: u64 = 30 * 60 * 1000;
+
+/// Default agent timeout: `execution.task_timeout_minutes`, else 30 minutes.
+pub(crate) fn config_timeout_ms(config: &ExecutionConfig) -> u64 {
+    config
+        .task_timeout_minutes
+        .filter(|m| *m > 0)
+        .map_or(DEFAULT_TIMEOUT_MS, |m| u64::from(m) * 60 * 1000)
+}
+
+/// Timeout for `task`: its `### Timeout` override, else `default_ms`.
+pub(crate) fn task_timeout_ms(task: &SubTask, default_ms: u64) -> u64 {
+    task.timeout_minutes
+        .map_or(default_ms, |m| u64::from(m) * 60 * 1000)
+}
 
 /// Status patterns for detecting agent completion in pane output
-struct StatusPatterns {
+pub(crate) struct StatusPatterns {
     subtask_complete: Regex,
     verification_failed: Regex,
     all_complete: Regex,
@@ -38,7 +78,7 @@ struct StatusPatterns {
 }
 
 impl StatusPatterns {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             subtask_complete: status_regex("SUBTASK_COMPLETE"),
             verification_failed: status_regex("VERIFICATION_FAILED"),
@@ -85,10 +125,12 @@ pub struct TokenUsage {
 }
 
 /// Status of an execution result
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ExecutionStatus {
     SubtaskComplete,
     VerificationFailed,
+    Timeout,
     Error,
 }
 
@@ -101,6 +143,9 @@ struct AgentHandle {
     #[allow(dead_code)]
     command: String,
     output_file: Option<PathBuf>,
+    result_file: Option<PathBuf>,
+    protocol: CompletionProtocol,
+    container: Option<Container>,
 }
 
 /// Aggregated results from a batch of executions
@@ -115,8 +160,15 @@ pub struct AggregatedResults {
 
 /// Select the appropriate skill for a task.
 ///
+/// The first matching `execution.skill_rules` entry wins; otherwise
 /// Verification Gate tasks use `/verify`, all others use `/execute`.
-pub fn select_skill_for_task(task: &SubTask) -> &str {
+pub fn select_skill_for_task<'a>(task: &SubTask, rules: &'a [SkillRule]) -> &'a str {
+    if let Some(rule) = rules
+        .iter()
+        .find(|r| r.matches(&task.identifier, &task.title, &task.labels))
+    {
+        return &rule.skill;
+    }
     let title_lower = task.title.to_lowercase();
     if title_lower.contains("verification") && title_lower.contains("gate") {
         VERIFICATION_SKILL
@@ -193,6 +245,176 @@ pub fn build_claude_command(
     )
 }
 
+/// Shell command for one agent plus the files it reports through.
+pub(crate) struct AgentCommand {
+    pub command: String,
+    /// Stream-json output file (Claude only, when `output_dir` is set)
+    pub output_file: Option<PathBuf>,
+    /// `$MOBIUS_RESULT_FILE` path, unless the protocol is `capture`
+    pub result_file: Option<PathBuf>,
+    /// Completion comes from the exit status alone (see
+    /// [`runtime_adapter::completes_on_exit`])
+    pub completes_on_exit: bool,
+    /// Sandbox container the agent runs in, if any
+    pub container: Option<Container>,
+}
+
+/// Runtime and model a sub-task executes with after per-task routing.
+pub(crate) struct TaskRuntime<'a> {
+    pub runtime: AgentRuntime,
+    /// Model passed to the runtime CLI
+    pub model: String,
+    pub model_override: Option<&'a str>,
+    pub thinking_level_override: Option<&'a str>,
+}
+
+/// Route `task` to its runtime (see [`runtime_adapter::route_runtime`]).
+///
+/// The CLI model and thinking overrides target `default_runtime`, so they only
+/// apply to tasks that stay on it; a rule's model applies to its runtime.
+pub(crate) fn resolve_task_runtime<'a>(
+    task: &SubTask,
+    config: &'a ExecutionConfig,
+    default_runtime: AgentRuntime,
+    model_override: Option<&'a str>,
+    thinking_level_override: Option<&'a str>,
+) -> TaskRuntime<'a> {
+    let route = runtime_adapter::route_runtime(task.scoring.as_ref(), config, default_runtime);
+    let (model_override, thinking_level_override) = if route.runtime == default_runtime {
+        (model_override, thinking_level_override)
+    } else {
+        (None, None)
+    };
+    let model_override = route.model.or(model_override);
+
+    let model = match route.runtime {
+        AgentRuntime::Claude => {
+            let default_model = config.model.parse::<Model>().unwrap_or_default();
+            route
+                .model
+                .and_then(|m| m.parse::<Model>().ok())
+                .unwrap_or_else(|| select_model_for_task(task, default_model))
+                .to_string()
+        }
+        AgentRuntime::Opencode | AgentRuntime::Codex | AgentRuntime::Aider => {
+            runtime_adapter::effective_model_for_runtime(route.runtime, config, model_override)
+        }
+    };
+
+    TaskRuntime {
+        runtime: route.runtime,
+        model,
+        model_override,
+        thinking_level_override,
+    }
+}
+
+/// Build the shell command that runs `task` under the configured runtime.
+///
+/// Any stale result file from a previous attempt is removed so the executor
+/// never mistakes it for this run's completion.
+pub(crate) fn build_agent_command(task: &SubTask, context: ExecutionContext<'_>) -> AgentCommand {
+    let skill = select_skill_for_task(task, &context.config.skill_rules);
+    let task_runtime = resolve_task_runtime(
+        task,
+        context.config,
+        context.runtime,
+        context.model_override,
+        context.thinking_level_override,
+    );
+    let runtime = task_runtime.runtime;
+    let output_file = if runtime == AgentRuntime::Claude {
+        context
+            .output_dir
+            .map(|dir| dir.join(format!("{}.jsonl", task.identifier)))
+    } else {
+        None
+    };
+    let output_file_str = output_file
+        .as_ref()
+        .map(|p| p.to_string_lossy().to_string());
+
+    let command = if runtime == AgentRuntime::Claude {
+        build_claude_command(
+            &task.identifier,
+            skill,
+            context.worktree_path,
+            context.config,
+            context.context_file_path,
+            task_runtime.model.parse::<Model>().unwrap_or_default(),
+            output_file_str.as_deref(),
+        )
+    } else {
+        let options = runtime_adapter::ExecutionCommand {
+            subtask_identifier: &task.identifier,
+            skill,
+            worktree_path: context.worktree_path,
+            config: context.config,
+            context_file_path: context.context_file_path,
+            model_override: task_runtime.model_override,
+            thinking_level_override: task_runtime.thinking_level_override,
+        };
+        build_runtime_command(runtime, &options)
+    };
+    let command = apply_attribution(
+        &command,
+        &context.config.attribution,
+        runtime,
+        &task_runtime.model,
+        context.worktree_path,
+    );
+
+    let result_file = context
+        .result_dir
+        .filter(|_| context.config.completion_protocol != CompletionProtocol::Capture)
+        .map(|dir| result_file_for(dir, &task.identifier));
+    let command = match result_file {
+        Some(ref path) => {
+            clear_agent_result(path);
+            with_result_file_env(&command, path)
+        }
+        None => command,
+    };
+    let cache_plan = plan_caches(
+        &context.config.build_cache,
+        Path::new(context.worktree_path),
+    );
+    let command = match &cache_plan {
+        Some(plan) => with_cache_env(&command, plan),
+        None => command,
+    };
+    let sandbox = Sandbox::for_config(context.config);
+    let command = match &sandbox {
+        Some(sandbox) => {
+            let hooks_dir = get_hooks_dir();
+            let mounts: Vec<&Path> = [
+                context.result_dir,
+                context.output_dir,
+                Some(hooks_dir.as_path()).filter(|d| d.is_dir()),
+                cache_plan.as_ref().map(|plan| plan.root.as_path()),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            sandbox.wrap(
+                &command,
+                &task.identifier,
+                Path::new(context.worktree_path),
+                &mounts,
+            )
+        }
+        None => with_resource_limits(&command, &context.config.limits),
+    };
+
+    AgentCommand {
+        command,
+        output_file,
+        result_file,
+        completes_on_exit: runtime_adapter::completes_on_exit(runtime),
+        container: sandbox.map(|s| s.container(&task.identifier)),
+    }
+}
+
 /// Calculate the actual parallelism level given ready tasks and config.
 pub fn calculate_parallelism(ready_task_count: usize, config: &ExecutionConfig) -> usize {
     let max_parallel = config.max_parallel_agents.unwrap_or(3) as usize;
@@ -208,6 +430,8 @@ pub struct ExecutionContext<'a> {
     pub model_override: Option<&'a str>,
     pub thinking_level_override: Option<&'a str>,
     pub output_dir: Option<&'a Path>,
+    /// Directory for `$MOBIUS_RESULT_FILE` completion results
+    pub result_dir: Option<&'a Path>,
 }
 
 /// Execute tasks in parallel using tmux panes.
@@ -221,7 +445,7 @@ pub async fn execute_parallel(
     context: ExecutionContext<'_>,
     timeout_ms: Option<u64>,
 ) -> Vec<ExecutionResult> {
-    let timeout = timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS);
+    let timeout = timeout_ms.unwrap_or_else(|| config_timeout_ms(context.config));
     let actual_parallelism = calculate_parallelism(tasks.len(), context.config);
 
     if actual_parallelism == 0 {
@@ -257,7 +481,17 @@ pub async fn execute_parallel(
     // Wait for all agents concurrently
     let futures: Vec<_> = handles
         .into_iter()
-        .map(|handle| wait_for_agent(handle, timeout))
+        .map(|handle| {
+            let timeout = task_timeout_ms(&handle.task, timeout);
+            let span = telemetry::agent_span(&handle.task, "tmux");
+            async move {
+                let result = wait_for_agent(handle, timeout)
+                    .instrument(span.clone())
+                    .await;
+                telemetry::record_agent_result(&span, &result);
+                result
+            }
+        })
         .collect();
 
     let settled = futures::future::join_all(futures).await;
@@ -271,42 +505,13 @@ pub async fn spawn_agent_in_pane(
     context: ExecutionContext<'_>,
 ) -> ExecutionResult {
     let start_time = Instant::now();
-    let skill = select_skill_for_task(task);
-    let output_file = if context.runtime == AgentRuntime::Claude {
-        context
-            .output_dir
-            .map(|dir| dir.join(format!("{}.jsonl", task.identifier)))
-    } else {
-        None
-    };
-    let output_file_str = output_file
-        .as_ref()
-        .map(|p| p.to_string_lossy().to_string());
-
-    let command = if context.runtime == AgentRuntime::Claude {
-        let default_model = context.config.model.parse::<Model>().unwrap_or_default();
-        let model = select_model_for_task(task, default_model);
-        build_claude_command(
-            &task.identifier,
-            skill,
-            context.worktree_path,
-            context.config,
-            context.context_file_path,
-            model,
-            output_file_str.as_deref(),
-        )
-    } else {
-        let options = runtime_adapter::ExecutionCommand {
-            subtask_identifier: &task.identifier,
-            skill,
-            worktree_path: context.worktree_path,
-            config: context.config,
-            context_file_path: context.context_file_path,
-            model_override: context.model_override,
-            thinking_level_override: context.thinking_level_override,
-        };
-        build_runtime_command(context.runtime, &options)
-    };
+    let AgentCommand {
+        command,
+        output_file,
+        result_file,
+        container,
+        ..
+    } = build_agent_command(task, context);
 
     run_in_pane(&pane.id, &command, true).await;
 
@@ -317,9 +522,13 @@ pub async fn spawn_agent_in_pane(
         is_primary: false,
         command,
         output_file,
+        result_file,
+        protocol: context.config.completion_protocol,
+        container,
     };
 
-    wait_for_agent(handle, DEFAULT_TIMEOUT_MS).await
+    let timeout = task_timeout_ms(task, config_timeout_ms(context.config));
+    wait_for_agent(handle, timeout).await
 }
 
 /// Check if an agent in a pane is still active (no completion status detected).
@@ -406,41 +615,13 @@ async fn spawn_agents(
             .await?
         };
 
-        let skill = select_skill_for_task(task);
-        let output_file = if context.runtime == AgentRuntime::Claude {
-            context
-                .output_dir
-                .map(|dir| dir.join(format!("{}.jsonl", task.identifier)))
-        } else {
-            None
-        };
-        let output_file_str = output_file
-            .as_ref()
-            .map(|p| p.to_string_lossy().to_string());
-        let command = if context.runtime == AgentRuntime::Claude {
-            let default_model = context.config.model.parse::<Model>().unwrap_or_default();
-            let model = select_model_for_task(task, default_model);
-            build_claude_command(
-                &task.identifier,
-                skill,
-                context.worktree_path,
-                context.config,
-                context.context_file_path,
-                model,
-                output_file_str.as_deref(),
-            )
-        } else {
-            let options = runtime_adapter::ExecutionCommand {
-                subtask_identifier: &task.identifier,
-                skill,
-                worktree_path: context.worktree_path,
-                config: context.config,
-                context_file_path: context.context_file_path,
-                model_override: context.model_override,
-                thinking_level_override: context.thinking_level_override,
-            };
-            build_runtime_command(context.runtime, &options)
-        };
+        let AgentCommand {
+            command,
+            output_file,
+            result_file,
+            container,
+            ..
+        } = build_agent_command(task, context);
 
         run_in_pane(&pane.id, &command, true).await;
 
@@ -451,17 +632,28 @@ async fn spawn_agents(
             is_primary: i == 0,
             command,
             output_file,
+            result_file,
+            protocol: context.config.completion_protocol,
+            container,
         });
     }
 
     Ok(handles)
 }
 
-/// Poll a pane for agent completion, returning the result when done or on timeout.
+/// Wait for an agent to report completion, returning the result when done or
+/// on timeout.
 async fn wait_for_agent(handle: AgentHandle, timeout_ms: u64) -> ExecutionResult {
     let deadline = Duration::from_millis(timeout_ms);
     let patterns = StatusPatterns::new();
-    let error_summary_re = Regex::new(r"### Error Summary\n([^\n]+)").unwrap();
+    let error_summary_re = error_summary_regex();
+    let watched: Vec<&Path> = [handle.result_file.as_deref(), handle.output_file.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect();
+    let watcher = CompletionWatcher::new(&watched);
+    let mut stream = handle.output_file.as_deref().map(StreamTail::new);
+    let needs_pane_capture = handle.protocol != CompletionProtocol::ResultFile && stream.is_none();
 
     loop {
         let elapsed = handle.start_time.elapsed();
@@ -480,13 +672,17 @@ async fn wait_for_agent(handle: AgentHandle, timeout_ms: u64) -> ExecutionResult
             if !handle.is_primary {
                 kill_pane(&handle.pane.id).await;
             }
+            if let Some(container) = &handle.container {
+                container.remove().await;
+            }
             let timeout_output = capture_pane_content(&handle.pane.id, 200).await;
+            log_pane_capture(&handle, "timeout", &timeout_output);
 
             return ExecutionResult {
                 task_id: handle.task.id.clone(),
                 identifier: handle.task.identifier.clone(),
                 success: false,
-                status: ExecutionStatus::Error,
+                status: ExecutionStatus::Timeout,
                 token_usage: None,
                 duration_ms: elapsed.as_millis() as u64,
                 error: Some(format!(
@@ -504,16 +700,67 @@ async fn wait_for_agent(handle: AgentHandle, timeout_ms: u64) -> ExecutionResult
             };
         }
 
-        let content = capture_pane_content(&handle.pane.id, 200).await;
+        if shutdown::is_interrupted() {
+            interrupt_pane(&handle.pane.id).await;
+            if let Some(container) = &handle.container {
+                container.remove().await;
+            }
+            let title = format!("\u{2717} {}: INTERRUPTED", handle.task.identifier);
+            set_pane_title(&handle.pane.id, &title).await;
+            let tokens = handle
+                .output_file
+                .as_ref()
+                .and_then(|f| stream_json::parse_current_tokens(f));
 
-        if let Some(mut result) = parse_agent_output(
-            &content,
-            &handle.task,
-            handle.start_time,
-            &handle.pane.id,
-            &patterns,
-            &error_summary_re,
-        ) {
+            return ExecutionResult {
+                task_id: handle.task.id.clone(),
+                identifier: handle.task.identifier.clone(),
+                success: false,
+                status: ExecutionStatus::Error,
+                token_usage: None,
+                duration_ms: elapsed.as_millis() as u64,
+                error: Some(INTERRUPTED_ERROR.to_string()),
+                pane_id: Some(handle.pane.id.clone()),
+                raw_output: None,
+                input_tokens: tokens.as_ref().map(|t| t.input_tokens),
+                output_tokens: tokens.as_ref().map(|t| t.output_tokens),
+            };
+        }
+
+        // Prefer the structured result file; scrape pane output only as a fallback.
+        let reported = handle
+            .result_file
+            .as_deref()
+            .and_then(read_agent_result)
+            .map(|r| {
+                agent_result_to_execution(
+                    &r,
+                    &handle.task,
+                    handle.start_time,
+                    Some(&handle.pane.id),
+                )
+            });
+        let parsed = match reported {
+            Some(result) => Some(result),
+            None if handle.protocol != CompletionProtocol::ResultFile => {
+                scrape_agent_status(&handle, stream.as_mut(), &patterns, &error_summary_re).await
+            }
+            None => None,
+        };
+
+        if let Some(mut result) = parsed {
+            // Keep the final pane content for the output archive
+            if result.raw_output.is_none() {
+                let content = capture_pane_content(&handle.pane.id, 200).await;
+                result.raw_output = (!content.is_empty()).then_some(content);
+            }
+            if let Some(content) = &result.raw_output {
+                log_pane_capture(
+                    &handle,
+                    &format!("{:?}", result.status).to_lowercase(),
+                    content,
+                );
+            }
             // Extract final token usage from output file
             if let Some(ref output_file) = handle.output_file {
                 let tokens = stream_json::parse_final_tokens(output_file)
@@ -535,14 +782,115 @@ async fn wait_for_agent(handle: AgentHandle, timeout_ms: u64) -> ExecutionResult
             return result;
         }
 
-        sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+        // Files wake us as soon as they change; the timer only covers missed
+        // events and runtimes whose status is visible in the pane alone.
+        let interval = if watcher.is_some() && !needs_pane_capture {
+            WATCH_FALLBACK_INTERVAL_MS
+        } else {
+            POLL_INTERVAL_MS
+        };
+        let wait = Duration::from_millis(interval).min(deadline.saturating_sub(elapsed));
+        tokio::select! {
+            _ = sleep(wait) => {}
+            _ = async {
+                match &watcher {
+                    Some(w) => w.changed().await,
+                    None => std::future::pending().await,
+                }
+            } => {}
+            _ = shutdown::interrupted() => {}
+        }
+    }
+}
+
+/// Look for STATUS markers when the agent has not written a result file.
+///
+/// With a stream-json transcript the markers are matched against everything
+/// the agent has said, so they can't scroll out of view; the pane is only
+/// captured once the transcript has ended without one. Runtimes without a
+/// transcript fall back to capturing the pane.
+async fn scrape_agent_status(
+    handle: &AgentHandle,
+    stream: Option<&mut StreamTail>,
+    patterns: &StatusPatterns,
+    error_summary_re: &Regex,
+) -> Option<ExecutionResult> {
+    let parse = |content: &str| {
+        parse_agent_output(
+            content,
+            &handle.task,
+            handle.start_time,
+            &handle.pane.id,
+            patterns,
+            error_summary_re,
+        )
+    };
+
+    let Some(tail) = stream else {
+        let content = capture_pane_content(&handle.pane.id, 200).await;
+        return parse(&content);
+    };
+    tail.poll();
+    if let Some(result) = parse(tail.text()) {
+        return Some(result);
+    }
+    if !tail.is_finished() {
+        return None;
     }
+    // The result file may have landed just after it was last checked
+    if let Some(reported) = handle.result_file.as_deref().and_then(read_agent_result) {
+        return Some(agent_result_to_execution(
+            &reported,
+            &handle.task,
+            handle.start_time,
+            Some(&handle.pane.id),
+        ));
+    }
+    let content = capture_pane_content(&handle.pane.id, 200).await;
+    parse(&content).or_else(|| {
+        Some(ExecutionResult {
+            task_id: handle.task.id.clone(),
+            identifier: handle.task.identifier.clone(),
+            success: false,
+            status: ExecutionStatus::Error,
+            token_usage: None,
+            duration_ms: handle.start_time.elapsed().as_millis() as u64,
+            error: Some(NO_STATUS_ERROR.to_string()),
+            pane_id: Some(handle.pane.id.clone()),
+            raw_output: (!content.is_empty()).then_some(content),
+            input_tokens: None,
+            output_tokens: None,
+        })
+    })
+}
+
+/// Record the end of an agent's pane output in the debug log (`--debug verbose`).
+fn log_pane_capture(handle: &AgentHandle, reason: &str, content: &str) {
+    let mut start = content.len().saturating_sub(PANE_SNAPSHOT_BYTES);
+    while !content.is_char_boundary(start) {
+        start += 1;
+    }
+    debug_log(
+        DebugEventType::PaneCapture,
+        DebugEventSource::Executor,
+        Some(&handle.task.identifier),
+        HashMap::from([
+            ("pane".to_string(), serde_json::json!(handle.pane.id)),
+            ("reason".to_string(), serde_json::json!(reason)),
+            ("content".to_string(), serde_json::json!(&content[start..])),
+        ]),
+    );
+}
+
+/// Regex capturing the first line of an agent's `### Error Summary` section.
+pub(crate) fn error_summary_regex() -> Regex {
+    Regex::new(r"### Error Summary\n([^\n]+)").unwrap()
 }
 
 /// Parse captured pane content for completion status patterns.
 ///
 /// Returns `None` if no completion pattern is found (agent still running).
-fn parse_agent_output(
+pub(crate) fn parse_agent_output(
     content: &str,
     task: &SubTask,
     start_time: Instant,
@@ -551,6 +899,8 @@ fn parse_agent_output(
     error_summary_re: &Regex,
 ) -> Option<ExecutionResult> {
     let duration_ms = start_time.elapsed().as_millis() as u64;
+    let translated = runtime_adapter::translate_codex_output(content);
+    let content = translated.as_str();
     let token_usage = parse_token_usage(content);
 
     // Check for successful completion
@@ -687,7 +1037,7 @@ fn parse_token_usage(content: &str) -> Option<TokenUsage> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::TaskStatus;
+    use crate::types::{TaskStatus, TaskType};
 
     fn make_task(id: &str, identifier: &str, title: &str) -> SubTask {
         SubTask {
@@ -699,32 +1049,84 @@ mod tests {
             blocks: vec![],
             git_branch_name: String::new(),
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         }
     }
 
     #[test]
     fn test_select_skill_for_task_execute() {
         let task = make_task("1", "MOB-101", "Implement feature X");
-        assert_eq!(select_skill_for_task(&task), "/execute");
+        assert_eq!(select_skill_for_task(&task, &[]), "/execute");
+    }
+
+    #[test]
+    fn test_task_timeout_overrides() {
+        let mut config = ExecutionConfig::default();
+        assert_eq!(config_timeout_ms(&config), DEFAULT_TIMEOUT_MS);
+        config.task_timeout_minutes = Some(45);
+        let default_ms = config_timeout_ms(&config);
+        assert_eq!(default_ms, 45 * 60 * 1000);
+
+        let mut task = make_task("1", "MOB-101", "Long migration");
+        assert_eq!(task_timeout_ms(&task, default_ms), default_ms);
+        task.timeout_minutes = Some(90);
+        assert_eq!(task_timeout_ms(&task, default_ms), 90 * 60 * 1000);
+    }
+
+    #[test]
+    fn test_select_skill_for_task_rules() {
+        let rule = |label: Option<&str>, title: Option<&str>, prefix: Option<&str>, skill: &str| {
+            SkillRule {
+                label: label.map(String::from),
+                title: title.map(String::from),
+                identifier_prefix: prefix.map(String::from),
+                skill: skill.to_string(),
+            }
+        };
+        let rules = vec![
+            rule(Some("docs"), None, None, "/docs"),
+            rule(None, Some(r"^migrate\b"), None, "/migrate"),
+            rule(None, None, Some("OPS-"), "/ops"),
+            rule(None, Some("(unclosed"), None, "/never"),
+        ];
+
+        let mut task = make_task("1", "MOB-101", "Write API reference");
+        task.labels = vec!["Docs".to_string()];
+        assert_eq!(select_skill_for_task(&task, &rules), "/docs");
+
+        let task = make_task("2", "MOB-102", "Migrate users table");
+        assert_eq!(select_skill_for_task(&task, &rules), "/migrate");
+
+        let task = make_task("3", "ops-7", "Rotate keys");
+        assert_eq!(select_skill_for_task(&task, &rules), "/ops");
+
+        // Unmatched tasks keep the built-in routing
+        let task = make_task("4", "MOB-104", "Verification Gate");
+        assert_eq!(select_skill_for_task(&task, &rules), "/verify");
+        let task = make_task("5", "MOB-105", "Add (unclosed paren");
+        assert_eq!(select_skill_for_task(&task, &rules), "/execute");
     }
 
     #[test]
     fn test_select_skill_for_task_verify() {
         let task = make_task("vg", "MOB-VG", "[MOB-100] Verification Gate");
-        assert_eq!(select_skill_for_task(&task), "/verify");
+        assert_eq!(select_skill_for_task(&task, &[]), "/verify");
     }
 
     #[test]
     fn test_select_skill_for_task_verify_case_insensitive() {
         let task = make_task("vg", "MOB-VG", "VERIFICATION GATE for MOB-100");
-        assert_eq!(select_skill_for_task(&task), "/verify");
+        assert_eq!(select_skill_for_task(&task, &[]), "/verify");
     }
 
     #[test]
     fn test_select_skill_for_task_partial_match() {
         // Must contain both "verification" AND "gate"
         let task = make_task("1", "MOB-101", "Verification of types");
-        assert_eq!(select_skill_for_task(&task), "/execute");
+        assert_eq!(select_skill_for_task(&task, &[]), "/execute");
     }
 
     #[test]
@@ -827,8 +1229,10 @@ mod tests {
 
     #[test]
     fn test_build_claude_command_with_disallowed_tools() {
-        let mut config = ExecutionConfig::default();
-        config.disallowed_tools = Some(vec!["Bash".to_string(), "Write".to_string()]);
+        let config = ExecutionConfig {
+            disallowed_tools: Some(vec!["Bash".to_string(), "Write".to_string()]),
+            ..ExecutionConfig::default()
+        };
 
         let cmd = build_claude_command(
             "MOB-101",
@@ -845,8 +1249,10 @@ mod tests {
 
     #[test]
     fn test_build_claude_command_without_disallowed_tools() {
-        let mut config = ExecutionConfig::default();
-        config.disallowed_tools = None;
+        let config = ExecutionConfig {
+            disallowed_tools: None,
+            ..ExecutionConfig::default()
+        };
 
         let cmd = build_claude_command(
             "MOB-101",
@@ -959,6 +1365,27 @@ mod tests {
         assert_eq!(result.status, ExecutionStatus::SubtaskComplete);
     }
 
+    #[test]
+    fn test_parse_agent_output_codex_json_events() {
+        let task = make_task("1", "MOB-101", "Test task");
+        let patterns = StatusPatterns::new();
+        let start = Instant::now();
+
+        let content = r#"{"type":"item.completed","item":{"id":"i3","type":"agent_message","text":"STATUS: VERIFICATION_FAILED\n### Error Summary\ncargo test failed"}}"#;
+        let result = parse_agent_output(
+            content,
+            &task,
+            start,
+            "%0",
+            &patterns,
+            &error_summary_regex(),
+        )
+        .unwrap();
+
+        assert_eq!(result.status, ExecutionStatus::VerificationFailed);
+        assert_eq!(result.error.as_deref(), Some("cargo test failed"));
+    }
+
     #[test]
     fn test_parse_agent_output_execution_complete() {
         let task = make_task("1", "MOB-101", "Test task");
@@ -1133,8 +1560,10 @@ mod tests {
 
     #[test]
     fn test_calculate_parallelism_no_config() {
-        let mut config = ExecutionConfig::default();
-        config.max_parallel_agents = None;
+        let config = ExecutionConfig {
+            max_parallel_agents: None,
+            ..ExecutionConfig::default()
+        };
         // Default fallback is 3
         assert_eq!(calculate_parallelism(5, &config), 3);
     }
@@ -1238,8 +1667,10 @@ mod tests {
 
     #[test]
     fn test_build_claude_command_empty_disallowed_tools() {
-        let mut config = ExecutionConfig::default();
-        config.disallowed_tools = Some(vec![]);
+        let config = ExecutionConfig {
+            disallowed_tools: Some(vec![]),
+            ..ExecutionConfig::default()
+        };
 
         let cmd = build_claude_command(
             "MOB-101",
@@ -1469,7 +1900,7 @@ mod tests {
 
     #[test]
     fn test_aggregate_results_all_failure() {
-        let errors = vec![
+        let errors = [
             "Type mismatch in foo.rs",
             "Test assertion failed",
             "Lint error: unused variable",
@@ -1586,6 +2017,7 @@ mod tests {
             risk: 1,
             recommended_model: Model::Haiku,
             rationale: "Simple task".to_string(),
+            recommended_runtime: None,
         });
 
         let model = select_model_for_task(&task, Model::Opus);
@@ -1613,8 +2045,70 @@ mod tests {
                 risk: 3,
                 recommended_model: expected_model,
                 rationale: "Test".to_string(),
+                recommended_runtime: None,
             });
             assert_eq!(select_model_for_task(&task, Model::Opus), expected_model);
         }
     }
+
+    // --- resolve_task_runtime Tests ---
+
+    fn scored_task(complexity: u8) -> SubTask {
+        use crate::types::task_graph::TaskScoring;
+
+        let mut task = make_task("1", "MOB-101", "Task");
+        task.scoring = Some(TaskScoring {
+            complexity,
+            risk: 2,
+            recommended_model: Model::Sonnet,
+            rationale: "Test".to_string(),
+            recommended_runtime: None,
+        });
+        task
+    }
+
+    #[test]
+    fn test_resolve_task_runtime_uses_rule_model() {
+        let config: ExecutionConfig = serde_yaml::from_str(
+            "runtime_rules:\n  - min_complexity: 7\n    runtime: claude\n    model: opus\n",
+        )
+        .unwrap();
+
+        let complex =
+            resolve_task_runtime(&scored_task(8), &config, AgentRuntime::Claude, None, None);
+        assert_eq!(complex.runtime, AgentRuntime::Claude);
+        assert_eq!(complex.model, "opus");
+
+        // Unmatched tasks keep their scored model
+        let simple =
+            resolve_task_runtime(&scored_task(3), &config, AgentRuntime::Claude, None, None);
+        assert_eq!(simple.model, "sonnet");
+    }
+
+    #[test]
+    fn test_resolve_task_runtime_rule_model_wins_over_cli_override() {
+        let config: ExecutionConfig = serde_yaml::from_str(
+            "model: openai/gpt-5.3-codex\nruntime_rules:\n  - max_complexity: 4\n    runtime: opencode\n    model: openai/gpt-5-mini\n",
+        )
+        .unwrap();
+
+        let simple = resolve_task_runtime(
+            &scored_task(2),
+            &config,
+            AgentRuntime::Opencode,
+            Some("anthropic/claude-sonnet-4"),
+            Some("high"),
+        );
+        assert_eq!(simple.model, "openai/gpt-5-mini");
+        assert_eq!(simple.thinking_level_override, Some("high"));
+
+        let complex = resolve_task_runtime(
+            &scored_task(9),
+            &config,
+            AgentRuntime::Opencode,
+            Some("anthropic/claude-sonnet-4"),
+            None,
+        );
+        assert_eq!(complex.model, "anthropic/claude-sonnet-4");
+    }
 }
@@ -0,0 +1,136 @@
+//! External URL blockers.
+//!
+//! A sub-task may list a URL in `blockedBy` (another repository's pull request,
+//! a vendor ticket). Unlike blockers that are simply outside the graph, these
+//! keep the task blocked until they are resolved, either manually with
+//! `mobius deps resolve` or automatically once a linked GitHub pull request is
+//! merged. Resolutions are stored in
+//! `.mobius/issues/{issueId}/external-blockers.json`.
+
+use std::collections::HashSet;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use tracing::warn;
+
+use crate::github::{GithubClient, GithubError};
+use crate::local_state::{read_resolved_blockers, write_resolved_blockers, BlockerResolution};
+use crate::types::task_graph::{get_external_blockers, resolve_external_blockers, TaskGraph};
+
+/// A pull request addressed by a `github.com/{owner}/{repo}/pull/{number}` URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GithubPullRef {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+}
+
+/// Parse a GitHub pull request URL. Trailing paths (`/files`) and fragments are ignored.
+pub fn parse_github_pull_url(url: &str) -> Option<GithubPullRef> {
+    static PULL_URL: OnceLock<Regex> = OnceLock::new();
+    let re = PULL_URL.get_or_init(|| {
+        Regex::new(r"^https?://(?:www\.)?github\.com/([^/\s]+)/([^/\s]+)/pull/(\d+)(?:[/?#].*)?$")
+            .unwrap()
+    });
+    let caps = re.captures(url.trim())?;
+    Some(GithubPullRef {
+        owner: caps[1].to_string(),
+        repo: caps[2].to_string(),
+        number: caps[3].parse().ok()?,
+    })
+}
+
+/// URLs already marked as resolved for an issue
+pub fn resolved_urls(issue_id: &str) -> HashSet<String> {
+    read_resolved_blockers(issue_id)
+        .into_iter()
+        .map(|r| r.url)
+        .collect()
+}
+
+/// Unresolved external blocker URLs in the graph, deduplicated and sorted
+pub fn pending_urls(graph: &TaskGraph) -> Vec<String> {
+    let mut urls: Vec<String> = get_external_blockers(graph)
+        .into_iter()
+        .map(|(_, url)| url.to_string())
+        .collect();
+    urls.sort();
+    urls.dedup();
+    urls
+}
+
+/// Apply recorded resolutions without contacting GitHub.
+pub fn apply_resolved_blockers(issue_id: &str, graph: &TaskGraph) -> TaskGraph {
+    resolve_external_blockers(graph, &resolved_urls(issue_id))
+}
+
+/// Record merged GitHub pull requests as resolved, then apply all resolutions.
+///
+/// Lookup failures (no token, private repository, network) leave the blocker
+/// in place and are logged. Offline, only blockers already resolved apply.
+pub async fn refresh_external_blockers(issue_id: &str, graph: &TaskGraph) -> TaskGraph {
+    let graph = apply_resolved_blockers(issue_id, graph);
+    let pending = pending_urls(&graph);
+    if pending.is_empty() || crate::offline::is_offline() {
+        return graph;
+    }
+
+    let merged = find_merged_pulls(&pending).await;
+    if merged.is_empty() {
+        return graph;
+    }
+    if let Err(e) = write_resolved_blockers(issue_id, &merged, BlockerResolution::Merged) {
+        warn!("Failed to record merged external blockers: {}", e);
+    }
+    resolve_external_blockers(&graph, &merged.into_iter().collect())
+}
+
+/// The subset of `urls` that are merged GitHub pull requests.
+pub async fn find_merged_pulls(urls: &[String]) -> Vec<String> {
+    let mut merged = Vec::new();
+    for url in urls {
+        let Some(pull) = parse_github_pull_url(url) else {
+            continue;
+        };
+        match is_pull_merged(&pull).await {
+            Ok(true) => merged.push(url.clone()),
+            Ok(false) => {}
+            Err(GithubError::MissingToken) => {
+                warn!("GITHUB_TOKEN is not set; cannot check {}", url);
+                break;
+            }
+            Err(e) => warn!("Failed to check external blocker {}: {}", url, e),
+        }
+    }
+    merged
+}
+
+async fn is_pull_merged(pull: &GithubPullRef) -> Result<bool, GithubError> {
+    GithubClient::for_repository(&pull.owner, &pull.repo)?
+        .fetch_pull_request_merged(pull.number)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_github_pull_url() {
+        assert_eq!(
+            parse_github_pull_url("https://github.com/acme/api/pull/42/files"),
+            Some(GithubPullRef {
+                owner: "acme".to_string(),
+                repo: "api".to_string(),
+                number: 42,
+            })
+        );
+        assert_eq!(
+            parse_github_pull_url("https://github.com/acme/api/pull/7#discussion_r1")
+                .map(|p| p.number),
+            Some(7)
+        );
+        assert!(parse_github_pull_url("https://github.com/acme/api/issues/42").is_none());
+        assert!(parse_github_pull_url("https://vendor.example.com/tickets/42").is_none());
+    }
+}
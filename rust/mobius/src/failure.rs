@@ -0,0 +1,261 @@
+//! Failure classification for agent attempts.
+//!
+//! Maps an attempt's execution status, the status the agent reported, its error
+//! text, and the stream-json `result` event onto a `FailureClass`, so failures
+//! can be counted by cause with `mobius stats --failures`. Provider incidents
+//! (overload, 5xx) are also detected so the loop can switch to
+//! `execution.fallback`.
+
+use crate::executor::ExecutionStatus;
+use crate::stream_json::ResultEvent;
+use crate::types::enums::{FailureClass, SkillOutputStatus};
+
+/// Output text scanned from the end of an agent's captured output
+const OUTPUT_TAIL_CHARS: usize = 4000;
+
+const RATE_LIMIT_PATTERNS: &[&str] = &[
+    "rate limit",
+    "rate_limit",
+    "ratelimit",
+    "too many requests",
+    "overloaded",
+    "usage limit",
+];
+
+/// Provider-side capacity problems and server errors, as opposed to the
+/// account hitting its own rate or usage limit
+const PROVIDER_INCIDENT_PATTERNS: &[&str] = &[
+    "overloaded",
+    "internal server error",
+    "service unavailable",
+    "bad gateway",
+    "gateway timeout",
+    "api error: 500",
+    "api error: 502",
+    "api error: 503",
+    "api error: 529",
+    "server_error",
+    "api_error",
+];
+
+const MERGE_CONFLICT_PATTERNS: &[&str] = &[
+    "merge conflict",
+    "conflict (content)",
+    "automatic merge failed",
+    "could not apply",
+    "unmerged paths",
+];
+
+const VERIFY_FAILURE_PATTERNS: &[&str] = &[
+    "verification_failed",
+    "tests failed",
+    "test failed",
+    "typecheck failed",
+    "lint failed",
+];
+
+const GAVE_UP_PATTERNS: &[&str] = &[
+    "subtask_partial",
+    "all_blocked",
+    "needs_work",
+    "giving up",
+    "unable to complete",
+];
+
+/// Everything known about a failed attempt
+#[derive(Debug, Clone, Copy)]
+pub struct FailureSignals<'a> {
+    pub status: &'a ExecutionStatus,
+    /// Status from `$MOBIUS_RESULT_FILE`, when the agent wrote one
+    pub reported_status: Option<SkillOutputStatus>,
+    pub error: Option<&'a str>,
+    /// Captured pane or process output
+    pub output: Option<&'a str>,
+    /// Final stream-json `result` event (Claude runtime only)
+    pub result_event: Option<&'a ResultEvent>,
+}
+
+/// Classify a failed attempt.
+///
+/// Infrastructure causes (timeout, rate limit, merge conflict) win over what
+/// the agent reported, since they usually explain a verify failure or an early
+/// stop rather than the other way around.
+pub fn classify_failure(signals: &FailureSignals<'_>) -> FailureClass {
+    if *signals.status == ExecutionStatus::Timeout {
+        return FailureClass::Timeout;
+    }
+
+    let text = signal_text(signals);
+    let mentions = |patterns: &[&str]| patterns.iter().any(|p| text.contains(p));
+
+    if mentions(RATE_LIMIT_PATTERNS) {
+        return FailureClass::RateLimit;
+    }
+    if mentions(MERGE_CONFLICT_PATTERNS) {
+        return FailureClass::MergeConflict;
+    }
+    if *signals.status == ExecutionStatus::VerificationFailed
+        || matches!(
+            signals.reported_status,
+            Some(SkillOutputStatus::VerificationFailed | SkillOutputStatus::Fail)
+        )
+        || mentions(VERIFY_FAILURE_PATTERNS)
+    {
+        return FailureClass::VerifyFailure;
+    }
+
+    let out_of_turns = signals
+        .result_event
+        .and_then(|e| e.subtype.as_deref())
+        .is_some_and(|subtype| subtype == "error_max_turns");
+    if out_of_turns
+        || matches!(
+            signals.reported_status,
+            Some(
+                SkillOutputStatus::SubtaskPartial
+                    | SkillOutputStatus::AllBlocked
+                    | SkillOutputStatus::NeedsWork
+            )
+        )
+        || mentions(GAVE_UP_PATTERNS)
+    {
+        return FailureClass::AgentGaveUp;
+    }
+
+    FailureClass::Unknown
+}
+
+/// Whether a failed attempt was caused by the provider being overloaded or
+/// returning server errors.
+pub fn is_provider_incident(signals: &FailureSignals<'_>) -> bool {
+    if *signals.status == ExecutionStatus::Timeout {
+        return false;
+    }
+    let text = signal_text(signals);
+    PROVIDER_INCIDENT_PATTERNS.iter().any(|p| text.contains(p))
+}
+
+/// Lowercased error text, output tail, and result message.
+fn signal_text(signals: &FailureSignals<'_>) -> String {
+    let output_tail = signals.output.map(|output| {
+        let start = output
+            .char_indices()
+            .rev()
+            .nth(OUTPUT_TAIL_CHARS)
+            .map_or(0, |(i, _)| i);
+        &output[start..]
+    });
+    [
+        signals.error,
+        output_tail,
+        signals.result_event.and_then(|e| e.result.as_deref()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("\n")
+    .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signals(status: &ExecutionStatus) -> FailureSignals<'_> {
+        FailureSignals {
+            status,
+            reported_status: None,
+            error: None,
+            output: None,
+            result_event: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_failure_from_status_and_text() {
+        let error = ExecutionStatus::Error;
+        let verify = ExecutionStatus::VerificationFailed;
+
+        assert_eq!(
+            classify_failure(&signals(&ExecutionStatus::Timeout)),
+            FailureClass::Timeout
+        );
+        assert_eq!(
+            classify_failure(&signals(&verify)),
+            FailureClass::VerifyFailure
+        );
+        assert_eq!(
+            classify_failure(&FailureSignals {
+                error: Some("API Error: 429 Too Many Requests"),
+                ..signals(&verify)
+            }),
+            FailureClass::RateLimit
+        );
+        assert_eq!(
+            classify_failure(&FailureSignals {
+                output: Some(
+                    "Auto-merging src/lib.rs\nCONFLICT (content): Merge conflict in src/lib.rs"
+                ),
+                ..signals(&error)
+            }),
+            FailureClass::MergeConflict
+        );
+        assert_eq!(classify_failure(&signals(&error)), FailureClass::Unknown);
+    }
+
+    #[test]
+    fn test_classify_failure_agent_gave_up() {
+        let error = ExecutionStatus::Error;
+
+        assert_eq!(
+            classify_failure(&FailureSignals {
+                reported_status: Some(SkillOutputStatus::SubtaskPartial),
+                ..signals(&error)
+            }),
+            FailureClass::AgentGaveUp
+        );
+
+        let event = ResultEvent {
+            subtype: Some("error_max_turns".to_string()),
+            is_error: true,
+            result: None,
+        };
+        assert_eq!(
+            classify_failure(&FailureSignals {
+                result_event: Some(&event),
+                ..signals(&error)
+            }),
+            FailureClass::AgentGaveUp
+        );
+
+        // A reported verification failure outranks an early stop
+        assert_eq!(
+            classify_failure(&FailureSignals {
+                reported_status: Some(SkillOutputStatus::VerificationFailed),
+                result_event: Some(&event),
+                ..signals(&error)
+            }),
+            FailureClass::VerifyFailure
+        );
+    }
+
+    #[test]
+    fn test_is_provider_incident() {
+        let error = ExecutionStatus::Error;
+
+        assert!(is_provider_incident(&FailureSignals {
+            error: Some("API Error: 529 {\"type\":\"overloaded_error\"}"),
+            ..signals(&error)
+        }));
+        assert!(is_provider_incident(&FailureSignals {
+            output: Some("stream error: 503 Service Unavailable"),
+            ..signals(&error)
+        }));
+        // Hitting the account's own limits is not a provider incident
+        assert!(!is_provider_incident(&FailureSignals {
+            error: Some("API Error: 429 Too Many Requests"),
+            ..signals(&error)
+        }));
+        assert!(!is_provider_incident(&signals(&ExecutionStatus::Timeout)));
+    }
+}
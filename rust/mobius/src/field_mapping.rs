@@ -0,0 +1,476 @@
+//! Tracker field mapping for task scoring.
+//!
+//! Maps the backend fields configured under `field_mapping` onto
+//! `TaskScoring` and the sub-task verify command. `mobius pull` copies
+//! scoring authored in Linear or Jira into the local sub-task specs, and
+//! `mobius push --scoring` writes locally computed scoring back.
+//!
+//! Field names are backend field IDs (`customfield_10016`, `estimate`) or
+//! `label:<prefix>`, which reads the value from the first label starting
+//! with the prefix (e.g. `label:model:` matches `model:sonnet`). Label
+//! mappings are read-only.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde_json::Value;
+
+use crate::context::extract_verify_commands;
+use crate::jira::JiraClient;
+use crate::linear::LinearClient;
+use crate::local_state::{read_subtasks, write_subtask_spec};
+use crate::types::config::FieldMapping;
+use crate::types::context::SubTaskContext;
+use crate::types::enums::{Backend, Model};
+use crate::types::task_graph::TaskScoring;
+
+/// Raw backend field values keyed by field ID
+pub type FieldValues = HashMap<String, Value>;
+
+const LABEL_PREFIX: &str = "label:";
+const LABELS_FIELD: &str = "labels";
+
+/// Backends that support field mapping.
+pub fn supports_field_mapping(backend: Backend) -> bool {
+    matches!(backend, Backend::Linear | Backend::Jira)
+}
+
+/// Backend field IDs to request for `mapping`. Label mappings resolve to the
+/// `labels` field.
+pub fn mapped_field_ids(mapping: &FieldMapping) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for field in mapped_fields(mapping) {
+        let id = if field.starts_with(LABEL_PREFIX) {
+            LABELS_FIELD
+        } else {
+            field
+        };
+        if !ids.iter().any(|existing| existing == id) {
+            ids.push(id.to_string());
+        }
+    }
+    ids
+}
+
+/// Resolve a mapped field to text.
+///
+/// Jira option fields (`{"value": "opus"}`) yield their value; multi-value
+/// fields yield their first entry.
+pub fn field_text(fields: &FieldValues, field: &str) -> Option<String> {
+    if let Some(prefix) = field.strip_prefix(LABEL_PREFIX) {
+        return fields
+            .get(LABELS_FIELD)?
+            .as_array()?
+            .iter()
+            .filter_map(Value::as_str)
+            .find_map(|label| label.strip_prefix(prefix))
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+    }
+    value_text(fields.get(field)?)
+}
+
+/// Build scoring from backend fields, overlaying `existing` local scoring.
+///
+/// Mapped fields that are unset keep their local value. Returns `existing`
+/// unchanged when no mapped field has a usable value, or when complexity,
+/// risk and model cannot all be resolved.
+pub fn scoring_from_fields(
+    mapping: &FieldMapping,
+    fields: &FieldValues,
+    existing: Option<&TaskScoring>,
+    backend: Backend,
+) -> Option<TaskScoring> {
+    let read = |field: &Option<String>| field.as_deref().and_then(|f| field_text(fields, f));
+    let complexity = read(&mapping.complexity).and_then(|t| parse_score(&t));
+    let risk = read(&mapping.risk).and_then(|t| parse_score(&t));
+    let model = read(&mapping.recommended_model).and_then(|t| parse_model(&t));
+
+    if complexity.is_none() && risk.is_none() && model.is_none() {
+        return existing.cloned();
+    }
+
+    let merged = (|| {
+        Some(TaskScoring {
+            complexity: complexity.or(existing.map(|s| s.complexity))?,
+            risk: risk.or(existing.map(|s| s.risk))?,
+            recommended_model: model.or(existing.map(|s| s.recommended_model))?,
+            rationale: existing
+                .map(|s| s.rationale.clone())
+                .unwrap_or_else(|| format!("Scored in {}", backend)),
+            recommended_runtime: existing.and_then(|s| s.recommended_runtime),
+        })
+    })();
+    merged.or_else(|| existing.cloned())
+}
+
+/// Verify command from the mapped field, if any.
+pub fn verify_command_from_fields(mapping: &FieldMapping, fields: &FieldValues) -> Option<String> {
+    mapping
+        .verify_command
+        .as_deref()
+        .and_then(|f| field_text(fields, f))
+}
+
+/// Set the `### Verify Command` block of a sub-task description, replacing
+/// an existing block or appending a new one.
+pub fn with_verify_command(description: &str, command: &str) -> String {
+    let block = format!("### Verify Command\n```bash\n{}\n```", command.trim());
+    let pattern =
+        Regex::new(r"(?i)###\s+Verify\s+Command\s*\n\s*```bash\s*\n[\s\S]*?\n\s*```").unwrap();
+    if pattern.is_match(description) {
+        return pattern
+            .replace(description, regex::NoExpand(&block))
+            .into_owned();
+    }
+    if description.trim().is_empty() {
+        block
+    } else {
+        format!("{}\n\n{}", description.trim_end(), block)
+    }
+}
+
+/// Apply backend field values to a local sub-task. Returns true when the
+/// scoring or verify command changed.
+pub fn apply_fields_to_subtask(
+    task: &mut SubTaskContext,
+    mapping: &FieldMapping,
+    fields: &FieldValues,
+    backend: Backend,
+) -> bool {
+    let mut changed = false;
+
+    let scoring = scoring_from_fields(mapping, fields, task.scoring.as_ref(), backend);
+    if scoring != task.scoring {
+        task.scoring = scoring;
+        changed = true;
+    }
+
+    if let Some(command) = verify_command_from_fields(mapping, fields) {
+        let description = with_verify_command(&task.description, &command);
+        if description != task.description {
+            task.description = description;
+            changed = true;
+        }
+    }
+
+    changed
+}
+
+/// Field values to write back for a sub-task's local scoring and verify
+/// command. Label mappings are skipped.
+pub fn fields_from_subtask(mapping: &FieldMapping, task: &SubTaskContext) -> FieldValues {
+    let mut fields = FieldValues::new();
+    let mut set = |field: &Option<String>, value: Value| {
+        if let Some(id) = field.as_deref().filter(|f| !f.starts_with(LABEL_PREFIX)) {
+            fields.insert(id.to_string(), value);
+        }
+    };
+
+    if let Some(scoring) = &task.scoring {
+        set(&mapping.complexity, Value::from(scoring.complexity));
+        set(&mapping.risk, Value::from(scoring.risk));
+        set(
+            &mapping.recommended_model,
+            Value::from(scoring.recommended_model.to_string()),
+        );
+    }
+    if let Some(verify) = extract_verify_commands(std::slice::from_ref(task)).pop() {
+        set(&mapping.verify_command, Value::from(verify.command));
+    }
+
+    fields
+}
+
+/// Fetch the raw fields `mapping` needs for one issue.
+pub async fn fetch_backend_fields(
+    backend: Backend,
+    identifier: &str,
+    mapping: &FieldMapping,
+) -> Result<FieldValues> {
+    match backend {
+        Backend::Jira => Ok(JiraClient::new()?
+            .fetch_jira_issue_fields(identifier, &mapped_field_ids(mapping))
+            .await?),
+        Backend::Linear => Ok(LinearClient::new()?
+            .fetch_linear_issue_fields(identifier)
+            .await?),
+        Backend::Github | Backend::Gitlab | Backend::Local => {
+            anyhow::bail!("Field mapping is not supported for the {} backend", backend)
+        }
+    }
+}
+
+/// Write field values to one issue.
+pub async fn update_backend_fields(
+    backend: Backend,
+    identifier: &str,
+    fields: &FieldValues,
+) -> Result<()> {
+    match backend {
+        Backend::Jira => Ok(JiraClient::new()?
+            .update_jira_issue_fields(identifier, fields)
+            .await?),
+        Backend::Linear => Ok(LinearClient::new()?
+            .update_linear_issue_fields(identifier, fields)
+            .await?),
+        Backend::Github | Backend::Gitlab | Backend::Local => {
+            anyhow::bail!("Field mapping is not supported for the {} backend", backend)
+        }
+    }
+}
+
+/// Pull mapped fields for the local sub-tasks of `parent_id` and persist
+/// changed specs. Returns the number of sub-tasks updated.
+///
+/// With `only`, sub-tasks outside that set are left untouched. Sub-tasks
+/// without a backend identifier are skipped; per-issue fetch failures are
+/// logged and do not abort the sync.
+pub fn sync_fields_from_backend(
+    parent_id: &str,
+    backend: Backend,
+    mapping: &FieldMapping,
+    only: Option<&HashSet<String>>,
+) -> Result<usize> {
+    let rt = tokio::runtime::Runtime::new().context("failed to start async runtime")?;
+    let mut updated = 0;
+
+    for mut task in read_subtasks(parent_id) {
+        if !is_backend_identifier(&task.identifier)
+            || only.is_some_and(|ids| !ids.contains(&task.identifier))
+        {
+            continue;
+        }
+        let fields = match rt.block_on(fetch_backend_fields(backend, &task.identifier, mapping)) {
+            Ok(fields) => fields,
+            Err(e) => {
+                tracing::warn!("Skipping field mapping for {}: {}", task.identifier, e);
+                continue;
+            }
+        };
+        if apply_fields_to_subtask(&mut task, mapping, &fields, backend) {
+            write_subtask_spec(parent_id, &task)?;
+            updated += 1;
+        }
+    }
+
+    Ok(updated)
+}
+
+/// Field updates to push for the local sub-tasks of `parent_id`, keyed by
+/// backend identifier. Sub-tasks with nothing to write are omitted.
+pub fn pending_field_updates(
+    parent_id: &str,
+    mapping: &FieldMapping,
+) -> Vec<(String, FieldValues)> {
+    let mut updates: Vec<(String, FieldValues)> = read_subtasks(parent_id)
+        .iter()
+        .filter(|task| is_backend_identifier(&task.identifier))
+        .map(|task| (task.identifier.clone(), fields_from_subtask(mapping, task)))
+        .filter(|(_, fields)| !fields.is_empty())
+        .collect();
+    updates.sort_by(|a, b| a.0.cmp(&b.0));
+    updates
+}
+
+fn mapped_fields(mapping: &FieldMapping) -> impl Iterator<Item = &str> {
+    [
+        &mapping.complexity,
+        &mapping.risk,
+        &mapping.recommended_model,
+        &mapping.verify_command,
+    ]
+    .into_iter()
+    .filter_map(|f| f.as_deref())
+}
+
+/// Linear and Jira issue keys (`MOB-123`); local `task-001` IDs never match.
+fn is_backend_identifier(identifier: &str) -> bool {
+    Regex::new(r"^[A-Z]+-\d+$").unwrap().is_match(identifier)
+}
+
+fn value_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Object(map) => map
+            .get("value")
+            .or_else(|| map.get("name"))
+            .and_then(value_text),
+        Value::Array(items) => items.iter().find_map(value_text),
+        Value::Bool(_) | Value::Null => None,
+    }
+}
+
+fn parse_score(text: &str) -> Option<u8> {
+    let n: f64 = text.trim().parse().ok()?;
+    (0.0..=u8::MAX as f64).contains(&n).then(|| n.round() as u8)
+}
+
+/// Accepts `opus` as well as longer names like `claude-opus-4`.
+fn parse_model(text: &str) -> Option<Model> {
+    text.parse().ok().or_else(|| {
+        let lower = text.to_lowercase();
+        [Model::Opus, Model::Sonnet, Model::Haiku]
+            .into_iter()
+            .find(|m| lower.contains(&m.to_string()))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::TaskType;
+
+    fn jira_mapping() -> FieldMapping {
+        FieldMapping {
+            complexity: Some("customfield_10016".to_string()),
+            risk: Some("customfield_10050".to_string()),
+            recommended_model: Some("customfield_10051".to_string()),
+            verify_command: Some("customfield_10052".to_string()),
+        }
+    }
+
+    fn subtask(description: &str, scoring: Option<TaskScoring>) -> SubTaskContext {
+        SubTaskContext {
+            id: "id-1".to_string(),
+            identifier: "PROJ-2".to_string(),
+            title: "Task".to_string(),
+            description: description.to_string(),
+            status: "To Do".to_string(),
+            git_branch_name: String::new(),
+            blocked_by: vec![],
+            blocks: vec![],
+            scoring,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_scoring_from_jira_fields() {
+        let fields: FieldValues = serde_json::from_value(serde_json::json!({
+            "customfield_10016": 8.0,
+            "customfield_10050": "3",
+            "customfield_10051": { "value": "Opus", "id": "10020" }
+        }))
+        .unwrap();
+
+        let scoring = scoring_from_fields(&jira_mapping(), &fields, None, Backend::Jira).unwrap();
+        assert_eq!(scoring.complexity, 8);
+        assert_eq!(scoring.risk, 3);
+        assert_eq!(scoring.recommended_model, Model::Opus);
+        assert_eq!(scoring.rationale, "Scored in jira");
+    }
+
+    #[test]
+    fn test_scoring_from_partial_fields_overlays_existing() {
+        let existing = TaskScoring {
+            complexity: 2,
+            risk: 2,
+            recommended_model: Model::Haiku,
+            rationale: "Refined locally".to_string(),
+            recommended_runtime: None,
+        };
+        let fields: FieldValues =
+            serde_json::from_value(serde_json::json!({ "customfield_10016": 6 })).unwrap();
+
+        let scoring =
+            scoring_from_fields(&jira_mapping(), &fields, Some(&existing), Backend::Jira).unwrap();
+        assert_eq!(scoring.complexity, 6);
+        assert_eq!(scoring.recommended_model, Model::Haiku);
+        assert_eq!(scoring.rationale, "Refined locally");
+
+        // Incomplete tracker data without local scoring leaves it unset
+        assert!(scoring_from_fields(&jira_mapping(), &fields, None, Backend::Jira).is_none());
+        assert!(
+            scoring_from_fields(&jira_mapping(), &FieldValues::new(), None, Backend::Jira)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_label_mapping_for_linear() {
+        let mapping = FieldMapping {
+            complexity: Some("estimate".to_string()),
+            risk: Some("label:risk-".to_string()),
+            recommended_model: Some("label:model:".to_string()),
+            verify_command: None,
+        };
+        assert_eq!(mapped_field_ids(&mapping), vec!["estimate", "labels"]);
+
+        let fields: FieldValues = serde_json::from_value(serde_json::json!({
+            "estimate": 5.0,
+            "labels": ["frontend", "risk-7", "model:claude-sonnet-4"]
+        }))
+        .unwrap();
+        let scoring = scoring_from_fields(&mapping, &fields, None, Backend::Linear).unwrap();
+        assert_eq!(scoring.complexity, 5);
+        assert_eq!(scoring.risk, 7);
+        assert_eq!(scoring.recommended_model, Model::Sonnet);
+
+        // Label mappings are never written back
+        let task = subtask("", Some(scoring));
+        let out = fields_from_subtask(&mapping, &task);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out["estimate"], serde_json::json!(5));
+    }
+
+    #[test]
+    fn test_with_verify_command() {
+        assert_eq!(
+            with_verify_command("Do the thing.\n", "cargo test"),
+            "Do the thing.\n\n### Verify Command\n```bash\ncargo test\n```"
+        );
+
+        let existing = "Intro\n\n### Verify Command\n```bash\nnpm test\n```\n\nOutro";
+        assert_eq!(
+            with_verify_command(existing, "just check $1"),
+            "Intro\n\n### Verify Command\n```bash\njust check $1\n```\n\nOutro"
+        );
+    }
+
+    #[test]
+    fn test_apply_and_extract_round_trip() {
+        let fields: FieldValues = serde_json::from_value(serde_json::json!({
+            "customfield_10016": 4,
+            "customfield_10050": 1,
+            "customfield_10051": "haiku",
+            "customfield_10052": "cargo test -p api"
+        }))
+        .unwrap();
+
+        let mut task = subtask("Implement endpoint.", None);
+        assert!(apply_fields_to_subtask(
+            &mut task,
+            &jira_mapping(),
+            &fields,
+            Backend::Jira
+        ));
+        assert!(!apply_fields_to_subtask(
+            &mut task,
+            &jira_mapping(),
+            &fields,
+            Backend::Jira
+        ));
+
+        let out = fields_from_subtask(&jira_mapping(), &task);
+        assert_eq!(out["customfield_10016"], serde_json::json!(4));
+        assert_eq!(out["customfield_10050"], serde_json::json!(1));
+        assert_eq!(out["customfield_10051"], serde_json::json!("haiku"));
+        assert_eq!(
+            out["customfield_10052"],
+            serde_json::json!("cargo test -p api")
+        );
+    }
+
+    #[test]
+    fn test_is_backend_identifier() {
+        assert!(is_backend_identifier("MOB-123"));
+        assert!(!is_backend_identifier("task-001"));
+        assert!(!is_backend_identifier("LOC-1x"));
+    }
+}
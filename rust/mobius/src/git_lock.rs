@@ -2,29 +2,67 @@
 //!
 //! Provides exclusive locking for git operations when multiple parallel agents
 //! share a worktree. Uses mkdir-based atomic locking with stale lock detection.
+//!
+//! Waiters take a ticket in `.git-lock-queue/` and only the oldest live ticket
+//! may try the lock, so agents get it in the order they asked for it rather
+//! than whichever one happens to poll first after a release.
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::time::{Duration, Instant};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::{bail, Context, Result};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 
+use crate::debug_logger::debug_log;
+use crate::types::enums::{DebugEventSource, DebugEventType};
+
 const LOCK_DIR_NAME: &str = ".git-lock";
 const LOCK_METADATA_FILE: &str = "lock.json";
+const QUEUE_DIR_NAME: &str = ".git-lock-queue";
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
 const STALE_LOCK_AGE: Duration = Duration::from_secs(5 * 60); // 5 minutes
 const RETRY_INTERVAL: Duration = Duration::from_millis(100);
 
+/// Keeps tickets taken by one process in the same nanosecond distinct
+static TICKET_SEQ: AtomicU32 = AtomicU32::new(0);
+
 /// Metadata stored in the lock directory.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct LockMetadata {
     pid: u32,
-    acquired: String, // ISO-8601 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    task: Option<String>,
+    #[serde(alias = "acquired")]
+    acquired_at: String, // ISO-8601 timestamp
     hostname: String,
 }
 
+/// Contents of a queue ticket. The waiter's PID is part of the file name, so
+/// a ticket is usable before its contents are written.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TicketData {
+    task: Option<String>,
+    enqueued_at: String,
+}
+
+/// A process waiting for the lock.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockWaiter {
+    pub pid: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    pub enqueued_at: String,
+    #[serde(skip)]
+    ticket: PathBuf,
+}
+
 /// A handle to an acquired lock. Dropping this does NOT release the lock;
 /// you must call `release()` explicitly or use `with_lock()`.
 #[derive(Debug)]
@@ -51,6 +89,11 @@ fn get_metadata_path(worktree_path: &Path) -> PathBuf {
     get_lock_path(worktree_path).join(LOCK_METADATA_FILE)
 }
 
+/// Get the queue directory path for a worktree.
+fn get_queue_path(worktree_path: &Path) -> PathBuf {
+    worktree_path.join(QUEUE_DIR_NAME)
+}
+
 /// Read lock metadata from disk.
 async fn read_lock_metadata(worktree_path: &Path) -> Option<LockMetadata> {
     let metadata_path = get_metadata_path(worktree_path);
@@ -104,7 +147,7 @@ fn is_process_alive(pid: u32) -> bool {
 }
 
 /// Attempt to acquire the lock once.
-async fn try_acquire_lock(worktree_path: &Path) -> Result<bool> {
+async fn try_acquire_lock(worktree_path: &Path, task: Option<&str>) -> Result<bool> {
     let lock_path = get_lock_path(worktree_path);
 
     // mkdir acts as atomic lock - fails with AlreadyExists if lock exists
@@ -113,7 +156,8 @@ async fn try_acquire_lock(worktree_path: &Path) -> Result<bool> {
             // Write metadata
             let metadata = LockMetadata {
                 pid: std::process::id(),
-                acquired: Utc::now().to_rfc3339(),
+                task: task.map(str::to_string),
+                acquired_at: Utc::now().to_rfc3339(),
                 hostname: std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_string()),
             };
             write_lock_metadata(worktree_path, &metadata).await?;
@@ -158,23 +202,277 @@ async fn try_cleanup_stale_lock(worktree_path: &Path) -> bool {
     false
 }
 
+/// Take a ticket at the back of the queue.
+///
+/// Ticket names start with the zero-padded enqueue time, so sorting them
+/// gives the queue order.
+async fn enqueue(worktree_path: &Path, task: Option<&str>) -> Result<PathBuf> {
+    let queue_path = get_queue_path(worktree_path);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let ticket = queue_path.join(format!(
+        "{:020}-{:010}-{:010}.json",
+        nanos,
+        std::process::id(),
+        TICKET_SEQ.fetch_add(1, Ordering::Relaxed)
+    ));
+    let data = serde_json::to_string(&TicketData {
+        task: task.map(str::to_string),
+        enqueued_at: Utc::now().to_rfc3339(),
+    })
+    .context("failed to serialize lock queue ticket")?;
+
+    // The last waiter to leave removes the queue directory, possibly between
+    // our create_dir_all and write
+    let mut retried = false;
+    loop {
+        tokio::fs::create_dir_all(&queue_path)
+            .await
+            .context("failed to create lock queue directory")?;
+        match tokio::fs::write(&ticket, &data).await {
+            Ok(()) => return Ok(ticket),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound && !retried => retried = true,
+            Err(e) => return Err(e).context("failed to write lock queue ticket"),
+        }
+    }
+}
+
+/// Give up a ticket, removing the queue directory once it is empty.
+async fn dequeue(ticket: &Path) {
+    let _ = tokio::fs::remove_file(ticket).await;
+    if let Some(queue_path) = ticket.parent() {
+        // Fails while other tickets remain
+        let _ = tokio::fs::remove_dir(queue_path).await;
+    }
+}
+
+/// PID encoded in a ticket file name.
+fn ticket_pid(ticket: &Path) -> Option<u32> {
+    if ticket.extension()? != "json" {
+        return None;
+    }
+    ticket
+        .file_stem()?
+        .to_str()?
+        .split('-')
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
+/// Live waiters in queue order. Tickets left by exited processes are removed.
+async fn read_queue(worktree_path: &Path) -> Vec<LockWaiter> {
+    let Ok(mut entries) = tokio::fs::read_dir(get_queue_path(worktree_path)).await else {
+        return Vec::new();
+    };
+    let mut tickets = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        tickets.push(entry.path());
+    }
+    tickets.sort();
+
+    let mut waiters = Vec::new();
+    for ticket in tickets {
+        let Some(pid) = ticket_pid(&ticket) else {
+            continue;
+        };
+        if !is_process_alive(pid) {
+            let _ = tokio::fs::remove_file(&ticket).await;
+            continue;
+        }
+        // A ticket that was just created may not have its contents yet
+        let data: TicketData = tokio::fs::read_to_string(&ticket)
+            .await
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        waiters.push(LockWaiter {
+            pid,
+            task: data.task,
+            enqueued_at: data.enqueued_at,
+            ticket,
+        });
+    }
+    waiters
+}
+
+/// "PID 123 (task MOB-1)"
+fn describe_holder(pid: u32, task: Option<&str>) -> String {
+    match task {
+        Some(task) => format!("PID {} (task {})", pid, task),
+        None => format!("PID {}", pid),
+    }
+}
+
+/// Why a wait for the lock looks like it may never end, if it does.
+///
+/// A holder that is itself queued for the lock it holds can only be freed by
+/// the timeout; a wait past half the timeout usually means the holder is stuck.
+fn deadlock_reason(
+    holder: Option<&LockMetadata>,
+    queue: &[LockWaiter],
+    waited: Duration,
+    timeout: Duration,
+) -> Option<String> {
+    if let Some(holder) = holder {
+        if queue
+            .iter()
+            .any(|w| w.pid == holder.pid && w.task == holder.task)
+        {
+            return Some(format!(
+                "{} holds the lock and is queued for it again",
+                describe_holder(holder.pid, holder.task.as_deref())
+            ));
+        }
+    }
+    (waited >= timeout / 2).then(|| {
+        format!(
+            "waited {}ms of the {}ms timeout",
+            waited.as_millis(),
+            timeout.as_millis()
+        )
+    })
+}
+
+/// Record a wait for the git lock (`--debug` only).
+fn log_lock_wait(
+    worktree_path: &Path,
+    task: Option<&str>,
+    holder: Option<&LockMetadata>,
+    ahead: usize,
+    start: Instant,
+    outcome: &str,
+    reason: Option<&str>,
+) {
+    let mut data = HashMap::from([
+        ("lock".to_string(), serde_json::json!("git")),
+        (
+            "path".to_string(),
+            serde_json::json!(get_lock_path(worktree_path).display().to_string()),
+        ),
+        (
+            "waitedMs".to_string(),
+            serde_json::json!(start.elapsed().as_millis() as u64),
+        ),
+        ("outcome".to_string(), serde_json::json!(outcome)),
+        ("queueAhead".to_string(), serde_json::json!(ahead)),
+    ]);
+    if let Some(holder) = holder {
+        data.insert("holderPid".to_string(), serde_json::json!(holder.pid));
+        if let Some(holder_task) = &holder.task {
+            data.insert("holderTask".to_string(), serde_json::json!(holder_task));
+        }
+    }
+    if let Some(reason) = reason {
+        data.insert("reason".to_string(), serde_json::json!(reason));
+    }
+    debug_log(
+        DebugEventType::LockContention,
+        DebugEventSource::Lock,
+        task,
+        data,
+    );
+}
+
 /// Acquire exclusive lock for git operations.
 ///
 /// Retries with a 100ms interval until the lock is acquired or the timeout is exceeded.
 /// Stale locks (older than 5 minutes or held by dead processes) are automatically cleaned up.
 pub async fn acquire_lock(worktree_path: &Path, timeout: Option<Duration>) -> Result<LockHandle> {
+    acquire_task_lock(worktree_path, None, timeout).await
+}
+
+/// Acquire exclusive lock for git operations on behalf of `task`.
+///
+/// The task is recorded in the queue and the lock metadata for
+/// `mobius lock status`. Waiters are served in the order they arrived.
+pub async fn acquire_task_lock(
+    worktree_path: &Path,
+    task: Option<&str>,
+    timeout: Option<Duration>,
+) -> Result<LockHandle> {
     let timeout = timeout.unwrap_or(DEFAULT_TIMEOUT);
+    exclude_lock_dirs(worktree_path).await;
+    let ticket = enqueue(worktree_path, task).await?;
+    let result = wait_for_turn(worktree_path, task, &ticket, timeout).await;
+    dequeue(&ticket).await;
+    result
+}
+
+/// List the lock and queue directories in the repository's `info/exclude`,
+/// so they never show up in `git status` or get staged by `git add -A`.
+///
+/// Best effort: outside a git repository this does nothing.
+async fn exclude_lock_dirs(worktree_path: &Path) {
+    let Ok(output) = tokio::process::Command::new("git")
+        .args(["rev-parse", "--git-path", "info/exclude"])
+        .current_dir(worktree_path)
+        .output()
+        .await
+    else {
+        return;
+    };
+    if !output.status.success() {
+        return;
+    }
+    let exclude = worktree_path.join(String::from_utf8_lossy(&output.stdout).trim());
+    let mut content = tokio::fs::read_to_string(&exclude)
+        .await
+        .unwrap_or_default();
+    let missing: Vec<String> = [LOCK_DIR_NAME, QUEUE_DIR_NAME]
+        .iter()
+        .map(|name| format!("{}/", name))
+        .filter(|entry| !content.lines().any(|line| line.trim() == entry))
+        .collect();
+    if missing.is_empty() {
+        return;
+    }
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for entry in missing {
+        content.push_str(&entry);
+        content.push('\n');
+    }
+    if let Some(parent) = exclude.parent() {
+        let _ = tokio::fs::create_dir_all(parent).await;
+    }
+    let _ = tokio::fs::write(&exclude, content).await;
+}
+
+/// Poll until `ticket` is at the front of the queue and the lock is free.
+async fn wait_for_turn(
+    worktree_path: &Path,
+    task: Option<&str>,
+    ticket: &Path,
+    timeout: Duration,
+) -> Result<LockHandle> {
     let start = Instant::now();
+    let mut contended_by = None;
+    let mut warned = false;
 
     loop {
-        // Try to acquire lock
-        if try_acquire_lock(worktree_path).await? {
-            let acquired = Utc::now();
-            let lock_path = get_lock_path(worktree_path);
-
+        let queue = read_queue(worktree_path).await;
+        // A ticket deleted by hand no longer waits its turn
+        let ahead = queue.iter().position(|w| w.ticket == ticket).unwrap_or(0);
+
+        if ahead == 0 && try_acquire_lock(worktree_path, task).await? {
+            if start.elapsed() >= RETRY_INTERVAL {
+                log_lock_wait(
+                    worktree_path,
+                    task,
+                    contended_by.as_ref(),
+                    0,
+                    start,
+                    "acquired",
+                    None,
+                );
+            }
             return Ok(LockHandle {
-                lock_path,
-                acquired,
+                lock_path: get_lock_path(worktree_path),
+                acquired: Utc::now(),
                 pid: std::process::id(),
             });
         }
@@ -182,18 +480,55 @@ pub async fn acquire_lock(worktree_path: &Path, timeout: Option<Duration>) -> Re
         // Try to clean up stale lock
         try_cleanup_stale_lock(worktree_path).await;
 
+        let holder = read_lock_metadata(worktree_path).await;
+        if holder.is_some() {
+            contended_by = holder.clone();
+        }
+        if !warned {
+            if let Some(reason) = deadlock_reason(holder.as_ref(), &queue, start.elapsed(), timeout)
+            {
+                warned = true;
+                log_lock_wait(
+                    worktree_path,
+                    task,
+                    holder.as_ref(),
+                    ahead,
+                    start,
+                    "possible_deadlock",
+                    Some(&reason),
+                );
+            }
+        }
+
         // Check timeout
-        let elapsed = start.elapsed();
-        if elapsed >= timeout {
-            let metadata = read_lock_metadata(worktree_path).await;
-            let owner_info = match metadata {
-                Some(m) => format!("Lock held by PID {} since {}", m.pid, m.acquired),
+        if start.elapsed() >= timeout {
+            log_lock_wait(
+                worktree_path,
+                task,
+                holder.as_ref(),
+                ahead,
+                start,
+                "timeout",
+                None,
+            );
+            let owner_info = match holder {
+                Some(m) => format!(
+                    "Lock held by {} since {}",
+                    describe_holder(m.pid, m.task.as_deref()),
+                    m.acquired_at
+                ),
                 None => "Unknown lock owner".to_string(),
             };
+            let queue_info = if ahead > 0 {
+                format!(" {} waiter(s) were ahead in the queue.", ahead)
+            } else {
+                String::new()
+            };
             bail!(
-                "Failed to acquire git lock after {}ms. {}. Lock path: {}",
+                "Failed to acquire git lock after {}ms. {}.{} Lock path: {}",
                 timeout.as_millis(),
                 owner_info,
+                queue_info,
                 get_lock_path(worktree_path).display()
             );
         }
@@ -203,11 +538,12 @@ pub async fn acquire_lock(worktree_path: &Path, timeout: Option<Duration>) -> Re
     }
 }
 
-/// Execute a function while holding the git lock.
+/// Execute a function while holding the git lock on behalf of `task`.
 ///
 /// The lock is automatically released when the function completes (or panics).
 pub async fn with_lock<T, F, Fut>(
     worktree_path: &Path,
+    task: Option<&str>,
     timeout: Option<Duration>,
     f: F,
 ) -> Result<T>
@@ -215,7 +551,7 @@ where
     F: FnOnce() -> Fut,
     Fut: std::future::Future<Output = Result<T>>,
 {
-    let handle = acquire_lock(worktree_path, timeout).await?;
+    let handle = acquire_task_lock(worktree_path, task, timeout).await?;
     let result = f().await;
     let _ = handle.release().await;
     result
@@ -257,16 +593,25 @@ pub async fn get_lock_info(worktree_path: &Path) -> Option<LockInfo> {
     let metadata = read_lock_metadata(worktree_path).await?;
     Some(LockInfo {
         pid: metadata.pid,
-        acquired: metadata.acquired,
+        task: metadata.task,
+        acquired_at: metadata.acquired_at,
         hostname: metadata.hostname,
     })
 }
 
+/// Processes waiting for the lock, first in line first.
+pub async fn get_lock_queue(worktree_path: &Path) -> Vec<LockWaiter> {
+    read_queue(worktree_path).await
+}
+
 /// Information about a lock holder.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct LockInfo {
     pub pid: u32,
-    pub acquired: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    pub acquired_at: String,
     pub hostname: String,
 }
 
@@ -335,7 +680,8 @@ mod tests {
 
         let metadata = LockMetadata {
             pid: 999999999, // Very unlikely to be a real PID
-            acquired: Utc::now().to_rfc3339(),
+            task: None,
+            acquired_at: Utc::now().to_rfc3339(),
             hostname: "test".to_string(),
         };
         let metadata_path = lock_path.join(LOCK_METADATA_FILE);
@@ -360,7 +706,7 @@ mod tests {
         let test_dir = unique_test_dir();
         std::fs::create_dir_all(&test_dir).unwrap();
 
-        let result = with_lock(&test_dir, None, || async { Ok(42) }).await;
+        let result = with_lock(&test_dir, Some("MOB-7"), None, || async { Ok(42) }).await;
         assert_eq!(result.unwrap(), 42);
 
         // Lock should be released after with_lock
@@ -405,9 +751,17 @@ mod tests {
         // Should have lock info
         let info = get_lock_info(&test_dir).await.unwrap();
         assert_eq!(info.pid, std::process::id());
+        assert_eq!(info.task, None);
 
         handle.release().await.unwrap();
 
+        let handle = acquire_task_lock(&test_dir, Some("MOB-7"), None)
+            .await
+            .unwrap();
+        let info = get_lock_info(&test_dir).await.unwrap();
+        assert_eq!(info.task.as_deref(), Some("MOB-7"));
+        handle.release().await.unwrap();
+
         // Cleanup
         std::fs::remove_dir_all(&test_dir).ok();
     }
@@ -423,4 +777,115 @@ mod tests {
         // Very high PID should not exist
         assert!(!is_process_alive(999999999));
     }
+
+    #[test]
+    fn test_lock_metadata_reads_legacy_acquired_field() {
+        let metadata: LockMetadata = serde_json::from_str(
+            r#"{"pid": 42, "acquired": "2024-01-15T14:30:00Z", "hostname": "box"}"#,
+        )
+        .unwrap();
+        assert_eq!(metadata.acquired_at, "2024-01-15T14:30:00Z");
+        assert_eq!(metadata.task, None);
+    }
+
+    #[tokio::test]
+    async fn test_queue_serves_waiters_in_order() {
+        let test_dir = unique_test_dir();
+        std::fs::create_dir_all(&test_dir).unwrap();
+
+        // An earlier waiter keeps a later one from taking the free lock
+        let first = enqueue(&test_dir, Some("MOB-1")).await.unwrap();
+        let result =
+            acquire_task_lock(&test_dir, Some("MOB-2"), Some(Duration::from_millis(200))).await;
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("1 waiter(s) were ahead"), "{message}");
+        assert!(!is_locked(&test_dir).await);
+
+        let queue = get_lock_queue(&test_dir).await;
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue[0].task.as_deref(), Some("MOB-1"));
+
+        // Once it leaves, the next waiter gets the lock and the queue is gone
+        dequeue(&first).await;
+        let handle = acquire_task_lock(&test_dir, Some("MOB-2"), None)
+            .await
+            .unwrap();
+        handle.release().await.unwrap();
+        assert!(!get_queue_path(&test_dir).exists());
+
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_queue_drops_dead_waiters() {
+        let test_dir = unique_test_dir();
+        let queue_path = get_queue_path(&test_dir);
+        std::fs::create_dir_all(&queue_path).unwrap();
+        let dead = queue_path.join("00000000000000000001-0999999999-0000000000.json");
+        std::fs::write(&dead, "{}").unwrap();
+
+        assert_eq!(ticket_pid(&dead), Some(999999999));
+        assert!(get_lock_queue(&test_dir).await.is_empty());
+        assert!(!dead.exists());
+
+        let handle = acquire_lock(&test_dir, Some(Duration::from_secs(2)))
+            .await
+            .unwrap();
+        handle.release().await.unwrap();
+
+        std::fs::remove_dir_all(&test_dir).ok();
+    }
+
+    #[test]
+    fn test_deadlock_reason() {
+        let holder = LockMetadata {
+            pid: 42,
+            task: Some("MOB-1".to_string()),
+            acquired_at: Utc::now().to_rfc3339(),
+            hostname: "test".to_string(),
+        };
+        let waiter = |task: &str| LockWaiter {
+            pid: 42,
+            task: Some(task.to_string()),
+            enqueued_at: String::new(),
+            ticket: PathBuf::new(),
+        };
+        let timeout = Duration::from_secs(30);
+        let short = Duration::from_secs(1);
+
+        assert_eq!(
+            deadlock_reason(Some(&holder), &[waiter("MOB-2")], short, timeout),
+            None
+        );
+        assert!(
+            deadlock_reason(Some(&holder), &[waiter("MOB-1")], short, timeout)
+                .unwrap()
+                .contains("PID 42 (task MOB-1) holds the lock")
+        );
+        assert!(deadlock_reason(None, &[], Duration::from_secs(15), timeout).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_acquire_excludes_lock_dirs_from_git() {
+        let tmp = tempfile::tempdir().unwrap();
+        let status = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(tmp.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let exclude = tmp.path().join(".git/info/exclude");
+        std::fs::write(&exclude, "*.log").unwrap();
+
+        for _ in 0..2 {
+            let handle = acquire_task_lock(tmp.path(), Some("MOB-3"), None)
+                .await
+                .unwrap();
+            handle.release().await.unwrap();
+        }
+        assert_eq!(
+            std::fs::read_to_string(&exclude).unwrap(),
+            "*.log\n.git-lock/\n.git-lock-queue/\n"
+        );
+    }
 }
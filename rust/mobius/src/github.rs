@@ -0,0 +1,779 @@
+//! GitHub Issues REST API client
+//!
+//! Issues are addressed by number (e.g., "42") within a single repository.
+//! Sub-tasks are GitHub sub-issues, and "blocked by" relations are parsed
+//! from `Blocked by #N` / `Depends on #N` lines in the issue body.
+//!
+//! Credentials are read from environment variables:
+//! - `GITHUB_TOKEN` (fallback `GH_TOKEN`): personal access or app token
+//! - `GITHUB_REPOSITORY`: target repository as `owner/repo` (falls back to
+//!   `github.owner` / `github.repo` in the mobius config)
+//! - `GITHUB_API_URL`: API base URL for GitHub Enterprise (optional)
+
+use anyhow::Result;
+use regex::Regex;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tracing::warn;
+
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::http_client::HttpClient;
+use crate::secrets;
+use crate::types::enums::{Backend, TaskType};
+use crate::types::task_graph::{LinearIssue, ParentIssue, Relation, Relations};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+const GITHUB_API_URL: &str = "https://api.github.com";
+const GITHUB_API_VERSION: &str = "2022-11-28";
+
+/// Labels used to express workflow states that GitHub's open/closed model lacks.
+const STATUS_LABELS: &[&str] = &["In Progress", "In Review"];
+
+/// Options for creating a GitHub issue.
+#[derive(Debug, Clone)]
+pub struct CreateGithubIssueOptions {
+    pub title: String,
+    pub body: Option<String>,
+    pub labels: Option<Vec<String>>,
+    pub assignees: Option<Vec<String>>,
+}
+
+/// Result of a GitHub issue creation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GithubCreatedIssue {
+    pub id: u64,
+    pub number: u64,
+    pub html_url: String,
+}
+
+/// Result of adding a comment.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GithubCommentResult {
+    pub id: u64,
+    pub html_url: String,
+}
+
+// ---------------------------------------------------------------------------
+// Internal GitHub API response types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct GithubIssueResponse {
+    id: u64,
+    number: u64,
+    title: Option<String>,
+    body: Option<String>,
+    state: Option<String>,
+    labels: Option<Vec<GithubLabel>>,
+    html_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubCommentBody {
+    body: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubPullResponse {
+    #[serde(default)]
+    merged: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubLabel {
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubCommentResponse {
+    id: u64,
+    html_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubUserResponse {
+    login: String,
+}
+
+// ---------------------------------------------------------------------------
+// Error helpers
+// ---------------------------------------------------------------------------
+
+/// Custom error type for GitHub API operations.
+#[derive(Debug, thiserror::Error)]
+pub enum GithubError {
+    #[error("GITHUB_TOKEN (or GH_TOKEN) is not set. Export it or run 'mobius auth login github'")]
+    MissingToken,
+    #[error("GitHub repository is not configured. Set GITHUB_REPOSITORY=owner/repo or github.owner/github.repo in config")]
+    MissingRepository,
+    #[error("Invalid GitHub repository \"{0}\". Expected owner/repo")]
+    InvalidRepository(String),
+    #[error("Invalid GitHub issue number: {0}")]
+    InvalidIssueNumber(String),
+    #[error("Authentication failed (401). Check GITHUB_TOKEN")]
+    AuthFailed,
+    #[error("Permission denied (403). The token may lack required scopes or be rate limited")]
+    PermissionDenied,
+    #[error("Resource not found (404): {0}")]
+    NotFound(String),
+    #[error("Validation failed (422): {0}")]
+    ValidationFailed(String),
+    #[error("GitHub API error (HTTP {status}): {message}")]
+    HttpError { status: u16, message: String },
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+// ---------------------------------------------------------------------------
+// Client
+// ---------------------------------------------------------------------------
+
+/// GitHub Issues REST API client bound to a single repository.
+pub struct GithubClient {
+    client: HttpClient,
+    base_url: String,
+    owner: String,
+    repo: String,
+    token: String,
+}
+
+impl std::fmt::Debug for GithubClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GithubClient")
+            .field("base_url", &self.base_url)
+            .field("owner", &self.owner)
+            .field("repo", &self.repo)
+            .field("token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl GithubClient {
+    /// Create a new client from environment variables and config.
+    ///
+    /// Reads `GITHUB_TOKEN` (or `GH_TOKEN`), `GITHUB_REPOSITORY` and `GITHUB_API_URL`.
+    pub fn new() -> Result<Self, GithubError> {
+        let repository = match std::env::var("GITHUB_REPOSITORY") {
+            Ok(r) => r,
+            Err(_) => repository_from_config().ok_or(GithubError::MissingRepository)?,
+        };
+        let (owner, repo) = parse_repository(&repository)?;
+        Self::for_repository(&owner, &repo)
+    }
+
+    /// Create a client for an explicit `owner/repo`, ignoring the configured repository.
+    ///
+    /// Reads `GITHUB_TOKEN` (or `GH_TOKEN`, then the OS keychain) and `GITHUB_API_URL`.
+    pub fn for_repository(owner: &str, repo: &str) -> Result<Self, GithubError> {
+        let token = secrets::backend_token(Backend::Github).ok_or(GithubError::MissingToken)?;
+
+        let base_url = std::env::var("GITHUB_API_URL")
+            .unwrap_or_else(|_| GITHUB_API_URL.to_string())
+            .trim_end_matches('/')
+            .to_string();
+
+        Ok(Self {
+            client: HttpClient::new(),
+            base_url,
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            token,
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Generic HTTP helpers
+    // -----------------------------------------------------------------------
+
+    fn repo_url(&self, path: &str) -> String {
+        format!(
+            "{}/repos/{}/{}/{}",
+            self.base_url,
+            self.owner,
+            self.repo,
+            path.trim_start_matches('/')
+        )
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, self.repo_url(path))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", GITHUB_API_VERSION)
+            .header("User-Agent", "mobius")
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, GithubError> {
+        let resp = self
+            .client
+            .send(self.request(reqwest::Method::GET, path))
+            .await?;
+        self.handle_response(resp, path).await
+    }
+
+    async fn send_json<T: serde::de::DeserializeOwned, B: Serialize>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: &B,
+    ) -> Result<T, GithubError> {
+        let request = self.request(method, path).json(body);
+        let resp = self.client.send(request).await?;
+        self.handle_response(resp, path).await
+    }
+
+    async fn handle_response<T: serde::de::DeserializeOwned>(
+        &self,
+        resp: reqwest::Response,
+        path: &str,
+    ) -> Result<T, GithubError> {
+        let status = resp.status();
+        if status.is_success() {
+            let parsed = resp
+                .json::<T>()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to parse GitHub response: {e}"))?;
+            Ok(parsed)
+        } else {
+            let body_text = resp.text().await.unwrap_or_default();
+            self.map_http_error(status, path, &body_text)
+        }
+    }
+
+    fn map_http_error<T>(
+        &self,
+        status: StatusCode,
+        path: &str,
+        body: &str,
+    ) -> Result<T, GithubError> {
+        warn!(
+            "GitHub API error: HTTP {} on {}: {}",
+            status.as_u16(),
+            path,
+            body
+        );
+        match status {
+            StatusCode::UNAUTHORIZED => Err(GithubError::AuthFailed),
+            StatusCode::FORBIDDEN => Err(GithubError::PermissionDenied),
+            StatusCode::NOT_FOUND => Err(GithubError::NotFound(path.to_string())),
+            StatusCode::UNPROCESSABLE_ENTITY => {
+                Err(GithubError::ValidationFailed(body.to_string()))
+            }
+            _ => Err(GithubError::HttpError {
+                status: status.as_u16(),
+                message: body.to_string(),
+            }),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Public API methods
+    // -----------------------------------------------------------------------
+
+    /// Fetch the login of the user the token belongs to.
+    pub async fn fetch_github_viewer(&self) -> Result<String, GithubError> {
+        let request = self
+            .client
+            .request(reqwest::Method::GET, format!("{}/user", self.base_url))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", GITHUB_API_VERSION)
+            .header("User-Agent", "mobius");
+        let resp = self.client.send(request).await?;
+        let user: GithubUserResponse = self.handle_response(resp, "user").await?;
+        Ok(user.login)
+    }
+
+    /// Fetch a GitHub issue by number (e.g., "42" or "#42").
+    pub async fn fetch_github_issue(&self, task_id: &str) -> Result<ParentIssue, GithubError> {
+        let number = parse_issue_number(task_id)?;
+        let resp: GithubIssueResponse = self.get(&format!("issues/{number}")).await?;
+
+        Ok(ParentIssue {
+            id: resp.number.to_string(),
+            identifier: resp.number.to_string(),
+            title: resp.title.unwrap_or_default(),
+            git_branch_name: github_branch_name(resp.number),
+        })
+    }
+
+    /// Fetch the current status name for a GitHub issue.
+    ///
+    /// See [`github_status_name`] for how open/closed plus labels map to a name.
+    pub async fn fetch_github_issue_status(&self, task_id: &str) -> Result<String, GithubError> {
+        let number = parse_issue_number(task_id)?;
+        let resp: GithubIssueResponse = self.get(&format!("issues/{number}")).await?;
+
+        Ok(github_status_name(
+            resp.state.as_deref().unwrap_or("open"),
+            &label_names(resp.labels.as_ref()),
+        ))
+    }
+
+    /// Fetch a GitHub issue's current body.
+    pub async fn fetch_github_issue_description(
+        &self,
+        task_id: &str,
+    ) -> Result<String, GithubError> {
+        let number = parse_issue_number(task_id)?;
+        let resp: GithubIssueResponse = self.get(&format!("issues/{number}")).await?;
+        Ok(resp.body.unwrap_or_default())
+    }
+
+    /// Bodies of the comments on an issue updated at or after `since` (RFC 3339).
+    pub async fn fetch_github_comments_since(
+        &self,
+        task_id: &str,
+        since: &str,
+    ) -> Result<Vec<String>, GithubError> {
+        let number = parse_issue_number(task_id)?;
+        let comments: Vec<GithubCommentBody> = self
+            .get(&format!(
+                "issues/{number}/comments?per_page=100&since={}",
+                since.replace('+', "%2B")
+            ))
+            .await?;
+        Ok(comments.into_iter().filter_map(|c| c.body).collect())
+    }
+
+    /// Whether a pull request in this repository has been merged.
+    pub async fn fetch_pull_request_merged(&self, number: u64) -> Result<bool, GithubError> {
+        let resp: GithubPullResponse = self.get(&format!("pulls/{number}")).await?;
+        Ok(resp.merged)
+    }
+
+    /// Fetch sub-issues of a parent issue.
+    ///
+    /// Blockers come from `Blocked by #N` lines in each sub-issue body.
+    pub async fn fetch_github_sub_issues(
+        &self,
+        parent_id: &str,
+    ) -> Result<Vec<LinearIssue>, GithubError> {
+        let number = parse_issue_number(parent_id)?;
+        let resp: Vec<GithubIssueResponse> = self
+            .get(&format!("issues/{number}/sub_issues?per_page=100"))
+            .await?;
+
+        let sub_issues = resp
+            .into_iter()
+            .map(|issue| {
+                let identifier = issue.number.to_string();
+                let labels = label_names(issue.labels.as_ref());
+                LinearIssue {
+                    id: identifier.clone(),
+                    identifier,
+                    title: issue.title.unwrap_or_default(),
+                    status: github_status_name(issue.state.as_deref().unwrap_or("open"), &labels),
+                    git_branch_name: github_branch_name(issue.number),
+                    relations: Some(Relations {
+                        blocked_by: extract_blocked_by_relations(issue.body.as_deref()),
+                        blocks: Vec::new(),
+                    }),
+                    scoring: None,
+                    priority: None,
+                    labels,
+                    timeout_minutes: None,
+                    task_type: TaskType::Agent,
+                }
+            })
+            .collect();
+
+        Ok(sub_issues)
+    }
+
+    /// Update a GitHub issue's status.
+    ///
+    /// "Done"/"Closed" close the issue as completed and "Canceled" closes it as
+    /// not planned. Any other status reopens the issue and replaces its status
+    /// label (e.g., "In Review"); "Open", "Todo" and "Backlog" just clear it.
+    pub async fn update_github_issue_status(
+        &self,
+        task_id: &str,
+        target_status: &str,
+    ) -> Result<(), GithubError> {
+        let number = parse_issue_number(task_id)?;
+        let current: GithubIssueResponse = self.get(&format!("issues/{number}")).await?;
+
+        let body = build_status_update(&label_names(current.labels.as_ref()), target_status);
+        let _: GithubIssueResponse = self
+            .send_json(reqwest::Method::PATCH, &format!("issues/{number}"), &body)
+            .await?;
+        Ok(())
+    }
+
+    /// Replace an issue's body.
+    pub async fn update_github_issue_body(
+        &self,
+        task_id: &str,
+        body: &str,
+    ) -> Result<(), GithubError> {
+        let number = parse_issue_number(task_id)?;
+        let _: GithubIssueResponse = self
+            .send_json(
+                reqwest::Method::PATCH,
+                &format!("issues/{number}"),
+                &serde_json::json!({ "body": body }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Add a comment to a GitHub issue.
+    pub async fn add_github_comment(
+        &self,
+        task_id: &str,
+        body: &str,
+    ) -> Result<GithubCommentResult, GithubError> {
+        let number = parse_issue_number(task_id)?;
+        let resp: GithubCommentResponse = self
+            .send_json(
+                reqwest::Method::POST,
+                &format!("issues/{number}/comments"),
+                &serde_json::json!({ "body": body }),
+            )
+            .await?;
+
+        Ok(GithubCommentResult {
+            id: resp.id,
+            html_url: resp.html_url.unwrap_or_default(),
+        })
+    }
+
+    /// Create a new GitHub issue.
+    pub async fn create_github_issue(
+        &self,
+        options: &CreateGithubIssueOptions,
+    ) -> Result<GithubCreatedIssue, GithubError> {
+        let mut body = serde_json::json!({ "title": &options.title });
+        let body_obj = body.as_object_mut().unwrap();
+
+        if let Some(ref description) = options.body {
+            body_obj.insert("body".to_string(), serde_json::json!(description));
+        }
+        if let Some(ref labels) = options.labels {
+            if !labels.is_empty() {
+                body_obj.insert("labels".to_string(), serde_json::json!(labels));
+            }
+        }
+        if let Some(ref assignees) = options.assignees {
+            if !assignees.is_empty() {
+                body_obj.insert("assignees".to_string(), serde_json::json!(assignees));
+            }
+        }
+
+        let resp: GithubIssueResponse = self
+            .send_json(reqwest::Method::POST, "issues", &body)
+            .await?;
+
+        Ok(GithubCreatedIssue {
+            id: resp.id,
+            number: resp.number,
+            html_url: resp.html_url.unwrap_or_default(),
+        })
+    }
+
+    /// Attach an existing issue as a sub-issue of `parent_id`.
+    ///
+    /// `child_issue_id` is the issue's database ID (from [`GithubCreatedIssue::id`]),
+    /// not its number.
+    pub async fn add_github_sub_issue(
+        &self,
+        parent_id: &str,
+        child_issue_id: u64,
+    ) -> Result<(), GithubError> {
+        let number = parse_issue_number(parent_id)?;
+        let _: GithubIssueResponse = self
+            .send_json(
+                reqwest::Method::POST,
+                &format!("issues/{number}/sub_issues"),
+                &serde_json::json!({ "sub_issue_id": child_issue_id }),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn repository_from_config() -> Option<String> {
+    let paths = resolve_paths();
+    let github = read_config(&paths.config_path).ok()?.github?;
+    Some(format!("{}/{}", github.owner?, github.repo?))
+}
+
+/// Split an `owner/repo` string into its parts.
+fn parse_repository(repository: &str) -> Result<(String, String), GithubError> {
+    let trimmed = repository.trim().trim_end_matches(".git");
+    match trimmed.split_once('/') {
+        Some((owner, repo)) if !owner.is_empty() && !repo.is_empty() && !repo.contains('/') => {
+            Ok((owner.to_string(), repo.to_string()))
+        }
+        _ => Err(GithubError::InvalidRepository(repository.to_string())),
+    }
+}
+
+/// Parse an issue number from "42" or "#42".
+fn parse_issue_number(task_id: &str) -> Result<u64, GithubError> {
+    task_id
+        .trim()
+        .trim_start_matches('#')
+        .parse()
+        .map_err(|_| GithubError::InvalidIssueNumber(task_id.to_string()))
+}
+
+fn github_branch_name(number: u64) -> String {
+    format!("feature/issue-{number}")
+}
+
+fn label_names(labels: Option<&Vec<GithubLabel>>) -> Vec<String> {
+    labels
+        .map(|ls| ls.iter().filter_map(|l| l.name.clone()).collect())
+        .unwrap_or_default()
+}
+
+fn is_status_label(label: &str) -> bool {
+    STATUS_LABELS.iter().any(|s| s.eq_ignore_ascii_case(label))
+}
+
+/// Derive a status name from a GitHub issue state and its labels.
+///
+/// Closed issues are "Done". Open issues report their status label
+/// (e.g., "In Progress") if present, otherwise "Open".
+pub fn github_status_name(state: &str, labels: &[String]) -> String {
+    if state.eq_ignore_ascii_case("closed") {
+        return "Done".to_string();
+    }
+
+    labels
+        .iter()
+        .find(|l| is_status_label(l))
+        .cloned()
+        .unwrap_or_else(|| "Open".to_string())
+}
+
+/// Build the PATCH body that moves an issue to `target_status`.
+fn build_status_update(current_labels: &[String], target_status: &str) -> serde_json::Value {
+    let target_lower = target_status.to_lowercase();
+    let mut labels: Vec<String> = current_labels
+        .iter()
+        .filter(|l| !is_status_label(l))
+        .cloned()
+        .collect();
+
+    match target_lower.as_str() {
+        "done" | "completed" | "closed" => serde_json::json!({
+            "state": "closed",
+            "state_reason": "completed",
+            "labels": labels,
+        }),
+        "canceled" | "cancelled" => serde_json::json!({
+            "state": "closed",
+            "state_reason": "not_planned",
+            "labels": labels,
+        }),
+        "open" | "todo" | "backlog" => serde_json::json!({
+            "state": "open",
+            "labels": labels,
+        }),
+        _ => {
+            let label = STATUS_LABELS
+                .iter()
+                .find(|s| s.eq_ignore_ascii_case(target_status))
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| target_status.to_string());
+            labels.push(label);
+            serde_json::json!({
+                "state": "open",
+                "labels": labels,
+            })
+        }
+    }
+}
+
+fn blocked_by_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?i)(?:blocked by|depends on)\s*:?\s*((?:#\d+[\s,]*(?:and\s+)?)+)")
+            .expect("valid regex")
+    })
+}
+
+fn issue_ref_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"#(\d+)").expect("valid regex"))
+}
+
+/// Extract "blocked by" relations from an issue body.
+///
+/// Recognises `Blocked by #12`, `Depends on #3, #4` and similar phrasing.
+fn extract_blocked_by_relations(body: Option<&str>) -> Vec<Relation> {
+    let Some(body) = body else {
+        return Vec::new();
+    };
+
+    let mut blocked_by: Vec<Relation> = Vec::new();
+
+    for caps in blocked_by_regex().captures_iter(body) {
+        for issue_ref in issue_ref_regex().captures_iter(&caps[1]) {
+            let number = issue_ref[1].to_string();
+            if !blocked_by.iter().any(|r| r.id == number) {
+                blocked_by.push(Relation {
+                    id: number.clone(),
+                    identifier: number,
+                });
+            }
+        }
+    }
+
+    blocked_by
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::TaskStatus;
+    use crate::types::task_graph::map_linear_status;
+
+    fn labels(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    // -- Parsing tests --
+
+    #[test]
+    fn test_parse_repository() {
+        let (owner, repo) = parse_repository("octo-org/widgets").unwrap();
+        assert_eq!(owner, "octo-org");
+        assert_eq!(repo, "widgets");
+
+        let (_, repo) = parse_repository("octo-org/widgets.git").unwrap();
+        assert_eq!(repo, "widgets");
+    }
+
+    #[test]
+    fn test_parse_repository_rejects_invalid() {
+        assert!(matches!(
+            parse_repository("widgets"),
+            Err(GithubError::InvalidRepository(_))
+        ));
+        assert!(parse_repository("/widgets").is_err());
+        assert!(parse_repository("a/b/c").is_err());
+    }
+
+    #[test]
+    fn test_parse_issue_number() {
+        assert_eq!(parse_issue_number("42").unwrap(), 42);
+        assert_eq!(parse_issue_number("#42").unwrap(), 42);
+        assert!(matches!(
+            parse_issue_number("MOB-42"),
+            Err(GithubError::InvalidIssueNumber(_))
+        ));
+    }
+
+    // -- Status mapping tests --
+
+    #[test]
+    fn test_status_name_closed_is_done() {
+        let name = github_status_name("closed", &labels(&["In Progress"]));
+        assert_eq!(name, "Done");
+        assert_eq!(map_linear_status(&name), TaskStatus::Done);
+    }
+
+    #[test]
+    fn test_status_name_open_with_status_label() {
+        let name = github_status_name("open", &labels(&["bug", "in review"]));
+        assert_eq!(name, "in review");
+        assert_eq!(map_linear_status(&name), TaskStatus::InProgress);
+    }
+
+    #[test]
+    fn test_status_name_open_without_status_label() {
+        let name = github_status_name("open", &labels(&["bug"]));
+        assert_eq!(name, "Open");
+        assert_eq!(map_linear_status(&name), TaskStatus::Pending);
+    }
+
+    #[test]
+    fn test_status_update_done_closes_and_drops_status_labels() {
+        let body = build_status_update(&labels(&["bug", "In Progress"]), "Done");
+        assert_eq!(body["state"], "closed");
+        assert_eq!(body["state_reason"], "completed");
+        assert_eq!(body["labels"], serde_json::json!(["bug"]));
+    }
+
+    #[test]
+    fn test_status_update_canceled_is_not_planned() {
+        let body = build_status_update(&[], "Canceled");
+        assert_eq!(body["state"], "closed");
+        assert_eq!(body["state_reason"], "not_planned");
+    }
+
+    #[test]
+    fn test_status_update_in_review_replaces_label() {
+        let body = build_status_update(&labels(&["bug", "In Progress"]), "in review");
+        assert_eq!(body["state"], "open");
+        assert_eq!(body["labels"], serde_json::json!(["bug", "In Review"]));
+    }
+
+    #[test]
+    fn test_status_update_todo_reopens_without_label() {
+        let body = build_status_update(&labels(&["In Review"]), "Todo");
+        assert_eq!(body["state"], "open");
+        assert_eq!(body["labels"], serde_json::json!([]));
+    }
+
+    // -- Blocker parsing tests --
+
+    #[test]
+    fn test_extract_blocked_by_single() {
+        let result = extract_blocked_by_relations(Some("Some text\n\nBlocked by #12\n"));
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].id, "12");
+        assert_eq!(result[0].identifier, "12");
+    }
+
+    #[test]
+    fn test_extract_blocked_by_multiple_and_dedup() {
+        let body = "Depends on: #3, #4 and #5\nblocked by #4";
+        let result = extract_blocked_by_relations(Some(body));
+        let ids: Vec<&str> = result.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["3", "4", "5"]);
+    }
+
+    #[test]
+    fn test_extract_blocked_by_ignores_plain_references() {
+        let result = extract_blocked_by_relations(Some("Related to #7. See #8."));
+        assert!(result.is_empty());
+        assert!(extract_blocked_by_relations(None).is_empty());
+    }
+
+    // -- Error type tests --
+
+    #[test]
+    fn test_error_display_auth_failed() {
+        let err = GithubError::AuthFailed;
+        assert!(err.to_string().contains("401"));
+        assert!(err.to_string().contains("GITHUB_TOKEN"));
+    }
+
+    #[test]
+    fn test_error_display_not_found() {
+        let err = GithubError::NotFound("issues/999".to_string());
+        assert!(err.to_string().contains("404"));
+        assert!(err.to_string().contains("999"));
+    }
+}
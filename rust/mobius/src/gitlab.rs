@@ -0,0 +1,1003 @@
+//! GitLab REST API v4 client
+//!
+//! Issues are addressed by project-scoped IID (e.g., "42"). Sub-tasks are the
+//! issues linked to the parent, and "blocked by" relations come from
+//! `is_blocked_by` issue links. Merge requests are created directly through
+//! the API for `mobius submit`.
+//!
+//! Credentials are read from environment variables:
+//! - `GITLAB_TOKEN`: personal, project or group access token with `api` scope
+//! - `GITLAB_PROJECT`: project path (`group/project`) or numeric ID (falls back
+//!   to `gitlab.project` in the mobius config)
+//! - `GITLAB_HOST`: instance hostname (defaults to `gitlab.com`, or `gitlab.base_url`)
+
+use anyhow::Result;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::http_client::HttpClient;
+use crate::secrets;
+use crate::types::config::GitlabConfig;
+use crate::types::enums::{Backend, TaskType};
+use crate::types::task_graph::{LinearIssue, ParentIssue, Relation, Relations};
+
+// ---------------------------------------------------------------------------
+// Constants
+// ---------------------------------------------------------------------------
+
+const GITLAB_DEFAULT_HOST: &str = "gitlab.com";
+
+/// Labels used to express workflow states that GitLab's opened/closed model lacks.
+const STATUS_LABELS: &[&str] = &["In Progress", "In Review"];
+
+/// Tries at turning on auto-merge while a new merge request awaits its pipeline
+const AUTO_MERGE_ATTEMPTS: u32 = 3;
+
+/// Wait between those tries
+const AUTO_MERGE_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Options for creating a GitLab issue.
+#[derive(Debug, Clone)]
+pub struct CreateGitlabIssueOptions {
+    pub title: String,
+    pub description: Option<String>,
+    pub labels: Option<Vec<String>>,
+}
+
+/// Result of a GitLab issue creation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabCreatedIssue {
+    pub id: u64,
+    pub iid: u64,
+    pub web_url: String,
+}
+
+/// Result of adding a note (comment).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabNoteResult {
+    pub id: u64,
+}
+
+/// Options for creating a GitLab merge request.
+#[derive(Debug, Clone)]
+pub struct CreateMergeRequestOptions {
+    pub source_branch: String,
+    pub target_branch: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub draft: bool,
+    pub remove_source_branch: bool,
+    pub labels: Vec<String>,
+    pub reviewer_ids: Vec<u64>,
+    pub milestone_id: Option<u64>,
+}
+
+/// Result of a merge request creation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitlabMergeRequest {
+    pub iid: u64,
+    pub web_url: String,
+}
+
+// ---------------------------------------------------------------------------
+// Internal GitLab API response types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct GitlabIssueResponse {
+    id: u64,
+    iid: u64,
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    state: Option<String>,
+    labels: Option<Vec<String>>,
+    web_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabNoteBody {
+    body: Option<String>,
+    #[serde(default)]
+    created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabLinkedIssue {
+    iid: u64,
+    title: Option<String>,
+    state: Option<String>,
+    labels: Option<Vec<String>>,
+    link_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabNoteResponse {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabMergeRequestResponse {
+    iid: u64,
+    web_url: Option<String>,
+}
+
+/// Merge request state, pipeline and merge readiness, for `mobius pr-status`
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitlabMergeRequestStatus {
+    pub iid: u64,
+    #[serde(default)]
+    pub web_url: String,
+    /// `opened`, `merged`, `closed` or `locked`
+    pub state: String,
+    #[serde(default)]
+    pub draft: bool,
+    /// e.g. `mergeable`, `not_approved`, `ci_still_running`
+    #[serde(default)]
+    pub detailed_merge_status: Option<String>,
+    #[serde(default)]
+    pub merge_when_pipeline_succeeds: bool,
+    #[serde(default)]
+    pub head_pipeline: Option<GitlabPipelineRef>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitlabPipelineRef {
+    /// e.g. `running`, `success`, `failed`
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabUserResponse {
+    username: String,
+}
+
+/// A user or milestone, when only its ID is needed
+#[derive(Debug, Deserialize)]
+struct GitlabIdRef {
+    id: u64,
+}
+
+// ---------------------------------------------------------------------------
+// Error helpers
+// ---------------------------------------------------------------------------
+
+/// Custom error type for GitLab API operations.
+#[derive(Debug, thiserror::Error)]
+pub enum GitlabError {
+    #[error("GITLAB_TOKEN is not set. Export it or run 'mobius auth login gitlab'")]
+    MissingToken,
+    #[error("GitLab project is not configured. Set GITLAB_PROJECT or gitlab.project in config")]
+    MissingProject,
+    #[error("Invalid GitLab issue IID: {0}")]
+    InvalidIssueIid(String),
+    #[error("Authentication failed (401). Check GITLAB_TOKEN")]
+    AuthFailed,
+    #[error("Permission denied (403). The token may lack the api scope")]
+    PermissionDenied,
+    #[error("Resource not found (404): {0}")]
+    NotFound(String),
+    #[error("Invalid request (400): {0}")]
+    BadRequest(String),
+    #[error("Conflict (409): {0}")]
+    Conflict(String),
+    #[error("GitLab API error (HTTP {status}): {message}")]
+    HttpError { status: u16, message: String },
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+// ---------------------------------------------------------------------------
+// Client
+// ---------------------------------------------------------------------------
+
+/// GitLab REST API v4 client bound to a single project.
+pub struct GitlabClient {
+    client: HttpClient,
+    base_url: String,
+    project: String,
+    token: String,
+}
+
+impl std::fmt::Debug for GitlabClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitlabClient")
+            .field("base_url", &self.base_url)
+            .field("project", &self.project)
+            .field("token", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl GitlabClient {
+    /// Create a new client from environment variables and config.
+    ///
+    /// Reads `GITLAB_TOKEN` (or the OS keychain), `GITLAB_PROJECT` and `GITLAB_HOST`.
+    pub fn new() -> Result<Self, GitlabError> {
+        let token = secrets::backend_token(Backend::Gitlab).ok_or(GitlabError::MissingToken)?;
+        let config = gitlab_config();
+
+        let project = std::env::var("GITLAB_PROJECT")
+            .ok()
+            .or_else(|| config.as_ref().and_then(|c| c.project.clone()))
+            .filter(|p| !p.trim().is_empty())
+            .ok_or(GitlabError::MissingProject)?;
+
+        let host = std::env::var("GITLAB_HOST")
+            .ok()
+            .or_else(|| config.as_ref().and_then(|c| c.base_url.clone()))
+            .unwrap_or_else(|| GITLAB_DEFAULT_HOST.to_string());
+
+        Ok(Self {
+            client: HttpClient::new(),
+            base_url: project_base_url(&host, &project),
+            project,
+            token,
+        })
+    }
+
+    // -----------------------------------------------------------------------
+    // Generic HTTP helpers
+    // -----------------------------------------------------------------------
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let url = format!("{}/{}", self.base_url, path.trim_start_matches('/'));
+        self.client
+            .request(method, url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .header("Accept", "application/json")
+    }
+
+    /// Like `request`, but relative to the API root instead of the project.
+    fn api_request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let root = self
+            .base_url
+            .split("/projects/")
+            .next()
+            .unwrap_or(&self.base_url);
+        let url = format!("{}/{}", root, path.trim_start_matches('/'));
+        self.client
+            .request(method, url)
+            .header("PRIVATE-TOKEN", &self.token)
+            .header("Accept", "application/json")
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, GitlabError> {
+        let resp = self
+            .client
+            .send(self.request(reqwest::Method::GET, path))
+            .await?;
+        self.handle_response(resp, path).await
+    }
+
+    async fn send_request<T: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+        path: &str,
+    ) -> Result<T, GitlabError> {
+        let resp = self.client.send(request).await?;
+        self.handle_response(resp, path).await
+    }
+
+    async fn send_json<T: serde::de::DeserializeOwned, B: Serialize>(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: &B,
+    ) -> Result<T, GitlabError> {
+        let request = self.request(method, path).json(body);
+        let resp = self.client.send(request).await?;
+        self.handle_response(resp, path).await
+    }
+
+    async fn handle_response<T: serde::de::DeserializeOwned>(
+        &self,
+        resp: reqwest::Response,
+        path: &str,
+    ) -> Result<T, GitlabError> {
+        let status = resp.status();
+        if status.is_success() {
+            let parsed = resp
+                .json::<T>()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to parse GitLab response: {e}"))?;
+            Ok(parsed)
+        } else {
+            let body_text = resp.text().await.unwrap_or_default();
+            self.map_http_error(status, path, &body_text)
+        }
+    }
+
+    fn map_http_error<T>(
+        &self,
+        status: StatusCode,
+        path: &str,
+        body: &str,
+    ) -> Result<T, GitlabError> {
+        warn!(
+            "GitLab API error: HTTP {} on {}: {}",
+            status.as_u16(),
+            path,
+            body
+        );
+        match status {
+            StatusCode::UNAUTHORIZED => Err(GitlabError::AuthFailed),
+            StatusCode::FORBIDDEN => Err(GitlabError::PermissionDenied),
+            StatusCode::NOT_FOUND => Err(GitlabError::NotFound(path.to_string())),
+            StatusCode::BAD_REQUEST => Err(GitlabError::BadRequest(body.to_string())),
+            StatusCode::CONFLICT => Err(GitlabError::Conflict(body.to_string())),
+            _ => Err(GitlabError::HttpError {
+                status: status.as_u16(),
+                message: body.to_string(),
+            }),
+        }
+    }
+
+    // -----------------------------------------------------------------------
+    // Public API methods
+    // -----------------------------------------------------------------------
+
+    /// Fetch a GitLab issue by IID (e.g., "42" or "#42").
+    pub async fn fetch_gitlab_issue(&self, task_id: &str) -> Result<ParentIssue, GitlabError> {
+        let iid = parse_issue_iid(task_id)?;
+        let resp: GitlabIssueResponse = self.get(&format!("issues/{iid}")).await?;
+
+        Ok(ParentIssue {
+            id: resp.iid.to_string(),
+            identifier: resp.iid.to_string(),
+            title: resp.title.unwrap_or_default(),
+            git_branch_name: gitlab_branch_name(resp.iid),
+        })
+    }
+
+    /// Fetch the current status name for a GitLab issue.
+    pub async fn fetch_gitlab_issue_status(&self, task_id: &str) -> Result<String, GitlabError> {
+        let iid = parse_issue_iid(task_id)?;
+        let resp: GitlabIssueResponse = self.get(&format!("issues/{iid}")).await?;
+
+        Ok(gitlab_status_name(
+            resp.state.as_deref().unwrap_or("opened"),
+            resp.labels.as_deref().unwrap_or_default(),
+        ))
+    }
+
+    /// Fetch a GitLab issue's current description.
+    pub async fn fetch_gitlab_issue_description(
+        &self,
+        task_id: &str,
+    ) -> Result<String, GitlabError> {
+        let iid = parse_issue_iid(task_id)?;
+        let resp: GitlabIssueResponse = self.get(&format!("issues/{iid}")).await?;
+        Ok(resp.description.unwrap_or_default())
+    }
+
+    /// Bodies of the notes added to an issue at or after `since` (RFC 3339),
+    /// among its 100 most recent.
+    pub async fn fetch_gitlab_comments_since(
+        &self,
+        task_id: &str,
+        since: &str,
+    ) -> Result<Vec<String>, GitlabError> {
+        let iid = parse_issue_iid(task_id)?;
+        let notes: Vec<GitlabNoteBody> = self
+            .get(&format!(
+                "issues/{iid}/notes?sort=desc&order_by=created_at&per_page=100"
+            ))
+            .await?;
+        let since = chrono::DateTime::parse_from_rfc3339(since).ok();
+        Ok(notes
+            .into_iter()
+            .filter(|n| {
+                let created = n
+                    .created_at
+                    .as_deref()
+                    .and_then(|c| chrono::DateTime::parse_from_rfc3339(c).ok());
+                match (since, created) {
+                    (Some(since), Some(created)) => created >= since,
+                    _ => true,
+                }
+            })
+            .filter_map(|n| n.body)
+            .collect())
+    }
+
+    /// Fetch sub-tasks of a parent issue.
+    ///
+    /// Every issue linked to the parent with a `relates_to` link is a sub-task;
+    /// each sub-task's own `is_blocked_by` links become its blockers.
+    pub async fn fetch_gitlab_sub_tasks(
+        &self,
+        parent_id: &str,
+    ) -> Result<Vec<LinearIssue>, GitlabError> {
+        let iid = parse_issue_iid(parent_id)?;
+        let links: Vec<GitlabLinkedIssue> = self.get(&format!("issues/{iid}/links")).await?;
+
+        let mut sub_tasks = Vec::new();
+        for linked in links
+            .into_iter()
+            .filter(|l| l.link_type.as_deref().unwrap_or("relates_to") == "relates_to")
+        {
+            let child_links: Vec<GitlabLinkedIssue> = self
+                .get(&format!("issues/{}/links", linked.iid))
+                .await
+                .unwrap_or_default();
+
+            let identifier = linked.iid.to_string();
+            sub_tasks.push(LinearIssue {
+                id: identifier.clone(),
+                identifier,
+                title: linked.title.unwrap_or_default(),
+                status: gitlab_status_name(
+                    linked.state.as_deref().unwrap_or("opened"),
+                    linked.labels.as_deref().unwrap_or_default(),
+                ),
+                git_branch_name: gitlab_branch_name(linked.iid),
+                relations: Some(Relations {
+                    blocked_by: extract_blocked_by_relations(&child_links),
+                    blocks: Vec::new(),
+                }),
+                scoring: None,
+                priority: None,
+                labels: linked.labels.unwrap_or_default(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
+            });
+        }
+
+        Ok(sub_tasks)
+    }
+
+    /// Update a GitLab issue's status.
+    ///
+    /// "Done"/"Closed"/"Canceled" close the issue. Any other status reopens it
+    /// and swaps its status label (e.g., "In Review"); "Open", "Todo" and
+    /// "Backlog" just clear the label.
+    pub async fn update_gitlab_issue_status(
+        &self,
+        task_id: &str,
+        target_status: &str,
+    ) -> Result<(), GitlabError> {
+        let iid = parse_issue_iid(task_id)?;
+        let body = build_status_update(target_status);
+        let _: GitlabIssueResponse = self
+            .send_json(reqwest::Method::PUT, &format!("issues/{iid}"), &body)
+            .await?;
+        Ok(())
+    }
+
+    /// Replace an issue's description.
+    pub async fn update_gitlab_issue_description(
+        &self,
+        task_id: &str,
+        description: &str,
+    ) -> Result<(), GitlabError> {
+        let iid = parse_issue_iid(task_id)?;
+        let _: GitlabIssueResponse = self
+            .send_json(
+                reqwest::Method::PUT,
+                &format!("issues/{iid}"),
+                &serde_json::json!({ "description": description }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Add a note (comment) to a GitLab issue.
+    pub async fn add_gitlab_comment(
+        &self,
+        task_id: &str,
+        body: &str,
+    ) -> Result<GitlabNoteResult, GitlabError> {
+        let iid = parse_issue_iid(task_id)?;
+        let resp: GitlabNoteResponse = self
+            .send_json(
+                reqwest::Method::POST,
+                &format!("issues/{iid}/notes"),
+                &serde_json::json!({ "body": body }),
+            )
+            .await?;
+
+        Ok(GitlabNoteResult { id: resp.id })
+    }
+
+    /// Create a new GitLab issue.
+    pub async fn create_gitlab_issue(
+        &self,
+        options: &CreateGitlabIssueOptions,
+    ) -> Result<GitlabCreatedIssue, GitlabError> {
+        let mut body = serde_json::json!({ "title": &options.title });
+        let body_obj = body.as_object_mut().unwrap();
+
+        if let Some(ref description) = options.description {
+            body_obj.insert("description".to_string(), serde_json::json!(description));
+        }
+        if let Some(ref labels) = options.labels {
+            if !labels.is_empty() {
+                body_obj.insert("labels".to_string(), serde_json::json!(labels.join(",")));
+            }
+        }
+
+        let resp: GitlabIssueResponse = self
+            .send_json(reqwest::Method::POST, "issues", &body)
+            .await?;
+
+        Ok(GitlabCreatedIssue {
+            id: resp.id,
+            iid: resp.iid,
+            web_url: resp.web_url.unwrap_or_default(),
+        })
+    }
+
+    /// Create an issue link of `link_type` ("relates_to", "blocks" or "is_blocked_by").
+    ///
+    /// Links are created from `source_iid` to `target_iid` in the same project.
+    pub async fn create_gitlab_issue_link(
+        &self,
+        source_iid: &str,
+        target_iid: &str,
+        link_type: &str,
+    ) -> Result<(), GitlabError> {
+        let source = parse_issue_iid(source_iid)?;
+        let target = parse_issue_iid(target_iid)?;
+        let _: serde_json::Value = self
+            .send_json(
+                reqwest::Method::POST,
+                &format!("issues/{source}/links"),
+                &serde_json::json!({
+                    "target_project_id": &self.project,
+                    "target_issue_iid": target,
+                    "link_type": link_type,
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Create a merge request.
+    pub async fn create_gitlab_merge_request(
+        &self,
+        options: &CreateMergeRequestOptions,
+    ) -> Result<GitlabMergeRequest, GitlabError> {
+        let body = build_merge_request_body(options);
+        let resp: GitlabMergeRequestResponse = self
+            .send_json(reqwest::Method::POST, "merge_requests", &body)
+            .await?;
+
+        Ok(GitlabMergeRequest {
+            iid: resp.iid,
+            web_url: resp.web_url.unwrap_or_default(),
+        })
+    }
+
+    /// Look up the user ID for each username, for merge request reviewers.
+    pub async fn resolve_gitlab_user_ids(
+        &self,
+        usernames: &[String],
+    ) -> Result<Vec<u64>, GitlabError> {
+        let mut ids = Vec::with_capacity(usernames.len());
+        for username in usernames {
+            let username = username.trim().trim_start_matches('@');
+            let request = self
+                .api_request(reqwest::Method::GET, "users")
+                .query(&[("username", username)]);
+            let users: Vec<GitlabIdRef> = self.send_request(request, "users").await?;
+            let user = users
+                .first()
+                .ok_or_else(|| GitlabError::NotFound(format!("user {username}")))?;
+            ids.push(user.id);
+        }
+        Ok(ids)
+    }
+
+    /// Fetch the username of the user the token belongs to.
+    pub async fn fetch_gitlab_viewer(&self) -> Result<String, GitlabError> {
+        let request = self.api_request(reqwest::Method::GET, "user");
+        let user: GitlabUserResponse = self.send_request(request, "user").await?;
+        Ok(user.username)
+    }
+
+    /// Look up a project milestone's ID by its title.
+    pub async fn resolve_gitlab_milestone_id(&self, title: &str) -> Result<u64, GitlabError> {
+        let request = self
+            .request(reqwest::Method::GET, "milestones")
+            .query(&[("title", title)]);
+        let milestones: Vec<GitlabIdRef> = self.send_request(request, "milestones").await?;
+        milestones
+            .first()
+            .map(|m| m.id)
+            .ok_or_else(|| GitlabError::NotFound(format!("milestone {title}")))
+    }
+
+    /// The most recent merge request opened from `branch`, in any state.
+    pub async fn fetch_gitlab_merge_request_for_branch(
+        &self,
+        branch: &str,
+    ) -> Result<Option<GitlabMergeRequestStatus>, GitlabError> {
+        let request = self
+            .request(reqwest::Method::GET, "merge_requests")
+            .query(&[("source_branch", branch), ("state", "all")]);
+        let found: Vec<GitlabMergeRequestResponse> =
+            self.send_request(request, "merge_requests").await?;
+        // The list omits the pipeline, so fetch the merge request itself
+        match found.first() {
+            Some(mr) => Ok(Some(self.get(&format!("merge_requests/{}", mr.iid)).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Merge the merge request once its pipeline succeeds.
+    ///
+    /// GitLab merges at once when the project runs no pipeline. It answers
+    /// 405 or 422 while the new merge request has no pipeline yet, so those
+    /// are retried briefly before giving up.
+    pub async fn enable_gitlab_auto_merge(
+        &self,
+        iid: u64,
+        squash: bool,
+    ) -> Result<GitlabAutoMerge, GitlabError> {
+        let body = serde_json::json!({
+            "merge_when_pipeline_succeeds": true,
+            "squash": squash,
+        });
+        let path = format!("merge_requests/{iid}/merge");
+        let mut attempt = 1;
+        loop {
+            match self
+                .send_json::<GitlabMergeRequestStatus, _>(reqwest::Method::PUT, &path, &body)
+                .await
+            {
+                Ok(mr) => return Ok(auto_merge_outcome(&mr)),
+                Err(GitlabError::HttpError { status, message }) if matches!(status, 405 | 422) => {
+                    if attempt >= AUTO_MERGE_ATTEMPTS {
+                        return Err(GitlabError::HttpError {
+                            status,
+                            message: format!(
+                                "merge request !{iid} cannot be set to merge yet (draft, no pipeline, or failing merge checks): {message}"
+                            ),
+                        });
+                    }
+                    attempt += 1;
+                    tokio::time::sleep(AUTO_MERGE_RETRY_DELAY).await;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// What asking GitLab to merge on pipeline success did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitlabAutoMerge {
+    /// Merges once the pipeline succeeds
+    Scheduled,
+    /// Merged right away; the project runs no pipeline
+    Merged,
+}
+
+fn auto_merge_outcome(mr: &GitlabMergeRequestStatus) -> GitlabAutoMerge {
+    if mr.state == "merged" {
+        GitlabAutoMerge::Merged
+    } else {
+        GitlabAutoMerge::Scheduled
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+fn gitlab_config() -> Option<GitlabConfig> {
+    let paths = resolve_paths();
+    read_config(&paths.config_path).ok()?.gitlab
+}
+
+/// Build the `/projects/:id` base URL, URL-encoding namespaced project paths.
+fn project_base_url(host: &str, project: &str) -> String {
+    let normalized_host = if host.starts_with("https://") || host.starts_with("http://") {
+        host.to_string()
+    } else {
+        format!("https://{host}")
+    };
+    let normalized_host = normalized_host.trim_end_matches('/');
+    let encoded_project = project.trim().trim_matches('/').replace('/', "%2F");
+
+    format!("{normalized_host}/api/v4/projects/{encoded_project}")
+}
+
+/// Parse an issue IID from "42" or "#42".
+fn parse_issue_iid(task_id: &str) -> Result<u64, GitlabError> {
+    task_id
+        .trim()
+        .trim_start_matches('#')
+        .parse()
+        .map_err(|_| GitlabError::InvalidIssueIid(task_id.to_string()))
+}
+
+fn gitlab_branch_name(iid: u64) -> String {
+    format!("feature/issue-{iid}")
+}
+
+fn is_status_label(label: &str) -> bool {
+    STATUS_LABELS.iter().any(|s| s.eq_ignore_ascii_case(label))
+}
+
+/// Derive a status name from a GitLab issue state and its labels.
+///
+/// Closed issues are "Done". Open issues report their status label
+/// (e.g., "In Progress") if present, otherwise "Open".
+pub fn gitlab_status_name(state: &str, labels: &[String]) -> String {
+    if state.eq_ignore_ascii_case("closed") {
+        return "Done".to_string();
+    }
+
+    labels
+        .iter()
+        .find(|l| is_status_label(l))
+        .cloned()
+        .unwrap_or_else(|| "Open".to_string())
+}
+
+/// Build the PUT body that moves an issue to `target_status`.
+fn build_status_update(target_status: &str) -> serde_json::Value {
+    let status_labels = STATUS_LABELS.join(",");
+
+    match target_status.to_lowercase().as_str() {
+        "done" | "completed" | "closed" | "canceled" | "cancelled" => serde_json::json!({
+            "state_event": "close",
+            "remove_labels": status_labels,
+        }),
+        "open" | "todo" | "backlog" => serde_json::json!({
+            "state_event": "reopen",
+            "remove_labels": status_labels,
+        }),
+        _ => {
+            let label = STATUS_LABELS
+                .iter()
+                .find(|s| s.eq_ignore_ascii_case(target_status))
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| target_status.to_string());
+            let remove: Vec<&str> = STATUS_LABELS
+                .iter()
+                .copied()
+                .filter(|s| !s.eq_ignore_ascii_case(&label))
+                .collect();
+            serde_json::json!({
+                "state_event": "reopen",
+                "add_labels": label,
+                "remove_labels": remove.join(","),
+            })
+        }
+    }
+}
+
+fn build_merge_request_body(options: &CreateMergeRequestOptions) -> serde_json::Value {
+    let title = if options.draft && !options.title.starts_with("Draft:") {
+        format!("Draft: {}", options.title)
+    } else {
+        options.title.clone()
+    };
+
+    let mut body = serde_json::json!({
+        "source_branch": &options.source_branch,
+        "target_branch": &options.target_branch,
+        "title": title,
+        "remove_source_branch": options.remove_source_branch,
+    });
+    let fields = body.as_object_mut().unwrap();
+    if let Some(ref description) = options.description {
+        fields.insert("description".to_string(), serde_json::json!(description));
+    }
+    if !options.labels.is_empty() {
+        fields.insert(
+            "labels".to_string(),
+            serde_json::json!(options.labels.join(",")),
+        );
+    }
+    if !options.reviewer_ids.is_empty() {
+        fields.insert(
+            "reviewer_ids".to_string(),
+            serde_json::json!(options.reviewer_ids),
+        );
+    }
+    if let Some(milestone_id) = options.milestone_id {
+        fields.insert("milestone_id".to_string(), serde_json::json!(milestone_id));
+    }
+    body
+}
+
+/// Extract "blocked by" relations from a sub-task's issue links.
+fn extract_blocked_by_relations(links: &[GitlabLinkedIssue]) -> Vec<Relation> {
+    links
+        .iter()
+        .filter(|l| l.link_type.as_deref() == Some("is_blocked_by"))
+        .map(|l| Relation {
+            id: l.iid.to_string(),
+            identifier: l.iid.to_string(),
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn linked(iid: u64, link_type: &str) -> GitlabLinkedIssue {
+        GitlabLinkedIssue {
+            iid,
+            title: Some(format!("Issue {iid}")),
+            state: Some("opened".to_string()),
+            labels: None,
+            link_type: Some(link_type.to_string()),
+        }
+    }
+
+    // -- URL construction tests --
+
+    #[test]
+    fn test_project_base_url_encodes_namespace() {
+        assert_eq!(
+            project_base_url("gitlab.com", "group/sub/project"),
+            "https://gitlab.com/api/v4/projects/group%2Fsub%2Fproject"
+        );
+    }
+
+    #[test]
+    fn test_project_base_url_with_scheme_and_numeric_id() {
+        assert_eq!(
+            project_base_url("https://gitlab.example.com/", "1234"),
+            "https://gitlab.example.com/api/v4/projects/1234"
+        );
+    }
+
+    #[test]
+    fn test_parse_issue_iid() {
+        assert_eq!(parse_issue_iid("42").unwrap(), 42);
+        assert_eq!(parse_issue_iid("#7").unwrap(), 7);
+        assert!(matches!(
+            parse_issue_iid("PROJ-1"),
+            Err(GitlabError::InvalidIssueIid(_))
+        ));
+    }
+
+    // -- Status tests --
+
+    #[test]
+    fn test_status_name() {
+        let labels = vec!["backend".to_string(), "In Progress".to_string()];
+        assert_eq!(gitlab_status_name("closed", &labels), "Done");
+        assert_eq!(gitlab_status_name("opened", &labels), "In Progress");
+        assert_eq!(gitlab_status_name("opened", &[]), "Open");
+    }
+
+    #[test]
+    fn test_status_update_close() {
+        let body = build_status_update("Done");
+        assert_eq!(body["state_event"], "close");
+        assert_eq!(body["remove_labels"], "In Progress,In Review");
+    }
+
+    #[test]
+    fn test_status_update_in_review_swaps_label() {
+        let body = build_status_update("in review");
+        assert_eq!(body["state_event"], "reopen");
+        assert_eq!(body["add_labels"], "In Review");
+        assert_eq!(body["remove_labels"], "In Progress");
+    }
+
+    // -- Merge request tests --
+
+    #[test]
+    fn test_auto_merge_outcome() {
+        let mr = |state: &str| -> GitlabMergeRequestStatus {
+            serde_json::from_value(serde_json::json!({
+                "iid": 7,
+                "state": state,
+                "merge_when_pipeline_succeeds": state == "opened",
+            }))
+            .unwrap()
+        };
+        assert_eq!(
+            auto_merge_outcome(&mr("opened")),
+            GitlabAutoMerge::Scheduled
+        );
+        // No pipeline: GitLab merges right away
+        assert_eq!(auto_merge_outcome(&mr("merged")), GitlabAutoMerge::Merged);
+    }
+
+    #[test]
+    fn test_merge_request_body_draft_prefix() {
+        let options = CreateMergeRequestOptions {
+            source_branch: "feature/issue-42".to_string(),
+            target_branch: "main".to_string(),
+            title: "Add widgets".to_string(),
+            description: Some("Closes #42".to_string()),
+            draft: true,
+            remove_source_branch: true,
+            labels: vec![],
+            reviewer_ids: vec![],
+            milestone_id: None,
+        };
+        let body = build_merge_request_body(&options);
+        assert_eq!(body["title"], "Draft: Add widgets");
+        assert_eq!(body["source_branch"], "feature/issue-42");
+        assert_eq!(body["target_branch"], "main");
+        assert_eq!(body["description"], "Closes #42");
+    }
+
+    #[test]
+    fn test_merge_request_body_without_draft() {
+        let options = CreateMergeRequestOptions {
+            source_branch: "b".to_string(),
+            target_branch: "main".to_string(),
+            title: "Title".to_string(),
+            description: None,
+            draft: false,
+            remove_source_branch: false,
+            labels: vec![],
+            reviewer_ids: vec![],
+            milestone_id: None,
+        };
+        let body = build_merge_request_body(&options);
+        assert_eq!(body["title"], "Title");
+        assert!(body.get("description").is_none());
+        assert!(body.get("labels").is_none());
+        assert!(body.get("reviewer_ids").is_none());
+    }
+
+    #[test]
+    fn test_merge_request_body_with_reviewers_labels_and_milestone() {
+        let options = CreateMergeRequestOptions {
+            source_branch: "b".to_string(),
+            target_branch: "main".to_string(),
+            title: "Title".to_string(),
+            description: None,
+            draft: false,
+            remove_source_branch: true,
+            labels: vec!["backend".to_string(), "mobius".to_string()],
+            reviewer_ids: vec![7, 9],
+            milestone_id: Some(3),
+        };
+        let body = build_merge_request_body(&options);
+        assert_eq!(body["labels"], "backend,mobius");
+        assert_eq!(body["reviewer_ids"], serde_json::json!([7, 9]));
+        assert_eq!(body["milestone_id"], 3);
+    }
+
+    // -- Link parsing tests --
+
+    #[test]
+    fn test_extract_blocked_by_relations() {
+        let links = vec![
+            linked(10, "is_blocked_by"),
+            linked(11, "relates_to"),
+            linked(12, "blocks"),
+            linked(13, "is_blocked_by"),
+        ];
+        let result = extract_blocked_by_relations(&links);
+        let ids: Vec<&str> = result.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["10", "13"]);
+    }
+
+    #[test]
+    fn test_extract_blocked_by_relations_empty() {
+        assert!(extract_blocked_by_relations(&[]).is_empty());
+    }
+
+    // -- Error type tests --
+
+    #[test]
+    fn test_error_display_auth_failed() {
+        let err = GitlabError::AuthFailed;
+        assert!(err.to_string().contains("401"));
+        assert!(err.to_string().contains("GITLAB_TOKEN"));
+    }
+}
@@ -0,0 +1,493 @@
+//! Shared HTTP layer for the backend API clients.
+//!
+//! Linear, Jira, GitHub and GitLab requests all go through [`HttpClient`],
+//! which applies the `http:` config section: a per-request timeout, an
+//! optional proxy, a process-wide cap on requests in flight, and retries with
+//! jittered exponential backoff on 429, 5xx, timeouts and connection
+//! failures. A `Retry-After` header from the server takes precedence over the
+//! computed backoff. POST and PATCH are not idempotent, so a 5xx or timeout
+//! (which may come after the server applied them) is not retried; they are
+//! retried only when they never reached the server or were refused by its
+//! rate limiter. Read-only POSTs (GraphQL queries, Jira's JQL search) go
+//! through [`HttpClient::send_idempotent`] and are retried like a GET.
+//!
+//! Rate-limit headers (`X-RateLimit-Remaining`/`-Reset` and the Linear and
+//! GitLab variants) are honoured too: once a host reports its window is used
+//! up, further requests to it wait for the reset instead of drawing 429s, and
+//! a 400 or 403 sent with an exhausted window (how Linear and GitHub report
+//! rate limiting) is retried like a 429.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use tokio::sync::Semaphore;
+use tracing::{debug, field, info_span, warn, Instrument};
+
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::types::HttpConfig;
+
+/// Upper bound for a single backoff or `Retry-After` wait
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Requests in flight across every client; sized by the first client built.
+static IN_FLIGHT: OnceLock<Semaphore> = OnceLock::new();
+
+/// Hosts whose rate-limit window is used up, and when it resets
+static PAUSED_UNTIL: OnceLock<Mutex<HashMap<String, Instant>>> = OnceLock::new();
+
+/// Headers carrying the requests left in the current rate-limit window
+const REMAINING_HEADERS: [&str; 3] = [
+    "x-ratelimit-remaining",
+    "x-ratelimit-requests-remaining",
+    "ratelimit-remaining",
+];
+
+/// Headers carrying when the rate-limit window resets
+const RESET_HEADERS: [&str; 3] = [
+    "x-ratelimit-reset",
+    "x-ratelimit-requests-reset",
+    "ratelimit-reset",
+];
+
+/// `reqwest::Client` wrapper adding retries, timeouts and a concurrency cap.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    client: reqwest::Client,
+    config: HttpConfig,
+}
+
+impl HttpClient {
+    /// Build a client from the project's `http:` config, or defaults if the
+    /// config cannot be read.
+    pub fn new() -> Self {
+        let config = read_config(&resolve_paths().config_path)
+            .map(|c| c.http)
+            .unwrap_or_default();
+        Self::with_config(config)
+    }
+
+    pub fn with_config(config: HttpConfig) -> Self {
+        let mut builder =
+            reqwest::Client::builder().timeout(Duration::from_secs(config.timeout_seconds));
+        if let Some(proxy) = &config.proxy {
+            match reqwest::Proxy::all(proxy) {
+                Ok(proxy) => builder = builder.proxy(proxy),
+                Err(e) => warn!("Ignoring invalid http.proxy {}: {}", proxy, e),
+            }
+        }
+        let client = builder.build().unwrap_or_else(|e| {
+            warn!("Failed to build HTTP client ({}); using defaults", e);
+            reqwest::Client::new()
+        });
+        Self { client, config }
+    }
+
+    /// Start a request; send it with [`HttpClient::send`].
+    pub fn request(&self, method: Method, url: impl reqwest::IntoUrl) -> RequestBuilder {
+        self.client.request(method, url)
+    }
+
+    /// Send `request`, retrying transient failures.
+    ///
+    /// Returns the final response whatever its status, so callers keep their
+    /// own error mapping; only the last attempt's transport error surfaces.
+    pub async fn send(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        self.send_with_retries(request, false).await
+    }
+
+    /// Like [`HttpClient::send`], for a POST or PATCH the caller knows is
+    /// safe to repeat, so 5xx responses and timeouts are retried as well.
+    pub async fn send_idempotent(&self, request: RequestBuilder) -> reqwest::Result<Response> {
+        self.send_with_retries(request, true).await
+    }
+
+    async fn send_with_retries(
+        &self,
+        request: RequestBuilder,
+        idempotent: bool,
+    ) -> reqwest::Result<Response> {
+        let limiter = IN_FLIGHT.get_or_init(|| Semaphore::new(self.config.max_concurrent.max(1)));
+        let mut attempt = 0;
+        loop {
+            // Streaming bodies cannot be replayed, so they get a single attempt.
+            let Some(this_try) = request.try_clone() else {
+                let _permit = limiter.acquire().await.ok();
+                return request.send().await;
+            };
+            let (client, built) = this_try.build_split();
+            let built = built?;
+            let method = built.method().clone();
+            let url = built.url().clone();
+            let host = url.host_str().unwrap_or_default().to_string();
+            wait_for_window(&host).await;
+
+            let span = info_span!(
+                "http.request",
+                http.method = %method,
+                http.host = url.host_str().unwrap_or_default(),
+                http.path = url.path(),
+                attempt = attempt + 1,
+                http.status = field::Empty,
+            );
+            let started = Instant::now();
+            let result = async {
+                let _permit = limiter.acquire().await.ok();
+                client.execute(built).await
+            }
+            .instrument(span.clone())
+            .await;
+            let elapsed_ms = started.elapsed().as_millis();
+            if let Ok(resp) = &result {
+                span.record("http.status", resp.status().as_u16());
+            }
+
+            let idempotent = idempotent || is_idempotent(&method);
+            let retry = match &result {
+                Ok(resp) => {
+                    debug!("{} {} -> {} ({}ms)", method, url, resp.status(), elapsed_ms);
+                    if rate_limit_exhausted(resp.headers()) {
+                        pause_host(&host, rate_limit_reset(resp.headers()));
+                    }
+                    (is_rate_limited(resp) || (idempotent && is_retryable_status(resp.status())))
+                        .then(|| (resp.status().to_string(), rate_limit_wait(resp.headers())))
+                }
+                Err(e) => {
+                    debug!("{} {} failed after {}ms: {}", method, url, elapsed_ms, e);
+                    (e.is_connect() || (idempotent && e.is_timeout()))
+                        .then(|| (e.to_string(), None))
+                }
+            };
+
+            match retry {
+                Some((reason, server_delay)) if attempt < self.config.max_retries => {
+                    let delay = server_delay
+                        .unwrap_or_else(|| backoff_delay(self.config.backoff_ms, attempt));
+                    attempt += 1;
+                    warn!(
+                        "{} {}: {}; retrying in {}ms ({}/{})",
+                        method,
+                        url.path(),
+                        reason,
+                        delay.as_millis(),
+                        attempt,
+                        self.config.max_retries
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                _ => {
+                    if let Err(e) = &result {
+                        if e.is_connect() {
+                            crate::offline::mark_unreachable(&host);
+                        }
+                    }
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether sending a `method` request twice has the same effect as once.
+fn is_idempotent(method: &Method) -> bool {
+    !matches!(*method, Method::POST | Method::PATCH)
+}
+
+/// 429 and server errors other than 501 are worth another attempt.
+fn is_retryable_status(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (status.is_server_error() && status != StatusCode::NOT_IMPLEMENTED)
+}
+
+/// Whether `resp` was refused for rate limiting: a 429, or a 400/403 sent
+/// with an exhausted rate-limit window.
+pub fn is_rate_limited(resp: &Response) -> bool {
+    let status = resp.status();
+    status == StatusCode::TOO_MANY_REQUESTS
+        || (matches!(status, StatusCode::BAD_REQUEST | StatusCode::FORBIDDEN)
+            && rate_limit_exhausted(resp.headers()))
+}
+
+/// How long the server asked clients to wait, from `Retry-After` or the
+/// rate-limit reset headers.
+pub fn rate_limit_wait(headers: &HeaderMap) -> Option<Duration> {
+    retry_after(headers).or_else(|| {
+        rate_limit_exhausted(headers)
+            .then(|| rate_limit_reset(headers))
+            .flatten()
+    })
+}
+
+/// `; try again in 42s` for a known wait, for rate-limit error messages.
+pub fn retry_hint(wait: &Option<Duration>) -> String {
+    match wait {
+        Some(wait) => format!("; try again in {}s", wait.as_secs().max(1)),
+        None => String::new(),
+    }
+}
+
+/// Wait out a rate-limit window `host` reported as used up.
+async fn wait_for_window(host: &str) {
+    let until = PAUSED_UNTIL
+        .get()
+        .and_then(|paused| paused.lock().ok()?.get(host).copied());
+    let Some(until) = until else {
+        return;
+    };
+    let wait = until.saturating_duration_since(Instant::now());
+    if !wait.is_zero() {
+        debug!(
+            "{} rate limit exhausted; waiting {}ms for the window to reset",
+            host,
+            wait.as_millis()
+        );
+        tokio::time::sleep(wait).await;
+    }
+    if let Some(mut paused) = PAUSED_UNTIL.get().and_then(|p| p.lock().ok()) {
+        if paused.get(host).is_some_and(|u| *u <= Instant::now()) {
+            paused.remove(host);
+        }
+    }
+}
+
+/// Hold further requests to `host` until its window resets; without a reset
+/// header the retry backoff alone paces it.
+fn pause_host(host: &str, reset: Option<Duration>) {
+    let Some(reset) = reset.filter(|r| !r.is_zero()) else {
+        return;
+    };
+    let paused = PAUSED_UNTIL.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(mut paused) = paused.lock() {
+        paused.insert(host.to_string(), Instant::now() + reset);
+    }
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, names: &[&str]) -> Option<&'a str> {
+    names
+        .iter()
+        .find_map(|name| headers.get(*name)?.to_str().ok())
+        .map(str::trim)
+}
+
+/// Whether the rate-limit headers report no requests left in the window.
+fn rate_limit_exhausted(headers: &HeaderMap) -> bool {
+    header_str(headers, &REMAINING_HEADERS)
+        .and_then(|v| v.parse::<f64>().ok())
+        .is_some_and(|remaining| remaining <= 0.0)
+}
+
+/// Time until the rate-limit window resets. Servers send seconds from now,
+/// a Unix timestamp in seconds (GitHub, GitLab) or milliseconds (Linear),
+/// or an ISO 8601 date (Jira).
+fn rate_limit_reset(headers: &HeaderMap) -> Option<Duration> {
+    let value = header_str(headers, &RESET_HEADERS)?;
+    let now = chrono::Utc::now();
+    let at = match value.parse::<i64>() {
+        // Anything this small is a relative delay, not a timestamp
+        Ok(n) if n < 1_000_000_000 => {
+            return Some(Duration::from_secs(n.max(0) as u64).min(MAX_RETRY_DELAY))
+        }
+        Ok(n) if n < 1_000_000_000_000 => chrono::DateTime::from_timestamp(n, 0)?,
+        Ok(n) => chrono::DateTime::from_timestamp_millis(n)?,
+        // Jira sends minutes precision, e.g. `2024-05-22T12:34Z`
+        Err(_) => match chrono::DateTime::parse_from_rfc3339(value) {
+            Ok(at) => at.with_timezone(&chrono::Utc),
+            Err(_) => chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%MZ")
+                .ok()?
+                .and_utc(),
+        },
+    };
+    Some(
+        (at - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+            .min(MAX_RETRY_DELAY),
+    )
+}
+
+/// Delay requested by a `Retry-After` header, as seconds or an HTTP date.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    let delay = match value.parse::<u64>() {
+        Ok(seconds) => Duration::from_secs(seconds),
+        Err(_) => {
+            let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+            (at.with_timezone(&chrono::Utc) - chrono::Utc::now())
+                .to_std()
+                .unwrap_or(Duration::ZERO)
+        }
+    };
+    Some(delay.min(MAX_RETRY_DELAY))
+}
+
+/// Exponential backoff for retry `attempt` (0-based), jittered into the
+/// upper half of the window so concurrent clients spread out.
+fn backoff_delay(base_ms: u64, attempt: u32) -> Duration {
+    let window = base_ms
+        .saturating_mul(1u64 << attempt.min(20))
+        .min(MAX_RETRY_DELAY.as_millis() as u64);
+    Duration::from_millis(window / 2 + fastrand::u64(0..=window / 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_backoff_and_retry_after() {
+        for attempt in 0..4 {
+            let window = 100 * (1 << attempt);
+            let delay = backoff_delay(100, attempt).as_millis() as u64;
+            assert!(
+                (window / 2..=window).contains(&delay),
+                "{delay} outside {window}"
+            );
+        }
+        let capped = backoff_delay(1_000, 30);
+        assert!(capped >= MAX_RETRY_DELAY / 2 && capped <= MAX_RETRY_DELAY);
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RETRY_AFTER, "2".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(2)));
+        headers.insert(RETRY_AFTER, "3600".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(MAX_RETRY_DELAY));
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::BAD_GATEWAY));
+        assert!(!is_retryable_status(StatusCode::NOT_IMPLEMENTED));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn test_rate_limit_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-remaining", "12".parse().unwrap());
+        headers.insert("x-ratelimit-reset", "30".parse().unwrap());
+        assert!(!rate_limit_exhausted(&headers));
+        assert_eq!(rate_limit_wait(&headers), None);
+
+        headers.insert("x-ratelimit-remaining", "0".parse().unwrap());
+        assert!(rate_limit_exhausted(&headers));
+        assert_eq!(rate_limit_wait(&headers), Some(Duration::from_secs(30)));
+        headers.insert(RETRY_AFTER, "5".parse().unwrap());
+        assert_eq!(rate_limit_wait(&headers), Some(Duration::from_secs(5)));
+
+        // Linear: epoch milliseconds
+        let mut linear = HeaderMap::new();
+        linear.insert("x-ratelimit-requests-remaining", "0".parse().unwrap());
+        let reset_ms = chrono::Utc::now().timestamp_millis() + 20_000;
+        linear.insert(
+            "x-ratelimit-requests-reset",
+            reset_ms.to_string().parse().unwrap(),
+        );
+        let wait = rate_limit_wait(&linear).unwrap();
+        assert!(wait > Duration::from_secs(15) && wait <= Duration::from_secs(20));
+
+        // GitHub: epoch seconds, already past
+        let mut github = HeaderMap::new();
+        github.insert("x-ratelimit-reset", "1700000000".parse().unwrap());
+        assert_eq!(rate_limit_reset(&github), Some(Duration::ZERO));
+
+        // Jira: ISO 8601, capped
+        let mut jira = HeaderMap::new();
+        jira.insert("x-ratelimit-reset", "2999-01-01T00:00Z".parse().unwrap());
+        assert_eq!(rate_limit_reset(&jira), Some(MAX_RETRY_DELAY));
+        jira.insert("x-ratelimit-reset", "2020-01-01T00:00:00Z".parse().unwrap());
+        assert_eq!(rate_limit_reset(&jira), Some(Duration::ZERO));
+
+        assert_eq!(retry_hint(&None), "");
+        assert_eq!(
+            retry_hint(&Some(Duration::from_millis(300))),
+            "; try again in 1s"
+        );
+    }
+
+    /// Serve `statuses` in order, one per connection, and count requests.
+    async fn serve(statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            for status in statuses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = socket.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                let response = format!(
+                    "HTTP/1.1 {status} X\r\nRetry-After: 0\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok"
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        (url, hits)
+    }
+
+    #[tokio::test]
+    async fn test_send_retries_transient_statuses() {
+        let client = HttpClient::with_config(HttpConfig {
+            max_retries: 2,
+            backoff_ms: 1,
+            ..HttpConfig::default()
+        });
+
+        let (url, hits) = serve(vec![503, 429, 200]).await;
+        let resp = client.send(client.request(Method::GET, &url)).await;
+        assert_eq!(resp.unwrap().status(), StatusCode::OK);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        // A POST may have been applied before the 503, so it is not resent...
+        let (url, hits) = serve(vec![503, 200]).await;
+        let resp = client
+            .send(client.request(Method::POST, &url).body("{}"))
+            .await;
+        assert_eq!(resp.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // ...but a rate-limit refusal was never applied
+        let (url, hits) = serve(vec![429, 200]).await;
+        let resp = client
+            .send(client.request(Method::POST, &url).body("{}"))
+            .await;
+        assert_eq!(resp.unwrap().status(), StatusCode::OK);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // A read-only POST such as a GraphQL query is retried like a GET
+        let (url, hits) = serve(vec![503, 200]).await;
+        let query = r#"{"query":"query Issue($id: String!) { issue(id: $id) { id } }"}"#;
+        let resp = client
+            .send_idempotent(client.request(Method::POST, &url).body(query))
+            .await;
+        assert_eq!(resp.unwrap().status(), StatusCode::OK);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        let (url, hits) = serve(vec![500, 500, 500, 500]).await;
+        let resp = client.send(client.request(Method::GET, &url)).await;
+        assert_eq!(resp.unwrap().status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        let (url, hits) = serve(vec![404, 200]).await;
+        let resp = client.send(client.request(Method::GET, &url)).await;
+        assert_eq!(resp.unwrap().status(), StatusCode::NOT_FOUND);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
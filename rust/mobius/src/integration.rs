@@ -0,0 +1,615 @@
+//! Merge completed sub-task branches into an integration branch.
+//!
+//! Each completed sub-task's own branch (as named for stacked PRs, and built
+//! from the loop branch the same way when missing) is merged into the
+//! integration branch in dependency order, blockers first, in a scratch
+//! worktree. After every merge the verify commands of the sub-tasks merged so
+//! far are re-run, so a merge that breaks an earlier sub-task is caught at the
+//! merge that did it. The first conflict or failing verify command stops the
+//! run; the integration branch is only advanced past merges that verified, so
+//! it always points at the last green state.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::bisect::{git_output, list_branch_commits, ScratchWorktree};
+use crate::context::{atomic_write_json, get_context_path, get_integration_report_path};
+use crate::stack::{build_stack_branches, plan_stack, subtask_branch_name, StackEntry};
+use crate::types::config::SubTaskVerifyCommand;
+use crate::types::enums::TaskStatus;
+use crate::types::task_graph::{get_verification_task, TaskGraph};
+use crate::verify::{run_verify_command, VerifyRun};
+
+/// A completed sub-task whose branch is to be merged
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeCandidate {
+    pub identifier: String,
+    pub title: String,
+    pub branch: String,
+}
+
+/// What happened to one sub-task's branch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStatus {
+    /// Merged and every verify command passed
+    Merged,
+    /// Already contained in the integration branch
+    UpToDate,
+    /// No local or `origin` branch exists; skipped
+    NoBranch,
+    /// The merge conflicted and was aborted
+    Conflict,
+    /// The merge applied but a verify command failed afterwards
+    VerifyFailed,
+}
+
+/// A verify command run after a merge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeCheck {
+    /// Sub-task the command belongs to (`None` for a `--command` override)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subtask_id: Option<String>,
+    #[serde(flatten)]
+    pub run: VerifyRun,
+    pub log: String,
+}
+
+/// One merge into the integration branch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeStep {
+    pub identifier: String,
+    pub branch: String,
+    pub status: MergeStatus,
+    /// Merge commit, when one was made
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha: Option<String>,
+    /// Files left conflicted by the aborted merge
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<MergeCheck>,
+}
+
+/// How an integration run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrationOutcome {
+    /// Every completed branch was merged and verified
+    Integrated,
+    /// Stopped at a merge conflict
+    Conflict,
+    /// Stopped at a failing verify command
+    VerifyFailed,
+}
+
+/// Result of `mobius integrate`, saved to `.mobius/issues/{id}/execution/integration.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrationReport {
+    pub parent_id: String,
+    pub generated_at: String,
+    /// Base commit the integration branch was rebuilt from
+    pub base: String,
+    pub branch: String,
+    pub outcome: IntegrationOutcome,
+    /// Integration branch head: the last merge that verified
+    pub head: String,
+    pub steps: Vec<MergeStep>,
+    /// Sub-tasks not attempted because the run stopped first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub not_merged: Vec<String>,
+}
+
+/// Inputs to [`run_integration`]
+#[derive(Debug, Clone)]
+pub struct IntegrationPlan {
+    pub parent_id: String,
+    pub base: String,
+    pub branch: String,
+    /// Branches to merge, in order
+    pub candidates: Vec<MergeCandidate>,
+    /// Sub-task verify commands; those of merged sub-tasks run after each merge
+    pub verify_commands: Vec<SubTaskVerifyCommand>,
+    /// Run only this command after each merge instead
+    pub command: Option<String>,
+    pub timeout: Duration,
+    /// Where each verify run's full output is written
+    pub log_dir: PathBuf,
+}
+
+/// Default integration branch for a parent issue.
+pub fn default_integration_branch(parent_id: &str) -> String {
+    format!("integration/{}", parent_id.to_lowercase())
+}
+
+/// Completed agent sub-tasks in dependency order: every sub-task comes after
+/// the completed sub-tasks blocking it, ties broken by identifier.
+///
+/// The verification gate and human tasks have no branch of their own and are
+/// left out.
+pub fn merge_order(graph: &TaskGraph, loop_branch: &str) -> Vec<MergeCandidate> {
+    let gate = get_verification_task(graph).map(|t| t.id.as_str());
+    let done: HashSet<&str> = graph
+        .tasks
+        .values()
+        .filter(|t| t.status == TaskStatus::Done && t.task_type.is_agent())
+        .filter(|t| Some(t.id.as_str()) != gate)
+        .map(|t| t.id.as_str())
+        .collect();
+    let pending_blockers = |id: &str, merged: &HashSet<&str>| {
+        graph.tasks[id]
+            .blocked_by
+            .iter()
+            .any(|b| done.contains(b.as_str()) && !merged.contains(b.as_str()))
+    };
+
+    let mut remaining: BTreeSet<(&str, &str)> = done
+        .iter()
+        .map(|id| (graph.tasks[*id].identifier.as_str(), *id))
+        .collect();
+    let mut merged: HashSet<&str> = HashSet::new();
+    let mut order = Vec::new();
+    while let Some(&next) = remaining
+        .iter()
+        .find(|(_, id)| !pending_blockers(id, &merged))
+        // A cycle among completed tasks: fall back to identifier order
+        .or_else(|| remaining.iter().next())
+    {
+        remaining.remove(&next);
+        merged.insert(next.1);
+        let task = &graph.tasks[next.1];
+        order.push(MergeCandidate {
+            identifier: task.identifier.clone(),
+            title: task.title.clone(),
+            branch: subtask_branch_name(task, loop_branch),
+        });
+    }
+    order
+}
+
+/// Build the branches of `candidates` that exist neither locally nor on
+/// `origin` from the loop branch, as `mobius submit --stacked` does, so
+/// integrating does not depend on a stacked submit having run first.
+///
+/// Returns the identifiers whose branch was built.
+pub fn build_missing_branches(
+    repo_path: &Path,
+    parent_id: &str,
+    graph: &TaskGraph,
+    base: &str,
+    loop_branch: &str,
+    candidates: &[MergeCandidate],
+) -> Result<Vec<String>> {
+    let missing: Vec<&str> = candidates
+        .iter()
+        .filter(|c| resolve_branch(repo_path, &c.branch).is_none())
+        .map(|c| c.branch.as_str())
+        .collect();
+    let Some(head) = resolve_branch(repo_path, loop_branch) else {
+        return Ok(Vec::new());
+    };
+    if missing.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let identifiers: Vec<String> = graph.tasks.values().map(|t| t.identifier.clone()).collect();
+    let commits = list_branch_commits(repo_path, base, &head, &identifiers)?;
+    let entries: Vec<StackEntry> = plan_stack(graph, &commits, base, loop_branch)
+        .with_context(|| format!("Cannot build sub-task branches from {}", loop_branch))?
+        .into_iter()
+        .filter(|e| missing.contains(&e.branch.as_str()))
+        .collect();
+    build_stack_branches(repo_path, parent_id, &entries)?;
+    Ok(entries.into_iter().map(|e| e.identifier).collect())
+}
+
+/// Rebuild `plan.branch` from `plan.base` by merging each candidate in turn,
+/// calling `on_step` after each one. Stops at the first conflict or failing
+/// verify command.
+pub fn run_integration<F>(
+    plan: &IntegrationPlan,
+    repo_path: &Path,
+    mut on_step: F,
+) -> Result<IntegrationReport>
+where
+    F: FnMut(&MergeStep),
+{
+    let base_sha = rev_parse(repo_path, &plan.base)
+        .with_context(|| format!("Base branch {} not found", plan.base))?;
+    git_output(repo_path, &["branch", "-f", &plan.branch, &base_sha])
+        .with_context(|| format!("Failed to reset {} to {}", plan.branch, plan.base))?;
+
+    let scratch = ScratchWorktree::create(
+        repo_path,
+        &format!("integrate-{}", plan.parent_id),
+        &base_sha,
+    )?;
+    let mut steps = Vec::new();
+    let result = (|| -> Result<IntegrationOutcome> {
+        let mut merged: Vec<&str> = Vec::new();
+        for candidate in &plan.candidates {
+            let step = merge_candidate(plan, &scratch.path, repo_path, candidate, &merged)?;
+            on_step(&step);
+            let status = step.status;
+            steps.push(step);
+            match status {
+                MergeStatus::Merged => {
+                    merged.push(&candidate.identifier);
+                    git_output(&scratch.path, &["branch", "-f", &plan.branch, "HEAD"])?;
+                }
+                MergeStatus::UpToDate => merged.push(&candidate.identifier),
+                MergeStatus::NoBranch => {}
+                MergeStatus::Conflict => return Ok(IntegrationOutcome::Conflict),
+                MergeStatus::VerifyFailed => return Ok(IntegrationOutcome::VerifyFailed),
+            }
+        }
+        Ok(IntegrationOutcome::Integrated)
+    })();
+    scratch.remove();
+    let outcome = result?;
+
+    let not_merged = plan
+        .candidates
+        .iter()
+        .skip(steps.len())
+        .map(|c| c.identifier.clone())
+        .collect();
+    Ok(IntegrationReport {
+        parent_id: plan.parent_id.clone(),
+        generated_at: Utc::now().to_rfc3339(),
+        base: base_sha,
+        branch: plan.branch.clone(),
+        outcome,
+        head: rev_parse(repo_path, &plan.branch)?,
+        steps,
+        not_merged,
+    })
+}
+
+/// Merge one candidate into the scratch worktree's HEAD and verify it.
+fn merge_candidate(
+    plan: &IntegrationPlan,
+    dir: &Path,
+    repo_path: &Path,
+    candidate: &MergeCandidate,
+    merged: &[&str],
+) -> Result<MergeStep> {
+    let mut step = MergeStep {
+        identifier: candidate.identifier.clone(),
+        branch: candidate.branch.clone(),
+        status: MergeStatus::NoBranch,
+        sha: None,
+        conflicts: Vec::new(),
+        checks: Vec::new(),
+    };
+    let Some(source) = resolve_branch(repo_path, &candidate.branch) else {
+        return Ok(step);
+    };
+    if git_output(dir, &["merge-base", "--is-ancestor", &source, "HEAD"]).is_ok() {
+        step.status = MergeStatus::UpToDate;
+        return Ok(step);
+    }
+
+    let message = format!(
+        "Merge {} ({}) into {}",
+        candidate.branch, candidate.identifier, plan.branch
+    );
+    if let Err(e) = git_output(
+        dir,
+        &["merge", "--no-ff", "--no-edit", "-m", &message, &source],
+    ) {
+        let conflicts = git_output(dir, &["diff", "--name-only", "--diff-filter=U"])
+            .unwrap_or_default()
+            .lines()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let _ = git_output(dir, &["merge", "--abort"]);
+        if conflicts.is_empty() {
+            return Err(e).with_context(|| format!("Failed to merge {}", candidate.branch));
+        }
+        step.status = MergeStatus::Conflict;
+        step.conflicts = conflicts;
+        return Ok(step);
+    }
+    step.sha = Some(rev_parse(dir, "HEAD")?);
+
+    let mut subtasks = merged.to_vec();
+    subtasks.push(&candidate.identifier);
+    step.status = MergeStatus::Merged;
+    for (subtask_id, command) in checks_after(plan, &subtasks) {
+        let name = subtask_id.unwrap_or("command");
+        let log_path = plan
+            .log_dir
+            .join(&candidate.identifier)
+            .join(format!("{}.log", name));
+        let run = run_verify_command(dir, command, plan.timeout, &log_path)?;
+        let passed = run.passed;
+        step.checks.push(MergeCheck {
+            subtask_id: subtask_id.map(str::to_string),
+            run,
+            log: log_path.display().to_string(),
+        });
+        if !passed {
+            step.status = MergeStatus::VerifyFailed;
+            break;
+        }
+    }
+    Ok(step)
+}
+
+/// Verify commands to run once `merged` are in: the override command, or the
+/// commands of the merged sub-tasks, each distinct command once.
+fn checks_after<'a>(plan: &'a IntegrationPlan, merged: &[&str]) -> Vec<(Option<&'a str>, &'a str)> {
+    if let Some(command) = &plan.command {
+        return vec![(None, command.as_str())];
+    }
+    let mut seen = HashSet::new();
+    plan.verify_commands
+        .iter()
+        .filter(|v| merged.iter().any(|m| v.subtask_id.eq_ignore_ascii_case(m)))
+        .filter(|v| seen.insert(v.command.as_str()))
+        .map(|v| (Some(v.subtask_id.as_str()), v.command.as_str()))
+        .collect()
+}
+
+/// The ref to merge for `branch`: the local branch, else `origin`'s.
+fn resolve_branch(repo_path: &Path, branch: &str) -> Option<String> {
+    [
+        format!("refs/heads/{}", branch),
+        format!("refs/remotes/origin/{}", branch),
+    ]
+    .into_iter()
+    .find(|r| rev_parse(repo_path, r).is_ok())
+}
+
+fn rev_parse(dir: &Path, rev: &str) -> Result<String> {
+    Ok(git_output(
+        dir,
+        &["rev-parse", "--verify", &format!("{}^{{commit}}", rev)],
+    )?
+    .trim()
+    .to_string())
+}
+
+/// Get the directory verify output of `mobius integrate` is written to.
+pub fn get_integration_log_dir(parent_id: &str) -> PathBuf {
+    get_context_path(parent_id).join("integration")
+}
+
+/// Read a previously written integration report.
+pub fn read_integration_report(parent_id: &str) -> Option<IntegrationReport> {
+    let content = fs::read_to_string(get_integration_report_path(parent_id)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Persist an integration report to `.mobius/issues/{id}/execution/integration.json`.
+pub fn write_integration_report(report: &IntegrationReport) -> Result<()> {
+    let path = get_integration_report_path(&report.parent_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    atomic_write_json(&path, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::task_graph::{build_task_graph, LinearIssue, Relation, Relations};
+    use std::process::Command;
+
+    fn issue(id: &str, title: &str, status: &str, blocked_by: &[&str]) -> LinearIssue {
+        LinearIssue {
+            id: id.to_string(),
+            identifier: id.to_string(),
+            title: title.to_string(),
+            status: status.to_string(),
+            git_branch_name: String::new(),
+            relations: Some(Relations {
+                blocked_by: blocked_by
+                    .iter()
+                    .map(|b| Relation {
+                        id: b.to_string(),
+                        identifier: b.to_string(),
+                    })
+                    .collect(),
+                blocks: vec![],
+            }),
+            scoring: None,
+            priority: None,
+            labels: vec![],
+            timeout_minutes: None,
+            task_type: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_merge_order_puts_blockers_first() {
+        let graph = build_task_graph(
+            "MOB-1",
+            "MOB-1",
+            &[
+                issue("MOB-2", "Parser", "Done", &["MOB-4"]),
+                issue("MOB-3", "Lexer", "Done", &[]),
+                issue("MOB-4", "Tokens", "Done", &["MOB-3"]),
+                issue("MOB-5", "Docs", "Done", &[]),
+                issue("MOB-6", "Wiring", "In Progress", &["MOB-2"]),
+                issue("MOB-7", "Verification Gate", "Done", &["MOB-2"]),
+            ],
+        );
+        let order = merge_order(&graph, "feat/mob-1");
+        let ids: Vec<&str> = order.iter().map(|c| c.identifier.as_str()).collect();
+        assert_eq!(ids, vec!["MOB-3", "MOB-4", "MOB-2", "MOB-5"]);
+        assert_eq!(order[0].branch, "feat/mob-1-mob-3");
+    }
+
+    #[test]
+    fn test_checks_after_runs_merged_commands_once() {
+        let verify = |id: &str, command: &str| SubTaskVerifyCommand {
+            subtask_id: id.to_string(),
+            title: String::new(),
+            command: command.to_string(),
+        };
+        let mut plan = IntegrationPlan {
+            parent_id: "MOB-1".to_string(),
+            base: "main".to_string(),
+            branch: "integration/mob-1".to_string(),
+            candidates: vec![],
+            verify_commands: vec![
+                verify("MOB-2", "cargo test"),
+                verify("MOB-3", "cargo test"),
+                verify("MOB-4", "npm test"),
+            ],
+            command: None,
+            timeout: Duration::from_secs(60),
+            log_dir: PathBuf::new(),
+        };
+        assert_eq!(
+            checks_after(&plan, &["MOB-3", "mob-4"]),
+            vec![(Some("MOB-3"), "cargo test"), (Some("MOB-4"), "npm test")]
+        );
+        assert!(checks_after(&plan, &["MOB-5"]).is_empty());
+
+        plan.command = Some("make check".to_string());
+        assert_eq!(checks_after(&plan, &["MOB-2"]), vec![(None, "make check")]);
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(dir)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn commit_on(dir: &Path, branch: &str, file: &str, content: &str) {
+        git(dir, &["checkout", "-q", "-B", branch, "main"]);
+        fs::write(dir.join(file), content).unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-qm", &format!("{} on {}", file, branch)]);
+        git(dir, &["checkout", "-q", "main"]);
+    }
+
+    #[test]
+    fn test_run_integration_merges_then_stops_at_conflict() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path().join("repo");
+        fs::create_dir(&repo).unwrap();
+        git(&repo, &["init", "-q", "-b", "main"]);
+        git(&repo, &["config", "user.email", "dev@example.com"]);
+        git(&repo, &["config", "user.name", "Dev"]);
+        fs::write(repo.join("shared.txt"), "base\n").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-qm", "base"]);
+        commit_on(&repo, "feat/mob-2", "a.txt", "a\n");
+        commit_on(&repo, "feat/mob-3", "shared.txt", "three\n");
+        commit_on(&repo, "feat/mob-4", "shared.txt", "four\n");
+
+        let candidate = |id: &str| MergeCandidate {
+            identifier: id.to_string(),
+            title: String::new(),
+            branch: format!("feat/{}", id.to_lowercase()),
+        };
+        let mut plan = IntegrationPlan {
+            parent_id: "MOB-1".to_string(),
+            base: "main".to_string(),
+            branch: "integration/mob-1".to_string(),
+            candidates: vec![
+                candidate("MOB-2"),
+                candidate("MOB-9"),
+                candidate("MOB-3"),
+                candidate("MOB-4"),
+                candidate("MOB-5"),
+            ],
+            verify_commands: vec![],
+            command: Some("test -f a.txt".to_string()),
+            timeout: Duration::from_secs(60),
+            log_dir: tmp.path().join("logs"),
+        };
+
+        let mut seen = Vec::new();
+        let report = run_integration(&plan, &repo, |s| seen.push(s.identifier.clone())).unwrap();
+        let statuses: Vec<MergeStatus> = report.steps.iter().map(|s| s.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                MergeStatus::Merged,
+                MergeStatus::NoBranch,
+                MergeStatus::Merged,
+                MergeStatus::Conflict
+            ]
+        );
+        assert_eq!(seen, vec!["MOB-2", "MOB-9", "MOB-3", "MOB-4"]);
+        assert_eq!(report.outcome, IntegrationOutcome::Conflict);
+        assert_eq!(report.steps[3].conflicts, vec!["shared.txt"]);
+        assert_eq!(report.not_merged, vec!["MOB-5"]);
+        assert_eq!(report.steps[2].sha.as_deref(), Some(report.head.as_str()));
+        assert!(report.steps[0].checks[0].run.passed);
+
+        // A failing check leaves the branch at the last merge that verified
+        plan.candidates = vec![candidate("MOB-2"), candidate("MOB-3")];
+        plan.command = Some("test ! -f a.txt || grep -q base shared.txt".to_string());
+        let report = run_integration(&plan, &repo, |_| {}).unwrap();
+        assert_eq!(report.outcome, IntegrationOutcome::VerifyFailed);
+        assert_eq!(report.steps[0].sha.as_deref(), Some(report.head.as_str()));
+        assert!(!report.steps[1].checks[0].run.passed);
+        assert!(Path::new(&report.steps[1].checks[0].log).exists());
+    }
+
+    #[test]
+    fn test_build_missing_branches_from_loop_branch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let repo = tmp.path();
+        git(repo, &["init", "-q", "-b", "main"]);
+        git(repo, &["config", "user.email", "dev@example.com"]);
+        git(repo, &["config", "user.name", "Dev"]);
+        fs::write(repo.join("base.txt"), "base\n").unwrap();
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-qm", "base"]);
+        git(repo, &["checkout", "-q", "-b", "feat/mob-1"]);
+        for (id, file) in [("MOB-2", "a.txt"), ("MOB-3", "b.txt")] {
+            fs::write(repo.join(file), "x\n").unwrap();
+            git(repo, &["add", "."]);
+            git(repo, &["commit", "-qm", &format!("{}: add {}", id, file)]);
+        }
+        git(repo, &["checkout", "-q", "main"]);
+        // MOB-2 already has a branch of its own, which is left alone
+        git(repo, &["branch", "feat/mob-1-mob-2", "main"]);
+
+        let graph = build_task_graph(
+            "MOB-1",
+            "MOB-1",
+            &[
+                issue("MOB-2", "A", "Done", &[]),
+                issue("MOB-3", "B", "Done", &["MOB-2"]),
+            ],
+        );
+        let candidates = merge_order(&graph, "feat/mob-1");
+        let built =
+            build_missing_branches(repo, "MOB-1", &graph, "main", "feat/mob-1", &candidates)
+                .unwrap();
+        assert_eq!(built, vec!["MOB-3"]);
+        let files = git_output(repo, &["ls-tree", "--name-only", "feat/mob-1-mob-3"]).unwrap();
+        assert!(files.contains("b.txt"));
+        assert_eq!(
+            rev_parse(repo, "feat/mob-1-mob-2").unwrap(),
+            rev_parse(repo, "main").unwrap()
+        );
+
+        // Nothing left to build
+        let built =
+            build_missing_branches(repo, "MOB-1", &graph, "main", "feat/mob-1", &candidates)
+                .unwrap();
+        assert!(built.is_empty());
+    }
+}
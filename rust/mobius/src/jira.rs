@@ -9,8 +9,13 @@
 use anyhow::Result;
 use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
 use tracing::warn;
 
+use crate::http_client::{is_rate_limited, rate_limit_wait, retry_hint, HttpClient};
+use crate::secrets;
+use crate::types::enums::{Backend, TaskType};
 use crate::types::task_graph::{LinearIssue, ParentIssue, Relation, Relations};
 
 /// Options for creating a Jira issue.
@@ -34,6 +39,26 @@ pub struct JiraCreatedIssue {
     pub self_url: String,
 }
 
+/// A workflow status defined in the Jira instance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraStatusInfo {
+    pub id: String,
+    pub name: String,
+    /// Status category key: `new`, `indeterminate`, or `done`
+    pub category: String,
+}
+
+/// A transition available on a specific issue.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JiraTransitionInfo {
+    pub id: String,
+    pub name: String,
+    pub to_id: String,
+    pub to_name: String,
+}
+
 /// Result of adding a comment.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JiraCommentResult {
@@ -53,11 +78,39 @@ struct JiraIssueResponse {
     fields: Option<JiraIssueFields>,
 }
 
+#[derive(Debug, Deserialize)]
+struct JiraCommentsResponse {
+    #[serde(default)]
+    comments: Vec<JiraComment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraComment {
+    #[serde(default)]
+    body: serde_json::Value,
+    #[serde(default)]
+    created: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraRawFieldsResponse {
+    fields: Option<HashMap<String, serde_json::Value>>,
+}
+
 #[derive(Debug, Deserialize)]
 struct JiraIssueFields {
     summary: Option<String>,
     status: Option<JiraStatus>,
     issuelinks: Option<Vec<JiraIssueLink>>,
+    #[serde(default)]
+    priority: Option<JiraPriority>,
+    #[serde(default)]
+    labels: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraPriority {
+    name: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -65,9 +118,17 @@ struct JiraStatus {
     name: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraUserResponse {
+    display_name: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
 struct JiraIssueLink {
+    #[serde(default)]
+    id: Option<String>,
     #[serde(rename = "type")]
     link_type: Option<JiraIssueLinkType>,
     #[serde(rename = "inwardIssue")]
@@ -93,6 +154,9 @@ struct JiraIssueLinkRef {
 #[derive(Debug, Deserialize)]
 struct JqlSearchResponse {
     issues: Option<Vec<JiraIssueResponse>>,
+    /// Set when more results follow; pass it back to get the next page
+    #[serde(default, rename = "nextPageToken")]
+    next_page_token: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -109,9 +173,28 @@ struct Transition {
 
 #[derive(Debug, Deserialize)]
 struct TransitionTarget {
+    id: Option<String>,
     name: Option<String>,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JiraStatusResponse {
+    id: Option<String>,
+    name: Option<String>,
+    status_category: Option<JiraStatusCategory>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JiraStatusCategory {
+    key: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectIssueTypeStatuses {
+    statuses: Option<Vec<JiraStatusResponse>>,
+}
+
 #[derive(Debug, Deserialize)]
 struct JiraCommentResponse {
     id: Option<String>,
@@ -136,9 +219,9 @@ struct JiraCreateIssueResponse {
 pub enum JiraError {
     #[error("JIRA_HOST environment variable is not set")]
     MissingHost,
-    #[error("JIRA_EMAIL environment variable is not set")]
+    #[error("JIRA_EMAIL is not set. Export it or run 'mobius auth login jira'")]
     MissingEmail,
-    #[error("JIRA_API_TOKEN environment variable is not set")]
+    #[error("JIRA_API_TOKEN is not set. Export it or run 'mobius auth login jira'")]
     MissingApiToken,
     #[error("Authentication failed (401). Check JIRA_EMAIL and JIRA_API_TOKEN")]
     AuthFailed,
@@ -150,6 +233,8 @@ pub enum JiraError {
     BadRequest(String),
     #[error("Jira API error (HTTP {status}): {message}")]
     HttpError { status: u16, message: String },
+    #[error("Jira rate limit exceeded and retries were exhausted{}", retry_hint(.retry_after))]
+    RateLimited { retry_after: Option<Duration> },
     #[error("No transition found to status \"{target}\". Available: {available}")]
     NoTransition { target: String, available: String },
     #[error(transparent)]
@@ -164,7 +249,7 @@ pub enum JiraError {
 
 /// Jira REST API v3 client.
 pub struct JiraClient {
-    client: reqwest::Client,
+    client: HttpClient,
     base_url: String,
     email: String,
     api_token: String,
@@ -183,11 +268,12 @@ impl std::fmt::Debug for JiraClient {
 impl JiraClient {
     /// Create a new client from environment variables.
     ///
-    /// Reads `JIRA_HOST`, `JIRA_EMAIL`, `JIRA_API_TOKEN`.
+    /// Reads `JIRA_HOST`, then `JIRA_EMAIL` and `JIRA_API_TOKEN` from the
+    /// environment or the OS keychain.
     pub fn new() -> Result<Self, JiraError> {
         let host = std::env::var("JIRA_HOST").map_err(|_| JiraError::MissingHost)?;
-        let email = std::env::var("JIRA_EMAIL").map_err(|_| JiraError::MissingEmail)?;
-        let api_token = std::env::var("JIRA_API_TOKEN").map_err(|_| JiraError::MissingApiToken)?;
+        let email = secrets::get_secret("JIRA_EMAIL").ok_or(JiraError::MissingEmail)?;
+        let api_token = secrets::backend_token(Backend::Jira).ok_or(JiraError::MissingApiToken)?;
 
         // Normalize host - ensure it has https:// prefix
         let normalized_host = if host.starts_with("https://") || host.starts_with("http://") {
@@ -201,7 +287,7 @@ impl JiraClient {
 
         let base_url = format!("{normalized_host}/rest/api/3");
 
-        let client = reqwest::Client::new();
+        let client = HttpClient::new();
 
         Ok(Self {
             client,
@@ -217,13 +303,12 @@ impl JiraClient {
 
     async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, JiraError> {
         let url = format!("{}/{}", self.base_url, path.trim_start_matches('/'));
-        let resp = self
+        let request = self
             .client
-            .get(&url)
+            .request(reqwest::Method::GET, &url)
             .basic_auth(&self.email, Some(&self.api_token))
-            .header("Accept", "application/json")
-            .send()
-            .await?;
+            .header("Accept", "application/json");
+        let resp = self.client.send(request).await?;
 
         self.handle_response(resp, path).await
     }
@@ -234,35 +319,86 @@ impl JiraClient {
         body: &B,
     ) -> Result<T, JiraError> {
         let url = format!("{}/{}", self.base_url, path.trim_start_matches('/'));
-        let resp = self
+        let request = self
             .client
-            .post(&url)
+            .request(reqwest::Method::POST, &url)
             .basic_auth(&self.email, Some(&self.api_token))
             .header("Accept", "application/json")
-            .json(body)
-            .send()
-            .await?;
+            .json(body);
+        let resp = self.client.send(request).await?;
+
+        self.handle_response(resp, path).await
+    }
+
+    /// POST a JQL search. It only reads, so unlike other POSTs a 5xx or
+    /// timeout is retried.
+    async fn search<T: serde::de::DeserializeOwned>(
+        &self,
+        body: &serde_json::Value,
+    ) -> Result<T, JiraError> {
+        let path = "search/jql";
+        let url = format!("{}/{}", self.base_url, path);
+        let request = self
+            .client
+            .request(reqwest::Method::POST, &url)
+            .basic_auth(&self.email, Some(&self.api_token))
+            .header("Accept", "application/json")
+            .json(body);
+        let resp = self.client.send_idempotent(request).await?;
 
         self.handle_response(resp, path).await
     }
 
     async fn post_no_response<B: Serialize>(&self, path: &str, body: &B) -> Result<(), JiraError> {
         let url = format!("{}/{}", self.base_url, path.trim_start_matches('/'));
-        let resp = self
+        let request = self
             .client
-            .post(&url)
+            .request(reqwest::Method::POST, &url)
             .basic_auth(&self.email, Some(&self.api_token))
             .header("Accept", "application/json")
-            .json(body)
-            .send()
-            .await?;
+            .json(body);
+        let resp = self.client.send(request).await?;
 
         let status = resp.status();
         if status.is_success() {
             Ok(())
         } else {
-            let body_text = resp.text().await.unwrap_or_default();
-            self.map_http_error(status, path, &body_text)
+            self.error_response(resp, path).await
+        }
+    }
+
+    async fn put_no_response<B: Serialize>(&self, path: &str, body: &B) -> Result<(), JiraError> {
+        let url = format!("{}/{}", self.base_url, path.trim_start_matches('/'));
+        let request = self
+            .client
+            .request(reqwest::Method::PUT, &url)
+            .basic_auth(&self.email, Some(&self.api_token))
+            .header("Accept", "application/json")
+            .json(body);
+        let resp = self.client.send(request).await?;
+
+        let status = resp.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            self.error_response(resp, path).await
+        }
+    }
+
+    async fn delete_no_response(&self, path: &str) -> Result<(), JiraError> {
+        let url = format!("{}/{}", self.base_url, path.trim_start_matches('/'));
+        let request = self
+            .client
+            .request(reqwest::Method::DELETE, &url)
+            .basic_auth(&self.email, Some(&self.api_token))
+            .header("Accept", "application/json");
+        let resp = self.client.send(request).await?;
+
+        let status = resp.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            self.error_response(resp, path).await
         }
     }
 
@@ -279,11 +415,21 @@ impl JiraClient {
                 .map_err(|e| anyhow::anyhow!("Failed to parse Jira response: {e}"))?;
             Ok(parsed)
         } else {
-            let body_text = resp.text().await.unwrap_or_default();
-            self.map_http_error(status, path, &body_text)
+            self.error_response(resp, path).await
         }
     }
 
+    async fn error_response<T>(&self, resp: reqwest::Response, path: &str) -> Result<T, JiraError> {
+        if is_rate_limited(&resp) {
+            return Err(JiraError::RateLimited {
+                retry_after: rate_limit_wait(resp.headers()),
+            });
+        }
+        let status = resp.status();
+        let body_text = resp.text().await.unwrap_or_default();
+        self.map_http_error(status, path, &body_text)
+    }
+
     fn map_http_error<T>(
         &self,
         status: StatusCode,
@@ -331,6 +477,12 @@ impl JiraClient {
         })
     }
 
+    /// Fetch the display name of the user the API token belongs to.
+    pub async fn fetch_jira_myself(&self) -> Result<String, JiraError> {
+        let resp: JiraUserResponse = self.get("myself").await?;
+        Ok(resp.display_name.unwrap_or_else(|| self.email.clone()))
+    }
+
     /// Fetch the current status name for a Jira issue.
     pub async fn fetch_jira_issue_status(&self, issue_key: &str) -> Result<String, JiraError> {
         let resp: JiraIssueResponse = self.get(&format!("issue/{issue_key}")).await?;
@@ -344,6 +496,75 @@ impl JiraClient {
         Ok(status_name)
     }
 
+    /// Fetch a Jira issue's current description as plain text.
+    pub async fn fetch_jira_issue_description(&self, issue_key: &str) -> Result<String, JiraError> {
+        let resp: JiraRawFieldsResponse = self
+            .get(&format!("issue/{issue_key}?fields=description"))
+            .await?;
+        Ok(resp
+            .fields
+            .and_then(|f| f.get("description").map(adf_text))
+            .unwrap_or_default())
+    }
+
+    /// Plain-text bodies of the comments added to an issue at or after
+    /// `since` (RFC 3339), among its 100 most recent.
+    pub async fn fetch_jira_comments_since(
+        &self,
+        issue_key: &str,
+        since: &str,
+    ) -> Result<Vec<String>, JiraError> {
+        let resp: JiraCommentsResponse = self
+            .get(&format!(
+                "issue/{issue_key}/comment?orderBy=-created&maxResults=100"
+            ))
+            .await?;
+        let since = chrono::DateTime::parse_from_rfc3339(since).ok();
+        Ok(resp
+            .comments
+            .into_iter()
+            .filter(|c| {
+                let created = c.created.as_deref().and_then(parse_jira_timestamp);
+                match (since, created) {
+                    (Some(since), Some(created)) => created >= since,
+                    _ => true,
+                }
+            })
+            .map(|c| adf_text(&c.body))
+            .collect())
+    }
+
+    /// Fetch raw field values for a Jira issue (e.g. `customfield_10016`).
+    ///
+    /// Fields that are unset on the issue are omitted from the result.
+    pub async fn fetch_jira_issue_fields(
+        &self,
+        issue_key: &str,
+        field_ids: &[String],
+    ) -> Result<HashMap<String, serde_json::Value>, JiraError> {
+        let resp: JiraRawFieldsResponse = self
+            .get(&format!("issue/{issue_key}?fields={}", field_ids.join(",")))
+            .await?;
+
+        Ok(resp
+            .fields
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, value)| !value.is_null())
+            .collect())
+    }
+
+    /// Set field values on a Jira issue.
+    pub async fn update_jira_issue_fields(
+        &self,
+        issue_key: &str,
+        fields: &HashMap<String, serde_json::Value>,
+    ) -> Result<(), JiraError> {
+        let body = serde_json::json!({ "fields": fields });
+        self.put_no_response(&format!("issue/{issue_key}"), &body)
+            .await
+    }
+
     /// Fetch sub-tasks (children) of a parent issue.
     ///
     /// Uses the enhanced JQL search API (`/search/jql`), NOT the deprecated `/search`.
@@ -351,17 +572,34 @@ impl JiraClient {
         &self,
         parent_key: &str,
     ) -> Result<Vec<LinearIssue>, JiraError> {
-        let body = serde_json::json!({
-            "jql": format!("parent = {parent_key}"),
-            "fields": ["summary", "status", "issuelinks", "issuetype"]
-        });
-
-        let resp: JqlSearchResponse = self.post("search/jql", &body).await?;
+        self.fetch_jira_sub_tasks_since(parent_key, None).await
+    }
 
+    /// Sub-tasks of `parent_key`, or with `minutes` only the ones updated
+    /// within the last `minutes`. Follows `nextPageToken` across pages.
+    pub async fn fetch_jira_sub_tasks_since(
+        &self,
+        parent_key: &str,
+        minutes: Option<i64>,
+    ) -> Result<Vec<LinearIssue>, JiraError> {
+        let jql = match minutes {
+            Some(minutes) => changed_sub_tasks_jql(parent_key, minutes),
+            None => format!("parent = {parent_key}"),
+        };
         let mut sub_tasks = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut body = serde_json::json!({
+                "jql": jql,
+                "fields": ["summary", "status", "issuelinks", "issuetype", "priority", "labels"],
+                "maxResults": 100
+            });
+            if let Some(token) = &page_token {
+                body["nextPageToken"] = serde_json::json!(token);
+            }
 
-        if let Some(issues) = resp.issues {
-            for issue in issues {
+            let resp: JqlSearchResponse = self.search(&body).await?;
+            for issue in resp.issues.unwrap_or_default() {
                 let blocked_by = extract_blocked_by_relations(
                     issue.fields.as_ref().and_then(|f| f.issuelinks.as_ref()),
                 );
@@ -389,11 +627,26 @@ impl JiraClient {
                         blocks: Vec::new(),
                     }),
                     scoring: None,
+                    priority: issue
+                        .fields
+                        .as_ref()
+                        .and_then(|f| f.priority.as_ref())
+                        .and_then(|p| p.name.as_deref())
+                        .and_then(jira_priority_rank),
+                    labels: issue
+                        .fields
+                        .as_ref()
+                        .map(|f| f.labels.clone())
+                        .unwrap_or_default(),
+                    timeout_minutes: None,
+                    task_type: TaskType::Agent,
                 });
             }
+            match resp.next_page_token {
+                Some(token) => page_token = Some(token),
+                None => return Ok(sub_tasks),
+            }
         }
-
-        Ok(sub_tasks)
     }
 
     /// Update a Jira issue's status using the transitions API.
@@ -405,12 +658,8 @@ impl JiraClient {
         issue_key: &str,
         target_status: &str,
     ) -> Result<(), JiraError> {
-        let resp: TransitionsResponse = self.get(&format!("issue/{issue_key}/transitions")).await?;
-
-        let transitions = resp.transitions.unwrap_or_default();
         let target_lower = target_status.to_lowercase();
-
-        let matching = transitions.iter().find(|t| {
+        self.transition_issue(issue_key, target_status, |t| {
             t.name
                 .as_ref()
                 .is_some_and(|n| n.to_lowercase() == target_lower)
@@ -418,7 +667,38 @@ impl JiraClient {
                     .as_ref()
                     .and_then(|to| to.name.as_ref())
                     .is_some_and(|n| n.to_lowercase() == target_lower)
-        });
+        })
+        .await
+    }
+
+    /// Update a Jira issue's status by target status ID.
+    ///
+    /// Unlike names, IDs do not depend on the instance's display language.
+    /// A transition ID is accepted as well.
+    pub async fn update_jira_issue_status_by_id(
+        &self,
+        issue_key: &str,
+        status_id: &str,
+    ) -> Result<(), JiraError> {
+        self.transition_issue(issue_key, &format!("id {status_id}"), |t| {
+            t.to.as_ref()
+                .and_then(|to| to.id.as_deref())
+                .is_some_and(|id| id == status_id)
+                || t.id.as_deref() == Some(status_id)
+        })
+        .await
+    }
+
+    async fn transition_issue(
+        &self,
+        issue_key: &str,
+        target_label: &str,
+        matches: impl Fn(&Transition) -> bool,
+    ) -> Result<(), JiraError> {
+        let resp: TransitionsResponse = self.get(&format!("issue/{issue_key}/transitions")).await?;
+
+        let transitions = resp.transitions.unwrap_or_default();
+        let matching = transitions.iter().find(|t| matches(t));
 
         let transition = matching.ok_or_else(|| {
             let available = transitions
@@ -434,7 +714,7 @@ impl JiraClient {
                 .collect::<Vec<_>>()
                 .join(", ");
             JiraError::NoTransition {
-                target: target_status.to_string(),
+                target: target_label.to_string(),
                 available,
             }
         })?;
@@ -452,6 +732,79 @@ impl JiraClient {
             .await
     }
 
+    /// List workflow statuses, scoped to `project_key` when given.
+    pub async fn list_jira_statuses(
+        &self,
+        project_key: Option<&str>,
+    ) -> Result<Vec<JiraStatusInfo>, JiraError> {
+        let raw: Vec<JiraStatusResponse> = match project_key {
+            Some(key) => {
+                let issue_types: Vec<ProjectIssueTypeStatuses> =
+                    self.get(&format!("project/{key}/statuses")).await?;
+                issue_types
+                    .into_iter()
+                    .flat_map(|t| t.statuses.unwrap_or_default())
+                    .collect()
+            }
+            None => self.get("status").await?,
+        };
+
+        let mut statuses: Vec<JiraStatusInfo> = Vec::new();
+        for status in raw {
+            let (Some(id), Some(name)) = (status.id, status.name) else {
+                continue;
+            };
+            // Issue types share statuses; keep the first occurrence
+            if statuses.iter().any(|s| s.id == id) {
+                continue;
+            }
+            statuses.push(JiraStatusInfo {
+                id,
+                name,
+                category: status
+                    .status_category
+                    .and_then(|c| c.key)
+                    .unwrap_or_default(),
+            });
+        }
+        Ok(statuses)
+    }
+
+    /// List the transitions currently available on an issue.
+    pub async fn list_jira_transitions(
+        &self,
+        issue_key: &str,
+    ) -> Result<Vec<JiraTransitionInfo>, JiraError> {
+        let resp: TransitionsResponse = self.get(&format!("issue/{issue_key}/transitions")).await?;
+        Ok(resp
+            .transitions
+            .unwrap_or_default()
+            .into_iter()
+            .map(|t| {
+                let (to_id, to_name) =
+                    t.to.map(|to| (to.id.unwrap_or_default(), to.name.unwrap_or_default()))
+                        .unwrap_or_default();
+                JiraTransitionInfo {
+                    id: t.id.unwrap_or_default(),
+                    name: t.name.unwrap_or_default(),
+                    to_id,
+                    to_name,
+                }
+            })
+            .collect())
+    }
+
+    /// Replace an issue's description.
+    pub async fn update_jira_issue_description(
+        &self,
+        issue_key: &str,
+        description: &str,
+    ) -> Result<(), JiraError> {
+        let body = serde_json::json!({ "fields": { "description": adf_document(description) } });
+        self.put_no_response(&format!("issue/{issue_key}"), &body)
+            .await
+    }
+
     /// Add a comment to a Jira issue.
     pub async fn add_jira_comment(
         &self,
@@ -535,6 +888,12 @@ impl JiraClient {
         })
     }
 
+    /// Delete an issue.
+    pub async fn delete_jira_issue(&self, issue_key: &str) -> Result<(), JiraError> {
+        self.delete_no_response(&format!("issue/{}", issue_key))
+            .await
+    }
+
     /// Create a "Blocks" link between two issues.
     ///
     /// The `blocker_key` issue blocks the `blocked_key` issue.
@@ -551,12 +910,44 @@ impl JiraClient {
 
         self.post_no_response("issueLink", &body).await
     }
+
+    /// Delete the "Blocks" links through which `blocker_key` blocks
+    /// `blocked_key`. Succeeds without a request when no such link exists.
+    pub async fn delete_jira_issue_link(
+        &self,
+        blocker_key: &str,
+        blocked_key: &str,
+    ) -> Result<(), JiraError> {
+        let resp: JiraIssueResponse = self
+            .get(&format!("issue/{blocked_key}?fields=issuelinks"))
+            .await?;
+        let links = resp.fields.and_then(|f| f.issuelinks);
+        for link_id in blocking_link_ids(links.as_ref(), blocker_key) {
+            self.delete_no_response(&format!("issueLink/{}", link_id))
+                .await?;
+        }
+        Ok(())
+    }
 }
 
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
 
+/// Whether a link uses a blocking link type ("Blocks", or an inward
+/// description of "is blocked by").
+fn is_blocking_link(link: &JiraIssueLink) -> bool {
+    link.link_type.as_ref().is_some_and(|lt| {
+        lt.inward
+            .as_ref()
+            .is_some_and(|s| s.to_lowercase().contains("blocked by"))
+            || lt
+                .name
+                .as_ref()
+                .is_some_and(|n| n.to_lowercase() == "blocks")
+    })
+}
+
 /// Extract "blocked by" relations from Jira issue links.
 ///
 /// In Jira, blocking relationships use a "Blocks" link type where the inward
@@ -570,17 +961,7 @@ fn extract_blocked_by_relations(issuelinks: Option<&Vec<JiraIssueLink>>) -> Vec<
 
     for link in links {
         // Check for "is blocked by" relationship (inward link)
-        let is_blocking_relation = link.link_type.as_ref().is_some_and(|lt| {
-            lt.inward
-                .as_ref()
-                .is_some_and(|s| s.to_lowercase().contains("blocked by"))
-                || lt
-                    .name
-                    .as_ref()
-                    .is_some_and(|n| n.to_lowercase() == "blocks")
-        });
-
-        if is_blocking_relation {
+        if is_blocking_link(link) {
             if let Some(ref inward) = link.inward_issue {
                 if let (Some(id), Some(key)) = (inward.id.as_ref(), inward.key.as_ref()) {
                     blocked_by.push(Relation {
@@ -595,14 +976,108 @@ fn extract_blocked_by_relations(issuelinks: Option<&Vec<JiraIssueLink>>) -> Vec<
     blocked_by
 }
 
+/// IDs of the blocking links through which `blocker` (a key or ID) blocks
+/// the issue the links were read from.
+fn blocking_link_ids(issuelinks: Option<&Vec<JiraIssueLink>>, blocker: &str) -> Vec<String> {
+    issuelinks
+        .into_iter()
+        .flatten()
+        .filter(|link| is_blocking_link(link))
+        .filter(|link| {
+            link.inward_issue.as_ref().is_some_and(|inward| {
+                inward.key.as_deref() == Some(blocker) || inward.id.as_deref() == Some(blocker)
+            })
+        })
+        .filter_map(|link| link.id.clone())
+        .collect()
+}
+
+/// Rank a Jira priority name, 1 = highest. Covers the default scheme and the
+/// legacy Blocker..Trivial names; custom names are left unranked.
+fn jira_priority_rank(name: &str) -> Option<u8> {
+    match name.trim().to_lowercase().as_str() {
+        "highest" | "blocker" => Some(1),
+        "high" | "critical" => Some(2),
+        "medium" | "major" => Some(3),
+        "low" | "minor" => Some(4),
+        "lowest" | "trivial" => Some(5),
+        _ => None,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
 
+/// JQL for sub-tasks updated within the last `minutes`.
+///
+/// A relative offset sidesteps JQL dates being read in the Jira user's
+/// timezone; the window is rounded up so the boundary is never missed.
+fn changed_sub_tasks_jql(parent_key: &str, minutes: i64) -> String {
+    format!(
+        "parent = {parent_key} AND updated >= \"-{}m\"",
+        minutes.max(0) + 1
+    )
+}
+
+/// Wrap plain text as an Atlassian Document Format document, one paragraph
+/// per blank-line-separated block (REST API v3 rejects plain strings).
+fn adf_document(text: &str) -> serde_json::Value {
+    let paragraphs: Vec<serde_json::Value> = text
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            serde_json::json!({
+                "type": "paragraph",
+                "content": [{ "type": "text", "text": p }]
+            })
+        })
+        .collect();
+    serde_json::json!({ "type": "doc", "version": 1, "content": paragraphs })
+}
+
+/// Plain text of an Atlassian Document Format node: block nodes are
+/// separated by blank lines, as [`adf_document`] writes them.
+fn adf_text(node: &serde_json::Value) -> String {
+    if let Some(text) = node.as_str() {
+        return text.to_string();
+    }
+    if let Some(text) = node.get("text").and_then(|t| t.as_str()) {
+        return text.to_string();
+    }
+    if node.get("type").and_then(|t| t.as_str()) == Some("hardBreak") {
+        return "\n".to_string();
+    }
+    let Some(children) = node.get("content").and_then(|c| c.as_array()) else {
+        return String::new();
+    };
+    let inline = children.iter().all(|c| {
+        c.get("text").is_some() || c.get("type").and_then(|t| t.as_str()) == Some("hardBreak")
+    });
+    let parts: Vec<String> = children.iter().map(adf_text).collect();
+    parts.join(if inline { "" } else { "\n\n" })
+}
+
+/// Jira timestamps look like `2024-01-31T10:00:00.000+0000`.
+fn parse_jira_timestamp(value: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .or_else(|_| chrono::DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+        .ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_adf_text_round_trips_adf_document() {
+        let text = "First paragraph\n\nSecond one";
+        assert_eq!(adf_text(&adf_document(text)), text);
+        assert_eq!(adf_text(&serde_json::json!("plain")), "plain");
+        assert!(parse_jira_timestamp("2024-01-31T10:00:00.000+0000").is_some());
+    }
+
     // -- URL construction tests --
 
     #[test]
@@ -690,11 +1165,20 @@ mod tests {
         assert_eq!(client.api_token, "my-secret-token");
     }
 
+    #[test]
+    fn test_jira_priority_rank() {
+        assert_eq!(jira_priority_rank("Highest"), Some(1));
+        assert_eq!(jira_priority_rank("critical"), Some(2));
+        assert_eq!(jira_priority_rank(" Lowest "), Some(5));
+        assert_eq!(jira_priority_rank("P0 - Drop everything"), None);
+    }
+
     // -- Issue link parsing tests --
 
     #[test]
     fn test_extract_blocked_by_with_blocks_type() {
         let links = vec![JiraIssueLink {
+            id: None,
             link_type: Some(JiraIssueLinkType {
                 name: Some("Blocks".to_string()),
                 inward: Some("is blocked by".to_string()),
@@ -716,6 +1200,7 @@ mod tests {
     #[test]
     fn test_extract_blocked_by_with_inward_text() {
         let links = vec![JiraIssueLink {
+            id: None,
             link_type: Some(JiraIssueLinkType {
                 name: Some("Dependency".to_string()),
                 inward: Some("is blocked by".to_string()),
@@ -736,6 +1221,7 @@ mod tests {
     #[test]
     fn test_extract_blocked_by_ignores_non_blocking() {
         let links = vec![JiraIssueLink {
+            id: None,
             link_type: Some(JiraIssueLinkType {
                 name: Some("Relates".to_string()),
                 inward: Some("relates to".to_string()),
@@ -768,6 +1254,7 @@ mod tests {
     #[test]
     fn test_extract_blocked_by_skips_incomplete_link() {
         let links = vec![JiraIssueLink {
+            id: None,
             link_type: Some(JiraIssueLinkType {
                 name: Some("Blocks".to_string()),
                 inward: Some("is blocked by".to_string()),
@@ -788,6 +1275,7 @@ mod tests {
     fn test_extract_blocked_by_multiple_links() {
         let links = vec![
             JiraIssueLink {
+                id: None,
                 link_type: Some(JiraIssueLinkType {
                     name: Some("Blocks".to_string()),
                     inward: Some("is blocked by".to_string()),
@@ -800,6 +1288,7 @@ mod tests {
                 outward_issue: None,
             },
             JiraIssueLink {
+                id: None,
                 link_type: Some(JiraIssueLinkType {
                     name: Some("Relates".to_string()),
                     inward: Some("relates to".to_string()),
@@ -812,6 +1301,7 @@ mod tests {
                 outward_issue: None,
             },
             JiraIssueLink {
+                id: None,
                 link_type: Some(JiraIssueLinkType {
                     name: Some("Blocks".to_string()),
                     inward: Some("is blocked by".to_string()),
@@ -831,6 +1321,33 @@ mod tests {
         assert_eq!(result[1].identifier, "PROJ-30");
     }
 
+    #[test]
+    fn test_blocking_link_ids_matches_blocker_key_or_id() {
+        let link = |id: &str, name: &str, key: &str| JiraIssueLink {
+            id: Some(id.to_string()),
+            link_type: Some(JiraIssueLinkType {
+                name: Some(name.to_string()),
+                inward: None,
+                outward: None,
+            }),
+            inward_issue: Some(JiraIssueLinkRef {
+                key: Some(key.to_string()),
+                id: Some(format!("{}0", id)),
+            }),
+            outward_issue: None,
+        };
+        let links = vec![
+            link("1", "Blocks", "PROJ-10"),
+            link("2", "Relates", "PROJ-10"),
+            link("3", "Blocks", "PROJ-20"),
+        ];
+
+        assert_eq!(blocking_link_ids(Some(&links), "PROJ-10"), vec!["1"]);
+        assert_eq!(blocking_link_ids(Some(&links), "30"), vec!["3"]);
+        assert!(blocking_link_ids(Some(&links), "PROJ-99").is_empty());
+        assert!(blocking_link_ids(None, "PROJ-10").is_empty());
+    }
+
     // -- Error type tests --
 
     #[test]
@@ -863,6 +1380,33 @@ mod tests {
         assert!(err.to_string().contains("Available"));
     }
 
+    #[test]
+    fn test_transition_and_status_deserialize_ids() {
+        let transitions: TransitionsResponse = serde_json::from_value(serde_json::json!({
+            "transitions": [
+                { "id": "21", "name": "Starten", "to": { "id": "3", "name": "In Arbeit" } }
+            ]
+        }))
+        .unwrap();
+        let transition = &transitions.transitions.unwrap()[0];
+        assert_eq!(transition.id.as_deref(), Some("21"));
+        assert_eq!(
+            transition.to.as_ref().and_then(|t| t.id.as_deref()),
+            Some("3")
+        );
+
+        let status: JiraStatusResponse = serde_json::from_value(serde_json::json!({
+            "id": "10001",
+            "name": "Fertig",
+            "statusCategory": { "key": "done" }
+        }))
+        .unwrap();
+        assert_eq!(
+            status.status_category.and_then(|c| c.key).as_deref(),
+            Some("done")
+        );
+    }
+
     // -- Verify POST to /search/jql (not deprecated /search) --
 
     #[test]
@@ -880,4 +1424,16 @@ mod tests {
             "https://test.atlassian.net/rest/api/3/search/jql"
         );
     }
+
+    #[test]
+    fn test_changed_sub_tasks_jql_rounds_window_up() {
+        assert_eq!(
+            changed_sub_tasks_jql("PROJ-1", 90),
+            "parent = PROJ-1 AND updated >= \"-91m\""
+        );
+        assert_eq!(
+            changed_sub_tasks_jql("PROJ-1", -5),
+            "parent = PROJ-1 AND updated >= \"-1m\""
+        );
+    }
 }
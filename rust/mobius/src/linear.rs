@@ -8,8 +8,13 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
 use tracing::warn;
 
+use crate::http_client::{is_rate_limited, rate_limit_wait, retry_hint, HttpClient};
+use crate::secrets;
+use crate::types::enums::{Backend, TaskType};
 use crate::types::task_graph::{LinearIssue, ParentIssue, Relation, Relations};
 
 // ---------------------------------------------------------------------------
@@ -25,7 +30,7 @@ const LINEAR_API_URL: &str = "https://api.linear.app/graphql";
 /// Custom error type for Linear API operations.
 #[derive(Debug, thiserror::Error)]
 pub enum LinearError {
-    #[error("LINEAR_API_KEY (or LINEAR_API_TOKEN) environment variable is not set")]
+    #[error("LINEAR_API_KEY (or LINEAR_API_TOKEN) is not set. Export it or run 'mobius auth login linear'")]
     MissingApiKey,
     #[error("Authentication failed (401). Check LINEAR_API_KEY")]
     AuthFailed,
@@ -33,6 +38,8 @@ pub enum LinearError {
     PermissionDenied,
     #[error("HTTP error ({status}): {message}")]
     HttpError { status: u16, message: String },
+    #[error("Linear rate limit exceeded and retries were exhausted{}", retry_hint(.retry_after))]
+    RateLimited { retry_after: Option<Duration> },
     #[error("GraphQL error: {0}")]
     GraphQL(String),
     #[error("No workflow state found matching \"{0}\"")]
@@ -77,6 +84,11 @@ struct IssueNode {
     state: Option<StateNode>,
     team: Option<TeamRef>,
     inverse_relations: Option<InverseRelationsConnection>,
+    /// 0 = no priority, 1 = urgent .. 4 = low
+    #[serde(default)]
+    priority: Option<f64>,
+    #[serde(default)]
+    labels: Option<LabelsConnection>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -121,6 +133,55 @@ struct IssuesConnection {
     nodes: Vec<IssueNode>,
 }
 
+#[derive(Debug, Deserialize)]
+struct SubTasksData {
+    issue: Option<SubTasksNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubTasksNode {
+    children: PagedIssuesConnection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PagedIssuesConnection {
+    nodes: Vec<IssueNode>,
+    page_info: PageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+// -- Description and comments --
+
+#[derive(Debug, Deserialize)]
+struct IssueTextData {
+    issue: Option<IssueTextNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueTextNode {
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    comments: Option<CommentsConnection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentsConnection {
+    nodes: Vec<CommentBodyNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommentBodyNode {
+    body: String,
+}
+
 // -- Team workflow states --
 
 #[derive(Debug, Deserialize)]
@@ -139,6 +200,54 @@ struct StatesConnection {
     nodes: Vec<StateNode>,
 }
 
+#[derive(Debug, Deserialize)]
+struct IssueFieldsData {
+    issue: Option<IssueFieldsNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueFieldsNode {
+    estimate: Option<f64>,
+    priority: Option<f64>,
+    labels: Option<LabelsConnection>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LabelsConnection {
+    nodes: Vec<LabelNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LabelNode {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkflowStatesData {
+    workflow_states: WorkflowStatesConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkflowStatesConnection {
+    nodes: Vec<WorkflowStateNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WorkflowStateNode {
+    id: String,
+    name: String,
+    #[serde(rename = "type")]
+    state_type: String,
+    team: Option<TeamKeyNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamKeyNode {
+    key: String,
+    name: String,
+}
+
 // -- Mutation responses --
 
 #[derive(Debug, Deserialize)]
@@ -182,9 +291,31 @@ struct IssueCreatePayload {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct CreatedIssueNode {
     id: String,
     identifier: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    branch_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamsData {
+    teams: TeamsConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamsConnection {
+    nodes: Vec<TeamIdNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamIdNode {
+    id: String,
+    key: String,
+    name: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -200,10 +331,73 @@ struct IssueRelationCreatePayload {
     success: bool,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueRelationDeleteData {
+    issue_relation_delete: Option<IssueDeletePayload>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueRelationsData {
+    issue: Option<IssueRelationsNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueRelationsNode {
+    inverse_relations: IssueRelationsConnection,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueRelationsConnection {
+    nodes: Vec<IssueRelationNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueRelationNode {
+    id: String,
+    #[serde(rename = "type")]
+    relation_type: String,
+    issue: RelatedIssueRef,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueDeleteData {
+    issue_delete: Option<IssueDeletePayload>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssueDeletePayload {
+    success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewerData {
+    viewer: ViewerNode,
+}
+
+#[derive(Debug, Deserialize)]
+struct ViewerNode {
+    name: String,
+}
+
 // ---------------------------------------------------------------------------
 // Public input / output types
 // ---------------------------------------------------------------------------
 
+/// A team workflow state.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LinearWorkflowState {
+    pub id: String,
+    pub name: String,
+    /// `triage`, `backlog`, `unstarted`, `started`, `completed`, or `canceled`
+    pub state_type: String,
+    /// Team key, e.g. `ENG`
+    pub team: String,
+}
+
 /// Options for creating a Linear issue.
 #[derive(Debug, Clone, Serialize)]
 pub struct CreateLinearIssueInput {
@@ -221,6 +415,8 @@ pub struct CreateLinearIssueInput {
 pub struct CreatedIssue {
     pub id: String,
     pub identifier: String,
+    pub url: String,
+    pub branch_name: String,
 }
 
 // ---------------------------------------------------------------------------
@@ -229,7 +425,7 @@ pub struct CreatedIssue {
 
 /// Linear GraphQL API client.
 pub struct LinearClient {
-    client: reqwest::Client,
+    client: HttpClient,
     api_key: String,
 }
 
@@ -244,14 +440,13 @@ impl std::fmt::Debug for LinearClient {
 impl LinearClient {
     /// Create a new client from environment variables.
     ///
-    /// Reads `LINEAR_API_KEY` with fallback to `LINEAR_API_TOKEN`.
+    /// Reads `LINEAR_API_KEY` with fallback to `LINEAR_API_TOKEN`, then the
+    /// OS keychain.
     pub fn new() -> Result<Self, LinearError> {
-        let api_key = std::env::var("LINEAR_API_KEY")
-            .or_else(|_| std::env::var("LINEAR_API_TOKEN"))
-            .map_err(|_| LinearError::MissingApiKey)?;
+        let api_key = secrets::backend_token(Backend::Linear).ok_or(LinearError::MissingApiKey)?;
 
         Ok(Self {
-            client: reqwest::Client::new(),
+            client: HttpClient::new(),
             api_key,
         })
     }
@@ -270,16 +465,25 @@ impl LinearClient {
             "variables": variables,
         });
 
-        let resp = self
+        let request = self
             .client
-            .post(LINEAR_API_URL)
+            .request(reqwest::Method::POST, LINEAR_API_URL)
             .header("Authorization", &self.api_key)
             .header("Content-Type", "application/json")
-            .json(&body)
-            .send()
-            .await?;
+            .json(&body);
+        // Queries only read, so a 5xx or timeout can safely be retried
+        let resp = if is_mutation(query) {
+            self.client.send(request).await?
+        } else {
+            self.client.send_idempotent(request).await?
+        };
 
         let status = resp.status();
+        if is_rate_limited(&resp) {
+            return Err(LinearError::RateLimited {
+                retry_after: rate_limit_wait(resp.headers()),
+            });
+        }
         if status == reqwest::StatusCode::UNAUTHORIZED {
             return Err(LinearError::AuthFailed);
         }
@@ -367,6 +571,8 @@ impl LinearClient {
                         identifier
                         title
                         branchName
+                        priority
+                        labels { nodes { name } }
                         state { name }
                         inverseRelations {
                             nodes {
@@ -383,53 +589,83 @@ impl LinearClient {
             .graphql(query, serde_json::json!({ "parentId": parent_id }))
             .await?;
 
-        let issues = data
+        Ok(data
             .issues
             .nodes
             .into_iter()
-            .map(|node| {
-                let status = node
-                    .state
-                    .as_ref()
-                    .map(|s| s.name.clone())
-                    .unwrap_or_else(|| "Backlog".to_string());
-
-                let branch_name = node
-                    .branch_name
-                    .filter(|b| !b.is_empty())
-                    .unwrap_or_else(|| format!("feat/{}", node.identifier.to_lowercase()));
-
-                let blocked_by: Vec<Relation> = node
-                    .inverse_relations
-                    .as_ref()
-                    .map(|ir| {
-                        ir.nodes
-                            .iter()
-                            .filter(|r| r.relation_type == "blocks")
-                            .map(|r| Relation {
-                                id: r.issue.id.clone(),
-                                identifier: r.issue.identifier.clone(),
-                            })
-                            .collect()
-                    })
-                    .unwrap_or_default();
-
-                LinearIssue {
-                    id: node.id,
-                    identifier: node.identifier,
-                    title: node.title,
-                    status,
-                    git_branch_name: branch_name,
-                    relations: Some(Relations {
-                        blocked_by,
-                        blocks: Vec::new(),
-                    }),
-                    scoring: None,
+            .map(sub_task_from_node)
+            .collect())
+    }
+
+    /// Sub-tasks of `parent_identifier`, or with `since` (RFC 3339) only the
+    /// ones updated after it. Follows pagination, so large parents are
+    /// fetched in full.
+    pub async fn fetch_linear_sub_tasks_since(
+        &self,
+        parent_identifier: &str,
+        since: Option<&str>,
+    ) -> Result<Vec<LinearIssue>, LinearError> {
+        let query = r#"
+            query GetSubTasksSince($id: String!, $filter: IssueFilter, $after: String) {
+                issue(id: $id) {
+                    children(filter: $filter, first: 100, after: $after) {
+                        nodes {
+                            id
+                            identifier
+                            title
+                            branchName
+                            priority
+                            labels { nodes { name } }
+                            state { name }
+                            inverseRelations {
+                                nodes {
+                                    type
+                                    issue { id identifier }
+                                }
+                            }
+                        }
+                        pageInfo { hasNextPage endCursor }
+                    }
                 }
-            })
-            .collect();
+            }
+        "#;
 
-        Ok(issues)
+        let filter = match since {
+            Some(since) => serde_json::json!({ "updatedAt": { "gt": since } }),
+            None => serde_json::json!({}),
+        };
+        let mut sub_tasks = Vec::new();
+        let mut after: Option<String> = None;
+        loop {
+            let data: SubTasksData = self
+                .graphql(
+                    query,
+                    serde_json::json!({
+                        "id": parent_identifier,
+                        "filter": filter,
+                        "after": after,
+                    }),
+                )
+                .await?;
+
+            let issue = data.issue.ok_or_else(|| {
+                LinearError::GraphQL(format!("Issue {} not found", parent_identifier))
+            })?;
+            let children = issue.children;
+            sub_tasks.extend(children.nodes.into_iter().map(sub_task_from_node));
+            match children.page_info.end_cursor {
+                Some(cursor) if children.page_info.has_next_page => after = Some(cursor),
+                _ => return Ok(sub_tasks),
+            }
+        }
+    }
+
+    /// Fetch the name of the user the API key belongs to.
+    pub async fn fetch_linear_viewer(&self) -> Result<String, LinearError> {
+        let data: ViewerData = self
+            .graphql("query { viewer { name } }", serde_json::json!({}))
+            .await?;
+        Ok(data.viewer.name)
     }
 
     /// Fetch the current status name for a Linear issue.
@@ -522,6 +758,15 @@ impl LinearClient {
         };
 
         // Step 3: update the issue
+        self.update_linear_issue_state_id(issue_id, state_id).await
+    }
+
+    /// Move a Linear issue to the workflow state with the given ID.
+    pub async fn update_linear_issue_state_id(
+        &self,
+        issue_id: &str,
+        state_id: &str,
+    ) -> Result<(), LinearError> {
         let mutation = r#"
             mutation UpdateIssueStatus($id: String!, $stateId: String!) {
                 issueUpdate(id: $id, input: { stateId: $stateId }) {
@@ -545,6 +790,171 @@ impl LinearClient {
         }
     }
 
+    /// List workflow states, limited to the team whose key or name is `team`.
+    pub async fn list_linear_workflow_states(
+        &self,
+        team: Option<&str>,
+    ) -> Result<Vec<LinearWorkflowState>, LinearError> {
+        let query = r#"
+            query ListWorkflowStates {
+                workflowStates(first: 250) {
+                    nodes { id name type team { key name } }
+                }
+            }
+        "#;
+
+        let data: WorkflowStatesData = self.graphql(query, serde_json::json!({})).await?;
+
+        Ok(data
+            .workflow_states
+            .nodes
+            .into_iter()
+            .filter(|s| {
+                team.map_or(true, |t| {
+                    s.team.as_ref().is_some_and(|n| {
+                        n.key.eq_ignore_ascii_case(t) || n.name.eq_ignore_ascii_case(t)
+                    })
+                })
+            })
+            .map(|s| LinearWorkflowState {
+                id: s.id,
+                name: s.name,
+                state_type: s.state_type,
+                team: s.team.map(|t| t.key).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Fetch the scoring-relevant fields of an issue.
+    ///
+    /// Linear has no free-form custom fields, so this returns `estimate` and
+    /// `priority` (when set) plus `labels` as an array of label names.
+    pub async fn fetch_linear_issue_fields(
+        &self,
+        identifier: &str,
+    ) -> Result<HashMap<String, serde_json::Value>, LinearError> {
+        let query = r#"
+            query GetIssueFields($id: String!) {
+                issue(id: $id) {
+                    estimate
+                    priority
+                    labels { nodes { name } }
+                }
+            }
+        "#;
+
+        let data: IssueFieldsData = self
+            .graphql(query, serde_json::json!({ "id": identifier }))
+            .await?;
+
+        let issue = data
+            .issue
+            .ok_or_else(|| LinearError::GraphQL(format!("Issue {} not found", identifier)))?;
+
+        let mut fields = HashMap::new();
+        if let Some(estimate) = issue.estimate {
+            fields.insert("estimate".to_string(), serde_json::json!(estimate));
+        }
+        if let Some(priority) = issue.priority {
+            fields.insert("priority".to_string(), serde_json::json!(priority));
+        }
+        let labels: Vec<String> = issue
+            .labels
+            .map(|l| l.nodes.into_iter().map(|n| n.name).collect())
+            .unwrap_or_default();
+        fields.insert("labels".to_string(), serde_json::json!(labels));
+
+        Ok(fields)
+    }
+
+    /// Update issue fields (`estimate`, `priority`) via `issueUpdate`.
+    pub async fn update_linear_issue_fields(
+        &self,
+        identifier: &str,
+        fields: &HashMap<String, serde_json::Value>,
+    ) -> Result<(), LinearError> {
+        let mutation = r#"
+            mutation UpdateIssueFields($id: String!, $input: IssueUpdateInput!) {
+                issueUpdate(id: $id, input: $input) {
+                    success
+                }
+            }
+        "#;
+
+        let update_data: IssueUpdateData = self
+            .graphql(
+                mutation,
+                serde_json::json!({ "id": identifier, "input": fields }),
+            )
+            .await?;
+
+        match update_data.issue_update {
+            Some(payload) if payload.success => Ok(()),
+            _ => Err(LinearError::GraphQL(
+                "issueUpdate mutation returned success=false".to_string(),
+            )),
+        }
+    }
+
+    /// Fetch an issue's current description.
+    pub async fn fetch_linear_issue_description(
+        &self,
+        identifier: &str,
+    ) -> Result<String, LinearError> {
+        let query = r#"
+            query GetIssueDescription($id: String!) {
+                issue(id: $id) { description }
+            }
+        "#;
+        let data: IssueTextData = self
+            .graphql(query, serde_json::json!({ "id": identifier }))
+            .await?;
+        let issue = data
+            .issue
+            .ok_or_else(|| LinearError::GraphQL(format!("Issue {} not found", identifier)))?;
+        Ok(issue.description.unwrap_or_default())
+    }
+
+    /// Bodies of the comments added to an issue at or after `since` (RFC 3339).
+    pub async fn fetch_linear_comments_since(
+        &self,
+        identifier: &str,
+        since: &str,
+    ) -> Result<Vec<String>, LinearError> {
+        let query = r#"
+            query GetIssueComments($id: String!, $since: DateTimeOrDuration!) {
+                issue(id: $id) {
+                    comments(filter: { createdAt: { gte: $since } }, first: 100) {
+                        nodes { body }
+                    }
+                }
+            }
+        "#;
+        let data: IssueTextData = self
+            .graphql(
+                query,
+                serde_json::json!({ "id": identifier, "since": since }),
+            )
+            .await?;
+        let issue = data
+            .issue
+            .ok_or_else(|| LinearError::GraphQL(format!("Issue {} not found", identifier)))?;
+        Ok(issue
+            .comments
+            .map(|c| c.nodes.into_iter().map(|n| n.body).collect())
+            .unwrap_or_default())
+    }
+
+    /// Replace an issue's description.
+    pub async fn update_linear_issue_description(
+        &self,
+        identifier: &str,
+        description: &str,
+    ) -> Result<(), LinearError> {
+        let fields = HashMap::from([("description".to_string(), serde_json::json!(description))]);
+        self.update_linear_issue_fields(identifier, &fields).await
+    }
+
     /// Add a comment to a Linear issue.
     pub async fn add_linear_comment(
         &self,
@@ -609,7 +1019,7 @@ impl LinearClient {
             mutation CreateIssue($input: IssueCreateInput!) {
                 issueCreate(input: $input) {
                     success
-                    issue { id identifier }
+                    issue { id identifier url branchName }
                 }
             }
         "#;
@@ -634,31 +1044,223 @@ impl LinearClient {
 
         // Create blocking relations
         for blocker_id in &input.blocked_by {
-            let rel_mutation = r#"
-                mutation CreateRelation($issueId: String!, $relatedIssueId: String!, $type: IssueRelationType!) {
-                    issueRelationCreate(input: { issueId: $issueId, relatedIssueId: $relatedIssueId, type: $type }) {
-                        success
-                    }
-                }
-            "#;
-
-            let _: IssueRelationCreateData = self
-                .graphql(
-                    rel_mutation,
-                    serde_json::json!({
-                        "issueId": blocker_id,
-                        "relatedIssueId": created.id,
-                        "type": "blocks",
-                    }),
-                )
+            self.add_linear_blocking_relation(blocker_id, &created.id)
                 .await?;
         }
 
         Ok(CreatedIssue {
             id: created.id,
             identifier: created.identifier,
+            url: created.url,
+            branch_name: created.branch_name,
         })
     }
+
+    /// Record that `blocker_id` blocks `blocked_id`.
+    pub async fn add_linear_blocking_relation(
+        &self,
+        blocker_id: &str,
+        blocked_id: &str,
+    ) -> Result<(), LinearError> {
+        let mutation = r#"
+            mutation CreateRelation($issueId: String!, $relatedIssueId: String!, $type: IssueRelationType!) {
+                issueRelationCreate(input: { issueId: $issueId, relatedIssueId: $relatedIssueId, type: $type }) {
+                    success
+                }
+            }
+        "#;
+
+        let _: IssueRelationCreateData = self
+            .graphql(
+                mutation,
+                serde_json::json!({
+                    "issueId": blocker_id,
+                    "relatedIssueId": blocked_id,
+                    "type": "blocks",
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Delete the "blocks" relations from `blocker_id` to `blocked_id`.
+    ///
+    /// Either argument may be an issue ID or identifier. Succeeds without a
+    /// mutation when the relation no longer exists.
+    pub async fn remove_linear_blocking_relation(
+        &self,
+        blocker_id: &str,
+        blocked_id: &str,
+    ) -> Result<(), LinearError> {
+        let query = r#"
+            query GetInverseRelations($id: String!) {
+                issue(id: $id) {
+                    inverseRelations {
+                        nodes {
+                            id
+                            type
+                            issue { id identifier }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let data: IssueRelationsData = self
+            .graphql(query, serde_json::json!({ "id": blocked_id }))
+            .await?;
+        let issue = data
+            .issue
+            .ok_or_else(|| LinearError::GraphQL(format!("Issue {} not found", blocked_id)))?;
+
+        let mutation = r#"
+            mutation DeleteRelation($id: String!) {
+                issueRelationDelete(id: $id) {
+                    success
+                }
+            }
+        "#;
+        for relation in issue.inverse_relations.nodes.into_iter().filter(|r| {
+            r.relation_type == "blocks"
+                && (r.issue.id == blocker_id || r.issue.identifier == blocker_id)
+        }) {
+            let data: IssueRelationDeleteData = self
+                .graphql(mutation, serde_json::json!({ "id": relation.id }))
+                .await?;
+            if !data.issue_relation_delete.is_some_and(|p| p.success) {
+                return Err(LinearError::GraphQL(
+                    "issueRelationDelete mutation returned success=false".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Look up the ID of the team whose key or name is `team`.
+    pub async fn find_linear_team_id(&self, team: &str) -> Result<String, LinearError> {
+        let query = r#"
+            query ListTeams {
+                teams(first: 250) {
+                    nodes { id key name }
+                }
+            }
+        "#;
+
+        let data: TeamsData = self.graphql(query, serde_json::json!({})).await?;
+
+        data.teams
+            .nodes
+            .into_iter()
+            .find(|t| t.key.eq_ignore_ascii_case(team) || t.name.eq_ignore_ascii_case(team))
+            .map(|t| t.id)
+            .ok_or_else(|| LinearError::GraphQL(format!("No Linear team named {}", team)))
+    }
+
+    /// Fetch an issue's ID and its team's ID, as needed to create children.
+    pub async fn fetch_linear_issue_team(
+        &self,
+        identifier: &str,
+    ) -> Result<(String, String), LinearError> {
+        let query = r#"
+            query GetIssueTeam($id: String!) {
+                issue(id: $id) {
+                    id
+                    identifier
+                    title
+                    team { id }
+                }
+            }
+        "#;
+
+        let data: IssueData = self
+            .graphql(query, serde_json::json!({ "id": identifier }))
+            .await?;
+
+        let issue = data
+            .issue
+            .ok_or_else(|| LinearError::GraphQL(format!("Issue {} not found", identifier)))?;
+        let team_id = issue
+            .team
+            .ok_or_else(|| LinearError::GraphQL("Issue has no team".to_string()))?
+            .id;
+        Ok((issue.id, team_id))
+    }
+
+    /// Delete an issue (Linear moves it to the trash).
+    pub async fn delete_linear_issue(&self, issue_id: &str) -> Result<(), LinearError> {
+        let mutation = r#"
+            mutation DeleteIssue($id: String!) {
+                issueDelete(id: $id) {
+                    success
+                }
+            }
+        "#;
+
+        let data: IssueDeleteData = self
+            .graphql(mutation, serde_json::json!({ "id": issue_id }))
+            .await?;
+
+        if !data.issue_delete.is_some_and(|p| p.success) {
+            return Err(LinearError::GraphQL(
+                "issueDelete mutation returned success=false".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Map a sub-task node to the task graph's issue shape.
+fn sub_task_from_node(node: IssueNode) -> LinearIssue {
+    let status = node
+        .state
+        .as_ref()
+        .map(|s| s.name.clone())
+        .unwrap_or_else(|| "Backlog".to_string());
+
+    let branch_name = node
+        .branch_name
+        .filter(|b| !b.is_empty())
+        .unwrap_or_else(|| format!("feat/{}", node.identifier.to_lowercase()));
+
+    let blocked_by: Vec<Relation> = node
+        .inverse_relations
+        .as_ref()
+        .map(|ir| {
+            ir.nodes
+                .iter()
+                .filter(|r| r.relation_type == "blocks")
+                .map(|r| Relation {
+                    id: r.issue.id.clone(),
+                    identifier: r.issue.identifier.clone(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    LinearIssue {
+        id: node.id,
+        identifier: node.identifier,
+        title: node.title,
+        status,
+        git_branch_name: branch_name,
+        relations: Some(Relations {
+            blocked_by,
+            blocks: Vec::new(),
+        }),
+        scoring: None,
+        priority: node.priority.filter(|p| *p >= 1.0).map(|p| p.round() as u8),
+        labels: node
+            .labels
+            .map(|l| l.nodes.into_iter().map(|n| n.name).collect())
+            .unwrap_or_default(),
+        timeout_minutes: None,
+        task_type: TaskType::Agent,
+    }
+}
+
+/// Whether a GraphQL document is a mutation rather than a read-only query.
+fn is_mutation(document: &str) -> bool {
+    document.trim_start().starts_with("mutation")
 }
 
 // ---------------------------------------------------------------------------
@@ -669,6 +1271,17 @@ impl LinearClient {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_mutation() {
+        assert!(is_mutation(
+            "\n    mutation AddComment($issueId: String!) { commentCreate { success } }"
+        ));
+        assert!(!is_mutation("query { viewer { name } }"));
+        assert!(!is_mutation(
+            "\n    query GetIssue($id: String!) { issue(id: $id) { id } }"
+        ));
+    }
+
     // -- Client construction tests --
 
     #[test]
@@ -0,0 +1,427 @@
+//! Linear webhook deliveries and how they update local issue state
+//!
+//! `mobius listen` receives Linear's `Issue` and `Comment` webhooks and
+//! applies them to the specs under `.mobius/issues/`. The loop re-reads those
+//! specs every iteration, so remote status changes, new sub-issues and
+//! comments reach the live task graph (and the next agents' context) without
+//! a manual `mobius pull`.
+
+use anyhow::Result;
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::local_state::{
+    is_valid_identifier, list_local_issue_ids, read_parent_spec, read_subtasks, write_parent_spec,
+    write_subtask_spec,
+};
+use crate::types::context::{ParentIssueContext, SubTaskContext, TaskComment};
+use crate::types::enums::TaskType;
+
+/// Header carrying the hex HMAC-SHA256 of the raw body
+pub const SIGNATURE_HEADER: &str = "linear-signature";
+
+/// Deliveries older than this are rejected as possible replays
+const MAX_DELIVERY_AGE_MS: i64 = 60_000;
+
+/// A webhook delivery; `data` is decoded according to `kind`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookPayload {
+    pub action: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub data: serde_json::Value,
+    /// Milliseconds since the epoch at which Linear sent the delivery
+    #[serde(default)]
+    pub webhook_timestamp: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueData {
+    id: String,
+    #[serde(default)]
+    identifier: String,
+    title: Option<String>,
+    description: Option<String>,
+    state: Option<NamedRef>,
+    parent_id: Option<String>,
+    /// 0 = no priority, 1 = urgent .. 4 = low
+    priority: Option<f64>,
+    labels: Option<Vec<NamedRef>>,
+    branch_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct NamedRef {
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CommentData {
+    id: String,
+    body: String,
+    issue_id: Option<String>,
+    issue: Option<IdRef>,
+    user: Option<NamedRef>,
+    #[serde(default)]
+    created_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct IdRef {
+    id: String,
+}
+
+/// What a delivery changed locally
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WebhookOutcome {
+    /// A sub-task's spec changed; `status` is set when its status moved
+    TaskUpdated {
+        parent: String,
+        task: String,
+        status: Option<String>,
+    },
+    TaskCreated {
+        parent: String,
+        task: String,
+    },
+    ParentUpdated {
+        parent: String,
+    },
+    CommentAdded {
+        parent: String,
+        task: String,
+    },
+    /// Nothing local matched, or the event carries nothing we track
+    Ignored(String),
+}
+
+/// Check `signature` (hex) against the HMAC-SHA256 of `body` keyed by `secret`.
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Ok(expected) = hex::decode(signature.trim()) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// Whether a delivery sent at `sent_ms` is recent enough to accept at `now_ms`.
+pub fn is_fresh(sent_ms: i64, now_ms: i64) -> bool {
+    (now_ms - sent_ms).abs() <= MAX_DELIVERY_AGE_MS
+}
+
+/// Apply a delivery to the local issue specs.
+pub fn apply_webhook(payload: &WebhookPayload) -> Result<WebhookOutcome> {
+    match (payload.kind.as_str(), payload.action.as_str()) {
+        ("Issue", "create" | "update") => {
+            let issue: IssueData = serde_json::from_value(payload.data.clone())?;
+            apply_issue(&issue)
+        }
+        ("Comment", "create" | "update") => {
+            let comment: CommentData = serde_json::from_value(payload.data.clone())?;
+            apply_comment(&comment)
+        }
+        (kind, action) => Ok(WebhookOutcome::Ignored(format!("{} {}", kind, action))),
+    }
+}
+
+fn apply_issue(issue: &IssueData) -> Result<WebhookOutcome> {
+    if let Some((parent, mut task)) = find_local_task(&issue.id) {
+        let status = update_task(&mut task, issue);
+        write_subtask_spec(&parent, &task)?;
+        return Ok(WebhookOutcome::TaskUpdated {
+            parent,
+            task: task.identifier,
+            status,
+        });
+    }
+
+    if let Some((parent, mut spec)) = find_local_parent(&issue.id) {
+        if let Some(title) = &issue.title {
+            spec.title = title.clone();
+        }
+        if let Some(state) = &issue.state {
+            spec.status = state.name.clone();
+        }
+        if let Some(labels) = &issue.labels {
+            spec.labels = labels.iter().map(|l| l.name.clone()).collect();
+        }
+        write_parent_spec(&parent, &spec)?;
+        return Ok(WebhookOutcome::ParentUpdated { parent });
+    }
+
+    // A new sub-issue of a parent this checkout is working on
+    if let Some((parent, _)) = issue.parent_id.as_deref().and_then(find_local_parent) {
+        if issue.identifier.is_empty() {
+            return Ok(WebhookOutcome::Ignored(
+                "sub-issue without identifier".into(),
+            ));
+        }
+        if !is_valid_identifier(&issue.identifier) {
+            return Ok(WebhookOutcome::Ignored(format!(
+                "sub-issue with invalid identifier '{}'",
+                issue.identifier
+            )));
+        }
+        let task = new_task(issue);
+        write_subtask_spec(&parent, &task)?;
+        return Ok(WebhookOutcome::TaskCreated {
+            parent,
+            task: task.identifier,
+        });
+    }
+
+    Ok(WebhookOutcome::Ignored(format!(
+        "{} is not a local issue",
+        display_id(issue)
+    )))
+}
+
+fn apply_comment(comment: &CommentData) -> Result<WebhookOutcome> {
+    let issue_id = comment
+        .issue_id
+        .as_deref()
+        .or(comment.issue.as_ref().map(|i| i.id.as_str()))
+        .unwrap_or_default();
+    let Some((parent, mut task)) = find_local_task(issue_id) else {
+        return Ok(WebhookOutcome::Ignored(
+            "comment on an issue that is not a local sub-task".into(),
+        ));
+    };
+    upsert_comment(
+        &mut task,
+        TaskComment {
+            id: comment.id.clone(),
+            author: comment
+                .user
+                .as_ref()
+                .map(|u| u.name.clone())
+                .unwrap_or_default(),
+            body: comment.body.clone(),
+            created_at: comment.created_at.clone(),
+        },
+    );
+    write_subtask_spec(&parent, &task)?;
+    Ok(WebhookOutcome::CommentAdded {
+        parent,
+        task: task.identifier,
+    })
+}
+
+/// Copy the fields present in `issue` onto `task`; returns the new status if it moved.
+fn update_task(task: &mut SubTaskContext, issue: &IssueData) -> Option<String> {
+    if let Some(title) = &issue.title {
+        task.title = title.clone();
+    }
+    if let Some(description) = &issue.description {
+        task.description = description.clone();
+    }
+    if let Some(labels) = &issue.labels {
+        task.labels = labels.iter().map(|l| l.name.clone()).collect();
+    }
+    if let Some(priority) = issue.priority {
+        task.priority = linear_priority(priority);
+    }
+    let state = issue.state.as_ref()?;
+    if state.name == task.status {
+        return None;
+    }
+    task.status = state.name.clone();
+    Some(task.status.clone())
+}
+
+fn new_task(issue: &IssueData) -> SubTaskContext {
+    SubTaskContext {
+        id: issue.id.clone(),
+        identifier: issue.identifier.clone(),
+        title: issue.title.clone().unwrap_or_default(),
+        description: issue.description.clone().unwrap_or_default(),
+        status: issue
+            .state
+            .as_ref()
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| "Backlog".to_string()),
+        git_branch_name: issue.branch_name.clone().unwrap_or_default(),
+        blocked_by: Vec::new(),
+        blocks: Vec::new(),
+        scoring: None,
+        priority: issue.priority.and_then(linear_priority),
+        labels: issue
+            .labels
+            .iter()
+            .flatten()
+            .map(|l| l.name.clone())
+            .collect(),
+        task_type: TaskType::Agent,
+        comments: Vec::new(),
+    }
+}
+
+/// Replace an edited comment in place, or append a new one.
+fn upsert_comment(task: &mut SubTaskContext, comment: TaskComment) {
+    match task.comments.iter_mut().find(|c| c.id == comment.id) {
+        Some(existing) => *existing = comment,
+        None => task.comments.push(comment),
+    }
+}
+
+fn linear_priority(priority: f64) -> Option<u8> {
+    (priority >= 1.0).then(|| priority.round() as u8)
+}
+
+fn display_id(issue: &IssueData) -> &str {
+    if issue.identifier.is_empty() {
+        &issue.id
+    } else {
+        &issue.identifier
+    }
+}
+
+/// The local parent and spec of the sub-task with Linear ID `id`.
+fn find_local_task(id: &str) -> Option<(String, SubTaskContext)> {
+    if id.is_empty() {
+        return None;
+    }
+    list_local_issue_ids().into_iter().find_map(|parent| {
+        read_subtasks(&parent)
+            .into_iter()
+            .find(|t| t.id == id)
+            .map(|task| (parent, task))
+    })
+}
+
+/// The local issue, and its spec, whose parent spec has Linear ID `id`.
+fn find_local_parent(id: &str) -> Option<(String, ParentIssueContext)> {
+    list_local_issue_ids().into_iter().find_map(|parent| {
+        read_parent_spec(&parent)
+            .filter(|spec| spec.id == id)
+            .map(|spec| (parent, spec))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn task() -> SubTaskContext {
+        new_task(&IssueData {
+            id: "uuid-1".into(),
+            identifier: "MOB-101".into(),
+            title: Some("Add endpoint".into()),
+            state: Some(NamedRef {
+                name: "Todo".into(),
+            }),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_verify_signature() {
+        let body = br#"{"action":"update","type":"Issue"}"#;
+        let signature = sign("s3cret", body);
+        assert!(verify_signature("s3cret", body, &signature));
+        assert!(!verify_signature("other", body, &signature));
+        assert!(!verify_signature("s3cret", b"{}", &signature));
+        assert!(!verify_signature("s3cret", body, "not-hex"));
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        assert!(is_fresh(1_000_000, 1_030_000));
+        assert!(!is_fresh(1_000_000, 1_090_000));
+    }
+
+    #[test]
+    fn test_parse_issue_payload() {
+        let payload: WebhookPayload = serde_json::from_str(
+            r#"{
+                "action": "update",
+                "type": "Issue",
+                "webhookTimestamp": 1676056940508,
+                "data": {
+                    "id": "uuid-1",
+                    "identifier": "MOB-101",
+                    "title": "Add endpoint",
+                    "state": {"id": "s1", "name": "In Progress", "type": "started"},
+                    "priority": 2,
+                    "labels": [{"id": "l1", "name": "backend"}],
+                    "parentId": "uuid-parent"
+                },
+                "updatedFrom": {"stateId": "s0"}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(payload.webhook_timestamp, Some(1676056940508));
+
+        let issue: IssueData = serde_json::from_value(payload.data).unwrap();
+        assert_eq!(issue.parent_id.as_deref(), Some("uuid-parent"));
+
+        let mut task = task();
+        assert_eq!(
+            update_task(&mut task, &issue).as_deref(),
+            Some("In Progress")
+        );
+        assert_eq!(task.priority, Some(2));
+        assert_eq!(task.labels, ["backend"]);
+        // Same state again is not a status change
+        assert_eq!(update_task(&mut task, &issue), None);
+    }
+
+    #[test]
+    fn test_update_task_keeps_fields_missing_from_the_delivery() {
+        let mut task = task();
+        task.description = "Spec body".into();
+        let status = update_task(
+            &mut task,
+            &IssueData {
+                id: "uuid-1".into(),
+                title: Some("Renamed".into()),
+                ..Default::default()
+            },
+        );
+        assert_eq!(status, None);
+        assert_eq!(task.title, "Renamed");
+        assert_eq!(task.description, "Spec body");
+        assert_eq!(task.status, "Todo");
+    }
+
+    #[test]
+    fn test_upsert_comment_replaces_edits() {
+        let mut task = task();
+        let comment = |body: &str| TaskComment {
+            id: "c1".into(),
+            author: "Dana".into(),
+            body: body.into(),
+            created_at: String::new(),
+        };
+        upsert_comment(&mut task, comment("first"));
+        upsert_comment(&mut task, comment("edited"));
+        assert_eq!(task.comments.len(), 1);
+        assert_eq!(task.comments[0].body, "edited");
+    }
+
+    #[test]
+    fn test_unhandled_events_are_ignored() {
+        let payload: WebhookPayload =
+            serde_json::from_str(r#"{"action": "create", "type": "Reaction", "data": {}}"#)
+                .unwrap();
+        assert!(matches!(
+            apply_webhook(&payload).unwrap(),
+            WebhookOutcome::Ignored(_)
+        ));
+    }
+}
@@ -4,7 +4,10 @@
 //! - Atomic LOC-{N} ID generation via counter.json
 //! - Parent/sub-task spec storage
 //! - Iteration logging for execution tracking
+//! - A [`StateStore`] for the counter, iteration logs and issue index, backed
+//!   by files (default) or SQLite (`state_store: sqlite`)
 //! - Pending update queuing for backend sync
+//! - Resolutions for external URL blockers
 //!
 //! Uses git repo root detection to ensure .mobius/ is always in the repository root,
 //! even when called from nested subdirectories.
@@ -16,15 +19,152 @@ use std::process::Command;
 use std::sync::OnceLock;
 
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::context::extract_timeout_minutes;
 use crate::types::context::{ParentIssueContext, SubTaskContext};
+use crate::types::enums::{FailureClass, StateStoreKind};
 use crate::types::task_graph::{LinearIssue, Relation, Relations};
+use crate::verify::VerifyRun;
+
+#[cfg(feature = "sqlite")]
+mod sqlite_store;
+#[cfg(feature = "sqlite")]
+pub use sqlite_store::SqliteStateStore;
 
 /// Cached git repo root, resolved once per process.
 static GIT_REPO_ROOT: OnceLock<PathBuf> = OnceLock::new();
 
+/// Configured state store, opened once per process.
+static STATE_STORE: OnceLock<Box<dyn StateStore>> = OnceLock::new();
+
+/// Storage for the local state written most often and by the most
+/// processes at once: the LOC ID counter, iteration logs, and the index of
+/// issues with local state.
+///
+/// Specs, pending updates and summaries stay as files under
+/// `.mobius/issues/` whichever store is configured.
+pub trait StateStore: Send + Sync {
+    /// Allocate the next `LOC-{N}` identifier.
+    fn next_local_id(&self) -> Result<String>;
+
+    /// Iteration log entries for an issue, oldest first.
+    fn read_iteration_log(&self, issue_id: &str) -> Vec<IterationLogEntry>;
+
+    fn append_iteration_log(&self, issue_id: &str, entry: &IterationLogEntry) -> Result<()>;
+
+    /// Record that local state exists for `issue_id`.
+    fn register_issue(&self, issue_id: &str) -> Result<()>;
+
+    /// Drop everything stored for `issue_id` once its directory is removed.
+    fn forget_issue(&self, issue_id: &str) -> Result<()>;
+
+    /// IDs of issues with local state, sorted.
+    fn list_issue_ids(&self) -> Vec<String>;
+}
+
+/// The default store: `counter.json`, per-issue `iterations.json`, and the
+/// issue directories themselves as the index.
+pub struct FileStateStore {
+    issues_path: PathBuf,
+}
+
+impl FileStateStore {
+    pub fn new(issues_path: PathBuf) -> Self {
+        Self { issues_path }
+    }
+
+    fn iteration_log_path(&self, issue_id: &str) -> PathBuf {
+        self.issues_path
+            .join(issue_id)
+            .join("execution")
+            .join("iterations.json")
+    }
+}
+
+impl StateStore for FileStateStore {
+    fn next_local_id(&self) -> Result<String> {
+        fs::create_dir_all(&self.issues_path)?;
+        allocate_local_id(&self.issues_path)
+    }
+
+    fn read_iteration_log(&self, issue_id: &str) -> Vec<IterationLogEntry> {
+        fs::read_to_string(self.iteration_log_path(issue_id))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn append_iteration_log(&self, issue_id: &str, entry: &IterationLogEntry) -> Result<()> {
+        // Locked read-modify-write: parallel agents finishing at once would
+        // otherwise drop each other's entries
+        let file_path = self.iteration_log_path(issue_id);
+        with_json_array_sync(&file_path, "iteration log", |mut entries| {
+            entries.push(entry.clone());
+            entries
+        })
+        .map(|_| ())
+    }
+
+    fn register_issue(&self, _issue_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn forget_issue(&self, _issue_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn list_issue_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = fs::read_dir(&self.issues_path)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                    .filter_map(|e| e.file_name().to_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        ids.sort();
+        ids
+    }
+}
+
+/// The store selected by `state_store` in the config.
+///
+/// Falls back to files, with a warning, when SQLite was requested but can't
+/// be opened or this build lacks the `sqlite` feature.
+pub fn state_store() -> &'static dyn StateStore {
+    STATE_STORE
+        .get_or_init(|| {
+            let kind = crate::config::loader::read_config(
+                &crate::config::paths::resolve_paths().config_path,
+            )
+            .map(|config| config.state_store)
+            .unwrap_or_default();
+            open_state_store(kind)
+        })
+        .as_ref()
+}
+
+fn open_state_store(kind: StateStoreKind) -> Box<dyn StateStore> {
+    if kind == StateStoreKind::Sqlite {
+        #[cfg(feature = "sqlite")]
+        match SqliteStateStore::open(
+            &get_project_mobius_path().join("state.db"),
+            &get_issues_path(),
+        ) {
+            Ok(store) => return Box::new(store),
+            Err(e) => tracing::warn!("Failed to open state.db, using files: {:#}", e),
+        }
+        #[cfg(not(feature = "sqlite"))]
+        tracing::warn!("state_store: sqlite needs a build with the sqlite feature; using files");
+    }
+    Box::new(FileStateStore::new(get_issues_path()))
+}
+
 /// Entry in the iteration log tracking execution attempts
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -41,6 +181,78 @@ pub struct IterationLogEntry {
     pub files_modified: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub commit_hash: Option<String>,
+    /// Retries preceding this attempt (0 on the first dispatch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_count: Option<u32>,
+    /// Cause of a failed or partial attempt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_class: Option<FailureClass>,
+    /// Model the agent ran with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Wall-clock time of the attempt
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Estimated spend for the attempt, when the model's pricing is known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+    /// Tokens the agent consumed, from its stream-json output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_tokens: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_tokens: Option<u64>,
+    /// The sub-task's verify command, run after the agent reported success
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify: Option<VerifyRun>,
+}
+
+impl IterationLogEntry {
+    /// An attempt with only its required fields set; outcome details such as
+    /// the error, tokens and cost start empty.
+    pub fn new(
+        subtask_id: impl Into<String>,
+        attempt: u32,
+        started_at: impl Into<String>,
+        status: IterationStatus,
+    ) -> Self {
+        Self {
+            subtask_id: subtask_id.into(),
+            attempt,
+            started_at: started_at.into(),
+            completed_at: None,
+            status,
+            error: None,
+            files_modified: None,
+            commit_hash: None,
+            retry_count: None,
+            failure_class: None,
+            model: None,
+            duration_ms: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+            verify: None,
+        }
+    }
+
+    /// Start and end of the attempt. The end falls back to the start plus
+    /// `duration_ms`, then to the start; `None` when the start won't parse.
+    pub fn span(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        let start = DateTime::parse_from_rfc3339(&self.started_at)
+            .ok()?
+            .with_timezone(&Utc);
+        let end = self
+            .completed_at
+            .as_deref()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc))
+            .or_else(|| {
+                self.duration_ms
+                    .map(|ms| start + chrono::Duration::milliseconds(ms as i64))
+            })
+            .unwrap_or(start);
+        Some((start, end.max(start)))
+    }
 }
 
 /// Status of an iteration
@@ -84,6 +296,26 @@ pub struct LocalPendingUpdate {
     pub payload: serde_json::Value,
 }
 
+/// An external URL blocker marked as resolved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedBlocker {
+    pub url: String,
+    pub resolved_at: String,
+    /// How the blocker was resolved
+    pub source: BlockerResolution,
+}
+
+/// How an external blocker was resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockerResolution {
+    /// `mobius deps resolve`
+    Manual,
+    /// The linked GitHub pull request was merged
+    Merged,
+}
+
 /// Counter file structure for LOC-{N} ID generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct Counter {
@@ -96,7 +328,7 @@ fn status_priority(status: &str) -> u32 {
         "pending" => 0,
         "ready" => 1,
         "in_progress" => 2,
-        "done" => 3,
+        "done" | "skipped" => 3,
         _ => 0,
     }
 }
@@ -105,9 +337,12 @@ fn status_priority(status: &str) -> u32 {
 ///
 /// Uses `git rev-parse --show-toplevel` to find the repo root.
 /// Result is cached for the process lifetime via `OnceLock`.
+///
+/// Inside one of mobius's own agent worktrees this resolves to the main
+/// repository instead, so state isn't nested under the worktree's mirror.
 fn get_git_repo_root() -> &'static Path {
     GIT_REPO_ROOT.get_or_init(|| {
-        match Command::new("git")
+        let root = match Command::new("git")
             .args(["rev-parse", "--show-toplevel"])
             .output()
         {
@@ -119,10 +354,47 @@ fn get_git_repo_root() -> &'static Path {
                 // Fallback to cwd if not in a git repo
                 std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
             }
+        };
+        match agent_worktree_main_root(&root) {
+            Some(main_root) => {
+                eprintln!(
+                    "Warning: {} is a mobius agent worktree; using state in {}",
+                    root.display(),
+                    main_root.display()
+                );
+                main_root
+            }
+            None => root,
         }
     })
 }
 
+/// Main repository root when `toplevel` is a linked worktree created by a
+/// mobius loop: it holds mirrored `.mobius/issues/` context and the main
+/// repository has a `.mobius/` of its own.
+pub fn agent_worktree_main_root(toplevel: &Path) -> Option<PathBuf> {
+    let main_root = linked_worktree_main_root(toplevel)?;
+    (toplevel.join(".mobius").join("issues").is_dir() && main_root.join(".mobius").is_dir())
+        .then_some(main_root)
+}
+
+/// Main repository root of a linked worktree, read from its `.git` file
+/// (`gitdir: <main>/.git/worktrees/<name>`). `None` for main checkouts,
+/// submodules, and bare repositories.
+fn linked_worktree_main_root(toplevel: &Path) -> Option<PathBuf> {
+    let content = fs::read_to_string(toplevel.join(".git")).ok()?;
+    let gitdir = toplevel.join(content.trim().strip_prefix("gitdir:")?.trim());
+    let worktrees = gitdir.parent()?;
+    if worktrees.file_name()? != "worktrees" {
+        return None;
+    }
+    let common_dir = worktrees.parent()?;
+    if common_dir.file_name()? != ".git" {
+        return None;
+    }
+    common_dir.parent().map(Path::to_path_buf)
+}
+
 /// Get the absolute path to the project-local .mobius/ directory.
 ///
 /// Always returns the path relative to the git repository root,
@@ -131,24 +403,53 @@ pub fn get_project_mobius_path() -> PathBuf {
     get_git_repo_root().join(".mobius")
 }
 
+/// Entries `.mobius/.gitignore` must hold: runtime state and per-checkout config
+const MOBIUS_GITIGNORE_ENTRIES: [&str; 2] = ["state/", "config.local.yaml"];
+
 /// Ensure the project-local .mobius/ directory exists with proper structure.
 ///
-/// Creates .mobius/ and a .gitignore file containing `state/` entry
-/// to keep runtime state out of version control while preserving specs.
+/// Creates .mobius/ and a .gitignore file ignoring `state/` and
+/// `config.local.yaml`, keeping runtime state and per-checkout config out of
+/// version control while preserving specs. An existing .gitignore gets the
+/// entries it lacks appended.
 pub fn ensure_project_mobius_dir() -> Result<()> {
     let mobius_path = get_project_mobius_path();
     fs::create_dir_all(&mobius_path)
         .with_context(|| format!("Failed to create {}", mobius_path.display()))?;
 
+    let missing = missing_gitignore_entries(&mobius_path);
+    if missing.is_empty() {
+        return Ok(());
+    }
     let gitignore_path = mobius_path.join(".gitignore");
-    if !gitignore_path.exists() {
-        fs::write(&gitignore_path, "state/\n")
-            .with_context(|| format!("Failed to write {}", gitignore_path.display()))?;
+    let mut content = fs::read_to_string(&gitignore_path).unwrap_or_default();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for entry in missing {
+        content.push_str(entry);
+        content.push('\n');
     }
+    fs::write(&gitignore_path, content)
+        .with_context(|| format!("Failed to write {}", gitignore_path.display()))?;
 
     Ok(())
 }
 
+/// Required entries missing from `<mobius_path>/.gitignore` (all of them when
+/// the file doesn't exist).
+pub fn missing_gitignore_entries(mobius_path: &Path) -> Vec<&'static str> {
+    let content = fs::read_to_string(mobius_path.join(".gitignore")).unwrap_or_default();
+    MOBIUS_GITIGNORE_ENTRIES
+        .into_iter()
+        .filter(|entry| {
+            !content
+                .lines()
+                .any(|line| line.trim().trim_start_matches('/') == *entry)
+        })
+        .collect()
+}
+
 /// Get the path to the issues directory within .mobius/
 fn get_issues_path() -> PathBuf {
     get_project_mobius_path().join("issues")
@@ -167,7 +468,7 @@ fn ensure_issue_dir(issue_id: &str) -> Result<()> {
         .with_context(|| format!("Failed to create tasks dir for {}", issue_id))?;
     fs::create_dir_all(issue_path.join("execution"))
         .with_context(|| format!("Failed to create execution dir for {}", issue_id))?;
-    Ok(())
+    state_store().register_issue(issue_id)
 }
 
 /// Write data to a file atomically using temp file + rename pattern.
@@ -222,40 +523,115 @@ fn scan_for_next_id(issues_path: &Path) -> u32 {
     max_id + 1
 }
 
-/// Get the next local ID by atomically incrementing counter.json.
+/// Get the next local ID by atomically incrementing the configured store's
+/// counter.
 ///
-/// Returns IDs in LOC-{N} format where N is zero-padded to 3 digits.
-/// Uses atomic write (temp file + rename) for the counter file.
-/// If counter.json is missing or corrupted, scans existing LOC-* directories
-/// to determine the next ID.
+/// Returns IDs in LOC-{N} format where N is zero-padded to 3 digits. The file
+/// store keeps the counter in counter.json, written atomically (temp file +
+/// rename) under the same advisory lock as runtime state so concurrent
+/// invocations never hand out the same ID. If counter.json is missing or
+/// corrupted, existing LOC-* directories are scanned for the next ID.
 pub fn get_next_local_id() -> Result<String> {
     ensure_project_mobius_dir()?;
-    let issues_path = get_issues_path();
-    fs::create_dir_all(&issues_path)?;
+    state_store().next_local_id()
+}
 
+/// Allocate the next LOC ID from `issues_path/counter.json` under its lock.
+fn allocate_local_id(issues_path: &Path) -> Result<String> {
     let counter_path = issues_path.join("counter.json");
-    let next_value = if counter_path.exists() {
-        match fs::read_to_string(&counter_path) {
-            Ok(content) => match serde_json::from_str::<Counter>(&content) {
-                Ok(counter) if counter.next > 0 => counter.next,
-                _ => scan_for_next_id(&issues_path),
-            },
-            Err(_) => scan_for_next_id(&issues_path),
-        }
-    } else {
-        scan_for_next_id(&issues_path)
-    };
+    let lock_path = issues_path.join("counter.json.lock");
+
+    crate::context::with_file_lock(&lock_path, "local ID counter", || {
+        let next_value = if counter_path.exists() {
+            match fs::read_to_string(&counter_path) {
+                Ok(content) => match serde_json::from_str::<Counter>(&content) {
+                    Ok(counter) if counter.next > 0 => counter.next,
+                    _ => scan_for_next_id(issues_path),
+                },
+                Err(_) => scan_for_next_id(issues_path),
+            }
+        } else {
+            scan_for_next_id(issues_path)
+        };
 
-    let new_counter = Counter {
-        next: next_value + 1,
-    };
-    atomic_write_json(&counter_path, &new_counter)?;
+        let new_counter = Counter {
+            next: next_value + 1,
+        };
+        atomic_write_json(&counter_path, &new_counter)?;
+
+        Ok(format!("LOC-{:03}", next_value))
+    })
+}
 
-    Ok(format!("LOC-{:03}", next_value))
+/// Describe what is wrong with counter.json, if anything.
+///
+/// A missing counter is recovered on the next allocation, but a corrupted one
+/// or one behind the highest LOC-* directory needs `mobius doctor --fix`.
+pub fn diagnose_local_counter() -> Option<String> {
+    diagnose_counter(&get_issues_path())
+}
+
+fn diagnose_counter(issues_path: &Path) -> Option<String> {
+    let content = fs::read_to_string(issues_path.join("counter.json")).ok()?;
+    let scanned = scan_for_next_id(issues_path);
+    match serde_json::from_str::<Counter>(&content) {
+        Ok(counter) if counter.next >= scanned => None,
+        Ok(counter) if counter.next > 0 => Some(format!(
+            "counter.json is at LOC-{:03} but LOC-{:03} exists",
+            counter.next,
+            scanned - 1
+        )),
+        _ => Some("counter.json is corrupted".to_string()),
+    }
+}
+
+/// Rewrite counter.json from a scan of the LOC-* directories, returning the
+/// next ID it will hand out.
+pub fn rebuild_local_counter() -> Result<String> {
+    rebuild_counter(&get_issues_path())
+}
+
+fn rebuild_counter(issues_path: &Path) -> Result<String> {
+    let lock_path = issues_path.join("counter.json.lock");
+    crate::context::with_file_lock(&lock_path, "local ID counter", || {
+        let next = scan_for_next_id(issues_path);
+        atomic_write_json(&issues_path.join("counter.json"), &Counter { next })?;
+        Ok(format!("LOC-{:03}", next))
+    })
+}
+
+/// IDs of issues with local state under .mobius/issues/, sorted
+pub fn list_local_issue_ids() -> Vec<String> {
+    state_store().list_issue_ids()
+}
+
+/// Drop an issue from the state store after its directory has been removed.
+pub fn forget_local_issue(issue_id: &str) -> Result<()> {
+    state_store().forget_issue(issue_id)
+}
+
+/// Whether `id` is safe to use as an issue or sub-task file name: letters,
+/// digits, `_` and `-` only.
+pub fn is_valid_identifier(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn check_identifier(id: &str) -> Result<()> {
+    if !is_valid_identifier(id) {
+        anyhow::bail!(
+            "Invalid issue identifier '{}': only letters, digits, '_' and '-' are allowed",
+            id
+        );
+    }
+    Ok(())
 }
 
 /// Write a parent issue spec to .mobius/issues/{issueId}/parent.json
 pub fn write_parent_spec(issue_id: &str, spec: &ParentIssueContext) -> Result<()> {
+    check_identifier(issue_id)?;
     ensure_issue_dir(issue_id)?;
     let file_path = get_issue_path(issue_id).join("parent.json");
     atomic_write_json(&file_path, spec)
@@ -280,6 +656,8 @@ pub fn write_subtask_spec(issue_id: &str, task: &SubTaskContext) -> Result<()> {
     if identifier.is_empty() {
         return Ok(());
     }
+    check_identifier(issue_id)?;
+    check_identifier(identifier)?;
 
     ensure_issue_dir(issue_id)?;
     let file_path = get_issue_path(issue_id)
@@ -288,6 +666,19 @@ pub fn write_subtask_spec(issue_id: &str, task: &SubTaskContext) -> Result<()> {
     atomic_write_json(&file_path, task)
 }
 
+/// Delete a sub-task spec from .mobius/issues/{issueId}/tasks/
+pub fn remove_subtask_spec(issue_id: &str, identifier: &str) -> Result<()> {
+    let file_path = get_issue_path(issue_id)
+        .join("tasks")
+        .join(format!("{}.json", identifier));
+    match fs::remove_file(&file_path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("Failed to remove {}", file_path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Update just the status field of a parent issue's parent.json file on disk.
 ///
 /// Reads the existing file, patches the status, and writes it back atomically.
@@ -331,6 +722,26 @@ pub fn update_subtask_status(issue_id: &str, task_identifier: &str, status: &str
     let _ = atomic_write_json(&file_path, &task);
 }
 
+/// Update a sub-task's dispatch priority (1 = highest) in its spec file.
+pub fn update_subtask_priority(issue_id: &str, task_identifier: &str, priority: u8) {
+    let file_path = get_issue_path(issue_id)
+        .join("tasks")
+        .join(format!("{}.json", task_identifier));
+
+    let content = match fs::read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(_) => return,
+    };
+
+    let mut task: SubTaskContext = match serde_json::from_str(&content) {
+        Ok(t) => t,
+        Err(_) => return,
+    };
+
+    task.priority = Some(priority);
+    let _ = atomic_write_json(&file_path, &task);
+}
+
 /// Read all sub-task specs from .mobius/issues/{issueId}/tasks/
 ///
 /// Returns an array of all valid sub-task specs found in the tasks directory.
@@ -379,9 +790,27 @@ pub fn read_subtasks(issue_id: &str) -> Vec<SubTaskContext> {
 /// string arrays for blockedBy/blocks) and the LinearIssue format expected by
 /// the task graph builder. Deduplicates by ID with status priority (higher status wins).
 pub fn read_local_subtasks_as_linear_issues(issue_id: &str) -> Vec<LinearIssue> {
-    let tasks = read_subtasks(issue_id);
+    let issues = subtasks_as_linear_issues(read_subtasks(issue_id));
+
+    // Deduplicate by id — prefer done > in_progress > ready > pending
+    let mut by_id: HashMap<String, LinearIssue> = HashMap::new();
+    for issue in issues {
+        let dominated = by_id
+            .get(&issue.id)
+            .map(|existing| status_priority(&issue.status) > status_priority(&existing.status))
+            .unwrap_or(true);
+
+        if dominated {
+            by_id.insert(issue.id.clone(), issue);
+        }
+    }
 
-    let issues: Vec<LinearIssue> = tasks
+    by_id.into_values().collect()
+}
+
+/// Convert sub-task specs to the LinearIssue format buildTaskGraph() expects.
+pub fn subtasks_as_linear_issues(tasks: Vec<SubTaskContext>) -> Vec<LinearIssue> {
+    tasks
         .into_iter()
         .map(|task| {
             let blocked_by: Vec<Relation> = task
@@ -416,64 +845,48 @@ pub fn read_local_subtasks_as_linear_issues(issue_id: &str) -> Vec<LinearIssue>
                 git_branch_name: task.git_branch_name,
                 relations: Some(Relations { blocked_by, blocks }),
                 scoring: task.scoring,
+                priority: task.priority,
+                labels: task.labels,
+                timeout_minutes: extract_timeout_minutes(&task.description),
+                task_type: task.task_type,
             }
         })
-        .collect();
-
-    // Deduplicate by id — prefer done > in_progress > ready > pending
-    let mut by_id: HashMap<String, LinearIssue> = HashMap::new();
-    for issue in issues {
-        let dominated = by_id
-            .get(&issue.id)
-            .map(|existing| status_priority(&issue.status) > status_priority(&existing.status))
-            .unwrap_or(true);
-
-        if dominated {
-            by_id.insert(issue.id.clone(), issue);
-        }
-    }
-
-    by_id.into_values().collect()
+        .collect()
 }
 
-/// Read all iteration log entries from .mobius/issues/{issueId}/execution/iterations.json
+/// Read all iteration log entries for an issue from the state store
+/// (.mobius/issues/{issueId}/execution/iterations.json with the file store).
 ///
-/// Returns an empty vec if the file doesn't exist or is corrupted.
+/// Returns an empty vec if there are none or the log is corrupted.
 pub fn read_iteration_log(issue_id: &str) -> Vec<IterationLogEntry> {
-    let file_path = get_issue_path(issue_id)
-        .join("execution")
-        .join("iterations.json");
-
-    let content = match fs::read_to_string(&file_path) {
-        Ok(c) => c,
-        Err(_) => return Vec::new(),
-    };
-
-    serde_json::from_str::<Vec<IterationLogEntry>>(&content).unwrap_or_default()
+    state_store().read_iteration_log(issue_id)
 }
 
-/// Write an iteration log entry to .mobius/issues/{issueId}/execution/iterations.json
-///
-/// Appends the entry to the existing array, or creates a new array if the file doesn't exist.
+/// Append an iteration log entry to the state store
+/// (.mobius/issues/{issueId}/execution/iterations.json with the file store).
 pub fn write_iteration_log(issue_id: &str, entry: IterationLogEntry) -> Result<()> {
     ensure_issue_dir(issue_id)?;
-    let file_path = get_issue_path(issue_id)
-        .join("execution")
-        .join("iterations.json");
-
-    let mut entries = if file_path.exists() {
-        match fs::read_to_string(&file_path) {
-            Ok(content) => {
-                serde_json::from_str::<Vec<IterationLogEntry>>(&content).unwrap_or_default()
-            }
-            Err(_) => Vec::new(),
-        }
-    } else {
-        Vec::new()
-    };
+    state_store().append_iteration_log(issue_id, &entry)
+}
 
-    entries.push(entry);
-    atomic_write_json(&file_path, &entries)
+/// Read-modify-write a JSON array file while holding its `.lock` file.
+///
+/// A missing or corrupted file reads as an empty array.
+fn with_json_array_sync<T, F>(file_path: &Path, label: &str, mutate: F) -> Result<Vec<T>>
+where
+    T: Serialize + DeserializeOwned,
+    F: FnOnce(Vec<T>) -> Vec<T>,
+{
+    let lock_path = file_path.with_extension("json.lock");
+    crate::context::with_file_lock(&lock_path, label, || {
+        let current = fs::read_to_string(file_path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        let updated = mutate(current);
+        atomic_write_json(file_path, &updated)?;
+        Ok(updated)
+    })
 }
 
 /// Write a completion summary to .mobius/issues/{issueId}/summary.json
@@ -492,26 +905,31 @@ pub fn queue_pending_update(
     update_type: &str,
     payload: serde_json::Value,
 ) -> Result<()> {
-    ensure_issue_dir(issue_id)?;
-    let file_path = get_issue_path(issue_id).join("pending-updates.json");
-
-    let mut updates: Vec<LocalPendingUpdate> = if file_path.exists() {
-        match fs::read_to_string(&file_path) {
-            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
-            Err(_) => Vec::new(),
-        }
-    } else {
-        Vec::new()
-    };
-
-    updates.push(LocalPendingUpdate {
+    let update = LocalPendingUpdate {
         id: Uuid::new_v4().to_string(),
         created_at: chrono::Utc::now().to_rfc3339(),
         update_type: update_type.to_string(),
         payload,
-    });
+    };
+    with_local_pending_updates_sync(issue_id, |mut updates| {
+        updates.push(update);
+        updates
+    })
+    .map(|_| ())
+}
 
-    atomic_write_json(&file_path, &updates)
+/// Atomically read-modify-write .mobius/issues/{issueId}/pending-updates.json
+/// with file locking.
+pub fn with_local_pending_updates_sync<F>(
+    issue_id: &str,
+    mutate: F,
+) -> Result<Vec<LocalPendingUpdate>>
+where
+    F: FnOnce(Vec<LocalPendingUpdate>) -> Vec<LocalPendingUpdate>,
+{
+    ensure_issue_dir(issue_id)?;
+    let file_path = get_issue_path(issue_id).join("pending-updates.json");
+    with_json_array_sync(&file_path, "pending updates", mutate)
 }
 
 /// Read all pending updates from .mobius/issues/{issueId}/pending-updates.json
@@ -530,12 +948,55 @@ pub fn write_pending_updates(issue_id: &str, updates: &[LocalPendingUpdate]) ->
     atomic_write_json(&file_path, &updates.to_vec())
 }
 
+/// Read resolved external blockers from .mobius/issues/{issueId}/external-blockers.json
+pub fn read_resolved_blockers(issue_id: &str) -> Vec<ResolvedBlocker> {
+    let file_path = get_issue_path(issue_id).join("external-blockers.json");
+    let content = match fs::read_to_string(&file_path) {
+        Ok(c) => c,
+        Err(_) => return Vec::new(),
+    };
+    serde_json::from_str(&content).unwrap_or_default()
+}
+
+/// Mark external blockers as resolved, skipping URLs that already are.
+///
+/// Returns the number of newly resolved URLs.
+pub fn write_resolved_blockers(
+    issue_id: &str,
+    urls: &[String],
+    source: BlockerResolution,
+) -> Result<usize> {
+    ensure_issue_dir(issue_id)?;
+    let file_path = get_issue_path(issue_id).join("external-blockers.json");
+
+    let mut resolved = read_resolved_blockers(issue_id);
+    let before = resolved.len();
+    let now = chrono::Utc::now().to_rfc3339();
+    for url in urls {
+        if !resolved.iter().any(|r| &r.url == url) {
+            resolved.push(ResolvedBlocker {
+                url: url.clone(),
+                resolved_at: now.clone(),
+                source,
+            });
+        }
+    }
+
+    let added = resolved.len() - before;
+    if added > 0 {
+        atomic_write_json(&file_path, &resolved)?;
+    }
+    Ok(added)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use tempfile::TempDir;
 
+    use crate::types::enums::TaskType;
+
     /// Helper to set up a fake git repo and override the cached root.
     /// Since OnceLock can only be set once, tests that need isolation
     /// work with explicit paths instead of relying on the global cache.
@@ -553,6 +1014,34 @@ mod tests {
         fs::create_dir_all(issue_path.join("execution")).unwrap();
     }
 
+    #[test]
+    fn test_missing_gitignore_entries() {
+        let dir = setup_test_dir();
+        assert_eq!(
+            missing_gitignore_entries(dir.path()),
+            vec!["state/", "config.local.yaml"]
+        );
+        // Projects created before local config existed only ignore state/
+        fs::write(dir.path().join(".gitignore"), "/state/\n").unwrap();
+        assert_eq!(
+            missing_gitignore_entries(dir.path()),
+            vec!["config.local.yaml"]
+        );
+        fs::write(dir.path().join(".gitignore"), "state/\nconfig.local.yaml\n").unwrap();
+        assert!(missing_gitignore_entries(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_is_valid_identifier() {
+        for id in ["MOB-123", "LOC-001", "task-007", "42", "abc_def"] {
+            assert!(is_valid_identifier(id), "{}", id);
+        }
+        for id in ["", "../x", "MOB/1", "a.json", "MOB 1", "..", "MOB-1\n"] {
+            assert!(!is_valid_identifier(id), "{:?}", id);
+        }
+        assert!(check_identifier("../../etc").is_err());
+    }
+
     #[test]
     fn test_get_next_local_id_sequential() {
         // Test that IDs are generated sequentially using scan_for_next_id
@@ -604,6 +1093,32 @@ mod tests {
         assert_eq!(scan_for_next_id(&issues), 3);
     }
 
+    #[test]
+    fn test_diagnose_and_rebuild_counter() {
+        let tmp = setup_test_dir();
+        let issues = tmp.path().join("issues");
+        fs::create_dir_all(issues.join("LOC-004")).unwrap();
+        assert_eq!(diagnose_counter(&issues), None);
+
+        let counter_path = issues.join("counter.json");
+        fs::write(&counter_path, r#"{"next": 5}"#).unwrap();
+        assert_eq!(diagnose_counter(&issues), None);
+
+        fs::write(&counter_path, r#"{"next": 2}"#).unwrap();
+        assert_eq!(
+            diagnose_counter(&issues).as_deref(),
+            Some("counter.json is at LOC-002 but LOC-004 exists")
+        );
+
+        fs::write(&counter_path, "{").unwrap();
+        assert_eq!(
+            diagnose_counter(&issues).as_deref(),
+            Some("counter.json is corrupted")
+        );
+        assert_eq!(rebuild_counter(&issues).unwrap(), "LOC-005");
+        assert_eq!(diagnose_counter(&issues), None);
+    }
+
     #[test]
     fn test_counter_with_valid_json() {
         let tmp = setup_test_dir();
@@ -688,6 +1203,10 @@ mod tests {
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         };
 
         let file_path = issues_path(tmp.path())
@@ -730,6 +1249,10 @@ mod tests {
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         };
 
         let task_done = SubTaskContext {
@@ -742,6 +1265,10 @@ mod tests {
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         };
 
         // Write both
@@ -782,6 +1309,10 @@ mod tests {
                 git_branch_name: task.git_branch_name,
                 relations: None,
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             };
 
             let dominated = by_id
@@ -813,6 +1344,14 @@ mod tests {
             error: None,
             files_modified: Some(vec!["src/main.rs".to_string()]),
             commit_hash: Some("abc1234".to_string()),
+            retry_count: None,
+            failure_class: None,
+            model: None,
+            duration_ms: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+            verify: None,
         };
 
         let file_path = issues_path(tmp.path())
@@ -841,6 +1380,14 @@ mod tests {
             error: Some("Test failed".to_string()),
             files_modified: None,
             commit_hash: None,
+            retry_count: None,
+            failure_class: None,
+            model: None,
+            duration_ms: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+            verify: None,
         };
 
         let mut all_entries = read_back;
@@ -1021,7 +1568,7 @@ mod tests {
         let content = fs::read_to_string(&counter_path).unwrap();
         match serde_json::from_str::<Counter>(&content) {
             Ok(counter) => assert!(
-                !(counter.next > 0),
+                counter.next == 0,
                 "Counter with next=0 should NOT pass the > 0 check"
             ),
             Err(_) => panic!("Should parse as valid Counter"),
@@ -1069,6 +1616,36 @@ mod tests {
     // Missing Directory Handling Tests
     // =========================================================================
 
+    #[test]
+    fn test_agent_worktree_main_root() {
+        let tmp = TempDir::new().unwrap();
+        let main = tmp.path().join("repo");
+        let worktree = tmp.path().join("repo-worktrees").join("MOB-1");
+        fs::create_dir_all(main.join(".git").join("worktrees").join("MOB-1")).unwrap();
+        fs::create_dir_all(main.join(".mobius")).unwrap();
+        fs::create_dir_all(&worktree).unwrap();
+        fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", main.join(".git/worktrees/MOB-1").display()),
+        )
+        .unwrap();
+
+        // A plain linked worktree is left alone
+        assert_eq!(agent_worktree_main_root(&worktree), None);
+        assert_eq!(linked_worktree_main_root(&worktree), Some(main.clone()));
+
+        // Mirrored issue context marks it as a mobius agent worktree
+        fs::create_dir_all(worktree.join(".mobius").join("issues")).unwrap();
+        assert_eq!(agent_worktree_main_root(&worktree), Some(main.clone()));
+
+        // Main checkouts and submodules aren't worktrees
+        assert_eq!(agent_worktree_main_root(&main), None);
+        let submodule = main.join("vendor");
+        fs::create_dir_all(submodule.join(".mobius").join("issues")).unwrap();
+        fs::write(submodule.join(".git"), "gitdir: ../.git/modules/vendor\n").unwrap();
+        assert_eq!(agent_worktree_main_root(&submodule), None);
+    }
+
     #[test]
     fn test_write_subtask_spec_creates_tasks_dir() {
         // write_subtask_spec should auto-create the tasks/ directory via ensure_issue_dir
@@ -1092,6 +1669,10 @@ mod tests {
             blocked_by: vec![],
             blocks: vec![],
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            task_type: TaskType::Agent,
+            comments: Vec::new(),
         };
         atomic_write_json(&file_path, &task).unwrap();
 
@@ -1119,6 +1700,14 @@ mod tests {
             error: None,
             files_modified: None,
             commit_hash: None,
+            retry_count: None,
+            failure_class: None,
+            model: None,
+            duration_ms: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+            verify: None,
         }];
 
         atomic_write_json(&file_path, &entries).unwrap();
@@ -1173,6 +1762,10 @@ mod tests {
             git_branch_name: String::new(),
             relations: None,
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         };
 
         let issue_b = LinearIssue {
@@ -1183,6 +1776,10 @@ mod tests {
             git_branch_name: String::new(),
             relations: None,
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         };
 
         // Insert first
@@ -1213,6 +1810,10 @@ mod tests {
             git_branch_name: String::new(),
             relations: None,
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         };
 
         let in_progress = LinearIssue {
@@ -1223,6 +1824,10 @@ mod tests {
             git_branch_name: String::new(),
             relations: None,
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         };
 
         by_id.insert(ready.id.clone(), ready);
@@ -1256,6 +1861,10 @@ mod tests {
             git_branch_name: String::new(),
             relations: None,
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         };
 
         let pending = LinearIssue {
@@ -1266,6 +1875,10 @@ mod tests {
             git_branch_name: String::new(),
             relations: None,
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         };
 
         by_id.insert(done.id.clone(), done);
@@ -1312,6 +1925,14 @@ mod tests {
             error: None,
             files_modified: Some(vec!["src/main.rs".to_string()]),
             commit_hash: Some("abc1234".to_string()),
+            retry_count: None,
+            failure_class: None,
+            model: None,
+            duration_ms: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+            verify: None,
         };
 
         let entries = vec![entry];
@@ -1345,6 +1966,14 @@ mod tests {
             error: None,
             files_modified: None,
             commit_hash: None,
+            retry_count: None,
+            failure_class: None,
+            model: None,
+            duration_ms: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+            verify: None,
         };
 
         let entries = vec![entry1];
@@ -1363,6 +1992,14 @@ mod tests {
             error: Some("Test assertion failed".to_string()),
             files_modified: None,
             commit_hash: None,
+            retry_count: None,
+            failure_class: None,
+            model: None,
+            duration_ms: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+            verify: None,
         };
 
         existing.push(entry2);
@@ -1420,6 +2057,14 @@ mod tests {
             error: None,
             files_modified: None,
             commit_hash: None,
+            retry_count: None,
+            failure_class: None,
+            model: None,
+            duration_ms: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+            verify: None,
         };
 
         let entries = vec![entry];
@@ -1473,6 +2118,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_allocate_local_id_sequential() {
+        let tmp = setup_test_dir();
+        let issues = tmp.path().join("issues");
+        fs::create_dir_all(issues.join("LOC-004")).unwrap();
+
+        assert_eq!(allocate_local_id(&issues).unwrap(), "LOC-005");
+        assert_eq!(allocate_local_id(&issues).unwrap(), "LOC-006");
+        // The lock is released once the counter is written
+        assert!(
+            crate::context::with_file_lock(&issues.join("counter.json.lock"), "test", || Ok(()))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_concurrent_allocate_local_id_no_collisions() {
+        use std::collections::HashSet;
+        use std::sync::{Arc, Barrier};
+        use std::thread;
+
+        let tmp = setup_test_dir();
+        let issues = tmp.path().join("issues");
+        fs::create_dir_all(&issues).unwrap();
+
+        let threads = 8;
+        let per_thread = 5;
+        let barrier = Arc::new(Barrier::new(threads));
+        let issues_path_shared = Arc::new(issues.clone());
+        let mut handles = vec![];
+
+        for _ in 0..threads {
+            let barrier = Arc::clone(&barrier);
+            let issues_path = Arc::clone(&issues_path_shared);
+
+            handles.push(thread::spawn(move || {
+                barrier.wait();
+                (0..per_thread)
+                    .map(|_| allocate_local_id(&issues_path).unwrap())
+                    .collect::<Vec<_>>()
+            }));
+        }
+
+        let ids: Vec<String> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .collect();
+        let unique: HashSet<&String> = ids.iter().collect();
+
+        assert_eq!(ids.len(), threads * per_thread);
+        assert_eq!(unique.len(), ids.len(), "Allocated IDs must be unique");
+        assert!(unique.contains(&"LOC-001".to_string()));
+        assert!(unique.contains(&format!("LOC-{:03}", threads * per_thread)));
+    }
+
     #[test]
     fn test_counter_race_with_scan_fallback() {
         // When no counter.json exists and multiple threads scan concurrently,
@@ -1508,4 +2208,64 @@ mod tests {
             assert_eq!(*result, 1, "Empty dir scan should return 1 for all threads");
         }
     }
+
+    #[test]
+    fn test_concurrent_iteration_log_writers_keep_all_entries() {
+        use std::sync::{Arc, Barrier};
+        use std::thread;
+
+        let tmp = setup_test_dir();
+        let file_path = Arc::new(tmp.path().join("iterations.json"));
+        let threads = 8;
+        let per_thread = 5;
+        let barrier = Arc::new(Barrier::new(threads));
+
+        let handles: Vec<_> = (0..threads)
+            .map(|t| {
+                let barrier = Arc::clone(&barrier);
+                let file_path = Arc::clone(&file_path);
+                thread::spawn(move || {
+                    barrier.wait();
+                    for attempt in 0..per_thread {
+                        let entry = IterationLogEntry {
+                            subtask_id: format!("MOB-{}", t),
+                            attempt,
+                            started_at: "2026-01-28T14:30:00Z".to_string(),
+                            completed_at: None,
+                            status: IterationStatus::Success,
+                            error: None,
+                            files_modified: None,
+                            commit_hash: None,
+                            retry_count: None,
+                            failure_class: None,
+                            model: None,
+                            duration_ms: None,
+                            cost_usd: None,
+                            input_tokens: None,
+                            output_tokens: None,
+                            verify: None,
+                        };
+                        with_json_array_sync(&file_path, "iteration log", |mut entries| {
+                            entries.push(entry);
+                            entries
+                        })
+                        .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let content = fs::read_to_string(file_path.as_path()).unwrap();
+        let entries: Vec<IterationLogEntry> = serde_json::from_str(&content).unwrap();
+        assert_eq!(entries.len(), threads * per_thread as usize);
+        assert!(crate::context::with_file_lock(
+            &file_path.with_extension("json.lock"),
+            "test",
+            || Ok(())
+        )
+        .is_ok());
+    }
 }
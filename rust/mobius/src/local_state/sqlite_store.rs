@@ -0,0 +1,261 @@
+//! SQLite-backed [`StateStore`] (`state_store: sqlite`)
+//!
+//! One `.mobius/state.db` replaces `counter.json`, the per-issue
+//! `iterations.json` files, and directory scans for the issue list. SQLite's
+//! own locking makes counter increments and log appends transactional across
+//! processes, and the iteration columns can be queried directly.
+//!
+//! A new database imports whatever the file store already holds, so switching
+//! an existing project over keeps its IDs and history.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, TransactionBehavior};
+
+use super::{scan_for_next_id, Counter, FileStateStore, IterationLogEntry, StateStore};
+
+/// Bumped when the schema changes; 0 means a database that was just created
+const SCHEMA_VERSION: i32 = 1;
+
+/// How long a writer waits for another process's transaction
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const LOCAL_ID_COUNTER: &str = "local_id";
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS counters (
+        name TEXT PRIMARY KEY,
+        value INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS issues (
+        id TEXT PRIMARY KEY
+    );
+    CREATE TABLE IF NOT EXISTS iterations (
+        seq INTEGER PRIMARY KEY AUTOINCREMENT,
+        issue_id TEXT NOT NULL,
+        subtask_id TEXT NOT NULL,
+        started_at TEXT NOT NULL,
+        status TEXT NOT NULL,
+        entry TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS iterations_by_issue ON iterations (issue_id, seq);
+";
+
+pub struct SqliteStateStore {
+    conn: Mutex<Connection>,
+    issues_path: PathBuf,
+}
+
+impl SqliteStateStore {
+    /// Open (or create) the database at `db_path`, importing the file store
+    /// under `issues_path` on first use.
+    pub fn open(db_path: &Path, issues_path: &Path) -> Result<Self> {
+        if let Some(parent) = db_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut conn = Connection::open(db_path)
+            .with_context(|| format!("Failed to open {}", db_path.display()))?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let version: i32 = tx.pragma_query_value(None, "user_version", |row| row.get(0))?;
+        if version == 0 {
+            tx.execute_batch(SCHEMA)?;
+            import_file_store(&tx, issues_path)?;
+            tx.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        }
+        tx.commit()?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            issues_path: issues_path.to_path_buf(),
+        })
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Copy the counter, issue directories and iteration logs into a new database.
+fn import_file_store(tx: &rusqlite::Transaction<'_>, issues_path: &Path) -> Result<()> {
+    let next = fs::read_to_string(issues_path.join("counter.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Counter>(&content).ok())
+        .map(|counter| counter.next)
+        .filter(|next| *next > 0)
+        .unwrap_or_else(|| scan_for_next_id(issues_path));
+    tx.execute(
+        "INSERT OR REPLACE INTO counters (name, value) VALUES (?1, ?2)",
+        params![LOCAL_ID_COUNTER, next],
+    )?;
+
+    let files = FileStateStore::new(issues_path.to_path_buf());
+    for issue_id in files.list_issue_ids() {
+        tx.execute(
+            "INSERT OR IGNORE INTO issues (id) VALUES (?1)",
+            params![issue_id],
+        )?;
+        for entry in files.read_iteration_log(&issue_id) {
+            insert_iteration(tx, &issue_id, &entry)?;
+        }
+    }
+    Ok(())
+}
+
+fn insert_iteration(conn: &Connection, issue_id: &str, entry: &IterationLogEntry) -> Result<()> {
+    let status = serde_json::to_value(&entry.status)?;
+    conn.execute(
+        "INSERT INTO iterations (issue_id, subtask_id, started_at, status, entry)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            issue_id,
+            entry.subtask_id,
+            entry.started_at,
+            status.as_str().unwrap_or_default(),
+            serde_json::to_string(entry)?,
+        ],
+    )?;
+    Ok(())
+}
+
+impl StateStore for SqliteStateStore {
+    fn next_local_id(&self) -> Result<String> {
+        let mut conn = self.conn();
+        let tx = conn.transaction_with_behavior(TransactionBehavior::Immediate)?;
+        let stored: Option<u32> = tx
+            .query_row(
+                "SELECT value FROM counters WHERE name = ?1",
+                params![LOCAL_ID_COUNTER],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let next = stored
+            .filter(|next| *next > 0)
+            .unwrap_or_else(|| scan_for_next_id(&self.issues_path));
+        tx.execute(
+            "INSERT OR REPLACE INTO counters (name, value) VALUES (?1, ?2)",
+            params![LOCAL_ID_COUNTER, next + 1],
+        )?;
+        tx.commit()?;
+        Ok(format!("LOC-{:03}", next))
+    }
+
+    fn read_iteration_log(&self, issue_id: &str) -> Vec<IterationLogEntry> {
+        let conn = self.conn();
+        let Ok(mut stmt) =
+            conn.prepare("SELECT entry FROM iterations WHERE issue_id = ?1 ORDER BY seq")
+        else {
+            return Vec::new();
+        };
+        stmt.query_map(params![issue_id], |row| row.get::<_, String>(0))
+            .map(|rows| {
+                rows.flatten()
+                    .filter_map(|json| serde_json::from_str(&json).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn append_iteration_log(&self, issue_id: &str, entry: &IterationLogEntry) -> Result<()> {
+        insert_iteration(&self.conn(), issue_id, entry)
+    }
+
+    fn register_issue(&self, issue_id: &str) -> Result<()> {
+        self.conn().execute(
+            "INSERT OR IGNORE INTO issues (id) VALUES (?1)",
+            params![issue_id],
+        )?;
+        Ok(())
+    }
+
+    fn forget_issue(&self, issue_id: &str) -> Result<()> {
+        let mut conn = self.conn();
+        let tx = conn.transaction()?;
+        tx.execute(
+            "DELETE FROM iterations WHERE issue_id = ?1",
+            params![issue_id],
+        )?;
+        tx.execute("DELETE FROM issues WHERE id = ?1", params![issue_id])?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    fn list_issue_ids(&self) -> Vec<String> {
+        let conn = self.conn();
+        let Ok(mut stmt) = conn.prepare("SELECT id FROM issues ORDER BY id") else {
+            return Vec::new();
+        };
+        stmt.query_map([], |row| row.get::<_, String>(0))
+            .map(|rows| rows.flatten().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::local_state::IterationStatus;
+
+    fn entry(subtask_id: &str) -> IterationLogEntry {
+        IterationLogEntry {
+            subtask_id: subtask_id.to_string(),
+            attempt: 1,
+            started_at: "2026-10-16T10:00:00Z".to_string(),
+            completed_at: None,
+            status: IterationStatus::Success,
+            error: None,
+            files_modified: None,
+            commit_hash: None,
+            retry_count: None,
+            failure_class: None,
+            model: None,
+            duration_ms: None,
+            cost_usd: None,
+            input_tokens: None,
+            output_tokens: None,
+            verify: None,
+        }
+    }
+
+    #[test]
+    fn test_sqlite_store_imports_file_state_and_persists() {
+        let dir = tempfile::tempdir().unwrap();
+        let issues = dir.path().join("issues");
+        let files = FileStateStore::new(issues.clone());
+        fs::create_dir_all(issues.join("LOC-004").join("execution")).unwrap();
+        files
+            .append_iteration_log("LOC-004", &entry("LOC-004-1"))
+            .unwrap();
+
+        let db = dir.path().join("state.db");
+        let store = SqliteStateStore::open(&db, &issues).unwrap();
+        assert_eq!(store.list_issue_ids(), vec!["LOC-004"]);
+        assert_eq!(store.read_iteration_log("LOC-004").len(), 1);
+        assert_eq!(store.next_local_id().unwrap(), "LOC-005");
+
+        store.register_issue("LOC-005").unwrap();
+        store
+            .append_iteration_log("LOC-005", &entry("LOC-005-1"))
+            .unwrap();
+        drop(store);
+
+        // Reopening doesn't import again
+        let store = SqliteStateStore::open(&db, &issues).unwrap();
+        assert_eq!(store.next_local_id().unwrap(), "LOC-006");
+        assert_eq!(store.read_iteration_log("LOC-004").len(), 1);
+        assert_eq!(
+            store.read_iteration_log("LOC-005")[0].subtask_id,
+            "LOC-005-1"
+        );
+
+        store.forget_issue("LOC-004").unwrap();
+        assert_eq!(store.list_issue_ids(), vec!["LOC-005"]);
+        assert!(store.read_iteration_log("LOC-004").is_empty());
+    }
+}
@@ -25,11 +25,12 @@ use crate::stream_json;
 use crate::tmux;
 use crate::tracker;
 use crate::tree_renderer;
+use crate::types::config::StatusMapping;
 use crate::types::context::{RuntimeActiveTask, RuntimeState};
 use crate::types::enums::{AgentRuntime, Backend, Model, SessionStatus, TaskStatus};
 use crate::types::task_graph::{
-    build_task_graph, get_blocked_tasks, get_graph_stats, get_ready_tasks, get_verification_task,
-    update_task_status, TaskGraph,
+    build_task_graph_with_mapping, get_blocked_tasks, get_graph_stats, get_ready_tasks,
+    get_verification_task, update_task_status, TaskGraph,
 };
 use crate::worktree::{self, WorktreeConfig};
 
@@ -89,7 +90,7 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
         exec_config.max_parallel_agents = Some(p);
     }
     if let Some(ref m) = options.model {
-        if loop_config.runtime == AgentRuntime::Opencode {
+        if loop_config.runtime != AgentRuntime::Claude {
             exec_config.model = m.trim().to_string();
         } else {
             exec_config.model = Model::from_str(m)
@@ -104,7 +105,7 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
         exec_config.sandbox = false;
     }
 
-    let execution_model_override = if loop_config.runtime == AgentRuntime::Opencode {
+    let execution_model_override = if loop_config.runtime != AgentRuntime::Claude {
         options.model.as_deref()
     } else {
         None
@@ -205,6 +206,7 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
         worktree_path: exec_config.worktree_path.clone(),
         base_branch: exec_config.base_branch.clone(),
         runtime: loop_config.runtime,
+        checkout: exec_config.checkout.clone(),
     };
     let worktree_info = worktree::create_worktree(&task_id, &branch_name, &wt_config).await?;
 
@@ -227,7 +229,7 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
     let worktree_path = worktree_info.path.to_string_lossy().to_string();
 
     // Create output directory for capturing raw stream-json output (token extraction)
-    let output_dir: PathBuf = std::env::temp_dir().join("mobius").join(&task_id);
+    let output_dir: PathBuf = crate::stream_json::agent_output_dir(&task_id);
     if let Err(e) = std::fs::create_dir_all(&output_dir) {
         eprintln!(
             "{}",
@@ -235,6 +237,16 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
         );
     }
 
+    // Agents report completion through per-task result files in this directory
+    let result_dir = context::get_results_directory_path(&task_id);
+    if let Err(e) = std::fs::create_dir_all(&result_dir) {
+        eprintln!(
+            "{}",
+            format!("Warning: Could not create result dir, falling back to output capture: {e}")
+                .yellow()
+        );
+    }
+
     // -----------------------------------------------------------------------
     // 9. Create tmux session
     // -----------------------------------------------------------------------
@@ -270,7 +282,9 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
         )
         .dimmed()
     );
-    let mut graph = build_task_graph(&parent_id, &parent_identifier, &issues);
+    let status_mapping = loop_config.status_mapping_for(backend);
+    let mut graph =
+        build_task_graph_with_mapping(&parent_id, &parent_identifier, &issues, status_mapping);
 
     // -----------------------------------------------------------------------
     // 11. Generate context for skills
@@ -322,7 +336,7 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
     // Pre-populate completed tasks from graph
     for task in graph.tasks.values() {
         if task.status == TaskStatus::Done {
-            runtime_state = context::complete_runtime_task(&runtime_state, &task.identifier);
+            runtime_state = context::complete_runtime_task(&runtime_state, &task.identifier, None);
         }
     }
     // Write the pre-populated state
@@ -331,10 +345,7 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
     // -----------------------------------------------------------------------
     // 14. Create execution tracker
     // -----------------------------------------------------------------------
-    let mut tracker = tracker::create_tracker(
-        exec_config.max_retries,
-        exec_config.verification_timeout.map(|v| v as u64),
-    );
+    let mut tracker = tracker::create_tracker_from_config(&exec_config);
 
     // -----------------------------------------------------------------------
     // MAIN LOOP
@@ -355,7 +366,13 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
             iteration += 1;
 
             // Re-sync graph from local state
-            graph = sync_graph_from_local(&graph, &parent_id, &parent_identifier, &task_id);
+            graph = sync_graph_from_local(
+                &graph,
+                &parent_id,
+                &parent_identifier,
+                &task_id,
+                status_mapping,
+            );
 
             // Check if verification task is complete
             if let Some(vt) = get_verification_task(&graph) {
@@ -464,6 +481,8 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
                         }),
                         input_tokens: None,
                         output_tokens: None,
+                        retry_count: None,
+                        worker: None,
                     },
                 );
             }
@@ -522,6 +541,7 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
                 model_override: execution_model_override,
                 thinking_level_override: None,
                 output_dir: Some(&output_dir),
+                result_dir: result_dir.is_dir().then_some(result_dir.as_path()),
             };
             let results = executor::execute_parallel(
                 &tasks_to_execute,
@@ -630,7 +650,7 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
                     // Mark done
                     graph = update_task_status(&graph, &result.task_id, TaskStatus::Done);
                     runtime_state =
-                        context::complete_runtime_task(&runtime_state, &result.identifier);
+                        context::complete_runtime_task(&runtime_state, &result.identifier, None);
                     local_state::update_subtask_status(&task_id, &result.identifier, "done");
                     println!(
                         "{}",
@@ -742,6 +762,14 @@ pub async fn run_loop(options: LoopOptions) -> Result<()> {
                     error: result.error.clone(),
                     files_modified: None,
                     commit_hash: None,
+                    retry_count: Some(result.attempts.saturating_sub(1)),
+                    failure_class: None,
+                    model: None,
+                    duration_ms: None,
+                    cost_usd: None,
+                    input_tokens: None,
+                    output_tokens: None,
+                    verify: None,
                 };
                 if let Err(e) = local_state::write_iteration_log(&task_id, entry) {
                     eprintln!(
@@ -934,6 +962,9 @@ async fn update_live_tokens_loop(
                         backend_statuses: None,
                         total_input_tokens: None,
                         total_output_tokens: None,
+                        task_costs_usd: Default::default(),
+                        total_cost_usd: None,
+                        cancelled_tasks: vec![],
                     }
                 }
             };
@@ -972,12 +1003,13 @@ fn sync_graph_from_local(
     parent_id: &str,
     parent_identifier: &str,
     task_id: &str,
+    status_mapping: Option<&StatusMapping>,
 ) -> TaskGraph {
     let local_issues = local_state::read_local_subtasks_as_linear_issues(task_id);
     if local_issues.is_empty() {
         return graph.clone();
     }
-    build_task_graph(parent_id, parent_identifier, &local_issues)
+    build_task_graph_with_mapping(parent_id, parent_identifier, &local_issues, status_mapping)
 }
 
 /// Format elapsed milliseconds as a human-readable string.
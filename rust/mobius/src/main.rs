@@ -1,27 +1,66 @@
+pub mod agent_pool;
+pub mod attribution;
+pub mod bisect;
+pub mod bootstrap;
+pub mod build_cache;
+pub mod checkpoint;
+pub mod churn;
 pub mod commands;
+pub mod completion;
 pub mod config;
 pub mod context;
+pub mod cost;
 pub mod debug_logger;
+pub mod error;
 pub mod executor;
+pub mod external_deps;
+pub mod failure;
+pub mod field_mapping;
 pub mod git_lock;
+pub mod github;
+pub mod gitlab;
+pub mod http_client;
+pub mod integration;
 pub mod jira;
 pub mod linear;
+pub mod linear_webhook;
 pub mod local_state;
 pub mod loop_command;
 pub mod mermaid_renderer;
+pub mod notifications;
+pub mod offline;
+pub mod output_archive;
 pub mod output_parser;
+pub mod pr_template;
+pub mod preflight;
+pub mod primer;
+pub mod process_executor;
 pub mod project_detector;
+pub mod report;
+pub mod resource_limits;
 pub mod runtime_adapter;
+pub mod sandbox;
+pub mod search_index;
+pub mod secrets;
+pub mod shutdown;
+pub mod stack;
 pub mod status_sync;
 pub mod stream_json;
+pub mod subtask_batch;
+pub mod subtask_sync;
+pub mod telemetry;
 pub mod tmux;
 pub mod tracker;
 pub mod tree_renderer;
 pub mod tui;
 pub mod types;
+pub mod verify;
+pub mod workspace;
 pub mod worktree;
 
 use clap::{Parser, Subcommand};
+use commands::output::OutputFormat;
+use types::enums::DebugVerbosity;
 
 #[derive(Parser)]
 #[command(
@@ -43,7 +82,7 @@ struct Cli {
     #[arg(short, long, hide = true)]
     local: bool,
 
-    /// Backend: linear, jira, or local
+    /// Backend: linear, jira, github, gitlab, or local
     #[arg(short, long)]
     backend: Option<String>,
 
@@ -87,6 +126,22 @@ struct Cli {
     #[arg(long)]
     no_submit: bool,
 
+    /// Start without checking tools, the working tree, base branch and API tokens
+    #[arg(long)]
+    skip_preflight: bool,
+
+    /// Output format for command results: text or json
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+
+    /// Config profile to apply (an entry under `profiles` in the config file)
+    #[arg(long, global = true, env = "MOBIUS_PROFILE")]
+    profile: Option<String>,
+
+    /// Never contact the issue tracker: use cached context and queue updates for a later push
+    #[arg(long, global = true)]
+    offline: bool,
+
     #[command(subcommand)]
     command: Option<Command>,
 }
@@ -112,45 +167,273 @@ enum Command {
     Shortcuts,
 
     /// Check system requirements and configuration
-    Doctor,
+    Doctor {
+        /// Repair local state problems (shortcuts, .gitignore, locks, runtime state, counter, session)
+        #[arg(long)]
+        fix: bool,
+    },
 
     /// Show current configuration
     Config {
         /// Open config in editor
         #[arg(short, long)]
         edit: bool,
+
+        /// List every effective setting with the file, profile or variable it came from
+        #[arg(long, conflicts_with = "edit")]
+        show_origin: bool,
     },
 
     /// List all local issues with their status
     List {
-        /// Backend: linear, jira, or local
+        /// Backend: linear, jira, github, gitlab, or local
         #[arg(short, long)]
         backend: Option<String>,
     },
 
+    /// Search local issue and sub-task titles, descriptions and comments
+    Search {
+        /// Words to search for; each also matches longer words it starts
+        #[arg(required = true, num_args = 1..)]
+        query: Vec<String>,
+
+        /// Maximum number of results
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+
     /// Remove completed issues from local .mobius/issues/ directory
     Clean {
         /// Preview what would be cleaned without deleting
         #[arg(long)]
         dry_run: bool,
 
-        /// Backend: linear, jira, or local
+        /// Backend: linear, jira, github, gitlab, or local
         #[arg(short, long)]
         backend: Option<String>,
     },
 
+    /// Remove worktrees and branches of done, cancelled or deleted issues
+    Gc {
+        /// Preview what would be removed without deleting
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also remove worktrees with uncommitted changes and unmerged branches
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Display sub-task dependency tree without execution
     Tree {
         /// Task ID
         task_id: String,
 
-        /// Backend: linear, jira, or local
+        /// Backend: linear, jira, github, gitlab, or local
         #[arg(short, long)]
         backend: Option<String>,
 
         /// Also output Mermaid diagram
         #[arg(short, long)]
         mermaid: bool,
+
+        /// With --mermaid, add a gantt chart of the attempts in the iteration log
+        #[arg(long, requires = "mermaid")]
+        timeline: bool,
+
+        /// Weight the critical path by complexity scoring
+        #[arg(long)]
+        weighted: bool,
+    },
+
+    /// Show files touched by multiple sub-tasks in a loop run
+    Churn {
+        /// Task ID
+        task_id: String,
+
+        /// Maximum files to show in the heatmap
+        #[arg(short, long)]
+        limit: Option<usize>,
+    },
+
+    /// Regenerate the HTML run report (graph, timeline, usage, failures, commits)
+    Report {
+        /// Task ID
+        task_id: String,
+
+        /// Open the report in the default browser
+        #[arg(long)]
+        open: bool,
+    },
+
+    /// Find the sub-task whose commits broke the integrated branch
+    Bisect {
+        /// Task ID
+        task_id: String,
+
+        /// Verify command to bisect with (default: detected test command)
+        #[arg(short, long)]
+        command: Option<String>,
+
+        /// Base branch (default: execution.base_branch)
+        #[arg(long)]
+        base: Option<String>,
+    },
+
+    /// Merge completed sub-task branches into an integration branch, verifying each merge
+    Integrate {
+        /// Task ID
+        task_id: String,
+
+        /// Integration branch (default: integration/<task-id>)
+        #[arg(short, long)]
+        branch: Option<String>,
+
+        /// Base branch (default: execution.base_branch)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Run this after each merge instead of the merged sub-tasks' verify commands
+        #[arg(short, long)]
+        command: Option<String>,
+    },
+
+    /// List or show the agent output captured for a task
+    Logs {
+        /// Task ID
+        task_id: String,
+
+        /// Sub-task identifier (omit to list available logs)
+        subtask: Option<String>,
+
+        /// Keep printing new output until the agent finishes
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Print the untouched stream-json lines
+        #[arg(long)]
+        raw: bool,
+    },
+
+    /// Summarize attempt outcomes, cost, and trends from iteration logs
+    Stats {
+        /// Task ID (defaults to all local issues)
+        task_id: Option<String>,
+
+        /// Break failures down by class and day
+        #[arg(long)]
+        failures: bool,
+
+        /// Show attempts, success rate, cost, and median duration by week
+        #[arg(long)]
+        trends: bool,
+
+        /// Print per sub-task attempts, tokens, duration, and cost as CSV
+        #[arg(long)]
+        csv: bool,
+    },
+
+    /// Stop a running loop: interrupt its agents, release git locks, end the session
+    Cancel {
+        /// Task ID (defaults to the current task)
+        task_id: Option<String>,
+    },
+
+    /// Take, show or break the git lock agents take turns on in a worktree
+    Lock {
+        #[command(subcommand)]
+        action: LockAction,
+    },
+
+    /// Let in-flight agents finish, then hold the loop before the next batch
+    Pause {
+        /// Task ID (defaults to the current task)
+        task_id: Option<String>,
+    },
+
+    /// Continue a loop held by `mobius pause`
+    Unpause {
+        /// Task ID (defaults to the current task)
+        task_id: Option<String>,
+    },
+
+    /// Summarize a loop's agents, progress, and pending sync without the TUI
+    Status {
+        /// Task ID (defaults to the current task)
+        task_id: Option<String>,
+    },
+
+    /// Print a shell completion script (bash, zsh, fish, powershell, elvish)
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+
+    /// List local issue IDs for shell completion
+    #[command(name = "__task-ids", hide = true)]
+    TaskIds,
+
+    /// List or resolve external URL blockers (other repos' PRs, vendor tickets) and edit sub-task relations
+    #[command(visible_alias = "dep")]
+    Deps {
+        #[command(subcommand)]
+        action: DepsAction,
+    },
+
+    /// Edit sub-task specs
+    Task {
+        #[command(subcommand)]
+        action: TaskAction,
+    },
+
+    /// Inspect the structured debug log recorded with `--debug`
+    Debug {
+        #[command(subcommand)]
+        action: DebugAction,
+    },
+
+    /// Inspect the configured issue tracker
+    Backend {
+        #[command(subcommand)]
+        action: BackendAction,
+    },
+
+    /// Store tracker tokens in the OS keychain instead of environment variables
+    Auth {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+
+    /// Show the cached repository primer embedded in agent context
+    Primer {
+        /// Regenerate even if no key files changed
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Run sub-task verify commands in the loop's worktree and log the results
+    Verify {
+        /// Parent task ID (defaults to the current task)
+        task_id: Option<String>,
+
+        /// Only this sub-task's verify command
+        subtask: Option<String>,
+
+        /// Directory to run in (defaults to the loop's worktree)
+        #[arg(long)]
+        path: Option<String>,
+    },
+
+    /// Print the test command scoped to the workspace packages changed on this branch
+    VerifyScope {
+        /// Branch to diff against (defaults to execution.base_branch)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Repository or worktree root
+        #[arg(long, default_value = ".")]
+        path: String,
     },
 
     /// Execute sub-tasks sequentially (use "loop" for parallel execution)
@@ -169,7 +452,7 @@ enum Command {
         #[arg(short, long, hide = true)]
         local: bool,
 
-        /// Backend: linear, jira, or local
+        /// Backend: linear, jira, github, gitlab, or local
         #[arg(short, long)]
         backend: Option<String>,
 
@@ -199,7 +482,7 @@ enum Command {
         #[arg(short, long, hide = true)]
         local: bool,
 
-        /// Backend: linear, jira, or local
+        /// Backend: linear, jira, github, gitlab, or local
         #[arg(short, long)]
         backend: Option<String>,
 
@@ -234,6 +517,181 @@ enum Command {
         /// Disable TUI dashboard (use plain text output)
         #[arg(long)]
         no_tui: bool,
+
+        /// Break dependency cycles instead of refusing to start
+        #[arg(long)]
+        break_cycles: bool,
+
+        /// Print the batches the scheduler would dispatch, without spawning agents
+        #[arg(long)]
+        plan: bool,
+
+        /// Start without checking tools, the working tree, base branch and API tokens
+        #[arg(long)]
+        skip_preflight: bool,
+    },
+
+    /// Resume an interrupted loop from its last checkpoint
+    Resume {
+        /// Task ID
+        task_id: String,
+
+        /// Backend: linear, jira, github, gitlab, or local
+        #[arg(short, long)]
+        backend: Option<String>,
+
+        /// Model profile or runtime model ID (e.g., opus or openai/gpt-5.3-codex)
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Thinking level for OpenCode (minimal, low, medium, high, max, xhigh)
+        #[arg(long, value_name = "LEVEL")]
+        thinking_level: Option<String>,
+
+        /// Max parallel agents (overrides config)
+        #[arg(short, long)]
+        parallel: Option<u32>,
+
+        /// Maximum iterations
+        #[arg(short = 'n', long)]
+        max_iterations: Option<u32>,
+
+        /// Skip automatic PR submission after successful completion
+        #[arg(long)]
+        no_submit: bool,
+
+        /// Disable TUI dashboard (use plain text output)
+        #[arg(long)]
+        no_tui: bool,
+
+        /// Break dependency cycles instead of refusing to start
+        #[arg(long)]
+        break_cycles: bool,
+
+        /// Start without checking tools, the working tree, base branch and API tokens
+        #[arg(long)]
+        skip_preflight: bool,
+    },
+
+    /// Join a loop's agent pool and run the tasks it hands to this machine
+    Worker {
+        /// Coordinator address (the loop's `pool.listen`), e.g. build-host:7420
+        #[arg(long, value_name = "ADDR")]
+        join: String,
+
+        /// Name shown in the loop's output (defaults to $HOSTNAME)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Agents to run at once (defaults to execution.max_parallel_agents)
+        #[arg(long)]
+        capacity: Option<usize>,
+
+        /// Shared secret matching the loop's `pool.token`
+        #[arg(long, env = "MOBIUS_POOL_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+    },
+
+    /// Receive Linear webhooks and apply status changes, new sub-issues and comments locally
+    Listen {
+        /// Port to listen on
+        #[arg(long, default_value_t = 7421)]
+        port: u16,
+
+        /// Address to bind; anything but loopback requires --secret
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+
+        /// Signing secret shown when the webhook was created in Linear
+        #[arg(long, env = "LINEAR_WEBHOOK_SECRET", hide_env_values = true)]
+        secret: Option<String>,
+    },
+
+    /// Serve a web dashboard and JSON API for task graphs, loop state, agent logs and loop control
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+
+        /// Address to bind; use 0.0.0.0 to share with your team
+        #[arg(long, default_value = "127.0.0.1")]
+        bind: String,
+
+        /// Bearer token API requests must present
+        #[arg(long, env = "MOBIUS_SERVE_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+
+        /// Serve read-only: no start, cancel, pause or unpause routes
+        #[arg(long)]
+        observe: bool,
+    },
+
+    /// Create a parent issue locally or on Linear/Jira and scaffold its local state
+    New {
+        /// Issue title
+        title: String,
+
+        /// Issue description
+        #[arg(short, long, conflicts_with = "description_file")]
+        description: Option<String>,
+
+        /// Read the description from a file ('-' reads stdin; piped stdin is read by default)
+        #[arg(short = 'f', long)]
+        description_file: Option<std::path::PathBuf>,
+
+        /// Backend: linear, jira, or local
+        #[arg(short, long)]
+        backend: Option<String>,
+
+        /// Label to add (repeatable; defaults to the backend's default_labels)
+        #[arg(short, long = "label")]
+        labels: Vec<String>,
+
+        /// Open an interactive /refine session to break the issue into sub-tasks
+        #[arg(long)]
+        refine: bool,
+    },
+
+    /// Write an issue's parent spec and sub-tasks to a portable bundle
+    Export {
+        /// Task ID (defaults to the current task)
+        task_id: Option<String>,
+
+        /// Bundle file to write (prints to stdout when omitted)
+        #[arg(short = 'o', long)]
+        out: Option<std::path::PathBuf>,
+    },
+
+    /// Create an issue's local spec and sub-tasks from a bundle written by export
+    Import {
+        /// Bundle file
+        file: std::path::PathBuf,
+
+        /// Import under this ID (local bundles get the next LOC ID by default)
+        #[arg(long)]
+        id: Option<String>,
+
+        /// Replace an existing local issue with the same ID
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Have the agent runtime break a parent issue into sub-tasks and write them locally
+    Refine {
+        /// Parent issue ID (defaults to the current task)
+        task_id: Option<String>,
+
+        /// Backend: linear, jira, github, gitlab, or local
+        #[arg(short, long)]
+        backend: Option<String>,
+
+        /// Show the proposed sub-tasks without writing or queueing them
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Replace the issue's existing sub-tasks
+        #[arg(long)]
+        force: bool,
     },
 
     /// Create a pull request (auto-detects issue from branch name if not specified)
@@ -241,7 +699,7 @@ enum Command {
         /// Task ID
         task_id: Option<String>,
 
-        /// Backend: linear, jira, or local
+        /// Backend: linear, jira, github, gitlab, or local
         #[arg(short, long)]
         backend: Option<String>,
 
@@ -260,6 +718,48 @@ enum Command {
         /// Skip automatic status update to "In Review" after PR creation
         #[arg(long)]
         skip_status_update: bool,
+
+        /// One PR per completed sub-task, each stacked on its blocker's branch
+        #[arg(long, requires = "task_id")]
+        stacked: bool,
+
+        /// Request a review from this user (repeatable; replaces submit.reviewers)
+        #[arg(long = "reviewer", value_name = "USER")]
+        reviewers: Vec<String>,
+
+        /// Add this label to the PR (repeatable; added to submit.labels)
+        #[arg(long = "label", value_name = "LABEL")]
+        labels: Vec<String>,
+
+        /// Milestone to set on the PR (overrides submit.milestone)
+        #[arg(long, value_name = "TITLE")]
+        milestone: Option<String>,
+
+        /// Merge the PR automatically once checks pass (method: submit.merge_method)
+        #[arg(long, conflicts_with = "stacked")]
+        auto_merge: bool,
+    },
+
+    /// Show CI checks and review state of a parent's PR; moves the issue to Done once merged
+    PrStatus {
+        /// Task ID (defaults to the current task)
+        task_id: Option<String>,
+
+        /// Backend: linear, jira, github, gitlab, or local
+        #[arg(short, long)]
+        backend: Option<String>,
+
+        /// Keep polling until the PR is merged or closed
+        #[arg(short, long)]
+        watch: bool,
+
+        /// Seconds between polls with --watch
+        #[arg(long, default_value_t = 60, value_name = "SECS")]
+        interval: u64,
+
+        /// Don't move the issue to Done when the PR has merged
+        #[arg(long)]
+        skip_status_update: bool,
     },
 
     /// Push pending local changes to Linear/Jira
@@ -267,7 +767,7 @@ enum Command {
         /// Parent ID
         parent_id: Option<String>,
 
-        /// Backend: linear, jira, or local
+        /// Backend: linear, jira, github, gitlab, or local
         #[arg(short, long)]
         backend: Option<String>,
 
@@ -282,6 +782,18 @@ enum Command {
         /// Generate and push loop execution summary
         #[arg(long)]
         summary: bool,
+
+        /// Push local sub-task scoring to the mapped backend fields
+        #[arg(long)]
+        scoring: bool,
+
+        /// Review queued sub-tasks and create them as one batch, rolling back on failure
+        #[arg(long)]
+        review: bool,
+
+        /// Settle updates held back because the remote changed (keep local, keep remote, or merge)
+        #[arg(long)]
+        resolve: bool,
     },
 
     /// Fetch fresh context from Linear/Jira
@@ -289,9 +801,13 @@ enum Command {
         /// Task ID
         task_id: Option<String>,
 
-        /// Backend: linear, jira, or local
+        /// Backend: linear, jira, github, gitlab, or local
         #[arg(short, long)]
         backend: Option<String>,
+
+        /// Download every sub-task, not only those changed since the last pull
+        #[arg(long)]
+        full: bool,
     },
 
     /// Set or show the current task ID
@@ -299,7 +815,7 @@ enum Command {
         /// Task ID
         task_id: Option<String>,
 
-        /// Backend: linear, jira, or local
+        /// Backend: linear, jira, github, gitlab, or local
         #[arg(short, long)]
         backend: Option<String>,
 
@@ -311,7 +827,12 @@ enum Command {
     /// Launch interactive TUI dashboard for monitoring task execution
     Tui {
         /// Task ID
-        task_id: String,
+        #[arg(required_unless_present = "all")]
+        task_id: Option<String>,
+
+        /// Overview of every issue with a running loop, with drill-down
+        #[arg(long, conflicts_with_all = ["task_id", "replay", "state_dir"])]
+        all: bool,
 
         /// Hide the status legend
         #[arg(long)]
@@ -328,11 +849,203 @@ enum Command {
         /// Number of output lines per agent panel
         #[arg(long)]
         lines: Option<u32>,
+
+        /// Watch read-only: never lock, write state, or stop the loop
+        #[arg(long)]
+        observe: bool,
+
+        /// Step through the run's recorded runtime state revisions
+        #[arg(long)]
+        replay: bool,
+    },
+
+    /// Print one line per task start/finish until the loop ends (no TUI)
+    Watch {
+        /// Task ID
+        task_id: String,
     },
 }
 
+#[derive(Subcommand)]
+enum DepsAction {
+    /// Show external blockers still pending and those already resolved
+    List {
+        /// Parent issue ID
+        task_id: String,
+    },
+
+    /// Mark external blockers as resolved so blocked sub-tasks can start
+    Resolve {
+        /// Parent issue ID
+        task_id: String,
+
+        /// Blocker URLs to mark as resolved
+        urls: Vec<String>,
+
+        /// Resolve every pending external blocker
+        #[arg(long)]
+        all: bool,
+
+        /// Resolve linked GitHub pull requests that have been merged
+        #[arg(long)]
+        check: bool,
+    },
+
+    /// Mark human sub-tasks (`type: human`) as done so their dependents can start
+    Done {
+        /// Parent issue ID
+        task_id: String,
+
+        /// Sub-task identifiers to mark as done
+        #[arg(required = true)]
+        tasks: Vec<String>,
+    },
+
+    /// Make a sub-task blocked by a sibling sub-task or an external URL
+    Add {
+        /// Parent issue ID
+        task_id: String,
+
+        /// Sub-task to block
+        task: String,
+
+        /// Sub-task or URL that blocks it
+        blocker: String,
+    },
+
+    /// Remove a blocker from a sub-task
+    Remove {
+        /// Parent issue ID
+        task_id: String,
+
+        /// Sub-task to unblock
+        task: String,
+
+        /// Sub-task or URL to remove from its blockers
+        blocker: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TaskAction {
+    /// Open a sub-task spec in $EDITOR, validate it, and queue a description update
+    Edit {
+        /// Sub-task identifier
+        subtask: String,
+
+        /// Parent issue ID (defaults to the current task, then any local issue holding the sub-task)
+        #[arg(long)]
+        parent: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum DebugAction {
+    /// Print debug events for an issue, oldest first (rotated files included)
+    Dump {
+        /// Parent issue ID
+        task_id: String,
+
+        /// Only events of this type (e.g. state_drift, lock_contention, pane_capture)
+        #[arg(long = "type", value_name = "TYPE")]
+        event_type: Option<String>,
+
+        /// Only events for this sub-task
+        #[arg(long)]
+        task: Option<String>,
+
+        /// Show only the last N matching events
+        #[arg(long, value_name = "N")]
+        tail: Option<usize>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BackendAction {
+    /// List workflow states and map task statuses to them by ID
+    States {
+        /// Issue key whose available transitions to list (Jira)
+        issue: Option<String>,
+
+        /// Save the discovered mapping to status_mapping in the config
+        #[arg(long)]
+        write: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum AuthAction {
+    /// Prompt for a backend's token (or read it from stdin) and store it in the keychain
+    Login {
+        /// Backend: linear, jira, github, or gitlab
+        backend: String,
+    },
+
+    /// Show where each backend's token is read from
+    Status,
+
+    /// Remove a backend's credentials from the keychain
+    Logout {
+        /// Backend: linear, jira, github, or gitlab
+        backend: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum LockAction {
+    /// Run a git command while holding the lock, queued behind other agents
+    Run {
+        /// Worktree path (defaults to the current directory)
+        #[arg(long)]
+        path: Option<String>,
+
+        /// Task recorded as the holder (set for agents by the loop)
+        #[arg(long, env = "MOBIUS_TASK_ID")]
+        task: Option<String>,
+
+        /// Command and arguments, after `--`
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+
+    /// Show the lock holder and the queue of waiting agents
+    Status {
+        /// Worktree path (defaults to the current directory)
+        path: Option<String>,
+    },
+
+    /// Force-release the lock, e.g. when its holder is stuck
+    Release {
+        /// Worktree path (defaults to the current directory)
+        path: Option<String>,
+    },
+}
+
+/// `--debug` alone means normal verbosity.
+fn debug_verbosity(flag: Option<Option<String>>) -> anyhow::Result<Option<DebugVerbosity>> {
+    flag.map(|level| {
+        level.map_or(Ok(DebugVerbosity::Normal), |level| {
+            level.parse().map_err(anyhow::Error::msg)
+        })
+    })
+    .transpose()
+}
+
 fn main() {
     let cli = Cli::parse();
+    let output = cli.output;
+
+    // Every config read picks the profile up from the environment, including
+    // the loop's subprocesses
+    if let Some(profile) = &cli.profile {
+        std::env::set_var(config::loader::PROFILE_ENV, profile);
+        if let Err(e) = config::loader::read_config(&config::paths::resolve_paths().config_path) {
+            error::exit_with("Config", e.into(), output);
+        }
+    }
+    if cli.offline {
+        std::env::set_var(offline::OFFLINE_ENV, "1");
+    }
 
     match cli.command {
         Some(command) => match command {
@@ -342,48 +1055,268 @@ fn main() {
                 install,
             } => {
                 if let Err(e) = commands::setup::run(update_skills, update_shortcuts, install) {
-                    eprintln!("Setup error: {}", e);
-                    std::process::exit(1);
+                    error::exit_with("Setup", e, output);
                 }
             }
             Command::Shortcuts => {
                 if let Err(e) = commands::shortcuts::run() {
-                    eprintln!("Shortcuts error: {}", e);
-                    std::process::exit(1);
+                    error::exit_with("Shortcuts", e, output);
                 }
             }
-            Command::Doctor => {
-                if let Err(e) = commands::doctor::run() {
-                    eprintln!("Doctor error: {}", e);
-                    std::process::exit(1);
+            Command::Doctor { fix } => {
+                if let Err(e) = commands::doctor::run(output, fix) {
+                    error::exit_with("Doctor", e, output);
                 }
             }
-            Command::Config { edit } => {
-                if let Err(e) = commands::config::run(edit) {
-                    eprintln!("Config error: {}", e);
-                    std::process::exit(1);
+            Command::Config { edit, show_origin } => {
+                if let Err(e) = commands::config::run(edit, show_origin, output) {
+                    error::exit_with("Config", e, output);
                 }
             }
             Command::List { backend } => {
-                if let Err(e) = commands::list::run(backend.as_deref()) {
-                    eprintln!("List error: {}", e);
-                    std::process::exit(1);
+                if let Err(e) = commands::list::run(backend.as_deref(), output) {
+                    error::exit_with("List", e, output);
+                }
+            }
+            Command::Search { query, limit } => {
+                if let Err(e) = commands::search::run(&query.join(" "), limit, output) {
+                    error::exit_with("Search", e, output);
                 }
             }
             Command::Clean { dry_run, backend } => {
                 if let Err(e) = commands::clean::run(dry_run, backend.as_deref()) {
-                    eprintln!("Clean error: {}", e);
-                    std::process::exit(1);
+                    error::exit_with("Clean", e, output);
+                }
+            }
+            Command::Gc { dry_run, force } => {
+                if let Err(e) = commands::gc::run(dry_run, force, output) {
+                    error::exit_with("Gc", e, output);
                 }
             }
             Command::Tree {
                 task_id,
                 backend,
                 mermaid,
+                timeline,
+                weighted,
+            } => {
+                if let Err(e) = commands::tree::run(
+                    &task_id,
+                    backend.as_deref(),
+                    mermaid,
+                    timeline,
+                    weighted,
+                    output,
+                ) {
+                    error::exit_with("Tree", e, output);
+                }
+            }
+            Command::Churn { task_id, limit } => {
+                if let Err(e) = commands::churn::run(&task_id, limit, output) {
+                    error::exit_with("Churn", e, output);
+                }
+            }
+            Command::Report { task_id, open } => {
+                if let Err(e) = commands::report::run(&task_id, open, output) {
+                    error::exit_with("Report", e, output);
+                }
+            }
+            Command::Bisect {
+                task_id,
+                command,
+                base,
+            } => {
+                if let Err(e) =
+                    commands::bisect::run(&task_id, command.as_deref(), base.as_deref(), output)
+                {
+                    error::exit_with("Bisect", e, output);
+                }
+            }
+            Command::Integrate {
+                task_id,
+                branch,
+                base,
+                command,
+            } => {
+                if let Err(e) = commands::integrate::run(
+                    &task_id,
+                    branch.as_deref(),
+                    base.as_deref(),
+                    command.as_deref(),
+                    output,
+                ) {
+                    error::exit_with("Integrate", e, output);
+                }
+            }
+            Command::Logs {
+                task_id,
+                subtask,
+                follow,
+                raw,
+            } => {
+                if let Err(e) =
+                    commands::logs::run(&task_id, subtask.as_deref(), follow, raw, output)
+                {
+                    error::exit_with("Logs", e, output);
+                }
+            }
+            Command::Stats {
+                task_id,
+                failures,
+                trends,
+                csv,
             } => {
-                if let Err(e) = commands::tree::run(&task_id, backend.as_deref(), mermaid) {
-                    eprintln!("Tree error: {}", e);
-                    std::process::exit(1);
+                if let Err(e) =
+                    commands::stats::run(task_id.as_deref(), failures, trends, csv, output)
+                {
+                    error::exit_with("Stats", e, output);
+                }
+            }
+            Command::Cancel { task_id } => {
+                if let Err(e) = commands::cancel::run(task_id.as_deref(), output) {
+                    error::exit_with("Cancel", e, output);
+                }
+            }
+            Command::Lock { action } => {
+                let result = match action {
+                    LockAction::Run {
+                        path,
+                        task,
+                        command,
+                    } => commands::lock::run_locked(path.as_deref(), task.as_deref(), &command),
+                    LockAction::Status { path } => {
+                        commands::lock::run_status(path.as_deref(), output)
+                    }
+                    LockAction::Release { path } => {
+                        commands::lock::run_release(path.as_deref(), output)
+                    }
+                };
+                if let Err(e) = result {
+                    error::exit_with("Lock", e, output);
+                }
+            }
+            Command::Pause { task_id } => {
+                if let Err(e) = commands::pause::run(task_id.as_deref(), true, output) {
+                    error::exit_with("Pause", e, output);
+                }
+            }
+            Command::Unpause { task_id } => {
+                if let Err(e) = commands::pause::run(task_id.as_deref(), false, output) {
+                    error::exit_with("Unpause", e, output);
+                }
+            }
+            Command::Status { task_id } => {
+                if let Err(e) = commands::status::run(task_id.as_deref(), output) {
+                    error::exit_with("Status", e, output);
+                }
+            }
+            Command::Completions { shell } => {
+                use clap::CommandFactory;
+                if let Err(e) = commands::completions::run(shell, Cli::command()) {
+                    error::exit_with("Completions", e, output);
+                }
+            }
+            Command::TaskIds => {
+                let _ = commands::completions::run_task_ids();
+            }
+            Command::Deps { action } => {
+                let result = match action {
+                    DepsAction::List { task_id } => commands::deps::run_list(&task_id, output),
+                    DepsAction::Resolve {
+                        task_id,
+                        urls,
+                        all,
+                        check,
+                    } => commands::deps::run_resolve(&task_id, &urls, all, check, output),
+                    DepsAction::Done { task_id, tasks } => {
+                        commands::deps::run_done(&task_id, &tasks, output)
+                    }
+                    DepsAction::Add {
+                        task_id,
+                        task,
+                        blocker,
+                    } => commands::deps::run_add(&task_id, &task, &blocker, output),
+                    DepsAction::Remove {
+                        task_id,
+                        task,
+                        blocker,
+                    } => commands::deps::run_remove(&task_id, &task, &blocker, output),
+                };
+                if let Err(e) = result {
+                    error::exit_with("Deps", e, output);
+                }
+            }
+            Command::Task { action } => {
+                let result = match action {
+                    TaskAction::Edit { subtask, parent } => {
+                        commands::task::run_edit(&subtask, parent.as_deref(), output)
+                    }
+                };
+                if let Err(e) = result {
+                    error::exit_with("Task", e, output);
+                }
+            }
+            Command::Debug { action } => {
+                let result = match action {
+                    DebugAction::Dump {
+                        task_id,
+                        event_type,
+                        task,
+                        tail,
+                    } => commands::debug::run_dump(
+                        &task_id,
+                        event_type.as_deref(),
+                        task.as_deref(),
+                        tail,
+                        output,
+                    ),
+                };
+                if let Err(e) = result {
+                    error::exit_with("Debug", e, output);
+                }
+            }
+            Command::Backend { action } => {
+                let result = match action {
+                    BackendAction::States { issue, write } => {
+                        commands::backend::run_states(issue.as_deref(), write, output)
+                    }
+                };
+                if let Err(e) = result {
+                    error::exit_with("Backend", e, output);
+                }
+            }
+            Command::Auth { action } => {
+                let result = match action {
+                    AuthAction::Login { backend } => commands::auth::run_login(&backend, output),
+                    AuthAction::Status => commands::auth::run_status(output),
+                    AuthAction::Logout { backend } => commands::auth::run_logout(&backend, output),
+                };
+                if let Err(e) = result {
+                    error::exit_with("Auth", e, output);
+                }
+            }
+            Command::Primer { refresh } => {
+                if let Err(e) = commands::primer::run(refresh, output) {
+                    error::exit_with("Primer", e, output);
+                }
+            }
+            Command::Verify {
+                task_id,
+                subtask,
+                path,
+            } => {
+                if let Err(e) = commands::verify::run(
+                    task_id.as_deref(),
+                    subtask.as_deref(),
+                    path.as_deref(),
+                    output,
+                ) {
+                    error::exit_with("Verify", e, output);
+                }
+            }
+            Command::VerifyScope { base, path } => {
+                if let Err(e) = commands::verify_scope::run(base.as_deref(), &path, output) {
+                    error::exit_with("Verify-scope", e, output);
                 }
             }
             Command::Run {
@@ -409,24 +1342,28 @@ fn main() {
                     model.as_deref(),
                     delay,
                 ) {
-                    eprintln!("Run error: {}", e);
-                    std::process::exit(1);
+                    error::exit_with("Run", e, output);
                 }
             }
             Command::Loop {
                 task_id,
-                no_sandbox: _,
-                local: _,
+                no_sandbox,
+                local,
                 backend,
                 model,
                 thinking_level,
                 parallel,
                 max_iterations,
                 fresh,
-                debug: _,
+                debug,
                 no_submit,
                 no_tui,
+                break_cycles,
+                plan,
+                skip_preflight,
             } => {
+                let debug =
+                    debug_verbosity(debug).unwrap_or_else(|e| error::exit_with("Loop", e, output));
                 if let Err(e) = commands::loop_cmd::run(
                     &task_id,
                     &commands::loop_cmd::LoopOptions {
@@ -438,10 +1375,128 @@ fn main() {
                         fresh,
                         no_submit,
                         no_tui,
+                        resume: false,
+                        break_cycles,
+                        plan,
+                        no_sandbox: no_sandbox || local,
+                        debug,
+                        skip_preflight,
+                    },
+                ) {
+                    error::exit_with("Loop", e, output);
+                }
+            }
+            Command::Resume {
+                task_id,
+                backend,
+                model,
+                thinking_level,
+                parallel,
+                max_iterations,
+                no_submit,
+                no_tui,
+                break_cycles,
+                skip_preflight,
+            } => {
+                if let Err(e) = commands::loop_cmd::run(
+                    &task_id,
+                    &commands::loop_cmd::LoopOptions {
+                        backend_override: backend.as_deref(),
+                        model_override: model.as_deref(),
+                        thinking_level_override: thinking_level.as_deref(),
+                        parallel_override: parallel,
+                        max_iterations_override: max_iterations,
+                        fresh: false,
+                        no_submit,
+                        no_tui,
+                        resume: true,
+                        break_cycles,
+                        plan: false,
+                        no_sandbox: false,
+                        debug: None,
+                        skip_preflight,
+                    },
+                ) {
+                    error::exit_with("Resume", e, output);
+                }
+            }
+            Command::Worker {
+                join,
+                name,
+                capacity,
+                token,
+            } => {
+                if let Err(e) =
+                    commands::worker::run(&join, name.as_deref(), capacity, token.as_deref())
+                {
+                    error::exit_with("Worker", e, output);
+                }
+            }
+            Command::Listen { port, bind, secret } => {
+                if let Err(e) = commands::listen::run(&bind, port, secret.as_deref()) {
+                    error::exit_with("Listen", e, output);
+                }
+            }
+            Command::Serve {
+                port,
+                bind,
+                token,
+                observe,
+            } => {
+                if let Err(e) = commands::serve::run(&bind, port, token.as_deref(), observe) {
+                    error::exit_with("Serve", e, output);
+                }
+            }
+            Command::Export { task_id, out } => {
+                if let Err(e) =
+                    commands::bundle::run_export(task_id.as_deref(), out.as_deref(), output)
+                {
+                    error::exit_with("Export", e, output);
+                }
+            }
+            Command::Import { file, id, force } => {
+                if let Err(e) = commands::bundle::run_import(&file, id.as_deref(), force, output) {
+                    error::exit_with("Import", e, output);
+                }
+            }
+            Command::New {
+                title,
+                description,
+                description_file,
+                backend,
+                labels,
+                refine,
+            } => {
+                if let Err(e) = commands::new::run(
+                    &commands::new::NewOptions {
+                        title: &title,
+                        description: description.as_deref(),
+                        description_file: description_file.as_deref(),
+                        backend_override: backend.as_deref(),
+                        labels: &labels,
+                        refine,
+                    },
+                    output,
+                ) {
+                    error::exit_with("New", e, output);
+                }
+            }
+            Command::Refine {
+                task_id,
+                backend,
+                dry_run,
+                force,
+            } => {
+                if let Err(e) = commands::refine::run(
+                    task_id.as_deref(),
+                    &commands::refine::RefineOptions {
+                        backend_override: backend.as_deref(),
+                        dry_run,
+                        force,
                     },
+                    output,
                 ) {
-                    eprintln!("Loop error: {}", e);
-                    std::process::exit(1);
+                    error::exit_with("Refine", e, output);
                 }
             }
             Command::Submit {
@@ -451,17 +1506,46 @@ fn main() {
                 thinking_level,
                 draft,
                 skip_status_update,
+                stacked,
+                reviewers,
+                labels,
+                milestone,
+                auto_merge,
             } => {
                 if let Err(e) = commands::submit::run(
+                    task_id.as_deref(),
+                    &commands::submit::SubmitOptions {
+                        backend_override: backend.as_deref(),
+                        model_override: model.as_deref(),
+                        thinking_level_override: thinking_level.as_deref(),
+                        draft,
+                        skip_status_update,
+                        stacked,
+                        reviewers,
+                        labels,
+                        milestone,
+                        auto_merge,
+                    },
+                ) {
+                    error::exit_with("Submit", e, output);
+                }
+            }
+            Command::PrStatus {
+                task_id,
+                backend,
+                watch,
+                interval,
+                skip_status_update,
+            } => {
+                if let Err(e) = commands::pr_status::run(
                     task_id.as_deref(),
                     backend.as_deref(),
-                    model.as_deref(),
-                    thinking_level.as_deref(),
-                    draft,
+                    watch,
+                    interval,
                     skip_status_update,
+                    output,
                 ) {
-                    eprintln!("Submit error: {}", e);
-                    std::process::exit(1);
+                    error::exit_with("Pr-status", e, output);
                 }
             }
             Command::Push {
@@ -470,22 +1554,33 @@ fn main() {
                 dry_run,
                 all,
                 summary,
+                scoring,
+                review,
+                resolve,
             } => {
                 if let Err(e) = commands::push::run(
                     parent_id.as_deref(),
-                    backend.as_deref(),
-                    dry_run,
-                    all,
-                    summary,
+                    &commands::push::PushOptions {
+                        backend_override: backend.as_deref(),
+                        dry_run,
+                        all,
+                        summary,
+                        scoring,
+                        review,
+                        resolve,
+                    },
+                    output,
                 ) {
-                    eprintln!("Push error: {}", e);
-                    std::process::exit(1);
+                    error::exit_with("Push", e, output);
                 }
             }
-            Command::Pull { task_id, backend } => {
-                if let Err(e) = commands::pull::run(task_id.as_deref(), backend.as_deref()) {
-                    eprintln!("Pull error: {}", e);
-                    std::process::exit(1);
+            Command::Pull {
+                task_id,
+                backend,
+                full,
+            } => {
+                if let Err(e) = commands::pull::run(task_id.as_deref(), backend.as_deref(), full) {
+                    error::exit_with("Pull", e, output);
                 }
             }
             Command::SetId {
@@ -495,53 +1590,45 @@ fn main() {
             } => {
                 if let Err(e) = commands::set_id::run(task_id.as_deref(), backend.as_deref(), clear)
                 {
-                    eprintln!("Set-id error: {}", e);
-                    std::process::exit(1);
+                    error::exit_with("Set-id", e, output);
+                }
+            }
+            Command::Watch { task_id } => {
+                if let Err(e) = commands::watch::run(&task_id, output) {
+                    error::exit_with("Watch", e, output);
                 }
             }
             Command::Tui {
                 task_id,
+                all,
                 no_legend: _,
                 state_dir,
                 refresh: _,
                 lines: _,
+                observe,
+                replay,
             } => {
-                // Resolve runtime state path
-                let mobius_path = local_state::get_project_mobius_path();
-                let state_path = if let Some(dir) = state_dir {
-                    std::path::PathBuf::from(dir).join("runtime.json")
-                } else {
-                    mobius_path
-                        .join("issues")
-                        .join(&task_id)
-                        .join("execution")
-                        .join("runtime.json")
+                let result = match task_id {
+                    Some(task_id) if !all => {
+                        // Resolve runtime state path
+                        let state_path = match state_dir {
+                            Some(dir) => std::path::PathBuf::from(dir).join("runtime.json"),
+                            None => context::get_runtime_path(&task_id),
+                        };
+                        tui::dashboard::run_issue_dashboard(
+                            &task_id,
+                            state_path,
+                            tui::dashboard::DashboardOptions {
+                                observe,
+                                replay,
+                                from_overview: false,
+                            },
+                        )
+                    }
+                    _ => tui::overview::run_overview(observe),
                 };
-
-                // Read sub-tasks from local state and build graph
-                let issues = local_state::read_local_subtasks_as_linear_issues(&task_id);
-                let graph = types::task_graph::build_task_graph(&task_id, &task_id, &issues);
-
-                // Read parent title
-                let parent_title = local_state::read_parent_spec(&task_id)
-                    .map(|p| p.title)
-                    .unwrap_or_else(|| task_id.clone());
-
-                // Load config to get max_parallel_agents
-                let paths = config::resolve_paths();
-                let loop_config = config::read_config(&paths.config_path).unwrap_or_default();
-                let max_parallel_agents =
-                    loop_config.execution.max_parallel_agents.unwrap_or(3) as usize;
-
-                if let Err(e) = tui::dashboard::run_dashboard(
-                    task_id,
-                    parent_title,
-                    graph,
-                    state_path,
-                    max_parallel_agents,
-                ) {
-                    eprintln!("TUI error: {}", e);
-                    std::process::exit(1);
+                if let Err(e) = result {
+                    error::exit_with("TUI", e, output);
                 }
             }
         },
@@ -562,24 +1649,30 @@ fn main() {
                         cli.model.as_deref(),
                         cli.delay,
                     ) {
-                        eprintln!("Run error: {}", e);
-                        std::process::exit(1);
+                        error::exit_with("Run", e, output);
                     }
-                } else if let Err(e) = commands::loop_cmd::run(
-                    &task_id,
-                    &commands::loop_cmd::LoopOptions {
-                        backend_override: cli.backend.as_deref(),
-                        model_override: cli.model.as_deref(),
-                        thinking_level_override: cli.thinking_level.as_deref(),
-                        parallel_override: cli.parallel,
-                        max_iterations_override: cli.max_iterations,
-                        fresh: cli.fresh,
-                        no_submit: cli.no_submit,
-                        no_tui: cli.no_tui,
-                    },
-                ) {
-                    eprintln!("Loop error: {}", e);
-                    std::process::exit(1);
+                } else if let Err(e) = debug_verbosity(cli.debug).and_then(|debug| {
+                    commands::loop_cmd::run(
+                        &task_id,
+                        &commands::loop_cmd::LoopOptions {
+                            backend_override: cli.backend.as_deref(),
+                            model_override: cli.model.as_deref(),
+                            thinking_level_override: cli.thinking_level.as_deref(),
+                            parallel_override: cli.parallel,
+                            max_iterations_override: cli.max_iterations,
+                            fresh: cli.fresh,
+                            no_submit: cli.no_submit,
+                            no_tui: cli.no_tui,
+                            resume: false,
+                            break_cycles: false,
+                            plan: false,
+                            no_sandbox: cli.no_sandbox || cli.local,
+                            debug,
+                            skip_preflight: cli.skip_preflight,
+                        },
+                    )
+                }) {
+                    error::exit_with("Loop", e, output);
                 }
             } else {
                 // No command and no task ID - show help
@@ -1,5 +1,9 @@
+use crate::local_state::{IterationLogEntry, IterationStatus};
 use crate::types::enums::TaskStatus;
-use crate::types::task_graph::TaskGraph;
+use crate::types::task_graph::{CriticalPath, TaskGraph};
+
+/// Border drawn around critical-path nodes
+const CRITICAL_PATH_STROKE: &str = "stroke:#D9534F,stroke-width:3px";
 
 /// Status icons for Mermaid node labels
 fn status_icon(status: TaskStatus) -> &'static str {
@@ -60,6 +64,21 @@ fn sanitize_node_id(identifier: &str) -> String {
 ///
 /// Returns Mermaid flowchart code (without markdown fence).
 pub fn render_mermaid_diagram(graph: &TaskGraph) -> String {
+    render_diagram(graph, None)
+}
+
+/// Generate a Mermaid flowchart with the critical path highlighted.
+///
+/// Critical-path edges are drawn as thick links (`==>`) and its nodes get a
+/// red border on top of their status fill.
+pub fn render_mermaid_diagram_with_critical_path(
+    graph: &TaskGraph,
+    critical_path: &CriticalPath,
+) -> String {
+    render_diagram(graph, Some(critical_path))
+}
+
+fn render_diagram(graph: &TaskGraph, critical_path: Option<&CriticalPath>) -> String {
     let mut lines: Vec<String> = Vec::new();
 
     // Flowchart header (top-down orientation)
@@ -89,7 +108,13 @@ pub fn render_mermaid_diagram(graph: &TaskGraph) -> String {
             if let Some(blocker_task) = graph.tasks.get(blocker_id) {
                 let from_id = sanitize_node_id(&blocker_task.identifier);
                 let to_id = sanitize_node_id(&task.identifier);
-                lines.push(format!("    {from_id} --> {to_id}"));
+                let arrow = if critical_path.is_some_and(|p| p.contains_edge(blocker_id, &task.id))
+                {
+                    "==>"
+                } else {
+                    "-->"
+                };
+                lines.push(format!("    {from_id} {arrow} {to_id}"));
             }
         }
     }
@@ -101,7 +126,13 @@ pub fn render_mermaid_diagram(graph: &TaskGraph) -> String {
     for task in &tasks {
         let node_id = sanitize_node_id(&task.identifier);
         let color = get_status_color(task.status);
-        lines.push(format!("    style {node_id} fill:{color}"));
+        if critical_path.is_some_and(|p| p.contains(&task.id)) {
+            lines.push(format!(
+                "    style {node_id} fill:{color},{CRITICAL_PATH_STROKE}"
+            ));
+        } else {
+            lines.push(format!("    style {node_id} fill:{color}"));
+        }
     }
 
     lines.join("\n")
@@ -122,6 +153,102 @@ pub fn render_mermaid_with_title(graph: &TaskGraph) -> String {
     format!("{title}{}", render_mermaid_markdown(graph))
 }
 
+/// Generate a titled Mermaid diagram with the critical path highlighted.
+pub fn render_mermaid_with_critical_path(
+    graph: &TaskGraph,
+    critical_path: &CriticalPath,
+) -> String {
+    format!(
+        "## Task Dependency Graph for {}\n\n```mermaid\n{}\n```",
+        graph.parent_identifier,
+        render_mermaid_diagram_with_critical_path(graph, critical_path)
+    )
+}
+
+/// Timestamp layout shared by the gantt `dateFormat` and task dates (UTC)
+const GANTT_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Generate a Mermaid gantt chart of the attempts in an iteration log.
+///
+/// One section per sub-task (graph order, then tasks the graph no longer
+/// has), one bar per attempt from `started_at` to `completed_at`. Successful
+/// attempts are drawn `done`, failed ones `crit`, partial ones `active`.
+/// Returns Mermaid gantt code (without markdown fence).
+pub fn render_mermaid_gantt(graph: &TaskGraph, log: &[IterationLogEntry]) -> String {
+    let mut lines = vec![
+        "gantt".to_string(),
+        format!(
+            "    title Execution timeline for {}",
+            escape_gantt_text(&graph.parent_identifier)
+        ),
+        "    dateFormat YYYY-MM-DDTHH:mm:ss".to_string(),
+        "    axisFormat %H:%M".to_string(),
+    ];
+
+    let mut attempts: Vec<(&IterationLogEntry, _)> = log
+        .iter()
+        .filter_map(|entry| entry.span().map(|span| (entry, span)))
+        .collect();
+    attempts.sort_by_key(|(_, (start, _))| *start);
+
+    let mut sections: Vec<&str> = graph
+        .tasks
+        .values()
+        .map(|t| t.identifier.as_str())
+        .collect();
+    sections.sort_unstable();
+    for (entry, _) in &attempts {
+        if !sections.contains(&entry.subtask_id.as_str()) {
+            sections.push(&entry.subtask_id);
+        }
+    }
+
+    let mut bar = 0;
+    for section in sections {
+        let task_attempts: Vec<_> = attempts
+            .iter()
+            .filter(|(entry, _)| entry.subtask_id == section)
+            .collect();
+        if task_attempts.is_empty() {
+            continue;
+        }
+        lines.push(format!("    section {}", escape_gantt_text(section)));
+        for (entry, (start, end)) in task_attempts {
+            let (tag, outcome) = match entry.status {
+                IterationStatus::Success => ("done", "success"),
+                IterationStatus::Failed => ("crit", "failed"),
+                IterationStatus::Partial => ("active", "partial"),
+            };
+            lines.push(format!(
+                "    Attempt {} {} :{}, a{}, {}, {}",
+                entry.attempt,
+                outcome,
+                tag,
+                bar,
+                start.format(GANTT_DATE_FORMAT),
+                end.format(GANTT_DATE_FORMAT)
+            ));
+            bar += 1;
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Generate a titled Mermaid gantt chart wrapped in a markdown code fence.
+pub fn render_mermaid_gantt_markdown(graph: &TaskGraph, log: &[IterationLogEntry]) -> String {
+    format!(
+        "## Execution Timeline for {}\n\n```mermaid\n{}\n```",
+        graph.parent_identifier,
+        render_mermaid_gantt(graph, log)
+    )
+}
+
+/// Gantt titles, sections, and task names end at `:`, `#`, or `;`.
+fn escape_gantt_text(text: &str) -> String {
+    text.replace([':', '#', ';'], " ")
+}
+
 /// Get all status colors as a list of (status, color) pairs.
 pub fn get_all_status_colors() -> Vec<(TaskStatus, &'static str)> {
     vec![
@@ -140,7 +267,10 @@ pub fn get_all_status_colors() -> Vec<(TaskStatus, &'static str)> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::task_graph::{build_task_graph, LinearIssue, Relation, Relations};
+    use crate::types::enums::TaskType;
+    use crate::types::task_graph::{
+        build_task_graph, critical_path, LinearIssue, Relation, Relations,
+    };
 
     fn make_sample_issues() -> Vec<LinearIssue> {
         vec![
@@ -158,6 +288,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "b".to_string(),
@@ -176,6 +310,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "c".to_string(),
@@ -191,6 +329,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ]
     }
@@ -286,6 +428,21 @@ mod tests {
         assert!(diagram.contains("style MOB_103 fill:#D3D3D3")); // blocked = gray
     }
 
+    #[test]
+    fn test_render_mermaid_highlights_critical_path() {
+        let issues = make_sample_issues();
+        let graph = build_task_graph("parent-1", "MOB-100", &issues);
+        let path = critical_path(&graph, false);
+        let diagram = render_mermaid_diagram_with_critical_path(&graph, &path);
+
+        // MOB-101 is done, so the path is MOB-102 -> MOB-103
+        assert!(diagram.contains("MOB_101 --> MOB_102"));
+        assert!(diagram.contains("MOB_102 ==> MOB_103"));
+        assert!(diagram.contains("style MOB_101 fill:#90EE90\n"));
+        assert!(diagram.contains("style MOB_103 fill:#D3D3D3,stroke:#D9534F,stroke-width:3px"));
+        assert!(!render_mermaid_diagram(&graph).contains("==>"));
+    }
+
     #[test]
     fn test_render_mermaid_markdown() {
         let issues = make_sample_issues();
@@ -341,6 +498,10 @@ mod tests {
             git_branch_name: String::new(),
             relations: None,
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         }];
         let graph = build_task_graph("parent-1", "MOB-400", &issues);
         let diagram = render_mermaid_diagram(&graph);
@@ -350,4 +511,55 @@ mod tests {
         assert!(!diagram.contains('>'));
         assert!(!diagram.contains('&'));
     }
+
+    #[test]
+    fn test_render_mermaid_gantt_one_bar_per_attempt() {
+        let graph = build_task_graph("parent-1", "MOB-100", &make_sample_issues());
+        let attempt =
+            |task: &str, attempt: u32, status, start: &str, end: Option<&str>| IterationLogEntry {
+                completed_at: end.map(str::to_string),
+                duration_ms: Some(90_000),
+                ..IterationLogEntry::new(task, attempt, start, status)
+            };
+        let log = vec![
+            attempt(
+                "MOB-102",
+                2,
+                IterationStatus::Success,
+                "2026-10-01T10:05:00Z",
+                None,
+            ),
+            attempt(
+                "MOB-101",
+                1,
+                IterationStatus::Success,
+                "2026-10-01T10:00:00Z",
+                Some("2026-10-01T10:02:00Z"),
+            ),
+            attempt(
+                "MOB-102",
+                1,
+                IterationStatus::Failed,
+                "2026-10-01T10:02:00Z",
+                Some("2026-10-01T10:04:30Z"),
+            ),
+            attempt("MOB-103", 1, IterationStatus::Partial, "not a time", None),
+        ];
+
+        let gantt = render_mermaid_gantt(&graph, &log);
+        let lines: Vec<&str> = gantt.lines().collect();
+        assert_eq!(lines[0], "gantt");
+        assert!(gantt.contains("title Execution timeline for MOB-100"));
+        let body: Vec<&str> = lines.iter().skip(4).map(|l| l.trim()).collect();
+        assert_eq!(
+            body,
+            vec![
+                "section MOB-101",
+                "Attempt 1 success :done, a0, 2026-10-01T10:00:00, 2026-10-01T10:02:00",
+                "section MOB-102",
+                "Attempt 1 failed :crit, a1, 2026-10-01T10:02:00, 2026-10-01T10:04:30",
+                "Attempt 2 success :done, a2, 2026-10-01T10:05:00, 2026-10-01T10:06:30",
+            ]
+        );
+    }
 }
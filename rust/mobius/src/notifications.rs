@@ -0,0 +1,318 @@
+//! Webhook notifications on task lifecycle events.
+//!
+//! Each webhook in `notifications.webhooks` receives a JSON POST for the events
+//! it subscribes to. Payloads carry `text` and `content` summaries so Slack and
+//! Discord incoming webhooks can take them unmodified; other services can read
+//! the structured fields. Delivery is best effort: failures are logged and
+//! never stop the loop.
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tracing::warn;
+
+use crate::types::{NotificationEvent, NotificationsConfig};
+
+/// Per-request timeout, so an unreachable endpoint cannot stall the loop
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON body POSTed to webhooks
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub event: NotificationEvent,
+    pub timestamp: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_id: Option<String>,
+    /// Sub-task identifier for `task_*` events
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attempt: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// `completed`, `failed`, or `interrupted` for `loop_finished`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tasks_completed: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tasks_total: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+    /// Pull or merge request URL for `pr_created`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Previous and new `runtime/model` for `model_fallback`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_model: Option<String>,
+    /// One-line summary (Slack)
+    pub text: String,
+    /// Same summary (Discord)
+    pub content: String,
+}
+
+impl Notification {
+    fn new(event: NotificationEvent, issue_id: Option<&str>, text: String) -> Self {
+        Self {
+            event,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            issue_id: issue_id.map(str::to_string),
+            task: None,
+            attempt: None,
+            error: None,
+            status: None,
+            tasks_completed: None,
+            tasks_total: None,
+            duration_ms: None,
+            url: None,
+            from_model: None,
+            to_model: None,
+            content: text.clone(),
+            text,
+        }
+    }
+
+    pub fn task_started(issue_id: &str, task: &str, attempt: u32) -> Self {
+        let retry = if attempt > 1 {
+            format!(" (attempt {})", attempt)
+        } else {
+            String::new()
+        };
+        Self {
+            task: Some(task.to_string()),
+            attempt: Some(attempt),
+            ..Self::new(
+                NotificationEvent::TaskStarted,
+                Some(issue_id),
+                format!("mobius: started {} in {}{}", task, issue_id, retry),
+            )
+        }
+    }
+
+    pub fn task_completed(issue_id: &str, task: &str, duration_ms: u64) -> Self {
+        Self {
+            task: Some(task.to_string()),
+            duration_ms: Some(duration_ms),
+            ..Self::new(
+                NotificationEvent::TaskCompleted,
+                Some(issue_id),
+                format!("mobius: completed {} in {}", task, issue_id),
+            )
+        }
+    }
+
+    pub fn task_failed(issue_id: &str, task: &str, attempts: u32, error: Option<&str>) -> Self {
+        Self {
+            task: Some(task.to_string()),
+            attempt: Some(attempts),
+            error: error.map(str::to_string),
+            ..Self::new(
+                NotificationEvent::TaskFailed,
+                Some(issue_id),
+                format!(
+                    "mobius: {} in {} failed after {} attempt(s): {}",
+                    task,
+                    issue_id,
+                    attempts,
+                    error.unwrap_or("unknown error")
+                ),
+            )
+        }
+    }
+
+    pub fn loop_finished(
+        issue_id: &str,
+        status: &str,
+        tasks_completed: usize,
+        tasks_total: usize,
+        duration_ms: u64,
+    ) -> Self {
+        Self {
+            status: Some(status.to_string()),
+            tasks_completed: Some(tasks_completed),
+            tasks_total: Some(tasks_total),
+            duration_ms: Some(duration_ms),
+            ..Self::new(
+                NotificationEvent::LoopFinished,
+                Some(issue_id),
+                format!(
+                    "mobius: loop for {} {} ({}/{} tasks)",
+                    issue_id, status, tasks_completed, tasks_total
+                ),
+            )
+        }
+    }
+
+    pub fn pr_created(issue_id: Option<&str>, url: Option<&str>) -> Self {
+        let target = issue_id
+            .map(|id| format!(" for {}", id))
+            .unwrap_or_default();
+        let link = url.map(|u| format!(": {}", u)).unwrap_or_default();
+        Self {
+            url: url.map(str::to_string),
+            ..Self::new(
+                NotificationEvent::PrCreated,
+                issue_id,
+                format!("mobius: opened pull request{}{}", target, link),
+            )
+        }
+    }
+
+    /// `task` hit a provider incident (`reason`), so remaining tasks moved from
+    /// `from` to `to`.
+    pub fn model_fallback(issue_id: &str, task: &str, from: &str, to: &str, reason: &str) -> Self {
+        Self {
+            task: Some(task.to_string()),
+            error: Some(reason.to_string()),
+            from_model: Some(from.to_string()),
+            to_model: Some(to.to_string()),
+            ..Self::new(
+                NotificationEvent::ModelFallback,
+                Some(issue_id),
+                format!(
+                    "mobius: switched {} from {} to {} after a provider incident on {}: {}",
+                    issue_id, from, to, task, reason
+                ),
+            )
+        }
+    }
+
+    /// Human sub-task `task` is unblocked; the loop waits on it.
+    pub fn human_action_needed(issue_id: &str, task: &str, title: &str) -> Self {
+        Self {
+            task: Some(task.to_string()),
+            ..Self::new(
+                NotificationEvent::HumanActionNeeded,
+                Some(issue_id),
+                format!(
+                    "mobius: {} is waiting on you for {}: {}",
+                    issue_id, task, title
+                ),
+            )
+        }
+    }
+}
+
+/// Delivers notifications to the configured webhooks
+pub struct Notifier {
+    config: NotificationsConfig,
+    client: reqwest::Client,
+}
+
+impl Notifier {
+    pub fn new(config: &NotificationsConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            config: config.clone(),
+            client,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.config.is_empty()
+    }
+
+    /// POST `notification` to every webhook subscribed to its event.
+    pub async fn send(&self, notification: &Notification) {
+        for webhook in &self.config.webhooks {
+            if !webhook.wants(notification.event) {
+                continue;
+            }
+            let mut request = self.client.post(&webhook.url).json(notification);
+            for (name, value) in &webhook.headers {
+                request = request.header(name, value);
+            }
+            match request.send().await {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => warn!(
+                    "Webhook {} rejected {}: HTTP {}",
+                    webhook.url,
+                    notification.event,
+                    response.status()
+                ),
+                Err(e) => warn!(
+                    "Failed to deliver {} to {}: {}",
+                    notification.event, webhook.url, e
+                ),
+            }
+        }
+    }
+
+    /// Send from synchronous code on the caller's runtime.
+    pub fn send_blocking(&self, rt: &tokio::runtime::Runtime, notification: &Notification) {
+        if self.is_enabled() {
+            rt.block_on(self.send(notification));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notification_payload() {
+        let value = serde_json::to_value(Notification::task_failed(
+            "MOB-1",
+            "MOB-2",
+            3,
+            Some("timeout"),
+        ))
+        .unwrap();
+        assert_eq!(value["event"], "task_failed");
+        assert_eq!(value["issueId"], "MOB-1");
+        assert_eq!(value["task"], "MOB-2");
+        assert_eq!(value["attempt"], 3);
+        assert_eq!(
+            value["text"],
+            "mobius: MOB-2 in MOB-1 failed after 3 attempt(s): timeout"
+        );
+        assert_eq!(value["content"], value["text"]);
+        assert!(value.get("url").is_none());
+
+        let value = serde_json::to_value(Notification::loop_finished(
+            "MOB-1",
+            "completed",
+            4,
+            4,
+            1200,
+        ))
+        .unwrap();
+        assert_eq!(value["event"], "loop_finished");
+        assert_eq!(value["status"], "completed");
+        assert_eq!(value["tasksCompleted"], 4);
+        assert!(value.get("task").is_none());
+
+        let value = serde_json::to_value(Notification::model_fallback(
+            "MOB-1",
+            "MOB-3",
+            "claude/opus",
+            "codex/gpt-5.3-codex",
+            "overloaded",
+        ))
+        .unwrap();
+        assert_eq!(value["event"], "model_fallback");
+        assert_eq!(value["fromModel"], "claude/opus");
+        assert_eq!(value["toModel"], "codex/gpt-5.3-codex");
+        assert_eq!(value["error"], "overloaded");
+
+        let value = serde_json::to_value(Notification::human_action_needed(
+            "MOB-1",
+            "MOB-4",
+            "Obtain API credentials",
+        ))
+        .unwrap();
+        assert_eq!(value["event"], "human_action_needed");
+        assert_eq!(value["task"], "MOB-4");
+        assert_eq!(
+            value["text"],
+            "mobius: MOB-1 is waiting on you for MOB-4: Obtain API credentials"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! Offline mode.
+//!
+//! With `--offline` (or `MOBIUS_OFFLINE=1`) mobius never contacts the issue
+//! tracker: `pull` rebuilds context from the cached specs, the loop runs from
+//! local state, and status changes stay in `pending-updates.json` until a
+//! later `mobius push`. A backend request that cannot connect switches the
+//! process offline by itself for [`RECHECK_AFTER`], after which the next
+//! request tries the network again.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+/// Variable `--offline` sets, so the loop's subprocesses inherit it
+pub const OFFLINE_ENV: &str = "MOBIUS_OFFLINE";
+
+/// How long a connection failure keeps the process offline
+pub const RECHECK_AFTER: Duration = Duration::from_secs(300);
+
+/// When a connection failure last switched this process offline
+static DETECTED_AT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Whether backend requests should be skipped right now.
+pub fn is_offline() -> bool {
+    offline_requested() || detected_recently()
+}
+
+/// Whether offline mode was asked for with `--offline` or `MOBIUS_OFFLINE`.
+pub fn offline_requested() -> bool {
+    std::env::var(OFFLINE_ENV).is_ok_and(|v| is_truthy(&v))
+}
+
+/// Record that a backend could not be reached, switching offline until
+/// [`RECHECK_AFTER`] has passed.
+pub fn mark_unreachable(reason: &str) {
+    let Ok(mut detected) = DETECTED_AT.lock() else {
+        return;
+    };
+    if detected.map_or(true, |at| at.elapsed() >= RECHECK_AFTER) {
+        warn!(
+            "Backend unreachable ({}); working offline and queueing updates",
+            reason
+        );
+    }
+    *detected = Some(Instant::now());
+}
+
+fn detected_recently() -> bool {
+    DETECTED_AT
+        .lock()
+        .ok()
+        .and_then(|detected| *detected)
+        .is_some_and(|at| at.elapsed() < RECHECK_AFTER)
+}
+
+fn is_truthy(value: &str) -> bool {
+    !matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "" | "0" | "false" | "no" | "off"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_truthy() {
+        for value in ["1", "true", "yes", "On"] {
+            assert!(is_truthy(value), "{value}");
+        }
+        for value in ["", "0", "false", "NO", " off "] {
+            assert!(!is_truthy(value), "{value}");
+        }
+    }
+}
@@ -0,0 +1,203 @@
+//! Post-mortem archive of agent output.
+//!
+//! Pane content and the tee'd stream-json log only live as long as the tmux
+//! pane and the temp directory. When a task finishes (or times out) the loop
+//! writes the final pane capture plus the tail of the agent's output to
+//! `execution/outputs/{identifier}.txt`, rotating earlier archives of the same
+//! task to `{identifier}.1.txt`, `{identifier}.2.txt`, ...
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::context::get_outputs_directory_path;
+use crate::types::config::OutputArchiveConfig;
+
+/// What to archive for one finished task
+#[derive(Debug, Clone, Copy)]
+pub struct TaskOutput<'a> {
+    pub identifier: &'a str,
+    /// Final status label (e.g. `SubtaskComplete`, `Timeout`)
+    pub status: &'a str,
+    /// Last captured pane content
+    pub pane: Option<&'a str>,
+    /// Raw output log the agent was tee'd to
+    pub log: Option<&'a Path>,
+}
+
+pub fn get_output_archive_path(parent_id: &str, identifier: &str) -> PathBuf {
+    get_outputs_directory_path(parent_id).join(format!("{}.txt", identifier))
+}
+
+/// Archive `output` for `parent_id`; see [`archive_output_in`].
+pub fn archive_output(
+    parent_id: &str,
+    output: &TaskOutput<'_>,
+    config: &OutputArchiveConfig,
+) -> Result<Option<PathBuf>> {
+    archive_output_in(&get_outputs_directory_path(parent_id), output, config)
+}
+
+/// Write `output` to `{dir}/{identifier}.txt`, rotating older archives.
+///
+/// Returns `None` without touching the directory when archiving is disabled
+/// (`max_kb: 0`) or there is nothing to archive.
+pub fn archive_output_in(
+    dir: &Path,
+    output: &TaskOutput<'_>,
+    config: &OutputArchiveConfig,
+) -> Result<Option<PathBuf>> {
+    let max_bytes = config.max_kb as usize * 1024;
+    if max_bytes == 0 {
+        return Ok(None);
+    }
+    let pane = output
+        .pane
+        .map(|p| tail_str(p.trim_end(), max_bytes))
+        .filter(|p| !p.is_empty());
+    let log = output
+        .log
+        .and_then(|path| read_tail(path, max_bytes).map(|tail| (path, tail)))
+        .filter(|(_, tail)| !tail.is_empty());
+    if pane.is_none() && log.is_none() {
+        return Ok(None);
+    }
+
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!("{}.txt", output.identifier));
+    rotate(dir, output.identifier, config.keep)?;
+
+    let mut content = format!(
+        "# {} ({})\n# archived {}\n",
+        output.identifier,
+        output.status,
+        Utc::now().to_rfc3339()
+    );
+    if let Some(pane) = pane {
+        content.push_str("\n## Pane\n\n");
+        content.push_str(pane);
+        content.push('\n');
+    }
+    if let Some((log_path, tail)) = log {
+        content.push_str(&format!(
+            "\n## Output (last {} KB of {})\n\n",
+            config.max_kb,
+            log_path.display()
+        ));
+        content.push_str(&tail);
+        if !tail.ends_with('\n') {
+            content.push('\n');
+        }
+    }
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(Some(path))
+}
+
+/// Shift `{id}.txt` to `{id}.1.txt` and so on, dropping anything past `keep`.
+fn rotate(dir: &Path, identifier: &str, keep: u32) -> Result<()> {
+    let numbered = |n: u32| dir.join(format!("{}.{}.txt", identifier, n));
+    let current = dir.join(format!("{}.txt", identifier));
+    if keep == 0 || !current.exists() {
+        return Ok(());
+    }
+    let _ = fs::remove_file(numbered(keep));
+    for n in (1..keep).rev() {
+        if numbered(n).exists() {
+            fs::rename(numbered(n), numbered(n + 1))
+                .with_context(|| format!("Failed to rotate {}", numbered(n).display()))?;
+        }
+    }
+    fs::rename(&current, numbered(1))
+        .with_context(|| format!("Failed to rotate {}", current.display()))
+}
+
+/// Last `max_bytes` of a file, starting at a line boundary when truncated.
+fn read_tail(path: &Path, max_bytes: usize) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let start = len.saturating_sub(max_bytes as u64);
+    file.seek(SeekFrom::Start(start)).ok()?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).ok()?;
+    if start > 0 {
+        if let Some(newline) = bytes.iter().position(|&b| b == b'\n') {
+            bytes.drain(..=newline);
+        }
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Last `max_bytes` of `s`, cut at a char boundary.
+fn tail_str(s: &str, max_bytes: usize) -> &str {
+    let mut start = s.len().saturating_sub(max_bytes);
+    while !s.is_char_boundary(start) {
+        start += 1;
+    }
+    &s[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_rotates_and_keeps_tail() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("outputs");
+        let log = tmp.path().join("MOB-1.jsonl");
+        let lines: String = (0..2000).map(|i| format!("line {:04}\n", i)).collect();
+        fs::write(&log, &lines).unwrap();
+        let config = OutputArchiveConfig { max_kb: 1, keep: 2 };
+        let output = TaskOutput {
+            identifier: "MOB-1",
+            status: "Timeout",
+            pane: Some("STATUS: working\n"),
+            log: Some(&log),
+        };
+
+        for _ in 0..4 {
+            archive_output_in(&dir, &output, &config).unwrap();
+        }
+        let content = fs::read_to_string(dir.join("MOB-1.txt")).unwrap();
+        assert!(content.starts_with("# MOB-1 (Timeout)"));
+        assert!(content.contains("## Pane\n\nSTATUS: working\n"));
+        assert!(content.ends_with("line 1999\n"));
+        assert!(!content.contains("line 0000"));
+        // The tail starts on a whole line
+        let tail = content.split("\n\n").last().unwrap();
+        assert!(tail.starts_with("line "));
+        assert!(tail.len() <= 1024);
+
+        assert!(dir.join("MOB-1.1.txt").exists());
+        assert!(dir.join("MOB-1.2.txt").exists());
+        assert!(!dir.join("MOB-1.3.txt").exists());
+    }
+
+    #[test]
+    fn test_archive_skips_when_disabled_or_empty() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let dir = tmp.path().join("outputs");
+        let output = TaskOutput {
+            identifier: "MOB-2",
+            status: "Error",
+            pane: Some("  \n"),
+            log: Some(&tmp.path().join("missing.jsonl")),
+        };
+        let config = OutputArchiveConfig::default();
+        assert!(archive_output_in(&dir, &output, &config).unwrap().is_none());
+
+        let output = TaskOutput {
+            pane: Some("boom"),
+            ..output
+        };
+        let disabled = OutputArchiveConfig { max_kb: 0, keep: 3 };
+        assert!(archive_output_in(&dir, &output, &disabled)
+            .unwrap()
+            .is_none());
+        assert!(!dir.exists());
+        assert!(archive_output_in(&dir, &output, &config).unwrap().is_some());
+    }
+}
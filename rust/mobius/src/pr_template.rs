@@ -0,0 +1,261 @@
+//! PR descriptions rendered from a user template (`submit.template`).
+//!
+//! Templates use a small handlebars subset: `{{name}}` substitutes a variable
+//! and `{{#if name}}...{{/if}}` keeps its body only when the variable is
+//! non-empty. Unknown variables are an error so typos don't ship silently.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::cost::format_cost;
+use crate::local_state::{IterationLogEntry, IterationStatus};
+use crate::mermaid_renderer::render_mermaid_markdown;
+use crate::types::enums::{Backend, TaskStatus};
+use crate::types::task_graph::TaskGraph;
+
+/// Variables available to PR templates
+pub type TemplateVars = BTreeMap<&'static str, String>;
+
+/// Build the template variables for `parent_id` from its task graph and
+/// iteration log.
+pub fn pr_template_vars(
+    parent_id: &str,
+    parent_title: &str,
+    backend: Backend,
+    graph: &TaskGraph,
+    iterations: &[IterationLogEntry],
+) -> TemplateVars {
+    let mut tasks: Vec<_> = graph.tasks.values().collect();
+    tasks.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    let done: Vec<_> = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Done)
+        .collect();
+
+    let subtasks = tasks
+        .iter()
+        .map(|t| {
+            let mark = if t.status == TaskStatus::Done {
+                "x"
+            } else {
+                " "
+            };
+            format!("- [{}] {}: {}", mark, t.identifier, t.title)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let completed_subtasks = done
+        .iter()
+        .map(|t| format!("- {}: {}", t.identifier, t.title))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let verification = tasks
+        .iter()
+        .filter(|t| {
+            let title = t.title.to_lowercase();
+            title.contains("verification") && title.contains("gate")
+        })
+        .map(|t| {
+            let last = iterations
+                .iter()
+                .rev()
+                .find(|e| e.subtask_id == t.identifier);
+            let outcome = if t.status == TaskStatus::Done {
+                "✅ passed"
+            } else if last.is_some_and(|e| e.status == IterationStatus::Failed) {
+                "❌ failed"
+            } else {
+                "⏳ not run"
+            };
+            format!("- {}: {} {}", t.identifier, t.title, outcome)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let input: u64 = iterations.iter().filter_map(|e| e.input_tokens).sum();
+    let output: u64 = iterations.iter().filter_map(|e| e.output_tokens).sum();
+    let tokens = if input + output > 0 {
+        format!("{} input / {} output", input, output)
+    } else {
+        String::new()
+    };
+    let costs: Vec<f64> = iterations.iter().filter_map(|e| e.cost_usd).collect();
+    let cost = if costs.is_empty() {
+        String::new()
+    } else {
+        format_cost(costs.iter().sum())
+    };
+
+    let closes = match backend {
+        Backend::Github | Backend::Gitlab => format!("Closes #{}", parent_id),
+        _ => format!("Closes {}", parent_id),
+    };
+
+    TemplateVars::from([
+        ("parent_id", parent_id.to_string()),
+        ("parent_title", parent_title.to_string()),
+        ("closes", closes),
+        ("subtasks", subtasks),
+        ("completed_subtasks", completed_subtasks),
+        ("completed_count", done.len().to_string()),
+        ("subtask_count", tasks.len().to_string()),
+        ("verification", verification),
+        ("tokens", tokens),
+        ("cost", cost),
+        ("mermaid", render_mermaid_markdown(graph)),
+    ])
+}
+
+/// Read the template at `path` and render it with `vars`.
+pub fn render_pr_body(path: &Path, vars: &TemplateVars) -> Result<String> {
+    let template = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read PR template {}", path.display()))?;
+    render_template(&template, vars)
+        .with_context(|| format!("Failed to render PR template {}", path.display()))
+}
+
+/// Render `template`, substituting `{{name}}` and evaluating
+/// `{{#if name}}...{{/if}}` blocks.
+pub fn render_template(template: &str, vars: &TemplateVars) -> Result<String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some((before, tag, after)) = next_tag(rest)? {
+        out.push_str(before);
+        rest = after;
+        if let Some(name) = tag.strip_prefix("#if ") {
+            let (body, tail) = split_if_block(rest)?;
+            if !lookup(vars, name.trim())?.trim().is_empty() {
+                out.push_str(&render_template(body, vars)?);
+            }
+            rest = tail;
+        } else if tag == "/if" {
+            bail!("'{{{{/if}}}}' without a matching '{{{{#if}}}}'");
+        } else {
+            out.push_str(lookup(vars, tag)?);
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Split `text` at the next `{{tag}}`: (text before, trimmed tag, text after).
+fn next_tag(text: &str) -> Result<Option<(&str, &str, &str)>> {
+    let Some(start) = text.find("{{") else {
+        return Ok(None);
+    };
+    let inner = &text[start + 2..];
+    let end = inner
+        .find("}}")
+        .ok_or_else(|| anyhow!("Unclosed '{{{{' in template"))?;
+    Ok(Some((
+        &text[..start],
+        inner[..end].trim(),
+        &inner[end + 2..],
+    )))
+}
+
+/// Split the text following an `{{#if}}` into its body and what follows the
+/// matching `{{/if}}`.
+fn split_if_block(text: &str) -> Result<(&str, &str)> {
+    let mut depth = 0;
+    let mut rest = text;
+    while let Some((before, tag, after)) = next_tag(rest)? {
+        if tag.starts_with("#if ") {
+            depth += 1;
+        } else if tag == "/if" {
+            if depth == 0 {
+                let body_len = text.len() - rest.len() + before.len();
+                return Ok((&text[..body_len], after));
+            }
+            depth -= 1;
+        }
+        rest = after;
+    }
+    bail!("'{{{{#if}}}}' without a matching '{{{{/if}}}}'")
+}
+
+fn lookup<'a>(vars: &'a TemplateVars, name: &str) -> Result<&'a str> {
+    vars.get(name).map(String::as_str).ok_or_else(|| {
+        let known: Vec<&str> = vars.keys().copied().collect();
+        anyhow!(
+            "Unknown template variable '{}' (available: {})",
+            name,
+            known.join(", ")
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::task_graph::{build_task_graph, LinearIssue};
+
+    fn vars() -> TemplateVars {
+        TemplateVars::from([
+            ("parent_title", "Add exports".to_string()),
+            ("cost", String::new()),
+            ("tokens", "10 input / 5 output".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_render_template_substitutes_and_evaluates_if_blocks() {
+        let template = "# {{ parent_title }}\n{{#if tokens}}Tokens: {{tokens}}{{#if cost}} ({{cost}}){{/if}}\n{{/if}}{{#if cost}}Cost: {{cost}}{{/if}}";
+        assert_eq!(
+            render_template(template, &vars()).unwrap(),
+            "# Add exports\nTokens: 10 input / 5 output\n"
+        );
+    }
+
+    #[test]
+    fn test_render_template_rejects_unknown_and_unbalanced_tags() {
+        let err = render_template("{{parent}}", &vars()).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Unknown template variable 'parent'"));
+        assert!(render_template("{{#if cost}}x", &vars()).is_err());
+        assert!(render_template("x{{/if}}", &vars()).is_err());
+        assert!(render_template("{{parent_title", &vars()).is_err());
+    }
+
+    #[test]
+    fn test_pr_template_vars_lists_subtasks_and_gate() {
+        let issue = |id: &str, title: &str, status: &str| LinearIssue {
+            id: id.to_string(),
+            identifier: id.to_string(),
+            title: title.to_string(),
+            status: status.to_string(),
+            git_branch_name: String::new(),
+            relations: None,
+            scoring: None,
+            priority: None,
+            labels: vec![],
+            timeout_minutes: None,
+            task_type: Default::default(),
+        };
+        let graph = build_task_graph(
+            "12",
+            "12",
+            &[
+                issue("13", "Write exporter", "Done"),
+                issue("14", "Verification Gate", "Todo"),
+            ],
+        );
+
+        let vars = pr_template_vars("12", "Exports", Backend::Github, &graph, &[]);
+        assert_eq!(vars["closes"], "Closes #12");
+        assert_eq!(
+            vars["subtasks"],
+            "- [x] 13: Write exporter\n- [ ] 14: Verification Gate"
+        );
+        assert_eq!(vars["completed_subtasks"], "- 13: Write exporter");
+        assert_eq!(vars["completed_count"], "1");
+        assert_eq!(vars["verification"], "- 14: Verification Gate ⏳ not run");
+        assert_eq!(vars["cost"], "");
+        assert!(vars["mermaid"].starts_with("```mermaid"));
+    }
+}
@@ -0,0 +1,692 @@
+//! Pre-flight checks shared by `mobius doctor` and loop start.
+//!
+//! The loop runs these before creating a worktree or spawning agents, so a
+//! missing tool, dirty tree, stale base branch or rejected token fails in
+//! seconds with a fix instead of part-way through a run.
+
+use std::process::Command;
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::github::{GithubClient, GithubError};
+use crate::gitlab::{GitlabClient, GitlabError};
+use crate::jira::{JiraClient, JiraError};
+use crate::linear::{LinearClient, LinearError};
+use crate::runtime_adapter;
+use crate::secrets;
+use crate::types::config::LoopConfig;
+use crate::types::enums::{AgentRuntime, Backend, ExecutionDriver};
+use crate::worktree::get_default_branch_name;
+
+#[derive(Debug, Serialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+    pub required: bool,
+    pub details: Option<String>,
+}
+
+impl CheckResult {
+    /// Whether this check blocks the loop from starting.
+    pub fn is_blocking(&self) -> bool {
+        matches!(self.status, CheckStatus::Fail) && self.required
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    Warn,
+}
+
+pub fn format_result(result: &CheckResult) -> String {
+    let icon = match result.status {
+        CheckStatus::Pass => "✓".green().to_string(),
+        CheckStatus::Fail => "✗".red().to_string(),
+        CheckStatus::Warn => "!".yellow().to_string(),
+    };
+
+    let required_str = if result.required { "" } else { " (optional)" };
+    let required_suffix = required_str.dimmed().to_string();
+
+    let message = match result.status {
+        CheckStatus::Fail => result.message.red().to_string(),
+        _ => result.message.clone(),
+    };
+
+    let mut line = format!("  {} {}: {}{}", icon, result.name, message, required_suffix);
+
+    if let Some(ref details) = result.details {
+        if !matches!(result.status, CheckStatus::Pass) {
+            line += &format!("\n      {}", details.dimmed());
+        }
+    }
+
+    line
+}
+
+/// Settings the loop's pre-flight checks depend on
+pub struct PreflightOptions {
+    pub backend: Backend,
+    pub driver: ExecutionDriver,
+    /// Skip the base branch check when resuming into an existing worktree
+    pub check_base_branch: bool,
+}
+
+/// Run every check the loop needs to pass before spawning agents.
+pub fn run_preflight(config: &LoopConfig, opts: &PreflightOptions) -> Vec<CheckResult> {
+    let mut results: Vec<CheckResult> = loop_runtimes(config)
+        .into_iter()
+        .map(check_runtime_cli)
+        .collect();
+    results.push(check_git());
+    results.push(check_clean_worktree());
+    if opts.check_base_branch {
+        results.push(check_base_branch(config.execution.base_branch.as_deref()));
+    }
+    if opts.driver == ExecutionDriver::Tmux {
+        let mut tmux = check_tmux();
+        tmux.required = true;
+        if let CheckStatus::Warn = tmux.status {
+            tmux.status = CheckStatus::Fail;
+            tmux.details = Some(
+                "Install tmux, or set 'execution.driver: process' to run agents without it".into(),
+            );
+        }
+        results.push(tmux);
+    }
+    results.push(check_gh(opts.backend));
+    results.push(check_cclean());
+    results.push(check_api_token(opts.backend));
+    results
+}
+
+/// The configured runtime plus any that rules or the fallback route to.
+fn loop_runtimes(config: &LoopConfig) -> Vec<AgentRuntime> {
+    let mut runtimes = vec![config.runtime];
+    let routed = config
+        .execution
+        .runtime_rules
+        .iter()
+        .map(|rule| rule.runtime)
+        .chain(config.execution.fallback.as_ref().map(|f| f.runtime));
+    for runtime in routed {
+        if !runtimes.contains(&runtime) {
+            runtimes.push(runtime);
+        }
+    }
+    runtimes
+}
+
+pub fn check_command_exists(name: &str) -> bool {
+    which::which(name).is_ok()
+}
+
+pub fn check_command_version(name: &str) -> Option<String> {
+    let output = Command::new(name).arg("--version").output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let version = if stdout.trim().is_empty() {
+        stderr.trim().to_string()
+    } else {
+        stdout.trim().to_string()
+    };
+    if version.is_empty() {
+        None
+    } else {
+        Some(version.lines().next().unwrap_or("").to_string())
+    }
+}
+
+struct RuntimeCliSpec {
+    command: &'static str,
+    display_name: &'static str,
+    install_hint: &'static str,
+}
+
+fn runtime_cli_spec(runtime: AgentRuntime) -> RuntimeCliSpec {
+    match runtime {
+        AgentRuntime::Claude => RuntimeCliSpec {
+            command: runtime_adapter::runtime_cli_command(runtime),
+            display_name: "Claude CLI",
+            install_hint: "Install: npm install -g @anthropic-ai/claude-code",
+        },
+        AgentRuntime::Opencode => RuntimeCliSpec {
+            command: runtime_adapter::runtime_cli_command(runtime),
+            display_name: "OpenCode CLI",
+            install_hint: "Install opencode and ensure it is available in PATH",
+        },
+        AgentRuntime::Codex => RuntimeCliSpec {
+            command: runtime_adapter::runtime_cli_command(runtime),
+            display_name: "Codex CLI",
+            install_hint: "Install: npm install -g @openai/codex",
+        },
+        AgentRuntime::Aider => RuntimeCliSpec {
+            command: runtime_adapter::runtime_cli_command(runtime),
+            display_name: "Aider",
+            install_hint: "Install: python -m pip install aider-install && aider-install",
+        },
+    }
+}
+
+fn check_runtime_cli_with<F, G>(
+    runtime: AgentRuntime,
+    command_exists: F,
+    command_version: G,
+) -> CheckResult
+where
+    F: Fn(&str) -> bool,
+    G: Fn(&str) -> Option<String>,
+{
+    let spec = runtime_cli_spec(runtime);
+
+    if command_exists(spec.command) {
+        let version = command_version(spec.command).unwrap_or_else(|| "unknown version".into());
+        CheckResult {
+            name: spec.display_name.into(),
+            status: CheckStatus::Pass,
+            message: format!("Installed ({})", version),
+            required: true,
+            details: None,
+        }
+    } else {
+        CheckResult {
+            name: spec.display_name.into(),
+            status: CheckStatus::Fail,
+            message: "Not found".into(),
+            required: true,
+            details: Some(spec.install_hint.into()),
+        }
+    }
+}
+
+pub fn check_runtime_cli(runtime: AgentRuntime) -> CheckResult {
+    check_runtime_cli_with(runtime, check_command_exists, check_command_version)
+}
+
+pub fn check_git() -> CheckResult {
+    if check_command_exists("git") {
+        let version = check_command_version("git").unwrap_or_else(|| "unknown".into());
+        CheckResult {
+            name: "Git".into(),
+            status: CheckStatus::Pass,
+            message: format!("Installed ({})", version),
+            required: true,
+            details: None,
+        }
+    } else {
+        CheckResult {
+            name: "Git".into(),
+            status: CheckStatus::Fail,
+            message: "Not found".into(),
+            required: true,
+            details: Some("Install git for your platform".into()),
+        }
+    }
+}
+
+fn git_output(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Check that tracked files have no uncommitted changes.
+pub fn check_clean_worktree() -> CheckResult {
+    match git_output(&["status", "--porcelain", "--untracked-files=no"]) {
+        Ok(status) if status.is_empty() => CheckResult {
+            name: "Working tree".into(),
+            status: CheckStatus::Pass,
+            message: "Clean".into(),
+            required: true,
+            details: None,
+        },
+        Ok(status) => CheckResult {
+            name: "Working tree".into(),
+            status: CheckStatus::Fail,
+            message: format!("{} uncommitted change(s)", status.lines().count()),
+            required: true,
+            details: Some("Commit or stash them first: git stash".into()),
+        },
+        Err(e) => CheckResult {
+            name: "Working tree".into(),
+            status: CheckStatus::Fail,
+            message: "Not a git repository".into(),
+            required: true,
+            details: Some(e),
+        },
+    }
+}
+
+/// Check that the local base branch has every commit on `origin`.
+pub fn check_base_branch(configured: Option<&str>) -> CheckResult {
+    let base = match configured {
+        Some(b) => Some(b.to_string()),
+        None => tokio::runtime::Runtime::new()
+            .ok()
+            .and_then(|rt| rt.block_on(get_default_branch_name())),
+    };
+    let Some(base) = base else {
+        return CheckResult {
+            name: "Base branch".into(),
+            status: CheckStatus::Fail,
+            message: "Could not determine the base branch".into(),
+            required: true,
+            details: Some("Set execution.base_branch in your config (mobius config -e)".into()),
+        };
+    };
+
+    if git_output(&["remote", "get-url", "origin"]).is_err() {
+        return CheckResult {
+            name: "Base branch".into(),
+            status: CheckStatus::Pass,
+            message: format!("{} (no origin remote)", base),
+            required: true,
+            details: None,
+        };
+    }
+    if let Err(e) = git_output(&["fetch", "--quiet", "origin", &base]) {
+        return CheckResult {
+            name: "Base branch".into(),
+            status: CheckStatus::Warn,
+            message: format!("Could not fetch origin/{}", base),
+            required: true,
+            details: Some(e),
+        };
+    }
+
+    let range = format!("{}...origin/{}", base, base);
+    let counts = git_output(&["rev-list", "--left-right", "--count", &range])
+        .ok()
+        .and_then(|out| parse_ahead_behind(&out));
+    match counts {
+        Some((_, 0)) => CheckResult {
+            name: "Base branch".into(),
+            status: CheckStatus::Pass,
+            message: format!("{} is up to date with origin", base),
+            required: true,
+            details: None,
+        },
+        Some((_, behind)) => {
+            let current = git_output(&["branch", "--show-current"]).unwrap_or_default();
+            let fix = if current == base {
+                "git pull --ff-only".to_string()
+            } else {
+                format!("git fetch origin {}:{}", base, base)
+            };
+            CheckResult {
+                name: "Base branch".into(),
+                status: CheckStatus::Fail,
+                message: format!("{} is {} commit(s) behind origin/{}", base, behind, base),
+                required: true,
+                details: Some(format!("Update it first: {}", fix)),
+            }
+        }
+        None => CheckResult {
+            name: "Base branch".into(),
+            status: CheckStatus::Warn,
+            message: format!("Could not compare {} with origin/{}", base, base),
+            required: true,
+            details: None,
+        },
+    }
+}
+
+/// Parse `git rev-list --left-right --count` output into (ahead, behind).
+fn parse_ahead_behind(output: &str) -> Option<(u32, u32)> {
+    let mut counts = output.split_whitespace().map(|n| n.parse().ok());
+    Some((counts.next()??, counts.next()??))
+}
+
+/// Check the `gh` CLI, which `mobius submit` needs on GitHub.
+pub fn check_gh(backend: Backend) -> CheckResult {
+    let required = backend == Backend::Github;
+    if check_command_exists("gh") {
+        CheckResult {
+            name: "GitHub CLI".into(),
+            status: CheckStatus::Pass,
+            message: "Installed".into(),
+            required,
+            details: None,
+        }
+    } else {
+        CheckResult {
+            name: "GitHub CLI".into(),
+            status: if required {
+                CheckStatus::Fail
+            } else {
+                CheckStatus::Warn
+            },
+            message: "Not found".into(),
+            required,
+            details: Some("Install: https://cli.github.com (used to open pull requests)".into()),
+        }
+    }
+}
+
+pub fn check_api_keys(backend: &Backend) -> CheckResult {
+    match backend {
+        Backend::Linear => match secrets::find_secret(secrets::token_vars(*backend)) {
+            Some(found) => CheckResult {
+                name: "API keys".into(),
+                status: CheckStatus::Pass,
+                message: found_message(&found),
+                required: true,
+                details: None,
+            },
+            None => CheckResult {
+                name: "API keys".into(),
+                status: CheckStatus::Fail,
+                message: "LINEAR_API_KEY not set".into(),
+                required: true,
+                details: Some(
+                    "Set LINEAR_API_KEY environment variable or run 'mobius auth login linear'"
+                        .into(),
+                ),
+            },
+        },
+        Backend::Jira => {
+            let has_host = std::env::var("JIRA_HOST").is_ok();
+            let email = secrets::find_secret(&["JIRA_EMAIL"]);
+            let token = secrets::find_secret(secrets::token_vars(*backend));
+
+            match (has_host, email, token) {
+                (true, Some(_), Some(token)) => CheckResult {
+                    name: "API keys".into(),
+                    status: CheckStatus::Pass,
+                    message: format!("JIRA_HOST, JIRA_EMAIL, {}", found_message(&token)),
+                    required: true,
+                    details: None,
+                },
+                (has_host, email, token) => {
+                    let mut missing = Vec::new();
+                    if !has_host {
+                        missing.push("JIRA_HOST");
+                    }
+                    if email.is_none() {
+                        missing.push("JIRA_EMAIL");
+                    }
+                    if token.is_none() {
+                        missing.push("JIRA_API_TOKEN");
+                    }
+                    CheckResult {
+                        name: "API keys".into(),
+                        status: CheckStatus::Fail,
+                        message: format!("Missing: {}", missing.join(", ")),
+                        required: true,
+                        details: Some(
+                            "Set Jira environment variables or run 'mobius auth login jira'".into(),
+                        ),
+                    }
+                }
+            }
+        }
+        Backend::Github => match secrets::find_secret(secrets::token_vars(*backend)) {
+            Some(found) => CheckResult {
+                name: "API keys".into(),
+                status: CheckStatus::Pass,
+                message: found_message(&found),
+                required: true,
+                details: None,
+            },
+            None => CheckResult {
+                name: "API keys".into(),
+                status: CheckStatus::Fail,
+                message: "GITHUB_TOKEN not set".into(),
+                required: true,
+                details: Some(
+                    "Set GITHUB_TOKEN environment variable or run 'mobius auth login github'"
+                        .into(),
+                ),
+            },
+        },
+        Backend::Gitlab => match secrets::find_secret(secrets::token_vars(*backend)) {
+            Some(found) => CheckResult {
+                name: "API keys".into(),
+                status: CheckStatus::Pass,
+                message: found_message(&found),
+                required: true,
+                details: None,
+            },
+            None => CheckResult {
+                name: "API keys".into(),
+                status: CheckStatus::Fail,
+                message: "GITLAB_TOKEN not set".into(),
+                required: true,
+                details: Some(
+                    "Set GITLAB_TOKEN environment variable or run 'mobius auth login gitlab'"
+                        .into(),
+                ),
+            },
+        },
+        Backend::Local => CheckResult {
+            name: "API keys".into(),
+            status: CheckStatus::Pass,
+            message: "Not required for local backend".into(),
+            required: true,
+            details: None,
+        },
+    }
+}
+
+/// e.g. `GITHUB_TOKEN set` or `GITHUB_TOKEN set (keychain)`
+fn found_message(found: &secrets::FoundSecret) -> String {
+    match found.source {
+        secrets::SecretSource::Env => format!("{} set", found.var),
+        secrets::SecretSource::Keychain => format!("{} set (keychain)", found.var),
+    }
+}
+
+/// Why a token could not be validated
+enum TokenProblem {
+    /// The tracker answered and refused the credentials
+    Rejected(String),
+    /// The tracker could not be reached or answered unexpectedly
+    Unreachable(String),
+}
+
+async fn fetch_token_owner(backend: Backend) -> Result<String, TokenProblem> {
+    let unreachable = |e: &dyn std::fmt::Display| TokenProblem::Unreachable(e.to_string());
+    match backend {
+        Backend::Linear => {
+            let client = LinearClient::new().map_err(|e| TokenProblem::Rejected(e.to_string()))?;
+            client.fetch_linear_viewer().await.map_err(|e| match e {
+                LinearError::AuthFailed | LinearError::PermissionDenied => {
+                    TokenProblem::Rejected(e.to_string())
+                }
+                e => unreachable(&e),
+            })
+        }
+        Backend::Jira => {
+            let client = JiraClient::new().map_err(|e| TokenProblem::Rejected(e.to_string()))?;
+            client.fetch_jira_myself().await.map_err(|e| match e {
+                JiraError::AuthFailed | JiraError::PermissionDenied => {
+                    TokenProblem::Rejected(e.to_string())
+                }
+                e => unreachable(&e),
+            })
+        }
+        Backend::Github => {
+            let client = GithubClient::new().map_err(|e| TokenProblem::Rejected(e.to_string()))?;
+            client.fetch_github_viewer().await.map_err(|e| match e {
+                GithubError::AuthFailed | GithubError::PermissionDenied => {
+                    TokenProblem::Rejected(e.to_string())
+                }
+                e => unreachable(&e),
+            })
+        }
+        Backend::Gitlab => {
+            let client = GitlabClient::new().map_err(|e| TokenProblem::Rejected(e.to_string()))?;
+            client.fetch_gitlab_viewer().await.map_err(|e| match e {
+                GitlabError::AuthFailed | GitlabError::PermissionDenied => {
+                    TokenProblem::Rejected(e.to_string())
+                }
+                e => unreachable(&e),
+            })
+        }
+        Backend::Local => Ok(String::new()),
+    }
+}
+
+/// Check that the backend's credentials are set and accepted by its API.
+pub fn check_api_token(backend: Backend) -> CheckResult {
+    if backend != Backend::Local && crate::offline::is_offline() {
+        return CheckResult {
+            name: "API keys".into(),
+            status: CheckStatus::Warn,
+            message: "Offline; not checking credentials".into(),
+            required: false,
+            details: Some(
+                "Backend updates are queued until 'mobius push' runs while online".into(),
+            ),
+        };
+    }
+    let keys = check_api_keys(&backend);
+    if !matches!(keys.status, CheckStatus::Pass) || backend == Backend::Local {
+        return keys;
+    }
+    let owner = match tokio::runtime::Runtime::new() {
+        Ok(rt) => rt.block_on(fetch_token_owner(backend)),
+        Err(e) => Err(TokenProblem::Unreachable(e.to_string())),
+    };
+    match owner {
+        Ok(owner) => CheckResult {
+            name: "API keys".into(),
+            status: CheckStatus::Pass,
+            message: format!("Authenticated with {} as {}", backend, owner),
+            required: true,
+            details: None,
+        },
+        Err(TokenProblem::Rejected(reason)) => CheckResult {
+            name: "API keys".into(),
+            status: CheckStatus::Fail,
+            message: format!("{} rejected the credentials", backend),
+            required: true,
+            details: Some(reason),
+        },
+        Err(TokenProblem::Unreachable(reason)) => CheckResult {
+            name: "API keys".into(),
+            status: CheckStatus::Warn,
+            message: format!("Could not reach {} to validate the token", backend),
+            required: true,
+            details: Some(reason),
+        },
+    }
+}
+
+pub fn check_tmux() -> CheckResult {
+    if check_command_exists("tmux") {
+        let version = check_command_version("tmux").unwrap_or_else(|| "unknown".into());
+        CheckResult {
+            name: "tmux".into(),
+            status: CheckStatus::Pass,
+            message: format!("Installed ({})", version),
+            required: false,
+            details: None,
+        }
+    } else {
+        CheckResult {
+            name: "tmux".into(),
+            status: CheckStatus::Warn,
+            message: "Not found".into(),
+            required: false,
+            details: Some("Install: brew install tmux (macOS) or apt install tmux (Linux)".into()),
+        }
+    }
+}
+
+pub fn check_cclean() -> CheckResult {
+    if check_command_exists("cclean") {
+        CheckResult {
+            name: "cclean".into(),
+            status: CheckStatus::Pass,
+            message: "Installed".into(),
+            required: false,
+            details: None,
+        }
+    } else {
+        CheckResult {
+            name: "cclean".into(),
+            status: CheckStatus::Warn,
+            message: "Not found".into(),
+            required: false,
+            details: Some("Install: npm install -g cclean".into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runtime_cli_spec_uses_expected_command_and_label() {
+        let claude = runtime_cli_spec(AgentRuntime::Claude);
+        assert_eq!(claude.command, "claude");
+        assert_eq!(claude.display_name, "Claude CLI");
+
+        let opencode = runtime_cli_spec(AgentRuntime::Opencode);
+        assert_eq!(opencode.command, "opencode");
+        assert_eq!(opencode.display_name, "OpenCode CLI");
+
+        let codex = runtime_cli_spec(AgentRuntime::Codex);
+        assert_eq!(codex.command, "codex");
+        assert_eq!(codex.display_name, "Codex CLI");
+    }
+
+    #[test]
+    fn opencode_runtime_does_not_require_claude_cli() {
+        let result = check_runtime_cli_with(
+            AgentRuntime::Opencode,
+            |command| command == "opencode",
+            |_| Some("opencode 1.0.0".to_string()),
+        );
+
+        assert!(matches!(result.status, CheckStatus::Pass));
+        assert_eq!(result.name, "OpenCode CLI");
+        assert!(result.message.contains("opencode 1.0.0"));
+    }
+
+    #[test]
+    fn opencode_runtime_failure_uses_opencode_install_hint() {
+        let result = check_runtime_cli_with(AgentRuntime::Opencode, |_| false, |_| None);
+
+        assert!(matches!(result.status, CheckStatus::Fail));
+        assert_eq!(result.name, "OpenCode CLI");
+        assert_eq!(result.message, "Not found");
+        assert!(result
+            .details
+            .as_deref()
+            .unwrap_or_default()
+            .contains("opencode"));
+    }
+
+    #[test]
+    fn parse_ahead_behind_reads_left_right_counts() {
+        assert_eq!(parse_ahead_behind("2\t5\n"), Some((2, 5)));
+        assert_eq!(parse_ahead_behind("0 0"), Some((0, 0)));
+        assert_eq!(parse_ahead_behind("fatal"), None);
+    }
+
+    #[test]
+    fn loop_runtimes_includes_routed_runtimes_once() {
+        let config: LoopConfig = serde_yaml::from_str(
+            "runtime: claude\nexecution:\n  runtime_rules:\n    - min_complexity: 7\n      runtime: aider\n    - runtime: claude\n",
+        )
+        .unwrap();
+        assert_eq!(
+            loop_runtimes(&config),
+            vec![AgentRuntime::Claude, AgentRuntime::Aider]
+        );
+    }
+}
@@ -0,0 +1,336 @@
+//! Repository primer for agent warm-start.
+//!
+//! Build commands, a directory map, and convention notes are extracted once per
+//! repository and cached in `.mobius/state/primer.json`. The primer is embedded in
+//! every issue context so agents start from a summary instead of re-exploring
+//! the tree. The cache is regenerated whenever a key file (manifests, task
+//! runners, agent instructions) or the top-level layout changes.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::context::atomic_write_json;
+use crate::project_detector::detect_project_info;
+use crate::types::enums::BuildSystem;
+use crate::workspace::detect_workspace;
+
+/// Files whose content invalidates the cached primer
+const KEY_FILES: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pnpm-workspace.yaml",
+    "justfile",
+    "Makefile",
+    "pyproject.toml",
+    "go.mod",
+    "build.gradle",
+    "build.gradle.kts",
+    "tsconfig.json",
+    "CLAUDE.md",
+    "AGENTS.md",
+    "CONTRIBUTING.md",
+    ".editorconfig",
+    "rustfmt.toml",
+    ".prettierrc",
+    "biome.json",
+    "eslint.config.js",
+    ".eslintrc.json",
+];
+
+/// Agent-instruction files quoted in the conventions section
+const CONVENTION_FILES: &[&str] = &["CLAUDE.md", "AGENTS.md", "CONTRIBUTING.md"];
+
+/// Formatter and linter configs listed by name
+const STYLE_FILES: &[&str] = &[
+    ".editorconfig",
+    "rustfmt.toml",
+    ".prettierrc",
+    "biome.json",
+    "eslint.config.js",
+    ".eslintrc.json",
+    "tsconfig.json",
+];
+
+/// Directories never shown in the directory map
+const SKIPPED_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    "vendor",
+    "__pycache__",
+];
+
+/// Lines quoted from each convention file before truncating
+const MAX_CONVENTION_LINES: usize = 60;
+
+/// Entries listed per directory in the directory map
+const MAX_MAP_ENTRIES: usize = 15;
+
+/// Cached repository primer (`.mobius/state/primer.json`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepoPrimer {
+    /// Hash of the key files and top-level layout the primer was built from
+    pub fingerprint: String,
+    pub generated_at: String,
+    /// Markdown primer text
+    pub content: String,
+}
+
+/// Path of the cached primer for the repository at `root`.
+pub fn get_primer_path(root: &Path) -> PathBuf {
+    root.join(".mobius").join("state").join("primer.json")
+}
+
+/// Read the cached primer, if any.
+pub fn read_primer(root: &Path) -> Option<RepoPrimer> {
+    let content = fs::read_to_string(get_primer_path(root)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Return the cached primer, regenerating it when stale or when `force` is set.
+pub fn load_or_generate_primer(root: &Path, force: bool) -> Result<RepoPrimer> {
+    let fingerprint = primer_fingerprint(root);
+    if !force {
+        if let Some(cached) = read_primer(root).filter(|p| p.fingerprint == fingerprint) {
+            return Ok(cached);
+        }
+    }
+
+    let primer = RepoPrimer {
+        fingerprint,
+        generated_at: Utc::now().to_rfc3339(),
+        content: generate_primer(root)?,
+    };
+    atomic_write_json(&get_primer_path(root), &primer)
+        .context("Failed to write repository primer")?;
+    Ok(primer)
+}
+
+/// Hash the key files and the top-level directory layout.
+pub fn primer_fingerprint(root: &Path) -> String {
+    let mut hasher = DefaultHasher::new();
+    for name in KEY_FILES {
+        if let Ok(bytes) = fs::read(root.join(name)) {
+            name.hash(&mut hasher);
+            bytes.hash(&mut hasher);
+        }
+    }
+    for (name, children) in layout(root) {
+        name.hash(&mut hasher);
+        children.hash(&mut hasher);
+    }
+    format!("{:016x}", hasher.finish())
+}
+
+/// Build the Markdown primer for the repository at `root`.
+pub fn generate_primer(root: &Path) -> Result<String> {
+    let root_str = root.to_string_lossy();
+    let info = detect_project_info(&root_str)?;
+    let mut out = String::from("# Repository primer\n\n");
+    out.push_str(
+        "Generated by mobius from the repository layout. Use it instead of re-exploring the tree.\n\n",
+    );
+
+    out.push_str("## Build\n\n");
+    if info.build_system != BuildSystem::Unknown {
+        out.push_str(&format!(
+            "- Build system: {}\n",
+            format!("{:?}", info.build_system).to_lowercase()
+        ));
+    }
+    let commands = &info.available_commands;
+    for (label, command) in [
+        ("Test", &commands.test),
+        ("Typecheck", &commands.typecheck),
+        ("Lint", &commands.lint),
+        ("Build", &commands.build),
+    ] {
+        if let Some(command) = command {
+            out.push_str(&format!("- {}: `{}`\n", label, command));
+        }
+    }
+    if let Some(workspace) = detect_workspace(root) {
+        out.push_str("- Workspace packages:\n");
+        for package in &workspace.packages {
+            out.push_str(&format!("  - `{}` ({})\n", package.name, package.path));
+        }
+    }
+    out.push('\n');
+
+    out.push_str("## Directory map\n\n");
+    for (name, children) in layout(root) {
+        match children {
+            Some(children) if !children.is_empty() => {
+                out.push_str(&format!("- `{}/`: {}\n", name, format_entries(&children)));
+            }
+            Some(_) => out.push_str(&format!("- `{}/`\n", name)),
+            None => out.push_str(&format!("- `{}`\n", name)),
+        }
+    }
+    out.push('\n');
+
+    let style: Vec<&str> = STYLE_FILES
+        .iter()
+        .copied()
+        .filter(|f| root.join(f).exists())
+        .collect();
+    let conventions: Vec<(&str, String)> = CONVENTION_FILES
+        .iter()
+        .filter_map(|f| Some((*f, fs::read_to_string(root.join(f)).ok()?)))
+        .collect();
+    if !style.is_empty() || !conventions.is_empty() {
+        out.push_str("## Conventions\n\n");
+        if !style.is_empty() {
+            out.push_str(&format!("- Style config: {}\n\n", style.join(", ")));
+        }
+        for (name, content) in conventions {
+            out.push_str(&format!("### {}\n\n", name));
+            let lines: Vec<&str> = content.lines().collect();
+            for line in lines.iter().take(MAX_CONVENTION_LINES) {
+                out.push_str(line);
+                out.push('\n');
+            }
+            if lines.len() > MAX_CONVENTION_LINES {
+                out.push_str(&format!("\n_(truncated; see {})_\n", name));
+            }
+            out.push('\n');
+        }
+    }
+
+    Ok(out.trim_end().to_string() + "\n")
+}
+
+/// Top-level entries with the names of each directory's children.
+///
+/// Files map to `None`; hidden entries and build output are skipped.
+fn layout(root: &Path) -> Vec<(String, Option<Vec<String>>)> {
+    list_dir(root)
+        .into_iter()
+        .map(|(name, is_dir)| {
+            let children = is_dir.then(|| {
+                list_dir(&root.join(&name))
+                    .into_iter()
+                    .map(|(child, child_is_dir)| {
+                        if child_is_dir {
+                            format!("{}/", child)
+                        } else {
+                            child
+                        }
+                    })
+                    .collect()
+            });
+            (name, children)
+        })
+        .collect()
+}
+
+/// Sorted visible entries of `dir`, directories first.
+fn list_dir(dir: &Path) -> Vec<(String, bool)> {
+    let mut entries: Vec<(String, bool)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| {
+                    let is_dir = e.file_type().map(|t| t.is_dir()).unwrap_or(false);
+                    (e.file_name().to_string_lossy().to_string(), is_dir)
+                })
+                .filter(|(name, is_dir)| {
+                    let skipped = *is_dir && SKIPPED_DIRS.contains(&name.as_str());
+                    !name.starts_with('.') && !skipped
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    entries
+}
+
+fn format_entries(entries: &[String]) -> String {
+    let mut shown: Vec<String> = entries
+        .iter()
+        .take(MAX_MAP_ENTRIES)
+        .map(|e| format!("`{}`", e))
+        .collect();
+    if entries.len() > MAX_MAP_ENTRIES {
+        shown.push(format!("… {} more", entries.len() - MAX_MAP_ENTRIES));
+    }
+    shown.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_file(dir: &Path, name: &str, content: &str) {
+        let file_path = dir.join(name);
+        fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        fs::write(file_path, content).unwrap();
+    }
+
+    fn sample_repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        create_file(
+            dir.path(),
+            "justfile",
+            "test:\n\tcargo test\n\nlint:\n\tcargo clippy\n",
+        );
+        create_file(dir.path(), "Cargo.toml", "[package]\nname = \"app\"\n");
+        create_file(dir.path(), "src/main.rs", "fn main() {}\n");
+        create_file(dir.path(), "src/commands/mod.rs", "");
+        create_file(dir.path(), "target/debug/app", "");
+        create_file(dir.path(), "CLAUDE.md", "Use anyhow for errors.\n");
+        create_file(dir.path(), "rustfmt.toml", "edition = \"2021\"\n");
+        dir
+    }
+
+    #[test]
+    fn test_generate_primer_sections() {
+        let dir = sample_repo();
+        let primer = generate_primer(dir.path()).unwrap();
+
+        assert!(primer.starts_with("# Repository primer"));
+        assert!(primer.contains("- Build system: just"));
+        assert!(primer.contains("- Test: `just test`"));
+        assert!(primer.contains("- Lint: `just lint`"));
+        assert!(primer.contains("- `src/`: `commands/`, `main.rs`"));
+        assert!(!primer.contains("target"));
+        assert!(primer.contains("- Style config: rustfmt.toml"));
+        assert!(primer.contains("### CLAUDE.md\n\nUse anyhow for errors."));
+    }
+
+    #[test]
+    fn test_load_or_generate_primer_refreshes_on_key_file_change() {
+        let dir = sample_repo();
+        let first = load_or_generate_primer(dir.path(), false).unwrap();
+        assert_eq!(read_primer(dir.path()), Some(first.clone()));
+
+        // Unrelated source edits keep the cache
+        create_file(dir.path(), "src/main.rs", "fn main() { println!(); }\n");
+        let cached = load_or_generate_primer(dir.path(), false).unwrap();
+        assert_eq!(cached, first);
+
+        // Key files and new top-level directories invalidate it
+        create_file(
+            dir.path(),
+            "CLAUDE.md",
+            "Prefer thiserror for client errors.\n",
+        );
+        let refreshed = load_or_generate_primer(dir.path(), false).unwrap();
+        assert_ne!(refreshed.fingerprint, first.fingerprint);
+        assert!(refreshed.content.contains("Prefer thiserror"));
+
+        create_file(dir.path(), "docs/guide.md", "");
+        let refreshed_again = load_or_generate_primer(dir.path(), false).unwrap();
+        assert_ne!(refreshed_again.fingerprint, refreshed.fingerprint);
+        assert!(refreshed_again.content.contains("- `docs/`: `guide.md`"));
+    }
+}
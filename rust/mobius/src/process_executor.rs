@@ -0,0 +1,509 @@
+//! Process-based agent execution (no tmux required)
+//!
+//! Spawns each agent as a child process with piped stdout/stderr. Completion
+//! comes from `$MOBIUS_RESULT_FILE` when the agent writes it, otherwise from
+//! the same STATUS markers the tmux executor reads from pane content. Runtimes
+//! without either (Aider) complete on their exit status.
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Instant;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::mpsc;
+use tokio::time::{interval, sleep, timeout, Duration};
+use tracing::Instrument;
+
+use crate::completion::{agent_result_to_execution, read_agent_result};
+use crate::executor::{
+    build_agent_command, calculate_parallelism, config_timeout_ms, error_summary_regex,
+    parse_agent_output, task_timeout_ms, AgentCommand, ExecutionContext, ExecutionResult,
+    ExecutionStatus, StatusPatterns, INTERRUPTED_ERROR,
+};
+use crate::shutdown;
+use crate::stream_json;
+use crate::telemetry;
+use crate::types::{CompletionProtocol, SubTask};
+
+/// Number of trailing output lines kept for status detection and error reports
+const OUTPUT_TAIL_LINES: usize = 200;
+
+/// How long an agent may keep running after reporting its status
+const EXIT_GRACE_MS: u64 = 5000;
+
+/// Polling interval for the agent's result file
+const RESULT_POLL_MS: u64 = 1000;
+
+/// Executes agents as child processes instead of tmux panes.
+pub struct ProcessExecutor<'a> {
+    context: ExecutionContext<'a>,
+    timeout_ms: u64,
+}
+
+impl<'a> ProcessExecutor<'a> {
+    pub fn new(context: ExecutionContext<'a>, timeout_ms: Option<u64>) -> Self {
+        Self {
+            context,
+            timeout_ms: timeout_ms.unwrap_or_else(|| config_timeout_ms(context.config)),
+        }
+    }
+
+    /// Execute tasks in parallel as child processes.
+    ///
+    /// Mirrors `executor::execute_parallel`: runs up to `max_parallel_agents`
+    /// agents and returns one result per spawned task.
+    pub async fn execute_parallel(&self, tasks: &[SubTask]) -> Vec<ExecutionResult> {
+        let actual_parallelism = calculate_parallelism(tasks.len(), self.context.config);
+        if actual_parallelism == 0 {
+            return vec![];
+        }
+
+        let futures: Vec<_> = tasks[..actual_parallelism]
+            .iter()
+            .map(|task| self.run_task(task))
+            .collect();
+
+        futures::future::join_all(futures).await
+    }
+
+    /// Run a single agent to completion, timeout, or exit.
+    pub async fn run_task(&self, task: &SubTask) -> ExecutionResult {
+        let span = telemetry::agent_span(task, "process");
+        let agent = build_agent_command(task, self.context);
+        let result = self
+            .run_command(task, &agent)
+            .instrument(span.clone())
+            .await;
+        telemetry::record_agent_result(&span, &result);
+        result
+    }
+
+    /// Run `agent` for `task`, watching its result file and output stream.
+    async fn run_command(&self, task: &SubTask, agent: &AgentCommand) -> ExecutionResult {
+        let start_time = Instant::now();
+        let output_file = agent.output_file.as_ref();
+        let mut child = match spawn_shell(&agent.command) {
+            Ok(child) => child,
+            Err(e) => {
+                return error_result(
+                    task,
+                    start_time,
+                    format!("Failed to spawn agent: {e}"),
+                    None,
+                )
+            }
+        };
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(forward_lines(stdout, tx.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward_lines(stderr, tx));
+        }
+
+        let protocol = self.context.config.completion_protocol;
+        let patterns = StatusPatterns::new();
+        let error_summary_re = error_summary_regex();
+        let mut tail = OutputTail::default();
+        let deadline = sleep(Duration::from_millis(task_timeout_ms(
+            task,
+            self.timeout_ms,
+        )));
+        tokio::pin!(deadline);
+        let mut result_poll = interval(Duration::from_millis(RESULT_POLL_MS));
+
+        // Prefer the structured result file; scrape output only as a fallback.
+        let detect = |tail: &OutputTail| -> Option<ExecutionResult> {
+            if let Some(reported) = agent.result_file.as_deref().and_then(read_agent_result) {
+                return Some(agent_result_to_execution(&reported, task, start_time, None));
+            }
+            if protocol == CompletionProtocol::ResultFile || agent.completes_on_exit {
+                return None;
+            }
+            parse_agent_output(
+                &tail.content(),
+                task,
+                start_time,
+                "",
+                &patterns,
+                &error_summary_re,
+            )
+        };
+
+        loop {
+            tokio::select! {
+                line = rx.recv() => match line {
+                    Some(line) => {
+                        tail.push(line);
+                        if let Some(result) = detect(&tail) {
+                            // Give the runtime a moment to flush and exit on its own.
+                            if timeout(Duration::from_millis(EXIT_GRACE_MS), child.wait())
+                                .await
+                                .is_err()
+                            {
+                                stop_agent(&mut child, agent).await;
+                            }
+                            return finish(result, output_file);
+                        }
+                    }
+                    None => {
+                        // Both streams closed: the agent has exited (or is about to).
+                        let exit = child.wait().await;
+                        if let Some(result) = detect(&tail) {
+                            return finish(result, output_file);
+                        }
+                        if agent.completes_on_exit {
+                            if let Ok(status) = exit {
+                                return exit_result(task, start_time, status, tail.content());
+                            }
+                        }
+                        let reason = match exit {
+                            Ok(status) => format!(
+                                "Agent exited ({}) without reporting a completion status",
+                                status
+                            ),
+                            Err(e) => format!("Failed to wait for agent: {e}"),
+                        };
+                        let mut result =
+                            error_result(task, start_time, reason, Some(tail.content()));
+                        apply_output_tokens(&mut result, output_file);
+                        return result;
+                    }
+                },
+                _ = result_poll.tick(), if agent.result_file.is_some() => {
+                    if let Some(reported) = agent.result_file.as_deref().and_then(read_agent_result) {
+                        if timeout(Duration::from_millis(EXIT_GRACE_MS), child.wait())
+                            .await
+                            .is_err()
+                        {
+                            stop_agent(&mut child, agent).await;
+                        }
+                        let result = agent_result_to_execution(&reported, task, start_time, None);
+                        return finish(result, output_file);
+                    }
+                }
+                _ = shutdown::interrupted() => {
+                    stop_agent(&mut child, agent).await;
+                    let mut result = error_result(
+                        task,
+                        start_time,
+                        INTERRUPTED_ERROR.to_string(),
+                        Some(tail.content()),
+                    );
+                    apply_output_tokens(&mut result, output_file);
+                    return result;
+                }
+                _ = &mut deadline => {
+                    stop_agent(&mut child, agent).await;
+                    let elapsed = start_time.elapsed();
+                    let mut result = error_result(
+                        task,
+                        start_time,
+                        format!("Agent timed out after {} seconds", elapsed.as_secs()),
+                        Some(tail.content()),
+                    );
+                    result.status = ExecutionStatus::Timeout;
+                    if let Some(tokens) = output_file
+                        .and_then(|f| stream_json::parse_current_tokens(f))
+                    {
+                        result.input_tokens = Some(tokens.input_tokens);
+                        result.output_tokens = Some(tokens.output_tokens);
+                    }
+                    return result;
+                }
+            }
+        }
+    }
+}
+
+// --- Internal functions ---
+
+/// Bounded buffer of the most recent output lines.
+#[derive(Default)]
+struct OutputTail {
+    lines: VecDeque<String>,
+}
+
+impl OutputTail {
+    fn push(&mut self, line: String) {
+        if self.lines.len() == OUTPUT_TAIL_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    fn content(&self) -> String {
+        let mut content = String::new();
+        for line in &self.lines {
+            content.push_str(line);
+            content.push('\n');
+        }
+        content
+    }
+}
+
+/// Kill the agent's shell and, when sandboxed, its container.
+async fn stop_agent(child: &mut Child, agent: &AgentCommand) {
+    let _ = child.kill().await;
+    if let Some(container) = &agent.container {
+        container.remove().await;
+    }
+}
+
+/// Spawn `command` under `sh -c` with piped output.
+fn spawn_shell(command: &str) -> std::io::Result<Child> {
+    Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+}
+
+/// Forward each line read from `reader` to `tx` until EOF.
+async fn forward_lines<R>(reader: R, tx: mpsc::UnboundedSender<String>)
+where
+    R: AsyncRead + Unpin,
+{
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if tx.send(line).is_err() {
+            break;
+        }
+    }
+}
+
+/// Finalize a parsed result: no pane is involved, and tokens come from the output file.
+fn finish(mut result: ExecutionResult, output_file: Option<&PathBuf>) -> ExecutionResult {
+    result.pane_id = None;
+    apply_output_tokens(&mut result, output_file);
+    result
+}
+
+fn apply_output_tokens(result: &mut ExecutionResult, output_file: Option<&PathBuf>) {
+    if let Some(output_file) = output_file {
+        let tokens = stream_json::parse_final_tokens(output_file)
+            .or_else(|| stream_json::parse_current_tokens(output_file));
+        if let Some(usage) = tokens {
+            result.input_tokens = Some(usage.input_tokens);
+            result.output_tokens = Some(usage.output_tokens);
+        }
+    }
+}
+
+fn error_result(
+    task: &SubTask,
+    start_time: Instant,
+    error: String,
+    raw_output: Option<String>,
+) -> ExecutionResult {
+    ExecutionResult {
+        task_id: task.id.clone(),
+        identifier: task.identifier.clone(),
+        success: false,
+        status: ExecutionStatus::Error,
+        token_usage: None,
+        duration_ms: start_time.elapsed().as_millis() as u64,
+        error: Some(error),
+        pane_id: None,
+        raw_output: raw_output.filter(|o| !o.is_empty()),
+        input_tokens: None,
+        output_tokens: None,
+    }
+}
+
+/// Result for an agent whose exit status is its completion report.
+fn exit_result(
+    task: &SubTask,
+    start_time: Instant,
+    status: std::process::ExitStatus,
+    output: String,
+) -> ExecutionResult {
+    if status.success() {
+        ExecutionResult {
+            success: true,
+            status: ExecutionStatus::SubtaskComplete,
+            error: None,
+            ..error_result(task, start_time, String::new(), Some(output))
+        }
+    } else {
+        error_result(
+            task,
+            start_time,
+            format!("Agent exited ({})", status),
+            Some(output),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{AgentRuntime, ExecutionConfig, TaskStatus, TaskType};
+
+    fn make_task(identifier: &str) -> SubTask {
+        SubTask {
+            id: format!("id-{identifier}"),
+            identifier: identifier.to_string(),
+            title: "Test task".to_string(),
+            status: TaskStatus::Ready,
+            blocked_by: vec![],
+            blocks: vec![],
+            git_branch_name: String::new(),
+            scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
+        }
+    }
+
+    async fn run_script(script: &str, timeout_ms: u64) -> ExecutionResult {
+        run_script_with_result(script, None, timeout_ms).await
+    }
+
+    async fn run_script_with_result(
+        script: &str,
+        result_file: Option<PathBuf>,
+        timeout_ms: u64,
+    ) -> ExecutionResult {
+        let config = ExecutionConfig::default();
+        let context = ExecutionContext {
+            runtime: AgentRuntime::Claude,
+            worktree_path: ".",
+            config: &config,
+            context_file_path: None,
+            model_override: None,
+            thinking_level_override: None,
+            output_dir: None,
+            result_dir: None,
+        };
+        let agent = AgentCommand {
+            command: script.to_string(),
+            output_file: None,
+            result_file,
+            completes_on_exit: false,
+            container: None,
+        };
+        ProcessExecutor::new(context, Some(timeout_ms))
+            .run_command(&make_task("LOC-001"), &agent)
+            .await
+    }
+
+    #[test]
+    fn test_output_tail_is_bounded() {
+        let mut tail = OutputTail::default();
+        for i in 0..(OUTPUT_TAIL_LINES + 10) {
+            tail.push(format!("line {i}"));
+        }
+        assert_eq!(tail.lines.len(), OUTPUT_TAIL_LINES);
+        assert!(tail.content().starts_with("line 10\n"));
+    }
+
+    #[tokio::test]
+    async fn test_detects_status_marker_in_stream() {
+        let result = run_script("echo working; echo 'STATUS: SUBTASK_COMPLETE'", 5000).await;
+        assert!(result.success);
+        assert_eq!(result.status, ExecutionStatus::SubtaskComplete);
+        assert!(result.pane_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_detects_verification_failure_on_stderr() {
+        let result = run_script(
+            "printf '### Error Summary\\nTests failed\\nSTATUS: VERIFICATION_FAILED\\n' >&2",
+            5000,
+        )
+        .await;
+        assert!(!result.success);
+        assert_eq!(result.status, ExecutionStatus::VerificationFailed);
+        assert_eq!(result.error.as_deref(), Some("Tests failed"));
+    }
+
+    #[tokio::test]
+    async fn test_exit_status_completes_exit_only_runtimes() {
+        let config = ExecutionConfig::default();
+        let context = ExecutionContext {
+            runtime: AgentRuntime::Aider,
+            worktree_path: ".",
+            config: &config,
+            context_file_path: None,
+            model_override: None,
+            thinking_level_override: None,
+            output_dir: None,
+            result_dir: None,
+        };
+        let executor = ProcessExecutor::new(context, Some(5000));
+        let run = |script: &str| AgentCommand {
+            command: script.to_string(),
+            output_file: None,
+            result_file: None,
+            completes_on_exit: true,
+            container: None,
+        };
+        let task = make_task("LOC-001");
+
+        // Markers are ignored; only the exit status counts
+        let result = executor
+            .run_command(&task, &run("echo 'STATUS: SUBTASK_COMPLETE'; exit 3"))
+            .await;
+        assert!(!result.success);
+        assert_eq!(result.status, ExecutionStatus::Error);
+        assert!(result.error.unwrap().contains("exit status: 3"));
+
+        let result = executor.run_command(&task, &run("echo applied edit")).await;
+        assert!(result.success);
+        assert_eq!(result.status, ExecutionStatus::SubtaskComplete);
+        assert!(result.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exit_without_status_is_error() {
+        let result = run_script("echo no marker here", 5000).await;
+        assert!(!result.success);
+        assert_eq!(result.status, ExecutionStatus::Error);
+        assert!(result.error.unwrap().contains("without reporting"));
+        assert!(result.raw_output.unwrap().contains("no marker here"));
+    }
+
+    #[tokio::test]
+    async fn test_result_file_preferred_over_output() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let result_file = tmp.path().join("LOC-001.json");
+        let script = format!(
+            "printf '{{\"status\":\"VERIFICATION_FAILED\",\"error\":\"lint\"}}' > '{}'; echo 'STATUS: SUBTASK_COMPLETE'",
+            result_file.display()
+        );
+
+        let result = run_script_with_result(&script, Some(result_file), 5000).await;
+        assert_eq!(result.status, ExecutionStatus::VerificationFailed);
+        assert_eq!(result.error.as_deref(), Some("lint"));
+    }
+
+    #[tokio::test]
+    async fn test_result_file_without_output_marker() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let result_file = tmp.path().join("LOC-001.json");
+        let script = format!(
+            "printf '{{\"status\":\"SUBTASK_COMPLETE\"}}' > '{}'",
+            result_file.display()
+        );
+
+        let result = run_script_with_result(&script, Some(result_file), 5000).await;
+        assert!(result.success);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_kills_agent() {
+        let started = Instant::now();
+        let result = run_script("sleep 5", 100).await;
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert_eq!(result.status, ExecutionStatus::Timeout);
+        assert!(result.error.unwrap().contains("timed out"));
+    }
+}
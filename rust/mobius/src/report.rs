@@ -0,0 +1,707 @@
+//! HTML run report.
+//!
+//! Renders `.mobius/issues/{id}/report.html` once a loop finishes: the
+//! dependency graph, a Gantt-style timeline of every attempt, token and cost
+//! usage per sub-task, failure details, and links to the commits each
+//! sub-task produced. The page is self-contained (inline CSS and SVG) so it
+//! can be archived or attached to a pull request as-is.
+//!
+//! Attempts come from the iteration log; commits are attributed like churn,
+//! by the task identifiers named in `base..head` commit messages.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+
+use crate::commands::stats::build_task_stats;
+use crate::context::get_html_report_path;
+use crate::cost::format_cost;
+use crate::local_state::{
+    read_iteration_log, read_local_subtasks_as_linear_issues, read_parent_spec, IterationLogEntry,
+    IterationStatus,
+};
+use crate::mermaid_renderer::get_status_color;
+use crate::tui::header::format_duration;
+use crate::types::enums::TaskStatus;
+use crate::types::task_graph::{build_task_graph, TaskGraph};
+
+/// Graph node size and spacing, in SVG pixels
+const NODE_WIDTH: usize = 180;
+const NODE_HEIGHT: usize = 44;
+const COLUMN_GAP: usize = 60;
+const ROW_GAP: usize = 16;
+
+/// Characters of a title shown inside a graph node
+const NODE_TITLE_CHARS: usize = 24;
+
+/// A sub-task as drawn in the report
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportTask {
+    pub identifier: String,
+    pub title: String,
+    pub status: TaskStatus,
+    /// Identifiers of the in-graph blockers
+    pub blocked_by: Vec<String>,
+    /// Longest chain of blockers above the task (0 = no blockers)
+    pub depth: usize,
+}
+
+/// A commit on the loop branch attributed to a sub-task
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskCommit {
+    pub task: String,
+    pub sha: String,
+    pub subject: String,
+    /// Web link when the origin remote is a known forge URL
+    pub url: Option<String>,
+}
+
+/// Everything the HTML report shows
+#[derive(Debug, Clone)]
+pub struct RunReport {
+    pub parent_id: String,
+    pub title: String,
+    pub generated_at: String,
+    /// Ordered by depth, then identifier
+    pub tasks: Vec<ReportTask>,
+    pub attempts: Vec<IterationLogEntry>,
+    pub commits: Vec<TaskCommit>,
+}
+
+/// Build the report data for one parent issue.
+pub fn build_run_report(
+    parent_id: &str,
+    title: &str,
+    graph: &TaskGraph,
+    attempts: Vec<IterationLogEntry>,
+    commits: Vec<TaskCommit>,
+) -> RunReport {
+    let depths = task_depths(graph);
+    let mut tasks: Vec<ReportTask> = graph
+        .tasks
+        .values()
+        .map(|task| ReportTask {
+            identifier: task.identifier.clone(),
+            title: task.title.clone(),
+            status: task.status,
+            blocked_by: task
+                .blocked_by
+                .iter()
+                .filter_map(|b| graph.tasks.get(b))
+                .map(|b| b.identifier.clone())
+                .collect(),
+            depth: depths.get(&task.id).copied().unwrap_or(0),
+        })
+        .collect();
+    tasks.sort_by(|a, b| a.depth.cmp(&b.depth).then(a.identifier.cmp(&b.identifier)));
+
+    RunReport {
+        parent_id: parent_id.to_string(),
+        title: title.to_string(),
+        generated_at: Utc::now().to_rfc3339(),
+        tasks,
+        attempts,
+        commits,
+    }
+}
+
+/// Read local state and git history, render the report, and write it to
+/// `.mobius/issues/{id}/report.html`.
+///
+/// Git failures (e.g. the branch no longer exists) are logged and the report
+/// is written without commit links.
+pub fn generate_html_report(
+    parent_id: &str,
+    repo_path: &Path,
+    base: &str,
+    head: &str,
+) -> Result<PathBuf> {
+    let spec = read_parent_spec(parent_id);
+    let identifier = spec
+        .as_ref()
+        .map(|s| s.identifier.clone())
+        .unwrap_or_else(|| parent_id.to_string());
+    let title = spec.map(|s| s.title).unwrap_or_default();
+    let issues = read_local_subtasks_as_linear_issues(parent_id);
+    let graph = build_task_graph(parent_id, &identifier, &issues);
+
+    let identifiers: Vec<String> = graph.tasks.values().map(|t| t.identifier.clone()).collect();
+    let commits = collect_task_commits(repo_path, base, head, &identifiers).unwrap_or_else(|e| {
+        tracing::warn!("Report: skipping git history for {}: {}", parent_id, e);
+        Vec::new()
+    });
+
+    let report = build_run_report(
+        &identifier,
+        &title,
+        &graph,
+        read_iteration_log(parent_id),
+        commits,
+    );
+    let path = get_html_report_path(parent_id);
+    fs::write(&path, render_html(&report))
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+/// Commits in `base..head` whose message names a task identifier, oldest
+/// first, linked to the origin remote's web UI when it has one.
+pub fn collect_task_commits(
+    repo_path: &Path,
+    base: &str,
+    head: &str,
+    identifiers: &[String],
+) -> Result<Vec<TaskCommit>> {
+    let output = Command::new("git")
+        .current_dir(repo_path)
+        .args([
+            "log",
+            "--reverse",
+            "--format=%x1e%H%x1f%s%x1f%b",
+            &format!("{}..{}", base, head),
+        ])
+        .output()
+        .context("failed to run git log")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git log {}..{} failed: {}",
+            base,
+            head,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let web_url = Command::new("git")
+        .current_dir(repo_path)
+        .args(["remote", "get-url", "origin"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| repo_web_url(String::from_utf8_lossy(&o.stdout).trim()));
+    Ok(parse_commit_log(
+        &String::from_utf8_lossy(&output.stdout),
+        identifiers,
+        web_url.as_deref(),
+    ))
+}
+
+/// Browser URL of a repository from its git remote URL (HTTPS, SSH, or
+/// scp-style), without credentials or the `.git` suffix.
+pub fn repo_web_url(remote: &str) -> Option<String> {
+    let remote = remote.trim().trim_end_matches('/');
+    let remote = remote.strip_suffix(".git").unwrap_or(remote);
+    let (host, path) = if let Some(rest) = remote
+        .strip_prefix("https://")
+        .or_else(|| remote.strip_prefix("http://"))
+        .or_else(|| remote.strip_prefix("ssh://"))
+    {
+        rest.split_once('/')?
+    } else {
+        // scp-style: git@github.com:owner/repo
+        remote.split_once(':')?
+    };
+    // Drop credentials and any SSH port
+    let host = host.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    if host.is_empty() || path.is_empty() || path.contains(':') {
+        return None;
+    }
+    Some(format!("https://{}/{}", host, path))
+}
+
+fn parse_commit_log(log: &str, identifiers: &[String], web_url: Option<&str>) -> Vec<TaskCommit> {
+    let patterns: Vec<(&String, Regex)> = identifiers
+        .iter()
+        .filter_map(|id| {
+            Regex::new(&format!(r"(?i)\b{}\b", regex::escape(id)))
+                .ok()
+                .map(|re| (id, re))
+        })
+        .collect();
+
+    let mut commits = Vec::new();
+    for record in log.split('\x1e').filter(|r| !r.trim().is_empty()) {
+        let mut fields = record.splitn(3, '\x1f');
+        let sha = fields.next().unwrap_or_default().trim().to_string();
+        let subject = fields.next().unwrap_or_default().trim().to_string();
+        let message = format!("{}\n{}", subject, fields.next().unwrap_or_default());
+        for (id, re) in &patterns {
+            if re.is_match(&message) {
+                commits.push(TaskCommit {
+                    task: (*id).clone(),
+                    sha: sha.clone(),
+                    subject: subject.clone(),
+                    url: web_url.map(|base| format!("{}/commit/{}", base, sha)),
+                });
+            }
+        }
+    }
+    commits
+}
+
+/// Longest chain of in-graph blockers above each task, keyed by task ID.
+/// Edges that close a cycle are ignored.
+fn task_depths(graph: &TaskGraph) -> HashMap<String, usize> {
+    fn depth(
+        graph: &TaskGraph,
+        id: &str,
+        memo: &mut HashMap<String, usize>,
+        visiting: &mut Vec<String>,
+    ) -> usize {
+        if let Some(d) = memo.get(id) {
+            return *d;
+        }
+        visiting.push(id.to_string());
+        let blockers: Vec<String> = graph.tasks[id]
+            .blocked_by
+            .iter()
+            .filter(|b| graph.tasks.contains_key(*b) && !visiting.contains(b))
+            .cloned()
+            .collect();
+        let d = blockers
+            .iter()
+            .map(|b| depth(graph, b, memo, visiting) + 1)
+            .max()
+            .unwrap_or(0);
+        visiting.pop();
+        memo.insert(id.to_string(), d);
+        d
+    }
+
+    let mut memo = HashMap::new();
+    let mut ids: Vec<&String> = graph.tasks.keys().collect();
+    ids.sort();
+    for id in ids {
+        depth(graph, id, &mut memo, &mut Vec::new());
+    }
+    memo
+}
+
+/// Render the report as a standalone HTML page.
+pub fn render_html(report: &RunReport) -> String {
+    let done = report
+        .tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Done)
+        .count();
+    let mut html = String::new();
+    let _ = write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{id} · mobius report</title>\n<style>{css}</style>\n</head>\n<body>\n\
+         <h1>{id} {title}</h1>\n\
+         <p class=\"meta\">{done}/{total} sub-tasks done · {attempts} attempt(s) · generated {at}</p>\n",
+        id = escape_html(&report.parent_id),
+        title = escape_html(&report.title),
+        css = STYLE,
+        done = done,
+        total = report.tasks.len(),
+        attempts = report.attempts.len(),
+        at = escape_html(&report.generated_at),
+    );
+    let _ = write!(
+        html,
+        "<h2>Dependency graph</h2>\n{}\n<h2>Timeline</h2>\n{}\n<h2>Usage</h2>\n{}\n\
+         <h2>Failures</h2>\n{}\n<h2>Commits</h2>\n{}\n</body>\n</html>\n",
+        render_graph_svg(&report.tasks),
+        render_timeline(report),
+        render_usage(report),
+        render_failures(&report.attempts),
+        render_commits(&report.commits),
+    );
+    html
+}
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem auto;max-width:1100px;\
+color:#222;padding:0 1rem}h1{font-size:1.5rem}h2{font-size:1.15rem;margin-top:2rem;\
+border-bottom:1px solid #ddd;padding-bottom:.25rem}.meta,.empty{color:#666}\
+table{border-collapse:collapse;width:100%;font-size:.9rem}th,td{text-align:left;\
+padding:.3rem .6rem;border-bottom:1px solid #eee}td.num{text-align:right;\
+font-variant-numeric:tabular-nums}pre{background:#f6f6f6;padding:.5rem;overflow-x:auto;\
+white-space:pre-wrap;margin:.25rem 0 1rem}code{font-size:.85rem}.graph{overflow-x:auto}\
+.gantt .row{display:flex;align-items:center;height:24px}.gantt .label{width:140px;\
+flex:none;font-size:.85rem}.gantt .track{position:relative;flex:1;height:16px;\
+background:#fafafa}.gantt .bar{position:absolute;top:0;height:16px;border-radius:3px;\
+min-width:2px}.success{background:#5cb85c}.partial{background:#f0ad4e}\
+.failed{background:#d9534f}";
+
+fn render_graph_svg(tasks: &[ReportTask]) -> String {
+    if tasks.is_empty() {
+        return "<p class=\"empty\">No sub-tasks.</p>".to_string();
+    }
+    let mut positions: HashMap<&str, (usize, usize)> = HashMap::new();
+    let mut rows_per_depth: HashMap<usize, usize> = HashMap::new();
+    for task in tasks {
+        let row = rows_per_depth.entry(task.depth).or_insert(0);
+        let x = task.depth * (NODE_WIDTH + COLUMN_GAP);
+        let y = *row * (NODE_HEIGHT + ROW_GAP);
+        positions.insert(&task.identifier, (x, y));
+        *row += 1;
+    }
+    let columns = rows_per_depth.len();
+    let rows = rows_per_depth.values().copied().max().unwrap_or(1);
+    let width = columns * (NODE_WIDTH + COLUMN_GAP) - COLUMN_GAP + 2;
+    let height = rows * (NODE_HEIGHT + ROW_GAP) - ROW_GAP + 2;
+
+    let mut svg = format!(
+        "<div class=\"graph\"><svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"-1 -1 {w} {h}\" font-size=\"12\">\n",
+        w = width,
+        h = height
+    );
+    for task in tasks {
+        let (x, y) = positions[task.identifier.as_str()];
+        for blocker in &task.blocked_by {
+            let Some(&(bx, by)) = positions.get(blocker.as_str()) else {
+                continue;
+            };
+            let (x1, y1) = (bx + NODE_WIDTH, by + NODE_HEIGHT / 2);
+            let (x2, y2) = (x, y + NODE_HEIGHT / 2);
+            let mid = (x1 + x2) / 2;
+            let _ = writeln!(
+                svg,
+                "<path d=\"M{x1},{y1} C{mid},{y1} {mid},{y2} {x2},{y2}\" fill=\"none\" stroke=\"#999\"/>"
+            );
+        }
+    }
+    for task in tasks {
+        let (x, y) = positions[task.identifier.as_str()];
+        let title: String = task.title.chars().take(NODE_TITLE_CHARS).collect();
+        let ellipsis = if task.title.chars().count() > NODE_TITLE_CHARS {
+            "…"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            svg,
+            "<g><title>{full}</title><rect x=\"{x}\" y=\"{y}\" width=\"{NODE_WIDTH}\" \
+             height=\"{NODE_HEIGHT}\" rx=\"6\" fill=\"{fill}\" stroke=\"#666\"/>\
+             <text x=\"{tx}\" y=\"{ty1}\" font-weight=\"bold\">{id}</text>\
+             <text x=\"{tx}\" y=\"{ty2}\">{title}{ellipsis}</text></g>",
+            full = escape_html(&format!(
+                "{} ({}): {}",
+                task.identifier, task.status, task.title
+            )),
+            fill = get_status_color(task.status),
+            tx = x + 8,
+            ty1 = y + 18,
+            ty2 = y + 34,
+            id = escape_html(&task.identifier),
+            title = escape_html(&title),
+        );
+    }
+    svg.push_str("</svg></div>");
+    svg
+}
+
+fn status_class(status: &IterationStatus) -> &'static str {
+    match status {
+        IterationStatus::Success => "success",
+        IterationStatus::Partial => "partial",
+        IterationStatus::Failed => "failed",
+    }
+}
+
+fn render_timeline(report: &RunReport) -> String {
+    let spans: Vec<(&IterationLogEntry, DateTime<Utc>, DateTime<Utc>)> = report
+        .attempts
+        .iter()
+        .filter_map(|e| e.span().map(|(start, end)| (e, start, end)))
+        .collect();
+    let (Some(first), Some(last)) = (
+        spans.iter().map(|s| s.1).min(),
+        spans.iter().map(|s| s.2).max(),
+    ) else {
+        return "<p class=\"empty\">No attempts recorded.</p>".to_string();
+    };
+    let total_ms = (last - first).num_milliseconds().max(1) as f64;
+
+    // One row per sub-task, in graph order, then any the graph no longer has
+    let mut order: Vec<&str> = report.tasks.iter().map(|t| t.identifier.as_str()).collect();
+    for (entry, _, _) in &spans {
+        if !order.contains(&entry.subtask_id.as_str()) {
+            order.push(&entry.subtask_id);
+        }
+    }
+
+    let mut html = format!(
+        "<div class=\"gantt\"><p class=\"meta\">{} → {} ({})</p>\n",
+        first.format("%Y-%m-%d %H:%M:%S UTC"),
+        last.format("%H:%M:%S"),
+        format_duration(total_ms as u64)
+    );
+    for task in order {
+        let bars: Vec<String> = spans
+            .iter()
+            .filter(|(e, _, _)| e.subtask_id == task)
+            .map(|(e, start, end)| {
+                let left = (*start - first).num_milliseconds() as f64 / total_ms * 100.0;
+                let width = (*end - *start).num_milliseconds() as f64 / total_ms * 100.0;
+                format!(
+                    "<div class=\"bar {class}\" style=\"left:{left:.2}%;width:{width:.2}%\" \
+                     title=\"attempt {attempt}: {status} in {duration}\"></div>",
+                    class = status_class(&e.status),
+                    attempt = e.attempt,
+                    status = status_class(&e.status),
+                    duration = format_duration((*end - *start).num_milliseconds() as u64),
+                )
+            })
+            .collect();
+        if bars.is_empty() {
+            continue;
+        }
+        let _ = writeln!(
+            html,
+            "<div class=\"row\"><div class=\"label\">{}</div><div class=\"track\">{}</div></div>",
+            escape_html(task),
+            bars.concat()
+        );
+    }
+    html.push_str("</div>");
+    html
+}
+
+fn render_usage(report: &RunReport) -> String {
+    let rows = build_task_stats(&report.parent_id, &report.attempts);
+    if rows.is_empty() {
+        return "<p class=\"empty\">No attempts recorded.</p>".to_string();
+    }
+    let mut html = String::from(
+        "<table><tr><th>Sub-task</th><th>Status</th><th>Attempts</th><th>Input tokens</th>\
+         <th>Output tokens</th><th>Time</th><th>Cost</th><th>Model</th></tr>\n",
+    );
+    for row in &rows {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+             <td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+            escape_html(&row.subtask_id),
+            status_class(&row.status),
+            row.attempts,
+            row.input_tokens,
+            row.output_tokens,
+            format_duration(row.duration_ms),
+            format_cost(row.cost_usd),
+            escape_html(row.model.as_deref().unwrap_or("")),
+        );
+    }
+    let _ = writeln!(
+        html,
+        "<tr><th>Total</th><th></th><th class=\"num\">{}</th><th class=\"num\">{}</th>\
+         <th class=\"num\">{}</th><th class=\"num\">{}</th><th class=\"num\">{}</th><th></th></tr></table>",
+        rows.iter().map(|r| r.attempts).sum::<usize>(),
+        rows.iter().map(|r| r.input_tokens).sum::<u64>(),
+        rows.iter().map(|r| r.output_tokens).sum::<u64>(),
+        format_duration(rows.iter().map(|r| r.duration_ms).sum()),
+        format_cost(rows.iter().map(|r| r.cost_usd).sum()),
+    );
+    html
+}
+
+fn render_failures(attempts: &[IterationLogEntry]) -> String {
+    let failures: Vec<&IterationLogEntry> = attempts
+        .iter()
+        .filter(|e| e.status != IterationStatus::Success)
+        .collect();
+    if failures.is_empty() {
+        return "<p class=\"empty\">No failed attempts.</p>".to_string();
+    }
+    let mut html = String::new();
+    for entry in failures {
+        let class = entry
+            .failure_class
+            .map(|c| format!(" · {}", c))
+            .unwrap_or_default();
+        let _ = writeln!(
+            html,
+            "<h3>{} attempt {} <small>({}{})</small></h3>\n<pre>{}</pre>",
+            escape_html(&entry.subtask_id),
+            entry.attempt,
+            status_class(&entry.status),
+            class,
+            escape_html(entry.error.as_deref().unwrap_or("No error recorded")),
+        );
+    }
+    html
+}
+
+fn render_commits(commits: &[TaskCommit]) -> String {
+    if commits.is_empty() {
+        return "<p class=\"empty\">No commits found on the loop branch.</p>".to_string();
+    }
+    let mut html =
+        String::from("<table><tr><th>Sub-task</th><th>Commit</th><th>Message</th></tr>\n");
+    for commit in commits {
+        let short: String = commit.sha.chars().take(7).collect();
+        let sha = match &commit.url {
+            Some(url) => format!(
+                "<a href=\"{}\"><code>{}</code></a>",
+                escape_html(url),
+                short
+            ),
+            None => format!("<code>{}</code>", short),
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+            escape_html(&commit.task),
+            sha,
+            escape_html(&commit.subject)
+        );
+    }
+    html.push_str("</table>");
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::task_graph::{LinearIssue, Relation, Relations};
+
+    fn issue(id: &str, status: &str, blocked_by: &[&str]) -> LinearIssue {
+        LinearIssue {
+            id: id.to_string(),
+            identifier: id.to_string(),
+            title: format!("Task {}", id),
+            status: status.to_string(),
+            git_branch_name: String::new(),
+            relations: Some(Relations {
+                blocked_by: blocked_by
+                    .iter()
+                    .map(|b| Relation {
+                        id: b.to_string(),
+                        identifier: b.to_string(),
+                    })
+                    .collect(),
+                blocks: vec![],
+            }),
+            scoring: None,
+            priority: None,
+            labels: vec![],
+            timeout_minutes: None,
+            task_type: Default::default(),
+        }
+    }
+
+    fn attempt(task: &str, status: IterationStatus, start: &str, end: &str) -> IterationLogEntry {
+        IterationLogEntry {
+            completed_at: Some(end.to_string()),
+            error: Some("cargo test failed: <assert>".to_string()),
+            model: Some("opus".to_string()),
+            duration_ms: Some(60_000),
+            cost_usd: Some(0.5),
+            input_tokens: Some(1000),
+            output_tokens: Some(200),
+            ..IterationLogEntry::new(task, 1, start, status)
+        }
+    }
+
+    #[test]
+    fn test_build_run_report_orders_tasks_by_depth() {
+        let graph = build_task_graph(
+            "MOB-1",
+            "MOB-1",
+            &[
+                issue("MOB-4", "Backlog", &["MOB-3", "MOB-2"]),
+                issue("MOB-3", "Backlog", &["MOB-2"]),
+                issue("MOB-2", "Done", &[]),
+            ],
+        );
+        let report = build_run_report("MOB-1", "Parent", &graph, Vec::new(), Vec::new());
+
+        let order: Vec<(&str, usize)> = report
+            .tasks
+            .iter()
+            .map(|t| (t.identifier.as_str(), t.depth))
+            .collect();
+        assert_eq!(order, vec![("MOB-2", 0), ("MOB-3", 1), ("MOB-4", 2)]);
+    }
+
+    #[test]
+    fn test_render_html_escapes_and_includes_every_section() {
+        let graph = build_task_graph("MOB-1", "MOB-1", &[issue("MOB-2", "Done", &[])]);
+        let attempts = vec![
+            attempt(
+                "MOB-2",
+                IterationStatus::Failed,
+                "2026-10-01T10:00:00Z",
+                "2026-10-01T10:01:00Z",
+            ),
+            attempt(
+                "MOB-2",
+                IterationStatus::Success,
+                "2026-10-01T10:02:00Z",
+                "2026-10-01T10:03:00Z",
+            ),
+        ];
+        let commits = vec![TaskCommit {
+            task: "MOB-2".to_string(),
+            sha: "0123456789abcdef".to_string(),
+            subject: "feat(MOB-2): add <thing>".to_string(),
+            url: Some("https://github.com/acme/app/commit/0123456789abcdef".to_string()),
+        }];
+        let html = render_html(&build_run_report(
+            "MOB-1", "Parent", &graph, attempts, commits,
+        ));
+
+        assert!(html.contains("<svg"));
+        assert!(html.contains("class=\"bar failed\""));
+        assert!(html.contains("class=\"bar success\""));
+        assert!(html.contains("$1.00"));
+        assert!(html.contains("cargo test failed: &lt;assert&gt;"));
+        assert!(html.contains("href=\"https://github.com/acme/app/commit/0123456789abcdef\""));
+        assert!(html.contains("add &lt;thing&gt;"));
+        assert!(!html.contains("<assert>"));
+    }
+
+    #[test]
+    fn test_repo_web_url_normalizes_remotes() {
+        for remote in [
+            "git@github.com:acme/app.git",
+            "https://github.com/acme/app.git",
+            "https://token@github.com/acme/app",
+            "ssh://git@github.com:22/acme/app.git",
+        ] {
+            assert_eq!(
+                repo_web_url(remote).as_deref(),
+                Some("https://github.com/acme/app"),
+                "{}",
+                remote
+            );
+        }
+        assert_eq!(repo_web_url("/srv/git/app.git"), None);
+    }
+
+    #[test]
+    fn test_parse_commit_log_attributes_by_identifier() {
+        let log = "\x1eaaa\x1ffeat(MOB-2): first\x1f\n\x1ebbb\x1fchore: tidy\x1fRefs mob-3\n\x1eccc\x1fdocs\x1f\n";
+        let commits = parse_commit_log(
+            log,
+            &["MOB-2".to_string(), "MOB-3".to_string()],
+            Some("https://gitlab.com/acme/app"),
+        );
+
+        let found: Vec<(&str, &str)> = commits
+            .iter()
+            .map(|c| (c.task.as_str(), c.sha.as_str()))
+            .collect();
+        assert_eq!(found, vec![("MOB-2", "aaa"), ("MOB-3", "bbb")]);
+        assert_eq!(
+            commits[1].url.as_deref(),
+            Some("https://gitlab.com/acme/app/commit/bbb")
+        );
+    }
+}
@@ -0,0 +1,152 @@
+//! Per-agent resource limits (`execution.limits`).
+//!
+//! Agent commands are wrapped so a runaway agent (say, one compiling in a
+//! loop) cannot starve the rest of the batch. The systemd enforcer runs each
+//! agent in a transient user scope whose cgroup limits cover every process
+//! the agent spawns. The ulimit enforcer sets soft rlimits in a subshell
+//! instead; those apply per process and cannot cap CPU share or process
+//! count, so `cpus` and `max_processes` are ignored there with a warning.
+
+use std::sync::Once;
+
+use tracing::warn;
+
+use crate::attribution::shell_quote;
+use crate::types::{LimitEnforcer, ResourceLimits};
+
+static ULIMIT_UNSUPPORTED_WARNING: Once = Once::new();
+
+/// Wrap `command` so it runs under `limits`. Empty limits leave it unchanged.
+pub fn with_resource_limits(command: &str, limits: &ResourceLimits) -> String {
+    if limits.is_empty() {
+        return command.to_string();
+    }
+    match resolve_enforcer(limits.enforcer, systemd_available()) {
+        LimitEnforcer::Systemd => systemd_command(command, limits),
+        LimitEnforcer::Auto | LimitEnforcer::Ulimit => {
+            if limits.cpus.is_some() || limits.max_processes.is_some() {
+                ULIMIT_UNSUPPORTED_WARNING.call_once(|| {
+                    warn!(
+                        "execution.limits: cpus and max_processes need the systemd enforcer; \
+                         only memory_mb and cpu_seconds apply"
+                    )
+                });
+            }
+            ulimit_command(command, limits)
+        }
+    }
+}
+
+/// Pick the concrete enforcer; `auto` prefers systemd when it can be used.
+fn resolve_enforcer(enforcer: LimitEnforcer, systemd_available: bool) -> LimitEnforcer {
+    match enforcer {
+        LimitEnforcer::Auto if systemd_available => LimitEnforcer::Systemd,
+        LimitEnforcer::Auto => LimitEnforcer::Ulimit,
+        explicit => explicit,
+    }
+}
+
+/// Transient scopes need `systemd-run` and a user session manager.
+fn systemd_available() -> bool {
+    cfg!(target_os = "linux")
+        && std::env::var_os("XDG_RUNTIME_DIR").is_some()
+        && which::which("systemd-run").is_ok()
+}
+
+/// `ulimit` commands for the limits rlimits can express.
+fn ulimit_prefix(limits: &ResourceLimits, include_memory: bool) -> Vec<String> {
+    let mut prefix = Vec::new();
+    if let Some(mb) = limits.memory_mb.filter(|_| include_memory) {
+        // RLIMIT_DATA counts heap and private writable mappings but not the
+        // address space runtimes like Node reserve up front.
+        prefix.push(format!("ulimit -S -d {}", mb.saturating_mul(1024)));
+    }
+    if let Some(seconds) = limits.cpu_seconds {
+        prefix.push(format!("ulimit -S -t {}", seconds));
+    }
+    prefix
+}
+
+fn ulimit_command(command: &str, limits: &ResourceLimits) -> String {
+    let mut parts = ulimit_prefix(limits, true);
+    parts.push(command.to_string());
+    format!("({})", parts.join(" && "))
+}
+
+fn systemd_command(command: &str, limits: &ResourceLimits) -> String {
+    let mut properties = Vec::new();
+    if let Some(mb) = limits.memory_mb {
+        properties.push(format!("MemoryMax={}M", mb));
+    }
+    if let Some(cpus) = limits.cpus.filter(|c| *c > 0.0) {
+        properties.push(format!("CPUQuota={}%", (cpus * 100.0).round() as u64));
+    }
+    if let Some(max) = limits.max_processes {
+        properties.push(format!("TasksMax={}", max));
+    }
+
+    // Cgroups have no CPU-time budget, so that one stays an rlimit
+    let mut inner = ulimit_prefix(limits, false);
+    inner.push(command.to_string());
+
+    let mut args = vec!["systemd-run --user --scope --quiet --collect".to_string()];
+    args.extend(properties.iter().map(|p| format!("-p {}", p)));
+    args.push(format!("-- sh -c {}", shell_quote(&inner.join(" && "))));
+    args.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(enforcer: LimitEnforcer) -> ResourceLimits {
+        ResourceLimits {
+            memory_mb: Some(2048),
+            cpus: Some(1.5),
+            cpu_seconds: Some(600),
+            max_processes: Some(256),
+            enforcer,
+        }
+    }
+
+    #[test]
+    fn test_empty_limits_leave_command_unchanged() {
+        let cmd = "cd \"/tmp/wt\" && claude -p";
+        assert_eq!(with_resource_limits(cmd, &ResourceLimits::default()), cmd);
+    }
+
+    #[test]
+    fn test_ulimit_wraps_command_in_subshell() {
+        let cmd = with_resource_limits("cd \"/tmp/wt\" && make", &limits(LimitEnforcer::Ulimit));
+        assert_eq!(
+            cmd,
+            "(ulimit -S -d 2097152 && ulimit -S -t 600 && cd \"/tmp/wt\" && make)"
+        );
+    }
+
+    #[test]
+    fn test_systemd_scope_carries_cgroup_limits() {
+        let cmd = systemd_command("echo 'hi' | cat", &limits(LimitEnforcer::Systemd));
+        assert_eq!(
+            cmd,
+            "systemd-run --user --scope --quiet --collect -p MemoryMax=2048M -p CPUQuota=150% \
+             -p TasksMax=256 -- sh -c 'ulimit -S -t 600 && echo '\\''hi'\\'' | cat'"
+        );
+    }
+
+    #[test]
+    fn test_auto_enforcer_prefers_systemd() {
+        assert_eq!(
+            resolve_enforcer(LimitEnforcer::Auto, true),
+            LimitEnforcer::Systemd
+        );
+        assert_eq!(
+            resolve_enforcer(LimitEnforcer::Auto, false),
+            LimitEnforcer::Ulimit
+        );
+        assert_eq!(
+            resolve_enforcer(LimitEnforcer::Ulimit, true),
+            LimitEnforcer::Ulimit
+        );
+    }
+}
@@ -1,6 +1,12 @@
-use crate::types::{AgentRuntime, ExecutionConfig};
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::types::{AgentRuntime, ExecutionConfig, ModelFallback, TaskScoring};
 
 const OPENCODE_DEFAULT_MODEL: &str = "openai/gpt-5.3-codex";
+const CODEX_DEFAULT_MODEL: &str = "gpt-5.3-codex";
+const AIDER_DEFAULT_MODEL: &str = "sonnet";
 
 fn normalize_opencode_model(raw_model: &str) -> String {
     let trimmed = raw_model.trim();
@@ -26,6 +32,42 @@ fn normalize_opencode_model(raw_model: &str) -> String {
     }
 }
 
+/// Codex takes bare OpenAI model IDs; profiles and `openai/` IDs are mapped.
+fn normalize_codex_model(raw_model: &str) -> String {
+    let trimmed = raw_model.trim();
+    let model = trimmed.strip_prefix("openai/").unwrap_or(trimmed);
+    match model.to_ascii_lowercase().replace(' ', "-").as_str() {
+        "" | "opus" | "sonnet" | "gpt-5.3" => CODEX_DEFAULT_MODEL.to_string(),
+        "haiku" => "gpt-5.1-codex-mini".to_string(),
+        _ => model.to_string(),
+    }
+}
+
+/// Codex `model_reasoning_effort` values: minimal, low, medium, high, xhigh.
+fn normalize_codex_reasoning_effort(raw_level: &str) -> String {
+    let alias = raw_level
+        .trim()
+        .to_ascii_lowercase()
+        .replace(['_', ' '], "-");
+    match alias.as_str() {
+        "max" | "very-high" | "veryhigh" => "xhigh".to_string(),
+        "xlow" | "min" => "minimal".to_string(),
+        "med" => "medium".to_string(),
+        _ => alias,
+    }
+}
+
+/// Aider resolves profile aliases (`opus`, `sonnet`, `haiku`) and
+/// provider-qualified IDs itself.
+fn normalize_aider_model(raw_model: &str) -> String {
+    let trimmed = raw_model.trim();
+    if trimmed.is_empty() {
+        AIDER_DEFAULT_MODEL.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
 fn normalize_opencode_variant(raw_variant: &str) -> String {
     let alias = raw_variant
         .trim()
@@ -52,6 +94,69 @@ fn normalize_skill_name(skill: &str) -> String {
     }
 }
 
+/// Codex invokes skills by `$name` mention.
+fn build_codex_skill_prompt(skill: &str, subtask_identifier: &str) -> String {
+    format!(
+        "Use the ${} skill for sub-task {}.",
+        normalize_skill_name(skill),
+        subtask_identifier
+    )
+}
+
+/// Codex sandbox flags for agent runs.
+///
+/// Inside the mobius Docker sandbox Codex's own sandbox is redundant, as
+/// `--dangerously-skip-permissions` is for Claude. Outside it, Codex keeps its
+/// workspace-write sandbox with the repository's git directory writable so
+/// agents can commit from the worktree.
+fn codex_sandbox_flags(config: &ExecutionConfig) -> &'static str {
+    if config.sandbox {
+        "--dangerously-bypass-approvals-and-sandbox"
+    } else {
+        "--full-auto --add-dir \"$(git rev-parse --git-common-dir)\""
+    }
+}
+
+/// Aider has no skills, so the prompt points it at the sub-task spec instead.
+fn build_aider_prompt(subtask_identifier: &str, context_file_path: Option<&str>) -> String {
+    let spec = if context_file_path.is_some() {
+        format!(
+            " Its spec is the {} entry under subTasks in the read-only context file.",
+            subtask_identifier
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        "Implement sub-task {}.{} Change only what the sub-task asks for.",
+        subtask_identifier, spec
+    )
+}
+
+/// Aider flags for unattended runs.
+///
+/// Aider's own author attribution is off and commits run git hooks, so the
+/// mobius `commit-msg` hook applies `execution.attribution` as for other runtimes.
+fn aider_flags(config: &ExecutionConfig) -> String {
+    let mut flags = vec![
+        "--yes-always",
+        "--no-pretty",
+        "--no-stream",
+        "--no-check-update",
+        "--no-show-model-warnings",
+        "--no-attribute-author",
+        "--no-attribute-committer",
+        "--git-commit-verify",
+    ];
+    flags.push(if config.aider.auto_commits {
+        "--auto-commits"
+    } else {
+        "--no-auto-commits"
+    });
+    flags.extend(config.aider.extra_args.iter().map(String::as_str));
+    flags.join(" ")
+}
+
 fn build_opencode_skill_prompt(skill: &str, subtask_identifier: &str) -> String {
     let skill_name = normalize_skill_name(skill);
     format!(
@@ -64,13 +169,16 @@ pub fn effective_thinking_level_for_runtime(
     runtime: AgentRuntime,
     thinking_level_override: Option<&str>,
 ) -> Option<String> {
+    let level = thinking_level_override
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
     match runtime {
         AgentRuntime::Claude => None,
-        AgentRuntime::Opencode => thinking_level_override
-            .map(str::trim)
-            .filter(|value| !value.is_empty())
+        AgentRuntime::Opencode => level
             .map(normalize_opencode_variant)
             .filter(|value| !value.is_empty()),
+        AgentRuntime::Codex => level.map(normalize_codex_reasoning_effort),
+        AgentRuntime::Aider => level.map(str::to_ascii_lowercase),
     }
 }
 
@@ -79,19 +187,137 @@ pub fn effective_model_for_runtime(
     config: &ExecutionConfig,
     model_override: Option<&str>,
 ) -> String {
+    let requested_model = || {
+        model_override
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| config.model.to_string())
+    };
     match runtime {
         AgentRuntime::Claude => config.model.to_string(),
-        AgentRuntime::Opencode => {
-            let requested_model = model_override
-                .map(str::trim)
-                .filter(|value| !value.is_empty())
-                .map(|value| value.to_string())
-                .unwrap_or_else(|| config.model.to_string());
-            normalize_opencode_model(&requested_model)
-        }
+        AgentRuntime::Opencode => normalize_opencode_model(&requested_model()),
+        AgentRuntime::Codex => normalize_codex_model(&requested_model()),
+        AgentRuntime::Aider => normalize_aider_model(&requested_model()),
+    }
+}
+
+/// Executable name of each runtime's CLI
+pub fn runtime_cli_command(runtime: AgentRuntime) -> &'static str {
+    match runtime {
+        AgentRuntime::Claude => "claude",
+        AgentRuntime::Opencode => "opencode",
+        AgentRuntime::Codex => "codex",
+        AgentRuntime::Aider => "aider",
     }
 }
 
+/// Whether the runtime's CLI is on PATH. Looked up once per runtime per
+/// process, since routing asks for every sub-task of every batch.
+pub fn is_runtime_installed(runtime: AgentRuntime) -> bool {
+    static INSTALLED: [OnceLock<bool>; 4] = [
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+        OnceLock::new(),
+    ];
+    let slot = match runtime {
+        AgentRuntime::Claude => &INSTALLED[0],
+        AgentRuntime::Opencode => &INSTALLED[1],
+        AgentRuntime::Codex => &INSTALLED[2],
+        AgentRuntime::Aider => &INSTALLED[3],
+    };
+    *slot.get_or_init(|| which::which(runtime_cli_command(runtime)).is_ok())
+}
+
+/// Runtime chosen for one sub-task, with the model named by its rule (if any).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RuntimeRoute<'a> {
+    pub runtime: AgentRuntime,
+    pub model: Option<&'a str>,
+}
+
+/// Pick the runtime for a sub-task: its scored `recommendedRuntime`, then the
+/// first matching `execution.runtime_rules` entry, then `default_runtime`.
+///
+/// A routed runtime whose CLI is not on PATH falls back to `default_runtime`.
+pub fn route_runtime<'a>(
+    scoring: Option<&TaskScoring>,
+    config: &'a ExecutionConfig,
+    default_runtime: AgentRuntime,
+) -> RuntimeRoute<'a> {
+    route_runtime_with(scoring, config, default_runtime, is_runtime_installed)
+}
+
+fn route_runtime_with<'a, F>(
+    scoring: Option<&TaskScoring>,
+    config: &'a ExecutionConfig,
+    default_runtime: AgentRuntime,
+    installed: F,
+) -> RuntimeRoute<'a>
+where
+    F: Fn(AgentRuntime) -> bool,
+{
+    let default_route = RuntimeRoute {
+        runtime: default_runtime,
+        model: None,
+    };
+    let route = match scoring.and_then(|s| s.recommended_runtime) {
+        Some(runtime) => RuntimeRoute {
+            runtime,
+            model: None,
+        },
+        None => config
+            .runtime_rules
+            .iter()
+            .find(|rule| rule.matches(scoring))
+            .map(|rule| RuntimeRoute {
+                runtime: rule.runtime,
+                model: rule.model.as_deref().filter(|m| !m.trim().is_empty()),
+            })
+            .unwrap_or(default_route),
+    };
+
+    if route.runtime != default_runtime && !installed(route.runtime) {
+        tracing::warn!(
+            "{} CLI not found in PATH; using {} instead",
+            runtime_cli_command(route.runtime),
+            default_runtime
+        );
+        return default_route;
+    }
+    route
+}
+
+/// Execution settings for the rest of a run once it has switched to
+/// `fallback`: the fallback model replaces the configured one and routing rules
+/// are dropped, so every remaining sub-task goes to the fallback runtime.
+pub fn fallback_execution_config(
+    config: &ExecutionConfig,
+    fallback: &ModelFallback,
+) -> ExecutionConfig {
+    let mut config = config.clone();
+    if let Some(model) = fallback.model.as_deref().filter(|m| !m.trim().is_empty()) {
+        config.model = model.trim().to_string();
+    }
+    config.runtime_rules.clear();
+    config.fallback = None;
+    config
+}
+
+/// `runtime/model` label for messages and events
+pub fn runtime_model_label(
+    runtime: AgentRuntime,
+    config: &ExecutionConfig,
+    model_override: Option<&str>,
+) -> String {
+    format!(
+        "{}/{}",
+        runtime,
+        effective_model_for_runtime(runtime, config, model_override)
+    )
+}
+
 pub struct ExecutionCommand<'a> {
     pub subtask_identifier: &'a str,
     pub skill: &'a str,
@@ -154,9 +380,49 @@ pub fn build_execution_command(runtime: AgentRuntime, options: &ExecutionCommand
                     .unwrap_or_default(),
             )
         }
+        AgentRuntime::Codex => {
+            let prompt = build_codex_skill_prompt(options.skill, options.subtask_identifier);
+            format!(
+                "cd \"{}\" && {}codex exec {} --model {}{} '{}'",
+                options.worktree_path,
+                env_prefix,
+                codex_sandbox_flags(options.config),
+                model,
+                effective_thinking_level_for_runtime(runtime, options.thinking_level_override)
+                    .map(|level| format!(" -c model_reasoning_effort={}", level))
+                    .unwrap_or_default(),
+                prompt,
+            )
+        }
+        AgentRuntime::Aider => {
+            let prompt = build_aider_prompt(options.subtask_identifier, options.context_file_path);
+            format!(
+                "cd \"{}\" && {}aider {} --model {}{}{} --message '{}'",
+                options.worktree_path,
+                env_prefix,
+                aider_flags(options.config),
+                model,
+                effective_thinking_level_for_runtime(runtime, options.thinking_level_override)
+                    .map(|level| format!(" --reasoning-effort {}", level))
+                    .unwrap_or_default(),
+                options
+                    .context_file_path
+                    .map(|path| format!(" --read \"{}\"", path))
+                    .unwrap_or_default(),
+                prompt,
+            )
+        }
     }
 }
 
+/// Whether `runtime` reports completion only through its exit status.
+///
+/// Such runtimes print no STATUS markers and write no result file, so they
+/// need the process executor, which sees the exit status.
+pub fn completes_on_exit(runtime: AgentRuntime) -> bool {
+    runtime == AgentRuntime::Aider
+}
+
 pub fn build_submit_command(
     runtime: AgentRuntime,
     model: &str,
@@ -188,9 +454,73 @@ pub fn build_submit_command(
                 .map(|level| format!(" --variant {}", level))
                 .unwrap_or_default(),
         ),
+        // `-` reads the prompt from stdin
+        AgentRuntime::Codex => format!(
+            "codex exec --dangerously-bypass-approvals-and-sandbox --model {}{} -",
+            normalize_codex_model(model),
+            effective_thinking_level_for_runtime(runtime, thinking_level_override)
+                .map(|level| format!(" -c model_reasoning_effort={}", level))
+                .unwrap_or_default(),
+        ),
+        AgentRuntime::Aider => format!(
+            "aider --yes-always --no-pretty --no-stream --model {}{} --message-file /dev/stdin",
+            normalize_aider_model(model),
+            effective_thinking_level_for_runtime(runtime, thinking_level_override)
+                .map(|level| format!(" --reasoning-effort {}", level))
+                .unwrap_or_default(),
+        ),
     }
 }
 
+/// Arguments that open an interactive runtime session seeded with `prompt`.
+///
+/// Returns `None` for Aider, which has no skills to run interactively.
+pub fn build_interactive_command(runtime: AgentRuntime, prompt: &str) -> Option<Vec<String>> {
+    let cli = runtime_cli_command(runtime).to_string();
+    match runtime {
+        AgentRuntime::Claude | AgentRuntime::Codex => Some(vec![cli, prompt.to_string()]),
+        AgentRuntime::Opencode => Some(vec![cli, "--prompt".to_string(), prompt.to_string()]),
+        AgentRuntime::Aider => None,
+    }
+}
+
+/// Rewrite Codex CLI output so completion markers start their own lines, where
+/// `executor::parse_agent_output` looks for them.
+///
+/// Agent messages are unpacked from `codex exec --json` events, and the
+/// `[timestamp] codex` label of the human-readable transcript is stripped when
+/// a message follows it on the same line. Other lines pass through unchanged.
+pub fn translate_codex_output(content: &str) -> String {
+    let label = Regex::new(r"^\[[^\]]+\]\s+codex:?\s+(\S.*)$").unwrap();
+    let mut translated = Vec::new();
+    for line in content.lines() {
+        if let Some(text) = codex_agent_message(line) {
+            translated.extend(text.lines().map(str::to_string));
+        } else if let Some(caps) = label.captures(line) {
+            translated.push(caps[1].to_string());
+        } else {
+            translated.push(line.to_string());
+        }
+    }
+    translated.join("\n")
+}
+
+/// Text of an `item.completed` agent message event, if `line` is one.
+fn codex_agent_message(line: &str) -> Option<String> {
+    let line = line.trim();
+    if !line.starts_with('{') {
+        return None;
+    }
+    let event: serde_json::Value = serde_json::from_str(line).ok()?;
+    let item = event.get("item")?;
+    if event.get("type")?.as_str()? != "item.completed"
+        || item.get("type")?.as_str()? != "agent_message"
+    {
+        return None;
+    }
+    item.get("text")?.as_str().map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -237,6 +567,90 @@ mod tests {
         assert!(!cmd.contains("echo '/execute MOB-101'"));
     }
 
+    #[test]
+    fn test_build_execution_command_codex() {
+        let config = ExecutionConfig {
+            model: "openai/gpt-5.2-codex".to_string(),
+            ..Default::default()
+        };
+        let options = ExecutionCommand {
+            subtask_identifier: "MOB-101",
+            skill: "/execute",
+            worktree_path: "/tmp/worktree",
+            config: &config,
+            context_file_path: Some("/tmp/context.json"),
+            model_override: None,
+            thinking_level_override: Some("very high"),
+        };
+        let cmd = build_execution_command(AgentRuntime::Codex, &options);
+
+        assert!(cmd.starts_with("cd \"/tmp/worktree\" && MOBIUS_CONTEXT_FILE="));
+        assert!(cmd.contains("codex exec --dangerously-bypass-approvals-and-sandbox"));
+        assert!(cmd.contains("--model gpt-5.2-codex"));
+        assert!(cmd.contains("-c model_reasoning_effort=xhigh"));
+        assert!(cmd.ends_with("'Use the $execute skill for sub-task MOB-101.'"));
+
+        // Outside the Docker sandbox Codex keeps its own
+        let unsandboxed = ExecutionConfig {
+            sandbox: false,
+            ..config.clone()
+        };
+        let options = ExecutionCommand {
+            config: &unsandboxed,
+            thinking_level_override: None,
+            ..options
+        };
+        let cmd = build_execution_command(AgentRuntime::Codex, &options);
+        assert!(cmd.contains("--full-auto --add-dir \"$(git rev-parse --git-common-dir)\""));
+        assert!(!cmd.contains("dangerously"));
+        assert!(!cmd.contains("model_reasoning_effort"));
+    }
+
+    #[test]
+    fn test_build_execution_command_aider() {
+        let config: ExecutionConfig = serde_yaml::from_str(
+            "model: opus\naider:\n  auto_commits: false\n  extra_args: [--map-tokens, \"2048\"]\n",
+        )
+        .unwrap();
+        let options = ExecutionCommand {
+            subtask_identifier: "MOB-101",
+            skill: "/execute",
+            worktree_path: "/tmp/worktree",
+            config: &config,
+            context_file_path: Some("/tmp/context.json"),
+            model_override: None,
+            thinking_level_override: Some("High"),
+        };
+        let cmd = build_execution_command(AgentRuntime::Aider, &options);
+
+        assert!(cmd.contains("aider --yes-always"));
+        assert!(cmd.contains("--git-commit-verify --no-auto-commits --map-tokens 2048"));
+        assert!(cmd.contains("--model opus --reasoning-effort high"));
+        assert!(cmd.contains("--read \"/tmp/context.json\""));
+        assert!(cmd.ends_with(
+            "--message 'Implement sub-task MOB-101. Its spec is the MOB-101 entry under subTasks in the read-only context file. Change only what the sub-task asks for.'"
+        ));
+        assert!(completes_on_exit(AgentRuntime::Aider));
+        assert!(!completes_on_exit(AgentRuntime::Codex));
+    }
+
+    #[test]
+    fn test_translate_codex_output() {
+        let json = concat!(
+            r#"{"type":"thread.started","thread_id":"t1"}"#,
+            "\n",
+            r#"{"type":"item.completed","item":{"id":"i1","type":"agent_message","text":"Done.\nSTATUS: SUBTASK_COMPLETE"}}"#,
+        );
+        let translated = translate_codex_output(json);
+        assert!(translated.lines().any(|l| l == "STATUS: SUBTASK_COMPLETE"));
+
+        let transcript = "[2026-01-05T10:00:00] codex: STATUS: ALL_BLOCKED\nplain line";
+        assert_eq!(
+            translate_codex_output(transcript),
+            "STATUS: ALL_BLOCKED\nplain line"
+        );
+    }
+
     #[test]
     fn test_build_execution_command_opencode_with_context_file() {
         let config = ExecutionConfig::default();
@@ -273,6 +687,19 @@ mod tests {
         assert!(cmd.contains("Use the verify skill for sub-task MOB-101"));
     }
 
+    #[test]
+    fn test_build_interactive_command() {
+        assert_eq!(
+            build_interactive_command(AgentRuntime::Claude, "/refine LOC-001").unwrap(),
+            vec!["claude", "/refine LOC-001"]
+        );
+        assert_eq!(
+            build_interactive_command(AgentRuntime::Opencode, "/refine LOC-001").unwrap(),
+            vec!["opencode", "--prompt", "/refine LOC-001"]
+        );
+        assert!(build_interactive_command(AgentRuntime::Aider, "/refine LOC-001").is_none());
+    }
+
     #[test]
     fn test_build_submit_command_claude() {
         let cmd = build_submit_command(AgentRuntime::Claude, "opus", true, Some("xhigh"));
@@ -292,6 +719,32 @@ mod tests {
         assert!(!cmd.contains("| cclean"));
     }
 
+    #[test]
+    fn test_build_submit_command_codex() {
+        let cmd = build_submit_command(AgentRuntime::Codex, "opus", false, Some("med"));
+        assert_eq!(
+            cmd,
+            "codex exec --dangerously-bypass-approvals-and-sandbox --model gpt-5.3-codex -c model_reasoning_effort=medium -"
+        );
+    }
+
+    #[test]
+    fn test_effective_model_for_runtime_codex() {
+        let config = ExecutionConfig::default();
+        assert_eq!(
+            effective_model_for_runtime(AgentRuntime::Codex, &config, None),
+            "gpt-5.3-codex"
+        );
+        assert_eq!(
+            effective_model_for_runtime(AgentRuntime::Codex, &config, Some("haiku")),
+            "gpt-5.1-codex-mini"
+        );
+        assert_eq!(
+            effective_model_for_runtime(AgentRuntime::Codex, &config, Some("o4-mini")),
+            "o4-mini"
+        );
+    }
+
     #[test]
     fn test_effective_model_for_runtime_claude_ignores_raw_override() {
         let config = ExecutionConfig::default();
@@ -325,6 +778,107 @@ mod tests {
         assert_eq!(model, "openai/gpt-5.2-codex");
     }
 
+    fn scoring(complexity: u8, recommended_runtime: Option<AgentRuntime>) -> TaskScoring {
+        TaskScoring {
+            complexity,
+            risk: 2,
+            recommended_model: crate::types::Model::Sonnet,
+            rationale: String::new(),
+            recommended_runtime,
+        }
+    }
+
+    fn routing_config() -> ExecutionConfig {
+        serde_yaml::from_str(
+            "runtime_rules:\n  - min_complexity: 7\n    runtime: claude\n    model: opus\n  - runtime: opencode\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_route_runtime_uses_first_matching_rule() {
+        let config = routing_config();
+        let all_installed = |_| true;
+
+        let route = route_runtime_with(
+            Some(&scoring(8, None)),
+            &config,
+            AgentRuntime::Opencode,
+            all_installed,
+        );
+        assert_eq!(route.runtime, AgentRuntime::Claude);
+        assert_eq!(route.model, Some("opus"));
+
+        let route = route_runtime_with(
+            Some(&scoring(3, None)),
+            &config,
+            AgentRuntime::Claude,
+            all_installed,
+        );
+        assert_eq!(route.runtime, AgentRuntime::Opencode);
+        assert_eq!(route.model, None);
+
+        // The scored runtime wins over the rules
+        let route = route_runtime_with(
+            Some(&scoring(9, Some(AgentRuntime::Opencode))),
+            &config,
+            AgentRuntime::Claude,
+            all_installed,
+        );
+        assert_eq!(route.runtime, AgentRuntime::Opencode);
+
+        // Unscored tasks only match the catch-all rule
+        let route = route_runtime_with(None, &config, AgentRuntime::Claude, all_installed);
+        assert_eq!(route.runtime, AgentRuntime::Opencode);
+    }
+
+    #[test]
+    fn test_route_runtime_falls_back_when_cli_missing() {
+        let config = routing_config();
+        let route = route_runtime_with(
+            Some(&scoring(3, None)),
+            &config,
+            AgentRuntime::Claude,
+            |runtime| runtime == AgentRuntime::Claude,
+        );
+        assert_eq!(
+            route,
+            RuntimeRoute {
+                runtime: AgentRuntime::Claude,
+                model: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_fallback_execution_config() {
+        let config = ExecutionConfig {
+            fallback: Some(ModelFallback {
+                runtime: AgentRuntime::Codex,
+                model: Some("gpt-5.2-codex".to_string()),
+            }),
+            ..routing_config()
+        };
+        let fallback = config.fallback.clone().unwrap();
+        let switched = fallback_execution_config(&config, &fallback);
+        assert!(switched.runtime_rules.is_empty());
+        assert!(switched.fallback.is_none());
+        assert_eq!(
+            runtime_model_label(fallback.runtime, &switched, None),
+            "codex/gpt-5.2-codex"
+        );
+
+        // Without a fallback model the configured one carries over
+        let switched = fallback_execution_config(
+            &config,
+            &ModelFallback {
+                model: None,
+                ..fallback
+            },
+        );
+        assert_eq!(switched.model, config.model);
+    }
+
     #[test]
     fn test_effective_thinking_level_for_runtime_opencode() {
         let level = effective_thinking_level_for_runtime(AgentRuntime::Opencode, Some("xhigh"));
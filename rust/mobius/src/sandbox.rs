@@ -0,0 +1,350 @@
+//! Container sandbox for agent runs.
+//!
+//! With `execution.sandbox` on and `execution.container.image` set, every
+//! agent command runs in its own throwaway container (`docker run --rm`, or
+//! Podman). The container sees only the task's worktree, the repository's
+//! git directory (so commits land), and the directories the agent reports
+//! through (result file, stream-json output); the rest of the host stays out
+//! of reach. Agent API keys are passed through from the host environment.
+//!
+//! The container runs in the foreground of the agent's pane or process, so
+//! output, completion detection and interrupts reach the executor exactly as
+//! for a host run. `execution.limits` become container flags.
+
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use crate::attribution::shell_quote;
+use crate::types::{ContainerEngine, ExecutionConfig, ResourceLimits};
+
+/// Host variables every sandboxed agent receives when they are set
+pub const AGENT_ENV_VARS: &[&str] = &[
+    "ANTHROPIC_API_KEY",
+    "ANTHROPIC_BASE_URL",
+    "CLAUDE_CODE_OAUTH_TOKEN",
+    "OPENAI_API_KEY",
+    "OPENROUTER_API_KEY",
+    "GEMINI_API_KEY",
+    "GITHUB_TOKEN",
+    "GH_TOKEN",
+];
+
+/// Where an agent command should run.
+#[derive(Debug, Clone)]
+pub struct Sandbox<'a> {
+    engine: &'static str,
+    image: &'a str,
+    config: &'a ExecutionConfig,
+}
+
+impl<'a> Sandbox<'a> {
+    /// Sandbox for `config`, or `None` when agents run on the host.
+    pub fn for_config(config: &'a ExecutionConfig) -> Option<Self> {
+        if !config.sandbox {
+            return None;
+        }
+        let image = config
+            .container
+            .image
+            .as_deref()
+            .map(str::trim)
+            .filter(|i| !i.is_empty())?;
+        Some(Self {
+            engine: resolve_engine(config.container.engine),
+            image,
+            config,
+        })
+    }
+
+    /// `docker` or `podman`
+    pub fn engine(&self) -> &'static str {
+        self.engine
+    }
+
+    /// One-line description for loop output, e.g. `docker (mobius-agent:latest)`
+    pub fn describe(&self) -> String {
+        format!("{} ({})", self.engine, self.image)
+    }
+
+    /// The container a task runs in, named uniquely within the loop.
+    ///
+    /// The loop's pid is part of the name, so two loops on the same machine
+    /// running a same-named sub-task don't remove each other's containers.
+    pub fn container(&self, identifier: &str) -> Container {
+        let slug: String = identifier
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        Container {
+            engine: self.engine,
+            name: format!(
+                "{}-{}-{}",
+                self.config.container_name,
+                std::process::id(),
+                slug
+            ),
+        }
+    }
+
+    /// Wrap `command` so it runs in a fresh container for `identifier`.
+    ///
+    /// `worktree` becomes the working directory; it and `extra_mounts` are
+    /// bind-mounted at their host paths so paths inside `command` still hold.
+    pub fn wrap(
+        &self,
+        command: &str,
+        identifier: &str,
+        worktree: &Path,
+        extra_mounts: &[&Path],
+    ) -> String {
+        let mut mounts = vec![worktree.to_path_buf()];
+        mounts.extend(git_common_dir(worktree));
+        mounts.extend(extra_mounts.iter().map(|p| p.to_path_buf()));
+        let mounts = outermost(mounts);
+
+        let container = self.container(identifier);
+        let name = shell_quote(&container.name);
+        let mut args = vec![
+            // A container left behind by a killed attempt would block the name
+            format!("{} rm -f {} >/dev/null 2>&1;", self.engine, name),
+            format!("{} run --rm -i --init --name {}", self.engine, name),
+            format!("--user {}", host_user()),
+            format!("-w {}", shell_quote(&worktree.display().to_string())),
+        ];
+        for mount in &mounts {
+            let path = shell_quote(&mount.display().to_string());
+            args.push(format!("-v {}:{}", path, path));
+        }
+
+        // Arbitrary UIDs often have no home in the image
+        args.push("-e HOME=/tmp".to_string());
+        for (key, value) in git_identity(worktree) {
+            args.push(format!("-e {}={}", key, shell_quote(&value)));
+        }
+        for name in AGENT_ENV_VARS
+            .iter()
+            .copied()
+            .chain(self.config.container.env.iter().map(String::as_str))
+        {
+            // `-e NAME` copies the host value and is skipped when unset
+            args.push(format!("-e {}", name));
+        }
+
+        args.extend(limit_flags(&self.config.limits));
+        args.extend(self.config.container.run_args.iter().cloned());
+        args.push(shell_quote(self.image));
+        args.push(format!("sh -c {}", shell_quote(command)));
+        args.join(" ")
+    }
+}
+
+/// A task's container, kept so it can be removed if the agent is killed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Container {
+    pub engine: &'static str,
+    pub name: String,
+}
+
+impl Container {
+    /// Force-remove the container. Killing a pane or process only stops the
+    /// engine's client, so the container could otherwise keep running.
+    pub async fn remove(&self) {
+        let result = tokio::process::Command::new(self.engine)
+            .args(["rm", "-f", &self.name])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .await;
+        if let Err(e) = result {
+            debug!("Failed to remove container {}: {}", self.name, e);
+        }
+    }
+}
+
+fn resolve_engine(engine: ContainerEngine) -> &'static str {
+    match engine {
+        ContainerEngine::Docker => "docker",
+        ContainerEngine::Podman => "podman",
+        ContainerEngine::Auto => {
+            if which::which("docker").is_err() && which::which("podman").is_ok() {
+                "podman"
+            } else {
+                "docker"
+            }
+        }
+    }
+}
+
+/// Container flags for `execution.limits`; cgroups cover the whole container.
+fn limit_flags(limits: &ResourceLimits) -> Vec<String> {
+    let mut flags = Vec::new();
+    if let Some(mb) = limits.memory_mb {
+        flags.push(format!("--memory {}m", mb));
+    }
+    if let Some(cpus) = limits.cpus.filter(|c| *c > 0.0) {
+        flags.push(format!("--cpus {}", cpus));
+    }
+    if let Some(seconds) = limits.cpu_seconds {
+        flags.push(format!("--ulimit cpu={}", seconds));
+    }
+    if let Some(max) = limits.max_processes {
+        flags.push(format!("--pids-limit {}", max));
+    }
+    flags
+}
+
+/// Run as the host user so files written to the worktree keep their owner.
+fn host_user() -> String {
+    #[cfg(unix)]
+    {
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        format!("{}:{}", uid, gid)
+    }
+    #[cfg(not(unix))]
+    {
+        "1000:1000".to_string()
+    }
+}
+
+/// The main repository's `.git`, which a worktree's `.git` file points into.
+fn git_common_dir(worktree: &Path) -> Option<PathBuf> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(worktree)
+        .args(["rev-parse", "--git-common-dir"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    let dir = if dir.is_absolute() {
+        dir
+    } else {
+        worktree.join(dir)
+    };
+    dir.canonicalize().ok()
+}
+
+/// The host's git identity, since the container has no `~/.gitconfig`.
+fn git_identity(worktree: &Path) -> Vec<(&'static str, String)> {
+    let get = |key: &str| {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(worktree)
+            .args(["config", key])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())?;
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|v| !v.is_empty())
+    };
+    let mut vars = Vec::new();
+    if let Some(name) = get("user.name") {
+        vars.push(("GIT_AUTHOR_NAME", name.clone()));
+        vars.push(("GIT_COMMITTER_NAME", name));
+    }
+    if let Some(email) = get("user.email") {
+        vars.push(("GIT_AUTHOR_EMAIL", email.clone()));
+        vars.push(("GIT_COMMITTER_EMAIL", email));
+    }
+    vars
+}
+
+/// Drop paths already covered by another mount, keeping order otherwise.
+fn outermost(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut kept: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        if kept.iter().any(|k| path.starts_with(k)) {
+            continue;
+        }
+        kept.retain(|k| !k.starts_with(&path));
+        kept.push(path);
+    }
+    kept
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ContainerConfig;
+
+    fn sandboxed(image: Option<&str>) -> ExecutionConfig {
+        ExecutionConfig {
+            sandbox: true,
+            container: ContainerConfig {
+                image: image.map(str::to_string),
+                engine: ContainerEngine::Podman,
+                env: vec!["NPM_TOKEN".to_string()],
+                run_args: vec!["--network=host".to_string()],
+            },
+            limits: ResourceLimits {
+                memory_mb: Some(4096),
+                cpus: Some(2.0),
+                ..ResourceLimits::default()
+            },
+            ..ExecutionConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_sandbox_needs_flag_and_image() {
+        assert!(Sandbox::for_config(&sandboxed(None)).is_none());
+        assert!(Sandbox::for_config(&sandboxed(Some(" "))).is_none());
+        let mut host = sandboxed(Some("mobius-agent:latest"));
+        host.sandbox = false;
+        assert!(Sandbox::for_config(&host).is_none());
+        assert!(Sandbox::for_config(&sandboxed(Some("mobius-agent:latest"))).is_some());
+    }
+
+    #[test]
+    fn test_wrap_mounts_worktree_and_passes_keys() {
+        let config = sandboxed(Some("mobius-agent:latest"));
+        let sandbox = Sandbox::for_config(&config).unwrap();
+        let worktree = tempfile::tempdir().unwrap();
+        let results = worktree.path().join("results");
+        let outputs = tempfile::tempdir().unwrap();
+
+        let cmd = sandbox.wrap(
+            "cd \"/wt\" && echo 'go'",
+            "MOB-12",
+            worktree.path(),
+            &[&results, outputs.path()],
+        );
+        let wt = worktree.path().display().to_string();
+        let out = outputs.path().display().to_string();
+
+        let name = format!("mobius-sandbox-{}-mob-12", std::process::id());
+        assert!(cmd.starts_with(&format!(
+            "podman rm -f '{name}' >/dev/null 2>&1; \
+             podman run --rm -i --init --name '{name}' --user "
+        )));
+        assert!(cmd.contains(&format!("-w '{wt}'")));
+        assert!(cmd.contains(&format!("-v '{wt}':'{wt}'")));
+        assert!(cmd.contains(&format!("-v '{out}':'{out}'")));
+        // Nested under the worktree, so already mounted
+        assert!(!cmd.contains("/results'"));
+        assert!(cmd.contains("-e ANTHROPIC_API_KEY -e"));
+        assert!(cmd.contains("-e NPM_TOKEN"));
+        assert!(cmd.contains("--memory 4096m --cpus 2 --network=host 'mobius-agent:latest'"));
+        assert!(cmd.ends_with(r#"sh -c 'cd "/wt" && echo '\''go'\'''"#));
+    }
+
+    #[test]
+    fn test_outermost_drops_nested_mounts() {
+        let kept = outermost(vec![
+            PathBuf::from("/repo/.mobius/issues/A/execution/results"),
+            PathBuf::from("/repo/.mobius"),
+            PathBuf::from("/wt"),
+            PathBuf::from("/repo/.mobius/issues"),
+        ]);
+        assert_eq!(
+            kept,
+            vec![PathBuf::from("/repo/.mobius"), PathBuf::from("/wt")]
+        );
+    }
+}
@@ -0,0 +1,448 @@
+//! Full-text index over local issues for `mobius search`.
+//!
+//! Parent specs, sub-task specs and queued comments are tokenized into
+//! per-document term counts kept in `.mobius/state/search-index.json`. Each
+//! refresh compares a cheap per-issue fingerprint (spec file sizes and
+//! modification times) and re-reads only the issues that changed, so pulls,
+//! refines and edits made by agents are all picked up without re-scanning
+//! every spec.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::context::atomic_write_json;
+use crate::local_state::{get_project_mobius_path, LocalPendingUpdate};
+use crate::types::context::{ParentIssueContext, SubTaskContext};
+
+/// Bumped when the stored format or tokenizer changes; older indexes are rebuilt
+const INDEX_VERSION: u32 = 1;
+
+/// Weight of a term that appears in the title rather than only the body
+const TITLE_BOOST: f64 = 3.0;
+
+/// Weight of a prefix match (`auth` → `authentication`) relative to an exact one
+const PREFIX_WEIGHT: f64 = 0.5;
+
+/// Words too common to narrow a search
+const STOP_WORDS: &[&str] = &[
+    "an", "and", "are", "as", "at", "be", "by", "for", "from", "in", "is", "it", "of", "on", "or",
+    "that", "the", "to", "with",
+];
+
+/// Path of the persisted index (`.mobius/state/search-index.json`).
+pub fn index_path() -> PathBuf {
+    get_project_mobius_path()
+        .join("state")
+        .join("search-index.json")
+}
+
+/// Kind of an indexed document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocKind {
+    Parent,
+    SubTask,
+}
+
+/// One issue or sub-task in the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexedDoc {
+    identifier: String,
+    title: String,
+    status: String,
+    kind: DocKind,
+    /// Term counts over title, description and comments
+    terms: BTreeMap<String, u32>,
+    title_terms: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedIssue {
+    fingerprint: u64,
+    docs: Vec<IndexedDoc>,
+}
+
+/// A search result
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+    pub identifier: String,
+    /// Local issue directory the hit belongs to
+    pub parent_id: String,
+    pub title: String,
+    pub status: String,
+    pub kind: DocKind,
+    pub score: f64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SearchIndex {
+    version: u32,
+    issues: BTreeMap<String, IndexedIssue>,
+}
+
+impl SearchIndex {
+    /// Load the index at `path`; a missing, corrupt or outdated file gives an
+    /// empty index that the next refresh rebuilds.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<SearchIndex>(&content).ok())
+            .filter(|index| index.version == INDEX_VERSION)
+            .unwrap_or_else(|| SearchIndex {
+                version: INDEX_VERSION,
+                issues: BTreeMap::new(),
+            })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        atomic_write_json(path, self)
+    }
+
+    /// Number of indexed documents
+    pub fn len(&self) -> usize {
+        self.issues.values().map(|issue| issue.docs.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Bring the index in line with `issues_root`, re-reading only issues
+    /// whose files changed. Returns how many issues were (re)indexed or
+    /// dropped.
+    pub fn refresh(&mut self, issues_root: &Path) -> usize {
+        let present: BTreeSet<String> = fs::read_dir(issues_root)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                    .filter_map(|e| e.file_name().to_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let before = self.issues.len();
+        self.issues.retain(|id, _| present.contains(id));
+        let mut changed = before - self.issues.len();
+
+        for issue_id in &present {
+            let dir = issues_root.join(issue_id);
+            let fingerprint = issue_fingerprint(&dir);
+            if self
+                .issues
+                .get(issue_id)
+                .is_some_and(|issue| issue.fingerprint == fingerprint)
+            {
+                continue;
+            }
+            self.issues.insert(
+                issue_id.clone(),
+                IndexedIssue {
+                    fingerprint,
+                    docs: read_issue_docs(&dir),
+                },
+            );
+            changed += 1;
+        }
+        changed
+    }
+
+    /// Rank documents containing every query term (or a word it prefixes).
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let query_terms: Vec<String> = tokenize(query).collect();
+        if query_terms.is_empty() {
+            return Vec::new();
+        }
+
+        let docs: Vec<(&str, &IndexedDoc)> = self
+            .issues
+            .iter()
+            .flat_map(|(id, issue)| issue.docs.iter().map(move |doc| (id.as_str(), doc)))
+            .collect();
+        let mut postings: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+        for (i, (_, doc)) in docs.iter().enumerate() {
+            for term in doc.terms.keys() {
+                postings.entry(term.as_str()).or_default().push(i);
+            }
+        }
+
+        let total = docs.len() as f64;
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+        for (n, query_term) in query_terms.iter().enumerate() {
+            let mut term_scores: HashMap<usize, f64> = HashMap::new();
+            let matching = postings
+                .range(query_term.as_str()..)
+                .take_while(|(term, _)| term.starts_with(query_term.as_str()));
+            for (term, doc_ids) in matching {
+                let idf = (1.0
+                    + (total - doc_ids.len() as f64 + 0.5) / (doc_ids.len() as f64 + 0.5))
+                    .ln();
+                let weight = if *term == query_term.as_str() {
+                    1.0
+                } else {
+                    PREFIX_WEIGHT
+                };
+                for &i in doc_ids {
+                    let doc = docs[i].1;
+                    let tf = f64::from(doc.terms[*term]);
+                    let boost = if doc.title_terms.contains(*term) {
+                        TITLE_BOOST
+                    } else {
+                        1.0
+                    };
+                    let score = idf * weight * boost * tf / (tf + 1.2);
+                    let best = term_scores.entry(i).or_insert(0.0);
+                    *best = best.max(score);
+                }
+            }
+            // Every query term must match
+            if n == 0 {
+                scores = term_scores;
+            } else {
+                scores.retain(|i, _| term_scores.contains_key(i));
+                for (i, score) in scores.iter_mut() {
+                    *score += term_scores[i];
+                }
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .map(|(i, score)| {
+                let (parent_id, doc) = docs[i];
+                SearchHit {
+                    identifier: doc.identifier.clone(),
+                    parent_id: parent_id.to_string(),
+                    title: doc.title.clone(),
+                    status: doc.status.clone(),
+                    kind: doc.kind,
+                    score,
+                }
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.identifier.cmp(&b.identifier))
+        });
+        hits.truncate(limit);
+        hits
+    }
+}
+
+/// Load the project's index, refresh it against `.mobius/issues`, and save
+/// it back when anything changed.
+pub fn refresh_index() -> Result<SearchIndex> {
+    let path = index_path();
+    let mut index = SearchIndex::load(&path);
+    let changed = index.refresh(&get_project_mobius_path().join("issues"));
+    if changed > 0 || !path.exists() {
+        index.save(&path)?;
+    }
+    Ok(index)
+}
+
+/// Lowercased words of `text`, without stop words and single characters.
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 1)
+        .map(str::to_lowercase)
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+}
+
+fn build_doc(
+    identifier: &str,
+    title: &str,
+    status: &str,
+    kind: DocKind,
+    body: &[&str],
+) -> IndexedDoc {
+    let mut terms = BTreeMap::new();
+    let title_terms: BTreeSet<String> = tokenize(title).chain(tokenize(identifier)).collect();
+    for term in title_terms
+        .iter()
+        .cloned()
+        .chain(body.iter().flat_map(|text| tokenize(text)))
+    {
+        *terms.entry(term).or_insert(0) += 1;
+    }
+    IndexedDoc {
+        identifier: identifier.to_string(),
+        title: title.to_string(),
+        status: status.to_string(),
+        kind,
+        terms,
+        title_terms,
+    }
+}
+
+/// Hash of the name, size and modification time of every file the issue's
+/// documents are read from.
+fn issue_fingerprint(dir: &Path) -> u64 {
+    let mut files = vec![dir.join("parent.json"), dir.join("pending-updates.json")];
+    if let Ok(entries) = fs::read_dir(dir.join("tasks")) {
+        let mut tasks: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        tasks.sort();
+        files.extend(tasks);
+    }
+
+    let mut hasher = DefaultHasher::new();
+    for file in files {
+        let Ok(meta) = fs::metadata(&file) else {
+            continue;
+        };
+        file.hash(&mut hasher);
+        meta.len().hash(&mut hasher);
+        meta.modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos())
+            .hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Option<T> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Documents for one issue directory: the parent and each sub-task, with
+/// queued comments attached to the issue they target.
+fn read_issue_docs(dir: &Path) -> Vec<IndexedDoc> {
+    let mut comments: HashMap<String, Vec<String>> = HashMap::new();
+    let updates: Vec<LocalPendingUpdate> =
+        read_json(&dir.join("pending-updates.json")).unwrap_or_default();
+    for update in updates.iter().filter(|u| u.update_type == "add_comment") {
+        let body = update.payload.get("body").and_then(|b| b.as_str());
+        let target = update
+            .payload
+            .get("identifier")
+            .or_else(|| update.payload.get("issueId"))
+            .and_then(|t| t.as_str());
+        if let (Some(body), Some(target)) = (body, target) {
+            comments
+                .entry(target.to_string())
+                .or_default()
+                .push(body.to_string());
+        }
+    }
+    let body_for = |identifier: &str, description: &str| -> Vec<String> {
+        std::iter::once(description.to_string())
+            .chain(comments.get(identifier).into_iter().flatten().cloned())
+            .collect()
+    };
+
+    let mut docs = Vec::new();
+    if let Some(parent) = read_json::<ParentIssueContext>(&dir.join("parent.json")) {
+        let body = body_for(&parent.identifier, &parent.description);
+        let body: Vec<&str> = body.iter().map(String::as_str).collect();
+        docs.push(build_doc(
+            &parent.identifier,
+            &parent.title,
+            &parent.status,
+            DocKind::Parent,
+            &body,
+        ));
+    }
+
+    let mut task_files: Vec<PathBuf> = fs::read_dir(dir.join("tasks"))
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    task_files.sort();
+    for path in task_files
+        .iter()
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+    {
+        let Some(task) = read_json::<SubTaskContext>(path) else {
+            continue;
+        };
+        let identifier = if task.identifier.is_empty() {
+            &task.id
+        } else {
+            &task.identifier
+        };
+        let body = body_for(identifier, &task.description);
+        let body: Vec<&str> = body.iter().map(String::as_str).collect();
+        docs.push(build_doc(
+            identifier,
+            &task.title,
+            &task.status,
+            DocKind::SubTask,
+            &body,
+        ));
+    }
+    docs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_issue(root: &Path, id: &str, title: &str, description: &str, tasks: &[(&str, &str)]) {
+        let dir = root.join(id);
+        fs::create_dir_all(dir.join("tasks")).unwrap();
+        let parent = serde_json::json!({
+            "id": id, "identifier": id, "title": title,
+            "description": description, "status": "Backlog",
+        });
+        fs::write(dir.join("parent.json"), parent.to_string()).unwrap();
+        for (task_id, task_title) in tasks {
+            let task = serde_json::json!({
+                "id": task_id, "identifier": task_id, "title": task_title,
+                "status": "Ready",
+            });
+            fs::write(
+                dir.join("tasks").join(format!("{}.json", task_id)),
+                task.to_string(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_search_ranks_title_matches_and_refreshes_incrementally() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        write_issue(
+            root,
+            "MOB-10",
+            "Session handling",
+            "Move the auth middleware behind a feature flag",
+            &[("MOB-11", "Add authentication middleware")],
+        );
+        write_issue(root, "MOB-20", "Billing export", "CSV for finance", &[]);
+
+        let mut index = SearchIndex::load(&root.join("missing.json"));
+        assert_eq!(index.refresh(root), 2);
+        assert_eq!(index.len(), 3);
+
+        let hits = index.search("auth middleware", 10);
+        let ids: Vec<&str> = hits.iter().map(|h| h.identifier.as_str()).collect();
+        // The title hit outranks the description-only hit; "auth" prefixes
+        // "authentication"
+        assert_eq!(ids, vec!["MOB-11", "MOB-10"]);
+        assert_eq!(hits[0].parent_id, "MOB-10");
+        assert_eq!(hits[0].kind, DocKind::SubTask);
+        assert!(index.search("auth billing", 10).is_empty());
+        assert_eq!(index.search("mob-20", 10)[0].identifier, "MOB-20");
+
+        // Unchanged issues are not re-read; removed ones are dropped
+        assert_eq!(index.refresh(root), 0);
+        fs::remove_dir_all(root.join("MOB-20")).unwrap();
+        assert_eq!(index.refresh(root), 1);
+        assert!(index.search("billing", 10).is_empty());
+
+        let path = root.join("index.json");
+        index.save(&path).unwrap();
+        assert_eq!(SearchIndex::load(&path).len(), 2);
+    }
+}
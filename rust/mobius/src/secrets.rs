@@ -0,0 +1,166 @@
+//! Tracker credentials from the environment or the OS keychain.
+//!
+//! `mobius auth login` stores a backend's token in the keychain (macOS
+//! Keychain, Windows Credential Manager, or the Secret Service on Linux)
+//! under the name of the environment variable it stands in for. Lookups try
+//! the environment first, so CI and existing setups keep working unchanged.
+//! Set `MOBIUS_NO_KEYCHAIN` to skip the keychain, e.g. on headless machines.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::types::enums::Backend;
+
+/// Keychain service every mobius secret is stored under
+const KEYCHAIN_SERVICE: &str = "mobius";
+
+/// Where a secret was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretSource {
+    Env,
+    Keychain,
+}
+
+/// A secret's value and the variable it was found under
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundSecret {
+    pub var: &'static str,
+    pub source: SecretSource,
+    pub value: String,
+}
+
+/// Variables holding `backend`'s API token, preferred first. `auth login`
+/// stores under the first.
+pub fn token_vars(backend: Backend) -> &'static [&'static str] {
+    match backend {
+        Backend::Linear => &["LINEAR_API_KEY", "LINEAR_API_TOKEN"],
+        Backend::Jira => &["JIRA_API_TOKEN"],
+        Backend::Github => &["GITHUB_TOKEN", "GH_TOKEN"],
+        Backend::Gitlab => &["GITLAB_TOKEN"],
+        Backend::Local => &[],
+    }
+}
+
+/// The first of `vars` set in the environment, else the first in the keychain.
+pub fn find_secret(vars: &[&'static str]) -> Option<FoundSecret> {
+    let from_env = vars.iter().find_map(|&var| {
+        std::env::var(var)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|value| FoundSecret {
+                var,
+                source: SecretSource::Env,
+                value,
+            })
+    });
+    from_env.or_else(|| {
+        vars.iter().find_map(|&var| {
+            keychain_secret(var)
+                .ok()
+                .flatten()
+                .map(|value| FoundSecret {
+                    var,
+                    source: SecretSource::Keychain,
+                    value,
+                })
+        })
+    })
+}
+
+/// Value of `var` from the environment or the keychain.
+pub fn get_secret(var: &'static str) -> Option<String> {
+    find_secret(&[var]).map(|s| s.value)
+}
+
+/// `backend`'s API token from the environment or the keychain.
+pub fn backend_token(backend: Backend) -> Option<String> {
+    find_secret(token_vars(backend)).map(|s| s.value)
+}
+
+/// Value stored in the keychain for `var`, if any.
+pub fn keychain_secret(var: &str) -> Result<Option<String>> {
+    if !keychain_enabled() {
+        return Ok(None);
+    }
+    match keychain_entry(var)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {} from the keychain", var)),
+    }
+}
+
+/// Store `value` in the keychain under `var`, replacing any previous value.
+pub fn store_secret(var: &str, value: &str) -> Result<()> {
+    if !keychain_enabled() {
+        anyhow::bail!("The keychain is disabled (MOBIUS_NO_KEYCHAIN is set)");
+    }
+    keychain_entry(var)?
+        .set_password(value)
+        .with_context(|| format!("Failed to store {} in the keychain", var))
+}
+
+/// Remove `var` from the keychain, returning whether it was there.
+pub fn delete_secret(var: &str) -> Result<bool> {
+    if !keychain_enabled() {
+        return Ok(false);
+    }
+    match keychain_entry(var)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e).with_context(|| format!("Failed to remove {} from the keychain", var)),
+    }
+}
+
+/// Compare a presented shared secret with the expected one in time that does
+/// not depend on where they differ.
+pub fn tokens_match(presented: &str, expected: &str) -> bool {
+    let (presented, expected) = (presented.as_bytes(), expected.as_bytes());
+    let mut diff = presented.len() ^ expected.len();
+    for (i, &byte) in expected.iter().enumerate() {
+        diff |= usize::from(presented.get(i).copied().unwrap_or(0) ^ byte);
+    }
+    diff == 0
+}
+
+fn keychain_entry(var: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, var)
+        .with_context(|| format!("Failed to open the keychain entry for {}", var))
+}
+
+/// Tests never touch the developer's real keychain.
+fn keychain_enabled() -> bool {
+    !cfg!(test) && std::env::var_os("MOBIUS_NO_KEYCHAIN").is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("secret", "secret"));
+        assert!(!tokens_match("secreT", "secret"));
+        assert!(!tokens_match("secret-longer", "secret"));
+        assert!(!tokens_match("", "secret"));
+        assert!(!tokens_match("secret", ""));
+    }
+
+    #[test]
+    fn test_find_secret_prefers_earlier_env_vars() {
+        std::env::set_var("MOBIUS_TEST_SECRET_B", "second");
+        std::env::set_var("MOBIUS_TEST_SECRET_C", " ");
+        let found = find_secret(&[
+            "MOBIUS_TEST_SECRET_A",
+            "MOBIUS_TEST_SECRET_C",
+            "MOBIUS_TEST_SECRET_B",
+        ])
+        .unwrap();
+        assert_eq!(found.var, "MOBIUS_TEST_SECRET_B");
+        assert_eq!(found.source, SecretSource::Env);
+        assert_eq!(found.value, "second");
+        assert_eq!(find_secret(&["MOBIUS_TEST_SECRET_A"]), None);
+        std::env::remove_var("MOBIUS_TEST_SECRET_B");
+        std::env::remove_var("MOBIUS_TEST_SECRET_C");
+    }
+}
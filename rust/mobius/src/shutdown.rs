@@ -0,0 +1,62 @@
+//! Graceful shutdown on SIGINT/SIGTERM.
+//!
+//! The first signal sets a process-wide flag: running agents are interrupted,
+//! the loop flushes its state and ends the session as interrupted. A second
+//! signal runs the force-exit callback and exits immediately.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+use tokio::sync::Notify;
+
+/// Exit status for a forced exit (128 + SIGINT)
+pub const FORCED_EXIT_CODE: i32 = 130;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+fn notify() -> &'static Notify {
+    static NOTIFY: OnceLock<Notify> = OnceLock::new();
+    NOTIFY.get_or_init(Notify::new)
+}
+
+/// Install the SIGINT/SIGTERM handler.
+///
+/// `on_force_exit` runs before exiting on the second signal.
+pub fn install_handler<F>(on_force_exit: F) -> Result<(), ctrlc::Error>
+where
+    F: Fn() + Send + 'static,
+{
+    ctrlc::set_handler(move || {
+        if request_shutdown() {
+            eprintln!(
+                "\nInterrupting agents and saving state... (press Ctrl-C again to exit immediately)"
+            );
+        } else {
+            on_force_exit();
+            std::process::exit(FORCED_EXIT_CODE);
+        }
+    })
+}
+
+/// Flag a shutdown and wake anything waiting in [`interrupted`].
+///
+/// Returns `true` on the first request.
+pub fn request_shutdown() -> bool {
+    let first = !INTERRUPTED.swap(true, Ordering::SeqCst);
+    notify().notify_waiters();
+    first
+}
+
+/// Whether a shutdown has been requested.
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Resolve once a shutdown has been requested.
+pub async fn interrupted() {
+    let notified = notify().notified();
+    if is_interrupted() {
+        return;
+    }
+    notified.await;
+}
@@ -0,0 +1,421 @@
+//! Stacked pull requests: one branch per completed sub-task.
+//!
+//! The loop lands every sub-task on one branch, which makes for a single
+//! large PR. For review, each completed sub-task's commits (attributed by the
+//! identifier in their message, as in churn and bisect) are cherry-picked
+//! onto its blocker's stacked branch, or onto the base branch when it has
+//! none, so each PR shows only that sub-task's change.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::bisect::{git_output, BranchCommit, ScratchWorktree};
+use crate::context::{atomic_write_json, get_context_path};
+use crate::types::enums::TaskStatus;
+use crate::types::task_graph::{SubTask, TaskGraph};
+
+/// One sub-task's branch in the stack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackEntry {
+    pub identifier: String,
+    pub title: String,
+    pub branch: String,
+    /// Branch this one is stacked on: a blocker's branch or the base branch
+    pub base: String,
+    /// Loop-branch commits cherry-picked onto `base`, oldest first
+    pub commits: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pr_url: Option<String>,
+    /// Commit last pushed to `origin`, the lease for the next push
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pushed: Option<String>,
+}
+
+/// The stack last submitted for a parent, kept in `.mobius/issues/{id}/stack.json`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StackState {
+    pub parent_id: String,
+    pub base_branch: String,
+    pub updated_at: String,
+    pub entries: Vec<StackEntry>,
+}
+
+impl StackState {
+    pub fn new(parent_id: &str, base_branch: &str, entries: Vec<StackEntry>) -> Self {
+        Self {
+            parent_id: parent_id.to_string(),
+            base_branch: base_branch.to_string(),
+            updated_at: Utc::now().to_rfc3339(),
+            entries,
+        }
+    }
+}
+
+/// Stack entries for the completed sub-tasks with commits in `commits`, in
+/// the order their first commit landed.
+///
+/// A sub-task is stacked on the most recently landed of its blockers that is
+/// itself in the stack, otherwise on `base_branch`. Fails when a sub-task's
+/// blockers are on separate branches of the stack: no single branch holds
+/// all of their changes, so its PR could not be based on one.
+pub fn plan_stack(
+    graph: &TaskGraph,
+    commits: &[BranchCommit],
+    base_branch: &str,
+    loop_branch: &str,
+) -> Result<Vec<StackEntry>> {
+    let mut entries: Vec<StackEntry> = Vec::new();
+    for commit in commits {
+        let Some(identifier) = &commit.task else {
+            continue;
+        };
+        if let Some(entry) = entries.iter_mut().find(|e| &e.identifier == identifier) {
+            entry.commits.push(commit.sha.clone());
+            continue;
+        }
+        let Some(task) = graph
+            .tasks
+            .values()
+            .find(|t| &t.identifier == identifier && t.status == TaskStatus::Done)
+        else {
+            continue;
+        };
+
+        let blockers: Vec<usize> = task
+            .blocked_by
+            .iter()
+            .filter_map(|id| graph.tasks.get(id))
+            .filter_map(|blocker| {
+                entries
+                    .iter()
+                    .position(|e| e.identifier == blocker.identifier)
+            })
+            .collect();
+        let base = match blockers.iter().max() {
+            Some(&latest) => {
+                let chain = stack_chain(&entries, latest);
+                let apart: Vec<&str> = blockers
+                    .iter()
+                    .filter(|i| !chain.contains(i))
+                    .map(|&i| entries[i].identifier.as_str())
+                    .collect();
+                if !apart.is_empty() {
+                    bail!(
+                        "{} is blocked by {} and {}, which are on separate stacked branches; \
+                         make one depend on the other or submit without --stacked",
+                        identifier,
+                        entries[latest].identifier,
+                        apart.join(", ")
+                    );
+                }
+                entries[latest].branch.clone()
+            }
+            None => base_branch.to_string(),
+        };
+        entries.push(StackEntry {
+            identifier: identifier.clone(),
+            title: task.title.clone(),
+            branch: subtask_branch_name(task, loop_branch),
+            base,
+            commits: vec![commit.sha.clone()],
+            pr_url: None,
+            pushed: None,
+        });
+    }
+    Ok(entries)
+}
+
+/// Indices of `entries[index]` and every entry it is stacked on, down to the
+/// base branch.
+fn stack_chain(entries: &[StackEntry], index: usize) -> Vec<usize> {
+    let mut chain = vec![index];
+    let mut current = index;
+    while let Some(below) = entries
+        .iter()
+        .position(|e| e.branch == entries[current].base)
+    {
+        if chain.contains(&below) {
+            break;
+        }
+        chain.push(below);
+        current = below;
+    }
+    chain
+}
+
+/// A sub-task's own branch: the loop branch suffixed with its identifier.
+///
+/// The tracker's branch name is not used, since a developer may already
+/// have work on it and stacking resets the branch.
+pub fn subtask_branch_name(task: &SubTask, loop_branch: &str) -> String {
+    format!("{}-{}", loop_branch, task.identifier.to_lowercase())
+}
+
+/// `git push` lease for `branch`: the commit the previous stack pushed to it,
+/// or an empty lease (the branch must not exist on `origin`) if it was never
+/// pushed. Stacks recorded before pushes were tracked fall back to the
+/// remote-tracking ref.
+pub fn push_lease(branch: &str, previous: Option<&StackState>) -> String {
+    let entry = previous.and_then(|p| p.entries.iter().find(|e| e.branch == branch));
+    match entry {
+        Some(StackEntry {
+            pushed: Some(sha), ..
+        }) => format!("--force-with-lease={}:{}", branch, sha),
+        Some(_) => format!("--force-with-lease={}", branch),
+        None => format!("--force-with-lease={}:", branch),
+    }
+}
+
+/// Create or reset each entry's branch to its commits cherry-picked onto its
+/// base, in a scratch worktree so the loop's checkout is never touched.
+pub fn build_stack_branches(
+    repo_path: &Path,
+    parent_id: &str,
+    entries: &[StackEntry],
+) -> Result<()> {
+    let Some(first) = entries.first() else {
+        return Ok(());
+    };
+    let scratch = ScratchWorktree::create(repo_path, &format!("stack-{}", parent_id), &first.base)?;
+    let result = entries.iter().try_for_each(|entry| {
+        git_output(
+            &scratch.path,
+            &["checkout", "--detach", "--force", &entry.base],
+        )?;
+        let mut args = vec!["cherry-pick"];
+        args.extend(entry.commits.iter().map(String::as_str));
+        if let Err(e) = git_output(&scratch.path, &args) {
+            let _ = git_output(&scratch.path, &["cherry-pick", "--abort"]);
+            return Err(e).with_context(|| {
+                format!(
+                    "{}'s commits do not apply cleanly on {}",
+                    entry.identifier, entry.base
+                )
+            });
+        }
+        git_output(&scratch.path, &["branch", "-f", &entry.branch, "HEAD"])?;
+        Ok(())
+    });
+    scratch.remove();
+    result
+}
+
+/// Markdown tree of the stack rooted at the base branch, with `current`
+/// (an identifier) marked as "this PR".
+pub fn render_stack_graph(state: &StackState, current: Option<&str>) -> String {
+    let mut lines = vec![format!("- `{}`", state.base_branch)];
+    push_children(state, &state.base_branch, 1, current, &mut lines);
+    lines.join("\n")
+}
+
+fn push_children(
+    state: &StackState,
+    base: &str,
+    depth: usize,
+    current: Option<&str>,
+    lines: &mut Vec<String>,
+) {
+    for entry in state.entries.iter().filter(|e| e.base == base) {
+        let link = entry
+            .pr_url
+            .as_deref()
+            .map(|url| format!(" ({})", url))
+            .unwrap_or_default();
+        let label = format!("{}: {}{}", entry.identifier, entry.title, link);
+        let label = if current == Some(entry.identifier.as_str()) {
+            format!("**{}** ← this PR", label)
+        } else {
+            label
+        };
+        lines.push(format!("{}- {}", "  ".repeat(depth), label));
+        push_children(state, &entry.branch, depth + 1, current, lines);
+    }
+}
+
+/// Get the path to stack.json.
+pub fn get_stack_path(parent_id: &str) -> PathBuf {
+    get_context_path(parent_id).join("stack.json")
+}
+
+/// Load the stack last submitted for `parent_id`, if any.
+pub fn read_stack(parent_id: &str) -> Option<StackState> {
+    let content = fs::read_to_string(get_stack_path(parent_id)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Persist the stack to `.mobius/issues/{id}/stack.json`.
+pub fn write_stack(state: &StackState) -> Result<()> {
+    let path = get_stack_path(&state.parent_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    atomic_write_json(&path, state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::task_graph::{build_task_graph, LinearIssue, Relation, Relations};
+
+    fn issue(id: &str, status: &str, blocked_by: &[&str]) -> LinearIssue {
+        LinearIssue {
+            id: id.to_string(),
+            identifier: id.to_string(),
+            title: format!("Task {}", id),
+            status: status.to_string(),
+            git_branch_name: String::new(),
+            relations: Some(Relations {
+                blocked_by: blocked_by
+                    .iter()
+                    .map(|b| Relation {
+                        id: b.to_string(),
+                        identifier: b.to_string(),
+                    })
+                    .collect(),
+                blocks: vec![],
+            }),
+            scoring: None,
+            priority: None,
+            labels: vec![],
+            timeout_minutes: None,
+            task_type: Default::default(),
+        }
+    }
+
+    fn commit(sha: &str, task: Option<&str>) -> BranchCommit {
+        BranchCommit {
+            sha: sha.to_string(),
+            subject: format!("commit {}", sha),
+            task: task.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_plan_stack_stacks_on_latest_blocker() {
+        let graph = build_task_graph(
+            "MOB-1",
+            "MOB-1",
+            &[
+                issue("MOB-2", "Done", &[]),
+                issue("MOB-3", "Done", &["MOB-2"]),
+                issue("MOB-4", "Done", &["MOB-2", "MOB-3"]),
+                issue("MOB-5", "In Progress", &["MOB-4"]),
+                issue("MOB-6", "Done", &[]),
+            ],
+        );
+        let commits = vec![
+            commit("a", Some("MOB-2")),
+            commit("b", Some("MOB-3")),
+            commit("c", None),
+            commit("d", Some("MOB-2")),
+            commit("e", Some("MOB-4")),
+            commit("f", Some("MOB-5")),
+            commit("g", Some("MOB-6")),
+        ];
+
+        let entries = plan_stack(&graph, &commits, "main", "feat/mob-1").unwrap();
+        let summary: Vec<(&str, &str, Vec<&str>)> = entries
+            .iter()
+            .map(|e| {
+                (
+                    e.branch.as_str(),
+                    e.base.as_str(),
+                    e.commits.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("feat/mob-1-mob-2", "main", vec!["a", "d"]),
+                ("feat/mob-1-mob-3", "feat/mob-1-mob-2", vec!["b"]),
+                ("feat/mob-1-mob-4", "feat/mob-1-mob-3", vec!["e"]),
+                ("feat/mob-1-mob-6", "main", vec!["g"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_stack_refuses_blockers_on_separate_branches() {
+        let graph = build_task_graph(
+            "MOB-1",
+            "MOB-1",
+            &[
+                issue("MOB-2", "Done", &[]),
+                issue("MOB-3", "Done", &[]),
+                issue("MOB-4", "Done", &["MOB-2", "MOB-3"]),
+            ],
+        );
+        let commits = vec![
+            commit("a", Some("MOB-2")),
+            commit("b", Some("MOB-3")),
+            commit("c", Some("MOB-4")),
+        ];
+
+        let err = plan_stack(&graph, &commits, "main", "feat/mob-1").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("MOB-4 is blocked by MOB-3 and MOB-2"));
+    }
+
+    #[test]
+    fn test_push_lease() {
+        let entry = |branch: &str, pushed: Option<&str>| StackEntry {
+            identifier: "MOB-2".to_string(),
+            title: "Task MOB-2".to_string(),
+            branch: branch.to_string(),
+            base: "main".to_string(),
+            commits: vec![],
+            pr_url: None,
+            pushed: pushed.map(str::to_string),
+        };
+        let previous = StackState::new(
+            "MOB-1",
+            "main",
+            vec![
+                entry("feat/mob-1-mob-2", Some("abc123")),
+                entry("old", None),
+            ],
+        );
+
+        assert_eq!(
+            push_lease("feat/mob-1-mob-2", Some(&previous)),
+            "--force-with-lease=feat/mob-1-mob-2:abc123"
+        );
+        assert_eq!(push_lease("old", Some(&previous)), "--force-with-lease=old");
+        assert_eq!(
+            push_lease("feat/mob-1-mob-3", Some(&previous)),
+            "--force-with-lease=feat/mob-1-mob-3:"
+        );
+        assert_eq!(push_lease("new", None), "--force-with-lease=new:");
+    }
+
+    #[test]
+    fn test_render_stack_graph_marks_current_pr() {
+        let entry = |id: &str, base: &str| StackEntry {
+            identifier: id.to_string(),
+            title: format!("Task {}", id),
+            branch: format!("b-{}", id),
+            base: base.to_string(),
+            commits: vec![],
+            pr_url: Some(format!("https://example.test/{}", id)),
+            pushed: None,
+        };
+        let state = StackState::new(
+            "MOB-1",
+            "main",
+            vec![entry("MOB-2", "main"), entry("MOB-3", "b-MOB-2")],
+        );
+
+        assert_eq!(
+            render_stack_graph(&state, Some("MOB-3")),
+            "- `main`\n  - MOB-2: Task MOB-2 (https://example.test/MOB-2)\n    - **MOB-3: Task MOB-3 (https://example.test/MOB-3)** ← this PR"
+        );
+    }
+}
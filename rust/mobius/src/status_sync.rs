@@ -1,16 +1,24 @@
 //! Status sync — fetch current issue statuses from backend and update local parent.json files.
 //!
 //! Used by `list` and `clean` commands to ensure local state reflects
-//! the actual backend status before making decisions or displaying info.
+//! the actual backend status before making decisions or displaying info,
+//! and by `loop` to notice human sub-tasks closed on the backend.
 
 use anyhow::Result;
 use regex::Regex;
 use std::fs;
 
+use crate::github::GithubClient;
+use crate::gitlab::GitlabClient;
 use crate::jira::JiraClient;
 use crate::linear::LinearClient;
-use crate::local_state::{get_project_mobius_path, read_parent_spec, update_parent_status};
-use crate::types::enums::Backend;
+use crate::local_state::{
+    get_project_mobius_path, read_parent_spec, update_parent_status, update_subtask_status,
+};
+use crate::offline::is_offline;
+use crate::types::config::StatusMapping;
+use crate::types::enums::{Backend, TaskStatus};
+use crate::types::task_graph::{get_awaiting_human_tasks, map_backend_status, TaskGraph};
 
 /// Result of a backend status sync operation.
 #[derive(Debug, Clone, Default)]
@@ -44,6 +52,14 @@ async fn fetch_backend_status(issue_id: &str, backend: Backend) -> Option<String
             let client = JiraClient::new().ok()?;
             client.fetch_jira_issue_status(issue_id).await.ok()
         }
+        Backend::Github => {
+            let client = GithubClient::new().ok()?;
+            client.fetch_github_issue_status(issue_id).await.ok()
+        }
+        Backend::Gitlab => {
+            let client = GitlabClient::new().ok()?;
+            client.fetch_gitlab_issue_status(issue_id).await.ok()
+        }
         Backend::Local => None,
     }
 }
@@ -55,7 +71,7 @@ async fn fetch_backend_status(issue_id: &str, backend: Backend) -> Option<String
 pub async fn sync_backend_statuses(backend: Backend) -> Result<SyncResult> {
     let mut result = SyncResult::default();
 
-    if backend == Backend::Local {
+    if backend == Backend::Local || is_offline() {
         return Ok(result);
     }
 
@@ -109,6 +125,35 @@ pub async fn sync_backend_statuses(backend: Backend) -> Result<SyncResult> {
     Ok(result)
 }
 
+/// Mark awaiting human sub-tasks of `issue_id` done locally once the backend
+/// reports them done.
+///
+/// Returns the identifiers that were closed on the backend.
+pub async fn sync_human_task_statuses(
+    issue_id: &str,
+    graph: &TaskGraph,
+    backend: Backend,
+    status_mapping: Option<&StatusMapping>,
+) -> Vec<String> {
+    let mut done = Vec::new();
+    if is_offline() {
+        return done;
+    }
+    for task in get_awaiting_human_tasks(graph) {
+        if is_local_id(&task.identifier) {
+            continue;
+        }
+        let Some(status) = fetch_backend_status(&task.identifier, backend).await else {
+            continue;
+        };
+        if map_backend_status(&status, status_mapping) == TaskStatus::Done {
+            update_subtask_status(issue_id, &task.identifier, "done");
+            done.push(task.identifier.clone());
+        }
+    }
+    done
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,7 +4,19 @@
 //! when invoked with `--output-format stream-json`.
 
 use std::fs;
-use std::path::Path;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+/// Longest tool input or result excerpt shown by [`parse_log_events`]
+const LOG_EXCERPT_CHARS: usize = 160;
+
+/// Directory the loop tees each agent's raw stream-json output to
+/// (`{tmp}/mobius/{parentId}/{identifier}.jsonl`).
+pub fn agent_output_dir(parent_id: &str) -> PathBuf {
+    std::env::temp_dir().join("mobius").join(parent_id)
+}
 
 /// Token usage data extracted from Claude CLI output.
 #[derive(Debug, Clone, Default)]
@@ -33,6 +45,39 @@ pub fn parse_final_tokens(file_path: &Path) -> Option<TokenUsage> {
     None
 }
 
+/// Final `result` event of a Claude CLI run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResultEvent {
+    /// `success`, `error_max_turns`, `error_during_execution`, ...
+    pub subtype: Option<String>,
+    pub is_error: bool,
+    /// Final assistant message or error text
+    pub result: Option<String>,
+}
+
+/// Parse the final `result` event from a Claude CLI output file.
+pub fn parse_result_event(file_path: &Path) -> Option<ResultEvent> {
+    let content = fs::read_to_string(file_path).ok()?;
+    let line = content.lines().rev().map(str::trim).find(|line| {
+        line.contains("\"type\":\"result\"") || line.contains("\"type\": \"result\"")
+    })?;
+    let value: serde_json::Value = serde_json::from_str(line).ok()?;
+    Some(ResultEvent {
+        subtype: value
+            .get("subtype")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+        is_error: value
+            .get("is_error")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        result: value
+            .get("result")
+            .and_then(|v| v.as_str())
+            .map(str::to_string),
+    })
+}
+
 /// Parse current token usage from a running Claude CLI output file.
 ///
 /// Scans backwards for the latest line containing token usage data,
@@ -53,6 +98,263 @@ pub fn parse_current_tokens(file_path: &Path) -> Option<TokenUsage> {
     None
 }
 
+/// Kind of a human-readable event rendered from stream-json
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogEventKind {
+    /// Session start (`system`/`init`)
+    System,
+    /// Assistant text
+    Text,
+    ToolUse,
+    ToolResult,
+    ToolError,
+    /// Final `result` event
+    Result,
+    /// Final `result` event reporting an error
+    ResultError,
+}
+
+/// One human-readable line rendered from a stream-json event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogEvent {
+    pub kind: LogEventKind,
+    pub text: String,
+}
+
+/// Render the assistant, tool, and result events in one stream-json line.
+///
+/// Other events (deltas, hooks) and invalid JSON produce nothing.
+pub fn parse_log_events(line: &str) -> Vec<LogEvent> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+        return Vec::new();
+    };
+    let event = |kind, text: String| LogEvent { kind, text };
+    let str_field =
+        |v: &serde_json::Value, key: &str| v.get(key).and_then(|f| f.as_str()).map(str::to_string);
+
+    match value.get("type").and_then(|t| t.as_str()) {
+        Some("system") if str_field(&value, "subtype").as_deref() == Some("init") => {
+            let model = str_field(&value, "model").unwrap_or_else(|| "unknown".to_string());
+            vec![event(
+                LogEventKind::System,
+                format!("session started (model {})", model),
+            )]
+        }
+        Some("assistant") | Some("user") => message_blocks(&value)
+            .iter()
+            .filter_map(|block| match block.get("type").and_then(|t| t.as_str()) {
+                Some("text") => str_field(block, "text")
+                    .filter(|t| !t.trim().is_empty())
+                    .map(|t| event(LogEventKind::Text, t.trim().to_string())),
+                Some("tool_use") => {
+                    let name = str_field(block, "name").unwrap_or_else(|| "tool".to_string());
+                    let input = block.get("input").map(summarize_tool_input);
+                    Some(event(
+                        LogEventKind::ToolUse,
+                        match input.filter(|i| !i.is_empty()) {
+                            Some(input) => format!("{}: {}", name, input),
+                            None => name,
+                        },
+                    ))
+                }
+                Some("tool_result") => {
+                    let is_error = block
+                        .get("is_error")
+                        .and_then(|e| e.as_bool())
+                        .unwrap_or(false);
+                    let content = block
+                        .get("content")
+                        .map(tool_result_text)
+                        .unwrap_or_default();
+                    let kind = if is_error {
+                        LogEventKind::ToolError
+                    } else {
+                        LogEventKind::ToolResult
+                    };
+                    let lines = content.lines().count();
+                    let first = content.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+                    let summary = match lines {
+                        0 => "(no output)".to_string(),
+                        1 => excerpt(first),
+                        n => format!("{} (+{} lines)", excerpt(first), n - 1),
+                    };
+                    Some(event(kind, summary))
+                }
+                _ => None,
+            })
+            .collect(),
+        Some("result") => {
+            let is_error = value
+                .get("is_error")
+                .and_then(|e| e.as_bool())
+                .unwrap_or(false);
+            let subtype = str_field(&value, "subtype").unwrap_or_else(|| "done".to_string());
+            let mut text = subtype;
+            if let Some(ms) = value.get("duration_ms").and_then(|d| d.as_u64()) {
+                text.push_str(&format!(" in {:.1}s", ms as f64 / 1000.0));
+            }
+            if let Some(cost) = value.get("total_cost_usd").and_then(|c| c.as_f64()) {
+                text.push_str(&format!(", ${:.2}", cost));
+            }
+            let kind = if is_error {
+                LogEventKind::ResultError
+            } else {
+                LogEventKind::Result
+            };
+            vec![event(kind, text)]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Incremental reader for a stream-json file that is still being written.
+///
+/// Each [`StreamTail::poll`] reads only the bytes appended since the last
+/// call and accumulates the assistant text, so STATUS markers are matched
+/// against the whole transcript rather than whatever a pane still shows.
+#[derive(Debug)]
+pub struct StreamTail {
+    path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+    text: String,
+    finished: bool,
+}
+
+impl StreamTail {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            offset: 0,
+            partial: Vec::new(),
+            text: String::new(),
+            finished: false,
+        }
+    }
+
+    /// Read newly appended lines. Returns `true` when new text arrived.
+    pub fn poll(&mut self) -> bool {
+        let Ok(mut file) = fs::File::open(&self.path) else {
+            return false;
+        };
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if len < self.offset {
+            // Truncated by a new attempt: start over
+            *self = Self::new(&self.path);
+        }
+        if len == self.offset || file.seek(SeekFrom::Start(self.offset)).is_err() {
+            return false;
+        }
+        let Ok(read) = file.read_to_end(&mut self.partial) else {
+            return false;
+        };
+        self.offset += read as u64;
+
+        // Keep a trailing incomplete line for the next poll
+        let Some(end) = self.partial.iter().rposition(|b| *b == b'\n') else {
+            return false;
+        };
+        let complete: Vec<u8> = self.partial.drain(..=end).collect();
+        let before = self.text.len();
+        for line in String::from_utf8_lossy(&complete).lines() {
+            self.push_line(line);
+        }
+        self.text.len() > before
+    }
+
+    fn push_line(&mut self, line: &str) {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(line.trim()) else {
+            return;
+        };
+        let mut push = |text: &str| {
+            if !self.text.is_empty() {
+                self.text.push('\n');
+            }
+            self.text.push_str(text);
+        };
+        match value.get("type").and_then(|t| t.as_str()) {
+            Some("assistant") => {
+                for block in message_blocks(&value) {
+                    if block.get("type").and_then(|t| t.as_str()) == Some("text") {
+                        if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+                            push(text);
+                        }
+                    }
+                }
+            }
+            Some("result") => {
+                if let Some(text) = value.get("result").and_then(|r| r.as_str()) {
+                    push(text);
+                }
+                self.finished = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// All assistant text seen so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether the final `result` event has been written.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+fn message_blocks(value: &serde_json::Value) -> Vec<serde_json::Value> {
+    value
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .and_then(|c| c.as_array())
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// The most telling field of a tool input, or the compact JSON.
+fn summarize_tool_input(input: &serde_json::Value) -> String {
+    for key in [
+        "command",
+        "file_path",
+        "path",
+        "pattern",
+        "url",
+        "description",
+    ] {
+        if let Some(text) = input.get(key).and_then(|v| v.as_str()) {
+            return excerpt(text);
+        }
+    }
+    match input.as_object() {
+        Some(obj) if obj.is_empty() => String::new(),
+        _ => excerpt(&input.to_string()),
+    }
+}
+
+fn tool_result_text(content: &serde_json::Value) -> String {
+    match content {
+        serde_json::Value::String(text) => text.clone(),
+        serde_json::Value::Array(parts) => parts
+            .iter()
+            .filter_map(|p| p.get("text").and_then(|t| t.as_str()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn excerpt(text: &str) -> String {
+    let first_line = text.lines().next().unwrap_or("").trim();
+    if first_line.chars().count() > LOG_EXCERPT_CHARS {
+        let cut: String = first_line.chars().take(LOG_EXCERPT_CHARS).collect();
+        format!("{}…", cut)
+    } else {
+        first_line.to_string()
+    }
+}
+
 /// Extract input_tokens and output_tokens from a JSON line.
 ///
 /// Handles both snake_case (`input_tokens`) and camelCase (`inputTokens`) keys.
@@ -131,6 +433,81 @@ mod tests {
         assert!(parse_final_tokens(file.path()).is_none());
     }
 
+    #[test]
+    fn test_parse_result_event_error_subtype() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, r#"{{"type":"message_start"}}"#).unwrap();
+        writeln!(
+            file,
+            r#"{{"type":"result","subtype":"error_max_turns","is_error":true,"result":"Reached max turns"}}"#
+        )
+        .unwrap();
+
+        let event = parse_result_event(file.path()).unwrap();
+        assert_eq!(event.subtype.as_deref(), Some("error_max_turns"));
+        assert!(event.is_error);
+        assert_eq!(event.result.as_deref(), Some("Reached max turns"));
+    }
+
+    #[test]
+    fn test_stream_tail_reads_appended_lines() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let mut tail = StreamTail::new(file.path());
+        assert!(!tail.poll());
+
+        writeln!(
+            file,
+            r#"{{"type":"assistant","message":{{"content":[{{"type":"text","text":"Working"}}]}}}}"#
+        )
+        .unwrap();
+        // An unterminated line waits for the rest of the event
+        write!(
+            file,
+            r#"{{"type":"result","result":"STATUS: SUBTASK_COMPLETE""#
+        )
+        .unwrap();
+        file.flush().unwrap();
+        assert!(tail.poll());
+        assert_eq!(tail.text(), "Working");
+        assert!(!tail.is_finished());
+
+        writeln!(file, "}}").unwrap();
+        assert!(tail.poll());
+        assert_eq!(tail.text(), "Working\nSTATUS: SUBTASK_COMPLETE");
+        assert!(tail.is_finished());
+        assert!(!tail.poll());
+    }
+
+    #[test]
+    fn test_parse_log_events() {
+        let init = r#"{"type":"system","subtype":"init","model":"claude-opus-4"}"#;
+        assert_eq!(
+            parse_log_events(init),
+            vec![LogEvent {
+                kind: LogEventKind::System,
+                text: "session started (model claude-opus-4)".to_string()
+            }]
+        );
+
+        let assistant = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Running tests"},{"type":"tool_use","name":"Bash","input":{"command":"cargo test\nmore"}}]}}"#;
+        let events = parse_log_events(assistant);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].text, "Running tests");
+        assert_eq!(events[1].kind, LogEventKind::ToolUse);
+        assert_eq!(events[1].text, "Bash: cargo test");
+
+        let result = r#"{"type":"user","message":{"content":[{"type":"tool_result","is_error":true,"content":[{"type":"text","text":"error[E0425]\nline 2\nline 3"}]}]}}"#;
+        let events = parse_log_events(result);
+        assert_eq!(events[0].kind, LogEventKind::ToolError);
+        assert_eq!(events[0].text, "error[E0425] (+2 lines)");
+
+        let done = r#"{"type":"result","subtype":"success","is_error":false,"duration_ms":12500,"total_cost_usd":0.4}"#;
+        assert_eq!(parse_log_events(done)[0].text, "success in 12.5s, $0.40");
+
+        assert!(parse_log_events(r#"{"type":"content_block_delta"}"#).is_empty());
+        assert!(parse_log_events("not json").is_empty());
+    }
+
     #[test]
     fn test_parse_current_tokens_finds_latest() {
         let mut file = tempfile::NamedTempFile::new().unwrap();
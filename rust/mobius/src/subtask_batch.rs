@@ -0,0 +1,464 @@
+//! Reviewed, all-or-nothing creation of queued sub-tasks
+//!
+//! `create_subtask` pending updates are drafts: a plan decomposed into dozens
+//! of sub-tasks should not reach the backend one update at a time. They are
+//! created together by `mobius push --review`, after confirmation, and if any
+//! creation fails the issues created so far in the batch are rolled back.
+//!
+//! Blockers that name another draft's title are resolved to the issue created
+//! for it; anything else is passed to the backend as an existing identifier.
+
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use crate::github::{CreateGithubIssueOptions, GithubClient};
+use crate::gitlab::{CreateGitlabIssueOptions, GitlabClient};
+use crate::jira::{CreateJiraIssueOptions, JiraClient};
+use crate::linear::{CreateLinearIssueInput, LinearClient};
+use crate::types::config::LoopConfig;
+use crate::types::context::{PendingUpdate, PendingUpdateData};
+use crate::types::enums::Backend;
+
+/// Jira issue type used for created sub-tasks
+const JIRA_SUBTASK_TYPE: &str = "Subtask";
+
+/// A queued `create_subtask` update awaiting review
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DraftSubtask {
+    /// ID of the pending update this draft came from
+    pub update_id: String,
+    pub parent_id: String,
+    pub title: String,
+    pub description: String,
+    pub blocked_by: Vec<String>,
+}
+
+impl DraftSubtask {
+    /// The draft held by an unsynced `create_subtask` update, if any.
+    pub fn from_update(update: &PendingUpdate) -> Option<Self> {
+        if update.synced_at.is_some() || update.error.is_some() {
+            return None;
+        }
+        match &update.data {
+            PendingUpdateData::CreateSubtask {
+                parent_id,
+                title,
+                description,
+                blocked_by,
+            } => Some(Self {
+                update_id: update.id.clone(),
+                parent_id: parent_id.clone(),
+                title: title.clone(),
+                description: description.clone(),
+                blocked_by: blocked_by.clone().unwrap_or_default(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// An issue created for a draft
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedSubtask {
+    pub update_id: String,
+    pub title: String,
+    /// Identifier shown to users (`ABC-124`, `#42`)
+    pub identifier: String,
+    /// Backend-internal ID used for relations and rollback
+    pub id: String,
+}
+
+/// A batch that stopped partway through
+#[derive(Debug)]
+pub struct BatchFailure {
+    /// Issues created before the failure; these need rolling back
+    pub created: Vec<CreatedSubtask>,
+    pub title: String,
+    pub error: anyhow::Error,
+}
+
+/// Order drafts so that blockers within the batch are created first.
+///
+/// Queue order is kept otherwise. Fails when drafts block each other in a
+/// cycle, since no creation order can satisfy it.
+pub fn order_drafts(drafts: Vec<DraftSubtask>) -> Result<Vec<DraftSubtask>> {
+    let index: HashMap<&str, usize> = drafts
+        .iter()
+        .enumerate()
+        .map(|(i, d)| (d.title.as_str(), i))
+        .collect();
+
+    let mut blocker_count = vec![0usize; drafts.len()];
+    let mut unblocks: Vec<Vec<usize>> = vec![Vec::new(); drafts.len()];
+    for (i, draft) in drafts.iter().enumerate() {
+        for blocker in &draft.blocked_by {
+            if let Some(&j) = index.get(blocker.as_str()) {
+                blocker_count[i] += 1;
+                unblocks[j].push(i);
+            }
+        }
+    }
+
+    let mut ready: VecDeque<usize> = (0..drafts.len())
+        .filter(|&i| blocker_count[i] == 0)
+        .collect();
+    let mut order = Vec::with_capacity(drafts.len());
+    while let Some(i) = ready.pop_front() {
+        order.push(i);
+        for &next in &unblocks[i] {
+            blocker_count[next] -= 1;
+            if blocker_count[next] == 0 {
+                ready.push_back(next);
+            }
+        }
+    }
+    if order.len() < drafts.len() {
+        let stuck: Vec<&str> = (0..drafts.len())
+            .filter(|i| !order.contains(i))
+            .map(|i| drafts[i].title.as_str())
+            .collect();
+        bail!(
+            "Queued sub-tasks block each other in a cycle: {}",
+            stuck.join(", ")
+        );
+    }
+
+    let mut slots: Vec<Option<DraftSubtask>> = drafts.into_iter().map(Some).collect();
+    Ok(order.into_iter().filter_map(|i| slots[i].take()).collect())
+}
+
+/// Backend clients plus what each needs to know about the parent issue
+enum Creator {
+    Linear {
+        client: LinearClient,
+        parent_id: String,
+        team_id: String,
+    },
+    Jira {
+        client: JiraClient,
+        project_key: String,
+        labels: Vec<String>,
+    },
+    Github {
+        client: GithubClient,
+        labels: Vec<String>,
+    },
+    Gitlab {
+        client: GitlabClient,
+        labels: Vec<String>,
+    },
+}
+
+impl Creator {
+    async fn new(backend: Backend, config: &LoopConfig, parent: &str) -> Result<Self> {
+        Ok(match backend {
+            Backend::Linear => {
+                let client = LinearClient::new()?;
+                let (parent_id, team_id) = client
+                    .fetch_linear_issue_team(parent)
+                    .await
+                    .with_context(|| format!("Failed to look up {}", parent))?;
+                Self::Linear {
+                    client,
+                    parent_id,
+                    team_id,
+                }
+            }
+            Backend::Jira => {
+                let jira = config.jira.as_ref();
+                // Sub-tasks live in their parent's project
+                let project_key = parent
+                    .split_once('-')
+                    .map(|(key, _)| key.to_string())
+                    .or_else(|| jira.and_then(|j| j.project_key.clone()))
+                    .with_context(|| format!("Cannot tell the Jira project of {}", parent))?;
+                Self::Jira {
+                    client: JiraClient::new()?,
+                    project_key,
+                    labels: jira
+                        .and_then(|j| j.default_labels.clone())
+                        .unwrap_or_default(),
+                }
+            }
+            Backend::Github => Self::Github {
+                client: GithubClient::new()?,
+                labels: config
+                    .github
+                    .as_ref()
+                    .and_then(|g| g.default_labels.clone())
+                    .unwrap_or_default(),
+            },
+            Backend::Gitlab => Self::Gitlab {
+                client: GitlabClient::new()?,
+                labels: config
+                    .gitlab
+                    .as_ref()
+                    .and_then(|g| g.default_labels.clone())
+                    .unwrap_or_default(),
+            },
+            Backend::Local => bail!("Local sub-tasks are not created on a backend"),
+        })
+    }
+
+    /// Create the issue, then attach it to its parent and blockers.
+    ///
+    /// An issue that was created but not fully linked is still returned
+    /// alongside the error, so it can be rolled back.
+    async fn create(
+        &self,
+        parent: &str,
+        draft: &DraftSubtask,
+        blockers: &[String],
+    ) -> (Option<CreatedSubtask>, Result<()>) {
+        let created = |id: String, identifier: String| CreatedSubtask {
+            update_id: draft.update_id.clone(),
+            title: draft.title.clone(),
+            identifier,
+            id,
+        };
+        match self {
+            Self::Linear {
+                client,
+                parent_id,
+                team_id,
+            } => {
+                let input = CreateLinearIssueInput {
+                    team_id: team_id.clone(),
+                    title: draft.title.clone(),
+                    description: Some(draft.description.clone()),
+                    parent_id: Some(parent_id.clone()),
+                    blocked_by: blockers.to_vec(),
+                    labels: Vec::new(),
+                    priority: None,
+                };
+                // Linear creates the blocker relations in the same call
+                match client.create_linear_issue(&input).await {
+                    Ok(issue) => (Some(created(issue.id, issue.identifier)), Ok(())),
+                    Err(e) => (None, Err(e.into())),
+                }
+            }
+            Self::Jira {
+                client,
+                project_key,
+                labels,
+            } => {
+                let options = CreateJiraIssueOptions {
+                    project_key: project_key.clone(),
+                    issue_type_name: JIRA_SUBTASK_TYPE.to_string(),
+                    summary: draft.title.clone(),
+                    description: Some(draft.description.clone()),
+                    parent_key: Some(parent.to_string()),
+                    labels: Some(labels.clone()),
+                    assignee_id: None,
+                };
+                let issue = match client.create_jira_issue(&options).await {
+                    Ok(issue) => created(issue.key.clone(), issue.key),
+                    Err(e) => return (None, Err(e.into())),
+                };
+                for blocker in blockers {
+                    if let Err(e) = client.create_jira_issue_link(blocker, &issue.id).await {
+                        return (Some(issue), Err(e.into()));
+                    }
+                }
+                (Some(issue), Ok(()))
+            }
+            Self::Github { client, labels } => {
+                let options = CreateGithubIssueOptions {
+                    title: draft.title.clone(),
+                    body: Some(with_blocked_by_lines(&draft.description, blockers)),
+                    labels: Some(labels.clone()),
+                    assignees: None,
+                };
+                let issue = match client.create_github_issue(&options).await {
+                    Ok(issue) => issue,
+                    Err(e) => return (None, Err(e.into())),
+                };
+                let result = client.add_github_sub_issue(parent, issue.id).await;
+                (
+                    Some(created(
+                        issue.number.to_string(),
+                        format!("#{}", issue.number),
+                    )),
+                    result.map_err(Into::into),
+                )
+            }
+            Self::Gitlab { client, labels } => {
+                let options = CreateGitlabIssueOptions {
+                    title: draft.title.clone(),
+                    description: Some(draft.description.clone()),
+                    labels: Some(labels.clone()),
+                };
+                let issue = match client.create_gitlab_issue(&options).await {
+                    Ok(issue) => issue,
+                    Err(e) => return (None, Err(e.into())),
+                };
+                let iid = issue.iid.to_string();
+                let issue = created(iid.clone(), format!("#{}", iid));
+                // Sub-tasks are the parent's `relates_to` links
+                if let Err(e) = client
+                    .create_gitlab_issue_link(parent, &iid, "relates_to")
+                    .await
+                {
+                    return (Some(issue), Err(e.into()));
+                }
+                for blocker in blockers {
+                    if let Err(e) = client
+                        .create_gitlab_issue_link(&iid, blocker, "is_blocked_by")
+                        .await
+                    {
+                        return (Some(issue), Err(e.into()));
+                    }
+                }
+                (Some(issue), Ok(()))
+            }
+        }
+    }
+
+    /// Remove an issue created by this batch. Returns how it was undone.
+    async fn undo(&self, issue: &CreatedSubtask) -> Result<&'static str> {
+        match self {
+            Self::Linear { client, .. } => {
+                client.delete_linear_issue(&issue.id).await?;
+                Ok("deleted")
+            }
+            Self::Jira { client, .. } => {
+                client.delete_jira_issue(&issue.id).await?;
+                Ok("deleted")
+            }
+            // Neither API lets ordinary tokens delete issues
+            Self::Github { client, .. } => {
+                client
+                    .update_github_issue_status(&issue.id, "Canceled")
+                    .await?;
+                Ok("closed")
+            }
+            Self::Gitlab { client, .. } => {
+                client
+                    .update_gitlab_issue_status(&issue.id, "Canceled")
+                    .await?;
+                Ok("closed")
+            }
+        }
+    }
+}
+
+/// Append `Blocked by #N` lines, the form GitHub sub-issue blockers are read from.
+fn with_blocked_by_lines(description: &str, blockers: &[String]) -> String {
+    if blockers.is_empty() {
+        return description.to_string();
+    }
+    let refs: Vec<String> = blockers
+        .iter()
+        .map(|b| format!("#{}", b.trim_start_matches('#')))
+        .collect();
+    format!(
+        "{}\n\nBlocked by {}\n",
+        description.trim_end(),
+        refs.join(", ")
+    )
+}
+
+/// Created issues for one parent's drafts, or how the batch failed
+pub type BatchResult = std::result::Result<Vec<CreatedSubtask>, BatchFailure>;
+
+/// A batch of drafts for one parent issue, with the clients to create them
+pub struct SubtaskBatch {
+    parent: String,
+    creator: Creator,
+}
+
+impl SubtaskBatch {
+    pub async fn new(backend: Backend, config: &LoopConfig, parent: &str) -> Result<Self> {
+        Ok(Self {
+            parent: parent.to_string(),
+            creator: Creator::new(backend, config, parent).await?,
+        })
+    }
+
+    /// Create `drafts`, which must already be in [`order_drafts`] order.
+    ///
+    /// Stops at the first failure, reporting every issue created so far.
+    pub async fn create(&self, drafts: &[DraftSubtask]) -> BatchResult {
+        let mut created: Vec<CreatedSubtask> = Vec::new();
+        for draft in drafts {
+            let blockers: Vec<String> = draft
+                .blocked_by
+                .iter()
+                .map(|blocker| {
+                    created
+                        .iter()
+                        .find(|c| &c.title == blocker)
+                        .map(|c| c.id.clone())
+                        .unwrap_or_else(|| blocker.clone())
+                })
+                .collect();
+            let (issue, result) = self.creator.create(&self.parent, draft, &blockers).await;
+            created.extend(issue);
+            if let Err(error) = result {
+                return Err(BatchFailure {
+                    created,
+                    title: draft.title.clone(),
+                    error,
+                });
+            }
+        }
+        Ok(created)
+    }
+
+    /// Undo `created`, newest first, pairing each identifier with how it
+    /// was undone.
+    pub async fn rollback(
+        &self,
+        created: &[CreatedSubtask],
+    ) -> Vec<(String, Result<&'static str>)> {
+        let mut outcomes = Vec::new();
+        for issue in created.iter().rev() {
+            outcomes.push((issue.identifier.clone(), self.creator.undo(issue).await));
+        }
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draft(title: &str, blocked_by: &[&str]) -> DraftSubtask {
+        DraftSubtask {
+            update_id: format!("u-{}", title),
+            parent_id: "MOB-1".to_string(),
+            title: title.to_string(),
+            description: String::new(),
+            blocked_by: blocked_by.iter().map(|b| b.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_order_drafts_puts_batch_blockers_first() {
+        let ordered = order_drafts(vec![
+            draft("api", &["schema"]),
+            draft("docs", &[]),
+            draft("schema", &["MOB-9"]),
+            draft("ui", &["api", "schema"]),
+        ])
+        .unwrap();
+        let titles: Vec<&str> = ordered.iter().map(|d| d.title.as_str()).collect();
+        assert_eq!(titles, vec!["docs", "schema", "api", "ui"]);
+
+        let err = order_drafts(vec![draft("a", &["b"]), draft("b", &["a"])]).unwrap_err();
+        assert!(err.to_string().contains("cycle"));
+    }
+
+    #[test]
+    fn test_with_blocked_by_lines() {
+        assert_eq!(with_blocked_by_lines("Body", &[]), "Body");
+        assert_eq!(
+            with_blocked_by_lines("Body\n", &["12".to_string(), "#3".to_string()]),
+            "Body\n\nBlocked by #12, #3\n"
+        );
+    }
+}
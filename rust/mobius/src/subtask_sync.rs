@@ -0,0 +1,239 @@
+//! Sub-task refresh for `mobius pull`.
+//!
+//! Copies the tracker-owned fields of Linear and Jira sub-tasks (title,
+//! status, priority, labels and branch) into the local sub-task specs. Once a
+//! pull has completed, later pulls ask the tracker only for sub-tasks updated
+//! since then, so refreshing a parent with dozens of sub-tasks is a single
+//! small query. Descriptions, scoring and dependencies written by `refine`
+//! stay as they are in the specs.
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+
+use crate::context::read_pending_updates;
+use crate::jira::JiraClient;
+use crate::linear::LinearClient;
+use crate::local_state::{read_subtasks, write_subtask_spec};
+use crate::types::config::StatusMapping;
+use crate::types::context::{PendingUpdateData, SubTaskContext};
+use crate::types::enums::Backend;
+use crate::types::task_graph::{map_backend_status, LinearIssue};
+
+/// Outcome of refreshing local sub-task specs from the tracker
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SubtaskRefresh {
+    /// Sub-tasks the tracker returned
+    pub fetched: usize,
+    /// Identifiers of the sub-tasks the tracker returned
+    pub identifiers: HashSet<String>,
+    /// Local specs rewritten with tracker changes
+    pub updated: usize,
+    /// Tracker sub-tasks with no local spec, left alone
+    pub untracked: usize,
+}
+
+/// Whether `pull` can refresh sub-tasks for `backend`.
+pub fn supports_subtask_refresh(backend: Backend) -> bool {
+    matches!(backend, Backend::Linear | Backend::Jira)
+}
+
+/// Sub-tasks of `parent_id`, or with `since` only those updated after it.
+pub async fn fetch_subtasks_since(
+    backend: Backend,
+    parent_id: &str,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<LinearIssue>> {
+    let sub_tasks = match backend {
+        Backend::Jira => {
+            let minutes = since.map(|since| (Utc::now() - since).num_minutes());
+            JiraClient::new()?
+                .fetch_jira_sub_tasks_since(parent_id, minutes)
+                .await?
+        }
+        Backend::Linear => {
+            let since = since.map(|since| since.to_rfc3339());
+            LinearClient::new()?
+                .fetch_linear_sub_tasks_since(parent_id, since.as_deref())
+                .await?
+        }
+        Backend::Github | Backend::Gitlab | Backend::Local => {
+            anyhow::bail!(
+                "Sub-task refresh is not supported for the {} backend",
+                backend
+            )
+        }
+    };
+    Ok(sub_tasks)
+}
+
+/// Refresh the local sub-task specs of `parent_id` from the tracker,
+/// downloading only sub-tasks changed after `since` when it is given.
+pub fn refresh_subtasks_from_backend(
+    parent_id: &str,
+    backend: Backend,
+    since: Option<DateTime<Utc>>,
+    status_mapping: Option<&StatusMapping>,
+) -> Result<SubtaskRefresh> {
+    let rt = tokio::runtime::Runtime::new().context("failed to start async runtime")?;
+    rt.block_on(refresh_subtasks(parent_id, backend, since, status_mapping))
+}
+
+/// [`refresh_subtasks_from_backend`] for callers that already run a runtime.
+pub async fn refresh_subtasks(
+    parent_id: &str,
+    backend: Backend,
+    since: Option<DateTime<Utc>>,
+    status_mapping: Option<&StatusMapping>,
+) -> Result<SubtaskRefresh> {
+    let remote = fetch_subtasks_since(backend, parent_id, since).await?;
+
+    let mut local: HashMap<String, SubTaskContext> = read_subtasks(parent_id)
+        .into_iter()
+        .map(|task| (task.identifier.clone(), task))
+        .collect();
+    let queued = queued_status_changes(parent_id);
+    let mut refresh = SubtaskRefresh {
+        fetched: remote.len(),
+        identifiers: remote.iter().map(|i| i.identifier.clone()).collect(),
+        ..SubtaskRefresh::default()
+    };
+
+    for issue in &remote {
+        let Some(task) = local.get_mut(&issue.identifier) else {
+            refresh.untracked += 1;
+            continue;
+        };
+        let keep_status = queued.contains(&issue.identifier);
+        if apply_remote_subtask(task, issue, keep_status, status_mapping) {
+            write_subtask_spec(parent_id, task)?;
+            refresh.updated += 1;
+        }
+    }
+
+    Ok(refresh)
+}
+
+/// Copy the tracker-owned fields of `remote` onto `task`, returning whether
+/// anything changed.
+///
+/// With `keep_status` the local status wins, because a status change for the
+/// task is still waiting in the push queue. A status that maps to the same
+/// task state is not rewritten.
+pub fn apply_remote_subtask(
+    task: &mut SubTaskContext,
+    remote: &LinearIssue,
+    keep_status: bool,
+    status_mapping: Option<&StatusMapping>,
+) -> bool {
+    let mut changed = false;
+    let mut set = |field: &mut String, value: &str| {
+        if !value.is_empty() && field != value {
+            *field = value.to_string();
+            changed = true;
+        }
+    };
+    set(&mut task.title, &remote.title);
+    set(&mut task.git_branch_name, &remote.git_branch_name);
+    if !keep_status
+        && map_backend_status(&task.status, status_mapping)
+            != map_backend_status(&remote.status, status_mapping)
+    {
+        set(&mut task.status, &remote.status);
+    }
+
+    if remote.priority.is_some() && task.priority != remote.priority {
+        task.priority = remote.priority;
+        changed = true;
+    }
+    if task.labels != remote.labels {
+        task.labels = remote.labels.clone();
+        changed = true;
+    }
+    changed
+}
+
+/// Identifiers with a status change queued but not yet pushed.
+fn queued_status_changes(parent_id: &str) -> HashSet<String> {
+    read_pending_updates(parent_id)
+        .updates
+        .into_iter()
+        .filter(|update| update.synced_at.is_none() && update.error.is_none())
+        .filter_map(|update| match update.data {
+            PendingUpdateData::StatusChange { identifier, .. } => Some(identifier),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::enums::TaskType;
+
+    fn spec(status: &str) -> SubTaskContext {
+        serde_json::from_value(serde_json::json!({
+            "id": "uuid-1",
+            "identifier": "MOB-11",
+            "title": "Old title",
+            "description": "Written by refine",
+            "status": status,
+            "gitBranchName": "feat/mob-11",
+            "blockedBy": ["task-001"],
+            "labels": ["backend"]
+        }))
+        .unwrap()
+    }
+
+    fn remote(status: &str) -> LinearIssue {
+        LinearIssue {
+            id: "uuid-1".to_string(),
+            identifier: "MOB-11".to_string(),
+            title: "New title".to_string(),
+            status: status.to_string(),
+            git_branch_name: "feat/mob-11".to_string(),
+            relations: None,
+            scoring: None,
+            priority: Some(2),
+            labels: vec!["backend".to_string(), "human".to_string()],
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
+        }
+    }
+
+    #[test]
+    fn test_apply_remote_subtask() {
+        let mut task = spec("ready");
+        assert!(apply_remote_subtask(
+            &mut task,
+            &remote("In Progress"),
+            false,
+            None
+        ));
+        assert_eq!(task.title, "New title");
+        assert_eq!(task.status, "In Progress");
+        assert_eq!(task.priority, Some(2));
+        assert_eq!(task.labels, vec!["backend", "human"]);
+        // Local-only fields survive
+        assert_eq!(task.description, "Written by refine");
+        assert_eq!(task.blocked_by[0].identifier, "task-001");
+        // Nothing left to change
+        assert!(!apply_remote_subtask(
+            &mut task,
+            &remote("In Progress"),
+            false,
+            None
+        ));
+
+        // A queued status change keeps the local status
+        let mut task = spec("done");
+        apply_remote_subtask(&mut task, &remote("Todo"), true, None);
+        assert_eq!(task.status, "done");
+
+        // Same state under another name is not rewritten
+        let mut task = spec("done");
+        apply_remote_subtask(&mut task, &remote("Done"), false, None);
+        assert_eq!(task.status, "done");
+    }
+}
@@ -0,0 +1,171 @@
+//! Tracing spans for long loop runs, exported over OTLP when configured.
+//!
+//! The loop, its iterations, each agent and each backend API call run inside
+//! `tracing` spans (`loop` > `iteration` > `agent`, plus `http.request`).
+//! They go nowhere unless `observability.otlp_endpoint` is set and mobius was
+//! built with the `otlp` feature; then they are batched to the collector
+//! (Jaeger, Grafana Tempo, an OpenTelemetry Collector) over OTLP/HTTP.
+
+use colored::Colorize;
+use tracing::{field, info_span, Span};
+
+use crate::executor::ExecutionResult;
+use crate::types::{ObservabilityConfig, SubTask};
+
+/// Flushes pending spans when dropped; keep it alive for the whole run.
+#[derive(Default)]
+pub struct Telemetry {
+    #[cfg(feature = "otlp")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        #[cfg(feature = "otlp")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("{}", format!("⚠ Failed to flush traces: {}", e).yellow());
+            }
+        }
+    }
+}
+
+/// Install the OTLP exporter if `config` asks for one.
+///
+/// Must run outside a tokio runtime: the exporter uses a blocking client on
+/// its own thread.
+pub fn init(config: &ObservabilityConfig) -> Telemetry {
+    let Some(endpoint) = config.otlp_endpoint.as_deref() else {
+        return Telemetry::default();
+    };
+
+    #[cfg(feature = "otlp")]
+    match otlp::install(&traces_url(endpoint), &config.service_name) {
+        Ok(provider) => Telemetry {
+            provider: Some(provider),
+        },
+        Err(e) => {
+            eprintln!("{}", format!("⚠ Trace export disabled: {:#}", e).yellow());
+            Telemetry::default()
+        }
+    }
+
+    #[cfg(not(feature = "otlp"))]
+    {
+        eprintln!(
+            "{}",
+            format!(
+                "⚠ observability.otlp_endpoint is set to {}, but this build lacks the `otlp` \
+                 feature; reinstall with `cargo install --path rust/mobius --features otlp`",
+                endpoint
+            )
+            .yellow()
+        );
+        Telemetry::default()
+    }
+}
+
+/// OTLP/HTTP traces URL for a collector base URL.
+#[cfg_attr(not(feature = "otlp"), allow(dead_code))]
+fn traces_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    }
+}
+
+/// Span covering one agent run; close it with [`record_agent_result`].
+pub fn agent_span(task: &SubTask, driver: &'static str) -> Span {
+    info_span!(
+        "agent",
+        task = %task.identifier,
+        title = %task.title,
+        driver,
+        status = field::Empty,
+        success = field::Empty,
+        duration_ms = field::Empty,
+        input_tokens = field::Empty,
+        output_tokens = field::Empty,
+        error = field::Empty,
+    )
+}
+
+/// Attach an agent's outcome to its span.
+pub fn record_agent_result(span: &Span, result: &ExecutionResult) {
+    span.record("status", field::debug(&result.status));
+    span.record("success", result.success);
+    span.record("duration_ms", result.duration_ms);
+    if let Some(tokens) = result.input_tokens {
+        span.record("input_tokens", tokens);
+    }
+    if let Some(tokens) = result.output_tokens {
+        span.record("output_tokens", tokens);
+    }
+    if let Some(error) = &result.error {
+        span.record("error", error.as_str());
+    }
+}
+
+#[cfg(feature = "otlp")]
+mod otlp {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry::KeyValue;
+    use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use opentelemetry_sdk::Resource;
+    use tracing::Level;
+    use tracing_subscriber::filter::Targets;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    use tracing_subscriber::Layer;
+
+    pub fn install(traces_url: &str, service_name: &str) -> anyhow::Result<SdkTracerProvider> {
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(traces_url)
+            .build()?;
+        let resource = Resource::builder()
+            .with_service_name(service_name.to_string())
+            .with_attribute(KeyValue::new("service.version", env!("CARGO_PKG_VERSION")))
+            .build();
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(resource)
+            .build();
+
+        // Only mobius's own spans; dependencies' internals would drown them out
+        let layer = tracing_opentelemetry::layer()
+            .with_tracer(provider.tracer("mobius"))
+            .with_filter(Targets::new().with_target("mobius", Level::INFO));
+        tracing_subscriber::registry().with(layer).try_init()?;
+        Ok(provider)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traces_url_appends_signal_path_once() {
+        assert_eq!(
+            traces_url("http://localhost:4318"),
+            "http://localhost:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("http://collector:4318/"),
+            "http://collector:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("https://otlp.example.com/v1/traces"),
+            "https://otlp.example.com/v1/traces"
+        );
+    }
+
+    #[test]
+    fn test_init_without_endpoint_is_a_no_op() {
+        let _telemetry = init(&ObservabilityConfig::default());
+    }
+}
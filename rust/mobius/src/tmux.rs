@@ -3,6 +3,8 @@ use std::env;
 use tokio::fs;
 use tokio::process::Command;
 
+use crate::error::MobiusError;
+
 /// Represents a tmux session handle
 #[derive(Debug, Clone)]
 pub struct TmuxSession {
@@ -86,7 +88,9 @@ pub async fn create_session(session_name: &str) -> Result<TmuxSession> {
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("tmux new-session failed: {}", stderr.trim());
+        return Err(
+            MobiusError::Tmux(format!("tmux new-session failed: {}", stderr.trim())).into(),
+        );
     }
 
     // Get the session ID and initial pane ID
@@ -141,7 +145,9 @@ pub async fn attach_to_session(session_name: &str) -> Result<()> {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("tmux switch-client failed: {}", stderr.trim());
+            return Err(
+                MobiusError::Tmux(format!("tmux switch-client failed: {}", stderr.trim())).into(),
+            );
         }
     } else {
         // attach-session needs inherited stdio for interactive use
@@ -152,7 +158,7 @@ pub async fn attach_to_session(session_name: &str) -> Result<()> {
             .context("Failed to attach to tmux session")?;
 
         if !status.success() {
-            anyhow::bail!("tmux attach-session failed");
+            return Err(MobiusError::Tmux("tmux attach-session failed".to_string()).into());
         }
     }
 
@@ -177,7 +183,7 @@ pub async fn create_agent_pane(
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-            anyhow::bail!("tmux split-window failed: {}", stderr);
+            return Err(MobiusError::Tmux(format!("tmux split-window failed: {}", stderr)).into());
         }
 
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
@@ -248,7 +254,11 @@ pub async fn create_status_pane(session: &TmuxSession) -> Result<TmuxPane> {
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("tmux split-window for status failed: {}", stderr.trim());
+        return Err(MobiusError::Tmux(format!(
+            "tmux split-window for status failed: {}",
+            stderr.trim()
+        ))
+        .into());
     }
 
     let pane_id = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -366,6 +376,153 @@ pub async fn is_pane_still_running(pane_id: &str) -> bool {
     }
 }
 
+/// List the IDs of every pane in a session (empty if the session is gone)
+pub async fn list_session_panes(session_name: &str) -> Vec<String> {
+    match Command::new("tmux")
+        .args(["list-panes", "-s", "-t", session_name, "-F", "#{pane_id}"])
+        .output()
+        .await
+    {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|id| !id.is_empty())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Find the pane running `identifier`'s agent by the title
+/// `create_agent_pane` gives it.
+///
+/// Blocking, for the dashboard's synchronous event loop.
+pub fn find_agent_pane(session_name: &str, identifier: &str) -> Option<String> {
+    let output = std::process::Command::new("tmux")
+        .args([
+            "list-panes",
+            "-s",
+            "-t",
+            session_name,
+            "-F",
+            "#{pane_id} #{pane_title}",
+        ])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    pane_for_identifier(&String::from_utf8_lossy(&output.stdout), identifier)
+}
+
+/// Pick the pane titled `{identifier}: ...` from `list-panes` output.
+fn pane_for_identifier(listing: &str, identifier: &str) -> Option<String> {
+    let prefix = format!("{}: ", identifier);
+    listing.lines().find_map(|line| {
+        let (pane_id, title) = line.trim().split_once(' ')?;
+        title.starts_with(&prefix).then(|| pane_id.to_string())
+    })
+}
+
+/// How `focus_pane` brought the user to a pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneFocus {
+    /// Switched this tmux client to another session; the caller keeps running
+    Switched,
+    /// Selected another window of the caller's own session
+    OtherWindow,
+    /// Selected another pane of the caller's own window
+    OtherPane,
+    /// Attached from outside tmux and returned when the user detached
+    Detached,
+}
+
+impl PaneFocus {
+    /// The tmux keys that take the user back to where they were, when the
+    /// caller kept running.
+    pub fn return_keys(self) -> Option<&'static str> {
+        match self {
+            PaneFocus::Switched => Some("prefix+L"),
+            PaneFocus::OtherWindow => Some("prefix+l"),
+            PaneFocus::OtherPane => Some("prefix+;"),
+            PaneFocus::Detached => None,
+        }
+    }
+}
+
+/// `#{session_id} #{window_id}` of `target`, or of the caller's own pane.
+fn pane_location(target: Option<&str>) -> Option<String> {
+    let mut args = vec!["display-message", "-p"];
+    if let Some(target) = target {
+        args.extend(["-t", target]);
+    }
+    args.push("#{session_id} #{window_id}");
+    let output = std::process::Command::new("tmux")
+        .args(&args)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Select `pane_id` and bring the user to it.
+///
+/// Inside tmux the current client switches to the pane's session, or just
+/// selects the pane when it is in the caller's own session. Outside,
+/// `attach-session` takes over the terminal until the user detaches, so the
+/// caller must release it first. Blocking.
+pub fn focus_pane(pane_id: &str) -> Result<PaneFocus> {
+    // Where the caller is, read before selecting anything moves it
+    let own = is_inside_tmux()
+        .then(|| pane_location(None).zip(pane_location(Some(pane_id))))
+        .flatten();
+
+    for args in [
+        ["select-window", "-t", pane_id],
+        ["select-pane", "-t", pane_id],
+    ] {
+        let output = std::process::Command::new("tmux")
+            .args(args)
+            .output()
+            .context("Failed to run tmux")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(
+                MobiusError::Tmux(format!("tmux {} failed: {}", args[0], stderr.trim())).into(),
+            );
+        }
+    }
+
+    if let Some((own, target)) = &own {
+        let same_session = own.split(' ').next() == target.split(' ').next();
+        if own == target {
+            return Ok(PaneFocus::OtherPane);
+        }
+        if same_session {
+            return Ok(PaneFocus::OtherWindow);
+        }
+    }
+    if is_inside_tmux() {
+        let output = std::process::Command::new("tmux")
+            .args(["switch-client", "-t", pane_id])
+            .output()
+            .context("Failed to switch tmux client")?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(
+                MobiusError::Tmux(format!("tmux switch-client failed: {}", stderr.trim())).into(),
+            );
+        }
+        return Ok(PaneFocus::Switched);
+    }
+
+    let status = std::process::Command::new("tmux")
+        .args(["attach-session", "-t", pane_id])
+        .status()
+        .context("Failed to attach to tmux session")?;
+    if !status.success() {
+        return Err(MobiusError::Tmux("tmux attach-session failed".to_string()).into());
+    }
+    Ok(PaneFocus::Detached)
+}
+
 /// Update the status pane with current loop status
 pub async fn update_status_pane(status: &LoopStatus, session_name: &str) -> Result<()> {
     let elapsed = format_elapsed(status.elapsed_ms);
@@ -448,6 +605,17 @@ pub mod tests {
         assert_eq!(get_session_name("PROJ-1"), "mobius-PROJ-1");
     }
 
+    #[test]
+    fn test_pane_for_identifier_matches_title_prefix() {
+        let listing = "%0 status\n%3 MOB-12: Add schema\n%4 MOB-1: Parent work\n";
+        assert_eq!(pane_for_identifier(listing, "MOB-1").as_deref(), Some("%4"));
+        assert_eq!(
+            pane_for_identifier(listing, "MOB-12").as_deref(),
+            Some("%3")
+        );
+        assert_eq!(pane_for_identifier(listing, "MOB-2"), None);
+    }
+
     #[test]
     fn test_get_status_file_path() {
         assert_eq!(
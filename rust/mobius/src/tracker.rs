@@ -1,13 +1,18 @@
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use regex::Regex;
 
 use crate::executor::{ExecutionResult, ExecutionStatus};
-use crate::types::{Backend, SubTask};
+use crate::types::config::ExecutionConfig;
+use crate::types::{Backend, RetryCondition, SubTask};
 
 /// Pattern matching local-only task identifiers (`LOC-001`, `task-001`).
 const LOCAL_ID_PATTERN: &str = r"^(LOC|task)-\d+$";
 
+/// Upper bound on a single retry backoff delay.
+const MAX_BACKOFF_SECONDS: u64 = 600;
+
 /// Check if an identifier looks like a local-only task (not from a backend).
 ///
 /// Local identifiers match `LOC-NNN` or `task-NNN` patterns.
@@ -24,6 +29,8 @@ pub struct TaskAssignment {
     pub identifier: String,
     pub attempts: u32,
     pub last_result: Option<ExecutionResult>,
+    /// Earliest time the task may be dispatched again after a failure
+    pub retry_after: Option<Instant>,
 }
 
 /// Tracks task assignments, attempts, and retry decisions.
@@ -32,6 +39,10 @@ pub struct ExecutionTracker {
     pub assignments: HashMap<String, TaskAssignment>,
     pub max_retries: u32,
     pub verification_timeout_ms: u64,
+    /// Base retry delay in seconds, doubled for each further attempt
+    pub backoff_seconds: u32,
+    /// Failure kinds eligible for retry
+    pub retry_on: Vec<RetryCondition>,
 }
 
 /// Execution result enriched with backend verification status.
@@ -48,6 +59,8 @@ pub struct VerifiedResult {
     pub backend_verified: bool,
     pub backend_status: Option<String>,
     pub should_retry: bool,
+    /// Number of times the task has been dispatched, including this one
+    pub attempts: u32,
 }
 
 impl From<&ExecutionResult> for VerifiedResult {
@@ -64,6 +77,7 @@ impl From<&ExecutionResult> for VerifiedResult {
             backend_verified: false,
             backend_status: None,
             should_retry: false,
+            attempts: 1,
         }
     }
 }
@@ -85,9 +99,26 @@ pub fn create_tracker(
         assignments: HashMap::new(),
         max_retries: max_retries.unwrap_or(2),
         verification_timeout_ms: verification_timeout_ms.unwrap_or(5000),
+        backoff_seconds: 0,
+        retry_on: vec![
+            RetryCondition::Timeout,
+            RetryCondition::VerificationFailed,
+            RetryCondition::Error,
+        ],
     }
 }
 
+/// Create an execution tracker using the retry policy from `execution` config.
+pub fn create_tracker_from_config(config: &ExecutionConfig) -> ExecutionTracker {
+    let mut tracker = create_tracker(
+        config.max_retries,
+        config.verification_timeout.map(|v| v as u64),
+    );
+    tracker.backoff_seconds = config.backoff_seconds.unwrap_or(0);
+    tracker.retry_on = config.retry_on.clone();
+    tracker
+}
+
 /// Record a task assignment (increments attempts if already assigned).
 pub fn assign_task(tracker: &mut ExecutionTracker, task: &SubTask) {
     if let Some(existing) = tracker.assignments.get_mut(&task.id) {
@@ -100,6 +131,7 @@ pub fn assign_task(tracker: &mut ExecutionTracker, task: &SubTask) {
                 identifier: task.identifier.clone(),
                 attempts: 1,
                 last_result: None,
+                retry_after: None,
             },
         );
     }
@@ -153,7 +185,14 @@ pub fn process_results(
                 verified_results.push(vr);
             }
         } else {
-            let can_retry = attempts <= tracker.max_retries;
+            let can_retry = attempts <= tracker.max_retries
+                && tracker.retry_on.contains(&classify_failure(result));
+            if can_retry {
+                let delay = backoff_delay(tracker.backoff_seconds, attempts);
+                if let Some(assign) = tracker.assignments.get_mut(&result.task_id) {
+                    assign.retry_after = Some(Instant::now() + delay);
+                }
+            }
             let mut vr = VerifiedResult::from(result);
             vr.backend_verified = false;
             vr.should_retry = can_retry;
@@ -161,9 +200,49 @@ pub fn process_results(
         }
     }
 
+    for vr in &mut verified_results {
+        vr.attempts = tracker
+            .assignments
+            .get(&vr.task_id)
+            .map(|a| a.attempts)
+            .unwrap_or(1);
+    }
+
     verified_results
 }
 
+/// Map a failed execution result to the retry condition it falls under.
+pub fn classify_failure(result: &ExecutionResult) -> RetryCondition {
+    match result.status {
+        ExecutionStatus::Timeout => RetryCondition::Timeout,
+        ExecutionStatus::VerificationFailed => RetryCondition::VerificationFailed,
+        ExecutionStatus::SubtaskComplete | ExecutionStatus::Error => RetryCondition::Error,
+    }
+}
+
+/// Backoff before retry number `attempts`: `base * 2^(attempts - 1)`, capped.
+pub fn backoff_delay(backoff_seconds: u32, attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    let seconds = (backoff_seconds as u64).saturating_mul(1 << exponent);
+    Duration::from_secs(seconds.min(MAX_BACKOFF_SECONDS))
+}
+
+/// Remaining backoff before `task_id` may be retried, if any.
+pub fn retry_wait(tracker: &ExecutionTracker, task_id: &str) -> Option<Duration> {
+    let retry_after = tracker.assignments.get(task_id)?.retry_after?;
+    let remaining = retry_after.saturating_duration_since(Instant::now());
+    (!remaining.is_zero()).then_some(remaining)
+}
+
+/// Number of previous attempts for `task_id` (0 if never dispatched).
+pub fn retry_count(tracker: &ExecutionTracker, task_id: &str) -> u32 {
+    tracker
+        .assignments
+        .get(task_id)
+        .map(|a| a.attempts.saturating_sub(1))
+        .unwrap_or(0)
+}
+
 /// Apply backend verification result to a verified result.
 ///
 /// Call this after checking the backend API status. If the backend says the task
@@ -222,6 +301,11 @@ pub fn has_permanent_failures(results: &[VerifiedResult]) -> bool {
     results.iter().any(|r| !r.success && !r.should_retry)
 }
 
+/// Forget `task_id`'s attempts so a manual retry starts with a full budget.
+pub fn forget_task(tracker: &mut ExecutionTracker, task_id: &str) {
+    tracker.assignments.remove(task_id);
+}
+
 /// Reset the tracker, clearing all assignments.
 pub fn reset_tracker(tracker: &mut ExecutionTracker) {
     tracker.assignments.clear();
@@ -251,7 +335,7 @@ pub fn get_tracker_stats(tracker: &ExecutionTracker) -> TrackerStats {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::TaskStatus;
+    use crate::types::{TaskStatus, TaskType};
 
     fn make_task(id: &str, identifier: &str) -> SubTask {
         SubTask {
@@ -263,6 +347,10 @@ mod tests {
             blocks: vec![],
             git_branch_name: String::new(),
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         }
     }
 
@@ -391,6 +479,75 @@ mod tests {
         assert!(!verified[0].should_retry); // 3 > 2 (max_retries)
     }
 
+    #[test]
+    fn test_process_results_respects_retry_on() {
+        let config = ExecutionConfig {
+            retry_on: vec![RetryCondition::Timeout],
+            ..ExecutionConfig::default()
+        };
+        let mut tracker = create_tracker_from_config(&config);
+        assign_task(&mut tracker, &make_task("1", "MOB-101"));
+        assign_task(&mut tracker, &make_task("2", "MOB-102"));
+
+        let timed_out = ExecutionResult {
+            status: ExecutionStatus::Timeout,
+            ..make_result("2", "MOB-102", false)
+        };
+        let results = vec![make_result("1", "MOB-101", false), timed_out];
+        let verified = process_results(&mut tracker, &results, Some(&Backend::Linear));
+
+        assert!(!verified[0].should_retry); // verification_failed not in retry_on
+        assert!(verified[1].should_retry);
+    }
+
+    #[test]
+    fn test_process_results_schedules_backoff() {
+        let config = ExecutionConfig {
+            backoff_seconds: Some(30),
+            ..ExecutionConfig::default()
+        };
+        let mut tracker = create_tracker_from_config(&config);
+        let task = make_task("1", "MOB-101");
+        assign_task(&mut tracker, &task);
+        assert!(retry_wait(&tracker, "1").is_none());
+
+        let results = vec![make_result("1", "MOB-101", false)];
+        let verified = process_results(&mut tracker, &results, Some(&Backend::Linear));
+        assert!(verified[0].should_retry);
+        assert_eq!(verified[0].attempts, 1);
+
+        let wait = retry_wait(&tracker, "1").unwrap();
+        assert!(wait > Duration::from_secs(25) && wait <= Duration::from_secs(30));
+
+        assign_task(&mut tracker, &task);
+        assert_eq!(retry_count(&tracker, "1"), 1);
+    }
+
+    #[test]
+    fn test_backoff_delay_is_exponential_and_capped() {
+        assert_eq!(backoff_delay(0, 3), Duration::ZERO);
+        assert_eq!(backoff_delay(10, 1), Duration::from_secs(10));
+        assert_eq!(backoff_delay(10, 2), Duration::from_secs(20));
+        assert_eq!(backoff_delay(10, 3), Duration::from_secs(40));
+        assert_eq!(
+            backoff_delay(10, 30),
+            Duration::from_secs(MAX_BACKOFF_SECONDS)
+        );
+    }
+
+    #[test]
+    fn test_classify_failure() {
+        let mut result = make_result("1", "MOB-101", false);
+        assert_eq!(
+            classify_failure(&result),
+            RetryCondition::VerificationFailed
+        );
+        result.status = ExecutionStatus::Timeout;
+        assert_eq!(classify_failure(&result), RetryCondition::Timeout);
+        result.status = ExecutionStatus::Error;
+        assert_eq!(classify_failure(&result), RetryCondition::Error);
+    }
+
     #[test]
     fn test_apply_backend_verification_success() {
         let result = make_result("1", "MOB-101", true);
@@ -2,8 +2,10 @@ use std::collections::HashMap;
 
 use colored::{Colorize, CustomColor};
 
-use crate::types::enums::TaskStatus;
-use crate::types::task_graph::{get_blockers, get_ready_tasks, SubTask, TaskGraph};
+use crate::types::enums::{TaskStatus, TaskType};
+use crate::types::task_graph::{
+    get_blockers, get_ready_tasks, is_external_blocker, CriticalPath, SubTask, TaskGraph,
+};
 
 // Nord color palette
 // https://www.nordtheme.com/docs/colors-and-palettes
@@ -119,13 +121,12 @@ fn color_identifier(identifier: &str, depth: usize) -> String {
 }
 
 /// Build a map of parent -> children relationships.
-/// A task's "parent" in the tree is its first blocker.
+/// A task's "parent" in the tree is its first blocker within the graph.
 fn build_children_map(graph: &TaskGraph) -> HashMap<String, Vec<SubTask>> {
     let mut children_map: HashMap<String, Vec<SubTask>> = HashMap::new();
 
     for task in graph.tasks.values() {
-        if !task.blocked_by.is_empty() {
-            let parent_id = &task.blocked_by[0];
+        if let Some(parent_id) = first_graph_blocker(task, graph) {
             children_map
                 .entry(parent_id.clone())
                 .or_default()
@@ -141,12 +142,19 @@ fn build_children_map(graph: &TaskGraph) -> HashMap<String, Vec<SubTask>> {
     children_map
 }
 
-/// Get root tasks (tasks with no blockers). If none, use all tasks.
+/// First blocker that is a task in the graph (external blockers are skipped)
+fn first_graph_blocker<'a>(task: &'a SubTask, graph: &TaskGraph) -> Option<&'a String> {
+    task.blocked_by
+        .iter()
+        .find(|b| graph.tasks.contains_key(*b))
+}
+
+/// Get root tasks (tasks with no blockers in the graph). If none, use all tasks.
 fn get_root_tasks(graph: &TaskGraph) -> Vec<SubTask> {
     let mut roots: Vec<SubTask> = graph
         .tasks
         .values()
-        .filter(|t| t.blocked_by.is_empty())
+        .filter(|t| first_graph_blocker(t, graph).is_none())
         .cloned()
         .collect();
 
@@ -193,9 +201,14 @@ fn render_task_node(
     let identifier = color_identifier(&task.identifier, depth);
     let title = task.title.custom_color(NORD4).to_string();
     let blocker_suffix = format_blocker_suffix(task, graph);
+    let human_suffix = if task.task_type == TaskType::Human && task.status != TaskStatus::Done {
+        " (human)".custom_color(NORD15).to_string()
+    } else {
+        String::new()
+    };
 
     lines.push(format!(
-        "{prefix}{colored_connector}{icon} {identifier}: {title}{blocker_suffix}"
+        "{prefix}{colored_connector}{icon} {identifier}: {title}{blocker_suffix}{human_suffix}"
     ));
 
     // Get children for this task
@@ -228,20 +241,23 @@ fn format_blocker_suffix(task: &SubTask, graph: &TaskGraph) -> String {
     }
 
     let blockers = get_blockers(graph, &task.id);
-    let unresolved: Vec<&SubTask> = blockers
+    let blocker_ids: Vec<String> = blockers
         .into_iter()
         .filter(|b| b.status != TaskStatus::Done)
+        .map(|b| b.identifier.as_str())
+        .chain(
+            task.blocked_by
+                .iter()
+                .filter(|b| is_external_blocker(b) && !graph.tasks.contains_key(*b))
+                .map(String::as_str),
+        )
+        .map(|id| id.custom_color(NORD11).to_string())
         .collect();
 
-    if unresolved.is_empty() {
+    if blocker_ids.is_empty() {
         return String::new();
     }
 
-    let blocker_ids: Vec<String> = unresolved
-        .iter()
-        .map(|b| b.identifier.custom_color(NORD11).to_string())
-        .collect();
-
     let separator = ", ".custom_color(NORD3).to_string();
     let joined = blocker_ids.join(&separator);
 
@@ -302,6 +318,35 @@ pub fn render_ready_summary(graph: &TaskGraph) -> String {
     )
 }
 
+/// Render the critical path as `MOB-1 → MOB-2 → MOB-3`
+pub fn render_critical_path(graph: &TaskGraph, path: &CriticalPath) -> String {
+    if path.task_ids.is_empty() {
+        return "Critical path: all tasks done"
+            .custom_color(NORD14)
+            .to_string();
+    }
+
+    let steps: Vec<String> = path
+        .task_ids
+        .iter()
+        .filter_map(|id| graph.tasks.get(id))
+        .map(|t| t.identifier.custom_color(NORD11).bold().to_string())
+        .collect();
+    let separator = " → ".custom_color(NORD3).to_string();
+    let detail = if path.length as usize == steps.len() {
+        format!(" ({} tasks)", steps.len())
+    } else {
+        format!(" ({} tasks, complexity {})", steps.len(), path.length)
+    };
+
+    format!(
+        "{}{}{}",
+        "Critical path: ".custom_color(NORD4),
+        steps.join(&separator),
+        detail.custom_color(NORD3)
+    )
+}
+
 /// Render the complete tree output including legend and summary
 pub fn render_full_tree_output(graph: &TaskGraph) -> String {
     let parts = [
@@ -317,7 +362,9 @@ pub fn render_full_tree_output(graph: &TaskGraph) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::task_graph::{build_task_graph, LinearIssue, Relation, Relations};
+    use crate::types::task_graph::{
+        build_task_graph, critical_path, LinearIssue, Relation, Relations,
+    };
 
     fn make_sample_issues() -> Vec<LinearIssue> {
         vec![
@@ -341,6 +388,10 @@ mod tests {
                     ],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "b".to_string(),
@@ -359,6 +410,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "c".to_string(),
@@ -374,6 +429,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "d".to_string(),
@@ -392,6 +451,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "e".to_string(),
@@ -408,6 +471,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ]
     }
@@ -490,6 +557,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "b".to_string(),
@@ -505,6 +576,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ];
         let graph = build_task_graph("parent-1", "MOB-200", &issues);
@@ -523,6 +598,36 @@ mod tests {
         assert!(legend.contains("In Progress"));
     }
 
+    #[test]
+    fn test_render_ascii_tree_external_blocker() {
+        let url = "https://github.com/acme/api/pull/42";
+        let mut issues = make_sample_issues();
+        issues[2].relations.as_mut().unwrap().blocked_by.insert(
+            0,
+            Relation {
+                id: url.to_string(),
+                identifier: url.to_string(),
+            },
+        );
+        let graph = build_task_graph("parent-1", "MOB-100", &issues);
+        let tree = render_ascii_tree(&graph);
+
+        // Still nested under its in-graph blocker, with the URL listed
+        assert!(tree.contains("MOB-103"));
+        assert!(tree.contains(url));
+    }
+
+    #[test]
+    fn test_render_critical_path() {
+        let issues = make_sample_issues();
+        let graph = build_task_graph("parent-1", "MOB-100", &issues);
+        let rendered = render_critical_path(&graph, &critical_path(&graph, false));
+        assert!(rendered.contains("MOB-104"));
+        assert!(rendered.contains("MOB-105"));
+        assert!(!rendered.contains("MOB-103"));
+        assert!(rendered.contains("(2 tasks)"));
+    }
+
     #[test]
     fn test_render_ready_summary_with_ready_tasks() {
         let issues = make_sample_issues();
@@ -549,6 +654,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "b".to_string(),
@@ -564,6 +673,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ];
         let graph = build_task_graph("parent-1", "MOB-300", &issues);
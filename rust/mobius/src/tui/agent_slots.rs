@@ -4,11 +4,15 @@ use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Widget;
 
-use super::theme::{model_color, MUTED_COLOR, NORD14, TEXT_COLOR};
+use super::theme::{format_tokens, model_color, MUTED_COLOR, NORD13, NORD14, TEXT_COLOR};
 
 pub struct ActiveTaskDisplay {
     pub id: String,
     pub model: Option<String>,
+    /// Retries preceding the current attempt
+    pub retry_count: u32,
+    /// Input plus output tokens of the current attempt, once reported
+    pub tokens: Option<u64>,
 }
 
 pub struct AgentSlots<'a> {
@@ -52,6 +56,18 @@ impl Widget for AgentSlots<'_> {
                         Style::default().fg(model_color(model)),
                     ));
                 }
+                if let Some(tokens) = task.tokens {
+                    spans.push(Span::styled(
+                        format!(" {}", format_tokens(tokens)),
+                        Style::default().fg(MUTED_COLOR),
+                    ));
+                }
+                if task.retry_count > 0 {
+                    spans.push(Span::styled(
+                        format!(" ↻{}", task.retry_count),
+                        Style::default().fg(NORD13),
+                    ));
+                }
             } else {
                 spans.push(Span::styled("○", Style::default().fg(MUTED_COLOR)));
             }
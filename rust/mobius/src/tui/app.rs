@@ -1,14 +1,24 @@
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
+use crate::context::{get_pending_updates_count, queue_task_action};
+use crate::local_state::{
+    read_iteration_log, update_subtask_priority, update_subtask_status, IterationLogEntry,
+};
 use crate::types::context::{
     AgentTodoFile, RuntimeActiveTask, RuntimeCompletedTask, RuntimeState, SessionInfo,
 };
 use crate::types::debug::DebugEvent;
-use crate::types::enums::{SessionStatus, TaskStatus};
+use crate::types::enums::{SessionStatus, TaskAction, TaskStatus};
 use crate::types::task_graph::{SubTask, TaskGraph};
 
+use super::log_pane::{agent_log_path, LogTail};
+use super::replay::Replay;
+use super::task_detail::TaskDetail;
+use super::task_tree::tree_order;
+use super::theme::Theme;
+
 /// Application state for the TUI dashboard.
 pub struct App {
     pub parent_id: String,
@@ -18,7 +28,14 @@ pub struct App {
     pub start_time: Instant,
     pub show_legend: bool,
     pub show_debug: bool,
+    pub show_log: bool,
     pub show_exit_modal: bool,
+    /// Read-only observer: never signals the loop process or writes state
+    pub observe: bool,
+    /// Loop is held by `mobius pause`
+    pub paused: bool,
+    /// Updates queued for push while the loop works offline
+    pub offline_queued: Option<usize>,
     pub is_complete: bool,
     pub debug_events: Vec<DebugEvent>,
     pub pending_count: usize,
@@ -28,6 +45,31 @@ pub struct App {
     pub agent_todos: HashMap<String, AgentTodoFile>,
     pub max_parallel_agents: usize,
     pub token_history: Vec<u64>,
+    /// Sub-task chosen for the log pane; falls back to the first active agent
+    pub selected_agent: Option<String>,
+    pub agent_log: LogTail,
+    /// Directory the loop tees agent stream-json output to
+    pub agent_output_dir: PathBuf,
+    /// Stepping through recorded revisions instead of following the live file
+    pub replay: Option<Replay>,
+    /// Task highlighted in the tree; `None` until the user moves the cursor
+    pub selected_task: Option<String>,
+    /// Open detail panel for `selected_task`
+    pub detail: Option<TaskDetail>,
+    /// Iteration log shown in the history view, when it is open
+    pub history: Option<Vec<IterationLogEntry>>,
+    /// Rows scrolled past from the newest attempt
+    pub history_scroll: usize,
+    /// Result of the last task action, shown under the task tree for a few ticks
+    pub notice: Option<String>,
+    notice_ticks: u8,
+    /// Agent pane the event loop should bring the user to
+    pub attach_request: Option<String>,
+    pub theme: Theme,
+    /// Opened from the multi-issue overview; quitting returns there
+    pub from_overview: bool,
+    /// `loop.max_cost_usd`, shown against the run's spend
+    pub max_cost_usd: Option<f64>,
     last_token_total: u64,
 }
 
@@ -39,6 +81,7 @@ impl App {
         runtime_state_path: PathBuf,
         max_parallel_agents: usize,
     ) -> Self {
+        let agent_output_dir = crate::stream_json::agent_output_dir(&parent_id);
         Self {
             parent_id,
             parent_title,
@@ -47,7 +90,11 @@ impl App {
             start_time: Instant::now(),
             show_legend: true,
             show_debug: false,
+            show_log: false,
             show_exit_modal: false,
+            observe: false,
+            paused: false,
+            offline_queued: None,
             is_complete: false,
             debug_events: Vec::new(),
             pending_count: 0,
@@ -57,20 +104,62 @@ impl App {
             agent_todos: HashMap::new(),
             max_parallel_agents,
             token_history: Vec::new(),
+            selected_agent: None,
+            agent_log: LogTail::default(),
+            agent_output_dir,
+            replay: None,
+            selected_task: None,
+            detail: None,
+            history: None,
+            history_scroll: 0,
+            notice: None,
+            notice_ticks: 0,
+            attach_request: None,
+            theme: Theme::default(),
+            from_overview: false,
+            max_cost_usd: None,
             last_token_total: 0,
         }
     }
 
+    /// Switch to replaying `replay`, showing its first revision.
+    pub fn start_replay(&mut self, replay: Replay) {
+        self.observe = true;
+        self.runtime_state = Some(replay.current().state.clone());
+        self.replay = Some(replay);
+    }
+
+    /// Move the replay by `delta` revisions.
+    pub fn replay_step(&mut self, delta: isize) {
+        if let Some(replay) = self.replay.as_mut() {
+            replay.step(delta);
+            self.runtime_state = Some(replay.current().state.clone());
+        }
+    }
+
+    /// Jump the replay to its first or last revision.
+    pub fn replay_jump(&mut self, to_end: bool) {
+        if let Some(replay) = self.replay.as_mut() {
+            replay.jump(to_end);
+            self.runtime_state = Some(replay.current().state.clone());
+        }
+    }
+
     /// Reload runtime state from the state file on disk.
     pub fn reload_runtime_state(&mut self) {
         if let Ok(content) = std::fs::read_to_string(&self.runtime_state_path) {
             if let Ok(state) = serde_json::from_str::<RuntimeState>(&content) {
-                self.runtime_state = Some(state);
-                self.check_completion();
+                self.set_runtime_state(state);
             }
         }
     }
 
+    /// Replace the runtime state and re-check completion.
+    pub fn set_runtime_state(&mut self, state: RuntimeState) {
+        self.runtime_state = Some(state);
+        self.check_completion();
+    }
+
     /// Get the path to the todos directory (sibling to runtime.json).
     pub fn todos_dir(&self) -> PathBuf {
         self.runtime_state_path.parent().unwrap().join("todos")
@@ -99,6 +188,10 @@ impl App {
 
     /// Check if execution is complete (all tasks in terminal state).
     fn check_completion(&mut self) {
+        // A replayed end state must not trigger the exit countdown
+        if self.replay.is_some() {
+            return;
+        }
         let total = self.graph.tasks.len();
         let (completed, failed) = self
             .runtime_state
@@ -118,6 +211,17 @@ impl App {
     pub fn on_tick(&mut self) {
         // Catch completion transitions even if file watchers miss an event.
         self.check_completion();
+        self.paused = self.replay.is_none() && self.pause_flag_path().exists();
+        self.offline_queued = (self.replay.is_none() && self.offline_flag_path().exists())
+            .then(|| get_pending_updates_count(&self.parent_id));
+
+        if self.notice.is_some() {
+            if self.notice_ticks == 0 {
+                self.notice = None;
+            } else {
+                self.notice_ticks -= 1;
+            }
+        }
 
         if let Some(ref mut ticks) = self.auto_exit_tick {
             if *ticks == 0 {
@@ -138,6 +242,22 @@ impl App {
         }
     }
 
+    /// The pause flag lives next to the runtime state file.
+    fn pause_flag_path(&self) -> PathBuf {
+        self.runtime_state_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(crate::context::PAUSE_FLAG_FILE)
+    }
+
+    /// The loop's offline flag sits beside the pause flag.
+    fn offline_flag_path(&self) -> PathBuf {
+        self.runtime_state_path
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(crate::context::OFFLINE_FLAG_FILE)
+    }
+
     /// Get combined total tokens (input + output) from runtime state.
     fn current_total_tokens(&self) -> u64 {
         self.runtime_state
@@ -153,7 +273,7 @@ impl App {
 
     /// Handle 'q' key press.
     pub fn on_quit_key(&mut self) {
-        if self.is_complete {
+        if self.is_complete || self.observe || self.from_overview {
             self.should_quit = true;
         } else if self.has_active_tasks() {
             self.show_exit_modal = true;
@@ -164,7 +284,9 @@ impl App {
 
     /// Handle exit confirmation from modal.
     pub fn confirm_exit(&mut self) {
-        self.kill_loop_process();
+        if !self.observe {
+            self.kill_loop_process();
+        }
         self.should_quit = true;
     }
 
@@ -178,6 +300,302 @@ impl App {
         self.show_debug = !self.show_debug;
     }
 
+    /// Toggle the agent log pane, loading the selected agent's output.
+    pub fn toggle_log(&mut self) {
+        self.show_log = !self.show_log;
+        self.refresh_agent_log();
+    }
+
+    /// Toggle the history view in place of the task tree.
+    pub fn toggle_history(&mut self) {
+        if self.history.take().is_none() {
+            self.history = Some(Vec::new());
+            self.history_scroll = 0;
+            self.refresh_history();
+        }
+    }
+
+    /// Scroll the history view by `delta` rows (positive goes back in time).
+    pub fn scroll_history(&mut self, delta: isize) {
+        let Some(entries) = &self.history else {
+            return;
+        };
+        let max = entries.len().saturating_sub(1);
+        self.history_scroll = self.history_scroll.saturating_add_signed(delta).min(max);
+    }
+
+    /// Re-read the iteration log while the history view is open.
+    pub fn refresh_history(&mut self) {
+        if self.history.is_some() {
+            self.history = Some(read_iteration_log(&self.parent_id));
+        }
+    }
+
+    /// Select the next active agent for the log pane.
+    pub fn select_next_agent(&mut self) {
+        let active: Vec<&str> = self
+            .runtime_state
+            .as_ref()
+            .map(|s| s.active_tasks.iter().map(|t| t.id.as_str()).collect())
+            .unwrap_or_default();
+        if active.is_empty() {
+            return;
+        }
+        let next = match self
+            .selected_agent_id()
+            .and_then(|id| active.iter().position(|a| *a == id))
+        {
+            Some(i) => active[(i + 1) % active.len()],
+            None => active[0],
+        };
+        self.selected_agent = Some(next.to_string());
+        self.refresh_agent_log();
+    }
+
+    /// Agent shown in the log pane.
+    ///
+    /// A finished selection stays put while no agent is active, so its final
+    /// output remains readable; otherwise the first active agent is shown.
+    pub fn selected_agent_id(&self) -> Option<&str> {
+        let active = self
+            .runtime_state
+            .as_ref()
+            .map(|s| s.active_tasks.as_slice())
+            .unwrap_or_default();
+        match self.selected_agent.as_deref() {
+            Some(id) if active.is_empty() || active.iter().any(|t| t.id == id) => Some(id),
+            _ => active.first().map(|t| t.id.as_str()),
+        }
+    }
+
+    /// Tail new output for the selected agent while the log pane is shown.
+    pub fn refresh_agent_log(&mut self) {
+        if !self.show_log {
+            return;
+        }
+        let Some(id) = self.selected_agent_id().map(str::to_string) else {
+            return;
+        };
+        self.agent_log
+            .follow(agent_log_path(&self.agent_output_dir, &id));
+        self.agent_log.poll();
+    }
+
+    /// Move the tree cursor by `delta` rows, following it in the detail panel.
+    pub fn select_task(&mut self, delta: isize) {
+        let order: Vec<&str> = tree_order(&self.graph)
+            .into_iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        if order.is_empty() {
+            return;
+        }
+        let current = self
+            .selected_task
+            .as_deref()
+            .and_then(|id| order.iter().position(|t| *t == id));
+        let next = match current {
+            Some(i) => (i as isize + delta).clamp(0, order.len() as isize - 1) as usize,
+            None if delta < 0 => order.len() - 1,
+            None => 0,
+        };
+        self.selected_task = Some(order[next].to_string());
+        self.refresh_detail();
+    }
+
+    /// Open the detail panel for the selected task (the first one if none is).
+    pub fn open_detail(&mut self) {
+        if self.selected_task.is_none() {
+            self.select_task(1);
+        }
+        self.detail = Some(TaskDetail::default());
+        self.refresh_detail();
+    }
+
+    /// Close the detail panel, keeping the tree cursor.
+    pub fn close_detail(&mut self) {
+        self.detail = None;
+    }
+
+    /// Rebuild the open detail panel from the spec, iteration log, and runtime state.
+    pub fn refresh_detail(&mut self) {
+        if self.detail.is_none() {
+            return;
+        }
+        let Some(task) = self
+            .selected_task
+            .as_ref()
+            .and_then(|id| self.graph.tasks.get(id))
+        else {
+            self.detail = None;
+            return;
+        };
+        let specs = crate::local_state::read_subtasks(&self.parent_id);
+        let spec = specs
+            .iter()
+            .find(|s| s.id == task.id || s.identifier == task.identifier);
+        let log = crate::local_state::read_iteration_log(&self.parent_id);
+        self.detail = Some(TaskDetail::build(
+            task,
+            &self.graph,
+            &self.status_overrides(),
+            spec,
+            &log,
+            self.runtime_state.as_ref(),
+        ));
+    }
+
+    /// Ask the loop to dispatch the selected failed task again.
+    pub fn retry_selected(&mut self) {
+        let Some(task) = self.actionable_task() else {
+            return;
+        };
+        if self.effective_status(&task) != TaskStatus::Failed {
+            self.set_notice(format!(
+                "{} has not failed; nothing to retry",
+                task.identifier
+            ));
+            return;
+        }
+        // A pending status lets `mobius resume` pick the task up too
+        update_subtask_status(&self.parent_id, &task.identifier, "pending");
+        self.send_action(
+            &task,
+            TaskAction::Retry,
+            TaskStatus::Ready,
+            "queued for retry",
+        );
+    }
+
+    /// Mark the selected task skipped: done for unblocking, never dispatched.
+    pub fn skip_selected(&mut self) {
+        let Some(task) = self.actionable_task() else {
+            return;
+        };
+        match self.effective_status(&task) {
+            TaskStatus::Done => {
+                self.set_notice(format!("{} is already done", task.identifier));
+                return;
+            }
+            TaskStatus::InProgress if self.active_task_info(&task.identifier).is_some() => {
+                self.set_notice(format!(
+                    "{} is running; wait for it to finish",
+                    task.identifier
+                ));
+                return;
+            }
+            _ => {}
+        }
+        update_subtask_status(&self.parent_id, &task.identifier, "skipped");
+        self.send_action(&task, TaskAction::Skip, TaskStatus::Done, "skipped");
+    }
+
+    /// Raise the selected task's dispatch priority by one level (1 = highest).
+    pub fn bump_selected_priority(&mut self) {
+        let Some(task) = self.actionable_task() else {
+            return;
+        };
+        if self.effective_status(&task) == TaskStatus::Done {
+            self.set_notice(format!("{} is already done", task.identifier));
+            return;
+        }
+        // Unprioritized tasks dispatch after every prioritized one
+        let priority = task.priority.map_or(1, |p| p.saturating_sub(1).max(1));
+        if task.priority == Some(priority) {
+            self.set_notice(format!(
+                "{} already has the highest priority",
+                task.identifier
+            ));
+            return;
+        }
+        update_subtask_priority(&self.parent_id, &task.identifier, priority);
+        if let Some(t) = self.graph.tasks.get_mut(&task.id) {
+            t.priority = Some(priority);
+        }
+        self.set_notice(format!(
+            "{} priority set to {}; applies from the next batch",
+            task.identifier, priority
+        ));
+    }
+
+    /// Request a jump to the tmux pane of the selected running task, or of
+    /// the agent shown in the log pane.
+    pub fn attach_selected(&mut self) {
+        if self.replay.is_some() {
+            self.set_notice("Replaying: there are no live agents to attach to".to_string());
+            return;
+        }
+        let selected = self
+            .selected_task
+            .as_ref()
+            .and_then(|id| self.graph.tasks.get(id))
+            .map(|t| t.identifier.clone())
+            .filter(|identifier| self.active_task_info(identifier).is_some());
+        let from_log = self
+            .selected_agent_id()
+            .filter(|_| self.show_log)
+            .map(str::to_string);
+        let Some(identifier) = selected.or(from_log) else {
+            self.set_notice("Select a running task to attach to its pane".to_string());
+            return;
+        };
+        let pane = self
+            .active_task_info(&identifier)
+            .map(|t| t.pane.clone())
+            .filter(|pane| !pane.is_empty())
+            .or_else(|| {
+                crate::tmux::find_agent_pane(
+                    &crate::tmux::get_session_name(&self.parent_id),
+                    &identifier,
+                )
+            });
+        match pane {
+            Some(pane) => self.attach_request = Some(pane),
+            None => self.set_notice(format!("{} has no tmux pane to attach to", identifier)),
+        }
+    }
+
+    /// The selected task, unless the dashboard is read-only.
+    fn actionable_task(&mut self) -> Option<SubTask> {
+        if self.observe {
+            let mode = if self.replay.is_some() {
+                "Replaying"
+            } else {
+                "Observing"
+            };
+            self.set_notice(format!("{}: task actions are disabled", mode));
+            return None;
+        }
+        let task = self
+            .selected_task
+            .as_ref()
+            .and_then(|id| self.graph.tasks.get(id))
+            .cloned();
+        if task.is_none() {
+            self.set_notice("Select a task with ↑/↓ first".to_string());
+        }
+        task
+    }
+
+    fn send_action(&mut self, task: &SubTask, action: TaskAction, status: TaskStatus, done: &str) {
+        match queue_task_action(&self.parent_id, &task.identifier, action) {
+            Ok(()) => {
+                if let Some(t) = self.graph.tasks.get_mut(&task.id) {
+                    t.status = status;
+                }
+                self.reload_runtime_state();
+                self.refresh_detail();
+                self.set_notice(format!("{} {}", task.identifier, done));
+            }
+            Err(e) => self.set_notice(format!("{}: {}", task.identifier, e)),
+        }
+    }
+
+    pub fn set_notice(&mut self, notice: String) {
+        self.notice = Some(notice);
+        self.notice_ticks = 4;
+    }
+
     /// Check if there are active tasks.
     pub fn has_active_tasks(&self) -> bool {
         self.runtime_state
@@ -187,8 +605,33 @@ impl App {
     }
 
     /// Get the elapsed time since TUI start in milliseconds.
+    ///
+    /// Observers join runs already in progress, so they measure from the
+    /// run's own start time instead; replays stop at the shown revision.
     pub fn elapsed_ms(&self) -> u64 {
-        self.start_time.elapsed().as_millis() as u64
+        if let Some(replay) = &self.replay {
+            let entry = replay.current();
+            let at = |t: &str| chrono::DateTime::parse_from_rfc3339(t).ok();
+            return match (at(&entry.state.started_at), at(&entry.recorded_at)) {
+                (Some(started), Some(recorded)) => recorded
+                    .signed_duration_since(started)
+                    .num_milliseconds()
+                    .max(0) as u64,
+                _ => 0,
+            };
+        }
+        let run_started = self
+            .runtime_state
+            .as_ref()
+            .filter(|_| self.observe)
+            .and_then(|s| chrono::DateTime::parse_from_rfc3339(&s.started_at).ok());
+        match run_started {
+            Some(started) => chrono::Utc::now()
+                .signed_duration_since(started)
+                .num_milliseconds()
+                .max(0) as u64,
+            None => self.start_time.elapsed().as_millis() as u64,
+        }
     }
 
     /// Get status overrides based on runtime state.
@@ -254,6 +697,7 @@ impl App {
                     duration: 0,
                     input_tokens: None,
                     output_tokens: None,
+                    model: None,
                 });
             }
         }
@@ -296,7 +740,10 @@ impl App {
 
         matches!(
             session.status,
-            SessionStatus::Completed | SessionStatus::Failed
+            SessionStatus::Completed
+                | SessionStatus::Failed
+                | SessionStatus::Interrupted
+                | SessionStatus::Cancelled
         )
     }
 }
@@ -313,6 +760,7 @@ fn extract_task_id(value: &serde_json::Value) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::enums::TaskType;
     use crate::types::task_graph::TaskGraph;
 
     fn make_graph(total: usize) -> TaskGraph {
@@ -330,6 +778,10 @@ mod tests {
                     blocks: Vec::new(),
                     git_branch_name: String::new(),
                     scoring: None,
+                    priority: None,
+                    labels: Vec::new(),
+                    timeout_minutes: None,
+                    task_type: TaskType::Agent,
                 },
             );
         }
@@ -448,4 +900,199 @@ mod tests {
 
         let _ = std::fs::remove_dir_all(exec_dir);
     }
+
+    #[test]
+    fn observer_quits_without_exit_modal() {
+        let exec_dir = unique_execution_dir("observer");
+        let runtime_path = exec_dir.join("runtime.json");
+
+        let mut runtime = make_runtime_state(2, 0, 0);
+        runtime["activeTasks"] = serde_json::json!([{
+            "id": "task-001",
+            "pid": 0,
+            "pane": "",
+            "startedAt": "2026-02-07T00:00:00Z",
+            "worktree": null
+        }]);
+        std::fs::write(
+            &runtime_path,
+            serde_json::to_string_pretty(&runtime).unwrap(),
+        )
+        .unwrap();
+
+        let mut app = App::new(
+            "MOB-1".to_string(),
+            "Parent".to_string(),
+            make_graph(2),
+            runtime_path,
+            3,
+        );
+        app.observe = true;
+        app.reload_runtime_state();
+        assert!(app.has_active_tasks());
+
+        app.on_quit_key();
+        assert!(!app.show_exit_modal);
+        assert!(app.should_quit);
+        // Elapsed time follows the run, not the observer
+        assert!(app.elapsed_ms() > 24 * 60 * 60 * 1000);
+
+        let _ = std::fs::remove_dir_all(exec_dir);
+    }
+
+    #[test]
+    fn on_tick_picks_up_pause_flag() {
+        let exec_dir = unique_execution_dir("paused");
+        let runtime_path = exec_dir.join("runtime.json");
+        let mut app = App::new(
+            "MOB-1".to_string(),
+            "Parent".to_string(),
+            make_graph(2),
+            runtime_path,
+            3,
+        );
+
+        app.on_tick();
+        assert!(!app.paused);
+        std::fs::write(exec_dir.join(crate::context::PAUSE_FLAG_FILE), "").unwrap();
+        app.on_tick();
+        assert!(app.paused);
+
+        let _ = std::fs::remove_dir_all(exec_dir);
+    }
+
+    #[test]
+    fn on_tick_picks_up_offline_flag() {
+        let exec_dir = unique_execution_dir("offline");
+        let runtime_path = exec_dir.join("runtime.json");
+        let mut app = App::new(
+            "MOB-1".to_string(),
+            "Parent".to_string(),
+            make_graph(2),
+            runtime_path,
+            3,
+        );
+
+        app.on_tick();
+        assert_eq!(app.offline_queued, None);
+        std::fs::write(exec_dir.join(crate::context::OFFLINE_FLAG_FILE), "").unwrap();
+        app.on_tick();
+        assert!(app.offline_queued.is_some());
+
+        let _ = std::fs::remove_dir_all(exec_dir);
+    }
+
+    #[test]
+    fn check_completion_marks_complete_when_session_interrupted() {
+        let exec_dir = unique_execution_dir("session-interrupted");
+        let runtime_path = exec_dir.join("runtime.json");
+
+        let runtime = make_runtime_state(8, 3, 0);
+        std::fs::write(
+            &runtime_path,
+            serde_json::to_string_pretty(&runtime).unwrap(),
+        )
+        .unwrap();
+
+        let session = serde_json::json!({
+            "parentId": "MOB-1",
+            "backend": "linear",
+            "startedAt": "2026-02-07T00:00:00Z",
+            "worktreePath": null,
+            "status": "interrupted"
+        });
+        std::fs::write(
+            exec_dir.join("session.json"),
+            serde_json::to_string_pretty(&session).unwrap(),
+        )
+        .unwrap();
+
+        let mut app = App::new(
+            "MOB-1".to_string(),
+            "Parent".to_string(),
+            make_graph(8),
+            runtime_path,
+            3,
+        );
+
+        app.reload_runtime_state();
+
+        assert!(app.is_complete);
+
+        let _ = std::fs::remove_dir_all(exec_dir);
+    }
+
+    #[test]
+    fn select_task_walks_tree_order_and_clamps() {
+        let mut app = App::new(
+            "MOB-1".to_string(),
+            "Parent".to_string(),
+            make_graph(3),
+            PathBuf::new(),
+            3,
+        );
+
+        app.select_task(-1);
+        assert_eq!(app.selected_task.as_deref(), Some("task-003"));
+        app.select_task(1);
+        assert_eq!(app.selected_task.as_deref(), Some("task-003"));
+        app.select_task(-5);
+        assert_eq!(app.selected_task.as_deref(), Some("task-001"));
+        assert!(app.detail.is_none());
+    }
+
+    #[test]
+    fn history_scroll_clamps_to_the_log() {
+        let mut app = App::new(
+            "MOB-1".to_string(),
+            "Parent".to_string(),
+            make_graph(2),
+            PathBuf::new(),
+            3,
+        );
+        app.scroll_history(1);
+        assert_eq!(app.history_scroll, 0);
+
+        let entry: IterationLogEntry = serde_json::from_value(serde_json::json!({
+            "subtaskId": "MOB-2",
+            "attempt": 1,
+            "startedAt": "2026-10-01T10:00:00Z",
+            "status": "failed"
+        }))
+        .unwrap();
+        app.history = Some(vec![entry; 3]);
+        app.scroll_history(isize::MAX);
+        assert_eq!(app.history_scroll, 2);
+        app.scroll_history(-1);
+        assert_eq!(app.history_scroll, 1);
+
+        app.toggle_history();
+        assert!(app.history.is_none());
+    }
+
+    #[test]
+    fn task_actions_are_disabled_when_observing() {
+        let mut app = App::new(
+            "MOB-1".to_string(),
+            "Parent".to_string(),
+            make_graph(2),
+            PathBuf::new(),
+            3,
+        );
+        app.observe = true;
+        app.select_task(1);
+
+        app.skip_selected();
+        assert_eq!(
+            app.notice.as_deref(),
+            Some("Observing: task actions are disabled")
+        );
+        assert_eq!(app.graph.tasks["task-001"].status, TaskStatus::Pending);
+
+        // The notice clears after a few ticks
+        for _ in 0..5 {
+            app.on_tick();
+        }
+        assert!(app.notice.is_none());
+    }
 }
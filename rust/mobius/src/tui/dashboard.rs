@@ -3,6 +3,7 @@ use std::io;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use crossterm::event::{KeyCode, KeyModifiers};
 use crossterm::execute;
 use crossterm::terminal::{
@@ -15,7 +16,13 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders};
 use ratatui::Terminal;
 
-use crate::types::task_graph::TaskGraph;
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::context::{normalize_completed_task, read_runtime_journal, RUNTIME_JOURNAL_FILE};
+use crate::external_deps::apply_resolved_blockers;
+use crate::local_state::{read_local_subtasks_as_linear_issues, read_parent_spec};
+use crate::tmux::{focus_pane, is_inside_tmux};
+use crate::types::task_graph::{build_task_graph, TaskGraph};
 
 use super::agent_progress::{calculate_height, AgentProgress};
 use super::agent_slots::{ActiveTaskDisplay, AgentSlots, AGENT_SLOTS_HEIGHT};
@@ -24,11 +31,78 @@ use super::debug_panel::{DebugPanel, DEBUG_PANEL_HEIGHT};
 use super::events::{EventHandler, TuiEvent};
 use super::exit_modal::ExitModal;
 use super::header::{Header, HEADER_HEIGHT};
+use super::history::{HistoryView, HISTORY_PAGE};
 use super::legend::{Legend, LEGEND_HEIGHT};
+use super::log_pane::{LogPane, LOG_PANE_HEIGHT};
+use super::replay::{Replay, ReplayBar, REPLAY_BAR_HEIGHT};
+use super::task_detail::TaskDetailPanel;
 use super::task_tree::{CompletedInfo, TaskTreeWidget};
-use super::theme::{BORDER_COLOR, HEADER_COLOR, MUTED_COLOR, NORD0, NORD11, NORD14, TEXT_COLOR};
+use super::theme::{
+    Theme, BORDER_COLOR, HEADER_COLOR, MUTED_COLOR, NORD0, NORD11, NORD13, NORD14, TEXT_COLOR,
+};
 use super::token_metrics::{TokenMetrics, TOKEN_METRICS_HEIGHT};
 
+/// Time inputs for a single render pass.
+///
+/// Live rendering reads the wall clock; snapshot tests pin both values so
+/// the same `App` always produces the same frame.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderClock {
+    /// Reference time for active task elapsed durations
+    pub now: DateTime<Utc>,
+    /// Total runtime shown in the header, completion bar, and exit modal
+    pub elapsed_ms: u64,
+}
+
+impl RenderClock {
+    /// Clock for the current instant of a live dashboard.
+    pub fn live(app: &App) -> Self {
+        Self {
+            now: Utc::now(),
+            elapsed_ms: app.elapsed_ms(),
+        }
+    }
+}
+
+/// How a dashboard was opened
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DashboardOptions {
+    /// Only read state files: quitting never signals the loop, so it is
+    /// safe to watch a run hosted by someone else
+    pub observe: bool,
+    /// Step through the run's runtime journal instead of following the
+    /// live state file
+    pub replay: bool,
+    /// Opened from `mobius tui --all`: quitting goes back to the overview
+    /// and leaves the loop running
+    pub from_overview: bool,
+}
+
+/// Build `parent_id`'s task graph from local state and run its dashboard.
+pub fn run_issue_dashboard(
+    parent_id: &str,
+    runtime_state_path: PathBuf,
+    options: DashboardOptions,
+) -> anyhow::Result<()> {
+    let issues = read_local_subtasks_as_linear_issues(parent_id);
+    let graph =
+        apply_resolved_blockers(parent_id, &build_task_graph(parent_id, parent_id, &issues));
+    let parent_title = read_parent_spec(parent_id)
+        .map(|p| p.title)
+        .unwrap_or_else(|| parent_id.to_string());
+    let config = read_config(&resolve_paths().config_path).unwrap_or_default();
+    let max_parallel_agents = config.execution.max_parallel_agents.unwrap_or(3) as usize;
+
+    run_dashboard(
+        parent_id.to_string(),
+        parent_title,
+        graph,
+        runtime_state_path,
+        max_parallel_agents,
+        options,
+    )
+}
+
 /// Run the TUI dashboard.
 pub fn run_dashboard(
     parent_id: String,
@@ -36,7 +110,24 @@ pub fn run_dashboard(
     graph: TaskGraph,
     runtime_state_path: PathBuf,
     max_parallel_agents: usize,
+    options: DashboardOptions,
 ) -> anyhow::Result<()> {
+    let replay = if options.replay {
+        let journal_path = runtime_state_path.with_file_name(RUNTIME_JOURNAL_FILE);
+        let entries = read_runtime_journal(&journal_path);
+        match Replay::new(entries) {
+            Some(replay) => Some(replay),
+            None => anyhow::bail!("No runtime journal at {}", journal_path.display()),
+        }
+    } else {
+        None
+    };
+
+    let config = read_config(&resolve_paths().config_path).unwrap_or_default();
+    let tui_config = config.execution.tui.clone().unwrap_or_default();
+    let theme = Theme::from_config(&tui_config)
+        .map_err(|e| anyhow::anyhow!("Invalid execution.tui.colors: {}", e))?;
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -53,17 +144,28 @@ pub fn run_dashboard(
         runtime_state_path.clone(),
         max_parallel_agents,
     );
+    app.observe = options.observe;
+    app.from_overview = options.from_overview;
+    app.theme = theme;
+    app.max_cost_usd = config.loop_settings.max_cost_usd;
 
     // Load initial runtime state if file exists
-    app.reload_runtime_state();
-
-    // Create event handler with todos directory watcher
-    let todos_dir = app.todos_dir();
-    let events = EventHandler::new(Some(runtime_state_path), Some(todos_dir));
+    let events = match replay {
+        Some(replay) => {
+            app.start_replay(replay);
+            EventHandler::new(None, None)
+        }
+        None => {
+            app.reload_runtime_state();
+            // Create event handler with todos directory watcher
+            let todos_dir = app.todos_dir();
+            EventHandler::new(Some(runtime_state_path), Some(todos_dir))
+        }
+    };
 
     // Main event loop
     loop {
-        terminal.draw(|frame| render_dashboard(frame, &app))?;
+        terminal.draw(|frame| render_dashboard(frame, &app, RenderClock::live(&app)))?;
 
         if app.should_quit {
             break;
@@ -72,7 +174,12 @@ pub fn run_dashboard(
         // Poll for events with a timeout
         if let Some(event) = events.next(Duration::from_millis(100)) {
             match event {
-                TuiEvent::Key(key) => handle_key_event(&mut app, key),
+                TuiEvent::Key(key) => {
+                    handle_key_event(&mut app, key);
+                    if let Some(pane) = app.attach_request.take() {
+                        attach_to_pane(&mut terminal, &events, &mut app, &pane)?;
+                    }
+                }
                 TuiEvent::StateFileChanged => {
                     app.reload_runtime_state();
                 }
@@ -81,6 +188,9 @@ pub fn run_dashboard(
                 }
                 TuiEvent::Tick => {
                     app.on_tick();
+                    app.refresh_agent_log();
+                    app.refresh_detail();
+                    app.refresh_history();
                 }
             }
         }
@@ -94,6 +204,43 @@ pub fn run_dashboard(
     Ok(())
 }
 
+/// Bring the user to an agent's tmux pane.
+///
+/// Outside tmux the dashboard hands the terminal to `tmux attach-session`
+/// and takes it back once the user detaches.
+fn attach_to_pane(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    events: &EventHandler,
+    app: &mut App,
+    pane: &str,
+) -> anyhow::Result<()> {
+    if is_inside_tmux() {
+        match focus_pane(pane) {
+            Ok(focus) => app.set_notice(format!(
+                "Switched to pane {}; {} returns",
+                pane,
+                focus.return_keys().unwrap_or("prefix+L")
+            )),
+            Err(e) => app.set_notice(format!("{:#}", e)),
+        }
+        return Ok(());
+    }
+
+    events.suspend_input(true);
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)?;
+    let result = focus_pane(pane);
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    terminal.clear()?;
+    events.suspend_input(false);
+
+    if let Err(e) = result {
+        app.set_notice(format!("{:#}", e));
+    }
+    Ok(())
+}
+
 fn handle_key_event(app: &mut App, key: crossterm::event::KeyEvent) {
     // Handle exit modal first
     if app.show_exit_modal {
@@ -109,6 +256,21 @@ fn handle_key_event(app: &mut App, key: crossterm::event::KeyEvent) {
         return;
     }
 
+    // The detail panel takes navigation keys while open
+    if app.detail.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => app.close_detail(),
+            KeyCode::Up | KeyCode::Char('k') => app.select_task(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.select_task(1),
+            KeyCode::Char('r') => app.retry_selected(),
+            KeyCode::Char('s') => app.skip_selected(),
+            KeyCode::Char('p') => app.bump_selected_priority(),
+            KeyCode::Char('a') => app.attach_selected(),
+            _ => {}
+        }
+        return;
+    }
+
     // Handle completion state (any key exits)
     if app.is_complete {
         match key.code {
@@ -121,9 +283,32 @@ fn handle_key_event(app: &mut App, key: crossterm::event::KeyEvent) {
     }
 
     // Normal mode key handling
+    let replaying = app.replay.is_some();
+    let history = app.history.is_some();
     match key.code {
+        KeyCode::Up | KeyCode::Char('k') if history => app.scroll_history(-1),
+        KeyCode::Down | KeyCode::Char('j') if history => app.scroll_history(1),
+        KeyCode::PageUp if history => app.scroll_history(-HISTORY_PAGE),
+        KeyCode::PageDown if history => app.scroll_history(HISTORY_PAGE),
+        KeyCode::Home if history => app.history_scroll = 0,
+        KeyCode::End if history => app.scroll_history(isize::MAX),
+        KeyCode::Esc if history => app.toggle_history(),
+        KeyCode::Char('h') => app.toggle_history(),
+        KeyCode::Left if replaying => app.replay_step(-1),
+        KeyCode::Right if replaying => app.replay_step(1),
+        KeyCode::Home if replaying => app.replay_jump(false),
+        KeyCode::End if replaying => app.replay_jump(true),
         KeyCode::Char('q') => app.on_quit_key(),
         KeyCode::Char('d') => app.toggle_debug(),
+        KeyCode::Char('l') => app.toggle_log(),
+        KeyCode::Tab if app.show_log => app.select_next_agent(),
+        KeyCode::Up | KeyCode::Char('k') => app.select_task(-1),
+        KeyCode::Down | KeyCode::Char('j') => app.select_task(1),
+        KeyCode::Enter => app.open_detail(),
+        KeyCode::Char('r') => app.retry_selected(),
+        KeyCode::Char('s') => app.skip_selected(),
+        KeyCode::Char('p') => app.bump_selected_priority(),
+        KeyCode::Char('a') => app.attach_selected(),
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             app.on_quit_key();
         }
@@ -131,7 +316,10 @@ fn handle_key_event(app: &mut App, key: crossterm::event::KeyEvent) {
     }
 }
 
-fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
+/// Render the full dashboard for `app` into `frame`.
+///
+/// Pure with respect to `app` and `clock`: no I/O and no wall-clock reads.
+pub fn render_dashboard(frame: &mut ratatui::Frame, app: &App, clock: RenderClock) {
     let size = frame.area();
 
     // Clear background
@@ -153,6 +341,10 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
         ));
     }
 
+    if app.show_log {
+        constraints.push(Constraint::Length(LOG_PANE_HEIGHT));
+    }
+
     if app.show_legend {
         constraints.push(Constraint::Length(LEGEND_HEIGHT + 2));
     }
@@ -161,6 +353,10 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
         constraints.push(Constraint::Length(DEBUG_PANEL_HEIGHT));
     }
 
+    if app.replay.is_some() {
+        constraints.push(Constraint::Length(REPLAY_BAR_HEIGHT));
+    }
+
     // Completion bar
     if app.is_complete {
         constraints.push(Constraint::Length(2));
@@ -173,12 +369,29 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
 
     let mut chunk_idx = 0;
 
+    let (total_input, total_output) = app
+        .runtime_state
+        .as_ref()
+        .map(|s| {
+            (
+                s.total_input_tokens.unwrap_or(0),
+                s.total_output_tokens.unwrap_or(0),
+            )
+        })
+        .unwrap_or((0, 0));
+
     // Render header
     let header = Header {
         parent_id: &app.parent_id,
         parent_title: &app.parent_title,
-        elapsed_ms: app.elapsed_ms(),
+        elapsed_ms: clock.elapsed_ms,
         has_runtime: app.runtime_state.is_some(),
+        observe: app.observe,
+        paused: app.paused,
+        offline_queued: app.offline_queued,
+        tokens: (total_input, total_output),
+        cost_usd: app.runtime_state.as_ref().and_then(|s| s.total_cost_usd),
+        max_cost_usd: app.max_cost_usd,
     };
     frame.render_widget(header, chunks[chunk_idx]);
     chunk_idx += 1;
@@ -195,7 +408,8 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
     if let Some(state) = &app.runtime_state {
         for task in &state.active_tasks {
             if let Ok(started) = chrono::DateTime::parse_from_rfc3339(&task.started_at) {
-                let elapsed = chrono::Utc::now()
+                let elapsed = clock
+                    .now
                     .signed_duration_since(started)
                     .num_milliseconds()
                     .max(0) as u64;
@@ -204,38 +418,58 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
         }
 
         for entry in &state.completed_tasks {
-            if let Some(obj) = entry.as_object() {
-                let id = obj
-                    .get("id")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or_default()
-                    .to_string();
-                let duration = obj.get("duration").and_then(|v| v.as_u64()).unwrap_or(0);
-                if !id.is_empty() {
-                    completed_info.insert(id, CompletedInfo { duration });
+            if entry.is_object() {
+                let completed = normalize_completed_task(entry);
+                let tokens = match (completed.input_tokens, completed.output_tokens) {
+                    (None, None) => None,
+                    (input, output) => Some(input.unwrap_or(0) + output.unwrap_or(0)),
+                };
+                if !completed.id.is_empty() {
+                    completed_info.insert(
+                        completed.id,
+                        CompletedInfo {
+                            duration: completed.duration,
+                            tokens,
+                        },
+                    );
                 }
             }
         }
     }
 
-    let task_tree_block = Block::default()
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(BORDER_COLOR))
-        .title(Span::styled(
-            " Task Tree ",
-            Style::default().fg(HEADER_COLOR),
-        ));
-    let task_tree_inner = task_tree_block.inner(main_area);
-    frame.render_widget(task_tree_block, main_area);
-
-    let task_tree = TaskTreeWidget {
-        graph: &app.graph,
-        status_overrides: &status_overrides,
-        active_elapsed: &active_elapsed,
-        completed_info: &completed_info,
-    };
-    frame.render_widget(task_tree, task_tree_inner);
+    if let Some(entries) = &app.history {
+        let history = HistoryView {
+            entries,
+            scroll: app.history_scroll,
+        };
+        frame.render_widget(history, main_area);
+    } else {
+        let mut task_tree_block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(BORDER_COLOR))
+            .title(Span::styled(
+                " Task Tree ",
+                Style::default().fg(HEADER_COLOR),
+            ));
+        if let Some(notice) = &app.notice {
+            task_tree_block = task_tree_block.title_bottom(Span::styled(
+                format!(" {} ", notice),
+                Style::default().fg(NORD13),
+            ));
+        }
+        let task_tree_inner = task_tree_block.inner(main_area);
+        frame.render_widget(task_tree_block, main_area);
+
+        let task_tree = TaskTreeWidget {
+            graph: &app.graph,
+            status_overrides: &status_overrides,
+            active_elapsed: &active_elapsed,
+            completed_info: &completed_info,
+            selected: app.selected_task.as_deref(),
+        };
+        frame.render_widget(task_tree, task_tree_inner);
+    }
 
     // Render agent slots
     let agent_area = chunks[chunk_idx];
@@ -258,6 +492,11 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
                 .map(|t| ActiveTaskDisplay {
                     id: t.id.clone(),
                     model: t.model.clone(),
+                    retry_count: t.retry_count.unwrap_or(0),
+                    tokens: match (t.input_tokens, t.output_tokens) {
+                        (None, None) => None,
+                        (input, output) => Some(input.unwrap_or(0) + output.unwrap_or(0)),
+                    },
                 })
                 .collect()
         })
@@ -284,22 +523,12 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
         }
     }
 
-    let (total_input, total_output) = app
-        .runtime_state
-        .as_ref()
-        .map(|s| {
-            (
-                s.total_input_tokens.unwrap_or(0),
-                s.total_output_tokens.unwrap_or(0),
-            )
-        })
-        .unwrap_or((0, 0));
-
     let token_metrics = TokenMetrics {
         total_input,
         total_output,
         per_model: &per_model,
         token_history: app.token_history(),
+        total_cost_usd: app.runtime_state.as_ref().and_then(|s| s.total_cost_usd),
     };
     frame.render_widget(token_metrics, token_area);
 
@@ -325,6 +554,25 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
         frame.render_widget(agent_progress, progress_inner);
     }
 
+    // Render agent log pane (if shown)
+    if app.show_log {
+        let selected = app.selected_agent_id();
+        let position = app.runtime_state.as_ref().and_then(|s| {
+            let index = s
+                .active_tasks
+                .iter()
+                .position(|t| Some(t.id.as_str()) == selected)?;
+            Some((index, s.active_tasks.len()))
+        });
+        let log_pane = LogPane {
+            task_id: selected,
+            events: &app.agent_log.events,
+            position,
+        };
+        frame.render_widget(log_pane, chunks[chunk_idx]);
+        chunk_idx += 1;
+    }
+
     // Render legend (if shown)
     if app.show_legend {
         let legend_area = chunks[chunk_idx];
@@ -352,6 +600,12 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
         chunk_idx += 1;
     }
 
+    // Render replay position and transition (if replaying)
+    if let Some(replay) = &app.replay {
+        frame.render_widget(ReplayBar { replay }, chunks[chunk_idx]);
+        chunk_idx += 1;
+    }
+
     // Render completion bar
     if app.is_complete {
         let (completed, total, failed) = app.execution_summary();
@@ -361,11 +615,20 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
             completed,
             total,
             failed,
-            app.elapsed_ms(),
+            clock.elapsed_ms,
             app.auto_exit_tick,
         );
     }
 
+    // Render task detail over the dashboard
+    if let Some(detail) = &app.detail {
+        let panel = TaskDetailPanel {
+            detail,
+            read_only: app.observe,
+        };
+        frame.render_widget(panel, size);
+    }
+
     // Render exit modal on top (last, so it overlays everything)
     if app.show_exit_modal {
         let (completed, total, failed) = app.execution_summary();
@@ -380,10 +643,12 @@ fn render_dashboard(frame: &mut ratatui::Frame, app: &App) {
             completed,
             total,
             failed,
-            elapsed_ms: app.elapsed_ms(),
+            elapsed_ms: clock.elapsed_ms,
         };
         frame.render_widget(modal, size);
     }
+
+    app.theme.apply(frame.buffer_mut());
 }
 
 fn render_completion_bar(
@@ -8,8 +8,8 @@ use crate::types::debug::DebugEvent;
 use crate::types::enums::DebugEventType;
 
 use super::theme::{
-    BORDER_COLOR, HEADER_COLOR, MUTED_COLOR, NORD10, NORD13, NORD14, NORD15, NORD3, NORD8, NORD9,
-    TEXT_COLOR,
+    BORDER_COLOR, HEADER_COLOR, MUTED_COLOR, NORD10, NORD11, NORD12, NORD13, NORD14, NORD15, NORD3,
+    NORD8, NORD9, TEXT_COLOR,
 };
 
 pub struct DebugPanel<'a> {
@@ -121,6 +121,9 @@ fn event_type_label(event_type: DebugEventType) -> (&'static str, ratatui::style
         DebugEventType::LockAcquire => ("LOCK+", NORD3),
         DebugEventType::LockRelease => ("LOCK-", NORD3),
         DebugEventType::TuiStateReceive => ("TUI:RECV", NORD9),
+        DebugEventType::StateDrift => ("DRIFT", NORD11),
+        DebugEventType::LockContention => ("LOCK:WAIT", NORD12),
+        DebugEventType::PaneCapture => ("PANE", NORD3),
     }
 }
 
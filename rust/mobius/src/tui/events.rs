@@ -1,5 +1,6 @@
 use std::path::PathBuf;
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::time::Duration;
 
 use crossterm::event::{self, Event, KeyEvent};
@@ -20,6 +21,10 @@ pub enum TuiEvent {
 /// Manages the three event sources: keyboard, file watcher, and tick timer.
 pub struct EventHandler {
     rx: mpsc::Receiver<TuiEvent>,
+    /// Set while another program owns the terminal's input
+    input_suspended: Arc<AtomicBool>,
+    /// Set on drop so the keyboard thread stops reading keys
+    closed: Arc<AtomicBool>,
     _keyboard_handle: std::thread::JoinHandle<()>,
     _tick_handle: std::thread::JoinHandle<()>,
     _watcher: Option<notify::RecommendedWatcher>,
@@ -34,7 +39,18 @@ impl EventHandler {
 
         // Keyboard event thread
         let tx_key = tx.clone();
+        let input_suspended = Arc::new(AtomicBool::new(false));
+        let suspended = Arc::clone(&input_suspended);
+        let closed = Arc::new(AtomicBool::new(false));
+        let stop = Arc::clone(&closed);
         let keyboard_handle = std::thread::spawn(move || loop {
+            if stop.load(Ordering::SeqCst) {
+                break;
+            }
+            if suspended.load(Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(50));
+                continue;
+            }
             if event::poll(Duration::from_millis(100)).unwrap_or(false) {
                 if let Ok(Event::Key(key)) = event::read() {
                     if tx_key.send(TuiEvent::Key(key)).is_err() {
@@ -98,6 +114,8 @@ impl EventHandler {
 
         Self {
             rx,
+            input_suspended,
+            closed,
             _keyboard_handle: keyboard_handle,
             _tick_handle: tick_handle,
             _watcher: watcher,
@@ -105,8 +123,28 @@ impl EventHandler {
         }
     }
 
+    /// Stop (or resume) reading keys, e.g. while tmux is attached to the terminal.
+    ///
+    /// Suspending waits out the keyboard thread's current poll so no key
+    /// meant for the other program is consumed.
+    pub fn suspend_input(&self, suspended: bool) {
+        self.input_suspended.store(suspended, Ordering::SeqCst);
+        if suspended {
+            std::thread::sleep(Duration::from_millis(150));
+        }
+    }
+
     /// Try to receive the next event, blocking up to the given timeout.
     pub fn next(&self, timeout: Duration) -> Option<TuiEvent> {
         self.rx.recv_timeout(timeout).ok()
     }
 }
+
+impl Drop for EventHandler {
+    /// Waits out the keyboard thread's current poll, so a dashboard opened
+    /// right after this one gets every key.
+    fn drop(&mut self) {
+        self.closed.store(true, Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(150));
+    }
+}
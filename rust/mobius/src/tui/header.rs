@@ -4,7 +4,9 @@ use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Widget;
 
-use super::theme::{HEADER_COLOR, MUTED_COLOR, TEXT_COLOR};
+use crate::cost::{budget_approaching, budget_exceeded, format_cost};
+
+use super::theme::{format_token_pair, HEADER_COLOR, MUTED_COLOR, NORD11, NORD13, TEXT_COLOR};
 
 const LOGO: &[&str] = &[
     "███╗   ███╗ ██████╗ ██████╗ ██╗██╗   ██╗███████╗",
@@ -20,6 +22,17 @@ pub struct Header<'a> {
     pub parent_title: &'a str,
     pub elapsed_ms: u64,
     pub has_runtime: bool,
+    /// Show the read-only observer badge
+    pub observe: bool,
+    /// Show that the loop is held by `mobius pause`
+    pub paused: bool,
+    /// Updates queued while the loop works offline; `None` when online
+    pub offline_queued: Option<usize>,
+    /// Cumulative input and output tokens of the run
+    pub tokens: (u64, u64),
+    pub cost_usd: Option<f64>,
+    /// `loop.max_cost_usd`, for the budget warning
+    pub max_cost_usd: Option<f64>,
 }
 
 impl Widget for Header<'_> {
@@ -50,7 +63,7 @@ impl Widget for Header<'_> {
                 "(waiting)".to_string()
             };
 
-            let info_line = Line::from(vec![
+            let mut info_line = Line::from(vec![
                 Span::styled(
                     format!("Task Tree for {}", self.parent_id),
                     Style::default().fg(TEXT_COLOR),
@@ -61,9 +74,49 @@ impl Widget for Header<'_> {
                     Style::default().fg(TEXT_COLOR),
                 ),
             ]);
+            if let Some(meter) = self.usage_meter() {
+                info_line
+                    .spans
+                    .push(Span::styled(" | ", Style::default().fg(MUTED_COLOR)));
+                info_line.spans.push(meter);
+            }
+            if self.paused {
+                info_line
+                    .spans
+                    .push(Span::styled(" | ", Style::default().fg(MUTED_COLOR)));
+                info_line
+                    .spans
+                    .push(Span::styled("PAUSED", Style::default().fg(NORD13)));
+            }
+            if let Some(queued) = self.offline_queued {
+                info_line
+                    .spans
+                    .push(Span::styled(" | ", Style::default().fg(MUTED_COLOR)));
+                info_line.spans.push(Span::styled(
+                    format!(
+                        "Offline, {} update{} queued",
+                        queued,
+                        if queued == 1 { "" } else { "s" }
+                    ),
+                    Style::default().fg(NORD13),
+                ));
+            }
+            if self.observe {
+                info_line
+                    .spans
+                    .push(Span::styled(" | ", Style::default().fg(MUTED_COLOR)));
+                info_line.spans.push(Span::styled(
+                    "Observing (read-only)",
+                    Style::default().fg(NORD13),
+                ));
+            }
 
             // Center the info line
-            let info_width: usize = info_line.spans.iter().map(|s| s.content.len()).sum();
+            let info_width: usize = info_line
+                .spans
+                .iter()
+                .map(|s| s.content.chars().count())
+                .sum();
             let x_offset = if area.width as usize > info_width {
                 (area.width as usize - info_width) / 2
             } else {
@@ -75,6 +128,39 @@ impl Widget for Header<'_> {
     }
 }
 
+impl Header<'_> {
+    /// Tokens and cost so far, against the budget when one is set. Yellow
+    /// near the budget, red once it is spent.
+    fn usage_meter(&self) -> Option<Span<'static>> {
+        let (input, output) = self.tokens;
+        let spent = self.cost_usd.unwrap_or(0.0);
+        if input == 0 && output == 0 && self.cost_usd.is_none() && self.max_cost_usd.is_none() {
+            return None;
+        }
+        let mut text = format_token_pair(input, output);
+        match (self.cost_usd, self.max_cost_usd) {
+            (_, Some(max)) => text.push_str(&format!(
+                " · {} of {}",
+                format_cost(spent),
+                format_cost(max)
+            )),
+            (Some(cost), None) => text.push_str(&format!(" · {}", format_cost(cost))),
+            (None, None) => {}
+        }
+        let color = if budget_exceeded(spent, self.max_cost_usd) {
+            NORD11
+        } else if budget_approaching(spent, self.max_cost_usd) {
+            NORD13
+        } else {
+            return Some(Span::styled(text, Style::default().fg(TEXT_COLOR)));
+        };
+        Some(Span::styled(
+            format!("⚠ {}", text),
+            Style::default().fg(color),
+        ))
+    }
+}
+
 /// Format a duration in milliseconds to a human-readable string.
 pub fn format_duration(ms: u64) -> String {
     let total_secs = ms / 1000;
@@ -98,6 +184,38 @@ pub const HEADER_HEIGHT: u16 = 8;
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_usage_meter_warns_near_budget() {
+        let mut header = Header {
+            parent_id: "MOB-1",
+            parent_title: "Parent",
+            elapsed_ms: 0,
+            has_runtime: true,
+            observe: false,
+            paused: false,
+            offline_queued: None,
+            tokens: (0, 0),
+            cost_usd: None,
+            max_cost_usd: None,
+        };
+        assert!(header.usage_meter().is_none());
+
+        header.tokens = (1_500, 500);
+        header.cost_usd = Some(1.0);
+        header.max_cost_usd = Some(5.0);
+        let meter = header.usage_meter().unwrap();
+        assert_eq!(meter.content, "1.5K in / 500 out · $1.00 of $5.00");
+        assert_eq!(meter.style.fg, Some(TEXT_COLOR));
+
+        header.cost_usd = Some(4.5);
+        let meter = header.usage_meter().unwrap();
+        assert!(meter.content.starts_with("⚠ "));
+        assert_eq!(meter.style.fg, Some(NORD13));
+
+        header.cost_usd = Some(5.0);
+        assert_eq!(header.usage_meter().unwrap().style.fg, Some(NORD11));
+    }
+
     #[test]
     fn test_format_duration_seconds() {
         assert_eq!(format_duration(5000), "5s");
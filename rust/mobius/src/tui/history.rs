@@ -0,0 +1,176 @@
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Widget};
+
+use crate::local_state::{IterationLogEntry, IterationStatus};
+
+use super::header::format_duration;
+use super::theme::{
+    model_color, BORDER_COLOR, HEADER_COLOR, MUTED_COLOR, NORD11, NORD13, NORD14, NORD8, TEXT_COLOR,
+};
+
+/// Rows moved by PageUp/PageDown
+pub const HISTORY_PAGE: isize = 10;
+
+/// The iteration log as a scrollable timeline, newest attempt first.
+pub struct HistoryView<'a> {
+    pub entries: &'a [IterationLogEntry],
+    /// Rows scrolled past from the newest attempt
+    pub scroll: usize,
+}
+
+impl Widget for HistoryView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let total = self.entries.len();
+        let visible = area.height.saturating_sub(3) as usize;
+        let scroll = self.scroll.min(total.saturating_sub(visible.max(1)));
+        let title = if total == 0 {
+            " History ".to_string()
+        } else {
+            format!(
+                " History ({}-{} of {}) ",
+                scroll + 1,
+                (scroll + visible).min(total),
+                total
+            )
+        };
+        let hint = "[↑↓/PgUp/PgDn] scroll  [h] close";
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(BORDER_COLOR))
+            .title(Span::styled(title, Style::default().fg(HEADER_COLOR)));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let hint_x = area.x + area.width.saturating_sub(hint.chars().count() as u16 + 2);
+        buf.set_string(hint_x, area.y, hint, Style::default().fg(MUTED_COLOR));
+
+        if total == 0 {
+            buf.set_string(
+                inner.x + 1,
+                inner.y,
+                "No iterations recorded yet",
+                Style::default().fg(MUTED_COLOR),
+            );
+            return;
+        }
+
+        let width = inner.width.saturating_sub(1);
+        buf.set_line(inner.x + 1, inner.y, &header_line(), width);
+        for (i, entry) in self
+            .entries
+            .iter()
+            .rev()
+            .skip(scroll)
+            .take(visible)
+            .enumerate()
+        {
+            let line = history_line(entry, width as usize);
+            buf.set_line(inner.x + 1, inner.y + 1 + i as u16, &line, width);
+        }
+    }
+}
+
+fn header_line() -> Line<'static> {
+    Line::from(Span::styled(
+        format!(
+            "{:<9}{:<12}{:<5}{:<10}{:<8}{:<9}{:<9}{}",
+            "Started", "Task", "#", "Status", "Model", "Took", "Commit", "Error"
+        ),
+        Style::default().fg(MUTED_COLOR),
+    ))
+}
+
+fn history_line(entry: &IterationLogEntry, width: usize) -> Line<'static> {
+    let started = chrono::DateTime::parse_from_rfc3339(&entry.started_at)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|_| "--:--:--".to_string());
+    let (icon, outcome, color) = match entry.status {
+        IterationStatus::Success => ("✓", "success", NORD14),
+        IterationStatus::Failed => ("✗", "failed", NORD11),
+        IterationStatus::Partial => ("◐", "partial", NORD13),
+    };
+    let duration = entry
+        .duration_ms
+        .map(format_duration)
+        .unwrap_or_else(|| "-".to_string());
+    let commit = entry
+        .commit_hash
+        .as_deref()
+        .map(|h| h.chars().take(7).collect::<String>())
+        .unwrap_or_else(|| "-".to_string());
+    let model = entry.model.clone().unwrap_or_default();
+
+    let mut spans = vec![
+        Span::styled(format!("{:<9}", started), Style::default().fg(MUTED_COLOR)),
+        Span::styled(
+            format!("{:<12}", entry.subtask_id),
+            Style::default().fg(TEXT_COLOR),
+        ),
+        Span::styled(
+            format!("{:<5}", entry.attempt),
+            Style::default().fg(MUTED_COLOR),
+        ),
+        Span::styled(
+            format!("{} {:<8}", icon, outcome),
+            Style::default().fg(color),
+        ),
+        Span::styled(
+            format!("{:<8}", model),
+            Style::default().fg(model_color(&model)),
+        ),
+        Span::styled(format!("{:<9}", duration), Style::default().fg(TEXT_COLOR)),
+        Span::styled(format!("{:<9}", commit), Style::default().fg(NORD8)),
+    ];
+
+    let used: usize = spans.iter().map(|s| s.content.chars().count()).sum();
+    if let Some(snippet) = error_snippet(entry, width.saturating_sub(used)) {
+        spans.push(Span::styled(snippet, Style::default().fg(NORD11)));
+    }
+    Line::from(spans)
+}
+
+/// First non-blank error line (or the failure class), cut to `room` columns.
+fn error_snippet(entry: &IterationLogEntry, room: usize) -> Option<String> {
+    let text = entry
+        .error
+        .as_deref()
+        .and_then(|e| e.lines().map(str::trim).find(|l| !l.is_empty()))
+        .map(str::to_string)
+        .or_else(|| entry.failure_class.map(|c| c.to_string()))?;
+    if room == 0 {
+        return None;
+    }
+    if text.chars().count() <= room {
+        return Some(text);
+    }
+    let cut: String = text.chars().take(room.saturating_sub(1)).collect();
+    Some(format!("{}…", cut))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_snippet_uses_first_line_and_truncates() {
+        let entry = IterationLogEntry {
+            error: Some("\n  cargo test failed: 3 tests\nmore".into()),
+            ..IterationLogEntry::new("MOB-2", 1, "2026-10-01T10:00:00Z", IterationStatus::Failed)
+        };
+        assert_eq!(
+            error_snippet(&entry, 80).as_deref(),
+            Some("cargo test failed: 3 tests")
+        );
+        assert_eq!(error_snippet(&entry, 8).as_deref(), Some("cargo t…"));
+        assert_eq!(error_snippet(&entry, 0), None);
+    }
+}
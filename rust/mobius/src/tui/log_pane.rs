@@ -0,0 +1,204 @@
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Widget};
+
+use crate::stream_json::{parse_log_events, LogEvent, LogEventKind};
+
+use super::theme::{BORDER_COLOR, HEADER_COLOR, MUTED_COLOR, NORD11, NORD14, NORD8, TEXT_COLOR};
+
+/// Parsed events kept per agent; older ones scroll away
+const MAX_LOG_EVENTS: usize = 500;
+
+/// Incremental reader for one agent's stream-json output file.
+///
+/// Each `poll` reads only the bytes appended since the last one and keeps a
+/// trailing partial line until the agent finishes writing it.
+#[derive(Debug, Default)]
+pub struct LogTail {
+    pub path: Option<PathBuf>,
+    pub events: Vec<LogEvent>,
+    offset: u64,
+    pending: String,
+}
+
+impl LogTail {
+    /// Point the tail at `path`, clearing events if it changed.
+    pub fn follow(&mut self, path: PathBuf) {
+        if self.path.as_deref() != Some(path.as_path()) {
+            *self = Self {
+                path: Some(path),
+                ..Self::default()
+            };
+        }
+    }
+
+    /// Read newly appended lines from the file, if any.
+    pub fn poll(&mut self) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        let Ok(mut file) = fs::File::open(&path) else {
+            return;
+        };
+        let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+        if len < self.offset {
+            // Truncated by a retry; start over
+            self.offset = 0;
+            self.pending.clear();
+            self.events.clear();
+        }
+        if len == self.offset {
+            return;
+        }
+        if file.seek(SeekFrom::Start(self.offset)).is_err() {
+            return;
+        }
+        let mut chunk = Vec::new();
+        if file.read_to_end(&mut chunk).is_err() {
+            return;
+        }
+        self.offset += chunk.len() as u64;
+        self.pending.push_str(&String::from_utf8_lossy(&chunk));
+
+        if let Some(idx) = self.pending.rfind('\n') {
+            let complete: String = self.pending.drain(..=idx).collect();
+            self.events
+                .extend(complete.lines().flat_map(parse_log_events));
+            let overflow = self.events.len().saturating_sub(MAX_LOG_EVENTS);
+            self.events.drain(..overflow);
+        }
+    }
+}
+
+/// Path of `identifier`'s output file in the loop's agent output directory.
+pub fn agent_log_path(output_dir: &Path, identifier: &str) -> PathBuf {
+    output_dir.join(format!("{}.jsonl", identifier))
+}
+
+pub struct LogPane<'a> {
+    /// Sub-task whose output is shown
+    pub task_id: Option<&'a str>,
+    pub events: &'a [LogEvent],
+    /// Position of `task_id` among active agents and their count, for the title
+    pub position: Option<(usize, usize)>,
+}
+
+impl Widget for LogPane<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = match (self.task_id, self.position) {
+            (Some(id), Some((index, total))) => {
+                format!(" Agent Log: {} ({}/{}) ", id, index + 1, total)
+            }
+            (Some(id), None) => format!(" Agent Log: {} ", id),
+            (None, _) => " Agent Log ".to_string(),
+        };
+        let hint = "[Tab] next  [l] close";
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(BORDER_COLOR))
+            .title(Span::styled(title, Style::default().fg(HEADER_COLOR)));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let hint_x = area.x + area.width.saturating_sub(hint.len() as u16 + 2);
+        buf.set_string(hint_x, area.y, hint, Style::default().fg(MUTED_COLOR));
+
+        if self.events.is_empty() {
+            let message = if self.task_id.is_some() {
+                "Waiting for agent output..."
+            } else {
+                "No agent selected"
+            };
+            buf.set_string(
+                inner.x + 1,
+                inner.y,
+                message,
+                Style::default().fg(MUTED_COLOR),
+            );
+            return;
+        }
+
+        // Newest events at the bottom; multi-line text shows its first line
+        let visible = inner.height as usize;
+        let start = self.events.len().saturating_sub(visible);
+        for (i, event) in self.events[start..].iter().enumerate() {
+            let line = event_line(event);
+            buf.set_line(
+                inner.x + 1,
+                inner.y + i as u16,
+                &line,
+                inner.width.saturating_sub(1),
+            );
+        }
+    }
+}
+
+fn event_line(event: &LogEvent) -> Line<'_> {
+    let text = event.text.lines().next().unwrap_or("");
+    let (prefix, style) = match event.kind {
+        LogEventKind::System => ("● ", Style::default().fg(MUTED_COLOR)),
+        LogEventKind::Text => ("", Style::default().fg(TEXT_COLOR)),
+        LogEventKind::ToolUse => ("→ ", Style::default().fg(NORD8)),
+        LogEventKind::ToolResult => ("  ", Style::default().fg(MUTED_COLOR)),
+        LogEventKind::ToolError => ("  ", Style::default().fg(NORD11)),
+        LogEventKind::Result => (
+            "✓ ",
+            Style::default().fg(NORD14).add_modifier(Modifier::BOLD),
+        ),
+        LogEventKind::ResultError => (
+            "✗ ",
+            Style::default().fg(NORD11).add_modifier(Modifier::BOLD),
+        ),
+    };
+    Line::from(vec![Span::styled(prefix, style), Span::styled(text, style)])
+}
+
+/// Log pane height (including borders)
+pub const LOG_PANE_HEIGHT: u16 = 12;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_log_tail_reads_appended_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = agent_log_path(dir.path(), "MOB-2");
+        let mut tail = LogTail::default();
+        tail.follow(path.clone());
+        tail.poll();
+        assert!(tail.events.is_empty());
+
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(
+            concat!(
+                r#"{"type":"system","subtype":"init","model":"opus"}"#,
+                "\n",
+                r#"{"type":"result","subtype":"success""#
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+        tail.poll();
+        assert_eq!(tail.events.len(), 1);
+
+        // The partial line completes on the next poll
+        file.write_all(b",\"is_error\":false}\n").unwrap();
+        tail.poll();
+        assert_eq!(tail.events.len(), 2);
+        assert_eq!(tail.events[1].kind, LogEventKind::Result);
+
+        // Following another file starts over
+        tail.follow(agent_log_path(dir.path(), "MOB-3"));
+        assert!(tail.events.is_empty());
+    }
+}
@@ -6,7 +6,13 @@ pub mod debug_panel;
 pub mod events;
 pub mod exit_modal;
 pub mod header;
+pub mod history;
 pub mod legend;
+pub mod log_pane;
+pub mod overview;
+pub mod replay;
+pub mod snapshot;
+pub mod task_detail;
 pub mod task_tree;
 pub mod theme;
 pub mod token_metrics;
@@ -0,0 +1,351 @@
+//! `mobius tui --all`: one row per parent issue with a running loop, with
+//! drill-down into that issue's dashboard.
+
+use std::fs;
+use std::io;
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Widget};
+use ratatui::Terminal;
+
+use crate::config::loader::read_config;
+use crate::config::paths::resolve_paths;
+use crate::context::{
+    get_mobius_base_path, get_pause_flag_path, get_runtime_path, is_process_running,
+    read_runtime_state, read_session,
+};
+use crate::cost::format_cost;
+use crate::local_state::{read_parent_spec, read_subtasks};
+use crate::types::context::RuntimeState;
+use crate::types::enums::SessionStatus;
+
+use super::dashboard::{run_issue_dashboard, DashboardOptions};
+use super::events::{EventHandler, TuiEvent};
+use super::theme::{
+    Theme, BORDER_COLOR, HEADER_COLOR, MUTED_COLOR, NORD0, NORD1, NORD11, NORD13, NORD14, NORD3,
+    NORD8, TEXT_COLOR,
+};
+
+/// Width of each row's progress bar
+const BAR_WIDTH: usize = 20;
+
+/// Title characters shown before truncation
+const TITLE_WIDTH: usize = 32;
+
+/// Progress of one parent issue's running loop
+#[derive(Debug, Clone, PartialEq)]
+pub struct IssueSummary {
+    pub parent_id: String,
+    pub title: String,
+    pub completed: usize,
+    pub failed: usize,
+    pub active: usize,
+    pub total: usize,
+    pub cost_usd: Option<f64>,
+    pub paused: bool,
+}
+
+impl IssueSummary {
+    /// Summarize `state`; `total_fallback` is used when the loop has not
+    /// recorded its task count yet.
+    pub fn from_state(
+        parent_id: &str,
+        title: String,
+        state: &RuntimeState,
+        total_fallback: usize,
+    ) -> Self {
+        let completed = state.completed_tasks.len();
+        Self {
+            parent_id: parent_id.to_string(),
+            title,
+            completed,
+            failed: state.failed_tasks.len(),
+            active: state.active_tasks.len(),
+            total: state
+                .total_tasks
+                .map_or(total_fallback, |t| t as usize)
+                .max(completed),
+            cost_usd: state.total_cost_usd,
+            paused: false,
+        }
+    }
+}
+
+/// Every issue under `.mobius/issues/` whose loop is still running,
+/// sorted by parent ID.
+pub fn discover_running_issues() -> Vec<IssueSummary> {
+    let Ok(entries) = fs::read_dir(get_mobius_base_path().join("issues")) else {
+        return Vec::new();
+    };
+    let mut ids: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter_map(|e| e.file_name().to_str().map(String::from))
+        .collect();
+    ids.sort();
+
+    ids.iter()
+        .filter_map(|id| {
+            let state = read_runtime_state(id)?;
+            let session_running = read_session(id)
+                .is_some_and(|s| matches!(s.status, SessionStatus::Active | SessionStatus::Paused));
+            if !session_running && !state.loop_pid.is_some_and(is_process_running) {
+                return None;
+            }
+            let title = read_parent_spec(id)
+                .map(|p| p.title)
+                .unwrap_or_else(|| state.parent_title.clone());
+            let mut summary = IssueSummary::from_state(id, title, &state, read_subtasks(id).len());
+            summary.paused = get_pause_flag_path(id).exists();
+            Some(summary)
+        })
+        .collect()
+}
+
+/// Run the overview until the user quits; Enter opens the selected issue's
+/// dashboard and quitting that returns here.
+pub fn run_overview(observe: bool) -> anyhow::Result<()> {
+    let config = read_config(&resolve_paths().config_path).unwrap_or_default();
+    let theme = Theme::from_config(&config.execution.tui.unwrap_or_default())
+        .map_err(|e| anyhow::anyhow!("Invalid execution.tui.colors: {}", e))?;
+
+    let events = EventHandler::new(None, None);
+    let mut selected = 0;
+    loop {
+        let Some(parent_id) = pick_issue(&events, &theme, &mut selected)? else {
+            return Ok(());
+        };
+
+        events.suspend_input(true);
+        let result = run_issue_dashboard(
+            &parent_id,
+            get_runtime_path(&parent_id),
+            DashboardOptions {
+                observe,
+                replay: false,
+                from_overview: true,
+            },
+        );
+        events.suspend_input(false);
+        // Drop ticks that piled up while the dashboard was open
+        while events.next(Duration::ZERO).is_some() {}
+        result?;
+    }
+}
+
+/// Show the overview until the user opens an issue (`Some`) or quits (`None`).
+fn pick_issue(
+    events: &EventHandler,
+    theme: &Theme,
+    selected: &mut usize,
+) -> anyhow::Result<Option<String>> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    terminal.clear()?;
+
+    let result = (|| -> anyhow::Result<Option<String>> {
+        let mut issues = discover_running_issues();
+        loop {
+            *selected = (*selected).min(issues.len().saturating_sub(1));
+            terminal.draw(|frame| {
+                let overview = Overview {
+                    issues: &issues,
+                    selected: *selected,
+                };
+                frame.render_widget(overview, frame.area());
+                theme.apply(frame.buffer_mut());
+            })?;
+
+            let Some(event) = events.next(Duration::from_millis(100)) else {
+                continue;
+            };
+            match event {
+                TuiEvent::Key(key) => match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(None);
+                    }
+                    KeyCode::Up | KeyCode::Char('k') => *selected = selected.saturating_sub(1),
+                    KeyCode::Down | KeyCode::Char('j') => *selected += 1,
+                    KeyCode::Enter => {
+                        if let Some(issue) = issues.get(*selected) {
+                            return Ok(Some(issue.parent_id.clone()));
+                        }
+                    }
+                    _ => {}
+                },
+                TuiEvent::Tick => issues = discover_running_issues(),
+                TuiEvent::StateFileChanged | TuiEvent::TodosChanged => {}
+            }
+        }
+    })();
+
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
+
+/// The overview table: one row per running loop.
+pub struct Overview<'a> {
+    pub issues: &'a [IssueSummary],
+    pub selected: usize,
+}
+
+impl Widget for Overview<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Block::default()
+            .style(Style::default().bg(NORD0))
+            .render(area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(BORDER_COLOR))
+            .title(Span::styled(
+                format!(" Running Loops ({}) ", self.issues.len()),
+                Style::default().fg(HEADER_COLOR),
+            ));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let hint = "[↑↓] select  [Enter] open  [q] quit";
+        let hint_x = area.x + area.width.saturating_sub(hint.chars().count() as u16 + 2);
+        buf.set_string(hint_x, area.y, hint, Style::default().fg(MUTED_COLOR));
+
+        if self.issues.is_empty() {
+            buf.set_string(
+                inner.x + 1,
+                inner.y,
+                "No running loops. Waiting for one to start...",
+                Style::default().fg(MUTED_COLOR),
+            );
+            return;
+        }
+
+        // Keep the selected row on screen
+        let visible = inner.height as usize;
+        let start = (self.selected + 1).saturating_sub(visible);
+        for (i, issue) in self.issues.iter().enumerate().skip(start).take(visible) {
+            let y = inner.y + (i - start) as u16;
+            let selected = i == self.selected;
+            if selected {
+                buf.set_style(
+                    Rect::new(inner.x, y, inner.width, 1),
+                    Style::default().bg(NORD1),
+                );
+            }
+            let line = issue_line(issue, selected);
+            buf.set_line(inner.x + 1, y, &line, inner.width.saturating_sub(1));
+        }
+    }
+}
+
+fn issue_line(issue: &IssueSummary, selected: bool) -> Line<'static> {
+    let filled = (issue.completed.min(issue.total) * BAR_WIDTH)
+        .checked_div(issue.total)
+        .unwrap_or(0);
+    let title: String = if issue.title.chars().count() > TITLE_WIDTH {
+        let cut: String = issue.title.chars().take(TITLE_WIDTH - 1).collect();
+        format!("{}…", cut)
+    } else {
+        issue.title.clone()
+    };
+    let id_style = if selected {
+        Style::default().fg(NORD8).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(NORD8)
+    };
+
+    let mut spans = vec![
+        Span::styled(if selected { "▶ " } else { "  " }, id_style),
+        Span::styled(format!("{:<12}", issue.parent_id), id_style),
+        Span::styled(
+            format!("{:<width$}  ", title, width = TITLE_WIDTH),
+            Style::default().fg(TEXT_COLOR),
+        ),
+        Span::styled("█".repeat(filled), Style::default().fg(NORD14)),
+        Span::styled("░".repeat(BAR_WIDTH - filled), Style::default().fg(NORD3)),
+        Span::styled(
+            format!(" {:>3}/{:<3}", issue.completed, issue.total),
+            Style::default().fg(TEXT_COLOR),
+        ),
+        Span::styled(
+            format!("  ● {} running", issue.active),
+            Style::default().fg(if issue.active > 0 {
+                NORD13
+            } else {
+                MUTED_COLOR
+            }),
+        ),
+    ];
+    if issue.failed > 0 {
+        spans.push(Span::styled(
+            format!("  ✗ {} failed", issue.failed),
+            Style::default().fg(NORD11),
+        ));
+    }
+    if let Some(cost) = issue.cost_usd {
+        spans.push(Span::styled(
+            format!("  {}", format_cost(cost)),
+            Style::default().fg(MUTED_COLOR),
+        ));
+    }
+    if issue.paused {
+        spans.push(Span::styled("  PAUSED", Style::default().fg(NORD13)));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::snapshot::buffer_to_text;
+
+    fn summary(parent_id: &str, completed: usize, total: usize) -> IssueSummary {
+        IssueSummary {
+            parent_id: parent_id.to_string(),
+            title: format!("Parent {}", parent_id),
+            completed,
+            failed: 0,
+            active: 1,
+            total,
+            cost_usd: None,
+            paused: false,
+        }
+    }
+
+    #[test]
+    fn test_overview_rows_show_progress_and_selection() {
+        let mut failing = summary("MOB-2", 0, 4);
+        failing.failed = 1;
+        failing.paused = true;
+        let issues = vec![summary("MOB-1", 5, 10), failing];
+
+        let area = Rect::new(0, 0, 120, 4);
+        let mut buf = Buffer::empty(area);
+        Overview {
+            issues: &issues,
+            selected: 1,
+        }
+        .render(area, &mut buf);
+        let text = buffer_to_text(&buf);
+
+        assert!(text.contains("Running Loops (2)"));
+        assert!(text.contains("  MOB-1       Parent MOB-1"));
+        assert!(text.contains("██████████░░░░░░░░░░   5/10"));
+        assert!(text.contains("▶ MOB-2"));
+        assert!(text.contains("✗ 1 failed  PAUSED"));
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashSet;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Widget};
+
+use crate::context::get_completed_task_id;
+use crate::types::context::{RuntimeJournalEntry, RuntimeState};
+
+use super::theme::{BORDER_COLOR, HEADER_COLOR, MUTED_COLOR, NORD13, TEXT_COLOR};
+
+/// Height of the replay bar including borders
+pub const REPLAY_BAR_HEIGHT: u16 = 4;
+
+/// Cursor over the recorded runtime.json revisions of one run.
+#[derive(Debug)]
+pub struct Replay {
+    entries: Vec<RuntimeJournalEntry>,
+    position: usize,
+}
+
+impl Replay {
+    /// Start at the first revision. Returns `None` for an empty journal.
+    pub fn new(entries: Vec<RuntimeJournalEntry>) -> Option<Self> {
+        (!entries.is_empty()).then_some(Self {
+            entries,
+            position: 0,
+        })
+    }
+
+    pub fn current(&self) -> &RuntimeJournalEntry {
+        &self.entries[self.position]
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Number of recorded revisions
+    pub fn revisions(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Move by `delta` revisions, stopping at either end.
+    pub fn step(&mut self, delta: isize) {
+        let last = self.entries.len() - 1;
+        self.position = self.position.saturating_add_signed(delta).min(last);
+    }
+
+    /// Jump to the first or last revision.
+    pub fn jump(&mut self, to_end: bool) {
+        self.position = if to_end { self.entries.len() - 1 } else { 0 };
+    }
+
+    /// What changed between the previous revision and the current one.
+    pub fn changes(&self) -> Vec<String> {
+        let before = self.position.checked_sub(1).map(|i| &self.entries[i].state);
+        describe_changes(before, &self.current().state)
+    }
+}
+
+fn task_ids(entries: &[serde_json::Value]) -> HashSet<String> {
+    entries.iter().map(get_completed_task_id).collect()
+}
+
+/// Summarize the transition from `before` to `after` as short phrases.
+pub fn describe_changes(before: Option<&RuntimeState>, after: &RuntimeState) -> Vec<String> {
+    let Some(before) = before else {
+        return vec![match after.total_tasks {
+            Some(total) => format!("run started ({} tasks)", total),
+            None => "run started".to_string(),
+        }];
+    };
+
+    let mut changes = Vec::new();
+    let was_active: HashSet<&str> = before.active_tasks.iter().map(|t| t.id.as_str()).collect();
+    for task in &after.active_tasks {
+        if !was_active.contains(task.id.as_str()) {
+            changes.push(format!("started {}", task.id));
+        }
+    }
+
+    let finished = [
+        ("completed", &before.completed_tasks, &after.completed_tasks),
+        ("failed", &before.failed_tasks, &after.failed_tasks),
+        ("cancelled", &before.cancelled_tasks, &after.cancelled_tasks),
+    ];
+    let mut settled = HashSet::new();
+    for (label, old, new) in finished {
+        let old = task_ids(old);
+        let mut added: Vec<String> = task_ids(new).difference(&old).cloned().collect();
+        added.sort();
+        for id in added {
+            changes.push(format!("{} {}", label, id));
+            settled.insert(id);
+        }
+    }
+
+    let is_active: HashSet<&str> = after.active_tasks.iter().map(|t| t.id.as_str()).collect();
+    let mut dropped: Vec<&str> = was_active
+        .difference(&is_active)
+        .filter(|id| !settled.contains(**id))
+        .copied()
+        .collect();
+    dropped.sort();
+    for id in dropped {
+        changes.push(format!("dropped {}", id));
+    }
+
+    let tokens =
+        |s: &RuntimeState| s.total_input_tokens.unwrap_or(0) + s.total_output_tokens.unwrap_or(0);
+    if tokens(after) > tokens(before) {
+        changes.push(format!("+{} tokens", tokens(after) - tokens(before)));
+    }
+
+    if let Some(statuses) = &after.backend_statuses {
+        let mut moved: Vec<String> = statuses
+            .iter()
+            .filter(|(id, entry)| {
+                before
+                    .backend_statuses
+                    .as_ref()
+                    .and_then(|old| old.get(*id))
+                    .map_or(true, |old| old.status != entry.status)
+            })
+            .map(|(id, entry)| format!("{} → {}", id, entry.status))
+            .collect();
+        moved.sort();
+        changes.extend(moved);
+    }
+
+    if changes.is_empty() {
+        changes.push("no task-level change".to_string());
+    }
+    changes
+}
+
+/// Bar showing the replay position and the current revision's changes.
+pub struct ReplayBar<'a> {
+    pub replay: &'a Replay,
+}
+
+impl Widget for ReplayBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let entry = self.replay.current();
+        let time = chrono::DateTime::parse_from_rfc3339(&entry.recorded_at)
+            .map(|t| t.format("%H:%M:%S").to_string())
+            .unwrap_or_else(|_| entry.recorded_at.clone());
+        let title = format!(
+            " Replay {}/{} @ {} ",
+            self.replay.position() + 1,
+            self.replay.revisions(),
+            time
+        );
+        let hint = "[←/→] step  [Home/End] jump";
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(BORDER_COLOR))
+            .title(Span::styled(title, Style::default().fg(HEADER_COLOR)));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let hint_x = area.x + area.width.saturating_sub(hint.chars().count() as u16 + 2);
+        buf.set_string(hint_x, area.y, hint, Style::default().fg(MUTED_COLOR));
+
+        let changes = self.replay.changes().join("  ·  ");
+        let lines = [
+            Line::from(Span::styled(changes, Style::default().fg(NORD13))),
+            Line::from(Span::styled(
+                format!(
+                    "{} active, {} completed, {} failed",
+                    entry.state.active_tasks.len(),
+                    entry.state.completed_tasks.len(),
+                    entry.state.failed_tasks.len()
+                ),
+                Style::default().fg(TEXT_COLOR),
+            )),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            if i as u16 >= inner.height {
+                break;
+            }
+            buf.set_line(inner.x + 1, inner.y + i as u16, line, inner.width);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::context::RuntimeActiveTask;
+
+    fn state(active: &[&str], completed: &[&str], tokens: u64) -> RuntimeState {
+        RuntimeState {
+            parent_id: "MOB-1".to_string(),
+            parent_title: "Replay".to_string(),
+            active_tasks: active
+                .iter()
+                .map(|id| RuntimeActiveTask {
+                    id: id.to_string(),
+                    pid: 1,
+                    pane: "%1".to_string(),
+                    started_at: "2026-10-16T10:00:00Z".to_string(),
+                    worktree: None,
+                    model: None,
+                    input_tokens: None,
+                    output_tokens: None,
+                    retry_count: None,
+                    worker: None,
+                })
+                .collect(),
+            completed_tasks: completed.iter().map(|id| serde_json::json!(id)).collect(),
+            failed_tasks: vec![],
+            cancelled_tasks: vec![],
+            started_at: "2026-10-16T10:00:00Z".to_string(),
+            updated_at: "2026-10-16T10:00:00Z".to_string(),
+            loop_pid: None,
+            total_tasks: Some(2),
+            backend_statuses: None,
+            total_input_tokens: Some(tokens),
+            total_output_tokens: None,
+            task_costs_usd: Default::default(),
+            total_cost_usd: None,
+        }
+    }
+
+    fn entry(state: RuntimeState) -> RuntimeJournalEntry {
+        RuntimeJournalEntry {
+            recorded_at: "2026-10-16T10:00:00Z".to_string(),
+            state,
+        }
+    }
+
+    #[test]
+    fn replay_steps_and_describes_transitions() {
+        let mut replay = Replay::new(vec![
+            entry(state(&[], &[], 0)),
+            entry(state(&["MOB-2", "MOB-3"], &[], 0)),
+            entry(state(&[], &["MOB-2"], 1500)),
+        ])
+        .unwrap();
+        assert_eq!(replay.changes(), vec!["run started (2 tasks)"]);
+
+        replay.step(-1);
+        assert_eq!(replay.position(), 0);
+        replay.step(1);
+        assert_eq!(replay.changes(), vec!["started MOB-2", "started MOB-3"]);
+
+        replay.step(5);
+        assert_eq!(replay.position(), 2);
+        assert_eq!(
+            replay.changes(),
+            vec!["completed MOB-2", "dropped MOB-3", "+1500 tokens"]
+        );
+
+        replay.jump(false);
+        assert_eq!(replay.position(), 0);
+        assert!(Replay::new(Vec::new()).is_none());
+    }
+}
@@ -0,0 +1,345 @@
+//! Deterministic text rendering of the dashboard for golden-file tests.
+//!
+//! Renders an `App` into an off-screen buffer with a pinned `RenderClock`
+//! and flattens it to plain text (styles dropped, trailing spaces trimmed),
+//! so widget changes can be reviewed as snapshot diffs.
+
+use std::path::PathBuf;
+
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
+
+use crate::types::context::RuntimeState;
+use crate::types::task_graph::TaskGraph;
+
+use super::app::App;
+use super::dashboard::{render_dashboard, RenderClock};
+
+/// Default snapshot frame width
+pub const SNAPSHOT_WIDTH: u16 = 100;
+
+/// Default snapshot frame height
+pub const SNAPSHOT_HEIGHT: u16 = 40;
+
+/// Build a dashboard `App` from a task graph and optional runtime state.
+///
+/// No state file is read; completion is derived from `runtime_state` alone.
+pub fn app_from_state(
+    parent_id: &str,
+    parent_title: &str,
+    graph: TaskGraph,
+    runtime_state: Option<RuntimeState>,
+    max_parallel_agents: usize,
+) -> App {
+    let mut app = App::new(
+        parent_id.to_string(),
+        parent_title.to_string(),
+        graph,
+        PathBuf::new(),
+        max_parallel_agents,
+    );
+    if let Some(state) = runtime_state {
+        app.set_runtime_state(state);
+    }
+    app
+}
+
+/// Render the dashboard for `app` into a `width` x `height` text frame.
+pub fn render_app_to_text(app: &App, clock: RenderClock, width: u16, height: u16) -> String {
+    let mut terminal =
+        Terminal::new(TestBackend::new(width, height)).expect("test backend never fails");
+    terminal
+        .draw(|frame| render_dashboard(frame, app, clock))
+        .expect("test backend never fails");
+    buffer_to_text(terminal.backend().buffer())
+}
+
+/// Flatten a buffer to newline-separated rows of cell symbols.
+pub fn buffer_to_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let mut row = String::new();
+        for x in area.left()..area.right() {
+            row.push_str(buffer[(x, y)].symbol());
+        }
+        text.push_str(row.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Compare `actual` against the golden file `src/tui/snapshots/{name}.snap`.
+///
+/// Set `MOBIUS_UPDATE_SNAPSHOTS=1` to (re)write golden files instead of
+/// asserting.
+#[cfg(test)]
+pub(crate) fn assert_snapshot(name: &str, actual: &str) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/tui/snapshots")
+        .join(format!("{name}.snap"));
+
+    if std::env::var("MOBIUS_UPDATE_SNAPSHOTS").is_ok_and(|v| v == "1") {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "Missing snapshot {}. Run with MOBIUS_UPDATE_SNAPSHOTS=1 to create it.\n\n{}",
+            path.display(),
+            actual
+        )
+    });
+
+    if expected != actual {
+        let mut diff = String::new();
+        let expected_lines: Vec<&str> = expected.lines().collect();
+        let actual_lines: Vec<&str> = actual.lines().collect();
+        for i in 0..expected_lines.len().max(actual_lines.len()) {
+            let e = expected_lines.get(i).copied().unwrap_or("");
+            let a = actual_lines.get(i).copied().unwrap_or("");
+            if e != a {
+                diff.push_str(&format!("{:>3} - {}\n{:>3} + {}\n", i + 1, e, i + 1, a));
+            }
+        }
+        panic!(
+            "Snapshot {} does not match. Run with MOBIUS_UPDATE_SNAPSHOTS=1 to accept.\n\n{}",
+            path.display(),
+            diff
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    use crate::types::context::RuntimeActiveTask;
+    use crate::types::enums::{TaskStatus, TaskType};
+    use crate::types::task_graph::{build_task_graph, LinearIssue, Relation, Relations};
+
+    fn clock() -> RenderClock {
+        RenderClock {
+            now: DateTime::parse_from_rfc3339("2026-01-01T00:10:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            elapsed_ms: 10 * 60 * 1000,
+        }
+    }
+
+    fn issue(identifier: &str, title: &str, status: &str, blocked_by: &[&str]) -> LinearIssue {
+        LinearIssue {
+            id: identifier.to_string(),
+            identifier: identifier.to_string(),
+            title: title.to_string(),
+            status: status.to_string(),
+            git_branch_name: String::new(),
+            relations: Some(Relations {
+                blocked_by: blocked_by
+                    .iter()
+                    .map(|id| Relation {
+                        id: id.to_string(),
+                        identifier: id.to_string(),
+                    })
+                    .collect(),
+                blocks: vec![],
+            }),
+            scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
+        }
+    }
+
+    fn graph() -> TaskGraph {
+        build_task_graph(
+            "MOB-100",
+            "MOB-100",
+            &[
+                issue("MOB-101", "Add schema", "Done", &[]),
+                issue("MOB-102", "Wire API client", "Todo", &["MOB-101"]),
+                issue("MOB-103", "Render list view", "Todo", &["MOB-101"]),
+                issue(
+                    "MOB-104",
+                    "Verification Gate",
+                    "Todo",
+                    &["MOB-102", "MOB-103"],
+                ),
+            ],
+        )
+    }
+
+    fn runtime_state() -> RuntimeState {
+        RuntimeState {
+            parent_id: "MOB-100".to_string(),
+            parent_title: "Task list feature".to_string(),
+            active_tasks: vec![RuntimeActiveTask {
+                id: "MOB-102".to_string(),
+                pid: 0,
+                pane: String::new(),
+                started_at: "2026-01-01T00:07:30Z".to_string(),
+                worktree: None,
+                model: Some("sonnet".to_string()),
+                input_tokens: Some(1200),
+                output_tokens: Some(300),
+                retry_count: Some(1),
+                worker: None,
+            }],
+            completed_tasks: vec![serde_json::json!({
+                "id": "MOB-101",
+                "completedAt": "2026-01-01T00:05:00Z",
+                "duration": 125000
+            })],
+            failed_tasks: vec![],
+            started_at: "2026-01-01T00:00:00Z".to_string(),
+            updated_at: "2026-01-01T00:09:00Z".to_string(),
+            loop_pid: None,
+            total_tasks: Some(4),
+            backend_statuses: None,
+            total_input_tokens: Some(5400),
+            total_output_tokens: Some(1800),
+            task_costs_usd: [("MOB-101".to_string(), 0.0432)].into_iter().collect(),
+            total_cost_usd: Some(0.0432),
+            cancelled_tasks: vec![],
+        }
+    }
+
+    #[test]
+    fn test_render_is_deterministic() {
+        let app = app_from_state("MOB-100", "Task list feature", graph(), None, 3);
+        let first = render_app_to_text(&app, clock(), SNAPSHOT_WIDTH, SNAPSHOT_HEIGHT);
+        let second = render_app_to_text(&app, clock(), SNAPSHOT_WIDTH, SNAPSHOT_HEIGHT);
+        assert_eq!(first, second);
+        assert_eq!(first.lines().count(), SNAPSHOT_HEIGHT as usize);
+    }
+
+    #[test]
+    fn test_buffer_to_text_trims_trailing_spaces() {
+        let mut buffer = Buffer::empty(ratatui::layout::Rect::new(0, 0, 5, 2));
+        buffer.set_string(0, 0, "ab", ratatui::style::Style::default());
+        assert_eq!(buffer_to_text(&buffer), "ab\n\n");
+    }
+
+    #[test]
+    fn test_snapshot_dashboard_waiting() {
+        let app = app_from_state("MOB-100", "Task list feature", graph(), None, 3);
+        let text = render_app_to_text(&app, clock(), SNAPSHOT_WIDTH, SNAPSHOT_HEIGHT);
+        assert_snapshot("dashboard_waiting", &text);
+    }
+
+    #[test]
+    fn test_snapshot_dashboard_running() {
+        let app = app_from_state(
+            "MOB-100",
+            "Task list feature",
+            graph(),
+            Some(runtime_state()),
+            3,
+        );
+        let text = render_app_to_text(&app, clock(), SNAPSHOT_WIDTH, SNAPSHOT_HEIGHT);
+        assert_snapshot("dashboard_running", &text);
+    }
+
+    #[test]
+    fn test_snapshot_dashboard_complete() {
+        let mut state = runtime_state();
+        state.active_tasks.clear();
+        state.completed_tasks = ["MOB-101", "MOB-102", "MOB-103", "MOB-104"]
+            .iter()
+            .map(|id| serde_json::json!({ "id": id, "completedAt": "", "duration": 60000 }))
+            .collect();
+        let app = app_from_state("MOB-100", "Task list feature", graph(), Some(state), 3);
+        assert!(app.is_complete);
+
+        let text = render_app_to_text(&app, clock(), SNAPSHOT_WIDTH, SNAPSHOT_HEIGHT);
+        assert_snapshot("dashboard_complete", &text);
+    }
+
+    #[test]
+    fn test_snapshot_dashboard_log_pane() {
+        let mut app = app_from_state(
+            "MOB-100",
+            "Task list feature",
+            graph(),
+            Some(runtime_state()),
+            3,
+        );
+        app.show_log = true;
+        app.agent_log.events = [
+            r#"{"type":"system","subtype":"init","model":"sonnet"}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Wiring the client"},{"type":"tool_use","name":"Read","input":{"file_path":"src/api.rs"}}]}}"#,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","content":"pub mod api;\nfn main() {}"}]}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}"#,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","is_error":true,"content":"error[E0425]: cannot find value"}]}}"#,
+        ]
+        .iter()
+        .flat_map(|line| crate::stream_json::parse_log_events(line))
+        .collect();
+        assert_eq!(app.selected_agent_id(), Some("MOB-102"));
+
+        let text = render_app_to_text(&app, clock(), SNAPSHOT_WIDTH, SNAPSHOT_HEIGHT);
+        assert_snapshot("dashboard_log_pane", &text);
+    }
+
+    #[test]
+    fn test_snapshot_dashboard_task_detail() {
+        use super::super::task_detail::TaskDetail;
+        use crate::local_state::IterationLogEntry;
+        use crate::types::context::SubTaskContext;
+
+        let mut app = app_from_state(
+            "MOB-100",
+            "Task list feature",
+            graph(),
+            Some(runtime_state()),
+            3,
+        );
+        app.selected_task = Some("MOB-102".to_string());
+        let spec: SubTaskContext = serde_json::from_value(serde_json::json!({
+            "id": "MOB-102",
+            "identifier": "MOB-102",
+            "title": "Wire API client",
+            "status": "Todo",
+            "description": "Call the list endpoint and map responses into the schema types.\n\n### Verify Command\n```bash\ncargo test -p api\n```"
+        }))
+        .unwrap();
+        let log: Vec<IterationLogEntry> = serde_json::from_value(serde_json::json!([{
+            "subtaskId": "MOB-102",
+            "attempt": 1,
+            "startedAt": "2026-01-01T00:05:00Z",
+            "status": "failed",
+            "error": "Verification failed: 2 tests failed\nassertion `left == right` failed",
+            "failureClass": "verify_failure",
+            "model": "sonnet",
+            "durationMs": 140000,
+            "inputTokens": 4000,
+            "outputTokens": 900
+        }]))
+        .unwrap();
+        let task = app.graph.tasks["MOB-102"].clone();
+        app.detail = Some(TaskDetail::build(
+            &task,
+            &app.graph,
+            &app.status_overrides(),
+            Some(&spec),
+            &log,
+            app.runtime_state.as_ref(),
+        ));
+
+        let detail = app.detail.as_ref().unwrap();
+        assert_eq!(detail.verify_command.as_deref(), Some("cargo test -p api"));
+        assert_eq!(detail.tokens, Some((5200, 1200)));
+        assert_eq!(
+            detail.blockers,
+            vec![("MOB-101".to_string(), TaskStatus::Done)]
+        );
+
+        let text = render_app_to_text(&app, clock(), SNAPSHOT_WIDTH, SNAPSHOT_HEIGHT);
+        assert_snapshot("dashboard_task_detail", &text);
+    }
+}
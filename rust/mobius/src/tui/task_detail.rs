@@ -0,0 +1,363 @@
+use std::collections::HashMap;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Widget};
+
+use crate::context::extract_verify_commands;
+use crate::cost::format_cost;
+use crate::local_state::{IterationLogEntry, IterationStatus};
+use crate::types::context::{RuntimeState, SubTaskContext};
+use crate::types::enums::TaskStatus;
+use crate::types::task_graph::{SubTask, TaskGraph};
+
+use super::header::format_duration;
+use super::theme::{
+    format_token_pair, model_color, status_color, status_icon, BORDER_COLOR, HEADER_COLOR,
+    MUTED_COLOR, NORD0, NORD11, NORD13, NORD14, NORD8, TEXT_COLOR,
+};
+
+/// Description lines shown before the rest is cut off
+const DESCRIPTION_LINES: usize = 4;
+
+/// Error lines shown for the last failure
+const ERROR_LINES: usize = 3;
+
+/// Everything the detail panel shows for one sub-task.
+///
+/// Built from the graph, the local spec, the iteration log, and runtime
+/// state, so rendering never touches the disk.
+#[derive(Debug, Clone, Default)]
+pub struct TaskDetail {
+    pub identifier: String,
+    pub title: String,
+    pub status: Option<TaskStatus>,
+    /// Description up to its first `###` section
+    pub description: String,
+    pub verify_command: Option<String>,
+    /// Blocking tasks with their current status
+    pub blockers: Vec<(String, TaskStatus)>,
+    /// Tasks this one blocks, with their current status
+    pub downstream: Vec<(String, TaskStatus)>,
+    pub attempts: Vec<IterationLogEntry>,
+    /// Input and output tokens across recorded attempts and the running one
+    pub tokens: Option<(u64, u64)>,
+    pub cost_usd: Option<f64>,
+    pub last_error: Option<String>,
+}
+
+impl TaskDetail {
+    /// Collect the detail for `task` from already-loaded state.
+    pub fn build(
+        task: &SubTask,
+        graph: &TaskGraph,
+        statuses: &HashMap<String, TaskStatus>,
+        spec: Option<&SubTaskContext>,
+        log: &[IterationLogEntry],
+        runtime: Option<&RuntimeState>,
+    ) -> Self {
+        let status_of = |t: &SubTask| statuses.get(&t.id).copied().unwrap_or(t.status);
+        let related = |ids: &[String]| -> Vec<(String, TaskStatus)> {
+            ids.iter()
+                .map(|id| match graph.tasks.get(id) {
+                    Some(t) => (t.identifier.clone(), status_of(t)),
+                    None => (id.clone(), TaskStatus::Pending),
+                })
+                .collect()
+        };
+
+        let attempts: Vec<IterationLogEntry> = log
+            .iter()
+            .filter(|e| e.subtask_id == task.identifier || e.subtask_id == task.id)
+            .cloned()
+            .collect();
+
+        let mut tokens = None;
+        for (input, output) in attempts
+            .iter()
+            .filter(|e| e.input_tokens.is_some() || e.output_tokens.is_some())
+            .map(|e| (e.input_tokens.unwrap_or(0), e.output_tokens.unwrap_or(0)))
+            .chain(
+                runtime
+                    .and_then(|s| s.active_tasks.iter().find(|t| t.id == task.id))
+                    .filter(|t| t.input_tokens.is_some() || t.output_tokens.is_some())
+                    .map(|t| (t.input_tokens.unwrap_or(0), t.output_tokens.unwrap_or(0))),
+            )
+        {
+            let (total_in, total_out) = tokens.get_or_insert((0, 0));
+            *total_in += input;
+            *total_out += output;
+        }
+
+        let cost_usd = runtime
+            .and_then(|s| s.task_costs_usd.get(&task.identifier).copied())
+            .or_else(|| {
+                let costs: Vec<f64> = attempts.iter().filter_map(|e| e.cost_usd).collect();
+                (!costs.is_empty()).then(|| costs.iter().sum())
+            });
+
+        let description = spec
+            .map(|s| s.description.split("\n###").next().unwrap_or("").trim())
+            .unwrap_or("")
+            .to_string();
+        let verify_command = spec.and_then(|s| {
+            extract_verify_commands(std::slice::from_ref(s))
+                .pop()
+                .map(|v| v.command)
+        });
+
+        let last_error = attempts
+            .iter()
+            .rev()
+            .find(|e| e.status != IterationStatus::Success)
+            .and_then(|e| e.error.clone());
+
+        Self {
+            identifier: task.identifier.clone(),
+            title: task.title.clone(),
+            status: Some(status_of(task)),
+            description,
+            verify_command,
+            blockers: related(&task.blocked_by),
+            downstream: related(&task.blocks),
+            attempts,
+            tokens,
+            cost_usd,
+            last_error,
+        }
+    }
+}
+
+/// Centered overlay showing one sub-task's detail.
+pub struct TaskDetailPanel<'a> {
+    pub detail: &'a TaskDetail,
+    /// Hide the action keys when observing or replaying
+    pub read_only: bool,
+}
+
+impl Widget for TaskDetailPanel<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let detail = self.detail;
+        let width = area.width.saturating_sub(8).min(96);
+        let lines = detail_lines(detail, width.saturating_sub(4) as usize);
+        let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+
+        let x = area.x + area.width.saturating_sub(width) / 2;
+        let y = area.y + area.height.saturating_sub(height) / 2;
+        let panel_area = Rect::new(x, y, width, height);
+
+        Clear.render(panel_area, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(BORDER_COLOR))
+            .style(Style::default().bg(NORD0))
+            .title(Span::styled(
+                format!(" {} ", detail.identifier),
+                Style::default().fg(HEADER_COLOR),
+            ));
+        let inner = block.inner(panel_area);
+        block.render(panel_area, buf);
+
+        let hint = if self.read_only {
+            "[↑↓] prev/next  [a]ttach  [Esc] close"
+        } else {
+            "[↑↓] prev/next  [r]etry [s]kip [p]riority [a]ttach  [Esc] close"
+        };
+        let hint_x = panel_area.x
+            + panel_area
+                .width
+                .saturating_sub(hint.chars().count() as u16 + 2);
+        buf.set_string(hint_x, panel_area.y, hint, Style::default().fg(MUTED_COLOR));
+
+        for (i, line) in lines.iter().enumerate() {
+            if i as u16 >= inner.height {
+                break;
+            }
+            buf.set_line(
+                inner.x + 1,
+                inner.y + i as u16,
+                line,
+                inner.width.saturating_sub(1),
+            );
+        }
+    }
+}
+
+fn detail_lines(detail: &TaskDetail, width: usize) -> Vec<Line<'static>> {
+    let label =
+        |text: &str| Span::styled(format!("{:<12}", text), Style::default().fg(MUTED_COLOR));
+    let text = |text: String| Span::styled(text, Style::default().fg(TEXT_COLOR));
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default()
+                .fg(HEADER_COLOR)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+
+    let mut lines = Vec::new();
+    let mut title = vec![Span::styled(
+        detail.title.clone(),
+        Style::default().fg(TEXT_COLOR).add_modifier(Modifier::BOLD),
+    )];
+    if let Some(status) = detail.status {
+        title.push(Span::styled(
+            format!("  {} {}", status_icon(status), status),
+            Style::default().fg(status_color(status)),
+        ));
+    }
+    lines.push(Line::from(title));
+    lines.push(Line::raw(""));
+
+    if !detail.description.is_empty() {
+        let wrapped = wrap(&detail.description, width);
+        let cut = wrapped.len() > DESCRIPTION_LINES;
+        for (i, row) in wrapped.into_iter().take(DESCRIPTION_LINES).enumerate() {
+            let row = if cut && i == DESCRIPTION_LINES - 1 {
+                format!("{}…", row)
+            } else {
+                row
+            };
+            lines.push(Line::from(text(row)));
+        }
+        lines.push(Line::raw(""));
+    }
+
+    lines.push(Line::from(vec![
+        label("Verify"),
+        match &detail.verify_command {
+            Some(command) => Span::styled(
+                command.lines().collect::<Vec<_>>().join(" && "),
+                Style::default().fg(NORD8),
+            ),
+            None => Span::styled("none", Style::default().fg(MUTED_COLOR)),
+        },
+    ]));
+    lines.push(relation_line(label("Blocked by"), &detail.blockers));
+    lines.push(relation_line(label("Blocks"), &detail.downstream));
+
+    let usage = match (detail.tokens, detail.cost_usd) {
+        (Some((input, output)), Some(cost)) => {
+            format!(
+                "{} · {}",
+                format_token_pair(input, output),
+                format_cost(cost)
+            )
+        }
+        (Some((input, output)), None) => format_token_pair(input, output),
+        (None, Some(cost)) => format_cost(cost),
+        (None, None) => "none recorded".to_string(),
+    };
+    lines.push(Line::from(vec![label("Tokens"), text(usage)]));
+    lines.push(Line::raw(""));
+
+    lines.push(heading("Attempts"));
+    if detail.attempts.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No attempts yet",
+            Style::default().fg(MUTED_COLOR),
+        )));
+    }
+    for entry in &detail.attempts {
+        let (outcome, color) = match entry.status {
+            IterationStatus::Success => ("success", NORD14),
+            IterationStatus::Failed => ("failed", NORD11),
+            IterationStatus::Partial => ("partial", NORD13),
+        };
+        let mut spans = vec![
+            Span::styled(
+                format!("  #{:<3}", entry.attempt),
+                Style::default().fg(MUTED_COLOR),
+            ),
+            Span::styled(format!("{:<9}", outcome), Style::default().fg(color)),
+        ];
+        if let Some(model) = &entry.model {
+            spans.push(Span::styled(
+                format!("{:<8}", model),
+                Style::default().fg(model_color(model)),
+            ));
+        }
+        if let Some(ms) = entry.duration_ms {
+            spans.push(text(format!("{:<9}", format_duration(ms))));
+        }
+        if let Some(class) = &entry.failure_class {
+            spans.push(Span::styled(
+                class.to_string(),
+                Style::default().fg(MUTED_COLOR),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    if let Some(error) = &detail.last_error {
+        lines.push(Line::raw(""));
+        lines.push(heading("Last error"));
+        for row in error
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .take(ERROR_LINES)
+        {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", row.trim_end()),
+                Style::default().fg(NORD11),
+            )));
+        }
+    }
+
+    lines
+}
+
+fn relation_line(label: Span<'static>, related: &[(String, TaskStatus)]) -> Line<'static> {
+    let mut spans = vec![label];
+    if related.is_empty() {
+        spans.push(Span::styled("none", Style::default().fg(MUTED_COLOR)));
+    }
+    for (i, (identifier, status)) in related.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled("  ", Style::default()));
+        }
+        spans.push(Span::styled(
+            format!("{} {}", status_icon(*status), identifier),
+            Style::default().fg(status_color(*status)),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Greedy word wrap; paragraphs are joined and blank lines dropped.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(10);
+    let mut rows = Vec::new();
+    let mut row = String::new();
+    for word in text.split_whitespace() {
+        if !row.is_empty() && row.chars().count() + 1 + word.chars().count() > width {
+            rows.push(std::mem::take(&mut row));
+        }
+        if !row.is_empty() {
+            row.push(' ');
+        }
+        row.push_str(word);
+    }
+    if !row.is_empty() {
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_breaks_on_words() {
+        assert_eq!(
+            wrap("Add the   schema\n\nfor tasks and lists", 16),
+            vec!["Add the schema", "for tasks and", "lists"]
+        );
+    }
+}
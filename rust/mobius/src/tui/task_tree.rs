@@ -6,15 +6,19 @@ use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Widget;
 
-use crate::types::enums::TaskStatus;
+use crate::types::enums::{TaskStatus, TaskType};
 use crate::types::task_graph::{SubTask, TaskGraph};
 
 use super::header::format_duration;
-use super::theme::{status_color, status_icon, MUTED_COLOR, TEXT_COLOR};
+use super::theme::{
+    format_tokens, status_color, status_icon, MUTED_COLOR, NORD1, NORD12, TEXT_COLOR,
+};
 
-/// Information about a completed task's timing.
+/// Information about a completed task's timing and usage.
 pub struct CompletedInfo {
     pub duration: u64,
+    /// Input plus output tokens, when recorded
+    pub tokens: Option<u64>,
 }
 
 /// Widget that renders the task dependency tree.
@@ -23,6 +27,8 @@ pub struct TaskTreeWidget<'a> {
     pub status_overrides: &'a HashMap<String, TaskStatus>,
     pub active_elapsed: &'a HashMap<String, u64>,
     pub completed_info: &'a HashMap<String, CompletedInfo>,
+    /// Task highlighted for the detail panel
+    pub selected: Option<&'a str>,
 }
 
 /// Context for recursive tree rendering, bundled to reduce argument count.
@@ -84,10 +90,15 @@ fn render_task_node(
 
     // Build runtime suffix
     let runtime_suffix = if let Some(info) = ctx.widget.completed_info.get(&task.id) {
-        if info.duration > 0 {
-            format!(" ({})", format_duration(info.duration))
-        } else {
-            String::new()
+        match (info.duration > 0, info.tokens) {
+            (true, Some(tokens)) => format!(
+                " ({}, {} tokens)",
+                format_duration(info.duration),
+                format_tokens(tokens)
+            ),
+            (true, None) => format!(" ({})", format_duration(info.duration)),
+            (false, Some(tokens)) => format!(" ({} tokens)", format_tokens(tokens)),
+            (false, None) => String::new(),
         }
     } else if let Some(&elapsed) = ctx.widget.active_elapsed.get(&task.id) {
         format!(" ({}...)", format_duration(elapsed))
@@ -124,6 +135,13 @@ fn render_task_node(
         String::new()
     };
 
+    // Human tasks are never dispatched; flag the ones waiting on someone
+    let human_suffix = match (task.task_type, effective_status) {
+        (TaskType::Agent, _) | (_, TaskStatus::Done) => String::new(),
+        (TaskType::Human, TaskStatus::Ready | TaskStatus::InProgress) => " ⏸ needs you".to_string(),
+        (TaskType::Human, _) => " (human)".to_string(),
+    };
+
     // Compose the line
     let icon = status_icon(effective_status);
     let color = status_color(effective_status);
@@ -142,9 +160,14 @@ fn render_task_node(
         ),
         Span::styled(runtime_suffix, Style::default().fg(MUTED_COLOR)),
         Span::styled(blocker_suffix, Style::default().fg(MUTED_COLOR)),
+        Span::styled(human_suffix, Style::default().fg(NORD12)),
     ]);
 
     ctx.buf.set_line(ctx.area.x, y, &line, ctx.area.width);
+    if ctx.widget.selected == Some(task.id.as_str()) {
+        let row = Rect::new(ctx.area.x, y, ctx.area.width, 1);
+        ctx.buf.set_style(row, Style::default().bg(NORD1));
+    }
 
     // Render children
     let mut current_y = y + 1;
@@ -173,6 +196,31 @@ fn render_task_node(
     current_y
 }
 
+/// Tasks in the order the tree draws them, top to bottom.
+pub fn tree_order(graph: &TaskGraph) -> Vec<&SubTask> {
+    fn visit<'a>(
+        task: &'a SubTask,
+        children_map: &HashMap<String, Vec<&'a SubTask>>,
+        order: &mut Vec<&'a SubTask>,
+    ) {
+        // The heuristic never cycles on a valid graph; guard against bad specs
+        if order.iter().any(|t| t.id == task.id) {
+            return;
+        }
+        order.push(task);
+        for child in children_map.get(&task.id).into_iter().flatten() {
+            visit(child, children_map, order);
+        }
+    }
+
+    let children_map = build_children_map(graph);
+    let mut order = Vec::new();
+    for root in get_root_tasks(graph) {
+        visit(root, &children_map, &mut order);
+    }
+    order
+}
+
 /// Build a map of parent task ID -> children tasks.
 /// Uses "first blocker = parent" heuristic.
 fn build_children_map(graph: &TaskGraph) -> HashMap<String, Vec<&SubTask>> {
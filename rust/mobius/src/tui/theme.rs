@@ -1,3 +1,6 @@
+use std::str::FromStr;
+
+use ratatui::buffer::Buffer;
 use ratatui::style::Color;
 
 // Nord Polar Night (dark backgrounds)
@@ -24,7 +27,8 @@ pub const NORD13: Color = Color::Rgb(235, 203, 139); // yellow
 pub const NORD14: Color = Color::Rgb(163, 190, 140); // green
 pub const NORD15: Color = Color::Rgb(180, 142, 173); // purple
 
-use crate::types::enums::TaskStatus;
+use crate::types::config::TuiConfig;
+use crate::types::enums::{TaskStatus, TuiTheme};
 
 pub fn status_color(status: TaskStatus) -> Color {
     match status {
@@ -54,6 +58,142 @@ pub const HEADER_COLOR: Color = NORD8;
 pub const TEXT_COLOR: Color = NORD4;
 pub const MUTED_COLOR: Color = NORD3;
 
+/// Names of the sixteen palette slots, in `NORD0..=NORD15` order, as
+/// accepted by `execution.tui.colors`.
+pub const PALETTE_SLOTS: [&str; 16] = [
+    "background",
+    "highlight",
+    "selection",
+    "muted",
+    "text",
+    "text_bright",
+    "text_brightest",
+    "teal",
+    "accent",
+    "border",
+    "blue",
+    "red",
+    "orange",
+    "yellow",
+    "green",
+    "purple",
+];
+
+const NORD: [Color; 16] = [
+    NORD0, NORD1, NORD2, NORD3, NORD4, NORD5, NORD6, NORD7, NORD8, NORD9, NORD10, NORD11, NORD12,
+    NORD13, NORD14, NORD15,
+];
+
+/// Colors the dashboard is actually drawn in.
+///
+/// Widgets draw with the Nord constants above; a theme maps each of those
+/// sixteen slots to the color shown on screen, so status colors, borders,
+/// and the highlighted row all follow it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    palette: [Color; 16],
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::builtin(TuiTheme::Dark)
+    }
+}
+
+impl Theme {
+    pub fn builtin(theme: TuiTheme) -> Self {
+        let palette = match theme {
+            TuiTheme::Dark => NORD,
+            TuiTheme::Light => [
+                Color::Rgb(236, 239, 244),
+                Color::Rgb(216, 222, 233),
+                Color::Rgb(200, 208, 222),
+                Color::Rgb(106, 115, 134),
+                Color::Rgb(46, 52, 64),
+                Color::Rgb(59, 66, 82),
+                Color::Rgb(67, 76, 94),
+                Color::Rgb(58, 124, 122),
+                Color::Rgb(34, 110, 140),
+                Color::Rgb(70, 100, 150),
+                Color::Rgb(50, 80, 130),
+                Color::Rgb(170, 45, 55),
+                Color::Rgb(175, 85, 40),
+                Color::Rgb(145, 105, 0),
+                Color::Rgb(65, 120, 35),
+                Color::Rgb(125, 70, 120),
+            ],
+            TuiTheme::HighContrast => [
+                Color::Black,
+                Color::DarkGray,
+                Color::DarkGray,
+                Color::Gray,
+                Color::White,
+                Color::White,
+                Color::White,
+                Color::Cyan,
+                Color::LightCyan,
+                Color::White,
+                Color::LightBlue,
+                Color::LightRed,
+                Color::Indexed(208),
+                Color::LightYellow,
+                Color::LightGreen,
+                Color::LightMagenta,
+            ],
+            TuiTheme::ColorblindSafe => {
+                let mut palette = NORD;
+                palette[7] = Color::Rgb(86, 180, 233);
+                palette[8] = Color::Rgb(0, 158, 115);
+                palette[11] = Color::Rgb(213, 94, 0);
+                palette[12] = Color::Rgb(230, 159, 0);
+                palette[13] = Color::Rgb(240, 228, 66);
+                palette[14] = Color::Rgb(86, 180, 233);
+                palette[15] = Color::Rgb(204, 121, 167);
+                palette
+            }
+        };
+        Self { palette }
+    }
+
+    /// The configured built-in theme with `colors` overrides applied.
+    pub fn from_config(config: &TuiConfig) -> Result<Self, String> {
+        let mut theme = Self::builtin(config.theme);
+        for (name, value) in &config.colors {
+            let slot = PALETTE_SLOTS
+                .iter()
+                .position(|s| s == name)
+                .ok_or_else(|| {
+                    format!(
+                        "unknown color '{}' (expected one of: {})",
+                        name,
+                        PALETTE_SLOTS.join(", ")
+                    )
+                })?;
+            theme.palette[slot] = Color::from_str(value)
+                .map_err(|_| format!("'{}' is not a valid color for {}", value, name))?;
+        }
+        Ok(theme)
+    }
+
+    /// The on-screen color for a Nord slot; other colors pass through.
+    pub fn color(&self, color: Color) -> Color {
+        NORD.iter()
+            .position(|c| *c == color)
+            .map_or(color, |slot| self.palette[slot])
+    }
+
+    /// Recolor a rendered frame.
+    pub fn apply(&self, buf: &mut Buffer) {
+        if self.palette == NORD {
+            return;
+        }
+        for cell in buf.content.iter_mut() {
+            cell.fg = self.color(cell.fg);
+            cell.bg = self.color(cell.bg);
+        }
+    }
+}
+
 /// Returns a Nord color for the given model string.
 /// Uses substring matching: opus=purple, sonnet=blue, haiku=green, else default text.
 pub fn model_color(model: &str) -> Color {
@@ -136,6 +276,29 @@ mod tests {
         assert_eq!(format_tokens(0), "0");
     }
 
+    #[test]
+    fn test_theme_maps_nord_slots_and_applies_overrides() {
+        assert_eq!(Theme::default().color(NORD14), NORD14);
+
+        let light = Theme::builtin(TuiTheme::Light);
+        assert_eq!(light.color(NORD4), Color::Rgb(46, 52, 64));
+        assert_eq!(light.color(Color::Reset), Color::Reset);
+
+        let mut config = TuiConfig {
+            theme: TuiTheme::HighContrast,
+            ..TuiConfig::default()
+        };
+        config.colors.insert("green".into(), "#00ff00".into());
+        let theme = Theme::from_config(&config).unwrap();
+        assert_eq!(theme.color(NORD14), Color::Rgb(0, 255, 0));
+        assert_eq!(theme.color(NORD11), Color::LightRed);
+
+        config.colors.insert("grean".into(), "red".into());
+        assert!(Theme::from_config(&config)
+            .unwrap_err()
+            .contains("unknown color 'grean'"));
+    }
+
     #[test]
     fn test_format_token_pair() {
         assert_eq!(format_token_pair(1_500, 500), "1.5K in / 500 out");
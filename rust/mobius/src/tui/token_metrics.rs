@@ -6,6 +6,8 @@ use ratatui::style::Style;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Sparkline, Widget};
 
+use crate::cost::format_cost;
+
 use super::theme::{
     format_token_pair, model_color, BORDER_COLOR, HEADER_COLOR, MUTED_COLOR, NORD8, TEXT_COLOR,
 };
@@ -20,6 +22,8 @@ pub struct TokenMetrics<'a> {
     pub total_output: u64,
     pub per_model: &'a HashMap<String, (u64, u64)>,
     pub token_history: &'a [u64],
+    /// Accumulated dollar cost, when the models in use are priced
+    pub total_cost_usd: Option<f64>,
 }
 
 impl Widget for TokenMetrics<'_> {
@@ -42,7 +46,7 @@ impl Widget for TokenMetrics<'_> {
         let mut row = inner.y;
 
         // Section 1: Cumulative totals
-        let totals_text = if self.total_input == 0 && self.total_output == 0 {
+        let mut totals_text = if self.total_input == 0 && self.total_output == 0 {
             "Tokens: —".to_string()
         } else {
             format!(
@@ -50,6 +54,9 @@ impl Widget for TokenMetrics<'_> {
                 format_token_pair(self.total_input, self.total_output)
             )
         };
+        if let Some(cost) = self.total_cost_usd {
+            totals_text.push_str(&format!(" · {}", format_cost(cost)));
+        }
         let totals_line = Line::from(Span::styled(totals_text, Style::default().fg(TEXT_COLOR)));
         buf.set_line(
             inner.x + 1,
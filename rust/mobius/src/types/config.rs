@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-use super::enums::{AgentRuntime, Backend, BuildSystem, JiraAuthMethod, Platform, ProjectType};
+use super::enums::{
+    AgentRuntime, Backend, BuildSystem, CommitAuthor, CompletionProtocol, ContainerEngine,
+    ExecutionDriver, JiraAuthMethod, LimitEnforcer, MergeMethod, NotificationEvent, Platform,
+    ProjectType, RetryCondition, SchedulingStrategy, StateStoreKind, TaskStatus, TuiTheme,
+};
+use super::task_graph::TaskScoring;
 
 /// TUI dashboard configuration options
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,6 +19,11 @@ pub struct TuiConfig {
     pub panel_refresh_ms: u32,
     #[serde(default = "default_panel_lines")]
     pub panel_lines: u32,
+    #[serde(default)]
+    pub theme: TuiTheme,
+    /// Per-color overrides on top of `theme`, e.g. `green: "#00ff00"`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub colors: HashMap<String, String>,
 }
 
 impl Default for TuiConfig {
@@ -22,6 +33,8 @@ impl Default for TuiConfig {
             state_dir: default_state_dir(),
             panel_refresh_ms: 300,
             panel_lines: 8,
+            theme: TuiTheme::default(),
+            colors: HashMap::new(),
         }
     }
 }
@@ -39,6 +52,20 @@ pub struct VerificationConfig {
     pub security_check: bool,
     #[serde(default = "default_max_rework_iterations")]
     pub max_rework_iterations: u32,
+    /// Scope test commands to the workspace packages a task changed
+    #[serde(default = "default_true")]
+    pub scope_to_workspace: bool,
+    /// Per-build-system test command templates; `{package}` is replaced with
+    /// each changed package name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub workspace_commands: HashMap<BuildSystem, String>,
+    /// Run each sub-task's `### Verify Command` after its agent reports
+    /// success; the attempt fails when the command does not pass
+    #[serde(default = "default_true")]
+    pub run_verify_commands: bool,
+    /// Time limit for one verify command run
+    #[serde(default = "default_verify_timeout_minutes")]
+    pub verify_timeout_minutes: u32,
 }
 
 impl Default for VerificationConfig {
@@ -49,10 +76,266 @@ impl Default for VerificationConfig {
             performance_check: true,
             security_check: true,
             max_rework_iterations: 3,
+            scope_to_workspace: true,
+            workspace_commands: HashMap::new(),
+            run_verify_commands: true,
+            verify_timeout_minutes: default_verify_timeout_minutes(),
+        }
+    }
+}
+
+/// Authorship of commits made by agents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AttributionConfig {
+    #[serde(default)]
+    pub author: CommitAuthor,
+    #[serde(default = "default_bot_name")]
+    pub bot_name: String,
+    #[serde(default = "default_bot_email")]
+    pub bot_email: String,
+    /// Credit the operator with a `Co-authored-by:` trailer when commits are
+    /// authored by the bot
+    #[serde(default = "default_true")]
+    pub co_author_user: bool,
+    /// Credit the agent runtime and model with a `Co-authored-by:` trailer
+    #[serde(default = "default_true")]
+    pub co_author_agent: bool,
+}
+
+impl Default for AttributionConfig {
+    fn default() -> Self {
+        Self {
+            author: CommitAuthor::User,
+            bot_name: default_bot_name(),
+            bot_email: default_bot_email(),
+            co_author_user: true,
+            co_author_agent: true,
+        }
+    }
+}
+
+/// Options for the Aider runtime
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AiderConfig {
+    /// Let Aider commit each edit (`--auto-commits`); otherwise changes are left
+    /// uncommitted in the worktree
+    #[serde(default = "default_true")]
+    pub auto_commits: bool,
+    /// Extra arguments appended to every `aider` invocation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_args: Vec<String>,
+}
+
+impl Default for AiderConfig {
+    fn default() -> Self {
+        Self {
+            auto_commits: true,
+            extra_args: Vec::new(),
+        }
+    }
+}
+
+/// Post-mortem archive of each agent's output (`execution/outputs/`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputArchiveConfig {
+    /// Tail of the agent's output kept per archive, in KB (0 disables archiving)
+    #[serde(default = "default_output_archive_max_kb")]
+    pub max_kb: u32,
+    /// Previous archives kept per task (`{identifier}.1.txt`, ...)
+    #[serde(default = "default_output_archive_keep")]
+    pub keep: u32,
+}
+
+impl Default for OutputArchiveConfig {
+    fn default() -> Self {
+        Self {
+            max_kb: default_output_archive_max_kb(),
+            keep: default_output_archive_keep(),
         }
     }
 }
 
+/// Container sandbox for agents (`execution.container`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContainerConfig {
+    /// Image agents run in; agents stay on the host until one is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default)]
+    pub engine: ContainerEngine,
+    /// Host environment variables to pass through, besides the agent API keys
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<String>,
+    /// Extra `run` arguments (e.g. `--network=none`), added after mobius's own
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub run_args: Vec<String>,
+}
+
+/// Per-agent resource limits (`execution.limits`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// Memory cap per agent, in MB
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<u64>,
+    /// CPU share per agent in cores (e.g. `1.5`); systemd enforcer only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpus: Option<f64>,
+    /// CPU time per agent process before it is killed, in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_seconds: Option<u64>,
+    /// Processes and threads per agent; systemd enforcer only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_processes: Option<u32>,
+    #[serde(default)]
+    pub enforcer: LimitEnforcer,
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        self.memory_mb.is_none()
+            && self.cpus.is_none()
+            && self.cpu_seconds.is_none()
+            && self.max_processes.is_none()
+    }
+}
+
+/// How new loop worktrees are checked out (`execution.checkout`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckoutConfig {
+    /// Clone worktrees from a persistent bare mirror of `origin` instead of
+    /// adding linked worktrees of the main checkout
+    #[serde(default)]
+    pub mirror: bool,
+    /// Clone without file contents (`--filter=blob:none`); the mirror serves
+    /// blobs as they are first read. Requires `mirror`
+    #[serde(default)]
+    pub partial_clone: bool,
+    /// Directories (e.g. `target`, `node_modules`) copied copy-on-write from
+    /// the main checkout into each new worktree
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reflink_caches: Vec<String>,
+}
+
+impl CheckoutConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Build caches shared by every worktree's agents (`execution.build_cache`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildCacheConfig {
+    /// Share the caches of the project's build systems
+    #[serde(default)]
+    pub enabled: bool,
+    /// Directory holding the shared caches (default `.build-cache` in the
+    /// worktree base)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dir: Option<String>,
+    /// Build systems to share caches for; detected from the manifests when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub build_systems: Vec<BuildSystem>,
+    /// Caches to keep per worktree (e.g. `target`, `pip`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Also link `node_modules`, which only works while every worktree
+    /// installs the same lockfile and never two at once
+    #[serde(default)]
+    pub share_node_modules: bool,
+}
+
+impl BuildCacheConfig {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Routes matching sub-tasks to another runtime (and optionally model).
+///
+/// Conditions are compared against the task's scoring and must all hold; a
+/// rule without conditions matches every task, while unscored tasks only match
+/// such catch-all rules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_complexity: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_complexity: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_risk: Option<u8>,
+    pub runtime: AgentRuntime,
+    /// Model for the runtime; defaults to the task's scored model (claude) or
+    /// the configured model (opencode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+impl RuntimeRule {
+    fn has_conditions(&self) -> bool {
+        self.min_complexity.is_some() || self.max_complexity.is_some() || self.min_risk.is_some()
+    }
+
+    /// Whether a task with `scoring` is routed by this rule.
+    pub fn matches(&self, scoring: Option<&TaskScoring>) -> bool {
+        let Some(scoring) = scoring else {
+            return !self.has_conditions();
+        };
+        self.min_complexity
+            .map_or(true, |min| scoring.complexity >= min)
+            && self
+                .max_complexity
+                .map_or(true, |max| scoring.complexity <= max)
+            && self.min_risk.map_or(true, |min| scoring.risk >= min)
+    }
+}
+
+/// Routes matching sub-tasks to a custom skill (e.g. `/docs`, `/migrate`).
+///
+/// Set conditions must all hold; a rule without conditions matches every
+/// task. Unmatched tasks keep the built-in choice (`/verify` for verification
+/// gates, `/execute` otherwise).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillRule {
+    /// Tracker label, compared case-insensitively
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Regex matched case-insensitively against the task title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifier_prefix: Option<String>,
+    pub skill: String,
+}
+
+impl SkillRule {
+    /// Whether a task with these fields is routed by this rule. A rule whose
+    /// title regex doesn't compile never matches.
+    pub fn matches(&self, identifier: &str, title: &str, labels: &[String]) -> bool {
+        self.label.as_ref().map_or(true, |label| {
+            labels.iter().any(|l| l.eq_ignore_ascii_case(label))
+        }) && self.identifier_prefix.as_ref().map_or(true, |prefix| {
+            identifier
+                .to_lowercase()
+                .starts_with(&prefix.to_lowercase())
+        }) && self.title.as_ref().map_or(true, |pattern| {
+            regex::RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .is_ok_and(|re| re.is_match(title))
+        })
+    }
+}
+
+/// Alternate runtime/model used for the rest of a run once the provider is
+/// overloaded or returning server errors
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelFallback {
+    pub runtime: AgentRuntime,
+    /// Model for the fallback runtime; defaults to the configured model
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
 /// Execution configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionConfig {
@@ -76,14 +359,64 @@ pub struct ExecutionConfig {
     pub base_branch: Option<String>,
     #[serde(default = "default_max_retries")]
     pub max_retries: Option<u32>,
+    /// Base delay before a retry; doubles with each further attempt
+    #[serde(default)]
+    pub backoff_seconds: Option<u32>,
+    /// Failure kinds that are retried (others fail the task immediately)
+    #[serde(default = "default_retry_on")]
+    pub retry_on: Vec<RetryCondition>,
     #[serde(default = "default_verification_timeout")]
     pub verification_timeout: Option<u32>,
+    /// Agent timeout per sub-task (default 30); a spec's `### Timeout` overrides it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_timeout_minutes: Option<u32>,
+    /// Wall-clock limit for a whole loop run; agents are interrupted and the
+    /// run checkpointed when it is reached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loop_timeout_minutes: Option<u32>,
     #[serde(default)]
     pub tui: Option<TuiConfig>,
     #[serde(default)]
     pub verification: Option<VerificationConfig>,
     #[serde(default)]
     pub disallowed_tools: Option<Vec<String>>,
+    #[serde(default)]
+    pub driver: ExecutionDriver,
+    #[serde(default)]
+    pub completion_protocol: CompletionProtocol,
+    /// Embed the cached repository primer in every agent's context
+    #[serde(default = "default_true")]
+    pub repo_primer: bool,
+    #[serde(default)]
+    pub attribution: AttributionConfig,
+    /// Per-task runtime routing; the first matching rule wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runtime_rules: Vec<RuntimeRule>,
+    #[serde(default)]
+    pub aider: AiderConfig,
+    /// Per-task skill routing; the first matching rule wins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skill_rules: Vec<SkillRule>,
+    /// Switch remaining tasks here on a provider incident
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<ModelFallback>,
+    /// Commands run once per worktree before agents start (e.g. `pnpm install`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bootstrap: Vec<String>,
+    #[serde(default)]
+    pub output_archive: OutputArchiveConfig,
+    /// CPU and memory caps applied to each spawned agent
+    #[serde(default, skip_serializing_if = "ResourceLimits::is_empty")]
+    pub limits: ResourceLimits,
+    /// Per-task containers used when `sandbox` is on
+    #[serde(default)]
+    pub container: ContainerConfig,
+    /// Mirror, partial-clone, and cache-copy options for new worktrees
+    #[serde(default, skip_serializing_if = "CheckoutConfig::is_empty")]
+    pub checkout: CheckoutConfig,
+    /// Build outputs and package caches shared between worktrees
+    #[serde(default, skip_serializing_if = "BuildCacheConfig::is_empty")]
+    pub build_cache: BuildCacheConfig,
 }
 
 impl Default for ExecutionConfig {
@@ -99,10 +432,28 @@ impl Default for ExecutionConfig {
             cleanup_on_success: Some(true),
             base_branch: Some("main".to_string()),
             max_retries: Some(2),
+            backoff_seconds: None,
+            retry_on: default_retry_on(),
             verification_timeout: Some(5000),
+            task_timeout_minutes: None,
+            loop_timeout_minutes: None,
             tui: None,
             verification: Some(VerificationConfig::default()),
             disallowed_tools: None,
+            driver: ExecutionDriver::Tmux,
+            completion_protocol: CompletionProtocol::Auto,
+            repo_primer: true,
+            attribution: AttributionConfig::default(),
+            runtime_rules: Vec::new(),
+            aider: AiderConfig::default(),
+            skill_rules: Vec::new(),
+            fallback: None,
+            bootstrap: Vec::new(),
+            output_archive: OutputArchiveConfig::default(),
+            limits: ResourceLimits::default(),
+            container: ContainerConfig::default(),
+            checkout: CheckoutConfig::default(),
+            build_cache: BuildCacheConfig::default(),
         }
     }
 }
@@ -124,6 +475,338 @@ pub struct JiraConfig {
     pub default_labels: Option<Vec<String>>,
 }
 
+/// GitHub Issues backend configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GithubConfig {
+    pub owner: Option<String>,
+    pub repo: Option<String>,
+    pub default_labels: Option<Vec<String>>,
+}
+
+/// GitLab backend configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitlabConfig {
+    pub base_url: Option<String>,
+    pub project: Option<String>,
+    pub default_labels: Option<Vec<String>>,
+}
+
+/// Per-backend mapping between backend workflow states and `TaskStatus`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StatusMapping {
+    /// Backend status name → task status (matched case-insensitively)
+    #[serde(default)]
+    pub from_backend: HashMap<String, TaskStatus>,
+    /// Task status → backend status name used when pushing status changes
+    #[serde(default)]
+    pub to_backend: HashMap<TaskStatus, String>,
+    /// Task status → backend status ID; preferred over `to_backend` names,
+    /// which break when the tracker is configured in another language.
+    /// Written by `mobius backend states --write`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub to_backend_id: HashMap<TaskStatus, String>,
+}
+
+impl StatusMapping {
+    /// Look up the task status configured for a backend status name.
+    pub fn task_status(&self, backend_status: &str) -> Option<TaskStatus> {
+        self.from_backend
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(backend_status.trim()))
+            .map(|(_, status)| *status)
+    }
+
+    /// Translate a requested status into the backend's workflow state.
+    ///
+    /// Only names that spell a `TaskStatus` ("done", "In Progress", ...) are
+    /// translated; backend-native names like "In Review" pass through as `None`.
+    pub fn backend_status(&self, requested: &str) -> Option<&str> {
+        self.to_backend
+            .get(&requested_task_status(requested)?)
+            .map(String::as_str)
+    }
+
+    /// Backend status ID configured for a requested status, if any.
+    pub fn backend_status_id(&self, requested: &str) -> Option<&str> {
+        self.to_backend_id
+            .get(&requested_task_status(requested)?)
+            .map(String::as_str)
+    }
+}
+
+/// The `TaskStatus` a requested status name spells, if any.
+fn requested_task_status(requested: &str) -> Option<TaskStatus> {
+    let normalized = requested.trim().to_lowercase().replace([' ', '-'], "_");
+    let status = match normalized.as_str() {
+        "pending" => TaskStatus::Pending,
+        "ready" => TaskStatus::Ready,
+        "in_progress" => TaskStatus::InProgress,
+        "done" => TaskStatus::Done,
+        "blocked" => TaskStatus::Blocked,
+        "failed" => TaskStatus::Failed,
+        _ => return None,
+    };
+    Some(status)
+}
+
+/// Per-backend mapping from tracker fields to task scoring and verification.
+///
+/// Values name a backend field: a Jira field ID (`customfield_10016`), a
+/// Linear issue field (`estimate`, `priority`), or `label:<prefix>` to read
+/// the value from a label such as `model:sonnet`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FieldMapping {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub complexity: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recommended_model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verify_command: Option<String>,
+}
+
+impl FieldMapping {
+    /// True when no field is mapped.
+    pub fn is_empty(&self) -> bool {
+        self.complexity.is_none()
+            && self.risk.is_none()
+            && self.recommended_model.is_none()
+            && self.verify_command.is_none()
+    }
+}
+
+/// Loop-wide limits and scheduling (`loop:` section)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoopSettings {
+    /// Stop the loop once accumulated agent cost reaches this many dollars
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cost_usd: Option<f64>,
+    /// Order in which ready sub-tasks are dispatched
+    #[serde(default)]
+    pub strategy: SchedulingStrategy,
+}
+
+/// A webhook that receives lifecycle event payloads
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Events to deliver; empty means all events
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<NotificationEvent>,
+    /// Extra request headers, e.g. an authorization token
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+}
+
+impl WebhookConfig {
+    /// Whether this webhook subscribes to `event`.
+    pub fn wants(&self, event: NotificationEvent) -> bool {
+        self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// Outbound notification settings (`notifications:` section)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+impl NotificationsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.webhooks.is_empty()
+    }
+}
+
+/// Shared settings for backend API clients (`http:` section)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpConfig {
+    /// Per-request timeout in seconds
+    #[serde(default = "default_http_timeout_seconds")]
+    pub timeout_seconds: u64,
+    /// Retries after a 429, 5xx, timeout or connection failure
+    #[serde(default = "default_http_max_retries")]
+    pub max_retries: u32,
+    /// First retry delay in milliseconds; doubles per attempt, with jitter
+    #[serde(default = "default_http_backoff_ms")]
+    pub backoff_ms: u64,
+    /// Requests in flight at once across all backend clients
+    #[serde(default = "default_http_max_concurrent")]
+    pub max_concurrent: usize,
+    /// Proxy for all backend traffic (`HTTPS_PROXY`/`HTTP_PROXY` apply otherwise)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            timeout_seconds: default_http_timeout_seconds(),
+            max_retries: default_http_max_retries(),
+            backoff_ms: default_http_backoff_ms(),
+            max_concurrent: default_http_max_concurrent(),
+            proxy: None,
+        }
+    }
+}
+
+/// Multi-machine agent pool (`pool:` section)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolConfig {
+    /// Address the loop accepts `mobius worker --join` connections on, e.g.
+    /// `0.0.0.0:7420`; unset runs every agent on this machine
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listen: Option<String>,
+    /// Shared secret workers must present (`MOBIUS_POOL_TOKEN` also works)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Git remote the loop branch is exchanged through
+    #[serde(default = "default_pool_remote")]
+    pub remote: String,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            listen: None,
+            token: None,
+            remote: default_pool_remote(),
+        }
+    }
+}
+
+impl PoolConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Trace export (`observability:` section)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObservabilityConfig {
+    /// OTLP/HTTP collector base URL, e.g. `http://localhost:4318`; unset
+    /// disables export. Needs a build with the `otlp` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otlp_endpoint: Option<String>,
+    /// `service.name` reported on exported spans
+    #[serde(default = "default_service_name")]
+    pub service_name: String,
+}
+
+impl Default for ObservabilityConfig {
+    fn default() -> Self {
+        Self {
+            otlp_endpoint: None,
+            service_name: default_service_name(),
+        }
+    }
+}
+
+impl ObservabilityConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Pull request submission (`submit:` section)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SubmitConfig {
+    /// Markdown template for the PR description, relative to the config
+    /// file's directory; unset leaves the description to the PR skill
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Reviewers requested on every PR; `--reviewer` replaces them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+    /// Labels added to every PR, alongside any `--label`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Milestone set on every PR; `--milestone` overrides it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+    /// Also add the parent issue's tracker labels to the PR
+    #[serde(default)]
+    pub inherit_labels: bool,
+    /// Merge method used by `--auto-merge`
+    #[serde(default)]
+    pub merge_method: MergeMethod,
+}
+
+impl SubmitConfig {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Named overrides selected with `--profile` or `MOBIUS_PROFILE` (`profiles.<name>`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigProfile {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<AgentRuntime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<Backend>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linear: Option<LinearConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jira: Option<JiraConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github: Option<GithubConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gitlab: Option<GitlabConfig>,
+    /// Model profile or runtime model ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_parallel_agents: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub container: Option<ContainerConfig>,
+}
+
+impl ConfigProfile {
+    /// Replace the settings this profile sets, leaving the rest of `config` alone.
+    pub fn apply(self, config: &mut LoopConfig) {
+        if let Some(runtime) = self.runtime {
+            config.runtime = runtime;
+        }
+        if let Some(backend) = self.backend {
+            config.backend = backend;
+        }
+        if self.linear.is_some() {
+            config.linear = self.linear;
+        }
+        if self.jira.is_some() {
+            config.jira = self.jira;
+        }
+        if self.github.is_some() {
+            config.github = self.github;
+        }
+        if self.gitlab.is_some() {
+            config.gitlab = self.gitlab;
+        }
+        let execution = &mut config.execution;
+        if let Some(model) = self.model {
+            execution.model = model;
+        }
+        if self.max_parallel_agents.is_some() {
+            execution.max_parallel_agents = self.max_parallel_agents;
+        }
+        if let Some(sandbox) = self.sandbox {
+            execution.sandbox = sandbox;
+        }
+        if let Some(container_name) = self.container_name {
+            execution.container_name = container_name;
+        }
+        if let Some(container) = self.container {
+            execution.container = container;
+        }
+    }
+}
+
 /// Top-level loop configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LoopConfig {
@@ -136,7 +819,44 @@ pub struct LoopConfig {
     #[serde(default)]
     pub jira: Option<JiraConfig>,
     #[serde(default)]
+    pub github: Option<GithubConfig>,
+    #[serde(default)]
+    pub gitlab: Option<GitlabConfig>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub status_mapping: HashMap<Backend, StatusMapping>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub field_mapping: HashMap<Backend, FieldMapping>,
+    #[serde(default)]
     pub execution: ExecutionConfig,
+    #[serde(default, rename = "loop")]
+    pub loop_settings: LoopSettings,
+    #[serde(default, skip_serializing_if = "NotificationsConfig::is_empty")]
+    pub notifications: NotificationsConfig,
+    /// Storage for the local ID counter, iteration logs and issue index
+    #[serde(default)]
+    pub state_store: StateStoreKind,
+    #[serde(default)]
+    pub http: HttpConfig,
+    #[serde(default, skip_serializing_if = "PoolConfig::is_default")]
+    pub pool: PoolConfig,
+    #[serde(default, skip_serializing_if = "ObservabilityConfig::is_default")]
+    pub observability: ObservabilityConfig,
+    #[serde(default, skip_serializing_if = "SubmitConfig::is_default")]
+    pub submit: SubmitConfig,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub profiles: HashMap<String, ConfigProfile>,
+}
+
+impl LoopConfig {
+    /// Status mapping overrides configured for `backend`, if any.
+    pub fn status_mapping_for(&self, backend: Backend) -> Option<&StatusMapping> {
+        self.status_mapping.get(&backend)
+    }
+
+    /// Field mapping configured for `backend`, if any fields are mapped.
+    pub fn field_mapping_for(&self, backend: Backend) -> Option<&FieldMapping> {
+        self.field_mapping.get(&backend).filter(|m| !m.is_empty())
+    }
 }
 
 impl Default for LoopConfig {
@@ -146,7 +866,19 @@ impl Default for LoopConfig {
             backend: Backend::Linear,
             linear: None,
             jira: None,
+            github: None,
+            gitlab: None,
+            status_mapping: HashMap::new(),
+            field_mapping: HashMap::new(),
             execution: ExecutionConfig::default(),
+            loop_settings: LoopSettings::default(),
+            notifications: NotificationsConfig::default(),
+            state_store: StateStoreKind::default(),
+            http: HttpConfig::default(),
+            pool: PoolConfig::default(),
+            observability: ObservabilityConfig::default(),
+            submit: SubmitConfig::default(),
+            profiles: HashMap::new(),
         }
     }
 }
@@ -290,6 +1022,18 @@ fn default_max_rework_iterations() -> u32 {
     3
 }
 
+fn default_verify_timeout_minutes() -> u32 {
+    10
+}
+
+fn default_bot_name() -> String {
+    "mobius[bot]".to_string()
+}
+
+fn default_bot_email() -> String {
+    "mobius[bot]@users.noreply.github.com".to_string()
+}
+
 fn default_delay_seconds() -> u32 {
     3
 }
@@ -322,6 +1066,46 @@ fn default_max_retries() -> Option<u32> {
     Some(2)
 }
 
+fn default_retry_on() -> Vec<RetryCondition> {
+    vec![
+        RetryCondition::Timeout,
+        RetryCondition::VerificationFailed,
+        RetryCondition::Error,
+    ]
+}
+
+fn default_http_timeout_seconds() -> u64 {
+    30
+}
+
+fn default_http_max_retries() -> u32 {
+    3
+}
+
+fn default_http_backoff_ms() -> u64 {
+    500
+}
+
+fn default_http_max_concurrent() -> usize {
+    8
+}
+
+fn default_pool_remote() -> String {
+    "origin".to_string()
+}
+
+fn default_service_name() -> String {
+    "mobius".to_string()
+}
+
+fn default_output_archive_max_kb() -> u32 {
+    64
+}
+
+fn default_output_archive_keep() -> u32 {
+    3
+}
+
 fn default_verification_timeout() -> Option<u32> {
     Some(5000)
 }
@@ -349,6 +1133,7 @@ mod tests {
         assert_eq!(config.execution.base_branch, Some("main".to_string()));
         assert_eq!(config.execution.max_retries, Some(2));
         assert_eq!(config.execution.verification_timeout, Some(5000));
+        assert_eq!(config.execution.driver, ExecutionDriver::Tmux);
 
         let verification = config.execution.verification.unwrap();
         assert_eq!(verification.coverage_threshold, 80);
@@ -381,6 +1166,165 @@ mod tests {
         assert_eq!(parsed.execution.model, config.execution.model);
     }
 
+    #[test]
+    fn test_status_mapping_from_yaml() {
+        let yaml = r#"
+backend: jira
+status_mapping:
+  jira:
+    from_backend:
+      Ready for Dev: pending
+      Code Review: in_progress
+    to_backend:
+      in_progress: In Development
+      done: Closed
+"#;
+        let config: LoopConfig = serde_yaml::from_str(yaml).unwrap();
+        let mapping = config.status_mapping_for(Backend::Jira).unwrap();
+        assert_eq!(
+            mapping.task_status("code review"),
+            Some(TaskStatus::InProgress)
+        );
+        assert_eq!(mapping.task_status("Unknown"), None);
+        assert_eq!(
+            mapping.backend_status("In Progress"),
+            Some("In Development")
+        );
+        assert_eq!(mapping.backend_status("done"), Some("Closed"));
+        assert_eq!(mapping.backend_status("In Review"), None);
+        assert!(config.status_mapping_for(Backend::Linear).is_none());
+    }
+
+    #[test]
+    fn test_status_mapping_ids_from_yaml() {
+        let yaml = r#"
+backend: jira
+status_mapping:
+  jira:
+    from_backend:
+      In Arbeit: in_progress
+    to_backend_id:
+      in_progress: "3"
+      done: "10001"
+"#;
+        let config: LoopConfig = serde_yaml::from_str(yaml).unwrap();
+        let mapping = config.status_mapping_for(Backend::Jira).unwrap();
+        assert_eq!(mapping.backend_status_id("In Progress"), Some("3"));
+        assert_eq!(mapping.backend_status_id("done"), Some("10001"));
+        assert_eq!(mapping.backend_status_id("pending"), None);
+        assert_eq!(mapping.backend_status("done"), None);
+        assert_eq!(
+            mapping.task_status("in arbeit"),
+            Some(TaskStatus::InProgress)
+        );
+    }
+
+    #[test]
+    fn test_field_mapping_from_yaml() {
+        let yaml = r#"
+backend: jira
+field_mapping:
+  jira:
+    complexity: customfield_10016
+    recommended_model: customfield_10051
+  linear:
+    risk: priority
+    recommended_model: "label:model:"
+  github: {}
+"#;
+        let config: LoopConfig = serde_yaml::from_str(yaml).unwrap();
+        let jira = config.field_mapping_for(Backend::Jira).unwrap();
+        assert_eq!(jira.complexity.as_deref(), Some("customfield_10016"));
+        assert!(jira.risk.is_none());
+        let linear = config.field_mapping_for(Backend::Linear).unwrap();
+        assert_eq!(linear.recommended_model.as_deref(), Some("label:model:"));
+        assert!(config.field_mapping_for(Backend::Github).is_none());
+        assert!(config.field_mapping_for(Backend::Gitlab).is_none());
+    }
+
+    #[test]
+    fn test_execution_driver_from_yaml() {
+        let yaml = "execution:\n  driver: process\n";
+        let config: LoopConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.execution.driver, ExecutionDriver::Process);
+    }
+
+    #[test]
+    fn test_runtime_rules_from_yaml() {
+        let yaml = r#"
+execution:
+  runtime_rules:
+    - min_complexity: 7
+      runtime: claude
+      model: opus
+    - runtime: opencode
+"#;
+        let config: LoopConfig = serde_yaml::from_str(yaml).unwrap();
+        let rules = &config.execution.runtime_rules;
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].runtime, AgentRuntime::Claude);
+        assert_eq!(rules[0].model.as_deref(), Some("opus"));
+
+        let scoring = |complexity| TaskScoring {
+            complexity,
+            risk: 1,
+            recommended_model: crate::types::enums::Model::Sonnet,
+            rationale: String::new(),
+            recommended_runtime: None,
+        };
+        assert!(rules[0].matches(Some(&scoring(8))));
+        assert!(!rules[0].matches(Some(&scoring(3))));
+        assert!(!rules[0].matches(None));
+        assert!(rules[1].matches(None));
+    }
+
+    #[test]
+    fn test_retry_policy_from_yaml() {
+        let config: LoopConfig =
+            serde_yaml::from_str("execution:\n  max_iterations: 10\n").unwrap();
+        assert_eq!(config.execution.backoff_seconds, None);
+        assert_eq!(config.execution.retry_on.len(), 3);
+
+        let yaml = "execution:\n  max_retries: 4\n  backoff_seconds: 30\n  retry_on: [timeout]\n";
+        let config: LoopConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.execution.max_retries, Some(4));
+        assert_eq!(config.execution.backoff_seconds, Some(30));
+        assert_eq!(config.execution.retry_on, vec![RetryCondition::Timeout]);
+    }
+
+    #[test]
+    fn test_loop_settings_from_yaml() {
+        let config: LoopConfig = serde_yaml::from_str("backend: local\n").unwrap();
+        assert!(config.loop_settings.max_cost_usd.is_none());
+
+        assert_eq!(config.loop_settings.strategy, SchedulingStrategy::Priority);
+
+        let yaml = "loop:\n  max_cost_usd: 25.5\n  strategy: fanout\n";
+        let config: LoopConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.loop_settings.max_cost_usd, Some(25.5));
+        assert_eq!(config.loop_settings.strategy, SchedulingStrategy::Fanout);
+    }
+
+    #[test]
+    fn test_notifications_from_yaml() {
+        let yaml = r#"
+notifications:
+  webhooks:
+    - url: https://hooks.slack.com/services/T/B/X
+      events: [task_failed, loop_finished]
+    - url: https://example.com/mobius
+      headers:
+        Authorization: Bearer abc
+"#;
+        let config: LoopConfig = serde_yaml::from_str(yaml).unwrap();
+        let webhooks = &config.notifications.webhooks;
+        assert_eq!(webhooks.len(), 2);
+        assert!(webhooks[0].wants(NotificationEvent::TaskFailed));
+        assert!(!webhooks[0].wants(NotificationEvent::TaskStarted));
+        assert!(webhooks[1].wants(NotificationEvent::PrCreated));
+        assert_eq!(webhooks[1].headers["Authorization"], "Bearer abc");
+    }
+
     #[test]
     fn test_execution_state_serde() {
         let state = ExecutionState {
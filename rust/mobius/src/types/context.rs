@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 
 use super::config::{ProjectDetectionResult, SubTaskVerifyCommand};
-use super::enums::{Backend, PendingUpdateType, SessionStatus, TaskStatus, VerificationResult};
+use super::enums::{
+    Backend, PendingUpdateType, SessionStatus, TaskAction, TaskStatus, TaskType, VerificationResult,
+};
 use super::task_graph::TaskScoring;
 
 /// Parent issue details stored in local context
@@ -73,6 +75,30 @@ pub struct SubTaskContext {
     pub blocks: Vec<IssueRef>,
     #[serde(default)]
     pub scoring: Option<TaskScoring>,
+    /// Dispatch priority, 1 = highest (Linear: urgent 1 .. low 4)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    /// Tracker labels; used by `execution.skill_rules`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// `"type": "human"` marks an action for a person rather than an agent
+    #[serde(rename = "type", default, skip_serializing_if = "TaskType::is_agent")]
+    pub task_type: TaskType,
+    /// Tracker comments received by `mobius listen`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<TaskComment>,
+}
+
+/// A tracker comment on a sub-task
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskComment {
+    pub id: String,
+    #[serde(default)]
+    pub author: String,
+    pub body: String,
+    #[serde(default)]
+    pub created_at: String,
 }
 
 /// Deserialize blockedBy/blocks fields that can be either string arrays or IssueRef arrays.
@@ -169,6 +195,12 @@ pub struct RuntimeActiveTask {
     pub input_tokens: Option<u64>,
     #[serde(default)]
     pub output_tokens: Option<u64>,
+    /// Retries preceding the current attempt
+    #[serde(default)]
+    pub retry_count: Option<u32>,
+    /// Pool worker running the task; `None` for agents on this machine
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worker: Option<String>,
 }
 
 /// Completed or failed task with timing info (runtime monitoring)
@@ -182,6 +214,18 @@ pub struct RuntimeCompletedTask {
     pub input_tokens: Option<u64>,
     #[serde(default)]
     pub output_tokens: Option<u64>,
+    /// Model the final attempt ran with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+}
+
+/// Measurements of a finished attempt, recorded on its completed entry
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskAttemptMetrics {
+    pub duration_ms: u64,
+    pub input_tokens: Option<u64>,
+    pub output_tokens: Option<u64>,
+    pub model: Option<String>,
 }
 
 /// A single todo task from a Claude Code agent
@@ -212,7 +256,7 @@ pub struct BackendStatusEntry {
 }
 
 /// Runtime execution state for TUI monitoring
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RuntimeState {
     pub parent_id: String,
@@ -220,6 +264,9 @@ pub struct RuntimeState {
     pub active_tasks: Vec<RuntimeActiveTask>,
     pub completed_tasks: Vec<serde_json::Value>,
     pub failed_tasks: Vec<serde_json::Value>,
+    /// Tasks that were running when `mobius cancel` stopped the loop
+    #[serde(default)]
+    pub cancelled_tasks: Vec<serde_json::Value>,
     pub started_at: String,
     pub updated_at: String,
     pub loop_pid: Option<u32>,
@@ -229,12 +276,38 @@ pub struct RuntimeState {
     pub total_input_tokens: Option<u64>,
     #[serde(default)]
     pub total_output_tokens: Option<u64>,
+    /// Accumulated cost per task identifier, including retried attempts
+    #[serde(default)]
+    pub task_costs_usd: std::collections::HashMap<String, f64>,
+    #[serde(default)]
+    pub total_cost_usd: Option<f64>,
+}
+
+/// A dashboard action waiting in `actions.json` for the loop to apply
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskActionRequest {
+    /// Sub-task identifier
+    pub task: String,
+    pub action: TaskAction,
+    pub requested_at: String,
+}
+
+/// One revision of `runtime.json`, as appended to `runtime.journal.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeJournalEntry {
+    pub recorded_at: String,
+    pub state: RuntimeState,
 }
 
 /// Complete issue context stored locally
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct IssueContext {
+    /// Cached repository primer, read by agents before the task itself
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primer: Option<String>,
     pub parent: ParentIssueContext,
     pub sub_tasks: Vec<SubTaskContext>,
     pub metadata: ContextMetadata,
@@ -454,6 +527,13 @@ pub enum PendingUpdateData {
         issue_id: String,
         identifier: String,
         description: String,
+        /// Description as last pulled, to tell a remote edit from our own
+        #[serde(
+            rename = "oldDescription",
+            default,
+            skip_serializing_if = "Option::is_none"
+        )]
+        old_description: Option<String>,
     },
     #[serde(rename = "add_label")]
     AddLabel {
@@ -469,6 +549,26 @@ pub enum PendingUpdateData {
         identifier: String,
         label: String,
     },
+    #[serde(rename = "add_relation")]
+    AddRelation {
+        #[serde(rename = "issueId")]
+        issue_id: String,
+        identifier: String,
+        #[serde(rename = "blockerId")]
+        blocker_id: String,
+        #[serde(rename = "blockerIdentifier")]
+        blocker_identifier: String,
+    },
+    #[serde(rename = "remove_relation")]
+    RemoveRelation {
+        #[serde(rename = "issueId")]
+        issue_id: String,
+        identifier: String,
+        #[serde(rename = "blockerId")]
+        blocker_id: String,
+        #[serde(rename = "blockerIdentifier")]
+        blocker_identifier: String,
+    },
 }
 
 /// A pending update with metadata wrapper
@@ -479,10 +579,22 @@ pub struct PendingUpdate {
     pub created_at: String,
     pub synced_at: Option<String>,
     pub error: Option<String>,
+    /// Set when the backend changed since the update was queued; the update
+    /// is held back until `mobius push --resolve`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<UpdateConflict>,
     #[serde(flatten)]
     pub data: PendingUpdateData,
 }
 
+/// Remote value found to differ from the local snapshot at push time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateConflict {
+    pub remote_value: String,
+    pub detected_at: String,
+}
+
 /// Queue of pending updates waiting to be synced
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -580,6 +692,22 @@ mod tests {
         assert_eq!(parsed.status, "ready");
         assert_eq!(parsed.blocked_by.len(), 1);
         assert_eq!(parsed.blocked_by[0].identifier, "MOB-100");
+        assert_eq!(parsed.task_type, TaskType::Agent);
+        assert!(serde_json::to_value(&parsed).unwrap().get("type").is_none());
+    }
+
+    #[test]
+    fn test_subtask_context_human_type() {
+        let json = serde_json::json!({
+            "id": "task-003",
+            "title": "Obtain API credentials",
+            "status": "pending",
+            "type": "human"
+        });
+
+        let parsed: SubTaskContext = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.task_type, TaskType::Human);
+        assert_eq!(serde_json::to_value(&parsed).unwrap()["type"], "human");
     }
 
     #[test]
@@ -603,6 +731,7 @@ mod tests {
     #[test]
     fn test_issue_context_serde_roundtrip() {
         let ctx = IssueContext {
+            primer: None,
             parent: ParentIssueContext {
                 id: "abc".to_string(),
                 identifier: "MOB-100".to_string(),
@@ -830,6 +959,7 @@ mod tests {
             created_at: "2024-01-01T00:00:00Z".to_string(),
             synced_at: None,
             error: None,
+            conflict: None,
             data: PendingUpdateData::StatusChange {
                 issue_id: "abc".to_string(),
                 identifier: "MOB-101".to_string(),
@@ -874,6 +1004,7 @@ mod tests {
                 issue_id: "a".into(),
                 identifier: "MOB-1".into(),
                 description: "New desc".into(),
+                old_description: Some("Old desc".into()),
             },
             PendingUpdateData::AddLabel {
                 issue_id: "a".into(),
@@ -885,6 +1016,18 @@ mod tests {
                 identifier: "MOB-1".into(),
                 label: "Bug".into(),
             },
+            PendingUpdateData::AddRelation {
+                issue_id: "a".into(),
+                identifier: "MOB-1".into(),
+                blocker_id: "b".into(),
+                blocker_identifier: "MOB-2".into(),
+            },
+            PendingUpdateData::RemoveRelation {
+                issue_id: "a".into(),
+                identifier: "MOB-1".into(),
+                blocker_id: "b".into(),
+                blocker_identifier: "MOB-2".into(),
+            },
         ];
 
         let expected_types = [
@@ -894,6 +1037,8 @@ mod tests {
             "update_description",
             "add_label",
             "remove_label",
+            "add_relation",
+            "remove_relation",
         ];
 
         for (data, expected_type) in variants.into_iter().zip(expected_types.iter()) {
@@ -902,6 +1047,7 @@ mod tests {
                 created_at: "t".into(),
                 synced_at: None,
                 error: None,
+                conflict: None,
                 data,
             };
             let json = serde_json::to_string(&update).unwrap();
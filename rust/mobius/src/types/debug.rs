@@ -46,6 +46,7 @@ pub fn verbosity_event_types(verbosity: DebugVerbosity) -> &'static [DebugEventT
         DebugVerbosity::Minimal => &[
             DebugEventType::TaskStateChange,
             DebugEventType::BackendStatusUpdate,
+            DebugEventType::StateDrift,
         ],
         DebugVerbosity::Normal => &[
             DebugEventType::TaskStateChange,
@@ -53,6 +54,8 @@ pub fn verbosity_event_types(verbosity: DebugVerbosity) -> &'static [DebugEventT
             DebugEventType::PendingUpdatePush,
             DebugEventType::BackendStatusUpdate,
             DebugEventType::TuiStateReceive,
+            DebugEventType::StateDrift,
+            DebugEventType::LockContention,
         ],
         DebugVerbosity::Verbose => &[
             DebugEventType::RuntimeStateWrite,
@@ -65,6 +68,9 @@ pub fn verbosity_event_types(verbosity: DebugVerbosity) -> &'static [DebugEventT
             DebugEventType::LockAcquire,
             DebugEventType::LockRelease,
             DebugEventType::TuiStateReceive,
+            DebugEventType::StateDrift,
+            DebugEventType::LockContention,
+            DebugEventType::PaneCapture,
         ],
     }
 }
@@ -132,16 +138,19 @@ mod tests {
     #[test]
     fn test_verbosity_levels() {
         let minimal = verbosity_event_types(DebugVerbosity::Minimal);
-        assert_eq!(minimal.len(), 2);
+        assert_eq!(minimal.len(), 3);
         assert!(minimal.contains(&DebugEventType::TaskStateChange));
+        assert!(minimal.contains(&DebugEventType::StateDrift));
 
         let normal = verbosity_event_types(DebugVerbosity::Normal);
-        assert_eq!(normal.len(), 5);
+        assert_eq!(normal.len(), 7);
         assert!(normal.contains(&DebugEventType::PendingUpdateQueue));
+        assert!(normal.contains(&DebugEventType::LockContention));
 
         let verbose = verbosity_event_types(DebugVerbosity::Verbose);
-        assert_eq!(verbose.len(), 10);
+        assert_eq!(verbose.len(), 13);
         assert!(verbose.contains(&DebugEventType::LockAcquire));
         assert!(verbose.contains(&DebugEventType::RuntimeStateWrite));
+        assert!(verbose.contains(&DebugEventType::PaneCapture));
     }
 }
@@ -4,12 +4,14 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 
 /// Backend type for issue tracking
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Backend {
     #[default]
     Linear,
     Jira,
+    Github,
+    Gitlab,
     Local,
 }
 
@@ -20,6 +22,8 @@ pub enum AgentRuntime {
     #[default]
     Claude,
     Opencode,
+    Codex,
+    Aider,
 }
 
 impl fmt::Display for AgentRuntime {
@@ -27,6 +31,8 @@ impl fmt::Display for AgentRuntime {
         match self {
             AgentRuntime::Claude => write!(f, "claude"),
             AgentRuntime::Opencode => write!(f, "opencode"),
+            AgentRuntime::Codex => write!(f, "codex"),
+            AgentRuntime::Aider => write!(f, "aider"),
         }
     }
 }
@@ -38,18 +44,331 @@ impl FromStr for AgentRuntime {
         match s.to_lowercase().as_str() {
             "claude" => Ok(AgentRuntime::Claude),
             "opencode" => Ok(AgentRuntime::Opencode),
+            "codex" => Ok(AgentRuntime::Codex),
+            "aider" => Ok(AgentRuntime::Aider),
             _ => Err(format!(
-                "Unknown runtime: '{s}'. Expected: claude, opencode"
+                "Unknown runtime: '{s}'. Expected: claude, opencode, codex, aider"
             )),
         }
     }
 }
 
+/// How agents are spawned and monitored during loop execution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionDriver {
+    /// One tmux pane per agent, completion detected from pane content
+    #[default]
+    Tmux,
+    /// Child processes with piped output, for machines without tmux
+    Process,
+}
+
+impl fmt::Display for ExecutionDriver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutionDriver::Tmux => write!(f, "tmux"),
+            ExecutionDriver::Process => write!(f, "process"),
+        }
+    }
+}
+
+impl FromStr for ExecutionDriver {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "tmux" => Ok(ExecutionDriver::Tmux),
+            "process" => Ok(ExecutionDriver::Process),
+            _ => Err(format!(
+                "Unknown execution driver: '{s}'. Expected: tmux, process"
+            )),
+        }
+    }
+}
+
+/// How the executor learns that an agent has finished
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompletionProtocol {
+    /// Prefer `$MOBIUS_RESULT_FILE`, fall back to scraping agent output
+    #[default]
+    Auto,
+    /// Only trust the result file written by the agent
+    ResultFile,
+    /// Only scrape STATUS markers from agent output (legacy behavior)
+    Capture,
+}
+
+impl fmt::Display for CompletionProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompletionProtocol::Auto => write!(f, "auto"),
+            CompletionProtocol::ResultFile => write!(f, "result_file"),
+            CompletionProtocol::Capture => write!(f, "capture"),
+        }
+    }
+}
+
+/// Container runtime for the agent sandbox (`execution.container.engine`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerEngine {
+    /// Docker if installed, otherwise Podman
+    #[default]
+    Auto,
+    Docker,
+    Podman,
+}
+
+impl fmt::Display for ContainerEngine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerEngine::Auto => write!(f, "auto"),
+            ContainerEngine::Docker => write!(f, "docker"),
+            ContainerEngine::Podman => write!(f, "podman"),
+        }
+    }
+}
+
+/// How `execution.limits` are enforced on agent commands
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitEnforcer {
+    /// systemd when a user session is available, otherwise ulimit
+    #[default]
+    Auto,
+    /// Transient `systemd-run --user --scope` cgroup per agent
+    Systemd,
+    /// Soft rlimits set in a subshell; per process, no CPU share cap
+    Ulimit,
+}
+
+impl fmt::Display for LimitEnforcer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitEnforcer::Auto => write!(f, "auto"),
+            LimitEnforcer::Systemd => write!(f, "systemd"),
+            LimitEnforcer::Ulimit => write!(f, "ulimit"),
+        }
+    }
+}
+
+/// Order in which ready sub-tasks are dispatched (`loop.strategy`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulingStrategy {
+    /// Tracker priority first, then downstream fan-out, then identifier
+    #[default]
+    Priority,
+    /// Tasks unblocking the most transitive downstream work first, then
+    /// priority, then identifier
+    Fanout,
+}
+
+impl fmt::Display for SchedulingStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchedulingStrategy::Priority => write!(f, "priority"),
+            SchedulingStrategy::Fanout => write!(f, "fanout"),
+        }
+    }
+}
+
+/// Built-in dashboard color theme (`execution.tui.theme`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TuiTheme {
+    /// Nord colors for dark terminals
+    #[default]
+    Dark,
+    /// Darker text and accents for light terminal backgrounds
+    Light,
+    /// The terminal's own bright ANSI colors on black
+    HighContrast,
+    /// Okabe-Ito status colors that stay distinct with red-green color blindness
+    ColorblindSafe,
+}
+
+impl fmt::Display for TuiTheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuiTheme::Dark => write!(f, "dark"),
+            TuiTheme::Light => write!(f, "light"),
+            TuiTheme::HighContrast => write!(f, "high-contrast"),
+            TuiTheme::ColorblindSafe => write!(f, "colorblind-safe"),
+        }
+    }
+}
+
+/// How `mobius submit --auto-merge` merges the PR (`submit.merge_method`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MergeMethod {
+    #[default]
+    Squash,
+    Merge,
+    Rebase,
+}
+
+impl fmt::Display for MergeMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MergeMethod::Squash => write!(f, "squash"),
+            MergeMethod::Merge => write!(f, "merge"),
+            MergeMethod::Rebase => write!(f, "rebase"),
+        }
+    }
+}
+
+/// Where high-churn local state lives (`state_store`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateStoreKind {
+    /// JSON files under `.mobius/issues/`
+    #[default]
+    Files,
+    /// `.mobius/state.db`; needs a build with the `sqlite` feature
+    Sqlite,
+}
+
+impl fmt::Display for StateStoreKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateStoreKind::Files => write!(f, "files"),
+            StateStoreKind::Sqlite => write!(f, "sqlite"),
+        }
+    }
+}
+
+/// Identity agent commits are authored as (`execution.attribution.author`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommitAuthor {
+    /// The operator's own git identity (`user.name` / `user.email`)
+    #[default]
+    User,
+    /// The configured bot identity
+    Bot,
+}
+
+impl fmt::Display for CommitAuthor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommitAuthor::User => write!(f, "user"),
+            CommitAuthor::Bot => write!(f, "bot"),
+        }
+    }
+}
+
+/// Lifecycle events delivered to webhooks (`notifications.webhooks[].events`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// An agent was dispatched for a sub-task
+    TaskStarted,
+    /// A sub-task was verified as done
+    TaskCompleted,
+    /// A sub-task failed and will not be retried
+    TaskFailed,
+    /// The loop stopped (completed, failed, or interrupted)
+    LoopFinished,
+    /// A pull or merge request was opened
+    PrCreated,
+    /// A provider incident switched remaining tasks to `execution.fallback`
+    ModelFallback,
+    /// A human sub-task is unblocked and waiting on someone
+    HumanActionNeeded,
+}
+
+impl fmt::Display for NotificationEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotificationEvent::TaskStarted => write!(f, "task_started"),
+            NotificationEvent::TaskCompleted => write!(f, "task_completed"),
+            NotificationEvent::TaskFailed => write!(f, "task_failed"),
+            NotificationEvent::LoopFinished => write!(f, "loop_finished"),
+            NotificationEvent::PrCreated => write!(f, "pr_created"),
+            NotificationEvent::ModelFallback => write!(f, "model_fallback"),
+            NotificationEvent::HumanActionNeeded => write!(f, "human_action_needed"),
+        }
+    }
+}
+
+/// Failure kinds the loop may retry (`execution.retry_on`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryCondition {
+    /// Agent exceeded its execution timeout
+    Timeout,
+    /// Agent reported VERIFICATION_FAILED
+    VerificationFailed,
+    /// Any other failure (spawn errors, missing status, blocked)
+    Error,
+}
+
+impl fmt::Display for RetryCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryCondition::Timeout => write!(f, "timeout"),
+            RetryCondition::VerificationFailed => write!(f, "verification_failed"),
+            RetryCondition::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// Why an agent attempt failed, recorded in the iteration log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureClass {
+    /// Tests, typecheck, or lint failed
+    VerifyFailure,
+    /// Agent exceeded its execution timeout
+    Timeout,
+    /// Runtime API rate limit, overload, or usage cap
+    RateLimit,
+    /// Git merge or rebase conflict
+    MergeConflict,
+    /// Agent stopped early: partial, blocked, needs work, or out of turns
+    AgentGaveUp,
+    /// No recognizable signal
+    Unknown,
+}
+
+impl fmt::Display for FailureClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FailureClass::VerifyFailure => write!(f, "verify_failure"),
+            FailureClass::Timeout => write!(f, "timeout"),
+            FailureClass::RateLimit => write!(f, "rate_limit"),
+            FailureClass::MergeConflict => write!(f, "merge_conflict"),
+            FailureClass::AgentGaveUp => write!(f, "agent_gave_up"),
+            FailureClass::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl Backend {
+    /// Whether `task_id` has this backend's issue ID shape: `ABC-123` for
+    /// Linear and Jira, a number for GitHub and GitLab, `LOC-1`/`task-1`
+    /// for local issues.
+    pub fn is_valid_task_id(&self, task_id: &str) -> bool {
+        let pattern = match self {
+            Backend::Linear | Backend::Jira => r"^[A-Z]+-\d+$",
+            Backend::Github | Backend::Gitlab => r"^\d+$",
+            Backend::Local => r"^(LOC-\d+|task-\d+)$",
+        };
+        regex::Regex::new(pattern).unwrap().is_match(task_id)
+    }
+}
+
 impl fmt::Display for Backend {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Backend::Linear => write!(f, "linear"),
             Backend::Jira => write!(f, "jira"),
+            Backend::Github => write!(f, "github"),
+            Backend::Gitlab => write!(f, "gitlab"),
             Backend::Local => write!(f, "local"),
         }
     }
@@ -62,9 +381,11 @@ impl FromStr for Backend {
         match s.to_lowercase().as_str() {
             "linear" => Ok(Backend::Linear),
             "jira" => Ok(Backend::Jira),
+            "github" => Ok(Backend::Github),
+            "gitlab" => Ok(Backend::Gitlab),
             "local" => Ok(Backend::Local),
             _ => Err(format!(
-                "Unknown backend: '{s}'. Expected: linear, jira, local"
+                "Unknown backend: '{s}'. Expected: linear, jira, github, gitlab, local"
             )),
         }
     }
@@ -128,7 +449,7 @@ pub enum ProjectType {
 }
 
 /// Build system detected from project configuration files
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum BuildSystem {
     Npm,
@@ -179,6 +500,33 @@ impl fmt::Display for TaskStatus {
     }
 }
 
+/// Who works a sub-task (`"type"` in the task file)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskType {
+    /// Dispatched to an agent
+    #[default]
+    Agent,
+    /// An action for a person (credentials, sign-off); never dispatched, and
+    /// its dependents wait until it is marked done
+    Human,
+}
+
+impl TaskType {
+    pub fn is_agent(&self) -> bool {
+        *self == TaskType::Agent
+    }
+}
+
+impl fmt::Display for TaskType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskType::Agent => write!(f, "agent"),
+            TaskType::Human => write!(f, "human"),
+        }
+    }
+}
+
 /// Jira auth method
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -195,6 +543,20 @@ pub enum SessionStatus {
     Completed,
     Failed,
     Paused,
+    /// Stopped by SIGINT/SIGTERM; resumable with `mobius resume`
+    Interrupted,
+    /// Stopped with `mobius cancel`
+    Cancelled,
+}
+
+/// Dashboard action on a sub-task, queued for the running loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TaskAction {
+    /// Dispatch a failed task again with a fresh retry budget
+    Retry,
+    /// Treat the task as done so its dependents can start
+    Skip,
 }
 
 /// Verification result for individual checks
@@ -258,6 +620,8 @@ pub enum PendingUpdateType {
     UpdateDescription,
     AddLabel,
     RemoveLabel,
+    AddRelation,
+    RemoveRelation,
 }
 
 /// Debug event types
@@ -274,6 +638,12 @@ pub enum DebugEventType {
     LockAcquire,
     LockRelease,
     TuiStateReceive,
+    /// Local state disagrees with what the loop or tracker believes
+    StateDrift,
+    /// Waiting on a lock another process holds
+    LockContention,
+    /// Snapshot of an agent's tmux pane
+    PaneCapture,
 }
 
 /// Debug event source
@@ -284,6 +654,8 @@ pub enum DebugEventSource {
     Tui,
     Push,
     ContextGenerator,
+    Executor,
+    Lock,
 }
 
 /// Debug verbosity level
@@ -295,6 +667,21 @@ pub enum DebugVerbosity {
     Verbose,
 }
 
+impl FromStr for DebugVerbosity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "minimal" => Ok(DebugVerbosity::Minimal),
+            "normal" => Ok(DebugVerbosity::Normal),
+            "verbose" => Ok(DebugVerbosity::Verbose),
+            _ => Err(format!(
+                "Unknown debug verbosity: '{s}'. Expected: minimal, normal, verbose"
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,6 +691,8 @@ mod tests {
         assert_eq!(Backend::from_str("linear").unwrap(), Backend::Linear);
         assert_eq!(Backend::from_str("Linear").unwrap(), Backend::Linear);
         assert_eq!(Backend::from_str("JIRA").unwrap(), Backend::Jira);
+        assert_eq!(Backend::from_str("GitHub").unwrap(), Backend::Github);
+        assert_eq!(Backend::from_str("gitlab").unwrap(), Backend::Gitlab);
         assert_eq!(Backend::from_str("local").unwrap(), Backend::Local);
         assert!(Backend::from_str("unknown").is_err());
     }
@@ -312,9 +701,23 @@ mod tests {
     fn test_backend_display() {
         assert_eq!(Backend::Linear.to_string(), "linear");
         assert_eq!(Backend::Jira.to_string(), "jira");
+        assert_eq!(Backend::Github.to_string(), "github");
+        assert_eq!(Backend::Gitlab.to_string(), "gitlab");
         assert_eq!(Backend::Local.to_string(), "local");
     }
 
+    #[test]
+    fn test_backend_is_valid_task_id() {
+        assert!(Backend::Linear.is_valid_task_id("MOB-123"));
+        assert!(Backend::Jira.is_valid_task_id("PROJ-1"));
+        assert!(!Backend::Linear.is_valid_task_id("mob-123"));
+        assert!(Backend::Github.is_valid_task_id("42"));
+        assert!(!Backend::Gitlab.is_valid_task_id("MOB-1"));
+        assert!(Backend::Local.is_valid_task_id("LOC-7"));
+        assert!(Backend::Local.is_valid_task_id("task-7"));
+        assert!(!Backend::Local.is_valid_task_id("MOB-7"));
+    }
+
     #[test]
     fn test_runtime_from_str() {
         assert_eq!(
@@ -325,6 +728,14 @@ mod tests {
             AgentRuntime::from_str("Opencode").unwrap(),
             AgentRuntime::Opencode
         );
+        assert_eq!(
+            AgentRuntime::from_str("codex").unwrap(),
+            AgentRuntime::Codex
+        );
+        assert_eq!(
+            AgentRuntime::from_str("AIDER").unwrap(),
+            AgentRuntime::Aider
+        );
         assert!(AgentRuntime::from_str("unknown").is_err());
     }
 
@@ -334,6 +745,42 @@ mod tests {
         assert_eq!(AgentRuntime::Opencode.to_string(), "opencode");
     }
 
+    #[test]
+    fn test_execution_driver_from_str() {
+        assert_eq!(
+            ExecutionDriver::from_str("tmux").unwrap(),
+            ExecutionDriver::Tmux
+        );
+        assert_eq!(
+            ExecutionDriver::from_str("Process").unwrap(),
+            ExecutionDriver::Process
+        );
+        assert!(ExecutionDriver::from_str("screen").is_err());
+        assert_eq!(ExecutionDriver::default(), ExecutionDriver::Tmux);
+    }
+
+    #[test]
+    fn test_completion_protocol_serde() {
+        let parsed: CompletionProtocol = serde_json::from_str("\"result_file\"").unwrap();
+        assert_eq!(parsed, CompletionProtocol::ResultFile);
+        assert_eq!(CompletionProtocol::default(), CompletionProtocol::Auto);
+        assert_eq!(CompletionProtocol::Capture.to_string(), "capture");
+    }
+
+    #[test]
+    fn test_retry_condition_serde() {
+        let parsed: Vec<RetryCondition> =
+            serde_json::from_str(r#"["timeout", "verification_failed"]"#).unwrap();
+        assert_eq!(
+            parsed,
+            vec![RetryCondition::Timeout, RetryCondition::VerificationFailed]
+        );
+        assert_eq!(
+            RetryCondition::VerificationFailed.to_string(),
+            "verification_failed"
+        );
+    }
+
     #[test]
     fn test_model_from_str() {
         assert_eq!(Model::from_str("opus").unwrap(), Model::Opus);
@@ -473,5 +920,10 @@ mod tests {
         assert_eq!(json, "\"verbose\"");
         let deserialized: DebugVerbosity = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized, verbosity);
+        assert_eq!(
+            DebugVerbosity::from_str("Verbose").unwrap(),
+            DebugVerbosity::Verbose
+        );
+        assert!(DebugVerbosity::from_str("loud").is_err());
     }
 }
@@ -6,7 +6,10 @@ pub mod task_graph;
 
 // Re-export commonly used types for convenience
 pub use config::{
-    ExecutionConfig, ExecutionState, LinearConfig, LoopConfig, PathConfig, ProjectDetectionResult,
+    AiderConfig, AttributionConfig, BuildCacheConfig, CheckoutConfig, ContainerConfig,
+    ExecutionConfig, ExecutionState, HttpConfig, LinearConfig, LoopConfig, ModelFallback,
+    NotificationsConfig, ObservabilityConfig, OutputArchiveConfig, PathConfig, PoolConfig,
+    ProjectDetectionResult, ResourceLimits, RuntimeRule, SkillRule, SubmitConfig,
     VerificationCommands, VerificationConfig,
 };
 pub use context::{
@@ -16,8 +19,10 @@ pub use context::{
 };
 pub use debug::{DebugConfig, DebugEvent};
 pub use enums::{
-    AgentRuntime, Backend, BuildSystem, DebugEventType, DebugVerbosity, Model, PendingUpdateType,
-    Platform, ProjectType, SkillOutputStatus, TaskStatus,
+    AgentRuntime, Backend, BuildSystem, CommitAuthor, CompletionProtocol, ContainerEngine,
+    DebugEventType, DebugVerbosity, ExecutionDriver, LimitEnforcer, MergeMethod, Model,
+    NotificationEvent, PendingUpdateType, Platform, ProjectType, RetryCondition,
+    SchedulingStrategy, SkillOutputStatus, StateStoreKind, TaskStatus, TaskType, TuiTheme,
 };
 pub use task_graph::{
     GraphStats, LinearIssue, ParentIssue, Relation, Relations, SubTask, TaskGraph, TaskScoring,
@@ -1,17 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use serde::{Deserialize, Serialize};
 
-use super::enums::{Model, TaskStatus};
+use super::config::StatusMapping;
+use super::enums::{AgentRuntime, Model, SchedulingStrategy, TaskStatus, TaskType};
 
 /// Scoring data for per-task model routing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskScoring {
     pub complexity: u8,
     pub risk: u8,
     pub recommended_model: Model,
     pub rationale: String,
+    /// Runtime to execute with; overrides `execution.runtime_rules`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recommended_runtime: Option<AgentRuntime>,
 }
 
 /// Represents a sub-task in the dependency graph
@@ -27,6 +31,17 @@ pub struct SubTask {
     pub git_branch_name: String,
     #[serde(default)]
     pub scoring: Option<TaskScoring>,
+    /// Dispatch priority, 1 = highest; unset tasks go after prioritized ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Agent timeout override from the spec's `### Timeout` section
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_minutes: Option<u32>,
+    /// Human tasks are never dispatched; dependents wait until they are done
+    #[serde(default, skip_serializing_if = "TaskType::is_agent")]
+    pub task_type: TaskType,
 }
 
 /// The complete task dependency graph
@@ -73,6 +88,17 @@ pub struct LinearIssue {
     pub relations: Option<Relations>,
     #[serde(default)]
     pub scoring: Option<TaskScoring>,
+    /// Tracker priority, 1 = highest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Agent timeout override from the spec's `### Timeout` section
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_minutes: Option<u32>,
+    /// `type` from a local task file; a `human` label marks backend issues
+    #[serde(default, skip_serializing_if = "TaskType::is_agent")]
+    pub task_type: TaskType,
 }
 
 /// Blocking relations for an issue
@@ -99,7 +125,7 @@ pub fn map_linear_status(status: &str) -> TaskStatus {
     // Done states
     if matches!(
         status_lower.as_str(),
-        "done" | "completed" | "cancelled" | "canceled"
+        "done" | "completed" | "closed" | "cancelled" | "canceled" | "skipped"
     ) {
         return TaskStatus::Done;
     }
@@ -116,6 +142,13 @@ pub fn map_linear_status(status: &str) -> TaskStatus {
     TaskStatus::Pending
 }
 
+/// Map backend status string to TaskStatus, checking configured overrides first
+pub fn map_backend_status(status: &str, mapping: Option<&StatusMapping>) -> TaskStatus {
+    mapping
+        .and_then(|m| m.task_status(status))
+        .unwrap_or_else(|| map_linear_status(status))
+}
+
 /// Calculate whether a pending task is ready or blocked
 fn calculate_task_status(task: &SubTask, all_tasks: &HashMap<String, SubTask>) -> TaskStatus {
     if task.blocked_by.is_empty() {
@@ -125,7 +158,9 @@ fn calculate_task_status(task: &SubTask, all_tasks: &HashMap<String, SubTask>) -
     let all_blockers_done = task.blocked_by.iter().all(|blocker_id| {
         match all_tasks.get(blocker_id) {
             Some(blocker) => blocker.status == TaskStatus::Done,
-            // If blocker not in our graph (external), assume it's done
+            // External URLs block until resolved (see `resolve_external_blockers`)
+            None if is_external_blocker(blocker_id) => false,
+            // Other blockers outside our graph (another parent's issues) are assumed done
             None => true,
         }
     });
@@ -137,6 +172,22 @@ fn calculate_task_status(task: &SubTask, all_tasks: &HashMap<String, SubTask>) -
     }
 }
 
+/// A task's type: local files set it directly, backend issues through a
+/// `human` label.
+fn task_type_of(issue: &LinearIssue) -> TaskType {
+    if issue.labels.iter().any(|l| l.eq_ignore_ascii_case("human")) {
+        TaskType::Human
+    } else {
+        issue.task_type
+    }
+}
+
+/// Whether a blocker is an external URL (another repo's PR, a vendor ticket)
+/// rather than an issue ID.
+pub fn is_external_blocker(blocker_id: &str) -> bool {
+    blocker_id.starts_with("https://") || blocker_id.starts_with("http://")
+}
+
 /// Build a task graph from a list of issues.
 ///
 /// Two-pass algorithm:
@@ -146,6 +197,16 @@ pub fn build_task_graph(
     parent_id: &str,
     parent_identifier: &str,
     issues: &[LinearIssue],
+) -> TaskGraph {
+    build_task_graph_with_mapping(parent_id, parent_identifier, issues, None)
+}
+
+/// Build a task graph, resolving issue statuses through a backend status mapping.
+pub fn build_task_graph_with_mapping(
+    parent_id: &str,
+    parent_identifier: &str,
+    issues: &[LinearIssue],
+    status_mapping: Option<&StatusMapping>,
 ) -> TaskGraph {
     let mut tasks = HashMap::new();
     let mut edges = HashMap::new();
@@ -168,11 +229,15 @@ pub fn build_task_graph(
             id: issue.id.clone(),
             identifier: issue.identifier.clone(),
             title: issue.title.clone(),
-            status: map_linear_status(&issue.status),
+            status: map_backend_status(&issue.status, status_mapping),
             blocked_by: blocked_by_ids.clone(),
             blocks: blocks_ids,
             git_branch_name: issue.git_branch_name.clone(),
             scoring: issue.scoring.clone(),
+            priority: issue.priority,
+            labels: issue.labels.clone(),
+            timeout_minutes: issue.timeout_minutes,
+            task_type: task_type_of(issue),
         };
 
         tasks.insert(issue.id.clone(), task);
@@ -206,17 +271,71 @@ pub fn build_task_graph(
 /// Get all tasks that are ready for execution (no unresolved blockers).
 ///
 /// Includes both 'ready' tasks and 'in_progress' tasks that haven't completed yet.
+/// Human tasks are left out; see [`get_awaiting_human_tasks`].
 /// Results are sorted by identifier for consistent ordering.
 pub fn get_ready_tasks(graph: &TaskGraph) -> Vec<&SubTask> {
     let mut ready: Vec<&SubTask> = graph
         .tasks
         .values()
         .filter(|t| t.status == TaskStatus::Ready || t.status == TaskStatus::InProgress)
+        .filter(|t| t.task_type.is_agent())
         .collect();
-    ready.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    ready.sort_by_cached_key(|t| dispatch_key(graph, t, SchedulingStrategy::Priority));
     ready
 }
 
+/// Human tasks whose blockers are done but which are not done themselves:
+/// the actions the loop is waiting on someone to take.
+pub fn get_awaiting_human_tasks(graph: &TaskGraph) -> Vec<&SubTask> {
+    let mut awaiting: Vec<&SubTask> = graph
+        .tasks
+        .values()
+        .filter(|t| t.task_type == TaskType::Human)
+        .filter(|t| t.status == TaskStatus::Ready || t.status == TaskStatus::InProgress)
+        .collect();
+    awaiting.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    awaiting
+}
+
+/// Sort tasks into dispatch order for `strategy`; see [`dispatch_key`].
+pub fn sort_for_dispatch(graph: &TaskGraph, tasks: &mut [SubTask], strategy: SchedulingStrategy) {
+    tasks.sort_by_cached_key(|t| dispatch_key(graph, t, strategy));
+}
+
+/// Dispatch order. `Priority`: priority (unset last), then tasks that unblock
+/// the most outstanding downstream work, then identifier. `Fanout` swaps the
+/// first two so the widest unblockers always go first.
+fn dispatch_key(
+    graph: &TaskGraph,
+    task: &SubTask,
+    strategy: SchedulingStrategy,
+) -> (usize, usize, String) {
+    let priority = usize::from(task.priority.unwrap_or(u8::MAX));
+    let fan_out = usize::MAX - fan_out(graph, &task.id);
+    let (first, second) = match strategy {
+        SchedulingStrategy::Priority => (priority, fan_out),
+        SchedulingStrategy::Fanout => (fan_out, priority),
+    };
+    (first, second, task.identifier.clone())
+}
+
+/// Number of unfinished tasks that transitively wait on `task_id`
+fn fan_out(graph: &TaskGraph, task_id: &str) -> usize {
+    let mut waiting: HashSet<&str> = HashSet::new();
+    let mut stack = vec![task_id];
+    while let Some(id) = stack.pop() {
+        for task in graph.tasks.values() {
+            if task.status != TaskStatus::Done
+                && task.blocked_by.iter().any(|b| b == id)
+                && waiting.insert(task.id.as_str())
+            {
+                stack.push(task.id.as_str());
+            }
+        }
+    }
+    waiting.len()
+}
+
 /// Get all tasks that are blocked (have unresolved blockers)
 pub fn get_blocked_tasks(graph: &TaskGraph) -> Vec<&SubTask> {
     let mut blocked: Vec<&SubTask> = graph
@@ -382,6 +501,344 @@ pub fn get_verification_task(graph: &TaskGraph) -> Option<&SubTask> {
     })
 }
 
+/// Result of validating a task graph
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphValidation {
+    /// Dependency cycles as task IDs in blocking order; each task blocks the
+    /// next and the last blocks the first
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl GraphValidation {
+    pub fn is_valid(&self) -> bool {
+        self.cycles.is_empty()
+    }
+}
+
+/// A `blockedBy` relation removed to break a cycle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemovedEdge {
+    /// Task that was blocked
+    pub task_id: String,
+    /// Blocker it no longer waits for
+    pub blocker_id: String,
+}
+
+/// Detect cycles in the `blockedBy` relations between tasks in the graph.
+///
+/// A cyclic graph never finishes: every task in the cycle waits on another.
+/// Cycles are reported once each, rotated to start at their lowest identifier.
+pub fn validate_graph(graph: &TaskGraph) -> GraphValidation {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Mark {
+        Visiting,
+        Visited,
+    }
+
+    fn visit(
+        graph: &TaskGraph,
+        id: &str,
+        marks: &mut HashMap<String, Mark>,
+        stack: &mut Vec<String>,
+        cycles: &mut Vec<Vec<String>>,
+    ) {
+        marks.insert(id.to_string(), Mark::Visiting);
+        stack.push(id.to_string());
+
+        for blocker in sorted_blockers(graph, id) {
+            match marks.get(blocker.as_str()) {
+                Some(Mark::Visiting) => {
+                    // Stack runs blocked -> blocker; reverse into blocking order
+                    let start = stack.iter().position(|s| *s == blocker).unwrap_or(0);
+                    let mut cycle: Vec<String> = stack[start..].iter().rev().cloned().collect();
+                    let lowest = (0..cycle.len())
+                        .min_by_key(|&i| identifier_of(graph, &cycle[i]))
+                        .unwrap_or(0);
+                    cycle.rotate_left(lowest);
+                    if !cycles.contains(&cycle) {
+                        cycles.push(cycle);
+                    }
+                }
+                Some(Mark::Visited) => {}
+                None => visit(graph, &blocker, marks, stack, cycles),
+            }
+        }
+
+        stack.pop();
+        marks.insert(id.to_string(), Mark::Visited);
+    }
+
+    let mut ids: Vec<&String> = graph.tasks.keys().collect();
+    ids.sort_by_key(|id| identifier_of(graph, id));
+
+    let mut marks = HashMap::new();
+    let mut cycles = Vec::new();
+    for id in ids {
+        if !marks.contains_key(id.as_str()) {
+            visit(graph, id, &mut marks, &mut Vec::new(), &mut cycles);
+        }
+    }
+    GraphValidation { cycles }
+}
+
+/// Format a cycle from `validate_graph` as `MOB-1 -> MOB-2 -> MOB-1`.
+pub fn format_cycle(graph: &TaskGraph, cycle: &[String]) -> String {
+    cycle
+        .iter()
+        .chain(cycle.first())
+        .map(|id| identifier_of(graph, id))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// Break every dependency cycle and return the repaired graph.
+///
+/// Heuristic: within each cycle, the task with the lowest identifier was
+/// usually created first and is the intended root, so its `blockedBy` edge to
+/// the last task in the cycle is the one dropped. Repeats until the graph is
+/// acyclic, then recalculates ready/blocked status for unstarted tasks.
+pub fn break_cycles(graph: &TaskGraph) -> (TaskGraph, Vec<RemovedEdge>) {
+    let mut repaired = graph.clone();
+    let mut removed = Vec::new();
+
+    loop {
+        let validation = validate_graph(&repaired);
+        let Some(cycle) = validation.cycles.first() else {
+            break;
+        };
+        let root = cycle[0].clone();
+        let blocker = cycle[cycle.len() - 1].clone();
+
+        if let Some(task) = repaired.tasks.get_mut(&root) {
+            task.blocked_by.retain(|b| *b != blocker);
+        }
+        if let Some(task) = repaired.tasks.get_mut(&blocker) {
+            task.blocks.retain(|b| *b != root);
+        }
+        if let Some(edges) = repaired.edges.get_mut(&root) {
+            edges.retain(|b| *b != blocker);
+        }
+        removed.push(RemovedEdge {
+            task_id: root,
+            blocker_id: blocker,
+        });
+    }
+
+    if !removed.is_empty() {
+        recalculate_unstarted(&mut repaired);
+    }
+
+    (repaired, removed)
+}
+
+/// Unresolved external blockers, as `(task_id, url)` sorted by task identifier
+pub fn get_external_blockers(graph: &TaskGraph) -> Vec<(&SubTask, &str)> {
+    let mut external: Vec<(&SubTask, &str)> = graph
+        .tasks
+        .values()
+        .flat_map(|task| {
+            task.blocked_by
+                .iter()
+                .filter(|b| is_external_blocker(b) && !graph.tasks.contains_key(*b))
+                .map(move |b| (task, b.as_str()))
+        })
+        .collect();
+    external.sort_by(|a, b| a.0.identifier.cmp(&b.0.identifier).then(a.1.cmp(b.1)));
+    external
+}
+
+/// Drop resolved external blockers and return the updated graph.
+///
+/// Tasks whose remaining blockers are all done become ready.
+pub fn resolve_external_blockers(graph: &TaskGraph, resolved: &HashSet<String>) -> TaskGraph {
+    let mut updated = graph.clone();
+    let mut changed = false;
+
+    for (id, task) in updated.tasks.iter_mut() {
+        let before = task.blocked_by.len();
+        task.blocked_by
+            .retain(|b| !(is_external_blocker(b) && resolved.contains(b)));
+        if task.blocked_by.len() != before {
+            changed = true;
+            if let Some(edges) = updated.edges.get_mut(id) {
+                edges.retain(|b| !(is_external_blocker(b) && resolved.contains(b)));
+            }
+        }
+    }
+
+    if changed {
+        recalculate_unstarted(&mut updated);
+    }
+    updated
+}
+
+/// Recalculate ready/blocked status for tasks that have not started
+fn recalculate_unstarted(graph: &mut TaskGraph) {
+    let task_ids: Vec<String> = graph.tasks.keys().cloned().collect();
+    for id in &task_ids {
+        let recalculated = {
+            let t = &graph.tasks[id];
+            matches!(t.status, TaskStatus::Blocked | TaskStatus::Ready)
+                .then(|| calculate_task_status(t, &graph.tasks))
+        };
+        if let Some(status) = recalculated {
+            graph.tasks.get_mut(id).unwrap().status = status;
+        }
+    }
+}
+
+/// Longest chain of unfinished dependent tasks in the graph
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CriticalPath {
+    /// Task IDs from the first blocker to the last blocked task
+    pub task_ids: Vec<String>,
+    /// Sum of task weights along the path
+    pub length: u32,
+}
+
+impl CriticalPath {
+    pub fn contains(&self, task_id: &str) -> bool {
+        self.task_ids.iter().any(|id| id == task_id)
+    }
+
+    /// Whether `blocker_id -> task_id` is a consecutive step on the path
+    pub fn contains_edge(&self, blocker_id: &str, task_id: &str) -> bool {
+        self.task_ids
+            .windows(2)
+            .any(|pair| pair[0] == blocker_id && pair[1] == task_id)
+    }
+}
+
+/// Compute the critical path: the longest chain of unfinished tasks, which
+/// bounds how soon the parent issue can complete however many agents run.
+///
+/// Each task weighs 1, or its complexity score (unscored tasks count as 1)
+/// when `weighted` is set. Done tasks no longer gate completion and are left
+/// out. Edges that close a cycle are ignored. Ties go to the lower identifier.
+pub fn critical_path(graph: &TaskGraph, weighted: bool) -> CriticalPath {
+    fn longest(
+        graph: &TaskGraph,
+        id: &str,
+        weighted: bool,
+        memo: &mut HashMap<String, (u32, Option<String>)>,
+        visiting: &mut Vec<String>,
+    ) -> u32 {
+        if let Some((length, _)) = memo.get(id) {
+            return *length;
+        }
+        visiting.push(id.to_string());
+
+        let mut best: (u32, Option<String>) = (0, None);
+        for blocker in sorted_blockers(graph, id) {
+            let unfinished = graph.tasks[&blocker].status != TaskStatus::Done;
+            if !unfinished || visiting.contains(&blocker) {
+                continue;
+            }
+            let length = longest(graph, &blocker, weighted, memo, visiting);
+            if length > best.0 {
+                best = (length, Some(blocker));
+            }
+        }
+
+        visiting.pop();
+        let task = &graph.tasks[id];
+        let weight = if weighted {
+            task.scoring
+                .as_ref()
+                .map_or(1, |s| u32::from(s.complexity.max(1)))
+        } else {
+            1
+        };
+        let length = best.0 + weight;
+        memo.insert(id.to_string(), (length, best.1));
+        length
+    }
+
+    let mut ids: Vec<&String> = graph
+        .tasks
+        .iter()
+        .filter(|(_, t)| t.status != TaskStatus::Done)
+        .map(|(id, _)| id)
+        .collect();
+    ids.sort_by(|a, b| identifier_of(graph, a).cmp(identifier_of(graph, b)));
+
+    let mut memo = HashMap::new();
+    let mut end: Option<(&String, u32)> = None;
+    for id in ids {
+        let length = longest(graph, id, weighted, &mut memo, &mut Vec::new());
+        if end.map_or(true, |(_, best)| length > best) {
+            end = Some((id, length));
+        }
+    }
+
+    let Some((end_id, length)) = end else {
+        return CriticalPath::default();
+    };
+    let mut task_ids = vec![end_id.clone()];
+    while let Some((_, Some(prev))) = memo.get(task_ids.last().unwrap()) {
+        task_ids.push(prev.clone());
+    }
+    task_ids.reverse();
+    CriticalPath { task_ids, length }
+}
+
+/// Batches the loop would dispatch if every task succeeded: each batch takes
+/// up to `max_parallel` ready tasks in `strategy` order, then marks them done.
+///
+/// Stops after `max_batches` batches (0 = unlimited) or once nothing is ready;
+/// tasks left unscheduled are blocked on something outside the plan.
+pub fn plan_batches(
+    graph: &TaskGraph,
+    max_parallel: usize,
+    max_batches: usize,
+    strategy: SchedulingStrategy,
+) -> Vec<Vec<SubTask>> {
+    let mut graph = graph.clone();
+    let mut batches = Vec::new();
+    while max_batches == 0 || batches.len() < max_batches {
+        let mut ready: Vec<SubTask> = get_ready_tasks(&graph).into_iter().cloned().collect();
+        sort_for_dispatch(&graph, &mut ready, strategy);
+        let batch: Vec<SubTask> = ready.into_iter().take(max_parallel.max(1)).collect();
+        if batch.is_empty() {
+            break;
+        }
+        for task in &batch {
+            graph = update_task_status(&graph, &task.id, TaskStatus::Done);
+        }
+        batches.push(batch);
+    }
+    batches
+}
+
+/// In-graph blockers of a task, ordered by identifier
+fn sorted_blockers(graph: &TaskGraph, task_id: &str) -> Vec<String> {
+    let mut blockers: Vec<String> = graph
+        .tasks
+        .get(task_id)
+        .map(|t| {
+            t.blocked_by
+                .iter()
+                .filter(|b| graph.tasks.contains_key(*b))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    blockers.sort_by(|a, b| identifier_of(graph, a).cmp(identifier_of(graph, b)));
+    blockers.dedup();
+    blockers
+}
+
+fn identifier_of<'a>(graph: &'a TaskGraph, task_id: &'a str) -> &'a str {
+    graph
+        .tasks
+        .get(task_id)
+        .map(|t| t.identifier.as_str())
+        .unwrap_or(task_id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -402,6 +859,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "b".to_string(),
@@ -420,6 +881,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "c".to_string(),
@@ -435,6 +900,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ]
     }
@@ -479,6 +948,10 @@ mod tests {
                 blocks: vec![],
             }),
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         }];
         let graph = build_task_graph("parent-1", "MOB-100", &issues);
         assert_eq!(graph.tasks.get("x").unwrap().status, TaskStatus::Ready);
@@ -495,6 +968,10 @@ mod tests {
                 git_branch_name: String::new(),
                 relations: None,
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "vg".to_string(),
@@ -504,6 +981,10 @@ mod tests {
                 git_branch_name: String::new(),
                 relations: None,
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ];
         let graph = build_task_graph("parent-1", "MOB-100", &issues);
@@ -518,6 +999,8 @@ mod tests {
         assert_eq!(map_linear_status("completed"), TaskStatus::Done);
         assert_eq!(map_linear_status("Cancelled"), TaskStatus::Done);
         assert_eq!(map_linear_status("canceled"), TaskStatus::Done);
+        assert_eq!(map_linear_status("skipped"), TaskStatus::Done);
+        assert_eq!(map_linear_status("closed"), TaskStatus::Done);
         assert_eq!(map_linear_status("In Progress"), TaskStatus::InProgress);
         assert_eq!(map_linear_status("In Review"), TaskStatus::InProgress);
         assert_eq!(map_linear_status("started"), TaskStatus::InProgress);
@@ -526,6 +1009,29 @@ mod tests {
         assert_eq!(map_linear_status("Todo"), TaskStatus::Pending);
     }
 
+    #[test]
+    fn test_build_task_graph_with_status_mapping() {
+        let mut issues = make_chain_issues();
+        issues[0].status = "Code Review".to_string();
+        issues[1].status = "Ready for Dev".to_string();
+
+        let mapping = StatusMapping {
+            from_backend: HashMap::from([
+                ("code review".to_string(), TaskStatus::Done),
+                ("Ready for Dev".to_string(), TaskStatus::Pending),
+            ]),
+            ..Default::default()
+        };
+        let graph = build_task_graph_with_mapping("parent-1", "MOB-100", &issues, Some(&mapping));
+        assert_eq!(graph.tasks["a"].status, TaskStatus::Done);
+        assert_eq!(graph.tasks["b"].status, TaskStatus::Ready);
+
+        // Without the mapping, "Code Review" is unknown and A stays pending
+        let graph = build_task_graph("parent-1", "MOB-100", &issues);
+        assert_eq!(graph.tasks["a"].status, TaskStatus::Ready);
+        assert_eq!(graph.tasks["b"].status, TaskStatus::Blocked);
+    }
+
     #[test]
     fn test_graph_stats() {
         let issues = make_chain_issues();
@@ -552,6 +1058,83 @@ mod tests {
         assert_eq!(ready[0].identifier, "MOB-124");
     }
 
+    #[test]
+    fn test_human_tasks_are_never_ready() {
+        // MOB-201 (human, via label) gates MOB-202; MOB-203 is independent
+        let mut human = make_blocked_issue("a", "MOB-201", &[]);
+        human.labels = vec!["Human".to_string()];
+        let issues = vec![
+            human,
+            make_blocked_issue("b", "MOB-202", &[("a", "MOB-201")]),
+            make_blocked_issue("c", "MOB-203", &[]),
+        ];
+        let graph = build_task_graph("p", "MOB-200", &issues);
+
+        assert_eq!(graph.tasks["a"].task_type, TaskType::Human);
+        let ready: Vec<&str> = get_ready_tasks(&graph)
+            .into_iter()
+            .map(|t| t.identifier.as_str())
+            .collect();
+        assert_eq!(ready, vec!["MOB-203"]);
+        let awaiting: Vec<&str> = get_awaiting_human_tasks(&graph)
+            .into_iter()
+            .map(|t| t.identifier.as_str())
+            .collect();
+        assert_eq!(awaiting, vec!["MOB-201"]);
+        assert_eq!(graph.tasks["b"].status, TaskStatus::Blocked);
+
+        let graph = update_task_status(&graph, "a", TaskStatus::Done);
+        assert!(get_awaiting_human_tasks(&graph).is_empty());
+        assert_eq!(graph.tasks["b"].status, TaskStatus::Ready);
+        assert_eq!(
+            plan_batches(&graph, 4, 0, SchedulingStrategy::Priority).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_get_ready_tasks_dispatch_order() {
+        // MOB-300 unblocks MOB-301; MOB-302 and MOB-303 unblock nothing
+        let mut issues = vec![
+            make_blocked_issue("a", "MOB-300", &[]),
+            make_blocked_issue("b", "MOB-301", &[("a", "MOB-300")]),
+            make_blocked_issue("c", "MOB-302", &[]),
+            make_blocked_issue("d", "MOB-303", &[]),
+        ];
+        let order = |issues: &[LinearIssue]| -> Vec<String> {
+            get_ready_tasks(&build_task_graph("p", "MOB-100", issues))
+                .into_iter()
+                .map(|t| t.identifier.clone())
+                .collect()
+        };
+
+        // Fan-out first, then identifier
+        assert_eq!(order(&issues), vec!["MOB-300", "MOB-302", "MOB-303"]);
+
+        // Priority outranks fan-out; unprioritized tasks go last
+        issues[3].priority = Some(1);
+        issues[2].priority = Some(3);
+        assert_eq!(order(&issues), vec!["MOB-303", "MOB-302", "MOB-300"]);
+
+        // The fanout strategy puts the widest unblocker first regardless
+        let graph = build_task_graph("p", "MOB-100", &issues);
+        let mut ready: Vec<SubTask> = get_ready_tasks(&graph).into_iter().cloned().collect();
+        sort_for_dispatch(&graph, &mut ready, SchedulingStrategy::Fanout);
+        let ids: Vec<_> = ready.iter().map(|t| t.identifier.as_str()).collect();
+        assert_eq!(ids, vec!["MOB-300", "MOB-303", "MOB-302"]);
+    }
+
+    #[test]
+    fn test_fan_out_counts_transitive_downstream() {
+        let graph = build_task_graph("p", "MOB-100", &make_diamond_issues());
+        assert_eq!(fan_out(&graph, "a"), 3);
+        assert_eq!(fan_out(&graph, "b"), 1);
+        assert_eq!(fan_out(&graph, "d"), 0);
+
+        let graph = update_task_status(&graph, "b", TaskStatus::Done);
+        assert_eq!(fan_out(&graph, "a"), 2);
+    }
+
     #[test]
     fn test_get_task_by_identifier() {
         let issues = make_chain_issues();
@@ -608,6 +1191,10 @@ mod tests {
             blocks: vec!["b".to_string()],
             git_branch_name: "feature/mob-124".to_string(),
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         };
         let json = serde_json::to_string(&task).unwrap();
         let parsed: SubTask = serde_json::from_str(&json).unwrap();
@@ -631,6 +1218,10 @@ mod tests {
                 blocks: vec![],
             }),
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         };
         let json = serde_json::to_string(&issue).unwrap();
         let parsed: LinearIssue = serde_json::from_str(&json).unwrap();
@@ -648,6 +1239,10 @@ mod tests {
             git_branch_name: String::new(),
             relations: None,
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         }];
         let graph = build_task_graph("parent-1", "MOB-100", &issues);
         assert_eq!(graph.tasks.get("d").unwrap().status, TaskStatus::Done);
@@ -663,6 +1258,10 @@ mod tests {
             git_branch_name: String::new(),
             relations: None,
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         }];
         let graph = build_task_graph("parent-1", "MOB-100", &issues);
         let ready = get_ready_tasks(&graph);
@@ -695,6 +1294,10 @@ mod tests {
                     ],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "b".to_string(),
@@ -713,6 +1316,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "c".to_string(),
@@ -731,6 +1338,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "d".to_string(),
@@ -752,6 +1363,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ]
     }
@@ -815,6 +1430,10 @@ mod tests {
                     ],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "b".to_string(),
@@ -830,6 +1449,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "c".to_string(),
@@ -845,6 +1468,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "d".to_string(),
@@ -860,6 +1487,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ];
         let graph = build_task_graph("p1", "MOB-100", &issues);
@@ -900,6 +1531,10 @@ mod tests {
             git_branch_name: String::new(),
             relations: None,
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         }];
         let graph = build_task_graph("p1", "MOB-100", &issues);
         assert_eq!(graph.tasks.len(), 1);
@@ -923,6 +1558,10 @@ mod tests {
                 blocks: vec![],
             }),
             scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         }];
         let graph = build_task_graph("p1", "MOB-100", &issues);
         // External blocker not in graph → assumed done → task is Ready
@@ -949,6 +1588,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "b".to_string(),
@@ -967,6 +1610,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "c".to_string(),
@@ -985,6 +1632,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "d".to_string(),
@@ -1000,6 +1651,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ];
         let graph = build_task_graph("p1", "MOB-100", &issues);
@@ -1039,6 +1694,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "b".to_string(),
@@ -1054,6 +1713,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "x".to_string(),
@@ -1069,6 +1732,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "y".to_string(),
@@ -1084,6 +1751,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ];
         let graph = build_task_graph("p1", "MOB-100", &issues);
@@ -1116,6 +1787,10 @@ mod tests {
                     }],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "b".to_string(),
@@ -1131,6 +1806,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ];
         let graph = build_task_graph("p1", "MOB-100", &issues);
@@ -1213,6 +1892,10 @@ mod tests {
                 git_branch_name: String::new(),
                 relations: None,
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "ip1".to_string(),
@@ -1222,6 +1905,10 @@ mod tests {
                 git_branch_name: String::new(),
                 relations: None,
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "ready1".to_string(),
@@ -1231,6 +1918,10 @@ mod tests {
                 git_branch_name: String::new(),
                 relations: None,
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
             LinearIssue {
                 id: "blocked1".to_string(),
@@ -1246,6 +1937,10 @@ mod tests {
                     blocks: vec![],
                 }),
                 scoring: None,
+                priority: None,
+                labels: Vec::new(),
+                timeout_minutes: None,
+                task_type: TaskType::Agent,
             },
         ];
         let graph = build_task_graph("p1", "MOB-100", &issues);
@@ -1316,6 +2011,7 @@ mod tests {
             risk: 3,
             recommended_model: Model::Sonnet,
             rationale: "Moderate complexity, low risk".to_string(),
+            recommended_runtime: None,
         };
         let json = serde_json::to_string(&scoring).unwrap();
         assert!(json.contains("\"recommendedModel\":\"sonnet\""));
@@ -1340,7 +2036,12 @@ mod tests {
                 risk: 5,
                 recommended_model: Model::Opus,
                 rationale: "High complexity".to_string(),
+                recommended_runtime: None,
             }),
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         };
         let json = serde_json::to_string(&task).unwrap();
         let parsed: SubTask = serde_json::from_str(&json).unwrap();
@@ -1381,11 +2082,201 @@ mod tests {
                 risk: 1,
                 recommended_model: Model::Haiku,
                 rationale: "Simple task".to_string(),
+                recommended_runtime: None,
             }),
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
         };
         let json = serde_json::to_string(&issue).unwrap();
         let parsed: LinearIssue = serde_json::from_str(&json).unwrap();
         assert!(parsed.scoring.is_some());
         assert_eq!(parsed.scoring.unwrap().recommended_model, Model::Haiku);
     }
+
+    // ── Cycle Detection Tests ─────────────────────────────────────────
+
+    /// Helper: an issue blocked by the given `(id, identifier)` tasks
+    fn make_blocked_issue(id: &str, identifier: &str, blocked_by: &[(&str, &str)]) -> LinearIssue {
+        LinearIssue {
+            id: id.to_string(),
+            identifier: identifier.to_string(),
+            title: format!("Task {}", id.to_uppercase()),
+            status: "Backlog".to_string(),
+            git_branch_name: String::new(),
+            relations: Some(Relations {
+                blocked_by: blocked_by
+                    .iter()
+                    .map(|(id, identifier)| Relation {
+                        id: id.to_string(),
+                        identifier: identifier.to_string(),
+                    })
+                    .collect(),
+                blocks: vec![],
+            }),
+            scoring: None,
+            priority: None,
+            labels: Vec::new(),
+            timeout_minutes: None,
+            task_type: TaskType::Agent,
+        }
+    }
+
+    #[test]
+    fn test_validate_graph_accepts_acyclic_graphs() {
+        let chain = build_task_graph("p", "MOB-100", &make_chain_issues());
+        assert!(validate_graph(&chain).is_valid());
+
+        let diamond = build_task_graph("p", "MOB-100", &make_diamond_issues());
+        assert!(validate_graph(&diamond).is_valid());
+    }
+
+    #[test]
+    fn test_validate_graph_reports_cycle_path() {
+        // a <- c <- b <- a (each blocked by the next), plus d outside the cycle
+        let issues = vec![
+            make_blocked_issue("a", "MOB-301", &[("c", "MOB-303")]),
+            make_blocked_issue("b", "MOB-302", &[("a", "MOB-301")]),
+            make_blocked_issue("c", "MOB-303", &[("b", "MOB-302")]),
+            make_blocked_issue("d", "MOB-304", &[("c", "MOB-303"), ("x", "EXT-1")]),
+        ];
+        let graph = build_task_graph("p", "MOB-300", &issues);
+        assert!(get_ready_tasks(&graph).is_empty());
+
+        let validation = validate_graph(&graph);
+        assert_eq!(validation.cycles, vec![vec!["a", "b", "c"]]);
+        assert_eq!(
+            format_cycle(&graph, &validation.cycles[0]),
+            "MOB-301 -> MOB-302 -> MOB-303 -> MOB-301"
+        );
+
+        // Self-blocking tasks are cycles too
+        let graph = build_task_graph(
+            "p",
+            "MOB-300",
+            &[make_blocked_issue("a", "MOB-301", &[("a", "MOB-301")])],
+        );
+        assert_eq!(validate_graph(&graph).cycles, vec![vec!["a"]]);
+    }
+
+    #[test]
+    fn test_break_cycles_drops_root_edge_and_unblocks() {
+        let issues = vec![
+            make_blocked_issue("a", "MOB-301", &[("c", "MOB-303")]),
+            make_blocked_issue("b", "MOB-302", &[("a", "MOB-301")]),
+            make_blocked_issue("c", "MOB-303", &[("b", "MOB-302")]),
+        ];
+        let graph = build_task_graph("p", "MOB-300", &issues);
+        let (repaired, removed) = break_cycles(&graph);
+
+        assert_eq!(
+            removed,
+            vec![RemovedEdge {
+                task_id: "a".to_string(),
+                blocker_id: "c".to_string(),
+            }]
+        );
+        assert!(validate_graph(&repaired).is_valid());
+        assert!(repaired.tasks["a"].blocked_by.is_empty());
+        assert!(repaired.edges["a"].is_empty());
+        assert_eq!(repaired.tasks["a"].status, TaskStatus::Ready);
+        assert_eq!(repaired.tasks["b"].status, TaskStatus::Blocked);
+
+        let (unchanged, removed) =
+            break_cycles(&build_task_graph("p", "MOB-100", &make_chain_issues()));
+        assert!(removed.is_empty());
+        assert_eq!(unchanged.tasks.len(), 3);
+    }
+
+    #[test]
+    fn test_critical_path_longest_chain() {
+        let mut issues = make_diamond_issues();
+        let graph = build_task_graph("p", "MOB-100", &issues);
+
+        // Equal branches: the lower identifier wins
+        let path = critical_path(&graph, false);
+        assert_eq!(path.task_ids, vec!["a", "b", "d"]);
+        assert_eq!(path.length, 3);
+        assert!(path.contains_edge("b", "d"));
+        assert!(!path.contains("c"));
+
+        // Weighted by complexity, the heavier branch wins
+        issues[2].scoring = Some(TaskScoring {
+            complexity: 8,
+            risk: 2,
+            recommended_model: Model::Opus,
+            rationale: String::new(),
+            recommended_runtime: None,
+        });
+        let graph = build_task_graph("p", "MOB-100", &issues);
+        let path = critical_path(&graph, true);
+        assert_eq!(path.task_ids, vec!["a", "c", "d"]);
+        assert_eq!(path.length, 10);
+
+        // Done tasks drop out of the chain
+        issues[0].status = "Done".to_string();
+        let graph = build_task_graph("p", "MOB-100", &issues);
+        assert_eq!(critical_path(&graph, false).task_ids, vec!["b", "d"]);
+
+        for issue in &mut issues {
+            issue.status = "Done".to_string();
+        }
+        let graph = build_task_graph("p", "MOB-100", &issues);
+        assert_eq!(critical_path(&graph, false), CriticalPath::default());
+    }
+
+    #[test]
+    fn test_plan_batches_respects_dependencies_and_parallelism() {
+        let graph = build_task_graph("p", "MOB-100", &make_diamond_issues());
+        let ids = |batches: Vec<Vec<SubTask>>| -> Vec<Vec<String>> {
+            batches
+                .into_iter()
+                .map(|batch| batch.into_iter().map(|t| t.id).collect())
+                .collect()
+        };
+
+        assert_eq!(
+            ids(plan_batches(&graph, 3, 0, SchedulingStrategy::Priority)),
+            vec![vec!["a"], vec!["b", "c"], vec!["d"]]
+        );
+        assert_eq!(
+            ids(plan_batches(&graph, 1, 0, SchedulingStrategy::Priority)),
+            vec![vec!["a"], vec!["b"], vec!["c"], vec!["d"]]
+        );
+        assert_eq!(
+            ids(plan_batches(&graph, 3, 2, SchedulingStrategy::Priority)).len(),
+            2
+        );
+        // The input graph is left untouched
+        assert_eq!(graph.tasks["a"].status, TaskStatus::Ready);
+    }
+
+    #[test]
+    fn test_external_url_blocker_blocks_until_resolved() {
+        let url = "https://github.com/acme/api/pull/42";
+        let issues = vec![
+            make_blocked_issue("a", "MOB-401", &[(url, url)]),
+            make_blocked_issue("b", "MOB-402", &[("a", "MOB-401")]),
+            // Non-URL blockers outside the graph are still assumed done
+            make_blocked_issue("c", "MOB-403", &[("other", "EXT-9")]),
+        ];
+        let graph = build_task_graph("p", "MOB-400", &issues);
+        assert_eq!(graph.tasks["a"].status, TaskStatus::Blocked);
+        assert_eq!(graph.tasks["c"].status, TaskStatus::Ready);
+
+        let external = get_external_blockers(&graph);
+        assert_eq!(external.len(), 1);
+        assert_eq!((external[0].0.id.as_str(), external[0].1), ("a", url));
+
+        let unchanged = resolve_external_blockers(&graph, &HashSet::new());
+        assert_eq!(unchanged.tasks["a"].status, TaskStatus::Blocked);
+
+        let resolved: HashSet<String> = [url.to_string()].into_iter().collect();
+        let updated = resolve_external_blockers(&graph, &resolved);
+        assert_eq!(updated.tasks["a"].status, TaskStatus::Ready);
+        assert!(updated.tasks["a"].blocked_by.is_empty());
+        assert_eq!(updated.tasks["b"].status, TaskStatus::Blocked);
+        assert!(get_external_blockers(&updated).is_empty());
+    }
 }
@@ -0,0 +1,295 @@
+//! Runs sub-task verify commands directly instead of trusting the agent to.
+//!
+//! A sub-task's verify command is the bash block under `### Verify Command`
+//! in its spec. `mobius verify` runs them on demand, and the loop runs a
+//! sub-task's command after its agent reports success, failing the attempt
+//! when the command does not pass. Full output is written to
+//! `.mobius/issues/{id}/verify/{subtask}.log`; the iteration log keeps the
+//! outcome and the output's tail. On-demand runs are not attempts, so they go
+//! to `.mobius/issues/{id}/verify/runs.jsonl` instead.
+
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::context::{extract_verify_commands, get_context_path};
+use crate::executor::{ExecutionResult, ExecutionStatus};
+use crate::local_state::read_subtasks;
+use crate::types::config::SubTaskVerifyCommand;
+
+/// Lines of output kept in the iteration log
+const OUTPUT_TAIL_LINES: usize = 40;
+
+/// How often a running command is checked for exit or timeout
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// One run of a sub-task's verify command
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyRun {
+    pub command: String,
+    pub passed: bool,
+    /// `None` when the command was killed at the timeout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    /// Last lines of the combined stdout and stderr
+    #[serde(default)]
+    pub output: String,
+}
+
+impl VerifyRun {
+    /// Error recorded on an attempt whose verify command failed.
+    pub fn failure_message(&self) -> String {
+        let reason = match self.exit_code {
+            Some(code) => format!("exit {}", code),
+            None => "timed out".to_string(),
+        };
+        let last_line = self
+            .output
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|l| !l.is_empty());
+        match last_line {
+            Some(line) => format!("Verify command failed ({}): {}", reason, line),
+            None => format!("Verify command failed ({})", reason),
+        }
+    }
+}
+
+/// Get the path to a sub-task's latest verify output.
+pub fn get_verify_log_path(parent_id: &str, subtask_id: &str) -> PathBuf {
+    get_context_path(parent_id)
+        .join("verify")
+        .join(format!("{}.log", subtask_id))
+}
+
+/// Get the path to the log of on-demand verify runs.
+pub fn get_manual_runs_path(parent_id: &str) -> PathBuf {
+    get_context_path(parent_id)
+        .join("verify")
+        .join("runs.jsonl")
+}
+
+/// One `mobius verify` run, as appended to `runs.jsonl`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManualVerifyRun {
+    pub subtask_id: String,
+    pub started_at: String,
+    pub dir: String,
+    #[serde(flatten)]
+    pub run: VerifyRun,
+}
+
+/// Append an on-demand run to `parent_id`'s `runs.jsonl`.
+pub fn append_manual_run(parent_id: &str, entry: &ManualVerifyRun) -> Result<()> {
+    let path = get_manual_runs_path(parent_id);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Verify commands of `parent_id`'s sub-tasks, from the local specs.
+pub fn read_verify_commands(parent_id: &str) -> Vec<SubTaskVerifyCommand> {
+    extract_verify_commands(&read_subtasks(parent_id))
+}
+
+/// Run `command` with `sh -c` in `dir`, writing its output to `log_path` and
+/// killing it, along with anything it started, once `timeout` has passed.
+pub fn run_verify_command(
+    dir: &Path,
+    command: &str,
+    timeout: Duration,
+    log_path: &Path,
+) -> Result<VerifyRun> {
+    if let Some(parent) = log_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let log = File::create(log_path)
+        .with_context(|| format!("Failed to create {}", log_path.display()))?;
+
+    let started = Instant::now();
+    let mut cmd = Command::new("sh");
+    cmd.args(["-c", command])
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(log.try_clone()?)
+        .stderr(log);
+    // Its own process group, so a timeout also stops the test runners and
+    // servers the command spawned
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("Failed to run verify command `{}`", command))?;
+
+    let exit_code = loop {
+        if let Some(status) = child.try_wait()? {
+            // Killed by a signal counts as a failure
+            break Some(status.code().unwrap_or(-1));
+        }
+        if started.elapsed() >= timeout {
+            kill_process_group(&mut child);
+            break None;
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let output = fs::read_to_string(log_path).unwrap_or_default();
+    Ok(VerifyRun {
+        command: command.to_string(),
+        passed: exit_code == Some(0),
+        exit_code,
+        duration_ms: started.elapsed().as_millis() as u64,
+        output: tail_lines(&output, OUTPUT_TAIL_LINES),
+    })
+}
+
+/// Run the verify command of every successful result that has one, and turn
+/// results whose command fails into `VerificationFailed` failures.
+///
+/// Returns the runs by sub-task identifier; `on_run` sees each as it finishes.
+pub fn gate_results(
+    parent_id: &str,
+    results: &mut [ExecutionResult],
+    dir: &Path,
+    timeout: Duration,
+    mut on_run: impl FnMut(&str, &VerifyRun),
+) -> HashMap<String, VerifyRun> {
+    let commands = read_verify_commands(parent_id);
+    let mut runs = HashMap::new();
+    for result in results.iter_mut().filter(|r| r.success) {
+        let Some(verify) = commands.iter().find(|c| c.subtask_id == result.identifier) else {
+            continue;
+        };
+        let log_path = get_verify_log_path(parent_id, &result.identifier);
+        let run =
+            run_verify_command(dir, &verify.command, timeout, &log_path).unwrap_or_else(|e| {
+                VerifyRun {
+                    command: verify.command.clone(),
+                    passed: false,
+                    exit_code: Some(-1),
+                    duration_ms: 0,
+                    output: format!("{:#}", e),
+                }
+            });
+        on_run(&result.identifier, &run);
+        if !run.passed {
+            result.success = false;
+            result.status = ExecutionStatus::VerificationFailed;
+            result.error = Some(run.failure_message());
+        }
+        runs.insert(result.identifier.clone(), run);
+    }
+    runs
+}
+
+/// Kill `child` and every process in the group it leads.
+fn kill_process_group(child: &mut Child) {
+    #[cfg(unix)]
+    unsafe {
+        libc::kill(-(child.id() as libc::pid_t), libc::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+fn tail_lines(text: &str, count: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    lines[lines.len().saturating_sub(count)..].join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_verify_command_records_outcome_and_tail() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let log = tmp.path().join("verify").join("MOB-2.log");
+
+        let passed =
+            run_verify_command(tmp.path(), "echo ok", Duration::from_secs(10), &log).unwrap();
+        assert!(passed.passed);
+        assert_eq!(passed.exit_code, Some(0));
+        assert_eq!(passed.output, "ok");
+
+        let failed = run_verify_command(
+            tmp.path(),
+            "seq 1 50; echo 'assertion failed' >&2; exit 3",
+            Duration::from_secs(10),
+            &log,
+        )
+        .unwrap();
+        assert!(!failed.passed);
+        assert_eq!(failed.exit_code, Some(3));
+        assert_eq!(failed.output.lines().count(), OUTPUT_TAIL_LINES);
+        assert_eq!(
+            failed.failure_message(),
+            "Verify command failed (exit 3): assertion failed"
+        );
+        assert!(fs::read_to_string(&log).unwrap().starts_with("1\n"));
+    }
+
+    #[test]
+    fn test_run_verify_command_times_out() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let run = run_verify_command(
+            tmp.path(),
+            "sleep 5",
+            Duration::from_millis(200),
+            &tmp.path().join("v.log"),
+        )
+        .unwrap();
+        assert!(!run.passed);
+        assert_eq!(run.exit_code, None);
+        assert!(run.duration_ms < 5000);
+        assert_eq!(run.failure_message(), "Verify command failed (timed out)");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_run_verify_command_timeout_kills_spawned_processes() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let pid_file = tmp.path().join("bg.pid");
+        run_verify_command(
+            tmp.path(),
+            "sleep 30 & echo $! > bg.pid; wait",
+            Duration::from_millis(300),
+            &tmp.path().join("v.log"),
+        )
+        .unwrap();
+
+        let pid = fs::read_to_string(&pid_file).unwrap().trim().to_string();
+        // Gone, or a zombie waiting for init to reap it
+        let alive = || {
+            fs::read_to_string(format!("/proc/{pid}/stat")).is_ok_and(|stat| !stat.contains(") Z "))
+        };
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while alive() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(20));
+        }
+        assert!(!alive(), "background sleep {pid} survived the timeout");
+    }
+}
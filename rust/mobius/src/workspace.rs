@@ -0,0 +1,490 @@
+//! Monorepo workspace detection and verify-command scoping.
+//!
+//! Detects the packages of a Cargo, pnpm, Yarn, or npm workspace and maps a
+//! task's changed files onto them, so agents can run `cargo test -p api`
+//! instead of the whole repository's test suite. Any change outside a known
+//! package falls back to the full suite.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::Serialize;
+
+use crate::types::config::VerificationConfig;
+use crate::types::enums::BuildSystem;
+
+/// A member package of a workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct WorkspacePackage {
+    pub name: String,
+    /// Path relative to the workspace root, `/`-separated
+    pub path: String,
+}
+
+/// Workspace layout detected at a repository root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceLayout {
+    pub build_system: BuildSystem,
+    pub packages: Vec<WorkspacePackage>,
+}
+
+/// Verify command chosen for a set of changed files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyScope {
+    /// Command to run; scoped when `packages` is non-empty
+    pub command: Option<String>,
+    pub packages: Vec<String>,
+    pub changed_files: Vec<String>,
+    /// Why the full suite is used, when it is
+    pub fallback_reason: Option<String>,
+}
+
+/// Files that never force a full-suite run on their own
+const IGNORED_ROOT_FILES: &[&str] = &[
+    "Cargo.lock",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "package-lock.json",
+];
+
+/// Default test command template per workspace build system.
+pub fn default_workspace_template(build_system: BuildSystem) -> Option<&'static str> {
+    match build_system {
+        BuildSystem::Cargo => Some("cargo test -p {package}"),
+        BuildSystem::Pnpm => Some("pnpm --filter {package} test"),
+        BuildSystem::Yarn => Some("yarn workspace {package} test"),
+        BuildSystem::Npm => Some("npm test --workspace={package}"),
+        _ => None,
+    }
+}
+
+/// Detect a Cargo, pnpm, Yarn, or npm workspace at `root`.
+///
+/// Returns `None` for single-package repositories.
+pub fn detect_workspace(root: &Path) -> Option<WorkspaceLayout> {
+    let candidates = [
+        (BuildSystem::Cargo, cargo_member_globs(root)),
+        (BuildSystem::Pnpm, pnpm_member_globs(root)),
+        (
+            BuildSystem::Yarn,
+            package_json_member_globs(root).filter(|_| root.join("yarn.lock").exists()),
+        ),
+        (BuildSystem::Npm, package_json_member_globs(root)),
+    ];
+
+    candidates.into_iter().find_map(|(build_system, globs)| {
+        let packages = resolve_packages(root, &globs?, build_system);
+        (!packages.is_empty()).then_some(WorkspaceLayout {
+            build_system,
+            packages,
+        })
+    })
+}
+
+/// Packages containing `files`, or `Err(file)` for the first file outside
+/// every package (ignoring lockfiles and Markdown).
+pub fn packages_for_files<'a>(
+    layout: &'a WorkspaceLayout,
+    files: &[String],
+) -> std::result::Result<Vec<&'a WorkspacePackage>, String> {
+    let mut touched: Vec<&WorkspacePackage> = Vec::new();
+    for file in files {
+        let owner = layout
+            .packages
+            .iter()
+            .filter(|p| file.starts_with(&format!("{}/", p.path)))
+            .max_by_key(|p| p.path.len());
+        match owner {
+            Some(package) => {
+                if !touched.contains(&package) {
+                    touched.push(package);
+                }
+            }
+            None if is_ignored(file) => {}
+            None => return Err(file.clone()),
+        }
+    }
+    touched.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(touched)
+}
+
+/// Expand a `{package}` template for each package, joined with `&&`.
+pub fn render_scoped_command(template: &str, packages: &[&WorkspacePackage]) -> String {
+    packages
+        .iter()
+        .map(|p| template.replace("{package}", &p.name))
+        .collect::<Vec<_>>()
+        .join(" && ")
+}
+
+/// Choose the verify command for `changed_files`.
+///
+/// `full_command` is the repository-wide test command used whenever scoping
+/// is disabled, no workspace is detected, or a change falls outside every
+/// package.
+pub fn resolve_verify_scope(
+    root: &Path,
+    changed_files: Vec<String>,
+    full_command: Option<String>,
+    config: &VerificationConfig,
+) -> VerifyScope {
+    let full = |reason: &str| VerifyScope {
+        command: full_command.clone(),
+        packages: Vec::new(),
+        changed_files: changed_files.clone(),
+        fallback_reason: Some(reason.to_string()),
+    };
+
+    if !config.scope_to_workspace {
+        return full("workspace scoping disabled");
+    }
+    let Some(layout) = detect_workspace(root) else {
+        return full("no workspace detected");
+    };
+    let Some(template) = config
+        .workspace_commands
+        .get(&layout.build_system)
+        .map(String::as_str)
+        .or_else(|| default_workspace_template(layout.build_system))
+    else {
+        return full("no workspace command template");
+    };
+
+    match packages_for_files(&layout, &changed_files) {
+        Ok(packages) if packages.is_empty() => full("no package changes"),
+        Ok(packages) => VerifyScope {
+            command: Some(render_scoped_command(template, &packages)),
+            packages: packages.iter().map(|p| p.name.clone()).collect(),
+            changed_files,
+            fallback_reason: None,
+        },
+        Err(file) => full(&format!("{} is outside every workspace package", file)),
+    }
+}
+
+/// Files changed on the current branch relative to `base`, including
+/// uncommitted and untracked files.
+pub fn changed_files(repo_path: &Path, base: &str) -> Result<Vec<String>> {
+    let mut files: Vec<String> = Vec::new();
+    for args in [
+        vec!["diff", "--name-only", &format!("{}...HEAD", base)],
+        vec!["diff", "--name-only", "HEAD"],
+        vec!["ls-files", "--others", "--exclude-standard"],
+    ] {
+        let output = Command::new("git")
+            .current_dir(repo_path)
+            .args(&args)
+            .output()
+            .context("failed to run git")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        files.extend(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string),
+        );
+    }
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+fn is_ignored(file: &str) -> bool {
+    IGNORED_ROOT_FILES.contains(&file) || file.ends_with(".md")
+}
+
+/// `members = [...]` from the `[workspace]` section of the root Cargo.toml.
+fn cargo_member_globs(root: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(root.join("Cargo.toml")).ok()?;
+    let section = toml_section(&content, "workspace")?;
+    let members = Regex::new(r"(?s)members\s*=\s*\[(.*?)\]")
+        .unwrap()
+        .captures(section)?;
+    Some(quoted_strings(&members[1]))
+}
+
+/// `packages:` from pnpm-workspace.yaml.
+fn pnpm_member_globs(root: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(root.join("pnpm-workspace.yaml")).ok()?;
+    let yaml: serde_yaml::Value = serde_yaml::from_str(&content).ok()?;
+    let globs = yaml
+        .get("packages")?
+        .as_sequence()?
+        .iter()
+        .filter_map(|v| v.as_str())
+        .filter(|g| !g.starts_with('!'))
+        .map(str::to_string)
+        .collect();
+    Some(globs)
+}
+
+/// `workspaces` (array or `{ packages: [...] }`) from the root package.json.
+fn package_json_member_globs(root: &Path) -> Option<Vec<String>> {
+    let content = fs::read_to_string(root.join("package.json")).ok()?;
+    let pkg: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let workspaces = pkg.get("workspaces")?;
+    let list = workspaces
+        .as_array()
+        .or_else(|| workspaces.get("packages")?.as_array())?;
+    Some(
+        list.iter()
+            .filter_map(|v| v.as_str())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
+/// Expand member globs (plain paths or a trailing `/*`) into packages.
+fn resolve_packages(
+    root: &Path,
+    globs: &[String],
+    build_system: BuildSystem,
+) -> Vec<WorkspacePackage> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for glob in globs {
+        let glob = glob.trim_end_matches('/');
+        if let Some(parent) = glob.strip_suffix("/*").or(glob.strip_suffix("/**")) {
+            if let Ok(entries) = fs::read_dir(root.join(parent)) {
+                let mut children: Vec<PathBuf> = entries
+                    .flatten()
+                    .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+                    .map(|e| Path::new(parent).join(e.file_name()))
+                    .collect();
+                children.sort();
+                dirs.extend(children);
+            }
+        } else {
+            dirs.push(PathBuf::from(glob));
+        }
+    }
+
+    dirs.into_iter()
+        .filter_map(|dir| {
+            let name = match build_system {
+                BuildSystem::Cargo => cargo_package_name(&root.join(&dir)),
+                _ => npm_package_name(&root.join(&dir)),
+            }?;
+            Some(WorkspacePackage {
+                name,
+                path: dir.to_string_lossy().replace('\\', "/"),
+            })
+        })
+        .collect()
+}
+
+fn cargo_package_name(dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+    let section = toml_section(&content, "package")?;
+    Regex::new(r#"(?m)^\s*name\s*=\s*"([^"]+)""#)
+        .unwrap()
+        .captures(section)
+        .map(|c| c[1].to_string())
+}
+
+fn npm_package_name(dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(dir.join("package.json")).ok()?;
+    let pkg: serde_json::Value = serde_json::from_str(&content).ok()?;
+    pkg.get("name")?.as_str().map(str::to_string)
+}
+
+/// Body of a `[name]` TOML table, up to the next table header.
+fn toml_section<'a>(content: &'a str, name: &str) -> Option<&'a str> {
+    let header = format!("[{}]", name);
+    let start = content
+        .lines()
+        .scan(0usize, |offset, line| {
+            let line_start = *offset;
+            *offset += line.len() + 1;
+            Some((line_start, line))
+        })
+        .find(|(_, line)| line.trim() == header)
+        .map(|(offset, line)| offset + line.len())?;
+    let rest = &content[start.min(content.len())..];
+    let end = Regex::new(r"(?m)^\s*\[")
+        .unwrap()
+        .find(rest)
+        .map_or(rest.len(), |m| m.start());
+    Some(&rest[..end])
+}
+
+fn quoted_strings(text: &str) -> Vec<String> {
+    Regex::new(r#""([^"]+)""#)
+        .unwrap()
+        .captures_iter(text)
+        .map(|c| c[1].to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_file(dir: &Path, name: &str, content: &str) {
+        let file_path = dir.join(name);
+        fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        fs::write(file_path, content).unwrap();
+    }
+
+    fn cargo_workspace() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        create_file(
+            dir.path(),
+            "Cargo.toml",
+            "[workspace]\nmembers = [\n  \"crates/*\",\n  \"tools/cli\",\n]\nresolver = \"2\"\n\n[workspace.dependencies]\nserde = \"1\"\n",
+        );
+        create_file(
+            dir.path(),
+            "crates/api/Cargo.toml",
+            "[package]\nname = \"acme-api\"\nversion = \"0.1.0\"\n",
+        );
+        create_file(
+            dir.path(),
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"acme-core\"\n",
+        );
+        create_file(
+            dir.path(),
+            "tools/cli/Cargo.toml",
+            "[package]\nname = \"acme-cli\"\n\n[dependencies]\nname = \"x\"\n",
+        );
+        dir
+    }
+
+    #[test]
+    fn test_detect_cargo_workspace() {
+        let dir = cargo_workspace();
+        let layout = detect_workspace(dir.path()).unwrap();
+        assert_eq!(layout.build_system, BuildSystem::Cargo);
+        let names: Vec<_> = layout
+            .packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.path.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("acme-api", "crates/api"),
+                ("acme-core", "crates/core"),
+                ("acme-cli", "tools/cli"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect_pnpm_and_npm_workspaces() {
+        let dir = TempDir::new().unwrap();
+        create_file(
+            dir.path(),
+            "pnpm-workspace.yaml",
+            "packages:\n  - 'packages/*'\n  - '!packages/legacy'\n",
+        );
+        create_file(
+            dir.path(),
+            "packages/web/package.json",
+            r#"{"name": "@acme/web"}"#,
+        );
+        let layout = detect_workspace(dir.path()).unwrap();
+        assert_eq!(layout.build_system, BuildSystem::Pnpm);
+        assert_eq!(layout.packages[0].name, "@acme/web");
+
+        let dir = TempDir::new().unwrap();
+        create_file(
+            dir.path(),
+            "package.json",
+            r#"{"workspaces": {"packages": ["apps/site"]}}"#,
+        );
+        create_file(dir.path(), "apps/site/package.json", r#"{"name": "site"}"#);
+        let layout = detect_workspace(dir.path()).unwrap();
+        assert_eq!(layout.build_system, BuildSystem::Npm);
+        assert_eq!(layout.packages[0].path, "apps/site");
+
+        let dir = TempDir::new().unwrap();
+        create_file(dir.path(), "Cargo.toml", "[package]\nname = \"single\"\n");
+        assert!(detect_workspace(dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_resolve_verify_scope_scopes_to_changed_packages() {
+        let dir = cargo_workspace();
+        let config = VerificationConfig::default();
+        let files = |list: &[&str]| list.iter().map(|f| f.to_string()).collect::<Vec<_>>();
+
+        let scope = resolve_verify_scope(
+            dir.path(),
+            files(&[
+                "crates/core/src/lib.rs",
+                "crates/api/src/routes.rs",
+                "Cargo.lock",
+                "crates/api/README.md",
+            ]),
+            Some("cargo test".to_string()),
+            &config,
+        );
+        assert_eq!(scope.packages, vec!["acme-api", "acme-core"]);
+        assert_eq!(
+            scope.command.as_deref(),
+            Some("cargo test -p acme-api && cargo test -p acme-core")
+        );
+        assert!(scope.fallback_reason.is_none());
+
+        let scope = resolve_verify_scope(
+            dir.path(),
+            files(&["crates/api/src/lib.rs", "build.rs"]),
+            Some("cargo test".to_string()),
+            &config,
+        );
+        assert_eq!(scope.command.as_deref(), Some("cargo test"));
+        assert!(scope.packages.is_empty());
+        assert_eq!(
+            scope.fallback_reason.as_deref(),
+            Some("build.rs is outside every workspace package")
+        );
+    }
+
+    #[test]
+    fn test_resolve_verify_scope_uses_configured_template() {
+        let dir = cargo_workspace();
+        let mut config = VerificationConfig::default();
+        config.workspace_commands.insert(
+            BuildSystem::Cargo,
+            "cargo nextest run -p {package}".to_string(),
+        );
+
+        let scope = resolve_verify_scope(
+            dir.path(),
+            vec!["tools/cli/src/main.rs".to_string()],
+            None,
+            &config,
+        );
+        assert_eq!(
+            scope.command.as_deref(),
+            Some("cargo nextest run -p acme-cli")
+        );
+
+        config.scope_to_workspace = false;
+        let scope = resolve_verify_scope(
+            dir.path(),
+            vec!["tools/cli/src/main.rs".to_string()],
+            None,
+            &config,
+        );
+        assert!(scope.command.is_none());
+        assert_eq!(
+            scope.fallback_reason.as_deref(),
+            Some("workspace scoping disabled")
+        );
+    }
+}
@@ -5,11 +5,16 @@
 
 use std::path::{Path, PathBuf};
 
-use anyhow::{bail, Context, Result};
+use anyhow::{Context, Result};
 use tokio::process::Command;
 
+use crate::error::MobiusError;
+use crate::types::config::CheckoutConfig;
 use crate::types::enums::AgentRuntime;
 
+/// Name of the bare mirror kept in the worktree base directory
+const MIRROR_DIR_NAME: &str = ".mirror.git";
+
 /// Information about a created or resumed worktree.
 #[derive(Debug, Clone)]
 pub struct WorktreeInfo {
@@ -26,6 +31,7 @@ pub struct WorktreeConfig {
     pub worktree_path: Option<String>,
     pub base_branch: Option<String>,
     pub runtime: AgentRuntime,
+    pub checkout: CheckoutConfig,
 }
 
 /// Get the repository name from git remote or current directory name.
@@ -144,6 +150,11 @@ pub async fn get_git_repo_root() -> Result<PathBuf> {
 
 /// Get the worktree path for a given task.
 pub async fn get_worktree_path(task_id: &str, config: &WorktreeConfig) -> Result<PathBuf> {
+    Ok(get_worktree_base(config).await?.join(task_id))
+}
+
+/// Get the directory that holds every task's worktree.
+pub async fn get_worktree_base(config: &WorktreeConfig) -> Result<PathBuf> {
     let template = config
         .worktree_path
         .as_deref()
@@ -157,7 +168,7 @@ pub async fn get_worktree_path(task_id: &str, config: &WorktreeConfig) -> Result
     let repo_root = get_git_repo_root().await?;
 
     // Resolve path relative to the main repo root
-    Ok(repo_root.join(base_path).join(task_id))
+    Ok(repo_root.join(base_path))
 }
 
 /// Check if a worktree already exists for the given task.
@@ -221,6 +232,27 @@ impl MergeDetectionResult {
     }
 }
 
+/// Check whether any commit on the base branch mentions `identifier`.
+///
+/// Local-only counterpart to [`is_issue_merged_into_base`]: no remote is queried.
+pub async fn is_identifier_in_base_log(identifier: &str, base_branch: &str) -> Result<bool> {
+    let log_output = Command::new("git")
+        .args([
+            "log",
+            base_branch,
+            "--oneline",
+            &format!("--grep={}", identifier),
+        ])
+        .output()
+        .await
+        .context("failed to run git log")?;
+
+    Ok(log_output.status.success()
+        && !String::from_utf8_lossy(&log_output.stdout)
+            .trim()
+            .is_empty())
+}
+
 /// Check if an issue's branch has been merged into the base branch.
 ///
 /// Performs two independent checks:
@@ -249,23 +281,7 @@ pub async fn is_issue_merged_into_base(
     };
 
     // Check if identifier appears in base branch commit log
-    let log_output = Command::new("git")
-        .args([
-            "log",
-            base_branch,
-            "--oneline",
-            &format!("--grep={}", identifier),
-        ])
-        .output()
-        .await
-        .context("failed to run git log")?;
-
-    let found_in_base_log = if log_output.status.success() {
-        let stdout = String::from_utf8_lossy(&log_output.stdout);
-        !stdout.trim().is_empty()
-    } else {
-        false
-    };
+    let found_in_base_log = is_identifier_in_base_log(identifier, base_branch).await?;
 
     Ok(MergeDetectionResult {
         remote_branch_deleted,
@@ -277,11 +293,13 @@ fn runtime_config_dir(runtime: AgentRuntime) -> &'static str {
     match runtime {
         AgentRuntime::Claude => ".claude",
         AgentRuntime::Opencode => ".opencode",
+        AgentRuntime::Codex => ".codex",
+        AgentRuntime::Aider => ".aider",
     }
 }
 
 /// Symlink active runtime config directory from source repo to worktree.
-fn symlink_runtime_config_dir(source_repo: &Path, worktree_path: &Path, runtime: AgentRuntime) {
+pub fn symlink_runtime_config_dir(source_repo: &Path, worktree_path: &Path, runtime: AgentRuntime) {
     let runtime_dir = runtime_config_dir(runtime);
     let source_path = source_repo.join(runtime_dir);
     let target_path = worktree_path.join(runtime_dir);
@@ -314,7 +332,7 @@ fn symlink_runtime_config_dir(source_repo: &Path, worktree_path: &Path, runtime:
 }
 
 /// Get the actual default branch name from the repo.
-async fn get_default_branch_name() -> Option<String> {
+pub async fn get_default_branch_name() -> Option<String> {
     // Try to get from origin HEAD reference
     if let Ok(output) = Command::new("git")
         .args(["symbolic-ref", "refs/remotes/origin/HEAD"])
@@ -346,6 +364,9 @@ async fn get_default_branch_name() -> Option<String> {
 }
 
 /// Create a worktree for the given task.
+///
+/// With `checkout.mirror` the worktree is a clone of the shared bare mirror
+/// rather than a linked worktree of the main checkout.
 pub async fn create_worktree(
     task_id: &str,
     branch_name: &str,
@@ -366,90 +387,65 @@ pub async fn create_worktree(
         });
     }
 
-    // Check if branch already exists
+    // Check if branch already exists; a new branch starts from the base branch
     let branch = branch_exists(branch_name).await?;
+    let base_branch = if branch.local || branch.remote {
+        None
+    } else {
+        Some(resolve_base_branch(config).await?)
+    };
 
-    if branch.local || branch.remote {
-        // Branch exists locally or on remote, create worktree pointing to it
+    if config.checkout.mirror {
+        clone_worktree(&worktree_path, branch_name, base_branch.as_deref(), config).await?;
+    } else if let Some(base_branch) = base_branch {
+        // Create new branch off base branch
         let output = Command::new("git")
             .args([
                 "worktree",
                 "add",
                 &worktree_path.to_string_lossy(),
+                "-b",
                 branch_name,
+                &base_branch,
             ])
             .output()
             .await
-            .context("failed to run git worktree add")?;
+            .context("failed to run git worktree add -b")?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            bail!("git worktree add failed: {}", stderr.trim());
-        }
-    } else {
-        // Need to create a new branch - determine the base branch
-        let base_branch = match &config.base_branch {
-            Some(b) => b.clone(),
-            None => {
-                // Try to auto-detect the default branch
-                match get_default_branch_name().await {
-                    Some(detected) => detected,
-                    None => bail!(
-                        "Could not determine base branch for worktree creation.\n\n\
-                         This repository does not have a 'main' branch, and the default branch could not be detected.\n\n\
-                         Please set 'base_branch' in your mobius config:\n\
-                         \x20 1. Run: mobius config -e\n\
-                         \x20 2. Add under [execution]:\n\
-                         \x20    base_branch = \"master\"  # or your default branch name"
-                    ),
-                }
-            }
-        };
-
-        // Verify the base branch exists before attempting to create worktree
-        let base_exists = branch_exists(&base_branch).await?;
-        if !base_exists.local && !base_exists.remote {
-            let detected = get_default_branch_name().await;
-            let suggestion = detected
-                .as_deref()
-                .map(|d| format!("\n\nDetected '{}' as a possible default branch.", d))
-                .unwrap_or_default();
-
-            bail!(
-                "Base branch '{}' does not exist in this repository.{}\n\n\
-                 Please update 'base_branch' in your mobius config:\n\
-                 \x20 1. Run: mobius config -e\n\
-                 \x20 2. Update under [execution]:\n\
-                 \x20    base_branch = \"{}\"",
-                base_branch,
-                suggestion,
-                detected.as_deref().unwrap_or("your-default-branch")
+            return Err(
+                MobiusError::Git(format!("git worktree add -b failed: {}", stderr.trim())).into(),
             );
         }
-
-        // Create new branch off base branch
+    } else {
+        // Branch exists locally or on remote, create worktree pointing to it
         let output = Command::new("git")
             .args([
                 "worktree",
                 "add",
                 &worktree_path.to_string_lossy(),
-                "-b",
                 branch_name,
-                &base_branch,
             ])
             .output()
             .await
-            .context("failed to run git worktree add -b")?;
+            .context("failed to run git worktree add")?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            bail!("git worktree add -b failed: {}", stderr.trim());
+            return Err(
+                MobiusError::Git(format!("git worktree add failed: {}", stderr.trim())).into(),
+            );
         }
     }
 
     // Symlink active runtime config directory from source repo
     let cwd = std::env::current_dir().context("failed to get current directory")?;
     symlink_runtime_config_dir(&cwd, &worktree_path, config.runtime);
+    if !config.checkout.reflink_caches.is_empty() {
+        let repo_root = get_git_repo_root().await?;
+        reflink_caches(&repo_root, &worktree_path, &config.checkout.reflink_caches).await;
+    }
 
     Ok(WorktreeInfo {
         path: worktree_path,
@@ -459,6 +455,250 @@ pub async fn create_worktree(
     })
 }
 
+/// The configured base branch, or the detected default branch.
+///
+/// Fails with setup instructions when neither exists in the repository.
+async fn resolve_base_branch(config: &WorktreeConfig) -> Result<String> {
+    let base_branch = match &config.base_branch {
+        Some(b) => b.clone(),
+        None => {
+            // Try to auto-detect the default branch
+            match get_default_branch_name().await {
+                Some(detected) => detected,
+                None => {
+                    return Err(MobiusError::Config(
+                        "Could not determine base branch for worktree creation.\n\n\
+                         This repository does not have a 'main' branch, and the default branch could not be detected.\n\n\
+                         Please set 'base_branch' in your mobius config:\n\
+                         \x20 1. Run: mobius config -e\n\
+                         \x20 2. Add under [execution]:\n\
+                         \x20    base_branch = \"master\"  # or your default branch name"
+                            .to_string(),
+                    )
+                    .into());
+                }
+            }
+        }
+    };
+
+    // Verify the base branch exists before attempting to create worktree
+    let base_exists = branch_exists(&base_branch).await?;
+    if !base_exists.local && !base_exists.remote {
+        let detected = get_default_branch_name().await;
+        let suggestion = detected
+            .as_deref()
+            .map(|d| format!("\n\nDetected '{}' as a possible default branch.", d))
+            .unwrap_or_default();
+
+        return Err(MobiusError::Config(format!(
+            "Base branch '{}' does not exist in this repository.{}\n\n\
+             Please update 'base_branch' in your mobius config:\n\
+             \x20 1. Run: mobius config -e\n\
+             \x20 2. Update under [execution]:\n\
+             \x20    base_branch = \"{}\"",
+            base_branch,
+            suggestion,
+            detected.as_deref().unwrap_or("your-default-branch")
+        ))
+        .into());
+    }
+
+    Ok(base_branch)
+}
+
+/// Get the path of the bare mirror that `checkout.mirror` worktrees are
+/// cloned from. It lives in the worktree base and outlives every loop.
+pub async fn get_mirror_path(config: &WorktreeConfig) -> Result<PathBuf> {
+    Ok(get_worktree_base(config).await?.join(MIRROR_DIR_NAME))
+}
+
+/// Run git in `dir`, returning its trimmed stdout.
+async fn git_in(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .await
+        .with_context(|| format!("failed to run git {}", args.join(" ")))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(MobiusError::Git(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            stderr.trim()
+        ))
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Create the bare mirror of `origin`, or bring an existing one up to date.
+///
+/// A new mirror borrows what it can from the main checkout's objects, so only
+/// what the checkout lacks is downloaded. Returns the `origin` URL.
+async fn ensure_mirror(repo_root: &Path, mirror_path: &Path) -> Result<String> {
+    let origin_url = git_in(repo_root, &["remote", "get-url", "origin"])
+        .await
+        .map_err(|_| {
+            MobiusError::Config("execution.checkout.mirror needs an 'origin' remote".to_string())
+        })?;
+
+    if mirror_path.exists() {
+        // An outdated mirror only means agents fetch a little more later
+        if let Err(e) = git_in(mirror_path, &["fetch", "--prune", "--quiet", "origin"]).await {
+            tracing::warn!("Failed to update mirror {}: {}", mirror_path.display(), e);
+        }
+        return Ok(origin_url);
+    }
+
+    let parent = mirror_path
+        .parent()
+        .context("mirror path has no parent directory")?;
+    tokio::fs::create_dir_all(parent)
+        .await
+        .with_context(|| format!("failed to create {}", parent.display()))?;
+    // Clone beside the final path so a concurrent loop never sees a half-made mirror
+    let staging = parent.join(format!("{}.{}.tmp", MIRROR_DIR_NAME, std::process::id()));
+    let result = async {
+        git_in(
+            repo_root,
+            &[
+                "clone",
+                "--mirror",
+                "--quiet",
+                "--reference",
+                &repo_root.to_string_lossy(),
+                "--dissociate",
+                &origin_url,
+                &staging.to_string_lossy(),
+            ],
+        )
+        .await?;
+        // Partial clones ask the mirror for blobs as they need them
+        git_in(&staging, &["config", "uploadpack.allowFilter", "true"]).await?;
+        if tokio::fs::rename(&staging, mirror_path).await.is_err() && !mirror_path.exists() {
+            anyhow::bail!("failed to move mirror into {}", mirror_path.display());
+        }
+        Ok(())
+    }
+    .await;
+    let _ = tokio::fs::remove_dir_all(&staging).await;
+    result.map(|()| origin_url)
+}
+
+/// `git clone` arguments for a worktree cloned from the mirror.
+fn mirror_clone_args(mirror_path: &Path, worktree_path: &Path, partial: bool) -> Vec<String> {
+    let mut args: Vec<String> = ["clone", "--quiet", "--no-checkout"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    if partial {
+        // Local clones ignore --filter unless the source is a file:// URL
+        args.push("--filter=blob:none".to_string());
+        args.push(format!("file://{}", mirror_path.display()));
+    } else {
+        // A plain path hardlinks the mirror's object files
+        args.push(mirror_path.display().to_string());
+    }
+    args.push(worktree_path.display().to_string());
+    args
+}
+
+/// Clone `branch_name` into `worktree_path` from the mirror.
+///
+/// `origin` fetches from the mirror and pushes to the real remote. A branch
+/// that exists in the main checkout starts from its local tip, which may not
+/// have been pushed yet; otherwise it starts from `origin`.
+async fn clone_worktree(
+    worktree_path: &Path,
+    branch_name: &str,
+    base_branch: Option<&str>,
+    config: &WorktreeConfig,
+) -> Result<()> {
+    let repo_root = get_git_repo_root().await?;
+    let mirror_path = get_mirror_path(config).await?;
+    let origin_url = ensure_mirror(&repo_root, &mirror_path).await?;
+
+    let args = mirror_clone_args(&mirror_path, worktree_path, config.checkout.partial_clone);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    git_in(&repo_root, &args).await?;
+    git_in(
+        worktree_path,
+        &["remote", "set-url", "--push", "origin", &origin_url],
+    )
+    .await?;
+
+    let start_name = base_branch.unwrap_or(branch_name);
+    let start = if branch_exists(start_name).await?.local {
+        git_in(
+            worktree_path,
+            &[
+                "fetch",
+                "--quiet",
+                &repo_root.to_string_lossy(),
+                &format!("refs/heads/{}", start_name),
+            ],
+        )
+        .await?;
+        "FETCH_HEAD".to_string()
+    } else {
+        format!("origin/{}", start_name)
+    };
+    // Only an existing remote branch is the new branch's upstream
+    let track = if base_branch.is_none() && start != "FETCH_HEAD" {
+        "--track"
+    } else {
+        "--no-track"
+    };
+    git_in(
+        worktree_path,
+        &["checkout", "--quiet", track, "-b", branch_name, &start],
+    )
+    .await?;
+    Ok(())
+}
+
+/// Copy the `caches` directories from `source_repo` into a new worktree.
+///
+/// Copies are copy-on-write (`cp --reflink` on Linux, `cp -c` on macOS), so
+/// they are near-instant and share disk blocks until a build rewrites them.
+/// On a filesystem without reflinks the cache is skipped rather than copied
+/// the slow way. Timestamps are kept so build tools still see which outputs
+/// are older than their sources.
+pub async fn reflink_caches(source_repo: &Path, worktree_path: &Path, caches: &[String]) {
+    for cache in caches {
+        let source = source_repo.join(cache);
+        let target = worktree_path.join(cache);
+        if !source.is_dir() || target.exists() {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            let _ = tokio::fs::create_dir_all(parent).await;
+        }
+
+        let mut command = Command::new("cp");
+        if cfg!(target_os = "macos") {
+            command.args(["-c", "-R", "-p"]);
+        } else {
+            command.args(["-R", "-p", "--reflink=always"]);
+        }
+        let copied = command.arg(&source).arg(&target).output().await;
+        match copied {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => {
+                let _ = tokio::fs::remove_dir_all(&target).await;
+                tracing::warn!(
+                    "Skipped copying {} into the worktree: {}",
+                    cache,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+            Err(e) => tracing::warn!("Skipped copying {} into the worktree: {}", cache, e),
+        }
+    }
+}
+
 /// Remove a worktree for the given task.
 pub async fn remove_worktree(task_id: &str, config: &WorktreeConfig) -> Result<()> {
     let worktree_path = get_worktree_path(task_id, config).await?;
@@ -467,6 +707,16 @@ pub async fn remove_worktree(task_id: &str, config: &WorktreeConfig) -> Result<(
         return Ok(()); // Already removed or never existed
     }
 
+    // Clones from the mirror are standalone repositories, so their commits
+    // exist nowhere else until the branch is saved
+    if worktree_path.join(".git").is_dir() {
+        let repo_root = get_git_repo_root().await?;
+        save_clone_branch(&repo_root, &worktree_path).await?;
+        return tokio::fs::remove_dir_all(&worktree_path)
+            .await
+            .context("failed to remove worktree clone");
+    }
+
     let output = Command::new("git")
         .args([
             "worktree",
@@ -480,12 +730,45 @@ pub async fn remove_worktree(task_id: &str, config: &WorktreeConfig) -> Result<(
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("git worktree remove failed: {}", stderr.trim());
+        return Err(
+            MobiusError::Git(format!("git worktree remove failed: {}", stderr.trim())).into(),
+        );
     }
 
     Ok(())
 }
 
+/// Fetch a clone's branch into the main checkout so removing the clone loses
+/// no commits.
+///
+/// When the branch can't be fetched (e.g. it is checked out there or has
+/// diverged), the clone's HEAD must already be on a remote; otherwise this
+/// fails and the clone is kept.
+async fn save_clone_branch(repo_root: &Path, clone: &Path) -> Result<()> {
+    let head = git_in(clone, &["rev-parse", "HEAD"]).await?;
+    if let Ok(branch) = git_in(clone, &["symbolic-ref", "--short", "HEAD"]).await {
+        let refspec = format!("{}:{}", branch, branch);
+        let source = clone.to_string_lossy();
+        if git_in(repo_root, &["fetch", "--quiet", &source, &refspec])
+            .await
+            .is_ok()
+        {
+            return Ok(());
+        }
+    }
+    let pushed = git_in(clone, &["branch", "-r", "--contains", &head])
+        .await
+        .unwrap_or_default();
+    if pushed.is_empty() {
+        return Err(MobiusError::Git(format!(
+            "{} has commits that are neither in the main checkout nor on a remote; push or fetch them, then remove it",
+            clone.display()
+        ))
+        .into());
+    }
+    Ok(())
+}
+
 /// List all existing worktrees.
 pub async fn list_worktrees() -> Result<Vec<WorktreeEntry>> {
     let output = Command::new("git")
@@ -496,7 +779,9 @@ pub async fn list_worktrees() -> Result<Vec<WorktreeEntry>> {
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("git worktree list failed: {}", stderr.trim());
+        return Err(
+            MobiusError::Git(format!("git worktree list failed: {}", stderr.trim())).into(),
+        );
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
@@ -560,7 +845,9 @@ pub async fn prune_worktrees() -> Result<()> {
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("git worktree prune failed: {}", stderr.trim());
+        return Err(
+            MobiusError::Git(format!("git worktree prune failed: {}", stderr.trim())).into(),
+        );
     }
 
     Ok(())
@@ -623,6 +910,7 @@ mod tests {
             worktree_path: None,
             base_branch: None,
             runtime: AgentRuntime::Claude,
+            checkout: CheckoutConfig::default(),
         };
         let path = get_worktree_path("MOB-123", &config).await;
         assert!(path.is_ok());
@@ -637,6 +925,7 @@ mod tests {
             worktree_path: Some("../custom-<repo>-trees/".to_string()),
             base_branch: None,
             runtime: AgentRuntime::Claude,
+            checkout: CheckoutConfig::default(),
         };
         let path = get_worktree_path("MOB-456", &config).await;
         assert!(path.is_ok());
@@ -650,6 +939,7 @@ mod tests {
             worktree_path: Some("/tmp/nonexistent-worktrees/".to_string()),
             base_branch: None,
             runtime: AgentRuntime::Claude,
+            checkout: CheckoutConfig::default(),
         };
         let exists = worktree_exists("nonexistent-task-xyz", &config).await;
         assert!(exists.is_ok());
@@ -675,6 +965,7 @@ mod tests {
             worktree_path: Some(temp_dir.parent().unwrap().to_string_lossy().to_string()),
             base_branch: None,
             runtime: AgentRuntime::Claude,
+            checkout: CheckoutConfig::default(),
         };
 
         // The task_id matches the temp directory name
@@ -737,4 +1028,81 @@ mod tests {
             source_repo.join(".opencode")
         );
     }
+
+    #[test]
+    fn test_mirror_clone_args() {
+        let mirror = Path::new("/trees/.mirror.git");
+        let worktree = Path::new("/trees/MOB-1");
+        assert_eq!(
+            mirror_clone_args(mirror, worktree, false),
+            vec![
+                "clone",
+                "--quiet",
+                "--no-checkout",
+                "/trees/.mirror.git",
+                "/trees/MOB-1"
+            ]
+        );
+        let partial = mirror_clone_args(mirror, worktree, true);
+        assert!(partial.contains(&"--filter=blob:none".to_string()));
+        assert!(partial.contains(&"file:///trees/.mirror.git".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_reflink_caches_skips_missing_and_existing() {
+        let tmp = tempfile::tempdir().unwrap();
+        let source_repo = tmp.path().join("source");
+        let worktree = tmp.path().join("worktree");
+        std::fs::create_dir_all(source_repo.join("target")).unwrap();
+        std::fs::write(source_repo.join("target/stale"), "old").unwrap();
+        std::fs::create_dir_all(worktree.join("target")).unwrap();
+
+        let caches = vec!["target".to_string(), "node_modules".to_string()];
+        reflink_caches(&source_repo, &worktree, &caches).await;
+
+        // An existing cache is left alone and a missing source is not created
+        assert!(!worktree.join("target/stale").exists());
+        assert!(!worktree.join("node_modules").exists());
+    }
+
+    #[tokio::test]
+    async fn test_save_clone_branch_fetches_unpushed_commits() {
+        let tmp = tempfile::tempdir().unwrap();
+        let main = tmp.path().join("main");
+        let clone = tmp.path().join("clone");
+        let git = |dir: &Path, args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .current_dir(dir)
+                .args(args)
+                .env("GIT_AUTHOR_NAME", "Dev")
+                .env("GIT_AUTHOR_EMAIL", "dev@example.com")
+                .env("GIT_COMMITTER_NAME", "Dev")
+                .env("GIT_COMMITTER_EMAIL", "dev@example.com")
+                .status()
+                .unwrap();
+            assert!(status.success(), "git {:?} failed", args);
+        };
+        std::fs::create_dir(&main).unwrap();
+        git(&main, &["init", "-q", "-b", "main"]);
+        git(&main, &["commit", "-q", "--allow-empty", "-m", "base"]);
+        git(
+            tmp.path(),
+            &["clone", "-q", &main.to_string_lossy(), "clone"],
+        );
+        git(&clone, &["checkout", "-q", "-b", "feat/mob-1"]);
+        git(
+            &clone,
+            &["commit", "-q", "--allow-empty", "-m", "agent work"],
+        );
+
+        save_clone_branch(&main, &clone).await.unwrap();
+        let saved = git_in(&main, &["rev-parse", "feat/mob-1"]).await.unwrap();
+        assert_eq!(saved, git_in(&clone, &["rev-parse", "HEAD"]).await.unwrap());
+
+        // Diverged in the main checkout and never pushed: keep the clone
+        git(&main, &["checkout", "-q", "feat/mob-1"]);
+        git(&main, &["reset", "-q", "--hard", "main"]);
+        git(&main, &["commit", "-q", "--allow-empty", "-m", "other"]);
+        assert!(save_clone_branch(&main, &clone).await.is_err());
+    }
 }